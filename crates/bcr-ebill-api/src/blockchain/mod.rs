@@ -3,5 +3,7 @@ pub use bcr_ebill_core::blockchain::company;
 pub use bcr_ebill_core::blockchain::identity;
 
 pub use bcr_ebill_core::blockchain::Block;
+pub use bcr_ebill_core::blockchain::BlockValidationError;
 pub use bcr_ebill_core::blockchain::Blockchain;
+pub use bcr_ebill_core::blockchain::ChainVerificationReport;
 pub use bcr_ebill_core::blockchain::Error;
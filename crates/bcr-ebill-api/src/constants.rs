@@ -5,3 +5,74 @@ pub const VALID_FILE_MIME_TYPES: [&str; 3] = ["image/jpeg", "image/png", "applic
 
 // When subscribing events we subtract this from the last received event time
 pub const NOSTR_EVENT_TIME_SLACK: u64 = 3600; // 1 hour
+
+// The maximum time we wait for relays to return the profile of a contact
+pub const NOSTR_PROFILE_FETCH_TIMEOUT_SECONDS: u64 = 10;
+
+// The maximum time we wait for relays to return the events sent to us, e.g. to repair a chain
+pub const NOSTR_EVENTS_FETCH_TIMEOUT_SECONDS: u64 = 30;
+
+// Events are published with these Nostr event kinds, depending on their event type - configured
+// kinds have to be the legacy encrypted direct message kind, which is still subscribed to and
+// accepted for compatibility with older nodes, or regular events in the application range, so
+// relays store them
+pub const NOSTR_LEGACY_EVENT_KIND: u16 = 4;
+pub const DEFAULT_NOSTR_BILL_EVENT_KIND: u16 = NOSTR_LEGACY_EVENT_KIND;
+pub const DEFAULT_NOSTR_COMPANY_EVENT_KIND: u16 = NOSTR_LEGACY_EVENT_KIND;
+pub const DEFAULT_NOSTR_IDENTITY_EVENT_KIND: u16 = NOSTR_LEGACY_EVENT_KIND;
+pub const NOSTR_APPLICATION_EVENT_KINDS: std::ops::RangeInclusive<u16> = 1000..=9999;
+
+// Number of bill chains loaded concurrently when listing bills
+pub const MAX_CONCURRENT_BILL_CHAIN_LOADS: usize = 16;
+
+// Limits for handling inbound Nostr events
+pub const DEFAULT_NOSTR_MAX_CONCURRENT_EVENTS: usize = 10;
+pub const DEFAULT_NOSTR_EVENT_TIMEOUT_SECONDS: u64 = 30;
+
+// The upper bound for the exponential backoff between retries of failed Nostr messages
+pub const DEFAULT_NOSTR_MAX_RETRY_DELAY_SECONDS: u64 = 3600; // 1 hour
+// The number of retries of a failed Nostr message, before it's moved to the dead-letter state
+pub const DEFAULT_NOSTR_MAX_RETRIES: i32 = 10;
+
+// The coingecko-compatible provider used to fetch BTC/fiat rates
+pub const DEFAULT_FIAT_RATE_PROVIDER_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
+
+// The mempool.space-compatible endpoint used to fetch recommended miner fees
+pub const DEFAULT_FEE_ESTIMATOR_URL: &str = "https://mempool.space/api/v1/fees/recommended";
+
+// Requests to the block explorer fail after these timeouts, and are retried this many times
+pub const DEFAULT_BITCOIN_EXPLORER_CONNECT_TIMEOUT_SECONDS: u64 = 10;
+pub const DEFAULT_BITCOIN_EXPLORER_READ_TIMEOUT_SECONDS: u64 = 30;
+pub const DEFAULT_BITCOIN_EXPLORER_MAX_RETRIES: u32 = 2;
+
+// The mempool explorers links point to, if no explorer is configured
+pub const MEMPOOL_MAINNET_BASE_URL: &str = "https://mempool.space";
+pub const MEMPOOL_TESTNET_BASE_URL: &str = "https://mempool.space/testnet";
+
+// Temp uploads, which weren't used for this long are removed by the cleanup job
+pub const DEFAULT_TEMP_UPLOAD_TTL_SECONDS: u64 = 86400; // 1 day
+
+// Holders are reminded of bills, which mature within this lead time
+pub const DEFAULT_MATURITY_REMINDER_LEAD_TIME_SECONDS: u64 = 259200; // 3 days
+
+// A bill payment is only considered final once it has this many confirmations
+pub const DEFAULT_PAYMENT_CONFIRMATIONS_REQUIRED: u64 = 1;
+
+// The expected actor of an open request is warned this long before the request times out
+pub const DEFAULT_REQUEST_TIMEOUT_WARNING_SECONDS: u64 = 43200; // 12 hours
+
+// By default, the jobs of a job runner tick start at once, without a limit of concurrent jobs,
+// or of processed bills
+pub const DEFAULT_JOB_RUNNER_STAGGER_SECONDS: u64 = 0;
+pub const DEFAULT_JOB_RUNNER_MAX_CONCURRENT_JOBS: usize = 0;
+pub const DEFAULT_JOB_RUNNER_BILLS_PER_TICK: usize = 0;
+
+// At most this many bills are cached, the least recently used ones are evicted
+pub const DEFAULT_BILL_CACHE_MAX_SIZE: usize = 1000;
+
+// Bill chains with more blocks are rejected
+pub use bcr_ebill_core::constants::DEFAULT_MAX_BILL_CHAIN_BLOCKS;
+
+// A bill can have at most this many files, with at most this many bytes in total
+pub const DEFAULT_MAX_FILES_PER_BILL: usize = 100;
+pub const DEFAULT_MAX_TOTAL_ATTACHMENT_BYTES: usize = 100_000_000; // ~100 MB
@@ -1,11 +1,16 @@
-use crate::get_config;
+use crate::constants::{
+    DEFAULT_BITCOIN_EXPLORER_CONNECT_TIMEOUT_SECONDS, DEFAULT_BITCOIN_EXPLORER_MAX_RETRIES,
+    DEFAULT_BITCOIN_EXPLORER_READ_TIMEOUT_SECONDS,
+};
+use crate::{Config, get_config};
 use async_trait::async_trait;
-use bcr_ebill_core::{ServiceTraitBounds, util};
+use bcr_ebill_core::{ServiceTraitBounds, bill::PaymentTx, util};
 use bitcoin::{Network, secp256k1::Scalar};
-use log::debug;
-use serde::Deserialize;
-use std::str::FromStr;
+use log::{debug, warn};
+use serde::{Deserialize, de::DeserializeOwned};
+use std::{str::FromStr, sync::Arc, time::Duration};
 use thiserror::Error;
+use tokio_with_wasm::alias as tokio;
 
 /// Generic result type
 pub type Result<T> = std::result::Result<T, super::Error>;
@@ -17,6 +22,14 @@ pub enum Error {
     #[error("External Bitcoin Web API error: {0}")]
     Api(#[from] reqwest::Error),
 
+    /// a request to the web api didn't finish within the configured timeout
+    #[error("External Bitcoin Web API request to {0} timed out")]
+    Timeout(String),
+
+    /// the web api responded with a body, which couldn't be parsed
+    #[error("External Bitcoin Web API response error: {0}")]
+    Json(#[from] serde_json::Error),
+
     /// all errors originating from dealing with secp256k1 keys
     #[error("External Bitcoin Key error: {0}")]
     Key(#[from] bitcoin::secp256k1::Error),
@@ -39,10 +52,8 @@ use mockall::automock;
 pub trait BitcoinClientApi: ServiceTraitBounds {
     async fn get_address_info(&self, address: &str) -> Result<AddressInfo>;
 
-    #[allow(dead_code)]
     async fn get_transactions(&self, address: &str) -> Result<Transactions>;
 
-    #[allow(dead_code)]
     async fn get_last_block_height(&self) -> Result<u64>;
 
     #[allow(dead_code)]
@@ -50,6 +61,18 @@ pub trait BitcoinClientApi: ServiceTraitBounds {
 
     async fn check_if_paid(&self, address: &str, sum: u64) -> Result<(bool, u64)>;
 
+    /// Returns the sum of all confirmed outputs received on the given address, not counting
+    /// transactions that are still in the mempool
+    async fn get_confirmed_paid_sum(&self, address: &str) -> Result<u64>;
+
+    /// Returns the number of confirmations of the least confirmed transaction on the given
+    /// address, or 0 if there is no confirmed transaction
+    async fn get_payment_confirmations(&self, address: &str) -> Result<u64>;
+
+    /// Returns the transactions, which sent funds to the given address, including the ones that
+    /// are still in the mempool
+    async fn get_payment_transactions(&self, address: &str) -> Result<Vec<PaymentTx>>;
+
     fn get_address_to_pay(&self, bill_public_key: &str, holder_public_key: &str) -> Result<String>;
 
     fn generate_link_to_pay(&self, address: &str, sum: u64, message: &str) -> String;
@@ -61,10 +84,82 @@ pub trait BitcoinClientApi: ServiceTraitBounds {
     ) -> Result<String>;
 
     fn get_mempool_link_for_address(&self, address: &str) -> String;
+
+    /// Returns the currently recommended miner fee rates from the configured fee estimator
+    async fn get_recommended_fees(&self) -> Result<RecommendedFees>;
+}
+
+/// Fetches responses from the block explorer and the fee estimator
+#[cfg_attr(test, automock)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ExplorerHttpClientApi: ServiceTraitBounds {
+    /// Returns the body of the response to a GET request to the given url - fails, if the
+    /// request times out, or the response doesn't have a 2xx status code
+    async fn get(&self, url: &str) -> Result<String>;
+}
+
+#[derive(Clone)]
+pub struct ExplorerHttpClient {
+    client: reqwest::Client,
+}
+
+impl ServiceTraitBounds for ExplorerHttpClient {}
+
+#[cfg(test)]
+impl ServiceTraitBounds for MockExplorerHttpClientApi {}
+
+impl ExplorerHttpClient {
+    /// The timeouts aren't supported in WASM, where the browser decides when a request fails
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(connect_timeout: Duration, read_timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(connect_timeout)
+            .read_timeout(read_timeout)
+            .build()
+            .expect("could not create the HTTP client");
+        Self { client }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn new(_connect_timeout: Duration, _read_timeout: Duration) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl ExplorerHttpClientApi for ExplorerHttpClient {
+    async fn get(&self, url: &str) -> Result<String> {
+        let map_err = |e: reqwest::Error| {
+            if e.is_timeout() {
+                Error::Timeout(url.to_owned())
+            } else {
+                Error::Api(e)
+            }
+        };
+        let body = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(map_err)?
+            .text()
+            .await
+            .map_err(map_err)?;
+        Ok(body)
+    }
 }
 
 #[derive(Clone)]
-pub struct BitcoinClient;
+pub struct BitcoinClient {
+    http_client: Arc<dyn ExplorerHttpClientApi>,
+    max_retries: u32,
+    retry_base_delay_millis: u64,
+}
 
 impl ServiceTraitBounds for BitcoinClient {}
 
@@ -72,8 +167,58 @@ impl ServiceTraitBounds for BitcoinClient {}
 impl ServiceTraitBounds for MockBitcoinClientApi {}
 
 impl BitcoinClient {
+    // the delay before the first retry, which is doubled with every failed retry
+    const RETRY_BASE_DELAY_MILLIS: u64 = 1000;
+
+    /// Creates a client with the default timeouts and retries - use `from_config` to apply the
+    /// configured ones
     pub fn new() -> Self {
-        Self {}
+        Self::with_settings(
+            DEFAULT_BITCOIN_EXPLORER_CONNECT_TIMEOUT_SECONDS,
+            DEFAULT_BITCOIN_EXPLORER_READ_TIMEOUT_SECONDS,
+            DEFAULT_BITCOIN_EXPLORER_MAX_RETRIES,
+        )
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::with_settings(
+            config.bitcoin_explorer_connect_timeout_seconds,
+            config.bitcoin_explorer_read_timeout_seconds,
+            config.bitcoin_explorer_max_retries,
+        )
+    }
+
+    fn with_settings(
+        connect_timeout_seconds: u64,
+        read_timeout_seconds: u64,
+        max_retries: u32,
+    ) -> Self {
+        Self {
+            http_client: Arc::new(ExplorerHttpClient::new(
+                Duration::from_secs(connect_timeout_seconds),
+                Duration::from_secs(read_timeout_seconds),
+            )),
+            max_retries,
+            retry_base_delay_millis: Self::RETRY_BASE_DELAY_MILLIS,
+        }
+    }
+
+    /// Fetches and parses the response of the given url, retrying failed requests with backoff,
+    /// so a single stalled request doesn't fail the caller
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let mut attempt = 0;
+        loop {
+            match self.http_client.get(url).await {
+                Ok(body) => return Ok(serde_json::from_str(&body).map_err(Error::from)?),
+                Err(e) if attempt < self.max_retries => {
+                    warn!("Request to {url} failed (attempt {}): {e}", attempt + 1);
+                    let delay = self.retry_base_delay_millis * 2u64.pow(attempt);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
     }
 
     pub fn request_url(&self, path: &str) -> String {
@@ -88,14 +233,7 @@ impl BitcoinClient {
     }
 
     pub fn link_url(&self, path: &str) -> String {
-        match get_config().bitcoin_network() {
-            Network::Bitcoin => {
-                format!("https://blockstream.info{path}")
-            }
-            _ => {
-                format!("https://blockstream.info/testnet{path}")
-            }
-        }
+        format!("{}{path}", get_config().mempool_link_base())
     }
 }
 
@@ -109,35 +247,17 @@ impl Default for BitcoinClient {
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl BitcoinClientApi for BitcoinClient {
     async fn get_address_info(&self, address: &str) -> Result<AddressInfo> {
-        let address: AddressInfo = reqwest::get(&self.request_url(&format!("/address/{address}")))
-            .await
-            .map_err(Error::from)?
-            .json()
+        self.get_json(&self.request_url(&format!("/address/{address}")))
             .await
-            .map_err(Error::from)?;
-
-        Ok(address)
     }
 
     async fn get_transactions(&self, address: &str) -> Result<Transactions> {
-        let transactions: Transactions =
-            reqwest::get(&self.request_url(&format!("/address/{address}/txs")))
-                .await
-                .map_err(Error::from)?
-                .json()
-                .await
-                .map_err(Error::from)?;
-
-        Ok(transactions)
+        self.get_json(&self.request_url(&format!("/address/{address}/txs")))
+            .await
     }
 
     async fn get_last_block_height(&self) -> Result<u64> {
-        let height: u64 = reqwest::get(&self.request_url("/blocks/tip/height"))
-            .await?
-            .json()
-            .await?;
-
-        Ok(height)
+        self.get_json(&self.request_url("/blocks/tip/height")).await
     }
 
     fn get_first_transaction(&self, transactions: &Transactions) -> Option<Txid> {
@@ -168,6 +288,45 @@ impl BitcoinClientApi for BitcoinClient {
         }
     }
 
+    async fn get_confirmed_paid_sum(&self, address: &str) -> Result<u64> {
+        debug!("checking confirmed paid sum of btc address {address}");
+        let info_about_address = self.get_address_info(address).await?;
+        Ok(info_about_address.chain_stats.funded_txo_sum)
+    }
+
+    async fn get_payment_confirmations(&self, address: &str) -> Result<u64> {
+        debug!("checking payment confirmations of btc address {address}");
+        let transactions = self.get_transactions(address).await?;
+        let confirmed_heights: Vec<u64> = transactions
+            .iter()
+            .filter_map(|tx| tx.status.block_height)
+            .collect();
+        if confirmed_heights.is_empty() {
+            return Ok(0);
+        }
+        let tip = self.get_last_block_height().await?;
+        Ok(confirmed_heights
+            .into_iter()
+            .map(|height| tip.saturating_sub(height) + 1)
+            .min()
+            .unwrap_or(0))
+    }
+
+    async fn get_payment_transactions(&self, address: &str) -> Result<Vec<PaymentTx>> {
+        debug!("fetching payment transactions of btc address {address}");
+        let transactions = self.get_transactions(address).await?;
+        // the tip is only needed to count confirmations
+        let tip = if transactions
+            .iter()
+            .any(|tx| tx.status.block_height.is_some())
+        {
+            self.get_last_block_height().await?
+        } else {
+            0
+        };
+        Ok(payment_transactions(&transactions, address, tip))
+    }
+
     fn get_address_to_pay(&self, bill_public_key: &str, holder_public_key: &str) -> Result<String> {
         let public_key_bill = bitcoin::PublicKey::from_str(bill_public_key)
             .map_err(|e| Error::PublicKey(e.to_string()))?;
@@ -204,6 +363,10 @@ impl BitcoinClientApi for BitcoinClient {
     fn get_mempool_link_for_address(&self, address: &str) -> String {
         self.link_url(&format!("/address/{address}"))
     }
+
+    async fn get_recommended_fees(&self) -> Result<RecommendedFees> {
+        self.get_json(&get_config().fee_estimator_url).await
+    }
 }
 
 /// Fields documented at https://github.com/Blockstream/esplora/blob/master/API.md#addresses
@@ -225,11 +388,171 @@ pub type Transactions = Vec<Txid>;
 #[derive(Deserialize, Debug, Clone)]
 #[allow(dead_code)]
 pub struct Txid {
+    pub txid: String,
+    pub vout: Vec<Vout>,
     pub status: Status,
 }
 
-#[allow(dead_code)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct Vout {
+    /// not set for outputs without an address, e.g. OP_RETURN
+    pub scriptpubkey_address: Option<String>,
+    pub value: u64,
+}
+
+/// Returns the transactions with outputs to the given address, along with the sum of these
+/// outputs and the confirmations at the given chain tip
+fn payment_transactions(transactions: &Transactions, address: &str, tip: u64) -> Vec<PaymentTx> {
+    transactions
+        .iter()
+        .filter_map(|tx| {
+            let amount: u64 = tx
+                .vout
+                .iter()
+                .filter(|out| out.scriptpubkey_address.as_deref() == Some(address))
+                .map(|out| out.value)
+                .sum();
+            if amount == 0 {
+                return None;
+            }
+            Some(PaymentTx {
+                txid: tx.txid.clone(),
+                amount,
+                confirmations: tx
+                    .status
+                    .block_height
+                    .map(|height| tip.saturating_sub(height) + 1)
+                    .unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct Status {
-    pub block_height: u64,
+    pub confirmed: bool,
+    /// not set for transactions, which are still in the mempool
+    pub block_height: Option<u64>,
+}
+
+/// Recommended fee rates in sat/vB, fields documented at
+/// https://mempool.space/docs/api/rest#get-recommended-fees
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendedFees {
+    /// expected to confirm within the next block (~10 minutes)
+    pub fastest_fee: u64,
+    /// expected to confirm within ~30 minutes
+    pub half_hour_fee: u64,
+    /// expected to confirm within ~1 hour
+    pub hour_fee: u64,
+    /// expected to confirm within ~1 day
+    pub economy_fee: u64,
+    pub minimum_fee: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::tests::init_test_cfg;
+    use mockall::Sequence;
+
+    fn client(http_client: MockExplorerHttpClientApi) -> BitcoinClient {
+        BitcoinClient {
+            http_client: Arc::new(http_client),
+            max_retries: 2,
+            retry_base_delay_millis: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_are_retried_after_a_timeout() {
+        init_test_cfg();
+        let mut http_client = MockExplorerHttpClientApi::new();
+        let mut seq = Sequence::new();
+        http_client
+            .expect_get()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|url| Err(Error::Timeout(url.to_owned()).into()));
+        http_client
+            .expect_get()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok("850000".to_string()));
+
+        let res = client(http_client).get_last_block_height().await;
+        assert_eq!(res.unwrap(), 850000);
+    }
+
+    #[tokio::test]
+    async fn requests_fail_after_max_retries() {
+        init_test_cfg();
+        let mut http_client = MockExplorerHttpClientApi::new();
+        http_client
+            .expect_get()
+            .times(3)
+            .returning(|url| Err(Error::Timeout(url.to_owned()).into()));
+
+        let res = client(http_client).get_last_block_height().await;
+        assert!(matches!(
+            res,
+            Err(crate::external::Error::ExternalBitcoinApi(Error::Timeout(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn mempool_link_for_address_uses_configured_network() {
+        init_test_cfg();
+        assert_eq!(
+            BitcoinClient::new().get_mempool_link_for_address("bc1qaddress"),
+            "https://mempool.space/address/bc1qaddress"
+        );
+    }
+
+    #[test]
+    fn payment_transactions_sums_outputs_to_address() {
+        let transactions: Transactions = serde_json::from_str(
+            r#"[
+                {
+                    "txid": "mempool_tx",
+                    "vout": [{ "scriptpubkey_address": "bc1qaddress", "value": 500 }],
+                    "status": { "confirmed": false }
+                },
+                {
+                    "txid": "split_tx",
+                    "vout": [
+                        { "scriptpubkey_address": "bc1qaddress", "value": 1000 },
+                        { "scriptpubkey_address": "bc1qchange", "value": 300 },
+                        { "scriptpubkey_address": "bc1qaddress", "value": 2000 }
+                    ],
+                    "status": { "confirmed": true, "block_height": 100 }
+                },
+                {
+                    "txid": "spending_tx",
+                    "vout": [{ "value": 0 }, { "scriptpubkey_address": "bc1qother", "value": 2500 }],
+                    "status": { "confirmed": true, "block_height": 105 }
+                }
+            ]"#,
+        )
+        .unwrap();
+        let res = payment_transactions(&transactions, "bc1qaddress", 105);
+        assert_eq!(
+            res,
+            vec![
+                PaymentTx {
+                    txid: "mempool_tx".to_string(),
+                    amount: 500,
+                    confirmations: 0,
+                },
+                PaymentTx {
+                    txid: "split_tx".to_string(),
+                    amount: 3000,
+                    confirmations: 6,
+                },
+            ]
+        );
+    }
 }
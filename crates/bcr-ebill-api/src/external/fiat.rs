@@ -0,0 +1,151 @@
+use super::rate::RateProviderApi;
+use async_trait::async_trait;
+use bcr_ebill_core::{ServiceTraitBounds, bill::FiatRate, util};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+/// How long a fetched BTC/fiat rate is reused before it's fetched from the provider again
+pub const FIAT_RATE_CACHE_SECONDS: u64 = 300; // 5 minutes
+
+/// Generic result type
+pub type Result<T> = std::result::Result<T, super::Error>;
+
+/// Generic error type
+#[derive(Debug, Error)]
+pub enum Error {
+    /// the provider didn't return a rate for the requested fiat currency
+    #[error("External Fiat Rate API returned no rate for {0}")]
+    RateNotFound(String),
+}
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait FiatRateClientApi: ServiceTraitBounds {
+    /// Returns the price of one BTC in the given fiat currency (e.g. `eur`)
+    async fn get_btc_rate(&self, fiat_code: &str) -> Result<FiatRate>;
+}
+
+/// Fetches BTC/fiat rates from the given rate provider, caching them for
+/// `FIAT_RATE_CACHE_SECONDS`
+#[derive(Clone)]
+pub struct FiatRateClient {
+    rate_provider: Arc<dyn RateProviderApi>,
+    cache: Arc<Mutex<HashMap<String, FiatRate>>>,
+}
+
+impl ServiceTraitBounds for FiatRateClient {}
+
+#[cfg(test)]
+impl ServiceTraitBounds for MockFiatRateClientApi {}
+
+impl FiatRateClient {
+    pub fn new(rate_provider: Arc<dyn RateProviderApi>) -> Self {
+        Self {
+            rate_provider,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn get_cached_rate(&self, fiat_code: &str, now: u64) -> Option<FiatRate> {
+        let cache = self.cache.lock().ok()?;
+        cache
+            .get(fiat_code)
+            .filter(|rate| now.saturating_sub(rate.timestamp) < FIAT_RATE_CACHE_SECONDS)
+            .cloned()
+    }
+
+    fn set_cached_rate(&self, rate: FiatRate) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.insert(rate.fiat_code.clone(), rate);
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl FiatRateClientApi for FiatRateClient {
+    async fn get_btc_rate(&self, fiat_code: &str) -> Result<FiatRate> {
+        let fiat_code = fiat_code.to_lowercase();
+        let now = util::date::now().timestamp() as u64;
+        if let Some(rate) = self.get_cached_rate(&fiat_code, now) {
+            return Ok(rate);
+        }
+
+        let rate = self.rate_provider.get_rate("btc", &fiat_code, None).await?;
+        let fiat_rate = FiatRate {
+            fiat_code,
+            rate: rate.rate,
+            timestamp: now,
+        };
+        self.set_cached_rate(fiat_rate.clone());
+        Ok(fiat_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::rate::{MockRateProviderApi, Rate};
+
+    fn rate(timestamp: u64) -> FiatRate {
+        FiatRate {
+            fiat_code: "eur".to_string(),
+            rate: 50000.0,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn cached_rate_is_used_within_ttl() {
+        let client = FiatRateClient::new(Arc::new(MockRateProviderApi::new()));
+        client.set_cached_rate(rate(1000));
+        assert!(client.get_cached_rate("eur", 1000).is_some());
+        assert!(
+            client
+                .get_cached_rate("eur", 1000 + FIAT_RATE_CACHE_SECONDS - 1)
+                .is_some()
+        );
+        assert!(client.get_cached_rate("usd", 1000).is_none());
+    }
+
+    #[test]
+    fn cached_rate_expires_after_ttl() {
+        let client = FiatRateClient::new(Arc::new(MockRateProviderApi::new()));
+        client.set_cached_rate(rate(1000));
+        assert!(
+            client
+                .get_cached_rate("eur", 1000 + FIAT_RATE_CACHE_SECONDS)
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn get_btc_rate_uses_rate_provider_and_caches_rate() {
+        let mut rate_provider = MockRateProviderApi::new();
+        rate_provider
+            .expect_get_rate()
+            .withf(|from, to, at| from == "btc" && to == "eur" && at.is_none())
+            .times(1)
+            .returning(|from, to, _| {
+                Ok(Rate {
+                    from: from.to_owned(),
+                    to: to.to_owned(),
+                    rate: 50000.0,
+                    timestamp: 1731593928,
+                })
+            });
+        let client = FiatRateClient::new(Arc::new(rate_provider));
+
+        let res = client.get_btc_rate("EUR").await.unwrap();
+        assert_eq!(res.fiat_code, "eur".to_string());
+        assert_eq!(res.rate, 50000.0);
+        // the second call is served from the cache
+        let res_cached = client.get_btc_rate("eur").await.unwrap();
+        assert_eq!(res_cached.rate, 50000.0);
+    }
+}
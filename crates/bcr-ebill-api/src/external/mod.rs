@@ -1,5 +1,8 @@
 pub mod bitcoin;
+pub mod fiat;
+pub mod rate;
 pub mod time;
+pub mod webhook;
 
 use thiserror::Error;
 
@@ -13,4 +16,16 @@ pub enum Error {
     /// all errors originating from the external bitcoin API
     #[error("External Bitcoin API error: {0}")]
     ExternalBitcoinApi(#[from] bitcoin::Error),
+
+    /// all errors originating from the external fiat rate API
+    #[error("External Fiat Rate API error: {0}")]
+    ExternalFiatApi(#[from] fiat::Error),
+
+    /// all errors originating from the external rate provider
+    #[error("External Rate Provider error: {0}")]
+    ExternalRateApi(#[from] rate::Error),
+
+    /// all errors originating from the external webhook API
+    #[error("External Webhook API error: {0}")]
+    ExternalWebhookApi(#[from] webhook::Error),
 }
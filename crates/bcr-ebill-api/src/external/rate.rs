@@ -0,0 +1,227 @@
+use crate::get_config;
+use async_trait::async_trait;
+use bcr_ebill_core::{ServiceTraitBounds, util};
+use log::debug;
+use std::collections::HashMap;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Generic result type
+pub type Result<T> = std::result::Result<T, super::Error>;
+
+/// Generic error type
+#[derive(Debug, Error)]
+pub enum Error {
+    /// all errors originating from interacting with the web api
+    #[error("External Rate Provider Web API error: {0}")]
+    Api(#[from] reqwest::Error),
+
+    /// a request to the web api didn't finish within the timeout
+    #[error("External Rate Provider Web API request timed out")]
+    Timeout,
+
+    /// the configured url of the provider is invalid
+    #[error("External Rate Provider url is invalid: {0}")]
+    InvalidUrl(String),
+
+    /// the provider didn't return a rate for the requested currency pair
+    #[error("External Rate Provider returned no rate for {0}/{1}")]
+    RateNotFound(String, String),
+
+    /// the provider doesn't know the given currency
+    #[error("External Rate Provider doesn't support the currency {0}")]
+    UnsupportedCurrency(String),
+
+    /// the provider can only return current rates
+    #[error("External Rate Provider doesn't support historical rates")]
+    HistoricalRateNotSupported,
+}
+
+/// The price of one unit of the `from` currency in the `to` currency at the given timestamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rate {
+    pub from: String,
+    pub to: String,
+    pub rate: f64,
+    pub timestamp: u64,
+}
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait RateProviderApi: ServiceTraitBounds {
+    /// Returns the price of one unit of the `from` currency in the `to` currency (e.g. `btc` in
+    /// `eur`) at the given timestamp, or the current one, if no timestamp is given
+    async fn get_rate(&self, from: &str, to: &str, at: Option<u64>) -> Result<Rate>;
+}
+
+/// Fetches current rates from a coingecko-compatible provider
+#[derive(Clone)]
+pub struct HttpRateProvider {
+    client: reqwest::Client,
+}
+
+impl ServiceTraitBounds for HttpRateProvider {}
+
+#[cfg(test)]
+impl ServiceTraitBounds for MockRateProviderApi {}
+
+impl Default for HttpRateProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpRateProvider {
+    // the time a request to the provider may take, before it's aborted
+    const REQUEST_TIMEOUT_SECONDS: u64 = 10;
+
+    /// The timeout isn't supported in WASM, where the browser decides when a request fails
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(Self::REQUEST_TIMEOUT_SECONDS))
+            .build()
+            .expect("could not create the HTTP client");
+        Self { client }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// The currencies are passed as query parameters, so they are escaped
+    pub fn request_url(&self, provider_id: &str, to: &str) -> Result<reqwest::Url> {
+        let url = reqwest::Url::parse_with_params(
+            &get_config().fiat_rate_provider_url,
+            &[("ids", provider_id), ("vs_currencies", to)],
+        )
+        .map_err(|e| Error::InvalidUrl(e.to_string()))?;
+        Ok(url)
+    }
+
+    /// The provider identifies crypto currencies by name, not by code
+    fn provider_id(currency: &str) -> Option<&'static str> {
+        match currency {
+            "btc" => Some("bitcoin"),
+            _ => None,
+        }
+    }
+
+    /// Currency codes consist of letters only, e.g. `eur`
+    fn is_valid_currency_code(currency: &str) -> bool {
+        !currency.is_empty() && currency.chars().all(|c| c.is_ascii_alphabetic())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl RateProviderApi for HttpRateProvider {
+    async fn get_rate(&self, from: &str, to: &str, at: Option<u64>) -> Result<Rate> {
+        if at.is_some() {
+            return Err(Error::HistoricalRateNotSupported.into());
+        }
+        let from = from.to_lowercase();
+        let to = to.to_lowercase();
+        let provider_id =
+            Self::provider_id(&from).ok_or(Error::UnsupportedCurrency(from.clone()))?;
+        if !Self::is_valid_currency_code(&to) {
+            return Err(Error::UnsupportedCurrency(to).into());
+        }
+
+        debug!("fetching {from}/{to} rate");
+        let map_err = |e: reqwest::Error| {
+            if e.is_timeout() {
+                Error::Timeout
+            } else {
+                Error::Api(e)
+            }
+        };
+        // the response has the format {"bitcoin": {"eur": 12345.67}}
+        let response: HashMap<String, HashMap<String, f64>> = self
+            .client
+            .get(self.request_url(provider_id, &to)?)
+            .send()
+            .await
+            .map_err(map_err)?
+            .json()
+            .await
+            .map_err(map_err)?;
+
+        let rate = response
+            .get(provider_id)
+            .and_then(|rates| rates.get(&to))
+            .ok_or(Error::RateNotFound(from.clone(), to.clone()))?;
+
+        Ok(Rate {
+            from,
+            to,
+            rate: *rate,
+            timestamp: util::date::now().timestamp() as u64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::tests::init_test_cfg;
+
+    #[tokio::test]
+    async fn get_rate_fails_for_historical_rate() {
+        let res = HttpRateProvider::new()
+            .get_rate("btc", "eur", Some(1731593928))
+            .await;
+        assert!(matches!(
+            res,
+            Err(super::super::Error::ExternalRateApi(
+                Error::HistoricalRateNotSupported
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_rate_fails_for_unsupported_currency() {
+        let res = HttpRateProvider::new().get_rate("xyz", "eur", None).await;
+        assert!(matches!(
+            res,
+            Err(super::super::Error::ExternalRateApi(
+                Error::UnsupportedCurrency(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_rate_fails_for_invalid_currency_code() {
+        for to in ["", "eur&ids=ethereum", "e u r", "../eur"] {
+            let res = HttpRateProvider::new().get_rate("btc", to, None).await;
+            assert!(matches!(
+                res,
+                Err(super::super::Error::ExternalRateApi(
+                    Error::UnsupportedCurrency(_)
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn request_url_escapes_currencies() {
+        init_test_cfg();
+        let url = HttpRateProvider::new()
+            .request_url("bitcoin", "eur&x=1")
+            .unwrap();
+        assert_eq!(
+            url.query_pairs()
+                .find(|(k, _)| k == "vs_currencies")
+                .unwrap()
+                .1,
+            "eur&x=1"
+        );
+    }
+}
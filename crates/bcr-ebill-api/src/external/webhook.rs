@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use bcr_ebill_core::ServiceTraitBounds;
+use thiserror::Error;
+
+/// The header containing the signature of the request body
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-EBill-Signature";
+/// The header containing the node id of the signer, to verify the signature with
+pub const WEBHOOK_SIGNER_HEADER: &str = "X-EBill-Signer";
+
+/// Generic result type
+pub type Result<T> = std::result::Result<T, super::Error>;
+
+/// Generic error type
+#[derive(Debug, Error)]
+pub enum Error {
+    /// all errors originating from interacting with the webhook endpoint
+    #[error("External Webhook API error: {0}")]
+    Api(#[from] reqwest::Error),
+
+    /// the webhook endpoint responded with a non-2xx status code
+    #[error("External Webhook API responded with status {0}")]
+    UnexpectedStatus(u16),
+}
+
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait WebhookClientApi: ServiceTraitBounds {
+    /// Posts the given json body to the given url, along with its signature and the node id of
+    /// the signer. Fails, if the endpoint doesn't respond with a 2xx status code
+    async fn post(&self, url: &str, body: &str, signature: &str, signer: &str) -> Result<()>;
+}
+
+#[derive(Clone)]
+pub struct WebhookClient {
+    client: reqwest::Client,
+}
+
+impl ServiceTraitBounds for WebhookClient {}
+
+#[cfg(test)]
+impl ServiceTraitBounds for MockWebhookClientApi {}
+
+impl WebhookClient {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl Default for WebhookClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl WebhookClientApi for WebhookClient {
+    async fn post(&self, url: &str, body: &str, signature: &str, signer: &str) -> Result<()> {
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_SIGNATURE_HEADER, signature)
+            .header(WEBHOOK_SIGNER_HEADER, signer)
+            .body(body.to_owned())
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::UnexpectedStatus(status.as_u16()).into());
+        }
+        Ok(())
+    }
+}
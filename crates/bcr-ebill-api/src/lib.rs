@@ -1,5 +1,12 @@
 use anyhow::{Result, anyhow};
+use bcr_ebill_core::{
+    bill::validation::validate_payment_deadline_seconds, constants::MAX_PAYMENT_DEADLINE_SECONDS,
+};
 use bitcoin::Network;
+use constants::{
+    MEMPOOL_MAINNET_BASE_URL, MEMPOOL_TESTNET_BASE_URL, NOSTR_APPLICATION_EVENT_KINDS,
+    NOSTR_LEGACY_EVENT_KIND,
+};
 use std::sync::OnceLock;
 
 mod blockchain;
@@ -8,24 +15,89 @@ pub mod data;
 pub mod external;
 mod persistence;
 pub mod service;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 #[cfg(test)]
 mod tests;
 pub mod util;
 
 pub use blockchain::Block;
+pub use blockchain::BlockValidationError;
 pub use blockchain::Blockchain;
+pub use blockchain::ChainVerificationReport;
+pub use blockchain::bill::BillOpCode;
+pub use blockchain::company::{SignatoryChange, SignatoryChangeType};
 pub use persistence::DbContext;
 pub use persistence::Error as PersistenceError;
 pub use persistence::db::SurrealDbConfig;
 pub use persistence::get_db_context;
-pub use persistence::notification::NotificationFilter;
+pub use persistence::notification::{NotificationCursor, NotificationFilter, NotificationPage};
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub bitcoin_network: String,
-    pub nostr_relay: String,
+    pub nostr_relays: Vec<String>,
     pub surreal_db_connection: String,
     pub data_dir: String,
+    pub nostr_max_concurrent_events: usize,
+    pub nostr_event_timeout_seconds: u64,
+    pub nostr_max_retry_delay_seconds: u64,
+    /// The number of retries of a failed outgoing Nostr message, before it's moved to the
+    /// dead-letter state
+    pub nostr_max_retries: i32,
+    /// The Nostr event kinds bill, company and identity events are published and subscribed
+    /// with - they have to be the legacy kind 4, or in the application range
+    pub nostr_bill_event_kind: u16,
+    pub nostr_company_event_kind: u16,
+    pub nostr_identity_event_kind: u16,
+    pub fiat_rate_provider_url: String,
+    pub fee_estimator_url: String,
+    /// Requests to the block explorer and the fee estimator fail, if connecting, or reading the
+    /// response takes longer than this - not supported in WASM
+    pub bitcoin_explorer_connect_timeout_seconds: u64,
+    pub bitcoin_explorer_read_timeout_seconds: u64,
+    /// Failed requests to the block explorer and the fee estimator are retried this many times,
+    /// with exponential backoff
+    pub bitcoin_explorer_max_retries: u32,
+    pub webhook_urls: Vec<String>,
+    pub temp_upload_ttl_seconds: u64,
+    /// Whether temp upload files are encrypted at rest - can be disabled, if the filesystem is
+    /// already encrypted
+    pub encrypt_temp_uploads: bool,
+    /// Attached files are sharded into subfolders by the prefix with this length of the bill id
+    /// hash, 0 meaning a flat layout - only applies to the file system storage
+    pub file_storage_shard_prefix_length: usize,
+    /// Bill chains with more blocks are rejected
+    pub max_bill_chain_blocks: usize,
+    /// A bill can have at most this many files - at issuing and attached later on
+    pub max_files_per_bill: usize,
+    /// The files of a bill can have at most this many bytes in total
+    pub max_total_attachment_bytes: usize,
+    /// The deadline for paying an offer to sell, which is recorded with the offer - the payment
+    /// deadline of the bill, if not set
+    pub sell_deadline_seconds: Option<u64>,
+    /// At most this many bills are cached, the least recently used ones are evicted, 0 meaning
+    /// unlimited
+    pub bill_cache_max_size: usize,
+    /// Holders are reminded of bills, which mature within this lead time
+    pub maturity_reminder_lead_time_seconds: u64,
+    /// A bill is only set to paid, sold or recoursed once the payment has at least this many
+    /// confirmations
+    pub payment_confirmations_required: u64,
+    /// The base URL of the mempool explorer links point to - derived from the bitcoin network,
+    /// if not set
+    pub mempool_base_url: Option<String>,
+    /// The expected actor of an open request is warned this long before the request times out,
+    /// 0 meaning no warning
+    pub request_timeout_warning_seconds: u64,
+    /// The delay between the starts of the jobs of a job runner tick
+    pub job_runner_stagger_seconds: u64,
+    /// The maximum number of jobs running at the same time, 0 meaning no limit
+    pub job_runner_max_concurrent_jobs: usize,
+    /// The maximum number of bills each bill check job processes per tick, 0 meaning no limit
+    pub job_runner_bills_per_tick: usize,
+    /// Whether bills are accepted automatically, if they match an auto-accept rule of the drawee
+    pub auto_accept_enabled: bool,
 }
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
@@ -39,9 +111,66 @@ impl Config {
             _ => Network::Testnet,
         }
     }
+
+    /// The base URL of the mempool explorer for the configured network, without a trailing slash.
+    /// There is no public explorer for regtest, so it's empty, unless it's configured.
+    pub fn mempool_link_base(&self) -> String {
+        if let Some(ref url) = self.mempool_base_url {
+            return url.trim_end_matches('/').to_owned();
+        }
+        match self.bitcoin_network() {
+            Network::Bitcoin => MEMPOOL_MAINNET_BASE_URL.to_owned(),
+            Network::Regtest => String::new(),
+            _ => MEMPOOL_TESTNET_BASE_URL.to_owned(),
+        }
+    }
+
+    /// The first configured relay, which is used where only a single relay can be set, e.g. as
+    /// the relay of a newly created identity
+    pub fn nostr_relay(&self) -> String {
+        self.nostr_relays.first().cloned().unwrap_or_default()
+    }
+
+    /// Checks, that the configured Nostr event kinds are the legacy kind, or in the application
+    /// range
+    fn validate_nostr_event_kinds(&self) -> Result<()> {
+        for (event_type, kind) in [
+            ("bill", self.nostr_bill_event_kind),
+            ("company", self.nostr_company_event_kind),
+            ("identity", self.nostr_identity_event_kind),
+        ] {
+            if kind != NOSTR_LEGACY_EVENT_KIND && !NOSTR_APPLICATION_EVENT_KINDS.contains(&kind) {
+                return Err(anyhow!(
+                    "Could not initialize E-Bill API: the Nostr {event_type} event kind {kind} is neither {NOSTR_LEGACY_EVENT_KIND}, nor in the range {}-{}",
+                    NOSTR_APPLICATION_EVENT_KINDS.start(),
+                    NOSTR_APPLICATION_EVENT_KINDS.end()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks, that the configured sell deadline is within the bounds of a payment deadline
+    fn validate_sell_deadline_seconds(&self) -> Result<()> {
+        if let Some(sell_deadline_seconds) = self.sell_deadline_seconds {
+            validate_payment_deadline_seconds(sell_deadline_seconds).map_err(|_| {
+                anyhow!(
+                    "Could not initialize E-Bill API: the sell deadline has to be between 1 and {MAX_PAYMENT_DEADLINE_SECONDS} seconds"
+                )
+            })?;
+        }
+        Ok(())
+    }
 }
 
 pub fn init(conf: Config) -> Result<()> {
+    if conf.nostr_relays.is_empty() {
+        return Err(anyhow!(
+            "Could not initialize E-Bill API: at least one Nostr relay is required"
+        ));
+    }
+    conf.validate_nostr_event_kinds()?;
+    conf.validate_sell_deadline_seconds()?;
     CONFIG
         .set(conf)
         .map_err(|e| anyhow!("Could not initialize E-Bill API: {e:?}"))?;
@@ -51,3 +180,71 @@ pub fn init(conf: Config) -> Result<()> {
 pub fn get_config() -> &'static Config {
     CONFIG.get().expect("E-Bill API is not initialized")
 }
+
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+    use crate::tests::tests::test_config;
+
+    fn config(network: &str, mempool_base_url: Option<&str>) -> Config {
+        Config {
+            bitcoin_network: network.to_owned(),
+            mempool_base_url: mempool_base_url.map(|u| u.to_owned()),
+            ..test_config()
+        }
+    }
+
+    #[test]
+    fn mempool_link_base_is_derived_from_network() {
+        assert_eq!(
+            config("mainnet", None).mempool_link_base(),
+            "https://mempool.space"
+        );
+        assert_eq!(
+            config("testnet", None).mempool_link_base(),
+            "https://mempool.space/testnet"
+        );
+        assert_eq!(config("regtest", None).mempool_link_base(), "");
+    }
+
+    #[test]
+    fn mempool_link_base_can_be_configured() {
+        for network in ["mainnet", "testnet", "regtest"] {
+            assert_eq!(
+                config(network, Some("http://localhost:8999/")).mempool_link_base(),
+                "http://localhost:8999"
+            );
+        }
+    }
+
+    #[test]
+    fn nostr_event_kinds_have_to_be_legacy_or_in_application_range() {
+        assert!(test_config().validate_nostr_event_kinds().is_ok());
+        for kind in [1, 5, 999, 10000, 30078] {
+            let conf = Config {
+                nostr_company_event_kind: kind,
+                ..test_config()
+            };
+            assert!(conf.validate_nostr_event_kinds().is_err());
+        }
+        let conf = Config {
+            nostr_bill_event_kind: 1000,
+            nostr_company_event_kind: 4,
+            nostr_identity_event_kind: 9999,
+            ..test_config()
+        };
+        assert!(conf.validate_nostr_event_kinds().is_ok());
+    }
+
+    #[test]
+    fn sell_deadline_has_to_be_in_bounds() {
+        assert!(test_config().validate_sell_deadline_seconds().is_ok());
+        for (deadline, valid) in [(0, false), (86400 * 30, true), (u64::MAX, false)] {
+            let conf = Config {
+                sell_deadline_seconds: Some(deadline),
+                ..test_config()
+            };
+            assert_eq!(conf.validate_sell_deadline_seconds().is_ok(), valid);
+        }
+    }
+}
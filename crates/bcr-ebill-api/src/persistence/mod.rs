@@ -1,13 +1,14 @@
 use crate::Config;
 use bcr_ebill_persistence::{
     BackupStoreApi, ContactStoreApi, NostrEventOffsetStoreApi, NotificationStoreApi,
-    SurrealBackupStore, SurrealBillChainStore, SurrealBillStore, SurrealCompanyChainStore,
-    SurrealCompanyStore, SurrealContactStore, SurrealDbConfig, SurrealIdentityChainStore,
-    SurrealIdentityStore, SurrealNostrEventOffsetStore, SurrealNotificationStore,
-    bill::{BillChainStoreApi, BillStoreApi},
+    SurrealBackupStore, SurrealBillAutoAcceptStore, SurrealBillChainStore, SurrealBillStore,
+    SurrealBillTemplateStore, SurrealCompanyChainStore, SurrealCompanyStore, SurrealContactStore,
+    SurrealDbConfig, SurrealIdentityChainStore, SurrealIdentityStore, SurrealNostrEventOffsetStore,
+    SurrealNotificationStore,
+    bill::{BillAutoAcceptStoreApi, BillChainStoreApi, BillStoreApi, BillTemplateStoreApi},
     company::{CompanyChainStoreApi, CompanyStoreApi},
     db::nostr_send_queue::SurrealNostrEventQueueStore,
-    file_upload::FileUploadStoreApi,
+    file_upload::{FileUploadStoreApi, TempUploadEncryption},
     get_surreal_db,
     identity::{IdentityChainStoreApi, IdentityStoreApi},
     nostr::NostrQueuedMessageStoreApi,
@@ -33,6 +34,8 @@ pub struct DbContext {
     pub contact_store: Arc<dyn ContactStoreApi>,
     pub bill_store: Arc<dyn BillStoreApi>,
     pub bill_blockchain_store: Arc<dyn BillChainStoreApi>,
+    pub bill_template_store: Arc<dyn BillTemplateStoreApi>,
+    pub bill_auto_accept_store: Arc<dyn BillAutoAcceptStoreApi>,
     pub identity_store: Arc<dyn IdentityStoreApi>,
     pub identity_chain_store: Arc<dyn IdentityChainStoreApi>,
     pub company_chain_store: Arc<dyn CompanyChainStoreApi>,
@@ -50,30 +53,51 @@ pub async fn get_db_context(conf: &Config) -> bcr_ebill_persistence::Result<DbCo
     let db = get_surreal_db(&surreal_db_config).await?;
 
     let company_store = Arc::new(SurrealCompanyStore::new(db.clone()));
+    let identity_store = Arc::new(SurrealIdentityStore::new(db.clone()));
+
     #[cfg(target_arch = "wasm32")]
-    let file_upload_store =
-        Arc::new(bcr_ebill_persistence::db::file_upload::FileUploadStore::new(db.clone()));
+    let mut file_upload_store =
+        bcr_ebill_persistence::db::file_upload::FileUploadStore::new(db.clone());
 
     #[cfg(not(target_arch = "wasm32"))]
-    let file_upload_store = Arc::new(
-        bcr_ebill_persistence::file_upload::FileUploadStore::new(
-            &conf.data_dir,
-            "files",
-            "temp_upload",
-        )
-        .await?,
-    );
+    let mut file_upload_store = bcr_ebill_persistence::file_upload::FileUploadStore::new(
+        &conf.data_dir,
+        "files",
+        "temp_upload",
+    )
+    .await?;
+
+    if conf.encrypt_temp_uploads {
+        let data_key = identity_store.get_or_create_data_key().await?;
+        file_upload_store = file_upload_store.with_encryption(TempUploadEncryption::new(data_key));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        file_upload_store = file_upload_store.with_sharding(conf.file_storage_shard_prefix_length);
+        // move attached files, which were stored with a different layout
+        if let Err(e) = file_upload_store.migrate_attached_files_layout().await {
+            error!("Error migrating attached files to the configured layout: {e}");
+        }
+    }
 
     if let Err(e) = file_upload_store.cleanup_temp_uploads().await {
         error!("Error cleaning up temp uploads: {e}");
     }
+    // encrypt plaintext temp uploads, which were written before encryption was enabled
+    if let Err(e) = file_upload_store.encrypt_plaintext_temp_uploads().await {
+        error!("Error encrypting plaintext temp uploads: {e}");
+    }
+    let file_upload_store = Arc::new(file_upload_store);
 
     let contact_store = Arc::new(SurrealContactStore::new(db.clone()));
 
-    let bill_store = Arc::new(SurrealBillStore::new(db.clone()));
+    let bill_store =
+        Arc::new(SurrealBillStore::new(db.clone()).with_max_cache_size(conf.bill_cache_max_size));
     let bill_blockchain_store = Arc::new(SurrealBillChainStore::new(db.clone()));
+    let bill_template_store = Arc::new(SurrealBillTemplateStore::new(db.clone()));
+    let bill_auto_accept_store = Arc::new(SurrealBillAutoAcceptStore::new(db.clone()));
 
-    let identity_store = Arc::new(SurrealIdentityStore::new(db.clone()));
     let identity_chain_store = Arc::new(SurrealIdentityChainStore::new(db.clone()));
     let company_chain_store = Arc::new(SurrealCompanyChainStore::new(db.clone()));
 
@@ -86,6 +110,8 @@ pub async fn get_db_context(conf: &Config) -> bcr_ebill_persistence::Result<DbCo
         contact_store,
         bill_store,
         bill_blockchain_store,
+        bill_template_store,
+        bill_auto_accept_store,
         identity_store,
         identity_chain_store,
         company_chain_store,
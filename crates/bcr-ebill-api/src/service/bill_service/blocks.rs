@@ -1,15 +1,17 @@
 use bcr_ebill_core::{
-    Validate,
+    Validate, ValidationError,
     bill::{BillKeys, BitcreditBill, RecourseReason},
     blockchain::{
         self, Blockchain,
         bill::{
-            BillBlock, BillBlockchain,
+            BillBlock, BillBlockchain, BillOpCode,
             block::{
-                BillAcceptBlockData, BillEndorseBlockData, BillMintBlockData,
+                BillAcceptBlockData, BillAcknowledgeBlockData, BillApproveMintQuoteBlockData,
+                BillAttachFileBlockData, BillCorrectDraweeBlockData, BillEndorseBlockData,
+                BillExternalPaymentBlockData, BillMintBlockData, BillMintQuoteBlockData,
                 BillOfferToSellBlockData, BillRecourseBlockData, BillRecourseReasonBlockData,
                 BillRejectBlockData, BillRequestRecourseBlockData, BillRequestToAcceptBlockData,
-                BillRequestToPayBlockData, BillSellBlockData,
+                BillRequestToPayBlockData, BillSellBlockData, BillSignatoryBlockData,
             },
         },
         company::{CompanyBlock, CompanySignCompanyBillBlockData},
@@ -24,6 +26,7 @@ use bcr_ebill_core::{
 };
 
 use super::{BillAction, Result, error::Error, service::BillService};
+use crate::get_config;
 
 impl BillService {
     pub(super) async fn create_blocks_for_bill_action(
@@ -35,11 +38,97 @@ impl BillService {
         signer_public_data: &IdentityPublicData,
         signer_keys: &BcrKeys,
         identity: &IdentityWithAll,
+        acting_signatory: Option<&BillSignatoryBlockData>,
         timestamp: u64,
     ) -> Result<()> {
         let bill_id = bill.id.clone();
-        let signing_keys = self.get_bill_signing_keys(signer_public_data, signer_keys, identity);
+        let bill_actions = match bill_action {
+            // a combined request to accept and pay results in a request to accept block, if the
+            // bill wasn't accepted yet, followed by a request to pay block
+            BillAction::RequestAcceptanceAndPayment(currency) => {
+                let mut actions = Vec::with_capacity(2);
+                if !blockchain.block_with_operation_code_exists(BillOpCode::Accept) {
+                    actions.push(BillAction::RequestAcceptance);
+                }
+                actions.push(BillAction::RequestToPay(currency.to_owned()));
+                actions
+            }
+            _ => vec![bill_action.clone()],
+        };
+
+        // create and validate all blocks on a copy of the chain first, so nothing gets persisted
+        // if one of them fails
+        let mut new_chain = blockchain
+            .clone()
+            .with_max_blocks(get_config().max_bill_chain_blocks);
+        let mut blocks = Vec::with_capacity(bill_actions.len());
+        for action in bill_actions.iter() {
+            let block = self.create_block_for_bill_action(
+                bill,
+                &new_chain,
+                bill_keys,
+                action,
+                signer_public_data,
+                signer_keys,
+                identity,
+                acting_signatory,
+                timestamp,
+            )?;
+            if !new_chain.try_add_block(block.clone()) || !new_chain.is_chain_valid() {
+                return Err(Error::Blockchain(blockchain::Error::BlockchainInvalid));
+            }
+            blocks.push(block);
+        }
+
+        // the blocks of one action are stored together - if one of them can't be stored, the ones
+        // stored already are removed again
+        for (i, block) in blocks.iter().enumerate() {
+            if let Err(e) = self.blockchain_store.add_block(&bill_id, block).await {
+                if i > 0 {
+                    self.blockchain_store
+                        .remove_blocks_from(&bill_id, blocks[0].id)
+                        .await?;
+                }
+                return Err(e.into());
+            }
+        }
+        for block in blocks.iter() {
+            self.add_identity_and_company_chain_blocks_for_signed_bill_action(
+                signer_public_data,
+                &bill_id,
+                block,
+                &identity.key_pair,
+                signer_keys,
+                timestamp,
+            )
+            .await?;
+        }
+        *blockchain = new_chain;
+
+        Ok(())
+    }
+
+    fn create_block_for_bill_action(
+        &self,
+        bill: &BitcreditBill,
+        blockchain: &BillBlockchain,
+        bill_keys: &BillKeys,
+        bill_action: &BillAction,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        identity: &IdentityWithAll,
+        acting_signatory: Option<&BillSignatoryBlockData>,
+        timestamp: u64,
+    ) -> Result<BillBlock> {
+        let bill_id = bill.id.clone();
         let previous_block = blockchain.get_latest_block();
+        let mut signing_keys =
+            self.get_bill_signing_keys(signer_public_data, signer_keys, identity);
+        // a company signs with the keys of the local identity, which is the only signatory the
+        // caller can choose to record
+        if let Some(signatory) = acting_signatory {
+            signing_keys.signatory_identity = Some(signatory.clone());
+        }
 
         let block = match bill_action {
             BillAction::Accept => {
@@ -78,7 +167,9 @@ impl BillService {
                     timestamp,
                 )?
             }
-            BillAction::RequestToPay(currency) => {
+            // the request to accept part of a combined request is created separately
+            BillAction::RequestToPay(currency)
+            | BillAction::RequestAcceptanceAndPayment(currency) => {
                 let block_data = BillRequestToPayBlockData {
                     requester: signer_public_data.clone().into(),
                     currency: currency.to_owned(),
@@ -189,6 +280,8 @@ impl BillService {
                     signatory: signing_keys.signatory_identity,
                     signing_timestamp: timestamp,
                     signing_address: signer_public_data.postal_address.clone(),
+                    // recorded with the offer, so every participant applies the same deadline
+                    sell_deadline_seconds: get_config().sell_deadline_seconds,
                 };
                 block_data.validate()?;
                 BillBlock::create_block_for_offer_to_sell(
@@ -242,12 +335,13 @@ impl BillService {
                     timestamp,
                 )?
             }
-            BillAction::RejectAcceptance => {
+            BillAction::RejectAcceptance(reason) => {
                 let block_data = BillRejectBlockData {
                     rejecter: signer_public_data.clone().into(),
                     signatory: signing_keys.signatory_identity,
                     signing_timestamp: timestamp,
                     signing_address: signer_public_data.postal_address.clone(),
+                    reason: reason.clone(),
                 };
                 block_data.validate()?;
                 BillBlock::create_block_for_reject_to_accept(
@@ -260,12 +354,13 @@ impl BillService {
                     timestamp,
                 )?
             }
-            BillAction::RejectBuying => {
+            BillAction::RejectBuying(reason) => {
                 let block_data = BillRejectBlockData {
                     rejecter: signer_public_data.clone().into(),
                     signatory: signing_keys.signatory_identity,
                     signing_timestamp: timestamp,
                     signing_address: signer_public_data.postal_address.clone(),
+                    reason: reason.clone(),
                 };
                 block_data.validate()?;
                 BillBlock::create_block_for_reject_to_buy(
@@ -278,12 +373,13 @@ impl BillService {
                     timestamp,
                 )?
             }
-            BillAction::RejectPayment => {
+            BillAction::RejectPayment(reason) => {
                 let block_data = BillRejectBlockData {
                     rejecter: signer_public_data.clone().into(),
                     signatory: signing_keys.signatory_identity,
                     signing_timestamp: timestamp,
                     signing_address: signer_public_data.postal_address.clone(),
+                    reason: reason.clone(),
                 };
                 block_data.validate()?;
                 BillBlock::create_block_for_reject_to_pay(
@@ -302,6 +398,7 @@ impl BillService {
                     signatory: signing_keys.signatory_identity,
                     signing_timestamp: timestamp,
                     signing_address: signer_public_data.postal_address.clone(),
+                    reason: None,
                 };
                 block_data.validate()?;
                 BillBlock::create_block_for_reject_to_pay_recourse(
@@ -314,37 +411,195 @@ impl BillService {
                     timestamp,
                 )?
             }
+            BillAction::CancelOfferToSell => {
+                let block_data = BillRejectBlockData {
+                    rejecter: signer_public_data.clone().into(),
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                    reason: None,
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_cancel_offer_to_sell(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::CancelRequestToAccept => {
+                let block_data = BillRejectBlockData {
+                    rejecter: signer_public_data.clone().into(),
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                    reason: None,
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_cancel_request_to_accept(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::AttachFile(files) => {
+                let block_data = BillAttachFileBlockData {
+                    attacher: signer_public_data.clone().into(),
+                    files: files.to_owned(),
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_attach_file(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::MarkPaidExternally(reference) => {
+                let block_data = BillExternalPaymentBlockData {
+                    payer: bill.drawee.clone().into(),
+                    holder: signer_public_data.clone().into(),
+                    reference: reference.to_owned(),
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_external_payment(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::Acknowledge => {
+                let block_data = BillAcknowledgeBlockData {
+                    acknowledger: signer_public_data.clone().into(),
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_acknowledge(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::CorrectDrawee(new_drawee) => {
+                let block_data = BillCorrectDraweeBlockData {
+                    drawer: signer_public_data.clone().into(),
+                    drawee: new_drawee.clone().into(),
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_correct_drawee(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::SubmitMintQuote(sum, currency, expiry_timestamp) => {
+                let mint_request = blockchain
+                    .get_mint_request_waiting_for_quote(bill_keys)?
+                    .ok_or(Error::Validation(
+                        ValidationError::BillWasNotRequestedToMint,
+                    ))?;
+                let block_data = BillMintQuoteBlockData {
+                    mint: signer_public_data.clone().into(),
+                    requester: mint_request.requester,
+                    mint_request_block_id: mint_request.block_id,
+                    currency: currency.to_owned(),
+                    sum: *sum,
+                    expiry_timestamp: *expiry_timestamp,
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_mint_quote(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::ApproveMintQuote => {
+                let (quote_block_id, quote) = blockchain
+                    .get_open_mint_quote(bill_keys)?
+                    .ok_or(Error::Validation(ValidationError::BillHasNoOpenMintQuote))?;
+                let block_data = BillApproveMintQuoteBlockData {
+                    approver: signer_public_data.clone().into(),
+                    mint: quote.mint,
+                    quote_block_id,
+                    currency: quote.currency,
+                    sum: quote.sum,
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_approve_mint_quote(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
+            BillAction::RejectMintQuote => {
+                let block_data = BillRejectBlockData {
+                    rejecter: signer_public_data.clone().into(),
+                    signatory: signing_keys.signatory_identity,
+                    signing_timestamp: timestamp,
+                    signing_address: signer_public_data.postal_address.clone(),
+                    reason: None,
+                };
+                block_data.validate()?;
+                BillBlock::create_block_for_reject_mint_quote(
+                    bill_id.to_owned(),
+                    previous_block,
+                    &block_data,
+                    &signing_keys.signatory_keys,
+                    signing_keys.company_keys.as_ref(),
+                    &BcrKeys::from_private_key(&bill_keys.private_key)?,
+                    timestamp,
+                )?
+            }
         };
 
-        self.validate_and_add_block(&bill_id, blockchain, block.clone())
-            .await?;
-
-        self.add_identity_and_company_chain_blocks_for_signed_bill_action(
-            signer_public_data,
-            &bill_id,
-            &block,
-            &identity.key_pair,
-            signer_keys,
-            timestamp,
-        )
-        .await?;
-
-        Ok(())
-    }
-
-    pub(super) async fn validate_and_add_block(
-        &self,
-        bill_id: &str,
-        blockchain: &mut BillBlockchain,
-        new_block: BillBlock,
-    ) -> Result<()> {
-        let try_add_block = blockchain.try_add_block(new_block.clone());
-        if try_add_block && blockchain.is_chain_valid() {
-            self.blockchain_store.add_block(bill_id, &new_block).await?;
-            Ok(())
-        } else {
-            Err(Error::Blockchain(blockchain::Error::BlockchainInvalid))
-        }
+        Ok(block)
     }
 
     pub(super) async fn add_identity_and_company_chain_blocks_for_signed_bill_action(
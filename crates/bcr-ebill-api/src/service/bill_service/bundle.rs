@@ -0,0 +1,113 @@
+use super::Result;
+use crate::util;
+use bcr_ebill_core::{ValidationError, blockchain::bill::BillBlock};
+use serde::{Deserialize, Serialize};
+
+/// The version of the bill bundle container format, stored as the first byte of a bundle
+pub const BILL_BUNDLE_VERSION: u8 = 1;
+
+/// A portable export of a bill - the bill keys and attached files are encrypted to the exporting
+/// identity, and the payload is signed with its private key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillBundle {
+    pub payload: BillBundlePayload,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillBundlePayload {
+    pub bill_id: String,
+    /// The node id of the exporting identity, which the keys and files are encrypted to
+    pub signer_node_id: String,
+    pub blocks: Vec<BillBlock>,
+    /// The base58 encoded, encrypted bill keys
+    pub encrypted_keys: String,
+    pub files: Vec<BillBundleFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillBundleFile {
+    pub name: String,
+    /// The base58 encoded file content, encrypted to the exporting identity
+    pub encrypted_content: String,
+}
+
+impl BillBundlePayload {
+    /// The hash of the payload, which is signed by the exporting identity
+    pub fn hash(&self) -> Result<String> {
+        let bytes = serde_json::to_vec(self).map_err(|_| ValidationError::InvalidBillBundle)?;
+        Ok(util::sha256_hash(&bytes))
+    }
+}
+
+impl BillBundle {
+    /// Serializes the bundle into the versioned container format
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = vec![BILL_BUNDLE_VERSION];
+        bytes.extend(serde_json::to_vec(self).map_err(|_| ValidationError::InvalidBillBundle)?);
+        Ok(bytes)
+    }
+
+    /// Deserializes a bundle from the versioned container format, failing for unknown versions
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.split_first() {
+            None => Err(ValidationError::InvalidBillBundle.into()),
+            Some((&BILL_BUNDLE_VERSION, rest)) => {
+                let bundle: BillBundle =
+                    serde_json::from_slice(rest).map_err(|_| ValidationError::InvalidBillBundle)?;
+                Ok(bundle)
+            }
+            Some((version, _)) => Err(ValidationError::UnknownBillBundleVersion(*version).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::bill_service::Error;
+
+    fn empty_bundle() -> BillBundle {
+        BillBundle {
+            payload: BillBundlePayload {
+                bill_id: "some_bill".to_string(),
+                signer_node_id: "some_node".to_string(),
+                blocks: vec![],
+                encrypted_keys: "keys".to_string(),
+                files: vec![],
+            },
+            signature: "sig".to_string(),
+        }
+    }
+
+    #[test]
+    fn bundle_roundtrip() {
+        let bytes = empty_bundle().to_bytes().unwrap();
+        assert_eq!(bytes[0], BILL_BUNDLE_VERSION);
+        let bundle = BillBundle::from_bytes(&bytes).unwrap();
+        assert_eq!(bundle.payload.bill_id, "some_bill".to_string());
+        assert_eq!(bundle.signature, "sig".to_string());
+    }
+
+    #[test]
+    fn bundle_with_unknown_version_fails() {
+        let mut bytes = empty_bundle().to_bytes().unwrap();
+        bytes[0] = BILL_BUNDLE_VERSION + 1;
+        assert!(matches!(
+            BillBundle::from_bytes(&bytes),
+            Err(Error::Validation(ValidationError::UnknownBillBundleVersion(v))) if v == BILL_BUNDLE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn empty_or_invalid_bundle_fails() {
+        assert!(matches!(
+            BillBundle::from_bytes(&[]),
+            Err(Error::Validation(ValidationError::InvalidBillBundle))
+        ));
+        assert!(matches!(
+            BillBundle::from_bytes(&[BILL_BUNDLE_VERSION, 1, 2, 3]),
+            Err(Error::Validation(ValidationError::InvalidBillBundle))
+        ));
+    }
+}
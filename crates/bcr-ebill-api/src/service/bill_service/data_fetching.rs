@@ -1,4 +1,6 @@
+use crate::external::bitcoin::{BitcoinClient, BitcoinClientApi};
 use crate::util;
+use crate::util::log_context::LogContext;
 
 use super::service::BillService;
 use super::{Error, Result};
@@ -6,17 +8,18 @@ use bcr_ebill_core::bill::validation::get_deadline_base_for_req_to_pay;
 use bcr_ebill_core::constants::RECOURSE_DEADLINE_SECONDS;
 use bcr_ebill_core::contact::Contact;
 use bcr_ebill_core::{
+    File,
     bill::{
         BillAcceptanceStatus, BillCurrentWaitingState, BillData, BillKeys, BillParticipants,
         BillPaymentStatus, BillRecourseStatus, BillSellStatus, BillStatus,
         BillWaitingForPaymentState, BillWaitingForRecourseState, BillWaitingForSellState,
-        BitcreditBill, BitcreditBillResult,
+        BitcreditBill, BitcreditBillResult, SettlementStatus,
     },
     blockchain::{
-        Blockchain,
+        self, Blockchain,
         bill::{
             BillBlockchain, BillOpCode, OfferToSellWaitingForPayment, RecourseWaitingForPayment,
-            block::BillSignatoryBlockData,
+            block::{BillOfferToSellBlockData, BillSignatoryBlockData},
         },
     },
     constants::{ACCEPT_DEADLINE_SECONDS, PAYMENT_DEADLINE_SECONDS},
@@ -42,56 +45,94 @@ impl BillService {
         identity: &Identity,
         contacts: &HashMap<String, Contact>,
     ) -> Result<BitcreditBill> {
-        let bill_first_version = chain.get_first_version_bill(bill_keys)?;
-        let bill_parties = chain.get_bill_parties(bill_keys, &bill_first_version)?;
-
-        let payee = bill_parties.payee;
-        let drawee_contact = self
+        let mut bill = last_version_bill_from_chain(chain, bill_keys)?;
+        bill.drawee = self
             .extend_bill_chain_identity_data_from_contacts_or_identity(
-                bill_parties.drawee,
+                bill.drawee.into(),
                 identity,
                 contacts,
             )
             .await;
-        let drawer_contact = self
+        bill.drawer = self
             .extend_bill_chain_identity_data_from_contacts_or_identity(
-                bill_parties.drawer,
+                bill.drawer.into(),
                 identity,
                 contacts,
             )
             .await;
-        let payee_contact = self
-            .extend_bill_chain_identity_data_from_contacts_or_identity(payee, identity, contacts)
+        bill.payee = self
+            .extend_bill_chain_identity_data_from_contacts_or_identity(
+                bill.payee.into(),
+                identity,
+                contacts,
+            )
             .await;
-        let endorsee_contact = match bill_parties.endorsee {
-            Some(endorsee) => {
-                let endorsee_contact = self
-                    .extend_bill_chain_identity_data_from_contacts_or_identity(
-                        endorsee, identity, contacts,
-                    )
-                    .await;
-                Some(endorsee_contact)
-            }
+        bill.endorsee = match bill.endorsee {
+            Some(endorsee) => Some(
+                self.extend_bill_chain_identity_data_from_contacts_or_identity(
+                    endorsee.into(),
+                    identity,
+                    contacts,
+                )
+                .await,
+            ),
             None => None,
         };
+        Ok(bill)
+    }
 
-        Ok(BitcreditBill {
-            id: bill_first_version.id,
-            country_of_issuing: bill_first_version.country_of_issuing,
-            city_of_issuing: bill_first_version.city_of_issuing,
-            drawee: drawee_contact,
-            drawer: drawer_contact,
-            payee: payee_contact,
-            endorsee: endorsee_contact,
-            currency: bill_first_version.currency,
-            sum: bill_first_version.sum,
-            maturity_date: bill_first_version.maturity_date,
-            issue_date: bill_first_version.issue_date,
-            country_of_payment: bill_first_version.country_of_payment,
-            city_of_payment: bill_first_version.city_of_payment,
-            language: bill_first_version.language,
-            files: bill_first_version.files,
-        })
+    /// Checks the locally stored attached files of the bill against the hashes recorded in the
+    /// chain and returns the names of the files that don't match. Files, which aren't available
+    /// locally are skipped, since they can't be checked. Files are only decrypted, if their
+    /// content hash isn't known from storing, or checking them before.
+    pub(super) async fn get_tampered_files(
+        &self,
+        bill_id: &str,
+        files: &[File],
+        bill_keys: &BillKeys,
+    ) -> Vec<String> {
+        let log_ctx = LogContext::bill(bill_id);
+        let mut tampered_files = vec![];
+        for file in files.iter() {
+            if let Some(content_hash) = self.known_file_hash(bill_id, &file.name) {
+                if content_hash.as_deref() != Some(file.hash.as_str()) {
+                    tampered_files.push(file.name.clone());
+                }
+                continue;
+            }
+            let encrypted = match self
+                .file_upload_store
+                .open_attached_file(bill_id, &file.name)
+                .await
+            {
+                Ok(encrypted) => encrypted,
+                Err(e) => {
+                    debug!(
+                        "{log_ctx} File {} is not available locally: {e}",
+                        &file.name
+                    );
+                    continue;
+                }
+            };
+            let content_hash = match util::crypto::decrypt_ecies(&encrypted, &bill_keys.private_key)
+            {
+                Ok(decrypted) => Some(util::sha256_hash(&decrypted)),
+                Err(e) => {
+                    error!("{log_ctx} Could not decrypt file {}: {e}", &file.name);
+                    None
+                }
+            };
+            let matches_hash = content_hash.as_deref() == Some(file.hash.as_str());
+            self.remember_file_hash(bill_id, &file.name, content_hash);
+            if !matches_hash {
+                error!(
+                    "{log_ctx} File {} doesn't match the hash recorded in the chain",
+                    &file.name
+                );
+                tampered_files.push(file.name.clone());
+            }
+        }
+        tampered_files
     }
 
     pub(super) fn get_bill_signing_keys(
@@ -126,363 +167,46 @@ impl BillService {
         // fetch contacts to get current contact data for participants
         let contacts = self.contact_store.get_map().await?;
 
-        let bill = self
-            .get_last_version_bill(chain, bill_keys, local_identity, &contacts)
-            .await?;
-        let first_version_bill = chain.get_first_version_bill(bill_keys)?;
-        let time_of_drawing = first_version_bill.signing_timestamp;
-
-        let bill_participants = chain.get_all_nodes_from_bill(bill_keys)?;
-        let endorsements_count = chain.get_endorsements_count();
-
-        let holder = match bill.endorsee {
-            None => &bill.payee,
-            Some(ref endorsee) => endorsee,
+        let bill_id = chain.get_first_block().bill_id.clone();
+        let mut local_state = LocalBillState {
+            current_identity_node_id: current_identity_node_id.to_owned(),
+            ..Default::default()
         };
-
-        let mut paid = false;
-        let mut requested_to_pay = false;
-        let mut rejected_to_pay = false;
-        let mut request_to_pay_timed_out = false;
-        let mut time_of_request_to_pay = None;
-        if let Some(req_to_pay_block) =
-            chain.get_last_version_block_with_op_code(BillOpCode::RequestToPay)
+        if chain
+            .get_last_version_block_with_op_code(BillOpCode::RequestToPay)
+            .is_some()
         {
-            requested_to_pay = true;
-            time_of_request_to_pay = Some(req_to_pay_block.timestamp);
-            paid = self.store.is_paid(&bill.id).await?;
-            rejected_to_pay = chain.block_with_operation_code_exists(BillOpCode::RejectToPay);
-            let deadline_base =
-                get_deadline_base_for_req_to_pay(req_to_pay_block.timestamp, &bill.maturity_date)?;
-            if !paid
-                && !rejected_to_pay
-                && util::date::check_if_deadline_has_passed(
-                    deadline_base,
-                    current_timestamp,
-                    PAYMENT_DEADLINE_SECONDS,
-                )
-            {
-                request_to_pay_timed_out = true;
-            }
-        }
-
-        // calculate, if the caller has received funds at any point in the bill
-        let mut redeemed_funds_available =
-            chain.is_beneficiary_from_a_block(bill_keys, current_identity_node_id);
-        if holder.node_id == current_identity_node_id && paid {
-            redeemed_funds_available = true;
+            local_state.paid = self.store.is_paid(&bill_id).await?;
+            local_state.paid_sum = self.store.get_paid_sum(&bill_id).await?;
         }
 
-        let has_requested_funds =
-            chain.is_beneficiary_from_a_request_funds_block(bill_keys, current_identity_node_id);
-
-        let mut offered_to_sell = false;
-        let mut rejected_offer_to_sell = false;
-        let mut offer_to_sell_timed_out = false;
-        let mut sold = false;
-        let mut time_of_last_offer_to_sell = None;
-        if let Some(last_offer_to_sell_block) =
-            chain.get_last_version_block_with_op_code(BillOpCode::OfferToSell)
-        {
-            time_of_last_offer_to_sell = Some(last_offer_to_sell_block.timestamp);
-            offered_to_sell = true;
-            if let Some(last_reject_offer_to_sell_block) =
-                chain.get_last_version_block_with_op_code(BillOpCode::RejectToBuy)
-            {
-                if last_reject_offer_to_sell_block.id > last_offer_to_sell_block.id {
-                    rejected_offer_to_sell = true;
-                }
-            }
-            if let Some(last_sell_block) =
-                chain.get_last_version_block_with_op_code(BillOpCode::Sell)
-            {
-                if last_sell_block.id > last_offer_to_sell_block.id {
-                    // last offer to sell was sold
-                    sold = true;
-                }
-            }
-            if !sold
-                && !rejected_offer_to_sell
-                && util::date::check_if_deadline_has_passed(
-                    last_offer_to_sell_block.timestamp,
-                    current_timestamp,
-                    PAYMENT_DEADLINE_SECONDS,
-                )
-            {
-                offer_to_sell_timed_out = true;
-            }
-        }
+        let mut bill = fold_bill_chain(
+            chain,
+            bill_keys,
+            current_timestamp,
+            &local_state,
+            self.bitcoin_client.as_ref(),
+        )?;
 
-        let mut requested_to_recourse = false;
-        let mut request_to_recourse_timed_out = false;
-        let mut time_of_last_request_to_recourse = None;
-        let mut rejected_request_to_recourse = false;
-        let mut recoursed = false;
-        if let Some(last_req_to_recourse_block) =
-            chain.get_last_version_block_with_op_code(BillOpCode::RequestRecourse)
-        {
-            requested_to_recourse = true;
-            time_of_last_request_to_recourse = Some(last_req_to_recourse_block.timestamp);
-            if let Some(last_reject_to_pay_recourse_block) =
-                chain.get_last_version_block_with_op_code(BillOpCode::RejectToPayRecourse)
-            {
-                if last_reject_to_pay_recourse_block.id > last_req_to_recourse_block.id {
-                    rejected_request_to_recourse = true;
-                }
-            }
-            if let Some(last_recourse_block) =
-                chain.get_last_version_block_with_op_code(BillOpCode::Recourse)
-            {
-                if last_recourse_block.id > last_req_to_recourse_block.id {
-                    recoursed = true
-                }
-            }
-            if !recoursed
-                && !rejected_request_to_recourse
-                && util::date::check_if_deadline_has_passed(
-                    last_req_to_recourse_block.timestamp,
-                    current_timestamp,
-                    RECOURSE_DEADLINE_SECONDS,
-                )
-            {
-                request_to_recourse_timed_out = true;
-            }
-        }
-
-        let mut request_to_accept_timed_out = false;
-        let rejected_to_accept = chain.block_with_operation_code_exists(BillOpCode::RejectToAccept);
-        let accepted = chain.block_with_operation_code_exists(BillOpCode::Accept);
-        let mut time_of_request_to_accept = None;
-        let mut requested_to_accept = false;
-        if let Some(req_to_accept_block) =
-            chain.get_last_version_block_with_op_code(BillOpCode::RequestToAccept)
-        {
-            requested_to_accept = true;
-            time_of_request_to_accept = Some(req_to_accept_block.timestamp);
+        self.extend_bill_identities_from_contacts_or_identity(&mut bill, local_identity, &contacts)
+            .await;
 
-            if !accepted
-                && !rejected_to_accept
-                && util::date::check_if_deadline_has_passed(
-                    req_to_accept_block.timestamp,
-                    current_timestamp,
-                    ACCEPT_DEADLINE_SECONDS,
-                )
-            {
-                request_to_accept_timed_out = true;
-            }
+        if let Some(deadline) = fee_estimate_deadline(&bill)? {
+            let fee_estimate = self.get_fee_estimate(deadline, current_timestamp).await;
+            match bill.current_waiting_state.as_mut() {
+                Some(BillCurrentWaitingState::Sell(state)) => state.fee_estimate = fee_estimate,
+                Some(BillCurrentWaitingState::Payment(state)) => state.fee_estimate = fee_estimate,
+                Some(BillCurrentWaitingState::Recourse(state)) => state.fee_estimate = fee_estimate,
+                None => (),
+            };
         }
 
-        let last_block = chain.get_latest_block();
-        let current_waiting_state = match last_block.op_code {
-            BillOpCode::OfferToSell => {
-                if let OfferToSellWaitingForPayment::Yes(payment_info) = chain
-                    .is_last_offer_to_sell_block_waiting_for_payment(bill_keys, current_timestamp)?
-                {
-                    // we're waiting, collect data
-                    let buyer = self
-                        .extend_bill_chain_identity_data_from_contacts_or_identity(
-                            payment_info.buyer.clone(),
-                            local_identity,
-                            &contacts,
-                        )
-                        .await;
-                    let seller = self
-                        .extend_bill_chain_identity_data_from_contacts_or_identity(
-                            payment_info.seller.clone(),
-                            local_identity,
-                            &contacts,
-                        )
-                        .await;
-
-                    let address_to_pay = payment_info.payment_address;
-
-                    let link_to_pay = self.bitcoin_client.generate_link_to_pay(
-                        &address_to_pay,
-                        payment_info.sum,
-                        &format!("Payment in relation to a bill {}", &bill.id),
-                    );
-
-                    let mempool_link_for_address_to_pay = self
-                        .bitcoin_client
-                        .get_mempool_link_for_address(&address_to_pay);
-
-                    Some(BillCurrentWaitingState::Sell(BillWaitingForSellState {
-                        time_of_request: last_block.timestamp,
-                        seller,
-                        buyer,
-                        currency: payment_info.currency,
-                        sum: currency::sum_to_string(payment_info.sum),
-                        link_to_pay,
-                        address_to_pay,
-                        mempool_link_for_address_to_pay,
-                    }))
-                } else {
-                    None
-                }
-            }
-            BillOpCode::RequestToPay => {
-                if paid {
-                    // it's paid - we're not waiting anymore
-                    None
-                } else if request_to_pay_timed_out {
-                    // it timed out, we're not waiting anymore
-                    None
-                } else {
-                    // we're waiting, collect data
-                    let address_to_pay = self
-                        .bitcoin_client
-                        .get_address_to_pay(&bill_keys.public_key, &holder.node_id)?;
-
-                    let link_to_pay = self.bitcoin_client.generate_link_to_pay(
-                        &address_to_pay,
-                        bill.sum,
-                        &format!("Payment in relation to a bill {}", bill.id.clone()),
-                    );
-
-                    let mempool_link_for_address_to_pay = self
-                        .bitcoin_client
-                        .get_mempool_link_for_address(&address_to_pay);
-
-                    Some(BillCurrentWaitingState::Payment(
-                        BillWaitingForPaymentState {
-                            time_of_request: last_block.timestamp,
-                            payer: bill.drawee.clone(),
-                            payee: holder.clone(),
-                            currency: bill.currency.clone(),
-                            sum: currency::sum_to_string(bill.sum),
-                            link_to_pay,
-                            address_to_pay,
-                            mempool_link_for_address_to_pay,
-                        },
-                    ))
-                }
-            }
-            BillOpCode::RequestRecourse => {
-                if let RecourseWaitingForPayment::Yes(payment_info) = chain
-                    .is_last_request_to_recourse_block_waiting_for_payment(
-                        bill_keys,
-                        current_timestamp,
-                    )?
-                {
-                    // we're waiting, collect data
-                    let recourser = self
-                        .extend_bill_chain_identity_data_from_contacts_or_identity(
-                            payment_info.recourser.clone(),
-                            local_identity,
-                            &contacts,
-                        )
-                        .await;
-                    let recoursee = self
-                        .extend_bill_chain_identity_data_from_contacts_or_identity(
-                            payment_info.recoursee.clone(),
-                            local_identity,
-                            &contacts,
-                        )
-                        .await;
-
-                    let address_to_pay = self.bitcoin_client.get_address_to_pay(
-                        &bill_keys.public_key,
-                        &payment_info.recourser.node_id,
-                    )?;
-
-                    let link_to_pay = self.bitcoin_client.generate_link_to_pay(
-                        &address_to_pay,
-                        payment_info.sum,
-                        &format!("Payment in relation to a bill {}", &bill.id),
-                    );
-
-                    let mempool_link_for_address_to_pay = self
-                        .bitcoin_client
-                        .get_mempool_link_for_address(&address_to_pay);
-
-                    Some(BillCurrentWaitingState::Recourse(
-                        BillWaitingForRecourseState {
-                            time_of_request: last_block.timestamp,
-                            recourser,
-                            recoursee,
-                            currency: payment_info.currency,
-                            sum: currency::sum_to_string(payment_info.sum),
-                            link_to_pay,
-                            address_to_pay,
-                            mempool_link_for_address_to_pay,
-                        },
-                    ))
-                } else {
-                    // it timed out, we're not waiting anymore
-                    request_to_recourse_timed_out = true;
-                    requested_to_recourse = true;
-                    None
-                }
-            }
-            _ => None,
-        };
-
-        let status = BillStatus {
-            acceptance: BillAcceptanceStatus {
-                time_of_request_to_accept,
-                requested_to_accept,
-                accepted,
-                request_to_accept_timed_out,
-                rejected_to_accept,
-            },
-            payment: BillPaymentStatus {
-                time_of_request_to_pay,
-                requested_to_pay,
-                paid,
-                request_to_pay_timed_out,
-                rejected_to_pay,
-            },
-            sell: BillSellStatus {
-                time_of_last_offer_to_sell,
-                sold,
-                offered_to_sell,
-                offer_to_sell_timed_out,
-                rejected_offer_to_sell,
-            },
-            recourse: BillRecourseStatus {
-                time_of_last_request_to_recourse,
-                recoursed,
-                requested_to_recourse,
-                request_to_recourse_timed_out,
-                rejected_request_to_recourse,
-            },
-            redeemed_funds_available,
-            has_requested_funds,
-        };
-
-        let participants = BillParticipants {
-            drawee: bill.drawee,
-            drawer: bill.drawer,
-            payee: bill.payee,
-            endorsee: bill.endorsee,
-            endorsements_count,
-            all_participant_node_ids: bill_participants,
-        };
-
-        let bill_data = BillData {
-            language: bill.language,
-            time_of_drawing,
-            issue_date: bill.issue_date,
-            time_of_maturity: util::date::date_string_to_timestamp(&bill.maturity_date, None)
-                .unwrap_or(0) as u64,
-            maturity_date: bill.maturity_date,
-            country_of_issuing: bill.country_of_issuing,
-            city_of_issuing: bill.city_of_issuing,
-            country_of_payment: bill.country_of_payment,
-            city_of_payment: bill.city_of_payment,
-            currency: bill.currency,
-            sum: currency::sum_to_string(bill.sum),
-            files: bill.files,
-            active_notification: None,
-        };
+        bill.status.hold = self.store.get_hold(&bill.id).await?;
+        bill.data.tampered_files = self
+            .get_tampered_files(&bill.id, &bill.data.files, bill_keys)
+            .await;
 
-        Ok(BitcreditBillResult {
-            id: bill.id,
-            participants,
-            data: bill_data,
-            status,
-            current_waiting_state,
-        })
+        Ok(bill)
     }
 
     pub(super) fn check_requests_for_expiration(
@@ -492,65 +216,64 @@ impl BillService {
     ) -> Result<bool> {
         let mut invalidate_and_recalculate = false;
         let acceptance = &bill.status.acceptance;
-        if acceptance.requested_to_accept && !acceptance.accepted && !acceptance.rejected_to_accept
+        if acceptance.requested_to_accept
+            && !acceptance.accepted
+            && !acceptance.rejected_to_accept
+            && let Some(time_of_request_to_accept) = acceptance.time_of_request_to_accept
+            && util::date::check_if_deadline_has_passed(
+                time_of_request_to_accept,
+                current_timestamp,
+                ACCEPT_DEADLINE_SECONDS,
+            )
         {
-            if let Some(time_of_request_to_accept) = acceptance.time_of_request_to_accept {
-                if util::date::check_if_deadline_has_passed(
-                    time_of_request_to_accept,
-                    current_timestamp,
-                    ACCEPT_DEADLINE_SECONDS,
-                ) {
-                    invalidate_and_recalculate = true;
-                }
-            }
+            invalidate_and_recalculate = true;
         }
 
         let payment = &bill.status.payment;
-        if payment.requested_to_pay && !payment.paid && !payment.rejected_to_pay {
-            if let Some(time_of_request_to_pay) = payment.time_of_request_to_pay {
-                let deadline_base = get_deadline_base_for_req_to_pay(
-                    time_of_request_to_pay,
-                    &bill.data.maturity_date,
-                )?;
-                if util::date::check_if_deadline_has_passed(
-                    deadline_base,
-                    current_timestamp,
-                    PAYMENT_DEADLINE_SECONDS,
-                ) {
-                    invalidate_and_recalculate = true;
-                }
+        if payment.requested_to_pay
+            && !payment.paid
+            && !payment.rejected_to_pay
+            && let Some(time_of_request_to_pay) = payment.time_of_request_to_pay
+        {
+            let deadline_base =
+                get_deadline_base_for_req_to_pay(time_of_request_to_pay, &bill.data.maturity_date)?;
+            if util::date::check_if_deadline_has_passed(
+                deadline_base,
+                current_timestamp,
+                bill.data.payment_deadline_seconds,
+            ) {
+                invalidate_and_recalculate = true;
             }
         }
 
         let sell = &bill.status.sell;
-        if sell.offered_to_sell && !sell.sold && !sell.rejected_offer_to_sell {
-            if let Some(time_of_last_offer_to_sell) = sell.time_of_last_offer_to_sell {
-                if util::date::check_if_deadline_has_passed(
-                    time_of_last_offer_to_sell,
-                    current_timestamp,
-                    PAYMENT_DEADLINE_SECONDS,
-                ) {
-                    invalidate_and_recalculate = true;
-                }
-            }
+        if sell.offered_to_sell
+            && !sell.sold
+            && !sell.rejected_offer_to_sell
+            && let Some(time_of_last_offer_to_sell) = sell.time_of_last_offer_to_sell
+            && util::date::check_if_deadline_has_passed(
+                time_of_last_offer_to_sell,
+                current_timestamp,
+                sell.sell_deadline_seconds
+                    .unwrap_or(bill.data.payment_deadline_seconds),
+            )
+        {
+            invalidate_and_recalculate = true;
         }
 
         let recourse = &bill.status.recourse;
         if recourse.requested_to_recourse
             && !recourse.recoursed
             && !recourse.rejected_request_to_recourse
-        {
-            if let Some(time_of_last_request_to_recourse) =
+            && let Some(time_of_last_request_to_recourse) =
                 recourse.time_of_last_request_to_recourse
-            {
-                if util::date::check_if_deadline_has_passed(
-                    time_of_last_request_to_recourse,
-                    current_timestamp,
-                    RECOURSE_DEADLINE_SECONDS,
-                ) {
-                    invalidate_and_recalculate = true;
-                }
-            }
+            && util::date::check_if_deadline_has_passed(
+                time_of_last_request_to_recourse,
+                current_timestamp,
+                RECOURSE_DEADLINE_SECONDS,
+            )
+        {
+            invalidate_and_recalculate = true;
         }
         Ok(invalidate_and_recalculate)
     }
@@ -644,6 +367,35 @@ impl BillService {
         };
     }
 
+    /// Loads the chain of the given bill - if it can't be loaded, because a block is corrupted,
+    /// the error identifies the height of the first corrupted block
+    pub(super) async fn get_chain_or_corrupted_block_error(
+        &self,
+        bill_id: &str,
+    ) -> Result<BillBlockchain> {
+        let load_err = match self.blockchain_store.get_chain(bill_id).await {
+            Ok(chain) => return Ok(chain),
+            Err(e) => e,
+        };
+        let (blocks, bill_keys) = match (
+            self.blockchain_store.get_blocks(bill_id).await,
+            self.store.get_keys(bill_id).await,
+        ) {
+            (Ok(blocks), Ok(bill_keys)) => (blocks, bill_keys),
+            _ => return Err(load_err.into()),
+        };
+        match BillBlockchain::verify_blocks(&blocks, &bill_keys).first_invalid_block_height {
+            Some(height) => {
+                error!(
+                    "{} Block at height {height} of bill chain is corrupted: {load_err}",
+                    LogContext::bill(bill_id)
+                );
+                Err(blockchain::Error::CorruptedBlock(height).into())
+            }
+            None => Err(load_err.into()),
+        }
+    }
+
     pub(super) async fn recalculate_and_cache_bill(
         &self,
         bill_id: &str,
@@ -651,7 +403,7 @@ impl BillService {
         current_identity_node_id: &str,
         current_timestamp: u64,
     ) -> Result<BitcreditBillResult> {
-        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        let chain = self.get_chain_or_corrupted_block_error(bill_id).await?;
         let bill_keys = self.store.get_keys(bill_id).await?;
         let calculated_bill = self
             .calculate_full_bill(
@@ -667,7 +419,10 @@ impl BillService {
             .save_bill_to_cache(bill_id, &calculated_bill)
             .await
         {
-            error!("Error saving calculated bill {bill_id} to cache: {e}");
+            error!(
+                "{} Error saving calculated bill to cache: {e}",
+                LogContext::bill(bill_id).with_node_id(current_identity_node_id)
+            );
         }
         Ok(calculated_bill)
     }
@@ -684,6 +439,7 @@ impl BillService {
             return Err(Error::NotFound);
         }
 
+        let log_ctx = LogContext::bill(bill_id).with_node_id(current_identity_node_id);
         // fetch contacts to get current contact data for participants
         let contacts = self.contact_store.get_map().await?;
 
@@ -703,7 +459,7 @@ impl BillService {
                 // accept/pay/recourse/sell is expired, we need to recalculate the bill
                 if self.check_requests_for_expiration(&bill, current_timestamp)? {
                     debug!(
-                        "Bill cache hit, but needs to recalculate because of request deadline {bill_id} - recalculating"
+                        "{log_ctx} Bill cache hit, but needs to recalculate because of request deadline - recalculating"
                     );
                     self.recalculate_and_cache_bill(
                         bill_id,
@@ -719,9 +475,9 @@ impl BillService {
             Ok(None) | Err(_) => {
                 // No cache, or error fetching it - recalculate the bill, cache it and return it
                 if let Err(e) = bill_cache_result {
-                    error!("Error fetching bill {bill_id} from cache: {e}");
+                    error!("{log_ctx} Error fetching bill from cache: {e}");
                 }
-                debug!("Bill cache miss for {bill_id} - recalculating");
+                debug!("{log_ctx} Bill cache miss - recalculating");
                 self.recalculate_and_cache_bill(
                     bill_id,
                     local_identity,
@@ -742,3 +498,507 @@ impl BillService {
         Ok(bill)
     }
 }
+
+/// Bill state, which isn't part of the chain - whether the bill was paid is checked on-chain
+/// by the payment jobs and some of the status depends on who looks at the bill
+#[derive(Debug, Clone, Default)]
+pub(super) struct LocalBillState {
+    pub current_identity_node_id: String,
+    pub paid: bool,
+    pub paid_sum: u64,
+}
+
+/// Reconstructs the bill from the given chain, the same way the bill service calculates it, but
+/// without any local state - participants only contain the data recorded in the chain, the bill
+/// only counts as paid if a payment was attested in the chain, the status isn't specific to a
+/// participant, and there are neither fee estimates, nor checks of the attached files
+pub fn reconstruct_bill_from_chain(
+    chain: &BillBlockchain,
+    bill_keys: &BillKeys,
+    current_timestamp: u64,
+) -> Result<BitcreditBillResult> {
+    fold_bill_chain(
+        chain,
+        bill_keys,
+        current_timestamp,
+        &LocalBillState::default(),
+        &BitcoinClient::new(),
+    )
+}
+
+/// Returns the latest version of the bill, with the participants as they're recorded in the chain
+pub(super) fn last_version_bill_from_chain(
+    chain: &BillBlockchain,
+    bill_keys: &BillKeys,
+) -> Result<BitcreditBill> {
+    let bill_first_version = chain.get_first_version_bill(bill_keys)?;
+    let bill_parties = chain.get_bill_parties(bill_keys, &bill_first_version)?;
+    let mut files = bill_first_version.files;
+    files.extend(chain.get_attached_files(bill_keys)?);
+
+    Ok(BitcreditBill {
+        id: bill_first_version.id,
+        country_of_issuing: bill_first_version.country_of_issuing,
+        city_of_issuing: bill_first_version.city_of_issuing,
+        drawee: bill_parties.drawee.into(),
+        drawer: bill_parties.drawer.into(),
+        payee: bill_parties.payee.into(),
+        endorsee: bill_parties.endorsee.map(|endorsee| endorsee.into()),
+        currency: bill_first_version.currency,
+        sum: bill_first_version.sum,
+        maturity_date: bill_first_version.maturity_date,
+        issue_date: bill_first_version.issue_date,
+        country_of_payment: bill_first_version.country_of_payment,
+        city_of_payment: bill_first_version.city_of_payment,
+        language: bill_first_version.language,
+        files,
+        payment_deadline_seconds: bill_first_version
+            .payment_deadline_seconds
+            .unwrap_or(PAYMENT_DEADLINE_SECONDS),
+        fiat_amount: bill_first_version.fiat_amount,
+    })
+}
+
+/// Returns the deadline of the payment the bill is currently waiting for, if any
+fn fee_estimate_deadline(bill: &BitcreditBillResult) -> Result<Option<u64>> {
+    Ok(match bill.current_waiting_state {
+        Some(BillCurrentWaitingState::Sell(ref state)) => Some(
+            state.time_of_request.saturating_add(
+                bill.status
+                    .sell
+                    .sell_deadline_seconds
+                    .unwrap_or(bill.data.payment_deadline_seconds),
+            ),
+        ),
+        Some(BillCurrentWaitingState::Payment(ref state)) => {
+            let deadline_base =
+                get_deadline_base_for_req_to_pay(state.time_of_request, &bill.data.maturity_date)?;
+            Some(deadline_base.saturating_add(bill.data.payment_deadline_seconds))
+        }
+        Some(BillCurrentWaitingState::Recourse(ref state)) => {
+            Some(state.time_of_request + RECOURSE_DEADLINE_SECONDS)
+        }
+        None => None,
+    })
+}
+
+/// Folds the blocks of the chain into the current state of the bill
+pub(super) fn fold_bill_chain(
+    chain: &BillBlockchain,
+    bill_keys: &BillKeys,
+    current_timestamp: u64,
+    local_state: &LocalBillState,
+    bitcoin_client: &dyn BitcoinClientApi,
+) -> Result<BitcreditBillResult> {
+    let current_identity_node_id = local_state.current_identity_node_id.as_str();
+    let bill = last_version_bill_from_chain(chain, bill_keys)?;
+    let first_version_bill = chain.get_first_version_bill(bill_keys)?;
+    let time_of_drawing = first_version_bill.signing_timestamp;
+
+    let bill_participants = chain.get_all_nodes_from_bill(bill_keys)?;
+    let endorsements_count = chain.get_endorsements_count();
+
+    let holder = match bill.endorsee {
+        None => &bill.payee,
+        Some(ref endorsee) => endorsee,
+    };
+
+    let mut paid = false;
+    let mut paid_sum = 0;
+    let mut requested_to_pay = false;
+    let mut rejected_to_pay = false;
+    let mut rejected_to_pay_reason = None;
+    let mut request_to_pay_timed_out = false;
+    let mut time_of_request_to_pay = None;
+    if let Some(req_to_pay_block) =
+        chain.get_last_version_block_with_op_code(BillOpCode::RequestToPay)
+    {
+        requested_to_pay = true;
+        time_of_request_to_pay = Some(req_to_pay_block.timestamp);
+        paid = local_state.paid;
+        paid_sum = local_state.paid_sum;
+        rejected_to_pay = chain.block_with_operation_code_exists(BillOpCode::RejectToPay);
+        rejected_to_pay_reason = chain.get_reject_reason(bill_keys, BillOpCode::RejectToPay)?;
+        let deadline_base =
+            get_deadline_base_for_req_to_pay(req_to_pay_block.timestamp, &bill.maturity_date)?;
+        if !paid
+            && !rejected_to_pay
+            && util::date::check_if_deadline_has_passed(
+                deadline_base,
+                current_timestamp,
+                bill.payment_deadline_seconds,
+            )
+        {
+            request_to_pay_timed_out = true;
+        }
+    }
+
+    // calculate, if the caller has received funds at any point in the bill
+    let mut redeemed_funds_available =
+        chain.is_beneficiary_from_a_block(bill_keys, current_identity_node_id);
+    if holder.node_id == current_identity_node_id && paid {
+        redeemed_funds_available = true;
+    }
+
+    // a payment attested by the holder counts as paid, but there are no funds to redeem
+    let external_payment_reference = chain.get_external_payment_reference(bill_keys)?;
+    if external_payment_reference.is_some() {
+        paid = true;
+        request_to_pay_timed_out = false;
+    }
+
+    let has_requested_funds =
+        chain.is_beneficiary_from_a_request_funds_block(bill_keys, current_identity_node_id);
+
+    let mut offered_to_sell = false;
+    let mut rejected_offer_to_sell = false;
+    let mut rejected_offer_to_sell_reason = None;
+    let mut offer_to_sell_timed_out = false;
+    let mut sold = false;
+    let mut cancelled_offer_to_sell = false;
+    let mut time_of_last_offer_to_sell = None;
+    let mut sell_deadline_seconds = None;
+    if let Some(last_offer_to_sell_block) =
+        chain.get_last_version_block_with_op_code(BillOpCode::OfferToSell)
+    {
+        time_of_last_offer_to_sell = Some(last_offer_to_sell_block.timestamp);
+        offered_to_sell = true;
+        let offer_to_sell: BillOfferToSellBlockData =
+            last_offer_to_sell_block.get_decrypted_block_bytes(bill_keys)?;
+        let offer_sell_deadline_seconds =
+            offer_to_sell.get_sell_deadline_seconds(bill.payment_deadline_seconds);
+        sell_deadline_seconds = Some(offer_sell_deadline_seconds);
+        if let Some(last_reject_offer_to_sell_block) =
+            chain.get_last_version_block_with_op_code(BillOpCode::RejectToBuy)
+            && last_reject_offer_to_sell_block.id > last_offer_to_sell_block.id
+        {
+            rejected_offer_to_sell = true;
+            rejected_offer_to_sell_reason =
+                chain.get_reject_reason(bill_keys, BillOpCode::RejectToBuy)?;
+        }
+        if let Some(last_sell_block) = chain.get_last_version_block_with_op_code(BillOpCode::Sell)
+            && last_sell_block.id > last_offer_to_sell_block.id
+        {
+            // last offer to sell was sold
+            sold = true;
+        }
+        if let Some(last_cancel_offer_to_sell_block) =
+            chain.get_last_version_block_with_op_code(BillOpCode::CancelOfferToSell)
+            && last_cancel_offer_to_sell_block.id > last_offer_to_sell_block.id
+        {
+            // last offer to sell was withdrawn by the seller, so it can't time out
+            cancelled_offer_to_sell = true;
+        }
+        if !sold
+            && !rejected_offer_to_sell
+            && !cancelled_offer_to_sell
+            && util::date::check_if_deadline_has_passed(
+                last_offer_to_sell_block.timestamp,
+                current_timestamp,
+                offer_sell_deadline_seconds,
+            )
+        {
+            offer_to_sell_timed_out = true;
+        }
+    }
+
+    let mut requested_to_recourse = false;
+    let mut request_to_recourse_timed_out = false;
+    let mut time_of_last_request_to_recourse = None;
+    let mut rejected_request_to_recourse = false;
+    let mut recoursed = false;
+    if let Some(last_req_to_recourse_block) =
+        chain.get_last_version_block_with_op_code(BillOpCode::RequestRecourse)
+    {
+        requested_to_recourse = true;
+        time_of_last_request_to_recourse = Some(last_req_to_recourse_block.timestamp);
+        if let Some(last_reject_to_pay_recourse_block) =
+            chain.get_last_version_block_with_op_code(BillOpCode::RejectToPayRecourse)
+            && last_reject_to_pay_recourse_block.id > last_req_to_recourse_block.id
+        {
+            rejected_request_to_recourse = true;
+        }
+        if let Some(last_recourse_block) =
+            chain.get_last_version_block_with_op_code(BillOpCode::Recourse)
+            && last_recourse_block.id > last_req_to_recourse_block.id
+        {
+            recoursed = true
+        }
+        if !recoursed
+            && !rejected_request_to_recourse
+            && util::date::check_if_deadline_has_passed(
+                last_req_to_recourse_block.timestamp,
+                current_timestamp,
+                RECOURSE_DEADLINE_SECONDS,
+            )
+        {
+            request_to_recourse_timed_out = true;
+        }
+    }
+
+    let mut request_to_accept_timed_out = false;
+    let rejected_to_accept = chain.block_with_operation_code_exists(BillOpCode::RejectToAccept);
+    let rejected_to_accept_reason =
+        chain.get_reject_reason(bill_keys, BillOpCode::RejectToAccept)?;
+    let accepted = chain.block_with_operation_code_exists(BillOpCode::Accept);
+    let mut time_of_request_to_accept = None;
+    let mut requested_to_accept = false;
+    // a request to accept that was cancelled by the requester isn't waited for anymore
+    if let Some(req_to_accept_block) = chain.get_last_active_request_to_accept_block() {
+        requested_to_accept = true;
+        time_of_request_to_accept = Some(req_to_accept_block.timestamp);
+
+        if !accepted
+            && !rejected_to_accept
+            && util::date::check_if_deadline_has_passed(
+                req_to_accept_block.timestamp,
+                current_timestamp,
+                ACCEPT_DEADLINE_SECONDS,
+            )
+        {
+            request_to_accept_timed_out = true;
+        }
+    }
+
+    let current_waiting_state = waiting_state_from_chain(
+        chain,
+        bill_keys,
+        current_timestamp,
+        local_state.paid,
+        bitcoin_client,
+    )?;
+    if current_waiting_state.is_none()
+        && chain.get_latest_block_for_waiting_state().op_code == BillOpCode::RequestRecourse
+    {
+        // it timed out, we're not waiting anymore
+        request_to_recourse_timed_out = true;
+        requested_to_recourse = true;
+    }
+
+    let mut status = BillStatus {
+        acceptance: BillAcceptanceStatus {
+            time_of_request_to_accept,
+            requested_to_accept,
+            accepted,
+            request_to_accept_timed_out,
+            rejected_to_accept,
+            rejected_to_accept_reason,
+        },
+        payment: BillPaymentStatus {
+            time_of_request_to_pay,
+            requested_to_pay,
+            paid,
+            paid_sum,
+            request_to_pay_timed_out,
+            rejected_to_pay,
+            rejected_to_pay_reason,
+            external_payment_reference,
+        },
+        sell: BillSellStatus {
+            time_of_last_offer_to_sell,
+            sold,
+            offered_to_sell,
+            offer_to_sell_timed_out,
+            rejected_offer_to_sell,
+            rejected_offer_to_sell_reason,
+            sell_deadline_seconds,
+        },
+        recourse: BillRecourseStatus {
+            time_of_last_request_to_recourse,
+            recoursed,
+            requested_to_recourse,
+            request_to_recourse_timed_out,
+            rejected_request_to_recourse,
+        },
+        redeemed_funds_available,
+        has_requested_funds,
+        hold: None,
+        last_op_code: chain.get_latest_block().op_code.clone(),
+        block_height: chain.block_height() as u64,
+        settlement_status: SettlementStatus::Active,
+    };
+    // the past holders are only needed, if the bill wasn't honoured
+    let recourse_possible = !status.is_dishonoured()
+        || !chain
+            .get_past_endorsees_for_bill(bill_keys, &holder.node_id)?
+            .is_empty();
+    status.settlement_status = status.derive_settlement_status(recourse_possible);
+
+    let participants = BillParticipants {
+        drawee: bill.drawee,
+        drawer: bill.drawer,
+        payee: bill.payee,
+        endorsee: bill.endorsee,
+        endorsements_count,
+        all_participant_node_ids: bill_participants,
+    };
+
+    let bill_data = BillData {
+        language: bill.language,
+        time_of_drawing,
+        issue_date: bill.issue_date,
+        time_of_maturity: util::date::date_string_to_timestamp(&bill.maturity_date, None)
+            .unwrap_or(0) as u64,
+        maturity_date: bill.maturity_date,
+        country_of_issuing: bill.country_of_issuing,
+        city_of_issuing: bill.city_of_issuing,
+        country_of_payment: bill.country_of_payment,
+        city_of_payment: bill.city_of_payment,
+        currency: bill.currency,
+        sum: currency::sum_to_string(bill.sum),
+        files: bill.files,
+        tampered_files: vec![],
+        payment_deadline_seconds: bill.payment_deadline_seconds,
+        fiat_amount: bill.fiat_amount,
+        active_notification: None,
+        note: None,
+        acknowledgements: chain.get_acknowledgements(bill_keys)?,
+    };
+
+    Ok(BitcreditBillResult {
+        id: bill.id,
+        participants,
+        data: bill_data,
+        status,
+        current_waiting_state,
+    })
+}
+
+/// Calculates the payment the bill is currently waiting for. Only the blocks needed for it are
+/// decrypted - if the block the waiting state depends on isn't a request for a payment, the bill
+/// isn't waiting and no block is decrypted at all. `paid` is the local payment state of the bill.
+pub(super) fn waiting_state_from_chain(
+    chain: &BillBlockchain,
+    bill_keys: &BillKeys,
+    current_timestamp: u64,
+    paid: bool,
+    bitcoin_client: &dyn BitcoinClientApi,
+) -> Result<Option<BillCurrentWaitingState>> {
+    let bill_id = &chain.get_first_block().bill_id;
+    let last_block = chain.get_latest_block_for_waiting_state();
+    let current_waiting_state = match last_block.op_code {
+        BillOpCode::OfferToSell => {
+            if let OfferToSellWaitingForPayment::Yes(payment_info) = chain
+                .is_last_offer_to_sell_block_waiting_for_payment(bill_keys, current_timestamp)?
+            {
+                // we're waiting, collect data
+                let address_to_pay = payment_info.payment_address;
+
+                let link_to_pay = bitcoin_client.generate_link_to_pay(
+                    &address_to_pay,
+                    payment_info.sum,
+                    &format!("Payment in relation to a bill {bill_id}"),
+                );
+
+                let mempool_link_for_address_to_pay =
+                    bitcoin_client.get_mempool_link_for_address(&address_to_pay);
+
+                Some(BillCurrentWaitingState::Sell(BillWaitingForSellState {
+                    time_of_request: last_block.timestamp,
+                    seller: payment_info.seller.into(),
+                    buyer: payment_info.buyer.into(),
+                    currency: payment_info.currency,
+                    sum: currency::sum_to_string(payment_info.sum),
+                    link_to_pay,
+                    address_to_pay,
+                    mempool_link_for_address_to_pay,
+                    fee_estimate: None,
+                }))
+            } else {
+                None
+            }
+        }
+        BillOpCode::RequestToPay => {
+            let bill_first_version = chain.get_first_version_bill(bill_keys)?;
+            let deadline_base = get_deadline_base_for_req_to_pay(
+                last_block.timestamp,
+                &bill_first_version.maturity_date,
+            )?;
+            let request_to_pay_timed_out = !chain
+                .block_with_operation_code_exists(BillOpCode::RejectToPay)
+                && util::date::check_if_deadline_has_passed(
+                    deadline_base,
+                    current_timestamp,
+                    bill_first_version
+                        .payment_deadline_seconds
+                        .unwrap_or(PAYMENT_DEADLINE_SECONDS),
+                );
+            if paid || chain.get_external_payment_reference(bill_keys)?.is_some() {
+                // it's paid - we're not waiting anymore
+                None
+            } else if request_to_pay_timed_out {
+                // it timed out, we're not waiting anymore
+                None
+            } else {
+                // we're waiting, collect data
+                let bill_parties = chain.get_bill_parties(bill_keys, &bill_first_version)?;
+                let holder: IdentityPublicData =
+                    bill_parties.endorsee.unwrap_or(bill_parties.payee).into();
+                let address_to_pay =
+                    bitcoin_client.get_address_to_pay(&bill_keys.public_key, &holder.node_id)?;
+
+                let link_to_pay = bitcoin_client.generate_link_to_pay(
+                    &address_to_pay,
+                    bill_first_version.sum,
+                    &format!("Payment in relation to a bill {bill_id}"),
+                );
+
+                let mempool_link_for_address_to_pay =
+                    bitcoin_client.get_mempool_link_for_address(&address_to_pay);
+
+                Some(BillCurrentWaitingState::Payment(
+                    BillWaitingForPaymentState {
+                        time_of_request: last_block.timestamp,
+                        payer: bill_parties.drawee.into(),
+                        payee: holder,
+                        currency: bill_first_version.currency,
+                        sum: currency::sum_to_string(bill_first_version.sum),
+                        link_to_pay,
+                        address_to_pay,
+                        mempool_link_for_address_to_pay,
+                        fee_estimate: None,
+                    },
+                ))
+            }
+        }
+        BillOpCode::RequestRecourse => {
+            if let RecourseWaitingForPayment::Yes(payment_info) = chain
+                .is_last_request_to_recourse_block_waiting_for_payment(
+                    bill_keys,
+                    current_timestamp,
+                )?
+            {
+                // we're waiting, collect data
+                let address_to_pay = bitcoin_client
+                    .get_address_to_pay(&bill_keys.public_key, &payment_info.recourser.node_id)?;
+
+                let link_to_pay = bitcoin_client.generate_link_to_pay(
+                    &address_to_pay,
+                    payment_info.sum,
+                    &format!("Payment in relation to a bill {bill_id}"),
+                );
+
+                let mempool_link_for_address_to_pay =
+                    bitcoin_client.get_mempool_link_for_address(&address_to_pay);
+
+                Some(BillCurrentWaitingState::Recourse(
+                    BillWaitingForRecourseState {
+                        time_of_request: last_block.timestamp,
+                        recourser: payment_info.recourser.into(),
+                        recoursee: payment_info.recoursee.into(),
+                        currency: payment_info.currency,
+                        sum: currency::sum_to_string(payment_info.sum),
+                        link_to_pay,
+                        address_to_pay,
+                        mempool_link_for_address_to_pay,
+                        fee_estimate: None,
+                    },
+                ))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+    Ok(current_waiting_state)
+}
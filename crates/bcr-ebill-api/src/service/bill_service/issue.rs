@@ -1,21 +1,70 @@
 use super::{BillAction, BillServiceApi, Result, error::Error, service::BillService};
+use crate::get_config;
 use crate::util;
+use crate::util::log_context::LogContext;
 use bcr_ebill_core::{
-    File, Validate,
-    bill::{BillIssueData, BillKeys, BillType, BitcreditBill, validation::validate_bill_issue},
+    File, Validate, ValidationError,
+    bill::{
+        BillFiatAmount, BillIssueData, BillKeys, BillType, BitcreditBill,
+        validation::{validate_bill_attachments, validate_bill_issue},
+    },
     blockchain::{
         Blockchain,
         bill::{BillBlockchain, block::BillIssueBlockData},
     },
+    constants::PAYMENT_DEADLINE_SECONDS,
     util::BcrKeys,
 };
 use bcr_ebill_transport::BillChainEvent;
 use log::{debug, error};
 
 impl BillService {
+    /// Returns the currency and sum the bill is issued with - for fiat bills, the sum is
+    /// converted to sat with the current BTC rate, which is recorded along with the fiat sum
+    async fn get_issue_sum(
+        &self,
+        currency: &str,
+        sum: u64,
+    ) -> Result<(String, u64, Option<BillFiatAmount>)> {
+        if !util::currency::is_fiat_currency(currency) {
+            return Ok((currency.to_owned(), sum, None));
+        }
+
+        let rate = match self.fiat_rate_client.get_btc_rate(currency).await {
+            Ok(rate) => rate,
+            Err(e) => {
+                error!("Could not fetch btc rate for {currency}: {e}");
+                return Err(ValidationError::FiatRateUnavailable.into());
+            }
+        };
+        let sat_sum = util::currency::fiat_to_sat(sum, rate.rate)
+            .ok_or(ValidationError::FiatRateUnavailable)?;
+        debug!(
+            "issuing bill with {} {currency} as {sat_sum} sat",
+            util::currency::fiat_sum_to_string(sum)
+        );
+
+        Ok((
+            "sat".to_owned(),
+            sat_sum,
+            Some(BillFiatAmount {
+                currency: rate.fiat_code,
+                sum: util::currency::fiat_sum_to_string(sum),
+                btc_rate: rate.rate,
+                rate_timestamp: rate.timestamp,
+            }),
+        ))
+    }
+
     pub(super) async fn issue_bill(&self, data: BillIssueData) -> Result<BitcreditBill> {
-        debug!("issuing bill with type {}", &data.t);
-        let (sum, bill_type) = validate_bill_issue(&data)?;
+        debug!(
+            "{} issuing bill with type {}",
+            LogContext::node(&data.drawer_public_data.node_id),
+            &data.t
+        );
+        self.issue_validator.validate(&data)?;
+        let (sum, currency, bill_type) = validate_bill_issue(&data)?;
+        let (currency, sum, fiat_amount) = self.get_issue_sum(&currency, sum).await?;
 
         let (public_data_drawee, public_data_payee) = match bill_type {
             // Drawer is payee
@@ -63,25 +112,40 @@ impl BillService {
                 (public_data_drawee, public_data_payee)
             }
         };
-        debug!("issuing bill with drawee {public_data_drawee:?} and payee {public_data_payee:?}");
+        debug!(
+            "{} issuing bill with drawee {public_data_drawee:?} and payee {public_data_payee:?}",
+            LogContext::node(&data.drawer_public_data.node_id)
+        );
 
         let identity = self.identity_store.get_full().await?;
         let keys = BcrKeys::new();
         let public_key = keys.get_public_key();
 
         let bill_id = util::sha256_hash(public_key.as_bytes());
+        let log_ctx = LogContext::bill(&bill_id).with_node_id(&data.drawer_public_data.node_id);
         let bill_keys = BillKeys {
             private_key: keys.get_private_key_string(),
             public_key: keys.get_public_key(),
         };
 
-        let mut bill_files: Vec<File> = vec![];
+        let mut uploads: Vec<(String, Vec<u8>)> = vec![];
         for file_upload_id in data.file_upload_ids.iter() {
-            let (file_name, file_bytes) = &self
-                .file_upload_store
-                .read_temp_upload_file(file_upload_id)
-                .await
-                .map_err(|_| Error::NoFileForFileUploadId)?;
+            uploads.push(
+                self.file_upload_store
+                    .read_temp_upload_file(file_upload_id)
+                    .await
+                    .map_err(|_| Error::NoFileForFileUploadId)?,
+            );
+        }
+        validate_bill_attachments(
+            uploads.len(),
+            uploads.iter().map(|(_, bytes)| bytes.len()).sum(),
+            get_config().max_files_per_bill,
+            get_config().max_total_attachment_bytes,
+        )?;
+
+        let mut bill_files: Vec<File> = vec![];
+        for (file_name, file_bytes) in uploads.iter() {
             bill_files.push(
                 self.encrypt_and_save_uploaded_file(file_name, file_bytes, &bill_id, &public_key)
                     .await?,
@@ -92,7 +156,7 @@ impl BillService {
             id: bill_id.clone(),
             country_of_issuing: data.country_of_issuing,
             city_of_issuing: data.city_of_issuing,
-            currency: data.currency,
+            currency,
             sum,
             maturity_date: data.maturity_date,
             issue_date: data.issue_date,
@@ -104,6 +168,10 @@ impl BillService {
             payee: public_data_payee,
             endorsee: None,
             files: bill_files,
+            payment_deadline_seconds: data
+                .payment_deadline_seconds
+                .unwrap_or(PAYMENT_DEADLINE_SECONDS),
+            fiat_amount,
         };
 
         let signing_keys =
@@ -156,7 +224,7 @@ impl BillService {
                 .await
             {
                 error!(
-                    "Error while cleaning up temporary file uploads for {}: {e}",
+                    "{log_ctx} Error while cleaning up temporary file uploads for {}: {e}",
                     &file_upload_id
                 );
             }
@@ -174,20 +242,21 @@ impl BillService {
             )?)
             .await
         {
-            error!("Error propagating bill via Nostr {e}");
+            error!("{log_ctx} Error propagating bill via Nostr {e}");
         }
 
-        debug!("issued bill with id {bill_id}");
+        debug!("{log_ctx} issued bill");
 
         // If we're the drawee, we immediately accept the bill with timestamp increased by 1 sec
         if bill.drawer == bill.drawee {
-            debug!("we are drawer and drawee of bill: {bill_id} - immediately accepting");
+            debug!("{log_ctx} we are drawer and drawee of the bill - immediately accepting");
             self.execute_bill_action(
                 &bill_id,
                 BillAction::Accept,
                 &data.drawer_public_data,
                 &data.drawer_keys,
                 data.timestamp + 1,
+                None,
             )
             .await?;
         }
@@ -0,0 +1,20 @@
+use bcr_ebill_core::{ServiceTraitBounds, ValidationError, bill::BillIssueData};
+
+/// Deployment specific rules, which are checked before a bill is issued, e.g. a minimum sum,
+/// approved drawees, or blocked countries. A rejected bill is not signed and the error is
+/// returned to the caller - `ValidationError::IssuePolicyViolation` can be used for custom rules
+pub trait BillIssueValidator: ServiceTraitBounds {
+    fn validate(&self, data: &BillIssueData) -> Result<(), ValidationError>;
+}
+
+/// The default validator, which accepts all bills
+#[derive(Debug, Clone, Default)]
+pub struct NoOpBillIssueValidator;
+
+impl ServiceTraitBounds for NoOpBillIssueValidator {}
+
+impl BillIssueValidator for NoOpBillIssueValidator {
+    fn validate(&self, _data: &BillIssueData) -> Result<(), ValidationError> {
+        Ok(())
+    }
+}
@@ -1,31 +1,46 @@
+use crate::blockchain::ChainVerificationReport;
 use crate::blockchain::bill::BillBlockchain;
 use crate::data::{
     File,
     bill::{
-        BillCombinedBitcoinKey, BillKeys, BillsBalanceOverview, BillsFilterRole, BitcreditBill,
-        BitcreditBillResult, Endorsement, LightBitcreditBillResult, PastEndorsee,
+        BillCombinedBitcoinKey, BillCurrentWaitingState, BillInconsistency, BillKeys,
+        BillsBalanceOverview, BillsBalanceOverviewWithFiat, BillsFilterRole, BillsFilterStatus,
+        BitcreditBill, BitcreditBillResult, Endorsement, ExposureSummary, LightBitcreditBillResult,
+        PastEndorsee, ResolvedParticipant, SuggestedAction,
     },
     contact::IdentityPublicData,
     identity::Identity,
+    notification::{ActionType, NotificationPreview},
 };
 use crate::util::BcrKeys;
 use async_trait::async_trait;
 use bcr_ebill_core::ServiceTraitBounds;
-use bcr_ebill_core::bill::{BillAction, BillIssueData, PastPaymentResult};
+use bcr_ebill_core::bill::{
+    BillAction, BillAutoAcceptRule, BillHistoryEntry, BillIssueData, BillTemplate,
+    BillTemplateOverrides, BillView, FieldDiff, PastPaymentResult, PaymentTx,
+};
+use bcr_ebill_transport::EventEnvelope;
+use std::collections::HashMap;
 
+pub use data_fetching::reconstruct_bill_from_chain;
 pub use error::Error;
+pub use issue_validator::{BillIssueValidator, NoOpBillIssueValidator};
 #[cfg(test)]
 use mockall::automock;
+pub use qr_payload::BillQrPayload;
 
 /// Generic result type
 pub type Result<T> = std::result::Result<T, error::Error>;
 
 mod blocks;
+mod bundle;
 mod data_fetching;
 pub mod error;
 mod issue;
+mod issue_validator;
 mod payment;
 mod propagation;
+mod qr_payload;
 pub mod service;
 #[cfg(test)]
 pub mod test_utils;
@@ -44,6 +59,28 @@ pub trait BillServiceApi: ServiceTraitBounds {
         current_identity_node_id: &str,
     ) -> Result<BillsBalanceOverview>;
 
+    /// Get the bill balances of the given company, separated from the personal bills of its
+    /// signatories - fails, if the caller isn't a signatory of the company
+    async fn get_company_bill_balances(
+        &self,
+        company_id: &str,
+        caller_node_id: &str,
+    ) -> Result<BillsBalanceOverview>;
+
+    /// Get the total sat exposure across all roles - the balances, the net position (what's
+    /// owed to the node minus what it owes) and the number of accepted, awaiting acceptance and
+    /// overdue bills
+    async fn get_total_exposure(&self, current_identity_node_id: &str) -> Result<ExposureSummary>;
+
+    /// Get bill balances, additionally converted to the given fiat currency - if the BTC/fiat
+    /// rate can't be fetched, the fiat sums and the rate are `None`
+    async fn get_bill_balances_with_fiat(
+        &self,
+        currency: &str,
+        fiat_code: &str,
+        current_identity_node_id: &str,
+    ) -> Result<BillsBalanceOverviewWithFiat>;
+
     /// Search for bills
     async fn search_bills(
         &self,
@@ -52,11 +89,53 @@ pub trait BillServiceApi: ServiceTraitBounds {
         date_range_from: Option<u64>,
         date_range_to: Option<u64>,
         role: &BillsFilterRole,
+        status: &BillsFilterStatus,
         current_identity_node_id: &str,
     ) -> Result<Vec<LightBitcreditBillResult>>;
 
     /// Gets all bills
-    async fn get_bills(&self, current_identity_node_id: &str) -> Result<Vec<BitcreditBillResult>>;
+    /// Archived bills are only included, if `include_archived` is set
+    async fn get_bills(
+        &self,
+        current_identity_node_id: &str,
+        include_archived: bool,
+    ) -> Result<Vec<BitcreditBillResult>>;
+
+    /// Gets all bills, in which the given participant node id is a participant. Only bills, in
+    /// which the current identity is a participant as well are returned
+    async fn get_bills_by_participant(
+        &self,
+        participant_node_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<Vec<LightBitcreditBillResult>>;
+
+    /// Gets the bills maturing between the given timestamps (both inclusive and optional), based
+    /// on their maturity date. Bills with a malformed maturity date and archived bills are
+    /// excluded
+    async fn get_bills_by_maturity(
+        &self,
+        current_identity_node_id: &str,
+        maturity_from: Option<u64>,
+        maturity_to: Option<u64>,
+    ) -> Result<Vec<LightBitcreditBillResult>>;
+
+    /// Gets the bills, in which the given node id started a request (to accept, to pay, to
+    /// recourse, or an offer to sell), which is still waiting for a counterparty at the given
+    /// timestamp. Archived bills are excluded
+    async fn get_bills_awaiting_others(
+        &self,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Vec<LightBitcreditBillResult>>;
+
+    /// Gets the bills in a light version for each of the given node ids (e.g. the personal
+    /// identity and the companies of the caller), keyed by node id. Bills are loaded only once,
+    /// so a bill shared between the given node ids is listed for each of them. Archived bills
+    /// are excluded
+    async fn get_bills_grouped(
+        &self,
+        node_ids: &[String],
+    ) -> Result<HashMap<String, Vec<LightBitcreditBillResult>>>;
 
     /// Gets the combined bitcoin private key for a given bill
     async fn get_combined_bitcoin_key_for_bill(
@@ -66,6 +145,25 @@ pub trait BillServiceApi: ServiceTraitBounds {
         caller_keys: &BcrKeys,
     ) -> Result<BillCombinedBitcoinKey>;
 
+    /// Recomputes the payment address of the given block - for payment blocks, the address of
+    /// the party to be paid, for other blocks, the address of the holder at that block. The
+    /// caller has to be a participant of the bill
+    async fn get_payment_address_at_block(
+        &self,
+        bill_id: &str,
+        block_height: u64,
+        caller_keys: &BcrKeys,
+    ) -> Result<String>;
+
+    /// Returns the transactions observed at the current payment address of the bill, i.e. the
+    /// one the payment check uses, so the holder can reconcile which transactions funded it.
+    /// The caller has to be a participant of the bill
+    async fn get_bill_payment_transactions(
+        &self,
+        bill_id: &str,
+        caller_keys: &BcrKeys,
+    ) -> Result<Vec<PaymentTx>>;
+
     /// Gets the detail for the given bill id
     async fn get_detail(
         &self,
@@ -75,9 +173,67 @@ pub trait BillServiceApi: ServiceTraitBounds {
         current_timestamp: u64,
     ) -> Result<BitcreditBillResult>;
 
+    /// Gets the detail for the given bill id, calculated from the valid blocks of its chain up to
+    /// the first corrupted block - the result isn't cached, since the bill might be outdated
+    async fn get_detail_up_to_corrupted_block(
+        &self,
+        bill_id: &str,
+        local_identity: &Identity,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<BitcreditBillResult>;
+
+    /// Compares the cached version of the given bill with the one calculated from its chain and
+    /// returns the status and participant fields, which differ. This doesn't refresh the cache,
+    /// so it can be used to diagnose a stale cache. If the bill isn't cached, the result is empty
+    async fn diff_bill_cache(
+        &self,
+        bill_id: &str,
+        local_identity: &Identity,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Vec<FieldDiff>>;
+
+    /// Gets only the payment the given bill is currently waiting for, if any - cheaper than
+    /// `get_detail`, so it can be polled to see if a payment arrived. Participants only contain
+    /// the data recorded in the chain and there is no fee estimate.
+    async fn get_bill_waiting_state(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Option<BillCurrentWaitingState>>;
+
+    /// Gets the single most relevant action the given identity should take next on the bill, or
+    /// None, if nothing is pending for them
+    async fn get_suggested_action(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Option<SuggestedAction>>;
+
+    /// Gets the timestamp at which the outstanding request to accept, pay, sell or recourse of
+    /// the bill times out, or None, if no request is outstanding
+    async fn get_next_timeout(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        now: u64,
+    ) -> Result<Option<u64>>;
+
     /// Gets the keys for a given bill
     async fn get_bill_keys(&self, bill_id: &str) -> Result<BillKeys>;
 
+    /// Verifies the integrity of the bill's chain block by block, reporting the first invalid
+    /// block and the reason, if there is one
+    async fn verify_bill_chain(&self, bill_id: &str) -> Result<ChainVerificationReport>;
+
+    /// Attempts to repair the bill's chain by replacing the blocks from the first corrupted block
+    /// on with the ones received from the relays - returns the verification report of the chain
+    /// after the repair attempt
+    async fn repair_bill_chain(&self, bill_id: &str) -> Result<ChainVerificationReport>;
+
     /// opens and decrypts the attached file from the given bill
     async fn open_and_decrypt_attached_file(
         &self,
@@ -99,7 +255,43 @@ pub trait BillServiceApi: ServiceTraitBounds {
     /// issues a new bill
     async fn issue_new_bill(&self, data: BillIssueData) -> Result<BitcreditBill>;
 
-    /// executes the given bill action
+    /// Saves the given bill template, creating a new id, if it doesn't have one yet, and returns
+    /// the id of the template
+    async fn save_bill_template(&self, template: BillTemplate) -> Result<String>;
+
+    /// Returns all saved bill templates
+    async fn list_bill_templates(&self) -> Result<Vec<BillTemplate>>;
+
+    /// Deletes the given bill template - bills, which were issued from it, aren't affected
+    async fn delete_bill_template(&self, template_id: &str) -> Result<()>;
+
+    /// Adds a rule for the given drawee node id to accept bills drawn by the given drawer
+    /// automatically, if their sum doesn't exceed the given maximum sum
+    async fn add_auto_accept_rule(
+        &self,
+        node_id: &str,
+        drawer_node_id: &str,
+        max_sum: u64,
+    ) -> Result<BillAutoAcceptRule>;
+
+    /// Returns the auto-accept rules of the given drawee node id
+    async fn get_auto_accept_rules(&self, node_id: &str) -> Result<Vec<BillAutoAcceptRule>>;
+
+    /// Removes the given auto-accept rule of the given drawee node id
+    async fn remove_auto_accept_rule(&self, node_id: &str, rule_id: &str) -> Result<()>;
+
+    /// Issues a new bill from the given template, with the given overrides applied
+    async fn issue_from_template(
+        &self,
+        template_id: &str,
+        overrides: BillTemplateOverrides,
+        drawer_keys: BcrKeys,
+        timestamp: u64,
+    ) -> Result<BitcreditBill>;
+
+    /// executes the given bill action - if an idempotency key is given and the same action was
+    /// already executed on the bill with that key, the chain is returned without adding a block,
+    /// if it's still being executed with that key, the call fails with `InvalidOperation`
     async fn execute_bill_action(
         &self,
         bill_id: &str,
@@ -107,6 +299,75 @@ pub trait BillServiceApi: ServiceTraitBounds {
         signer_public_data: &IdentityPublicData,
         signer_keys: &BcrKeys,
         timestamp: u64,
+        idempotency_key: Option<String>,
+    ) -> Result<BillBlockchain>;
+
+    /// executes the given bill action for a company like `execute_bill_action`, but records the
+    /// given signatory of the company in the created blocks - the blocks are signed with the keys
+    /// of the local identity, so it's the only signatory, which is accepted
+    async fn execute_bill_action_as_signatory(
+        &self,
+        bill_id: &str,
+        bill_action: BillAction,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        acting_signatory_node_id: &str,
+        timestamp: u64,
+        idempotency_key: Option<String>,
+    ) -> Result<BillBlockchain>;
+
+    /// Encrypts the uploaded files with the bill's public key, saves them and records their
+    /// hashes in a new AttachFile block - only the current holder can attach files
+    async fn attach_files(
+        &self,
+        bill_id: &str,
+        file_upload_ids: &[String],
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<BillBlockchain>;
+
+    /// Runs the same validation as `execute_bill_action` for the given bill action, without
+    /// creating blocks, sending notifications, or touching the cache
+    async fn validate_bill_action(
+        &self,
+        bill_id: &str,
+        bill_action: BillAction,
+        signer_public_data: &IdentityPublicData,
+        timestamp: u64,
+    ) -> Result<()>;
+
+    /// Submits a quote of the mint for the open request to mint of the bill, which is valid until
+    /// the given expiry timestamp - only the mint the bill was requested to mint from can do this
+    async fn submit_mint_quote(
+        &self,
+        bill_id: &str,
+        sum: u64,
+        currency: &str,
+        expiry_timestamp: u64,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<BillBlockchain>;
+
+    /// Approves the open, not yet expired quote of the mint - only the requester of the mint can
+    /// do this
+    async fn approve_mint_quote(
+        &self,
+        bill_id: &str,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<BillBlockchain>;
+
+    /// Rejects the open quote of the mint, after which the mint can submit a new quote - only the
+    /// requester of the mint can do this
+    async fn reject_mint_quote(
+        &self,
+        bill_id: &str,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
     ) -> Result<BillBlockchain>;
 
     /// Check payment status of bills that are requested to pay and not expired and not paid yet, updating their
@@ -125,6 +386,15 @@ pub trait BillServiceApi: ServiceTraitBounds {
     /// steps after timeout.
     async fn check_bills_timeouts(&self, now: u64) -> Result<()>;
 
+    /// Sends a reminder to the holder of bills, which mature within the given lead time. The
+    /// reminder is only sent once per bill.
+    async fn check_bills_maturity_reminders(&self, now: u64, lead_time_seconds: u64) -> Result<()>;
+
+    /// Accepts the bills, which were queued to be accepted automatically, because they matched
+    /// an auto-accept rule of the drawee when they were received. The rules are checked again and
+    /// the accept action is fully validated, before it's executed
+    async fn process_auto_accepts(&self, now: u64) -> Result<()>;
+
     /// Returns previous endorseers of the bill to select from for Recourse
     async fn get_past_endorsees(
         &self,
@@ -132,6 +402,15 @@ pub trait BillServiceApi: ServiceTraitBounds {
         current_identity_node_id: &str,
     ) -> Result<Vec<PastEndorsee>>;
 
+    /// Checks if the given candidate is one of the previous endorsees of the bill, which can be
+    /// selected for Recourse
+    async fn is_past_holder(
+        &self,
+        bill_id: &str,
+        candidate_node_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<bool>;
+
     /// Returns previous payment requests of the given bill, where the user with the given node id
     /// was the financial beneficiary, with the metadata and outcomes
     async fn get_past_payments(
@@ -149,34 +428,166 @@ pub trait BillServiceApi: ServiceTraitBounds {
         current_identity_node_id: &str,
     ) -> Result<Vec<Endorsement>>;
 
+    /// Returns the number of endorsements of the bill, without resolving the endorsements
+    /// themselves - e.g. for list views. Matches `endorsements_count` of the full bill
+    async fn get_endorsement_count(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<u64>;
+
+    /// Returns every block of the bill's chain as a human-readable event, ordered by block height
+    async fn get_bill_history(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<Vec<BillHistoryEntry>>;
+
+    /// Reconstructs the event for the block at the given height (starting at 1) and sends it to
+    /// the given node again, e.g. if the recipient was offline past the relay's retention
+    async fn resend_bill_event(
+        &self,
+        bill_id: &str,
+        block_height: usize,
+        node_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<()>;
+
+    /// Returns the participants of the bill at the block at the given height (starting at 1),
+    /// who the event for the block wasn't sent to yet - the signer of the block is never
+    /// returned, since it created the block. Used to resend events only to those participants
+    async fn get_unnotified_participants(
+        &self,
+        bill_id: &str,
+        block_height: usize,
+    ) -> Result<Vec<ResolvedParticipant>>;
+
     async fn clear_bill_cache(&self) -> Result<()>;
+
+    /// Cross-checks the persisted bill ids, keys and chains and returns every bill, for which
+    /// they don't match up, e.g. keys without a chain - for support triage
+    async fn find_inconsistent_bills(&self) -> Result<Vec<BillInconsistency>>;
+
+    /// Manually puts the given bill on hold with the given reason - held bills are skipped by
+    /// the `check_bills_*` jobs until the hold is cleared
+    async fn set_bill_hold(
+        &self,
+        bill_id: &str,
+        reason: &str,
+        current_identity_node_id: &str,
+        timestamp: u64,
+    ) -> Result<()>;
+
+    /// Clears the hold of the given bill, so the `check_bills_*` jobs pick it up again
+    async fn clear_bill_hold(&self, bill_id: &str, current_identity_node_id: &str) -> Result<()>;
+
+    /// Archives the given bill for the given identity, hiding it from the default bill list and
+    /// the balances - this is scoped per node id and doesn't touch the chain
+    async fn archive_bill(&self, bill_id: &str, current_identity_node_id: &str) -> Result<()>;
+
+    /// Unarchives the given bill for the given identity
+    async fn unarchive_bill(&self, bill_id: &str, current_identity_node_id: &str) -> Result<()>;
+
+    /// Sets the private note of the given identity on the given bill, or removes it, if the
+    /// note is `None` or empty - the note is only stored locally and never part of the chain,
+    /// events, or exported bundles
+    async fn set_bill_note(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        note: Option<String>,
+    ) -> Result<()>;
+
+    /// Exports the whole chain, the keys and the attached files of the given bill as a signed,
+    /// versioned bundle, with the keys and files encrypted to the caller
+    async fn export_bill_bundle(&self, bill_id: &str, caller_keys: &BcrKeys) -> Result<Vec<u8>>;
+
+    /// Imports a bill from a bundle created with `export_bill_bundle` by the caller, validating
+    /// the chain and the attached files before persisting them and returning the bill id. If the
+    /// import fails, nothing of the bill is kept
+    async fn import_bill_bundle(&self, bundle: &[u8], caller_keys: &BcrKeys) -> Result<String>;
+
+    /// Exports a read-only view of the given bill with the whole chain and a view key, which can
+    /// decrypt the blocks, but can't be used to act on the bill, e.g. for an auditor. The caller
+    /// has to be a participant of the bill
+    async fn export_bill_view(&self, bill_id: &str, caller_keys: &BcrKeys) -> Result<BillView>;
+
+    /// Imports a read-only view created with `export_bill_view`, validating the chain before
+    /// persisting it and returning the bill id. Actions on a bill, of which only a view was
+    /// imported, fail validation
+    async fn import_bill_view(&self, view: BillView) -> Result<String>;
+
+    /// Returns the details of a bill, of which a read-only view was imported
+    async fn get_bill_view_detail(
+        &self,
+        bill_id: &str,
+        identity: &Identity,
+        current_timestamp: u64,
+    ) -> Result<BitcreditBillResult>;
+
+    /// Returns a compact, signed and URL-safe base64 encoded summary of the given bill, which is small
+    /// enough for a QR code, e.g. to share the bill at a point of sale. The caller has to be a
+    /// participant of the bill
+    async fn get_bill_qr_payload(&self, bill_id: &str, caller_keys: &BcrKeys) -> Result<String>;
+
+    /// Decodes a payload created with `get_bill_qr_payload` and returns its fields, if it's
+    /// validly signed by its signer
+    async fn parse_bill_qr_payload(&self, payload: &str) -> Result<BillQrPayload>;
+
+    /// Returns the display data (name, postal address) of the participants of the given bill, as
+    /// embedded in its blocks - e.g. to show names of participants, who aren't in the contacts.
+    /// The caller has to be a participant of the bill
+    async fn resolve_bill_participants(
+        &self,
+        bill_id: &str,
+        caller_keys: &BcrKeys,
+    ) -> Result<Vec<ResolvedParticipant>>;
+
+    /// Ingests a bill event, which was received outside of the subscription (e.g. via a shared
+    /// file, or a QR code), validating and persisting the chain the same way as received events
+    /// and returning the bill id. If the chain is already known, only missing blocks are added
+    async fn ingest_bill_event(&self, envelope: EventEnvelope) -> Result<String>;
+
+    /// Renders the in-app, email and push notification texts counterparties would receive for
+    /// the given action on the given bill in the given language, without sending anything
+    async fn preview_notification(
+        &self,
+        bill_id: &str,
+        action: &ActionType,
+        language: &str,
+    ) -> Result<NotificationPreview>;
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
     use crate::{
-        persistence,
+        external,
+        persistence::{self, bill::BillChainStoreApi},
         service::company_service::tests::get_baseline_company_data,
         tests::tests::{
             TEST_BILL_ID, TEST_PRIVATE_KEY_SECP, TEST_PUB_KEY_SECP, VALID_PAYMENT_ADDRESS_TESTNET,
             empty_address, empty_identity_public_data, identity_public_data_only_node_id,
             init_test_cfg,
         },
-        util,
+        util::{self, job::JobControl},
     };
+    use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
     use bcr_ebill_core::{
-        ValidationError,
+        Field, File, ValidationError,
         bill::{
-            BillAcceptanceStatus, BillPaymentStatus, BillRecourseStatus, BillSellStatus,
-            PastPaymentStatus, RecourseReason,
+            BillAcceptanceStatus, BillCurrentWaitingState, BillHold, BillInconsistency,
+            BillInconsistencyKind, BillPaymentStatus, BillRecourseStatus, BillSellStatus,
+            BillViewKey, FeeEstimate, FiatRate, PastPaymentStatus, QueuedAutoAccept,
+            RecourseReason, SettlementStatus,
         },
         blockchain::{
-            Blockchain,
+            self, BlockValidationError, Blockchain,
             bill::{
                 BillBlock, BillOpCode,
                 block::{
-                    BillEndorseBlockData, BillMintBlockData, BillOfferToSellBlockData,
+                    BillAcceptBlockData, BillCorrectDraweeBlockData, BillEndorseBlockData,
+                    BillMintBlockData, BillMintQuoteBlockData, BillOfferToSellBlockData,
                     BillRecourseReasonBlockData, BillRejectBlockData, BillRequestRecourseBlockData,
                     BillRequestToAcceptBlockData, BillRequestToPayBlockData, BillSellBlockData,
                     BillSignatoryBlockData,
@@ -184,14 +595,18 @@ pub mod tests {
             },
         },
         constants::{ACCEPT_DEADLINE_SECONDS, PAYMENT_DEADLINE_SECONDS, RECOURSE_DEADLINE_SECONDS},
-        notification::ActionType,
+        notification::{ActionType, BillEventType},
     };
+    use bcr_ebill_transport::{BillChainEventPayload, Event};
     use core::str;
     use mockall::predicate::{always, eq, function};
     use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
     use test_utils::{
-        accept_block, get_baseline_bill, get_baseline_cached_bill, get_baseline_identity, get_ctx,
-        get_genesis_chain, get_service, offer_to_sell_block, recourse_block, reject_accept_block,
+        MockBillContext, accept_block, acknowledge_block, bill_keys, cancel_offer_to_sell_block,
+        cancel_request_to_accept_block, get_baseline_bill, get_baseline_cached_bill,
+        get_baseline_identity, get_ctx, get_genesis_chain, get_service, mint_block,
+        mint_quote_block, offer_to_sell_block, recourse_block, reject_accept_block,
         reject_buy_block, reject_recourse_block, reject_to_pay_block, request_to_accept_block,
         request_to_pay_block, request_to_recourse_block, sell_block,
     };
@@ -261,26 +676,105 @@ pub mod tests {
     }
 
     #[tokio::test]
-    async fn get_search_bill() {
+    async fn get_company_bill_balances_baseline() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let company_node_id = BcrKeys::new().get_public_key();
+        let (_, (mut company, _)) = get_baseline_company_data();
+        // the baseline company shares its key with the baseline identity, so use a separate one
+        let company_id = BcrKeys::new().get_public_key();
+        company.id = company_id.clone();
+        company.signatories = vec![identity.identity.node_id.clone()];
 
+        // the company is payee, the signatory personally is drawee
         let mut bill1 = get_baseline_bill(TEST_BILL_ID);
-        bill1.issue_date = "2020-05-01".to_string();
         bill1.sum = 1000;
-        bill1.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill1.payee = identity_public_data_only_node_id(company_id.clone());
         let mut bill2 = get_baseline_bill("4321");
-        bill2.issue_date = "2030-05-01".to_string();
         bill2.sum = 2000;
-        bill2.drawee = identity_public_data_only_node_id(company_node_id.clone());
+        bill2.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+
+        ctx.company_store.expect_exists().returning(|_| true);
+        ctx.company_store
+            .expect_get()
+            .returning(move |_| Ok(company.clone()));
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![String::from(TEST_BILL_ID), String::from("4321")]));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == TEST_BILL_ID)
+            .returning(move |_| Ok(get_genesis_chain(Some(bill1.clone()))));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "4321")
+            .returning(move |_| Ok(get_genesis_chain(Some(bill2.clone()))));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
+
+        let res = get_service(ctx)
+            .get_company_bill_balances(&company_id, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        // the personal bill of the signatory isn't part of the company's balances
+        assert_eq!(res.as_ref().unwrap().payee.sum, "1000".to_string());
+        assert_eq!(res.as_ref().unwrap().payer.sum, "0".to_string());
+        assert_eq!(res.as_ref().unwrap().contingent.sum, "0".to_string());
+    }
+
+    #[tokio::test]
+    async fn get_company_bill_balances_fails_for_non_signatory() {
+        let mut ctx = get_ctx();
+        let (company_id, (company, _)) = get_baseline_company_data();
+
+        ctx.company_store.expect_exists().returning(|_| true);
+        ctx.company_store
+            .expect_get()
+            .returning(move |_| Ok(company.clone()));
+        // balances aren't computed for a non-signatory
+        ctx.bill_store.expect_get_ids().never();
+
+        let res = get_service(ctx)
+            .get_company_bill_balances(&company_id, &BcrKeys::new().get_public_key())
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::CallerMustBeSignatory))
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_company_bill_balances_fails_if_company_does_not_exist() {
+        let mut ctx = get_ctx();
+        ctx.company_store.expect_exists().returning(|_| false);
+
+        let res = get_service(ctx)
+            .get_company_bill_balances(TEST_PUB_KEY_SECP, TEST_PUB_KEY_SECP)
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn get_total_exposure_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let now = util::date::now().timestamp() as u64;
+
+        // payer, accepted and overdue
+        let mut bill1 = get_baseline_bill(TEST_BILL_ID);
+        bill1.sum = 1000;
+        bill1.maturity_date = "2020-01-01".to_string();
+        bill1.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        // payee, awaiting acceptance
+        let mut bill2 = get_baseline_bill("4321");
+        bill2.sum = 5000;
         bill2.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        bill2.payee.name = "hayek".to_string();
+        bill2.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        // contingent
         let mut bill3 = get_baseline_bill("9999");
-        bill3.issue_date = "2030-05-01".to_string();
         bill3.sum = 20000;
         bill3.drawer = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        bill3.payee = identity_public_data_only_node_id(company_node_id.clone());
         bill3.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
 
         ctx.bill_store.expect_get_ids().returning(|| {
@@ -290,48 +784,190 @@ pub mod tests {
                 String::from("9999"),
             ])
         });
-        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
             .withf(|id| id == TEST_BILL_ID)
-            .returning(move |_| Ok(get_genesis_chain(Some(bill1.clone()))));
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill1.clone()));
+                assert!(chain.try_add_block(accept_block(TEST_BILL_ID, chain.get_latest_block())));
+                Ok(chain)
+            });
         ctx.bill_blockchain_store
             .expect_get_chain()
             .withf(|id| id == "4321")
-            .returning(move |_| Ok(get_genesis_chain(Some(bill2.clone()))));
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill2.clone()));
+                assert!(chain.try_add_block(request_to_accept_block(
+                    "4321",
+                    chain.get_latest_block(),
+                    Some(now)
+                )));
+                Ok(chain)
+            });
         ctx.bill_blockchain_store
             .expect_get_chain()
             .withf(|id| id == "9999")
             .returning(move |_| Ok(get_genesis_chain(Some(bill3.clone()))));
+        ctx.bill_store.expect_exists().returning(|_| true);
+
         ctx.notification_service
             .expect_get_active_bill_notification()
             .returning(|_| None);
 
         let service = get_service(ctx);
-        let res_all_comp = service
-            .search_bills(
-                "sat",
-                &None,
-                None,
-                None,
-                &BillsFilterRole::All,
-                &company_node_id,
-            )
-            .await;
-        assert!(res_all_comp.is_ok());
-        assert_eq!(res_all_comp.as_ref().unwrap().len(), 2);
-        let res_all = service
-            .search_bills(
-                "sat",
-                &None,
-                None,
-                None,
-                &BillsFilterRole::All,
-                &identity.identity.node_id,
-            )
-            .await;
-        assert!(res_all.is_ok());
-        assert_eq!(res_all.as_ref().unwrap().len(), 3);
+
+        let res = service
+            .get_total_exposure(&identity.identity.node_id)
+            .await
+            .expect("works");
+        assert_eq!(res.balances.payer.sum, "1000".to_string());
+        assert_eq!(res.balances.payee.sum, "5000".to_string());
+        assert_eq!(res.balances.contingent.sum, "20000".to_string());
+        assert_eq!(res.net_position, "4000".to_string());
+        assert_eq!(res.accepted_count, 1);
+        assert_eq!(res.awaiting_acceptance_count, 1);
+        assert_eq!(res.overdue_count, 1);
+    }
+
+    fn setup_balance_with_fiat_ctx(node_id: &str) -> MockBillContext {
+        let mut ctx = get_ctx();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.sum = 15000;
+        bill.drawee = identity_public_data_only_node_id(node_id.to_owned());
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![String::from(TEST_BILL_ID)]));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
+        ctx
+    }
+
+    #[tokio::test]
+    async fn get_bill_balances_with_fiat_baseline() {
+        let identity = get_baseline_identity();
+        let mut ctx = setup_balance_with_fiat_ctx(&identity.identity.node_id);
+        ctx.fiat_rate_client
+            .expect_get_btc_rate()
+            .with(eq("eur"))
+            .returning(|_| {
+                Ok(FiatRate {
+                    fiat_code: "eur".to_string(),
+                    rate: 50000.0,
+                    timestamp: 1731593928,
+                })
+            });
+
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bill_balances_with_fiat("sat", "eur", &identity.identity.node_id)
+            .await
+            .expect("works");
+        assert_eq!(res.payer.sum, "15000".to_string());
+        assert_eq!(res.payer.fiat_sum, Some("7.50".to_string()));
+        assert_eq!(res.payee.fiat_sum, Some("0.00".to_string()));
+        assert_eq!(res.fiat_rate.as_ref().unwrap().rate, 50000.0);
+        assert_eq!(res.fiat_rate.as_ref().unwrap().timestamp, 1731593928);
+    }
+
+    #[tokio::test]
+    async fn get_bill_balances_with_fiat_returns_sat_if_rate_fails() {
+        let identity = get_baseline_identity();
+        let mut ctx = setup_balance_with_fiat_ctx(&identity.identity.node_id);
+        ctx.fiat_rate_client
+            .expect_get_btc_rate()
+            .returning(|code| Err(external::fiat::Error::RateNotFound(code.to_owned()).into()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bill_balances_with_fiat("sat", "eur", &identity.identity.node_id)
+            .await
+            .expect("works");
+        assert_eq!(res.payer.sum, "15000".to_string());
+        assert!(res.payer.fiat_sum.is_none());
+        assert!(res.fiat_rate.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_search_bill() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let company_node_id = BcrKeys::new().get_public_key();
+
+        let mut bill1 = get_baseline_bill(TEST_BILL_ID);
+        bill1.issue_date = "2020-05-01".to_string();
+        bill1.sum = 1000;
+        bill1.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let mut bill2 = get_baseline_bill("4321");
+        bill2.issue_date = "2030-05-01".to_string();
+        bill2.sum = 2000;
+        bill2.drawee = identity_public_data_only_node_id(company_node_id.clone());
+        bill2.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill2.payee.name = "hayek".to_string();
+        let mut bill3 = get_baseline_bill("9999");
+        bill3.issue_date = "2030-05-01".to_string();
+        bill3.sum = 20000;
+        bill3.drawer = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill3.payee = identity_public_data_only_node_id(company_node_id.clone());
+        bill3.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+
+        ctx.bill_store.expect_get_ids().returning(|| {
+            Ok(vec![
+                String::from(TEST_BILL_ID),
+                String::from("4321"),
+                String::from("9999"),
+            ])
+        });
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == TEST_BILL_ID)
+            .returning(move |_| Ok(get_genesis_chain(Some(bill1.clone()))));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "4321")
+            .returning(move |_| Ok(get_genesis_chain(Some(bill2.clone()))));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "9999")
+            .returning(move |_| Ok(get_genesis_chain(Some(bill3.clone()))));
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
+
+        let service = get_service(ctx);
+        let res_all_comp = service
+            .search_bills(
+                "sat",
+                &None,
+                None,
+                None,
+                &BillsFilterRole::All,
+                &BillsFilterStatus::All,
+                &company_node_id,
+            )
+            .await;
+        assert!(res_all_comp.is_ok());
+        assert_eq!(res_all_comp.as_ref().unwrap().len(), 2);
+        let res_all = service
+            .search_bills(
+                "sat",
+                &None,
+                None,
+                None,
+                &BillsFilterRole::All,
+                &BillsFilterStatus::All,
+                &identity.identity.node_id,
+            )
+            .await;
+        assert!(res_all.is_ok());
+        assert_eq!(res_all.as_ref().unwrap().len(), 3);
 
         let res_term = service
             .search_bills(
@@ -340,11 +976,45 @@ pub mod tests {
                 None,
                 None,
                 &BillsFilterRole::All,
+                &BillsFilterStatus::All,
                 &identity.identity.node_id,
             )
             .await;
         assert!(res_term.is_ok());
         assert_eq!(res_term.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            res_term.as_ref().unwrap()[0].last_op_code,
+            BillOpCode::Issue
+        );
+        assert_eq!(res_term.as_ref().unwrap()[0].block_height, 1);
+
+        let res_term_fuzzy = service
+            .search_bills(
+                "sat",
+                &Some(String::from("hayke")),
+                None,
+                None,
+                &BillsFilterRole::All,
+                &BillsFilterStatus::All,
+                &identity.identity.node_id,
+            )
+            .await;
+        assert!(res_term_fuzzy.is_ok());
+        assert_eq!(res_term_fuzzy.as_ref().unwrap().len(), 1);
+
+        let res_term_diacritics = service
+            .search_bills(
+                "sat",
+                &Some(String::from("Hayék")),
+                None,
+                None,
+                &BillsFilterRole::All,
+                &BillsFilterStatus::All,
+                &identity.identity.node_id,
+            )
+            .await;
+        assert!(res_term_diacritics.is_ok());
+        assert_eq!(res_term_diacritics.as_ref().unwrap().len(), 1);
 
         let from_ts = util::date::date_string_to_timestamp("2030-05-01", None).unwrap();
         let to_ts = util::date::date_string_to_timestamp("2030-05-30", None).unwrap();
@@ -355,6 +1025,7 @@ pub mod tests {
                 Some(from_ts as u64),
                 Some(to_ts as u64),
                 &BillsFilterRole::All,
+                &BillsFilterStatus::All,
                 &identity.identity.node_id,
             )
             .await;
@@ -368,6 +1039,7 @@ pub mod tests {
                 None,
                 None,
                 &BillsFilterRole::Payer,
+                &BillsFilterStatus::All,
                 &identity.identity.node_id,
             )
             .await;
@@ -381,6 +1053,7 @@ pub mod tests {
                 Some(from_ts as u64),
                 Some(to_ts as u64),
                 &BillsFilterRole::Payee,
+                &BillsFilterStatus::All,
                 &identity.identity.node_id,
             )
             .await;
@@ -388,6 +1061,118 @@ pub mod tests {
         assert_eq!(res_comb.as_ref().unwrap().len(), 1);
     }
 
+    #[tokio::test]
+    async fn search_bills_by_status() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let now = util::date::now().timestamp() as u64;
+
+        // the local identity is the drawee of all bills
+        let paid_bill = get_baseline_bill("paid");
+        let mut overdue_bill = get_baseline_bill("overdue");
+        overdue_bill.maturity_date = "2020-05-01".to_string();
+        let request_to_accept_bill = get_baseline_bill("reqToAccept");
+        let request_to_pay_bill = get_baseline_bill("reqToPay");
+        let unpaid_bill = get_baseline_bill("unpaid");
+
+        ctx.bill_store.expect_get_ids().returning(|| {
+            Ok(vec![
+                String::from("paid"),
+                String::from("overdue"),
+                String::from("reqToAccept"),
+                String::from("reqToPay"),
+                String::from("unpaid"),
+            ])
+        });
+        ctx.bill_store
+            .expect_is_paid()
+            .returning(|id| Ok(id == "paid"));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "paid")
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(paid_bill.clone()));
+                assert!(chain.try_add_block(request_to_pay_block(
+                    "paid",
+                    chain.get_latest_block(),
+                    Some(now - 10)
+                )));
+                Ok(chain)
+            });
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "overdue")
+            .returning(move |_| Ok(get_genesis_chain(Some(overdue_bill.clone()))));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "reqToAccept")
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(request_to_accept_bill.clone()));
+                assert!(chain.try_add_block(request_to_accept_block(
+                    "reqToAccept",
+                    chain.get_latest_block(),
+                    Some(now - 10)
+                )));
+                Ok(chain)
+            });
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "reqToPay")
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(request_to_pay_bill.clone()));
+                assert!(chain.try_add_block(request_to_pay_block(
+                    "reqToPay",
+                    chain.get_latest_block(),
+                    Some(now - 10)
+                )));
+                Ok(chain)
+            });
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == "unpaid")
+            .returning(move |_| Ok(get_genesis_chain(Some(unpaid_bill.clone()))));
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
+
+        let service = get_service(ctx);
+        let search = |status: BillsFilterStatus| {
+            let service = service.clone();
+            let node_id = identity.identity.node_id.clone();
+            async move {
+                let mut ids: Vec<String> = service
+                    .search_bills(
+                        "sat",
+                        &None,
+                        None,
+                        None,
+                        &BillsFilterRole::All,
+                        &status,
+                        &node_id,
+                    )
+                    .await
+                    .expect("search works")
+                    .into_iter()
+                    .map(|b| b.id)
+                    .collect();
+                ids.sort();
+                ids
+            }
+        };
+
+        assert_eq!(search(BillsFilterStatus::All).await.len(), 5);
+        assert_eq!(search(BillsFilterStatus::Paid).await, vec!["paid"]);
+        assert_eq!(
+            search(BillsFilterStatus::Unpaid).await,
+            vec!["overdue", "reqToAccept", "reqToPay", "unpaid"]
+        );
+        assert_eq!(search(BillsFilterStatus::Overdue).await, vec!["overdue"]);
+        assert_eq!(
+            search(BillsFilterStatus::AwaitingAction).await,
+            vec!["reqToAccept", "reqToPay"]
+        );
+    }
+
     #[tokio::test]
     async fn issue_bill_baseline() {
         let mut ctx = get_ctx();
@@ -403,6 +1188,10 @@ pub mod tests {
         ctx.file_upload_store
             .expect_save_attached_file()
             .returning(move |_, _, _| Ok(()));
+        // the file integrity check reads the saved file
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("test error"))));
         ctx.bill_store.expect_save_keys().returning(|_, _| Ok(()));
         ctx.bill_store
             .expect_save_bill_to_cache()
@@ -423,7 +1212,7 @@ pub mod tests {
         let bill = service
             .issue_new_bill(BillIssueData {
                 t: 2,
-                country_of_issuing: String::from("UK"),
+                country_of_issuing: String::from("GB"),
                 city_of_issuing: String::from("London"),
                 issue_date: String::from("2030-01-01"),
                 maturity_date: String::from("2030-04-01"),
@@ -438,85 +1227,475 @@ pub mod tests {
                 drawer_public_data: IdentityPublicData::new(drawer.identity).unwrap(),
                 drawer_keys: drawer.key_pair,
                 timestamp: 1731593928,
+                payment_deadline_seconds: Some(86400 * 14),
             })
             .await
             .unwrap();
 
         assert_eq!(bill.files.first().unwrap().name, expected_file_name);
+        assert_eq!(bill.payment_deadline_seconds, 86400 * 14);
     }
 
-    #[tokio::test]
-    async fn issue_bill_as_company() {
-        let mut ctx = get_ctx();
-        let expected_file_name = "invoice_00000000-0000-0000-0000-000000000000.pdf";
-        let file_bytes = String::from("hello world").as_bytes().to_vec();
+    fn get_baseline_bill_template(id: &str) -> BillTemplate {
+        let drawer = get_baseline_identity();
+        BillTemplate {
+            id: id.to_string(),
+            name: "monthly rent".to_string(),
+            t: 2,
+            country_of_issuing: String::from("GB"),
+            city_of_issuing: String::from("London"),
+            issue_date: String::from("2030-01-01"),
+            maturity_date: String::from("2030-04-01"),
+            drawee: BcrKeys::new().get_public_key(),
+            payee: BcrKeys::new().get_public_key(),
+            sum: String::from("100"),
+            currency: String::from("sat"),
+            country_of_payment: String::from("AT"),
+            city_of_payment: String::from("Vienna"),
+            language: String::from("en-UK"),
+            file_upload_ids: vec![],
+            drawer_public_data: IdentityPublicData::new(drawer.identity).unwrap(),
+            payment_deadline_seconds: None,
+        }
+    }
 
-        ctx.file_upload_store
-            .expect_read_temp_upload_file()
-            .returning(move |_| Ok((expected_file_name.to_string(), file_bytes.clone())));
-        ctx.file_upload_store
-            .expect_remove_temp_upload_folder()
-            .returning(|_| Ok(()));
-        ctx.file_upload_store
-            .expect_save_attached_file()
-            .returning(move |_, _, _| Ok(()));
+    fn get_ctx_for_issuing_from_template() -> MockBillContext {
+        let mut ctx = get_ctx();
         ctx.bill_store.expect_save_keys().returning(|_, _| Ok(()));
         ctx.bill_store
             .expect_save_bill_to_cache()
             .returning(|_, _| Ok(()));
-        // should send a bill is signed event
         ctx.notification_service
             .expect_send_bill_is_signed_event()
             .returning(|_| Ok(()));
+        ctx.bill_template_store
+            .expect_get()
+            .with(eq("template"))
+            .returning(|id| Ok(Some(get_baseline_bill_template(id))));
+        ctx
+    }
 
+    #[tokio::test]
+    async fn save_bill_template_creates_id() {
+        let mut ctx = get_ctx();
+        ctx.bill_template_store
+            .expect_save()
+            .withf(|template| !template.id.is_empty())
+            .returning(|_| Ok(()))
+            .times(1);
         let service = get_service(ctx);
 
-        let drawer = get_baseline_company_data();
-        let mut drawee = empty_identity_public_data();
-        drawee.node_id = BcrKeys::new().get_public_key();
-        let mut payee = empty_identity_public_data();
-        payee.node_id = BcrKeys::new().get_public_key();
+        let id = service
+            .save_bill_template(get_baseline_bill_template(""))
+            .await
+            .unwrap();
+        assert!(!id.is_empty());
+    }
+
+    #[tokio::test]
+    async fn issue_from_template_with_overrides() {
+        let service = get_service(get_ctx_for_issuing_from_template());
 
         let bill = service
-            .issue_new_bill(BillIssueData {
-                t: 2,
-                country_of_issuing: String::from("UK"),
-                city_of_issuing: String::from("London"),
-                issue_date: String::from("2030-01-01"),
-                maturity_date: String::from("2030-04-01"),
-                drawee: drawee.node_id,
-                payee: payee.node_id,
-                sum: String::from("100"),
-                currency: String::from("sat"),
-                country_of_payment: String::from("AT"),
-                city_of_payment: String::from("Vienna"),
-                language: String::from("en-UK"),
-                file_upload_ids: vec![TEST_BILL_ID.to_string()],
-                drawer_public_data: IdentityPublicData::from(drawer.1.0),
-                drawer_keys: BcrKeys::from_private_key(&drawer.1.1.private_key).unwrap(),
-                timestamp: 1731593928,
-            })
+            .issue_from_template(
+                "template",
+                BillTemplateOverrides {
+                    sum: Some(String::from("500")),
+                    issue_date: Some(String::from("2030-02-01")),
+                    maturity_date: Some(String::from("2030-05-01")),
+                    file_upload_ids: None,
+                },
+                get_baseline_identity().key_pair,
+                1731593928,
+            )
             .await
             .unwrap();
 
-        assert_eq!(bill.files.first().unwrap().name, expected_file_name);
-        assert_eq!(bill.drawer.node_id, drawer.0);
+        assert_eq!(bill.sum, 500);
+        assert_eq!(bill.issue_date, "2030-02-01".to_string());
+        assert_eq!(bill.maturity_date, "2030-05-01".to_string());
+        // the rest is taken from the template
+        assert_eq!(bill.currency, "sat".to_string());
+        assert_eq!(bill.city_of_payment, "Vienna".to_string());
+        assert!(bill.files.is_empty());
     }
 
     #[tokio::test]
-    async fn save_encrypt_open_decrypt_compare_hashes() {
+    async fn issue_from_template_fails_for_unknown_template() {
         let mut ctx = get_ctx();
-        let bill_id = "test_bill_id";
-        let file_name = "invoice_00000000-0000-0000-0000-000000000000.pdf";
-        let file_bytes = String::from("hello world").as_bytes().to_vec();
-        let expected_encrypted =
-            util::crypto::encrypt_ecies(&file_bytes, TEST_PUB_KEY_SECP).unwrap();
+        ctx.bill_template_store.expect_get().returning(|_| Ok(None));
+        let service = get_service(ctx);
 
-        ctx.file_upload_store
-            .expect_save_attached_file()
-            .with(always(), eq(bill_id), eq(file_name))
-            .times(1)
-            .returning(|_, _, _| Ok(()));
+        let res = service
+            .issue_from_template(
+                "unknown",
+                BillTemplateOverrides::default(),
+                get_baseline_identity().key_pair,
+                1731593928,
+            )
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn delete_bill_template_does_not_affect_issued_bills() {
+        let mut ctx = get_ctx_for_issuing_from_template();
+        // deleting only touches the template store - the chain and the cache of issued bills
+        // would fail the test with an unexpected call
+        ctx.bill_template_store
+            .expect_delete()
+            .with(eq("template"))
+            .returning(|_| Ok(()))
+            .times(1);
+        let service = get_service(ctx);
+
+        let bill = service
+            .issue_from_template(
+                "template",
+                BillTemplateOverrides::default(),
+                get_baseline_identity().key_pair,
+                1731593928,
+            )
+            .await
+            .unwrap();
+        service.delete_bill_template("template").await.unwrap();
+
+        assert_eq!(bill.sum, 100);
+        assert_eq!(bill.issue_date, "2030-01-01".to_string());
+        assert_eq!(bill.maturity_date, "2030-04-01".to_string());
+    }
+
+    struct MinSumValidator {
+        min_sum: u64,
+    }
+
+    impl ServiceTraitBounds for MinSumValidator {}
+
+    impl BillIssueValidator for MinSumValidator {
+        fn validate(&self, data: &BillIssueData) -> std::result::Result<(), ValidationError> {
+            match data.sum.parse::<u64>() {
+                Ok(sum) if sum >= self.min_sum => Ok(()),
+                _ => Err(ValidationError::IssuePolicyViolation(format!(
+                    "sum must be at least {}",
+                    self.min_sum
+                ))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn issue_bill_fails_if_rejected_by_issue_validator() {
+        let mut ctx = get_ctx();
+        // nothing is signed, or sent
+        ctx.bill_store.expect_save_keys().never();
+        ctx.notification_service
+            .expect_send_bill_is_signed_event()
+            .never();
+        let service =
+            get_service(ctx).with_issue_validator(Arc::new(MinSumValidator { min_sum: 1000 }));
+
+        let drawer = get_baseline_identity();
+        let res = service
+            .issue_new_bill(BillIssueData {
+                t: 2,
+                country_of_issuing: String::from("GB"),
+                city_of_issuing: String::from("London"),
+                issue_date: String::from("2030-01-01"),
+                maturity_date: String::from("2030-04-01"),
+                drawee: BcrKeys::new().get_public_key(),
+                payee: BcrKeys::new().get_public_key(),
+                sum: String::from("100"),
+                currency: String::from("sat"),
+                country_of_payment: String::from("AT"),
+                city_of_payment: String::from("Vienna"),
+                language: String::from("en-UK"),
+                file_upload_ids: vec![],
+                drawer_public_data: IdentityPublicData::new(drawer.identity).unwrap(),
+                drawer_keys: drawer.key_pair,
+                timestamp: 1731593928,
+                payment_deadline_seconds: None,
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::IssuePolicyViolation(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn issue_bill_fails_with_too_long_payment_deadline() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_save_keys().never();
+        let service = get_service(ctx);
+
+        let drawer = get_baseline_identity();
+        let res = service
+            .issue_new_bill(BillIssueData {
+                t: 2,
+                country_of_issuing: String::from("GB"),
+                city_of_issuing: String::from("London"),
+                issue_date: String::from("2030-01-01"),
+                maturity_date: String::from("2030-04-01"),
+                drawee: BcrKeys::new().get_public_key(),
+                payee: BcrKeys::new().get_public_key(),
+                sum: String::from("100"),
+                currency: String::from("sat"),
+                country_of_payment: String::from("AT"),
+                city_of_payment: String::from("Vienna"),
+                language: String::from("en-UK"),
+                file_upload_ids: vec![],
+                drawer_public_data: IdentityPublicData::new(drawer.identity).unwrap(),
+                drawer_keys: drawer.key_pair,
+                timestamp: 1731593928,
+                payment_deadline_seconds: Some(u64::MAX),
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidPaymentDeadline))
+        ));
+    }
+
+    #[tokio::test]
+    async fn issue_bill_as_company() {
+        let mut ctx = get_ctx();
+        let expected_file_name = "invoice_00000000-0000-0000-0000-000000000000.pdf";
+        let file_bytes = String::from("hello world").as_bytes().to_vec();
+
+        ctx.file_upload_store
+            .expect_read_temp_upload_file()
+            .returning(move |_| Ok((expected_file_name.to_string(), file_bytes.clone())));
+        ctx.file_upload_store
+            .expect_remove_temp_upload_folder()
+            .returning(|_| Ok(()));
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .returning(move |_, _, _| Ok(()));
+        // the file integrity check reads the saved file
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("test error"))));
+        ctx.bill_store.expect_save_keys().returning(|_, _| Ok(()));
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        // should send a bill is signed event
+        ctx.notification_service
+            .expect_send_bill_is_signed_event()
+            .returning(|_| Ok(()));
+
+        let service = get_service(ctx);
+
+        let drawer = get_baseline_company_data();
+        let mut drawee = empty_identity_public_data();
+        drawee.node_id = BcrKeys::new().get_public_key();
+        let mut payee = empty_identity_public_data();
+        payee.node_id = BcrKeys::new().get_public_key();
+
+        let bill = service
+            .issue_new_bill(BillIssueData {
+                t: 2,
+                country_of_issuing: String::from("GB"),
+                city_of_issuing: String::from("London"),
+                issue_date: String::from("2030-01-01"),
+                maturity_date: String::from("2030-04-01"),
+                drawee: drawee.node_id,
+                payee: payee.node_id,
+                sum: String::from("100"),
+                currency: String::from("sat"),
+                country_of_payment: String::from("AT"),
+                city_of_payment: String::from("Vienna"),
+                language: String::from("en-UK"),
+                file_upload_ids: vec![TEST_BILL_ID.to_string()],
+                drawer_public_data: IdentityPublicData::from(drawer.1.0),
+                drawer_keys: BcrKeys::from_private_key(&drawer.1.1.private_key).unwrap(),
+                timestamp: 1731593928,
+                payment_deadline_seconds: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(bill.files.first().unwrap().name, expected_file_name);
+        assert_eq!(bill.drawer.node_id, drawer.0);
+        assert_eq!(bill.payment_deadline_seconds, PAYMENT_DEADLINE_SECONDS);
+    }
+
+    /// issues a bill with one uploaded file per given size, with the test config limits of 3 files
+    /// and 1000 bytes
+    async fn issue_bill_with_files(file_sizes: &[usize]) -> Result<BitcreditBill> {
+        let mut ctx = get_ctx();
+        let sizes: HashMap<String, usize> = file_sizes
+            .iter()
+            .enumerate()
+            .map(|(idx, size)| (format!("upload_{idx}"), *size))
+            .collect();
+        ctx.file_upload_store
+            .expect_read_temp_upload_file()
+            .returning(move |id| Ok((format!("{id}.pdf"), vec![0; sizes[id]])));
+        ctx.file_upload_store
+            .expect_remove_temp_upload_folder()
+            .returning(|_| Ok(()));
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .returning(move |_, _, _| Ok(()));
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("test error"))));
+        ctx.bill_store.expect_save_keys().returning(|_, _| Ok(()));
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.notification_service
+            .expect_send_bill_is_signed_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let drawer = get_baseline_identity();
+        service
+            .issue_new_bill(BillIssueData {
+                t: 2,
+                country_of_issuing: String::from("GB"),
+                city_of_issuing: String::from("London"),
+                issue_date: String::from("2030-01-01"),
+                maturity_date: String::from("2030-04-01"),
+                drawee: BcrKeys::new().get_public_key(),
+                payee: BcrKeys::new().get_public_key(),
+                sum: String::from("100"),
+                currency: String::from("sat"),
+                country_of_payment: String::from("AT"),
+                city_of_payment: String::from("Vienna"),
+                language: String::from("en-UK"),
+                file_upload_ids: (0..file_sizes.len())
+                    .map(|idx| format!("upload_{idx}"))
+                    .collect(),
+                drawer_public_data: IdentityPublicData::new(drawer.identity).unwrap(),
+                drawer_keys: drawer.key_pair,
+                timestamp: 1731593928,
+                payment_deadline_seconds: None,
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn issue_bill_with_max_files() {
+        let bill = issue_bill_with_files(&[10, 10, 10]).await.unwrap();
+        assert_eq!(bill.files.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn issue_bill_fails_with_too_many_files() {
+        assert!(matches!(
+            issue_bill_with_files(&[10, 10, 10, 10]).await,
+            Err(Error::Validation(ValidationError::TooManyFiles(3)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn issue_bill_with_max_total_attachment_bytes() {
+        let bill = issue_bill_with_files(&[500, 500]).await.unwrap();
+        assert_eq!(bill.files.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn issue_bill_fails_with_too_big_attachments() {
+        assert!(matches!(
+            issue_bill_with_files(&[500, 501]).await,
+            Err(Error::Validation(ValidationError::AttachmentsTooBig(1000)))
+        ));
+    }
+
+    fn fiat_bill_issue_data() -> BillIssueData {
+        let drawer = get_baseline_identity();
+        BillIssueData {
+            t: 2,
+            country_of_issuing: String::from("GB"),
+            city_of_issuing: String::from("London"),
+            issue_date: String::from("2030-01-01"),
+            maturity_date: String::from("2030-04-01"),
+            drawee: BcrKeys::new().get_public_key(),
+            payee: BcrKeys::new().get_public_key(),
+            sum: String::from("7.50"),
+            currency: String::from("EUR"),
+            country_of_payment: String::from("AT"),
+            city_of_payment: String::from("Vienna"),
+            language: String::from("en-UK"),
+            file_upload_ids: vec![],
+            drawer_public_data: IdentityPublicData::new(drawer.identity).unwrap(),
+            drawer_keys: drawer.key_pair,
+            timestamp: 1731593928,
+            payment_deadline_seconds: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn issue_fiat_bill_records_sat_equivalent() {
+        let mut ctx = get_ctx();
+        ctx.fiat_rate_client
+            .expect_get_btc_rate()
+            .with(eq("EUR"))
+            .returning(|_| {
+                Ok(FiatRate {
+                    fiat_code: "eur".to_string(),
+                    rate: 50000.0,
+                    timestamp: 1731593900,
+                })
+            });
+        ctx.bill_store.expect_save_keys().returning(|_, _| Ok(()));
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.notification_service
+            .expect_send_bill_is_signed_event()
+            .returning(|_| Ok(()));
+
+        let service = get_service(ctx);
+
+        let bill = service
+            .issue_new_bill(fiat_bill_issue_data())
+            .await
+            .unwrap();
+
+        assert_eq!(bill.currency, "sat".to_string());
+        assert_eq!(bill.sum, 15000);
+        let fiat_amount = bill.fiat_amount.expect("fiat amount is set");
+        assert_eq!(fiat_amount.currency, "eur".to_string());
+        assert_eq!(fiat_amount.sum, "7.50".to_string());
+        assert_eq!(fiat_amount.btc_rate, 50000.0);
+        assert_eq!(fiat_amount.rate_timestamp, 1731593900);
+    }
+
+    #[tokio::test]
+    async fn issue_fiat_bill_fails_if_no_rate_could_be_fetched() {
+        let mut ctx = get_ctx();
+        ctx.fiat_rate_client
+            .expect_get_btc_rate()
+            .returning(|code| Err(external::fiat::Error::RateNotFound(code.to_owned()).into()));
+        ctx.bill_store.expect_save_keys().never();
+
+        let service = get_service(ctx);
+
+        let result = service.issue_new_bill(fiat_bill_issue_data()).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Validation(ValidationError::FiatRateUnavailable))
+        ));
+    }
+
+    #[tokio::test]
+    async fn save_encrypt_open_decrypt_compare_hashes() {
+        let mut ctx = get_ctx();
+        let bill_id = "test_bill_id";
+        let file_name = "invoice_00000000-0000-0000-0000-000000000000.pdf";
+        let file_bytes = String::from("hello world").as_bytes().to_vec();
+        let expected_encrypted =
+            util::crypto::encrypt_ecies(&file_bytes, TEST_PUB_KEY_SECP).unwrap();
+
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .with(always(), eq(bill_id), eq(file_name))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
 
         ctx.file_upload_store
             .expect_open_attached_file()
@@ -592,660 +1771,994 @@ pub mod tests {
     }
 
     #[tokio::test]
-    async fn get_bill_keys_propagates_errors() {
+    async fn verify_bill_chain_baseline() {
         let mut ctx = get_ctx();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store
-            .expect_get_keys()
-            .returning(|_| Err(persistence::Error::Io(std::io::Error::other("test error"))));
+        ctx.bill_blockchain_store
+            .expect_get_blocks()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| {
+                let mut bill = get_baseline_bill(TEST_BILL_ID);
+                bill.drawer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+                let mut chain = get_genesis_chain(Some(bill));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    None,
+                )));
+                Ok(chain.blocks().to_owned())
+            });
         let service = get_service(ctx);
-        assert!(service.get_bill_keys("test").await.is_err());
+
+        let res = service.verify_bill_chain(TEST_BILL_ID).await;
+        assert!(res.is_ok());
+        let report = res.unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.valid_blocks, 2);
+        assert_eq!(report.first_invalid_block_height, None);
+        assert_eq!(report.reason, None);
     }
 
     #[tokio::test]
-    async fn get_bills_baseline() {
+    async fn verify_bill_chain_reports_tampered_block() {
         let mut ctx = get_ctx();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
-
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| {
-                let chain = get_genesis_chain(Some(bill.clone()));
-                Ok(chain)
+            .expect_get_blocks()
+            .returning(|_| {
+                let mut blocks = get_genesis_chain(None).blocks().to_owned();
+                blocks[0].timestamp += 1;
+                Ok(blocks)
             });
-        ctx.bill_store
-            .expect_get_ids()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
-        ctx.bill_store.expect_exists().returning(|_| true);
+        let service = get_service(ctx);
 
-        ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+        let report = service.verify_bill_chain(TEST_BILL_ID).await.unwrap();
+        assert!(!report.is_valid());
+        assert_eq!(report.valid_blocks, 0);
+        assert_eq!(report.first_invalid_block_height, Some(1));
+        assert_eq!(report.reason, Some(BlockValidationError::InvalidHash));
+    }
 
+    #[tokio::test]
+    async fn verify_bill_chain_fails_if_bill_does_not_exist() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| false);
         let service = get_service(ctx);
 
-        let res = service
-            .get_bills(&get_baseline_identity().identity.node_id)
-            .await;
-        assert!(res.is_ok());
-        let returned_bills = res.unwrap();
-        assert!(returned_bills.len() == 1);
-        assert_eq!(returned_bills[0].id, TEST_BILL_ID.to_string());
+        assert!(matches!(
+            service.verify_bill_chain(TEST_BILL_ID).await,
+            Err(Error::NotFound)
+        ));
+    }
+
+    /// Returns a valid chain of three blocks with the local identity as drawer and drawee, and the
+    /// same chain with the block in the middle corrupted
+    fn get_chain_with_corrupted_middle_block() -> (Vec<BillBlock>, Vec<BillBlock>) {
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = identity_public_data_only_node_id(get_baseline_identity().identity.node_id);
+        bill.drawee = identity_public_data_only_node_id(get_baseline_identity().identity.node_id);
+        let mut chain = get_genesis_chain(Some(bill));
+        assert!(chain.try_add_block(request_to_accept_block(
+            TEST_BILL_ID,
+            chain.get_latest_block(),
+            None
+        )));
+        assert!(chain.try_add_block(reject_accept_block(TEST_BILL_ID, chain.get_latest_block())));
+        let valid_blocks = chain.blocks().to_owned();
+        let mut corrupted_blocks = valid_blocks.clone();
+        corrupted_blocks[1].data = util::base58_encode("corrupted".as_bytes());
+        (valid_blocks, corrupted_blocks)
     }
 
     #[tokio::test]
-    async fn get_bills_baseline_from_cache() {
+    async fn get_detail_reports_height_of_corrupted_block() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut chain_bill = get_baseline_bill("4321");
-        chain_bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        let mut bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
-        // make sure the local identity is part of the bill
-        bill.participants.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        bill.participants
-            .all_participant_node_ids
-            .push(identity.identity.node_id.clone());
-
+        let (_, corrupted_blocks) = get_chain_with_corrupted_middle_block();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let chain = get_genesis_chain(Some(chain_bill.clone()));
-                Ok(chain)
-            })
-            .times(1);
-        ctx.bill_store
-            .expect_get_bills_from_cache()
-            .returning(move |_| Ok(vec![bill.clone()]));
-        ctx.bill_store
-            .expect_get_ids()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
-        ctx.bill_store.expect_exists().returning(|_| true);
-
-        ctx.notification_service
-            .expect_get_active_bill_notifications()
-            .returning(|_| HashMap::new());
-
+            .returning(|_| Err(persistence::Error::NoBillBlock));
+        ctx.bill_blockchain_store
+            .expect_get_blocks()
+            .returning(move |_| Ok(corrupted_blocks.clone()));
         let service = get_service(ctx);
 
         let res = service
-            .get_bills(&get_baseline_identity().identity.node_id)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                1731593928,
+            )
             .await;
-        assert!(res.is_ok());
-        let returned_bills = res.unwrap();
-        assert!(returned_bills.len() == 2);
+        assert!(matches!(
+            res,
+            Err(Error::Blockchain(blockchain::Error::CorruptedBlock(2)))
+        ));
     }
 
     #[tokio::test]
-    async fn get_bills_baseline_from_cache_with_payment_expiration() {
+    async fn get_detail_up_to_corrupted_block_returns_valid_prefix() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut chain_bill = get_baseline_bill("4321");
-        chain_bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        let mut bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
-        // make sure the local identity is part of the bill
-        bill.participants.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        bill.participants
-            .all_participant_node_ids
-            .push(identity.identity.node_id.clone());
-        bill.status.payment = BillPaymentStatus {
-            time_of_request_to_pay: Some(1531593928), // more than 2 days before request
-            requested_to_pay: true,
-            paid: false,
-            request_to_pay_timed_out: false,
-            rejected_to_pay: false,
-        };
+        let (_, corrupted_blocks) = get_chain_with_corrupted_middle_block();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_blocks()
+            .returning(move |_| Ok(corrupted_blocks.clone()));
+        ctx.bill_store.expect_save_bill_to_cache().never();
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
+        let service = get_service(ctx);
+
+        let res = service
+            .get_detail_up_to_corrupted_block(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                1731593928,
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.id, TEST_BILL_ID.to_string());
+        // the request to accept is in the corrupted block
+        assert!(!res.status.acceptance.requested_to_accept);
+    }
 
+    #[tokio::test]
+    async fn get_detail_up_to_corrupted_block_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let (_, corrupted_blocks) = get_chain_with_corrupted_middle_block();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| {
-                let chain = get_genesis_chain(Some(chain_bill.clone()));
-                Ok(chain)
-            })
-            .times(2);
-        ctx.bill_store
-            .expect_get_bills_from_cache()
-            .returning(move |_| Ok(vec![bill.clone()]));
-        ctx.bill_store
-            .expect_get_ids()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+            .expect_get_blocks()
+            .returning(move |_| Ok(corrupted_blocks.clone()));
+        let service = get_service(ctx);
+
+        let res = service
+            .get_detail_up_to_corrupted_block(
+                TEST_BILL_ID,
+                &get_baseline_identity().identity,
+                "some_other_node_id",
+                1731593928,
+            )
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn repair_bill_chain_replaces_corrupted_blocks_from_relay() {
+        let mut ctx = get_ctx();
+        let (valid_blocks, corrupted_blocks) = get_chain_with_corrupted_middle_block();
+        let fetched_blocks = valid_blocks.clone();
+        let expected_blocks = valid_blocks[1..].to_vec();
         ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_blocks()
+            .returning(move |_| Ok(corrupted_blocks.clone()));
+        ctx.notification_service
+            .expect_fetch_bill_blocks()
+            .with(eq(TEST_BILL_ID))
+            .returning(move |_| Ok(fetched_blocks.clone()));
+        ctx.bill_blockchain_store
+            .expect_replace_blocks_from()
+            .with(
+                eq(TEST_BILL_ID),
+                function(move |b: &[BillBlock]| b == expected_blocks.as_slice()),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store.expect_add_block().never();
+        let service = get_service(ctx);
+
+        let report = service.repair_bill_chain(TEST_BILL_ID).await.unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.valid_blocks, 3);
+    }
 
+    #[tokio::test]
+    async fn repair_bill_chain_keeps_chain_if_relay_has_no_valid_block() {
+        let mut ctx = get_ctx();
+        let (_, corrupted_blocks) = get_chain_with_corrupted_middle_block();
+        let fetched_blocks = corrupted_blocks.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_blocks()
+            .returning(move |_| Ok(corrupted_blocks.clone()));
         ctx.notification_service
-            .expect_get_active_bill_notifications()
-            .returning(|_| HashMap::new());
+            .expect_fetch_bill_blocks()
+            .returning(move |_| Ok(fetched_blocks.clone()));
+        ctx.bill_blockchain_store
+            .expect_replace_blocks_from()
+            .never();
+        ctx.bill_blockchain_store.expect_add_block().never();
+        let service = get_service(ctx);
+
+        let report = service.repair_bill_chain(TEST_BILL_ID).await.unwrap();
+        assert!(!report.is_valid());
+        assert_eq!(report.valid_blocks, 1);
+        assert_eq!(report.first_invalid_block_height, Some(2));
+    }
 
+    #[tokio::test]
+    async fn repair_bill_chain_does_nothing_for_valid_chain() {
+        let mut ctx = get_ctx();
+        let (valid_blocks, _) = get_chain_with_corrupted_middle_block();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_blocks()
+            .returning(move |_| Ok(valid_blocks.clone()));
+        ctx.notification_service.expect_fetch_bill_blocks().never();
         let service = get_service(ctx);
 
-        let res = service
-            .get_bills(&get_baseline_identity().identity.node_id)
-            .await;
-        assert!(res.is_ok());
-        let returned_bills = res.unwrap();
-        assert!(returned_bills.len() == 2);
+        let report = service.repair_bill_chain(TEST_BILL_ID).await.unwrap();
+        assert!(report.is_valid());
+        assert_eq!(report.valid_blocks, 3);
     }
 
     #[tokio::test]
-    async fn get_bills_baseline_company() {
+    async fn find_inconsistent_bills_reports_each_kind() {
         let mut ctx = get_ctx();
-        let company_node_id = BcrKeys::new().get_public_key();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_get_ids().returning(|| {
+            Ok(vec![
+                TEST_BILL_ID.to_string(),
+                "chain_only".to_string(),
+                "invalid_chain".to_string(),
+            ])
+        });
+        ctx.bill_store.expect_get_key_ids().returning(|| {
+            Ok(vec![
+                TEST_BILL_ID.to_string(),
+                "keys_only".to_string(),
+                "invalid_chain".to_string(),
+            ])
+        });
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(|_| Ok(get_genesis_chain(None)));
+            .withf(|id| id == "invalid_chain")
+            .returning(|_| Err(persistence::Error::NoBillBlock));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .withf(|id| id == TEST_BILL_ID)
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service.find_inconsistent_bills().await;
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            vec![
+                BillInconsistency {
+                    bill_id: "chain_only".to_string(),
+                    kind: BillInconsistencyKind::ChainWithoutKeys,
+                },
+                BillInconsistency {
+                    bill_id: "invalid_chain".to_string(),
+                    kind: BillInconsistencyKind::InvalidChain,
+                },
+                BillInconsistency {
+                    bill_id: "keys_only".to_string(),
+                    kind: BillInconsistencyKind::KeysWithoutChain,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_inconsistent_bills_consistent_store() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
         ctx.bill_store
             .expect_get_ids()
             .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
-        ctx.bill_store.expect_exists().returning(|_| true);
-
-        ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
-
+        ctx.bill_store
+            .expect_get_key_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         let service = get_service(ctx);
 
-        let res = service
-            .get_bills(&get_baseline_identity().identity.node_id)
-            .await;
+        let res = service.find_inconsistent_bills().await;
         assert!(res.is_ok());
-        let returned_bills = res.unwrap();
-        assert!(returned_bills.len() == 1);
-        assert_eq!(returned_bills[0].id, TEST_BILL_ID.to_string());
+        assert!(res.unwrap().is_empty());
+    }
 
-        let res = service.get_bills(&company_node_id).await;
-        assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().len(), 0);
+    #[tokio::test]
+    async fn get_bill_keys_propagates_errors() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store
+            .expect_get_keys()
+            .returning(|_| Err(persistence::Error::Io(std::io::Error::other("test error"))));
+        let service = get_service(ctx);
+        assert!(service.get_bill_keys("test").await.is_err());
     }
 
     #[tokio::test]
-    async fn get_bills_req_to_pay() {
+    async fn get_bills_baseline() {
         let mut ctx = get_ctx();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
-                let now = util::date::now().timestamp() as u64;
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = BillBlock::create_block_for_request_to_pay(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestToPayBlockData {
-                        requester: IdentityPublicData::new(get_baseline_identity().identity)
-                            .unwrap()
-                            .into(),
-                        currency: "sat".to_string(),
-                        signatory: None,
-                        signing_timestamp: now,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now,
-                )
-                .unwrap();
-                assert!(chain.try_add_block(req_to_pay_block));
+                let chain = get_genesis_chain(Some(bill.clone()));
                 Ok(chain)
             });
-        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_store
             .expect_get_ids()
             .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
         ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_store.expect_exists().returning(|_| true);
+
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
             .returning(|_| None);
 
-        let res = get_service(ctx)
-            .get_bills(&get_baseline_identity().identity.node_id)
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills(&get_baseline_identity().identity.node_id, false)
             .await;
         assert!(res.is_ok());
         let returned_bills = res.unwrap();
         assert!(returned_bills.len() == 1);
         assert_eq!(returned_bills[0].id, TEST_BILL_ID.to_string());
-        assert!(returned_bills[0].status.payment.requested_to_pay);
-        assert!(returned_bills[0].status.payment.paid);
     }
 
-    #[tokio::test]
-    async fn get_bills_empty_for_no_bills() {
-        let mut ctx = get_ctx();
-        ctx.bill_store.expect_get_ids().returning(|| Ok(vec![]));
-        let res = get_service(ctx)
-            .get_bills(&get_baseline_identity().identity.node_id)
-            .await;
-        assert!(res.is_ok());
-        assert!(res.unwrap().is_empty());
+    /// Delegates to the given store, but yields before returning a chain - the earlier a bill is
+    /// in the list, the more often, so chains of later bills are loaded first
+    struct ReorderingBillChainStore {
+        inner: Arc<dyn BillChainStoreApi>,
+    }
+
+    #[async_trait]
+    impl BillChainStoreApi for ReorderingBillChainStore {
+        async fn get_latest_block(&self, id: &str) -> bcr_ebill_persistence::Result<BillBlock> {
+            self.inner.get_latest_block(id).await
+        }
+        async fn add_block(
+            &self,
+            id: &str,
+            block: &BillBlock,
+        ) -> bcr_ebill_persistence::Result<()> {
+            self.inner.add_block(id, block).await
+        }
+        async fn get_chain(&self, id: &str) -> bcr_ebill_persistence::Result<BillBlockchain> {
+            let position: usize = id.trim_start_matches("bill_").parse().unwrap_or(0);
+            for _ in position..40 {
+                tokio::task::yield_now().await;
+            }
+            self.inner.get_chain(id).await
+        }
+        async fn get_blocks(&self, id: &str) -> bcr_ebill_persistence::Result<Vec<BillBlock>> {
+            self.inner.get_blocks(id).await
+        }
+        async fn remove_blocks_from(
+            &self,
+            id: &str,
+            block_id: u64,
+        ) -> bcr_ebill_persistence::Result<()> {
+            self.inner.remove_blocks_from(id, block_id).await
+        }
+        async fn replace_blocks_from(
+            &self,
+            id: &str,
+            blocks: &[BillBlock],
+        ) -> bcr_ebill_persistence::Result<()> {
+            self.inner.replace_blocks_from(id, blocks).await
+        }
     }
 
     #[tokio::test]
-    async fn get_detail_bill_baseline() {
+    async fn get_bills_keeps_bill_id_order_when_loading_chains_concurrently() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
+        let bill_ids: Vec<String> = (0..40).map(|i| format!("bill_{i}")).collect();
+
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |id| {
+                let mut bill = get_baseline_bill(id);
+                bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+                Ok(get_genesis_chain(Some(bill)))
+            });
+        let ids = bill_ids.clone();
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(move || Ok(ids.clone()));
+        ctx.bill_store.expect_exists().returning(|_| true);
+
+        let mut service = get_service(ctx);
+        service.blockchain_store = Arc::new(ReorderingBillChainStore {
+            inner: service.blockchain_store.clone(),
+        });
+
+        let res = service
+            .get_bills(&get_baseline_identity().identity.node_id, false)
+            .await;
+        assert!(res.is_ok());
+        let returned_bill_ids: Vec<String> = res.unwrap().into_iter().map(|b| b.id).collect();
+        assert_eq!(returned_bill_ids, bill_ids);
+    }
+
+    #[tokio::test]
+    async fn get_bills_by_maturity_filters_on_maturity_date() {
+        let mut ctx = get_ctx();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |id| {
+                let mut bill = get_baseline_bill(id);
+                bill.maturity_date = match id {
+                    "before" => "2099-09-30",
+                    "start" => "2099-10-01",
+                    "inside" => "2099-10-15",
+                    "end" => "2099-10-31",
+                    "after" => "2099-11-01",
+                    _ => "15.10.2099",
+                }
+                .to_string();
+                Ok(get_genesis_chain(Some(bill)))
+            });
+        ctx.bill_store.expect_get_ids().returning(|| {
+            Ok(["before", "start", "inside", "end", "after", "malformed"]
+                .iter()
+                .map(|id| id.to_string())
+                .collect())
+        });
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let service = get_service(ctx);
+        let node_id = get_baseline_identity().identity.node_id;
+        let from = util::date::date_string_to_timestamp("2099-10-01", None).unwrap();
+        let to = util::date::date_string_to_timestamp("2099-10-31", None).unwrap();
+
+        let res = service
+            .get_bills_by_maturity(&node_id, Some(from), Some(to))
+            .await
+            .unwrap();
+        let ids: Vec<String> = res.into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec!["start", "inside", "end"]);
+
+        // open ranges
+        let res = service
+            .get_bills_by_maturity(&node_id, Some(from), None)
+            .await
+            .unwrap();
+        let ids: Vec<String> = res.into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec!["start", "inside", "end", "after"]);
+        let res = service
+            .get_bills_by_maturity(&node_id, None, Some(to))
+            .await
+            .unwrap();
+        let ids: Vec<String> = res.into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec!["before", "start", "inside", "end"]);
+
+        // malformed maturity dates are excluded, even without a range
+        let res = service
+            .get_bills_by_maturity(&node_id, None, None)
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 5);
+        assert!(!res.iter().any(|b| b.id == "malformed"));
+    }
+
+    #[tokio::test]
+    async fn get_bills_excludes_archived_bills_by_default() {
+        let mut ctx = get_ctx();
+        let node_id = get_baseline_identity().identity.node_id;
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store
+            .expect_get_archived_bill_ids()
+            .with(eq(node_id.clone()))
+            .times(1)
+            .returning(|_| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.notification_service
             .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
             .returning(|_| None);
 
-        let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                1731593928,
-            )
-            .await;
+        let service = get_service(ctx);
+
+        let res = service.get_bills(&node_id, false).await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(!res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(!res.as_ref().unwrap().status.payment.paid);
-        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
+        assert!(res.unwrap().is_empty());
+
+        // archived bills are returned if requested, without checking the archive
+        let res_archived = service.get_bills(&node_id, true).await;
+        assert!(res_archived.is_ok());
+        assert_eq!(res_archived.as_ref().unwrap().len(), 1);
+        assert_eq!(res_archived.unwrap()[0].id, TEST_BILL_ID.to_string());
     }
 
     #[tokio::test]
-    async fn get_detail_bill_baseline_from_cache() {
+    async fn get_bills_baseline_from_cache() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
+        let mut chain_bill = get_baseline_bill("4321");
+        chain_bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
         let mut bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
         // make sure the local identity is part of the bill
-        bill.participants.drawee = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        let drawee_node_id = bill.participants.drawee.node_id.clone();
+        bill.participants.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
         bill.participants
             .all_participant_node_ids
-            .push(drawee_node_id.clone());
-        ctx.bill_store.expect_exists().returning(|_| true);
+            .push(identity.identity.node_id.clone());
+
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let chain = get_genesis_chain(Some(chain_bill.clone()));
+                Ok(chain)
+            })
+            .times(1);
         ctx.bill_store
-            .expect_get_bill_from_cache()
-            .returning(move |_| Ok(Some(bill.clone())));
-        ctx.bill_blockchain_store.expect_get_chain().never();
+            .expect_get_bills_from_cache()
+            .returning(move |_| Ok(vec![bill.clone()]));
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_store.expect_exists().returning(|_| true);
+
         ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+            .expect_get_active_bill_notifications()
+            .returning(|_| HashMap::new());
 
-        let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                1731593928,
-            )
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills(&get_baseline_identity().identity.node_id, false)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(!res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(!res.as_ref().unwrap().status.payment.paid);
-        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
+        let returned_bills = res.unwrap();
+        assert!(returned_bills.len() == 2);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_baseline_from_cache_with_payment_expiration() {
+    async fn get_bills_by_participant_baseline() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut chain_bill = get_baseline_bill(TEST_BILL_ID);
-        chain_bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        // the current identity and the counterparty are participants
         let mut bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
-        // make sure the local identity is part of the bill
-        bill.participants.drawee = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        let drawee_node_id = bill.participants.drawee.node_id.clone();
+        bill.participants.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
         bill.participants
             .all_participant_node_ids
-            .push(drawee_node_id.clone());
-        bill.status.payment = BillPaymentStatus {
-            time_of_request_to_pay: Some(1531593928), // more than 2 days before request
-            requested_to_pay: true,
-            paid: false,
-            request_to_pay_timed_out: false,
-            rejected_to_pay: false,
-        };
-        ctx.bill_store.expect_exists().returning(|_| true);
+            .push(identity.identity.node_id.clone());
+        bill.participants
+            .all_participant_node_ids
+            .push("counterparty".to_string());
+        // only the counterparty is a participant
+        let mut other_bill = get_baseline_cached_bill("4321".to_string());
+        other_bill
+            .participants
+            .all_participant_node_ids
+            .push("counterparty".to_string());
+
         ctx.bill_store
-            .expect_get_bill_from_cache()
-            .returning(move |_| Ok(Some(bill.clone())));
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(chain_bill.clone()))))
-            .times(1);
+            .expect_get_bill_ids_by_participant()
+            .with(eq("counterparty"))
+            .returning(|_| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
+        ctx.bill_store
+            .expect_get_unindexed_bill_ids()
+            .returning(|| Ok(vec![]));
+        ctx.bill_store
+            .expect_get_bills_from_cache()
+            .returning(move |_| Ok(vec![bill.clone(), other_bill.clone()]));
         ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+            .expect_get_active_bill_notifications()
+            .returning(|_| HashMap::new());
 
-        let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                1731593928,
-            )
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills_by_participant("counterparty", &identity.identity.node_id)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(!res.as_ref().unwrap().status.payment.paid);
-        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
+        let returned_bills = res.unwrap();
+        assert_eq!(returned_bills.len(), 1);
+        assert_eq!(returned_bills[0].id, TEST_BILL_ID.to_string());
     }
 
     #[tokio::test]
-    async fn get_detail_bill_baseline_error_from_cache() {
+    async fn get_bills_grouped_lists_shared_bill_for_each_node_id() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
+        let identity_node_id = get_baseline_identity().identity.node_id;
+        let company_node_id = "company".to_string();
+        // both the personal identity and the company are participants
+        let mut shared_bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        shared_bill.participants.all_participant_node_ids =
+            vec![identity_node_id.clone(), company_node_id.clone()];
+        // only the personal identity is a participant
+        let mut personal_bill = get_baseline_cached_bill("4321".to_string());
+        personal_bill.participants.all_participant_node_ids = vec![identity_node_id.clone()];
+
         ctx.bill_store
-            .expect_get_bill_from_cache()
-            .returning(move |_| Err(persistence::Error::Io(std::io::Error::other("test error"))));
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
+        ctx.bill_store
+            .expect_get_bills_from_cache()
+            .times(1)
+            .returning(move |_| Ok(vec![shared_bill.clone(), personal_bill.clone()]));
         ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+            .expect_get_active_bill_notifications()
+            .returning(|_| HashMap::new());
 
-        let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                1731593928,
-            )
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills_grouped(&[identity_node_id.clone(), company_node_id.clone()])
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(!res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(!res.as_ref().unwrap().status.payment.paid);
-        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
+        let grouped = res.unwrap();
+        assert_eq!(grouped.len(), 2);
+        let personal_bills = grouped.get(&identity_node_id).unwrap();
+        assert_eq!(personal_bills.len(), 2);
+        assert!(personal_bills.iter().any(|b| b.id == TEST_BILL_ID));
+        assert!(personal_bills.iter().any(|b| b.id == "4321"));
+        let company_bills = grouped.get(&company_node_id).unwrap();
+        assert_eq!(company_bills.len(), 1);
+        assert_eq!(company_bills[0].id, TEST_BILL_ID);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_fails_for_non_participant() {
+    async fn get_bills_by_participant_calculates_unindexed_bills() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_store.expect_exists().returning(|_| true);
+        bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        let drawee_node_id = bill.drawee.node_id.clone();
+
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store
+            .expect_get_bill_ids_by_participant()
+            .returning(|_| Ok(vec![]));
+        ctx.bill_store
+            .expect_get_unindexed_bill_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+            .expect_get_active_bill_notifications()
+            .returning(|_| HashMap::new());
 
-        let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &BcrKeys::new().get_public_key(),
-                1731593928,
-            )
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills_by_participant(&drawee_node_id, &identity.identity.node_id)
             .await;
-        assert!(res.is_err());
+        assert!(res.is_ok());
+        let returned_bills = res.unwrap();
+        assert_eq!(returned_bills.len(), 1);
+        assert_eq!(returned_bills[0].id, TEST_BILL_ID.to_string());
+
+        let res = service
+            .get_bills_by_participant("unrelated", &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        assert!(res.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn get_detail_waiting_for_offer_to_sell() {
+    async fn get_bills_baseline_from_cache_with_payment_expiration() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
+        let mut chain_bill = get_baseline_bill("4321");
+        chain_bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        let mut bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        // make sure the local identity is part of the bill
+        bill.participants.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        bill.participants
+            .all_participant_node_ids
+            .push(identity.identity.node_id.clone());
+        bill.status.payment = BillPaymentStatus {
+            time_of_request_to_pay: Some(1531593928), // more than 2 days before request
+            requested_to_pay: true,
+            paid: false,
+            paid_sum: 0,
+            request_to_pay_timed_out: false,
+            rejected_to_pay: false,
+            rejected_to_pay_reason: None,
+            external_payment_reference: None,
+        };
+
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
-                    None,
-                )));
+                let chain = get_genesis_chain(Some(chain_bill.clone()));
                 Ok(chain)
-            });
+            })
+            .times(2);
+        ctx.bill_store
+            .expect_get_bills_from_cache()
+            .returning(move |_| Ok(vec![bill.clone()]));
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_store.expect_exists().returning(|_| true);
+
         ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+            .expect_get_active_bill_notifications()
+            .returning(|_| HashMap::new());
 
-        let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                1731593928,
-            )
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills(&get_baseline_identity().identity.node_id, false)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
-        assert!(!res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
-        assert!(!res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_some());
-        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        let returned_bills = res.unwrap();
+        assert!(returned_bills.len() == 2);
     }
 
     #[tokio::test]
-    async fn get_detail_waiting_for_offer_to_sell_and_sell() {
+    async fn get_bills_baseline_company() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
+        let company_node_id = BcrKeys::new().get_public_key();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &bill.drawee,
-                    None,
-                )));
-                assert!(chain.try_add_block(sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &bill.drawee,
-                )));
-                Ok(chain)
-            });
+            .returning(|_| Ok(get_genesis_chain(None)));
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store.expect_exists().returning(|_| true);
+
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
             .returning(|_| None);
 
-        let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                1731593928,
-            )
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills(&get_baseline_identity().identity.node_id, false)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
-        assert!(!res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
-        assert!(!res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert_eq!(
-            res.as_ref()
-                .unwrap()
-                .participants
-                .endorsee
-                .as_ref()
-                .unwrap()
-                .node_id,
-            identity.identity.node_id
-        );
-        assert!(res.as_ref().unwrap().status.redeemed_funds_available); // caller is endorsee
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        let returned_bills = res.unwrap();
+        assert!(returned_bills.len() == 1);
+        assert_eq!(returned_bills[0].id, TEST_BILL_ID.to_string());
+
+        let res = service.get_bills(&company_node_id, false).await;
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().len(), 0);
     }
 
     #[tokio::test]
-    async fn get_detail_waiting_for_offer_to_sell_and_expire() {
+    async fn get_bills_req_to_pay() {
         let mut ctx = get_ctx();
-        let now = util::date::now().timestamp() as u64;
-        let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
+                let now = util::date::now().timestamp() as u64;
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
+                let req_to_pay_block = BillBlock::create_block_for_request_to_pay(
+                    TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &bill.drawee,
-                    // expired
-                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
-                )));
+                    &BillRequestToPayBlockData {
+                        requester: IdentityPublicData::new(get_baseline_identity().identity)
+                            .unwrap()
+                            .into(),
+                        currency: "sat".to_string(),
+                        signatory: None,
+                        signing_timestamp: now,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    None,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(req_to_pay_block));
                 Ok(chain)
             });
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
             .returning(|_| None);
 
         let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                now,
-            )
+            .get_bills(&get_baseline_identity().identity.node_id, false)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
-        assert!(res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
-        assert!(!res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        let returned_bills = res.unwrap();
+        assert!(returned_bills.len() == 1);
+        assert_eq!(returned_bills[0].id, TEST_BILL_ID.to_string());
+        assert!(returned_bills[0].status.payment.requested_to_pay);
+        assert!(returned_bills[0].status.payment.paid);
     }
 
     #[tokio::test]
-    async fn get_detail_waiting_for_offer_to_sell_and_reject() {
+    async fn get_bills_empty_for_no_bills() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        let now = util::date::now().timestamp() as u64;
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &bill.drawee,
-                    // expired
-                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
-                )));
-                assert!(
-                    chain.try_add_block(reject_buy_block(TEST_BILL_ID, chain.get_latest_block(),))
-                );
-                Ok(chain)
-            });
-        ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
-
+        ctx.bill_store.expect_get_ids().returning(|| Ok(vec![]));
         let res = get_service(ctx)
-            .get_detail(
-                TEST_BILL_ID,
-                &identity.identity,
-                &identity.identity.node_id,
-                now,
-            )
+            .get_bills(&get_baseline_identity().identity.node_id, false)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
-        assert!(!res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
-        assert!(res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert!(res.unwrap().is_empty());
     }
 
-    #[tokio::test]
-    async fn get_detail_bill_req_to_recourse() {
+    fn awaiting_others_ctx(
+        build_chain: impl Fn(BitcreditBill) -> BillBlockchain + Send + Sync + 'static,
+    ) -> MockBillContext {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = request_to_recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
-                    None,
-                );
-                assert!(chain.try_add_block(req_to_pay_block));
-                Ok(chain)
-            });
+            .returning(move |_| Ok(build_chain(bill.clone())));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
+        ctx
+    }
+
+    #[tokio::test]
+    async fn get_bills_awaiting_others_request_to_accept() {
+        let now = util::date::now().timestamp() as u64;
+        let ctx = awaiting_others_ctx(move |bill| {
+            let mut chain = get_genesis_chain(Some(bill.clone()));
+            assert!(chain.try_add_block(request_to_accept_block(
+                &bill.id,
+                chain.get_latest_block(),
+                Some(now),
+            )));
+            chain
+        });
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bills_awaiting_others(&get_baseline_identity().identity.node_id, now)
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, TEST_BILL_ID.to_string());
+        assert_eq!(res[0].last_op_code, BillOpCode::RequestToAccept);
+
+        // after the deadline, the request isn't waiting anymore
+        let res = service
+            .get_bills_awaiting_others(
+                &get_baseline_identity().identity.node_id,
+                now + ACCEPT_DEADLINE_SECONDS + 1,
+            )
+            .await
+            .unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_bills_awaiting_others_excludes_expired_request_to_accept() {
+        let ctx = awaiting_others_ctx(|bill| {
+            let mut chain = get_genesis_chain(Some(bill.clone()));
+            assert!(chain.try_add_block(request_to_accept_block(
+                &bill.id,
+                chain.get_latest_block(),
+                None,
+            )));
+            chain
+        });
+
+        let res = get_service(ctx)
+            .get_bills_awaiting_others(
+                &get_baseline_identity().identity.node_id,
+                util::date::now().timestamp() as u64,
+            )
+            .await
+            .unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_bills_awaiting_others_request_to_pay() {
+        let ctx = awaiting_others_ctx(req_to_pay_chain);
+
+        let res = get_service(ctx)
+            .get_bills_awaiting_others(
+                &get_baseline_identity().identity.node_id,
+                util::date::now().timestamp() as u64,
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].last_op_code, BillOpCode::RequestToPay);
+    }
+
+    #[tokio::test]
+    async fn get_bills_awaiting_others_excludes_rejected_request_to_pay() {
+        let ctx = awaiting_others_ctx(|bill| {
+            let mut chain = req_to_pay_chain(bill.clone());
+            assert!(chain.try_add_block(reject_to_pay_block(&bill.id, chain.get_latest_block())));
+            chain
+        });
+
+        let res = get_service(ctx)
+            .get_bills_awaiting_others(
+                &get_baseline_identity().identity.node_id,
+                util::date::now().timestamp() as u64,
+            )
+            .await
+            .unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_bills_awaiting_others_offer_to_sell() {
+        let now = util::date::now().timestamp() as u64;
+        let ctx = awaiting_others_ctx(move |bill| {
+            let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+            let mut chain = get_genesis_chain(Some(bill.clone()));
+            assert!(chain.try_add_block(offer_to_sell_block(
+                &bill.id,
+                chain.get_latest_block(),
+                &buyer,
+                Some(now),
+            )));
+            chain
+        });
+
+        let res = get_service(ctx)
+            .get_bills_awaiting_others(&get_baseline_identity().identity.node_id, now)
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].last_op_code, BillOpCode::OfferToSell);
+    }
+
+    #[tokio::test]
+    async fn get_bills_awaiting_others_empty_without_requests() {
+        let ctx = awaiting_others_ctx(|bill| get_genesis_chain(Some(bill)));
+
+        let res = get_service(ctx)
+            .get_bills_awaiting_others(
+                &get_baseline_identity().identity.node_id,
+                util::date::now().timestamp() as u64,
+            )
+            .await
+            .unwrap();
+        assert!(res.is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_detail_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1265,53 +2778,31 @@ pub mod tests {
             res.as_ref().unwrap().participants.drawee.node_id,
             drawee_node_id
         );
-        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .request_to_recourse_timed_out
-        );
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .rejected_request_to_recourse
-        );
-        assert!(res.as_ref().unwrap().current_waiting_state.is_some());
+        assert!(!res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(!res.as_ref().unwrap().status.payment.paid);
         assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Active
+        );
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_recourse_recoursed() {
+    async fn get_detail_bill_baseline_from_cache() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
+        let mut bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        // make sure the local identity is part of the bill
+        bill.participants.drawee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        let drawee_node_id = bill.participants.drawee.node_id.clone();
+        bill.participants
+            .all_participant_node_ids
+            .push(drawee_node_id.clone());
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = request_to_recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
-                    None,
-                );
-                assert!(chain.try_add_block(req_to_pay_block));
-                assert!(chain.try_add_block(recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &identity_public_data_only_node_id(bill.drawee.node_id.clone())
-                )));
-                Ok(chain)
-            });
+        ctx.bill_store
+            .expect_get_bill_from_cache()
+            .returning(move |_| Ok(Some(bill.clone())));
+        ctx.bill_blockchain_store.expect_get_chain().never();
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1331,52 +2822,42 @@ pub mod tests {
             res.as_ref().unwrap().participants.drawee.node_id,
             drawee_node_id
         );
-        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .request_to_recourse_timed_out
-        );
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .rejected_request_to_recourse
-        );
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.redeemed_funds_available); // caller is endorsee
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert!(!res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(!res.as_ref().unwrap().status.payment.paid);
+        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_recourse_rejected() {
+    async fn get_detail_bill_baseline_from_cache_with_payment_expiration() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
+        let mut chain_bill = get_baseline_bill(TEST_BILL_ID);
+        chain_bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let mut bill = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        // make sure the local identity is part of the bill
+        bill.participants.drawee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        let drawee_node_id = bill.participants.drawee.node_id.clone();
+        bill.participants
+            .all_participant_node_ids
+            .push(drawee_node_id.clone());
+        bill.status.payment = BillPaymentStatus {
+            time_of_request_to_pay: Some(1531593928), // more than 2 days before request
+            requested_to_pay: true,
+            paid: false,
+            paid_sum: 0,
+            request_to_pay_timed_out: false,
+            rejected_to_pay: false,
+            rejected_to_pay_reason: None,
+            external_payment_reference: None,
+        };
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store
+            .expect_get_bill_from_cache()
+            .returning(move |_| Ok(Some(bill.clone())));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = request_to_recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
-                    None,
-                );
-                assert!(chain.try_add_block(req_to_pay_block));
-                assert!(chain.try_add_block(reject_recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                )));
-                Ok(chain)
-            });
+            .returning(move |_| Ok(get_genesis_chain(Some(chain_bill.clone()))))
+            .times(1);
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1396,48 +2877,38 @@ pub mod tests {
             res.as_ref().unwrap().participants.drawee.node_id,
             drawee_node_id
         );
-        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .request_to_recourse_timed_out
-        );
-        assert!(
-            res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .rejected_request_to_recourse
-        );
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert!(!res.as_ref().unwrap().status.payment.paid);
+        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_recourse_expired() {
+    async fn get_detail_bill_recalculates_and_refreshes_stale_cache_entry() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        let now = util::date::now().timestamp() as u64;
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        // the store bypasses cache entries, which are behind the chain
+        ctx.bill_store
+            .expect_get_bill_from_cache()
+            .returning(|_| Ok(None));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = request_to_recourse_block(
+                assert!(chain.try_add_block(request_to_accept_block(
                     TEST_BILL_ID,
                     chain.get_latest_block(),
-                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
-                    Some(now - RECOURSE_DEADLINE_SECONDS * 2),
-                );
-                assert!(chain.try_add_block(req_to_pay_block));
+                    None
+                )));
                 Ok(chain)
             });
+        // the entry is refreshed at the height of the chain
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .withf(|id, bill| id == TEST_BILL_ID && bill.status.block_height == 2)
+            .returning(|_, _| Ok(()))
+            .times(1);
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1448,59 +2919,48 @@ pub mod tests {
                 TEST_BILL_ID,
                 &identity.identity,
                 &identity.identity.node_id,
-                now,
+                1731593928,
             )
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
-        assert!(
-            res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .request_to_recourse_timed_out
-        );
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .recourse
-                .rejected_request_to_recourse
-        );
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(res.as_ref().unwrap().status.block_height, 2);
+        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_pay() {
+    async fn diff_bill_cache_reports_stale_fields() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
         let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        // the cache entry is stale - it's behind the chain and claims the bill is paid
+        ctx.bill_store
+            .expect_get_bill_from_cache()
+            .returning(move |id| {
+                let mut cached_bill = get_baseline_cached_bill(id.to_owned());
+                cached_bill.participants.drawee =
+                    identity_public_data_only_node_id(drawee_node_id.clone());
+                cached_bill.status.payment.paid = true;
+                Ok(Some(cached_bill))
+            });
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block =
-                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
-                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(chain.try_add_block(request_to_accept_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    None
+                )));
                 Ok(chain)
             });
-        ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+        // the cache is not refreshed
+        ctx.bill_store.expect_save_bill_to_cache().never();
 
         let res = get_service(ctx)
-            .get_detail(
+            .diff_bill_cache(
                 TEST_BILL_ID,
                 &identity.identity,
                 &identity.identity.node_id,
@@ -1508,51 +2968,40 @@ pub mod tests {
             )
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        let diffs = res.unwrap();
+        let diff = |field: &str| diffs.iter().find(|d| d.field == field).cloned();
         assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
+            diff("status.block_height"),
+            Some(FieldDiff {
+                field: "status.block_height".to_string(),
+                cached: "1".to_string(),
+                current: "2".to_string(),
+            })
         );
-        assert!(!res.as_ref().unwrap().status.payment.paid);
-        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .payment
-                .request_to_pay_timed_out
+        assert_eq!(
+            diff("status.payment.paid"),
+            Some(FieldDiff {
+                field: "status.payment.paid".to_string(),
+                cached: "true".to_string(),
+                current: "false".to_string(),
+            })
         );
-        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_some());
-        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert!(diff("status.acceptance.requested_to_accept").is_some());
+        assert!(diff("participants.drawee").is_none());
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_pay_paid() {
+    async fn diff_bill_cache_is_empty_without_cache_entry() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block =
-                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
-                assert!(chain.try_add_block(req_to_pay_block));
-                Ok(chain)
-            });
-        ctx.notification_service
-            .expect_get_active_bill_notification()
-            .with(eq(TEST_BILL_ID))
-            .returning(|_| None);
+        ctx.bill_store
+            .expect_get_bill_from_cache()
+            .returning(|_| Ok(None));
+        ctx.bill_blockchain_store.expect_get_chain().never();
 
         let res = get_service(ctx)
-            .get_detail(
+            .diff_bill_cache(
                 TEST_BILL_ID,
                 &identity.identity,
                 &identity.identity.node_id,
@@ -1560,48 +3009,23 @@ pub mod tests {
             )
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.payment.paid);
-        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .payment
-                .request_to_pay_timed_out
-        );
-        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(!res.as_ref().unwrap().status.redeemed_funds_available); // caller not payee
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert!(res.unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_pay_rejected() {
+    async fn get_detail_bill_baseline_error_from_cache() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
         let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store
+            .expect_get_bill_from_cache()
+            .returning(move |_| Err(persistence::Error::Io(std::io::Error::other("test error"))));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block =
-                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
-                assert!(chain.try_add_block(req_to_pay_block));
-                assert!(
-                    chain
-                        .try_add_block(reject_to_pay_block(TEST_BILL_ID, chain.get_latest_block()))
-                );
-                Ok(chain)
-            });
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1621,42 +3045,21 @@ pub mod tests {
             res.as_ref().unwrap().participants.drawee.node_id,
             drawee_node_id
         );
+        assert!(!res.as_ref().unwrap().status.payment.requested_to_pay);
         assert!(!res.as_ref().unwrap().status.payment.paid);
-        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .payment
-                .request_to_pay_timed_out
-        );
-        assert!(res.as_ref().unwrap().status.payment.rejected_to_pay);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_pay_rejected_but_paid() {
+    async fn get_detail_bill_fails_for_non_participant() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block =
-                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
-                assert!(chain.try_add_block(req_to_pay_block));
-                assert!(
-                    chain
-                        .try_add_block(reject_to_pay_block(TEST_BILL_ID, chain.get_latest_block()))
-                );
-                Ok(chain)
-            });
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1666,54 +3069,29 @@ pub mod tests {
             .get_detail(
                 TEST_BILL_ID,
                 &identity.identity,
-                &identity.identity.node_id,
+                &BcrKeys::new().get_public_key(),
                 1731593928,
             )
             .await;
-        assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
-        assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.payment.paid);
-        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .payment
-                .request_to_pay_timed_out
-        );
-        assert!(res.as_ref().unwrap().status.payment.rejected_to_pay);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert!(res.is_err());
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_pay_expired() {
+    async fn get_detail_bill_flags_hold() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let now = util::date::now().timestamp() as u64;
-        bill.maturity_date =
-            util::date::format_date_string(util::date::seconds(now - PAYMENT_DEADLINE_SECONDS * 2));
-        let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_get_hold().returning(|_| {
+            Ok(Some(BillHold {
+                reason: "disputed".to_string(),
+                timestamp: 1731593928,
+            }))
+        });
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = request_to_pay_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
-                );
-                assert!(chain.try_add_block(req_to_pay_block));
-                Ok(chain)
-            });
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1724,102 +3102,109 @@ pub mod tests {
                 TEST_BILL_ID,
                 &identity.identity,
                 &identity.identity.node_id,
-                now,
+                1731593928,
             )
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
         assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(!res.as_ref().unwrap().status.payment.paid);
-        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(
-            res.as_ref()
-                .unwrap()
-                .status
-                .payment
-                .request_to_pay_timed_out
+            res.as_ref().unwrap().status.hold,
+            Some(BillHold {
+                reason: "disputed".to_string(),
+                timestamp: 1731593928,
+            })
         );
-        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_pay_expired_but_paid() {
+    async fn get_detail_bill_includes_note_of_current_identity() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
-        let now = util::date::now().timestamp() as u64;
+        let payee_node_id = bill.payee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        let node_id = identity.identity.node_id.clone();
+        ctx.bill_store
+            .expect_get_note()
+            .returning(move |id, note_node_id| {
+                if id == TEST_BILL_ID && note_node_id == node_id {
+                    Ok(Some("called customer".to_string()))
+                } else {
+                    Ok(None)
+                }
+            });
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = request_to_pay_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
-                );
-                assert!(chain.try_add_block(req_to_pay_block));
-                Ok(chain)
-            });
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
             .returning(|_| None);
+        let service = get_service(ctx);
 
-        let res = get_service(ctx)
+        let res = service
             .get_detail(
                 TEST_BILL_ID,
                 &identity.identity,
                 &identity.identity.node_id,
-                now,
+                1731593928,
             )
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
         assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
-        );
-        assert!(res.as_ref().unwrap().status.payment.paid);
-        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .payment
-                .request_to_pay_timed_out
+            res.as_ref().unwrap().data.note,
+            Some("called customer".to_string())
         );
-        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
-        assert!(res.as_ref().unwrap().status.has_requested_funds);
+
+        // the note is scoped per identity
+        let res_payee = service
+            .get_detail(TEST_BILL_ID, &identity.identity, &payee_node_id, 1731593928)
+            .await;
+        assert!(res_payee.is_ok());
+        assert_eq!(res_payee.as_ref().unwrap().data.note, None);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_accept() {
+    async fn get_detail_bill_flags_tampered_files() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let drawee_node_id = bill.drawee.node_id.clone();
+        let file_bytes = String::from("hello world").as_bytes().to_vec();
+        bill.files = vec![
+            File {
+                name: "valid.pdf".to_string(),
+                hash: util::sha256_hash(&file_bytes),
+            },
+            File {
+                name: "tampered.pdf".to_string(),
+                hash: util::sha256_hash(&file_bytes),
+            },
+            File {
+                name: "not_available.pdf".to_string(),
+                hash: util::sha256_hash(&file_bytes),
+            },
+        ];
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block =
-                    request_to_accept_block(TEST_BILL_ID, chain.get_latest_block(), None);
-                assert!(chain.try_add_block(req_to_pay_block));
-                Ok(chain)
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .with(eq(TEST_BILL_ID), eq("valid.pdf"))
+            .returning(move |_, _| {
+                Ok(util::crypto::encrypt_ecies(&file_bytes, TEST_PUB_KEY_SECP).unwrap())
+            });
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .with(eq(TEST_BILL_ID), eq("tampered.pdf"))
+            .returning(|_, _| {
+                Ok(util::crypto::encrypt_ecies("tampered".as_bytes(), TEST_PUB_KEY_SECP).unwrap())
             });
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .with(eq(TEST_BILL_ID), eq("not_available.pdf"))
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("test error"))));
         ctx.notification_service
             .expect_get_active_bill_notification()
             .with(eq(TEST_BILL_ID))
@@ -1834,41 +3219,85 @@ pub mod tests {
             )
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(res.as_ref().unwrap().data.files.len(), 3);
         assert_eq!(
-            res.as_ref().unwrap().participants.drawee.node_id,
-            drawee_node_id
+            res.as_ref().unwrap().data.tampered_files,
+            vec!["tampered.pdf".to_string()]
         );
-        assert!(!res.as_ref().unwrap().status.acceptance.accepted);
-        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .acceptance
-                .request_to_accept_timed_out
-        );
-        assert!(!res.as_ref().unwrap().status.acceptance.rejected_to_accept);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_accept_accepted() {
+    async fn get_tampered_files_decrypts_files_only_once() {
+        let mut ctx = get_ctx();
+        let file_bytes = String::from("hello world").as_bytes().to_vec();
+        let files = vec![
+            File {
+                name: "saved.pdf".to_string(),
+                hash: util::sha256_hash(&file_bytes),
+            },
+            File {
+                name: "tampered.pdf".to_string(),
+                hash: util::sha256_hash(&file_bytes),
+            },
+        ];
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .returning(|_, _, _| Ok(()));
+        // the saved file is never read, the other one only on the first check
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .with(eq(TEST_BILL_ID), eq("saved.pdf"))
+            .never();
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .with(eq(TEST_BILL_ID), eq("tampered.pdf"))
+            .times(1)
+            .returning(|_, _| {
+                Ok(util::crypto::encrypt_ecies("tampered".as_bytes(), TEST_PUB_KEY_SECP).unwrap())
+            });
+        let service = get_service(ctx);
+        service
+            .encrypt_and_save_uploaded_file(
+                "saved.pdf",
+                &file_bytes,
+                TEST_BILL_ID,
+                TEST_PUB_KEY_SECP,
+            )
+            .await
+            .unwrap();
+
+        let bill_keys = BillKeys {
+            private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+            public_key: TEST_PUB_KEY_SECP.to_owned(),
+        };
+        for _ in 0..2 {
+            assert_eq!(
+                service
+                    .get_tampered_files(TEST_BILL_ID, &files, &bill_keys)
+                    .await,
+                vec!["tampered.pdf".to_string()]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn get_detail_waiting_for_offer_to_sell() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
         let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block =
-                    request_to_accept_block(TEST_BILL_ID, chain.get_latest_block(), None);
-                assert!(chain.try_add_block(req_to_pay_block));
-                assert!(chain.try_add_block(accept_block(TEST_BILL_ID, chain.get_latest_block())));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
+                    None,
+                )));
                 Ok(chain)
             });
         ctx.notification_service
@@ -1890,40 +3319,60 @@ pub mod tests {
             res.as_ref().unwrap().participants.drawee.node_id,
             drawee_node_id
         );
-        assert!(res.as_ref().unwrap().status.acceptance.accepted);
-        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
-        assert!(
-            !res.as_ref()
-                .unwrap()
-                .status
-                .acceptance
-                .request_to_accept_timed_out
-        );
-        assert!(!res.as_ref().unwrap().status.acceptance.rejected_to_accept);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
+        assert!(!res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
+        assert!(!res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_some());
+        match res.as_ref().unwrap().current_waiting_state {
+            Some(BillCurrentWaitingState::Sell(ref state)) => assert_eq!(
+                state.fee_estimate,
+                Some(FeeEstimate {
+                    fee_rate_sat_vb: 5,
+                    estimated_confirmation_seconds: 86400,
+                })
+            ),
+            _ => panic!("expected the bill to wait for a sell payment"),
+        };
+        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+    }
+
+    #[test]
+    fn fee_estimate_for_deadline_picks_cheapest_tier_before_deadline() {
+        let fees = test_utils::get_recommended_fees();
+        let estimate = |seconds| payment::fee_estimate_for_deadline(&fees, seconds);
+        assert_eq!(estimate(86400 * 2).fee_rate_sat_vb, 5);
+        assert_eq!(estimate(86400 * 2).estimated_confirmation_seconds, 86400);
+        assert_eq!(estimate(7200).fee_rate_sat_vb, 10);
+        assert_eq!(estimate(3599).fee_rate_sat_vb, 15);
+        assert_eq!(estimate(1200).fee_rate_sat_vb, 20);
+        assert_eq!(estimate(1200).estimated_confirmation_seconds, 600);
+        assert_eq!(estimate(0).fee_rate_sat_vb, 20);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_accept_rejected() {
+    async fn get_detail_waiting_for_offer_to_sell_and_sell() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
         let drawee_node_id = bill.drawee.node_id.clone();
-        let now = util::date::now().timestamp() as u64;
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block =
-                    request_to_accept_block(TEST_BILL_ID, chain.get_latest_block(), None);
-                assert!(chain.try_add_block(req_to_pay_block));
-                assert!(
-                    chain
-                        .try_add_block(reject_accept_block(TEST_BILL_ID, chain.get_latest_block()))
-                );
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &bill.drawee,
+                    None,
+                )));
+                assert!(chain.try_add_block(sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &bill.drawee,
+                )));
                 Ok(chain)
             });
         ctx.notification_service
@@ -1936,7 +3385,7 @@ pub mod tests {
                 TEST_BILL_ID,
                 &identity.identity,
                 &identity.identity.node_id,
-                now,
+                1731593928,
             )
             .await;
         assert!(res.is_ok());
@@ -1945,39 +3394,44 @@ pub mod tests {
             res.as_ref().unwrap().participants.drawee.node_id,
             drawee_node_id
         );
-        assert!(!res.as_ref().unwrap().status.acceptance.accepted);
-        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
-        assert!(
-            !res.as_ref()
+        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
+        assert!(!res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
+        assert!(!res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert_eq!(
+            res.as_ref()
                 .unwrap()
-                .status
-                .acceptance
-                .request_to_accept_timed_out
+                .participants
+                .endorsee
+                .as_ref()
+                .unwrap()
+                .node_id,
+            identity.identity.node_id
         );
-        assert!(res.as_ref().unwrap().status.acceptance.rejected_to_accept);
-        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.redeemed_funds_available); // caller is endorsee
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
     }
 
     #[tokio::test]
-    async fn get_detail_bill_req_to_accept_expired() {
+    async fn get_detail_waiting_for_offer_to_sell_and_expire() {
         let mut ctx = get_ctx();
+        let now = util::date::now().timestamp() as u64;
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        let now = util::date::now().timestamp() as u64;
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
         let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_pay_block = request_to_accept_block(
+                assert!(chain.try_add_block(offer_to_sell_block(
                     TEST_BILL_ID,
                     chain.get_latest_block(),
-                    Some(now - ACCEPT_DEADLINE_SECONDS * 2),
-                );
-                assert!(chain.try_add_block(req_to_pay_block));
+                    &bill.drawee,
+                    // expired
+                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
+                )));
                 Ok(chain)
             });
         ctx.notification_service
@@ -1999,1279 +3453,5403 @@ pub mod tests {
             res.as_ref().unwrap().participants.drawee.node_id,
             drawee_node_id
         );
-        assert!(!res.as_ref().unwrap().status.acceptance.accepted);
-        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
-        assert!(
-            res.as_ref()
-                .unwrap()
-                .status
-                .acceptance
-                .request_to_accept_timed_out
-        );
-        assert!(!res.as_ref().unwrap().status.acceptance.rejected_to_accept);
+        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
+        assert!(res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
+        assert!(!res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
         assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
     }
 
     #[tokio::test]
-    async fn accept_bill_baseline() {
+    async fn get_detail_waiting_for_offer_to_sell_and_reject() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
+        let now = util::date::now().timestamp() as u64;
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-
-        // Should send bill accepted event
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &bill.drawee,
+                    // expired
+                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
+                )));
+                assert!(
+                    chain.try_add_block(reject_buy_block(TEST_BILL_ID, chain.get_latest_block(),))
+                );
+                Ok(chain)
+            });
         ctx.notification_service
-            .expect_send_bill_is_accepted_event()
-            .returning(|_| Ok(()));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::Accept,
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
             )
             .await;
         assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 2);
-        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::Accept);
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
+        assert!(!res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
+        assert!(res.as_ref().unwrap().status.sell.rejected_offer_to_sell);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
     }
 
     #[tokio::test]
-    async fn accept_bill_as_company() {
+    async fn get_detail_waiting_for_offer_to_sell_and_cancel() {
         let mut ctx = get_ctx();
-        let company = get_baseline_company_data();
+        let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(company.0.clone());
-
+        let now = util::date::now().timestamp() as u64;
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
-
-        // Should send bill accepted event
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &bill.drawee,
+                    // expired
+                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
+                )));
+                assert!(chain.try_add_block(cancel_offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                )));
+                Ok(chain)
+            });
         ctx.notification_service
-            .expect_send_bill_is_accepted_event()
-            .returning(|_| Ok(()));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::Accept,
-                &IdentityPublicData::from(company.1.0),
-                &BcrKeys::from_private_key(&company.1.1.private_key).unwrap(),
-                1731593928,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
             )
             .await;
         assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 2);
-        assert!(res.as_ref().unwrap().blocks()[1].op_code == BillOpCode::Accept);
-        // company is accepter
-        assert!(
-            res.as_ref().unwrap().blocks()[1]
-                .get_nodes_from_block(&BillKeys {
-                    private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
-                    public_key: TEST_PUB_KEY_SECP.to_owned(),
-                })
-                .unwrap()[0]
-                == company.0
-        );
+        assert!(res.as_ref().unwrap().status.sell.offered_to_sell);
+        assert!(!res.as_ref().unwrap().status.sell.offer_to_sell_timed_out);
+        assert!(!res.as_ref().unwrap().status.sell.sold);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
     }
 
     #[tokio::test]
-    async fn accept_bill_fails_if_drawee_not_caller() {
+    async fn get_detail_bill_req_to_recourse() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block = request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
+                    None,
+                );
+                assert!(chain.try_add_block(req_to_pay_block));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::Accept,
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
             .await;
-        assert!(res.is_err());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .request_to_recourse_timed_out
+        );
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .rejected_request_to_recourse
+        );
+        assert!(res.as_ref().unwrap().current_waiting_state.is_some());
+        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Active
+        );
     }
 
     #[tokio::test]
-    async fn accept_bill_fails_if_already_accepted() {
+    async fn get_detail_bill_req_to_recourse_recoursed() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let keys = identity.key_pair.clone();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let mut chain = get_genesis_chain(Some(bill.clone()));
-        chain.blocks_mut().push(
-            BillBlock::new(
-                TEST_BILL_ID.to_string(),
-                123456,
-                "prevhash".to_string(),
-                "hash".to_string(),
-                BillOpCode::Accept,
-                &keys,
-                None,
-                &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                1731593928,
-            )
-            .unwrap(),
-        );
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(chain.clone()));
-        let service = get_service(ctx);
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block = request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
+                    None,
+                );
+                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(chain.try_add_block(recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(bill.drawee.node_id.clone())
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::Accept,
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
             .await;
-        assert!(res.is_err());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .request_to_recourse_timed_out
+        );
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .rejected_request_to_recourse
+        );
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.redeemed_funds_available); // caller is endorsee
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Settled
+        );
     }
 
     #[tokio::test]
-    async fn request_pay_baseline() {
+    async fn get_detail_bill_req_to_recourse_rejected() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.maturity_date = "2022-11-12".to_string(); // maturity date has to be in the past
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        // Request to pay event should be sent
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block = request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
+                    None,
+                );
+                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(chain.try_add_block(reject_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                )));
+                Ok(chain)
+            });
         ctx.notification_service
-            .expect_send_request_to_pay_event()
-            .returning(|_| Ok(()));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::RequestToPay("sat".to_string()),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
             .await;
         assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 2);
-        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::RequestToPay);
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .request_to_recourse_timed_out
+        );
+        assert!(
+            res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .rejected_request_to_recourse
+        );
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Defaulted
+        );
     }
 
     #[tokio::test]
-    async fn request_pay_fails_if_payee_not_caller() {
+    async fn get_detail_bill_req_to_recourse_expired() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let now = util::date::now().timestamp() as u64;
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block = request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(bill.drawee.node_id.clone()),
+                    Some(now - RECOURSE_DEADLINE_SECONDS * 2),
+                );
+                assert!(chain.try_add_block(req_to_pay_block));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::RequestToPay("sat".to_string()),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
             )
             .await;
-        assert!(res.is_err());
-    }
-
-    #[tokio::test]
-    async fn request_acceptance_baseline() {
-        let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.recourse.requested_to_recourse);
+        assert!(
+            res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .request_to_recourse_timed_out
+        );
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .recourse
+                .rejected_request_to_recourse
+        );
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Defaulted
+        );
+    }
+
+    #[tokio::test]
+    async fn get_detail_bill_req_to_pay() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        // Request to accept event should be sent
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                Ok(chain)
+            });
         ctx.notification_service
-            .expect_send_request_to_accept_event()
-            .returning(|_| Ok(()));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::RequestAcceptance,
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
             .await;
         assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 2);
-        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::RequestToAccept);
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(!res.as_ref().unwrap().status.payment.paid);
+        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .payment
+                .request_to_pay_timed_out
+        );
+        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_some());
+        assert!(!res.as_ref().unwrap().status.redeemed_funds_available);
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+    }
+
+    fn req_to_pay_chain(bill: BitcreditBill) -> BillBlockchain {
+        let mut chain = get_genesis_chain(Some(bill));
+        let req_to_pay_block = request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+        assert!(chain.try_add_block(req_to_pay_block));
+        chain
+    }
+
+    #[test]
+    fn reconstruct_bill_from_chain_genesis() {
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let chain = get_genesis_chain(Some(bill.clone()));
+
+        let res = reconstruct_bill_from_chain(&chain, &bill_keys(), 1731593928).unwrap();
+        assert_eq!(res.id, TEST_BILL_ID.to_string());
+        assert_eq!(res.participants.drawee.node_id, bill.drawee.node_id);
+        assert_eq!(res.participants.payee.node_id, bill.payee.node_id);
+        assert_eq!(res.data.sum, bill.sum.to_string());
+        assert_eq!(res.status.block_height, 1);
+        assert_eq!(res.status.last_op_code, BillOpCode::Issue);
+        assert!(!res.status.acceptance.requested_to_accept);
+        assert!(!res.status.payment.requested_to_pay);
+        assert!(res.current_waiting_state.is_none());
+    }
+
+    #[test]
+    fn reconstruct_bill_from_chain_req_to_pay_transitions_to_timed_out() {
+        init_test_cfg();
+        let chain = req_to_pay_chain(get_baseline_bill(TEST_BILL_ID));
+
+        let res = reconstruct_bill_from_chain(&chain, &bill_keys(), 1731593928).unwrap();
+        assert!(res.status.payment.requested_to_pay);
+        assert!(!res.status.payment.paid);
+        assert!(!res.status.payment.request_to_pay_timed_out);
+        assert!(matches!(
+            res.current_waiting_state,
+            Some(BillCurrentWaitingState::Payment(_))
+        ));
+
+        let after_deadline = util::date::date_string_to_timestamp("2099-10-15", None).unwrap()
+            as u64
+            + PAYMENT_DEADLINE_SECONDS
+            + 86400;
+        let res = reconstruct_bill_from_chain(&chain, &bill_keys(), after_deadline).unwrap();
+        assert!(res.status.payment.requested_to_pay);
+        assert!(!res.status.payment.paid);
+        assert!(res.status.payment.request_to_pay_timed_out);
+        assert!(res.current_waiting_state.is_none());
     }
 
     #[tokio::test]
-    async fn request_acceptance_fails_if_payee_not_caller() {
+    async fn reconstruct_bill_from_chain_matches_service_calculation() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let chain = req_to_pay_chain(get_baseline_bill(TEST_BILL_ID));
+        let service_chain = chain.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+            .returning(move |_| Ok(service_chain.clone()));
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let calculated = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::RequestAcceptance,
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
-            .await;
-        assert!(res.is_err());
+            .await
+            .unwrap();
+        let reconstructed = reconstruct_bill_from_chain(&chain, &bill_keys(), 1731593928).unwrap();
+
+        assert_eq!(reconstructed.id, calculated.id);
+        assert_eq!(reconstructed.data.sum, calculated.data.sum);
+        // the participants are collected in a map, so their order isn't stable
+        let mut reconstructed_node_ids =
+            reconstructed.participants.all_participant_node_ids.clone();
+        let mut calculated_node_ids = calculated.participants.all_participant_node_ids.clone();
+        reconstructed_node_ids.sort();
+        calculated_node_ids.sort();
+        assert_eq!(reconstructed_node_ids, calculated_node_ids);
+        assert_eq!(
+            reconstructed.status.payment.requested_to_pay,
+            calculated.status.payment.requested_to_pay
+        );
+        assert_eq!(
+            reconstructed.status.payment.request_to_pay_timed_out,
+            calculated.status.payment.request_to_pay_timed_out
+        );
+        assert_eq!(
+            reconstructed.status.last_op_code,
+            calculated.status.last_op_code
+        );
+        match (
+            reconstructed.current_waiting_state,
+            calculated.current_waiting_state,
+        ) {
+            (
+                Some(BillCurrentWaitingState::Payment(reconstructed)),
+                Some(BillCurrentWaitingState::Payment(calculated)),
+            ) => {
+                assert_eq!(reconstructed.payer.node_id, calculated.payer.node_id);
+                assert_eq!(reconstructed.payee.node_id, calculated.payee.node_id);
+                assert_eq!(reconstructed.sum, calculated.sum);
+                // the payment address is derived locally, fee estimates need the network
+                assert!(reconstructed.fee_estimate.is_none());
+            }
+            _ => panic!("both should be waiting for payment"),
+        }
     }
 
     #[tokio::test]
-    async fn mint_bitcredit_bill_baseline() {
+    async fn get_suggested_action_drawee_should_accept() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                chain.try_add_block(accept_block(&bill.id, chain.get_latest_block()));
+                chain.try_add_block(request_to_accept_block(
+                    &bill.id,
+                    chain.get_latest_block(),
+                    None,
+                ));
                 Ok(chain)
             });
-        // Asset request to mint event is sent
         ctx.notification_service
-            .expect_send_request_to_mint_event()
-            .returning(|_, _| Ok(()));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::Mint(
-                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
-                    5000,
-                    "sat".to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+        let res = get_service(ctx)
+            .get_suggested_action(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
             .await;
-        assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 3);
-        assert!(res.unwrap().blocks()[2].op_code == BillOpCode::Mint);
+        assert_eq!(res.unwrap(), Some(SuggestedAction::Accept));
     }
 
     #[tokio::test]
-    async fn mint_bitcredit_bill_fails_if_not_accepted() {
+    async fn get_suggested_action_drawee_should_pay() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        // Asset request to mint event is sent
+            .returning(move |_| Ok(req_to_pay_chain(bill.clone())));
         ctx.notification_service
-            .expect_send_request_to_mint_event()
-            .returning(|_, _| Ok(()));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::Mint(
-                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
-                    5000,
-                    "sat".to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+        let res = get_service(ctx)
+            .get_suggested_action(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
             .await;
-        assert!(res.is_err());
+        assert_eq!(res.unwrap(), Some(SuggestedAction::Pay));
     }
 
     #[tokio::test]
-    async fn mint_bitcredit_bill_fails_if_payee_not_caller() {
+    async fn get_suggested_action_payee_should_request_to_pay_after_maturity() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.maturity_date = "2024-11-01".to_string();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::Mint(empty_identity_public_data(), 5000, "sat".to_string()),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+        let res = get_service(ctx)
+            .get_suggested_action(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
             .await;
-        assert!(res.is_err());
+        assert_eq!(res.unwrap(), Some(SuggestedAction::RequestToPay));
     }
 
     #[tokio::test]
-    async fn offer_to_sell_bitcredit_bill_baseline() {
+    async fn get_suggested_action_holder_should_recourse_after_rejection() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
         bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        // Request to sell event should be sent
+            .returning(move |_| {
+                let mut chain = req_to_pay_chain(bill.clone());
+                chain.try_add_block(reject_to_pay_block(&bill.id, chain.get_latest_block()));
+                Ok(chain)
+            });
         ctx.notification_service
-            .expect_send_offer_to_sell_event()
-            .returning(|_, _| Ok(()));
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::OfferToSell(
-                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
-                    15000,
-                    "sat".to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+        let res = get_service(ctx)
+            .get_suggested_action(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
             .await;
-        assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 2);
-        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::OfferToSell);
+        assert_eq!(res.unwrap(), Some(SuggestedAction::RequestRecourse));
     }
 
     #[tokio::test]
-    async fn offer_to_sell_bitcredit_bill_fails_if_payee_not_caller() {
+    async fn get_suggested_action_nothing_pending_before_maturity() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::OfferToSell(
-                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
-                    15000,
-                    "sat".to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+        let res = get_service(ctx)
+            .get_suggested_action(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
             .await;
-        assert!(res.is_err());
+        assert_eq!(res.unwrap(), None);
     }
 
     #[tokio::test]
-    async fn sell_bitcredit_bill_baseline() {
+    async fn get_bill_waiting_state_matches_get_detail() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let buyer_clone = buyer.clone();
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let chain = req_to_pay_chain(bill);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let offer_to_sell = BillBlock::create_block_for_offer_to_sell(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillOfferToSellBlockData {
-                        seller: bill.payee.clone().into(),
-                        buyer: buyer_clone.clone().into(),
-                        currency: "sat".to_owned(),
-                        sum: 15000,
-                        payment_address: "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk0".to_owned(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(offer_to_sell);
-                Ok(chain)
-            });
-        // Request to sell event should be sent
+            .returning(move |_| Ok(chain.clone()));
         ctx.notification_service
-            .expect_send_bill_is_sold_event()
-            .returning(|_, _| Ok(()));
-
+            .expect_get_active_bill_notification()
+            .returning(|_| None);
         let service = get_service(ctx);
 
-        let res = service
-            .execute_bill_action(
+        let detail = service
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::Sell(
-                    buyer,
-                    15000,
-                    "sat".to_string(),
-                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
-            .await;
-        assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 3);
-        assert!(res.as_ref().unwrap().blocks()[1].op_code == BillOpCode::OfferToSell);
-        assert!(res.as_ref().unwrap().blocks()[2].op_code == BillOpCode::Sell);
+            .await
+            .unwrap();
+        let waiting_state = service
+            .get_bill_waiting_state(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
+            .await
+            .unwrap();
+
+        match (waiting_state, detail.current_waiting_state) {
+            (
+                Some(BillCurrentWaitingState::Payment(waiting_state)),
+                Some(BillCurrentWaitingState::Payment(detail)),
+            ) => {
+                assert_eq!(waiting_state.time_of_request, detail.time_of_request);
+                assert_eq!(waiting_state.payer.node_id, detail.payer.node_id);
+                assert_eq!(waiting_state.payee.node_id, detail.payee.node_id);
+                assert_eq!(waiting_state.currency, detail.currency);
+                assert_eq!(waiting_state.sum, detail.sum);
+                assert_eq!(waiting_state.address_to_pay, detail.address_to_pay);
+                assert_eq!(waiting_state.link_to_pay, detail.link_to_pay);
+                assert_eq!(
+                    waiting_state.mempool_link_for_address_to_pay,
+                    detail.mempool_link_for_address_to_pay
+                );
+            }
+            _ => panic!("both should be waiting for payment"),
+        }
     }
 
     #[tokio::test]
-    async fn sell_bitcredit_bill_fails_if_sell_data_is_invalid() {
+    async fn get_bill_waiting_state_paid_or_not_requested() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let chain = req_to_pay_chain(bill.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(chain.clone()));
+        let res = get_service(ctx)
+            .get_bill_waiting_state(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
+            .await;
+        assert!(res.unwrap().is_none());
+
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        // without a request to pay, the payment state isn't checked
+        ctx.bill_store.expect_is_paid().never();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let res = get_service(ctx)
+            .get_bill_waiting_state(TEST_BILL_ID, &identity.identity.node_id, 1731593928)
+            .await;
+        assert!(res.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn get_bill_waiting_state_fails_if_not_my_bill() {
+        let mut ctx = get_ctx();
+        let chain = req_to_pay_chain(get_baseline_bill(TEST_BILL_ID));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(chain.clone()));
+        let res = get_service(ctx)
+            .get_bill_waiting_state(TEST_BILL_ID, "some_other_node_id", 1731593928)
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    fn next_timeout_ctx(
+        latest_block: impl Fn(&BillBlock) -> BillBlock + Send + Sync + 'static,
+    ) -> MockBillContext {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let offer_to_sell = BillBlock::create_block_for_offer_to_sell(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillOfferToSellBlockData {
-                        seller: bill.payee.clone().into(),
-                        buyer: bill.payee.clone().into(), // buyer is seller, which is invalid
-                        currency: "sat".to_owned(),
-                        sum: 10000, // different sum
-                        payment_address: "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk0".to_owned(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(offer_to_sell);
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(TEST_BILL_ID)));
+                assert!(chain.try_add_block(latest_block(chain.get_latest_block())));
                 Ok(chain)
             });
-        // Sold event should be sent
-        ctx.notification_service
-            .expect_send_bill_is_sold_event()
-            .returning(|_, _| Ok(()));
+        ctx
+    }
 
-        let service = get_service(ctx);
+    // request blocks are created one second after the genesis block
+    const NEXT_TIMEOUT_REQUEST_TS: u64 = 1731593921;
 
-        let res = service
-            .execute_bill_action(
+    #[tokio::test]
+    async fn get_next_timeout_request_to_accept() {
+        let ctx = next_timeout_ctx(|first| request_to_accept_block(TEST_BILL_ID, first, None));
+        let res = get_service(ctx)
+            .get_next_timeout(
                 TEST_BILL_ID,
-                BillAction::Sell(
-                    buyer,
-                    15000,
-                    "sat".to_string(),
-                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
+                &get_baseline_identity().identity.node_id,
+                NEXT_TIMEOUT_REQUEST_TS,
             )
             .await;
-        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap(),
+            Some(NEXT_TIMEOUT_REQUEST_TS + ACCEPT_DEADLINE_SECONDS)
+        );
     }
 
     #[tokio::test]
-    async fn sell_bitcredit_bill_fails_if_not_offer_to_sell_waiting_for_payment() {
-        let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        // Request to sell event should be sent
-        ctx.notification_service
-            .expect_send_bill_is_sold_event()
-            .returning(|_, _| Ok(()));
-        let service = get_service(ctx);
-
-        let res = service
-            .execute_bill_action(
+    async fn get_next_timeout_request_to_pay() {
+        let ctx = next_timeout_ctx(|first| request_to_pay_block(TEST_BILL_ID, first, None));
+        let res = get_service(ctx)
+            .get_next_timeout(
                 TEST_BILL_ID,
-                BillAction::Sell(
-                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
-                    15000,
-                    "sat".to_string(),
-                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
+                &get_baseline_identity().identity.node_id,
+                NEXT_TIMEOUT_REQUEST_TS,
             )
             .await;
-        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap(),
+            Some(NEXT_TIMEOUT_REQUEST_TS + PAYMENT_DEADLINE_SECONDS)
+        );
     }
 
     #[tokio::test]
-    async fn sell_bitcredit_bill_fails_if_payee_not_caller() {
-        let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+    async fn get_next_timeout_offer_to_sell() {
+        let ctx = next_timeout_ctx(|first| {
+            offer_to_sell_block(
+                TEST_BILL_ID,
+                first,
+                &identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                None,
+            )
+        });
+        let res = get_service(ctx)
+            .get_next_timeout(
+                TEST_BILL_ID,
+                &get_baseline_identity().identity.node_id,
+                NEXT_TIMEOUT_REQUEST_TS,
+            )
+            .await;
+        assert_eq!(
+            res.unwrap(),
+            // without a sell deadline recorded with the offer, the payment deadline applies
+            Some(NEXT_TIMEOUT_REQUEST_TS + PAYMENT_DEADLINE_SECONDS)
+        );
+    }
 
-        let res = service
-            .execute_bill_action(
+    #[tokio::test]
+    async fn get_next_timeout_request_recourse() {
+        let ctx = next_timeout_ctx(|first| {
+            request_to_recourse_block(
                 TEST_BILL_ID,
-                BillAction::Sell(
-                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
-                    15000,
-                    "sat".to_string(),
-                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
+                first,
+                &identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                None,
+            )
+        });
+        let res = get_service(ctx)
+            .get_next_timeout(
+                TEST_BILL_ID,
+                &get_baseline_identity().identity.node_id,
+                NEXT_TIMEOUT_REQUEST_TS,
             )
             .await;
-        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap(),
+            Some(NEXT_TIMEOUT_REQUEST_TS + RECOURSE_DEADLINE_SECONDS)
+        );
     }
 
     #[tokio::test]
-    async fn endorse_bitcredit_bill_baseline() {
+    async fn get_next_timeout_none_if_nothing_is_pending() {
+        // no request
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        // Bill is endorsed event should be sent
-        ctx.notification_service
-            .expect_send_bill_is_endorsed_event()
-            .returning(|_| Ok(()));
-        let service = get_service(ctx);
+            .returning(|_| Ok(get_genesis_chain(Some(get_baseline_bill(TEST_BILL_ID)))));
+        let res = get_service(ctx)
+            .get_next_timeout(
+                TEST_BILL_ID,
+                &get_baseline_identity().identity.node_id,
+                NEXT_TIMEOUT_REQUEST_TS,
+            )
+            .await;
+        assert_eq!(res.unwrap(), None);
 
-        let res = service
-            .execute_bill_action(
+        // expired request
+        let ctx = next_timeout_ctx(|first| request_to_accept_block(TEST_BILL_ID, first, None));
+        let res = get_service(ctx)
+            .get_next_timeout(
                 TEST_BILL_ID,
-                BillAction::Endorse(identity_public_data_only_node_id(
-                    BcrKeys::new().get_public_key(),
-                )),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
+                &get_baseline_identity().identity.node_id,
+                NEXT_TIMEOUT_REQUEST_TS + ACCEPT_DEADLINE_SECONDS,
             )
             .await;
-        assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 2);
-        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::Endorse);
+        assert_eq!(res.unwrap(), None);
+
+        // paid request to pay
+        let mut ctx = next_timeout_ctx(|first| request_to_pay_block(TEST_BILL_ID, first, None));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        let res = get_service(ctx)
+            .get_next_timeout(
+                TEST_BILL_ID,
+                &get_baseline_identity().identity.node_id,
+                NEXT_TIMEOUT_REQUEST_TS,
+            )
+            .await;
+        assert_eq!(res.unwrap(), None);
     }
 
     #[tokio::test]
-    async fn endorse_bitcredit_bill_fails_if_waiting_for_offer_to_sell() {
+    async fn get_next_timeout_fails_if_not_my_bill() {
+        let ctx = next_timeout_ctx(|first| request_to_accept_block(TEST_BILL_ID, first, None));
+        let res = get_service(ctx)
+            .get_next_timeout(TEST_BILL_ID, "some_other_node_id", NEXT_TIMEOUT_REQUEST_TS)
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn get_detail_bill_req_to_pay_partially_paid() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_get_paid_sum().returning(|_| Ok(1500));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
-                    None,
-                )));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
                 Ok(chain)
             });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let service = get_service(ctx);
-
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::Endorse(identity_public_data_only_node_id(
-                    BcrKeys::new().get_public_key(),
-                )),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
             .await;
-        assert!(res.is_err());
-        match res {
-            Ok(_) => panic!("expected an error"),
-            Err(e) => match e {
-                Error::Validation(ValidationError::BillIsOfferedToSellAndWaitingForPayment) => (),
-                _ => panic!("expected a different error"),
-            },
-        };
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(!res.as_ref().unwrap().status.payment.paid);
+        assert_eq!(res.as_ref().unwrap().status.payment.paid_sum, 1500);
     }
 
     #[tokio::test]
-    async fn endorse_bitcredit_bill_fails_if_payee_not_caller() {
+    async fn get_detail_bill_req_to_pay_paid() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .execute_bill_action(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                BillAction::Endorse(empty_identity_public_data()),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
                 1731593928,
             )
             .await;
-        assert!(res.is_err());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.payment.paid);
+        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .payment
+                .request_to_pay_timed_out
+        );
+        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(!res.as_ref().unwrap().status.redeemed_funds_available); // caller not payee
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Settled
+        );
     }
 
     #[tokio::test]
-    async fn get_combined_bitcoin_key_for_bill_baseline() {
-        init_test_cfg();
+    async fn get_detail_bill_req_to_pay_rejected() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(identity.key_pair.get_public_key());
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        // the drawer is a past holder, the payee can recourse against
+        bill.drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(
+                    chain
+                        .try_add_block(reject_to_pay_block(TEST_BILL_ID, chain.get_latest_block()))
+                );
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .get_combined_bitcoin_key_for_bill(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
+                &identity.identity,
+                &identity.identity.node_id,
+                1731593928,
             )
             .await;
         assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(!res.as_ref().unwrap().status.payment.paid);
+        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .payment
+                .request_to_pay_timed_out
+        );
+        assert!(res.as_ref().unwrap().status.payment.rejected_to_pay);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Active
+        );
     }
 
     #[tokio::test]
-    async fn get_combined_bitcoin_key_for_bill_err() {
+    async fn get_detail_bill_req_to_pay_rejected_without_recourse_is_defaulted() {
         let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        // the drawer is the holder, so there are no past holders to recourse against
+        bill.drawer = bill.payee.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(
+                    chain
+                        .try_add_block(reject_to_pay_block(TEST_BILL_ID, chain.get_latest_block()))
+                );
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let non_participant_keys = BcrKeys::new();
-        let res = service
-            .get_combined_bitcoin_key_for_bill(
+        let res = get_service(ctx)
+            .get_detail(
                 TEST_BILL_ID,
-                &identity_public_data_only_node_id(non_participant_keys.get_public_key()),
-                &non_participant_keys,
+                &identity.identity,
+                &identity.identity.node_id,
+                1731593928,
             )
             .await;
-        assert!(res.is_err());
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().status.payment.rejected_to_pay);
+        assert_eq!(
+            res.as_ref().unwrap().status.settlement_status,
+            SettlementStatus::Defaulted
+        );
     }
 
     #[tokio::test]
-    async fn check_bills_payment_baseline() {
+    async fn get_detail_bill_req_to_pay_rejected_but_paid() {
         let mut ctx = get_ctx();
-        let bill = get_baseline_bill(TEST_BILL_ID);
-        ctx.bill_store
-            .expect_get_bill_ids_waiting_for_payment()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
-        ctx.bill_store.expect_set_to_paid().returning(|_, _| Ok(()));
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-        let service = get_service(ctx);
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(
+                    chain
+                        .try_add_block(reject_to_pay_block(TEST_BILL_ID, chain.get_latest_block()))
+                );
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service.check_bills_payment().await;
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                1731593928,
+            )
+            .await;
         assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.payment.paid);
+        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .payment
+                .request_to_pay_timed_out
+        );
+        assert!(res.as_ref().unwrap().status.payment.rejected_to_pay);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
     }
 
     #[tokio::test]
-    async fn check_bills_offer_to_sell_payment_baseline() {
+    async fn get_detail_bill_req_to_pay_expired() {
         let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
-
-        ctx.bill_store
-            .expect_get_bill_ids_waiting_for_sell_payment()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
-        let buyer_node_id = BcrKeys::new().get_public_key();
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let now = util::date::now().timestamp() as u64;
+        bill.maturity_date =
+            util::date::format_date_string(util::date::seconds(now - PAYMENT_DEADLINE_SECONDS * 2));
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                assert!(chain.try_add_block(offer_to_sell_block(
+                let req_to_pay_block = request_to_pay_block(
                     TEST_BILL_ID,
                     chain.get_latest_block(),
-                    &identity_public_data_only_node_id(buyer_node_id.clone()),
-                    None,
-                )));
+                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
+                );
+                assert!(chain.try_add_block(req_to_pay_block));
                 Ok(chain)
             });
         ctx.notification_service
-            .expect_send_bill_is_sold_event()
-            .returning(|_, _| Ok(()));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service.check_bills_offer_to_sell_payment().await;
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
+            )
+            .await;
         assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(!res.as_ref().unwrap().status.payment.paid);
+        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(
+            res.as_ref()
+                .unwrap()
+                .status
+                .payment
+                .request_to_pay_timed_out
+        );
+        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
     }
 
     #[tokio::test]
-    async fn check_bills_offer_to_sell_payment_company_is_seller() {
+    async fn get_detail_bill_req_to_pay_expired_but_paid() {
         let mut ctx = get_ctx();
-        let mut identity = get_baseline_identity();
-        identity.key_pair = BcrKeys::new();
-        identity.identity.node_id = identity.key_pair.get_public_key();
-
-        let company = get_baseline_company_data();
+        let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = IdentityPublicData::from(company.1.0.clone());
-
-        ctx.bill_store
-            .expect_get_bill_ids_waiting_for_sell_payment()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
-        let company_clone = company.clone();
-        ctx.company_store.expect_get_all().returning(move || {
-            let mut map = HashMap::new();
-            map.insert(
-                company_clone.0.clone(),
-                (company_clone.1.0.clone(), company_clone.1.1.clone()),
-            );
-            Ok(map)
-        });
-        let buyer_node_id = BcrKeys::new().get_public_key();
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        let now = util::date::now().timestamp() as u64;
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-                assert!(chain.try_add_block(offer_to_sell_block(
+                let req_to_pay_block = request_to_pay_block(
                     TEST_BILL_ID,
                     chain.get_latest_block(),
-                    &identity_public_data_only_node_id(buyer_node_id.clone()),
-                    None,
-                )));
+                    Some(now - PAYMENT_DEADLINE_SECONDS * 2),
+                );
+                assert!(chain.try_add_block(req_to_pay_block));
                 Ok(chain)
             });
         ctx.notification_service
-            .expect_send_bill_is_sold_event()
-            .returning(|_, _| Ok(()));
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service.check_bills_offer_to_sell_payment().await;
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
+            )
+            .await;
         assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.payment.paid);
+        assert!(res.as_ref().unwrap().status.payment.requested_to_pay);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .payment
+                .request_to_pay_timed_out
+        );
+        assert!(!res.as_ref().unwrap().status.payment.rejected_to_pay);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+        assert!(res.as_ref().unwrap().status.has_requested_funds);
     }
 
     #[tokio::test]
-    async fn check_bills_timeouts_does_nothing_if_not_timed_out() {
+    async fn get_detail_bill_req_to_accept() {
         let mut ctx = get_ctx();
-        let op_codes = HashSet::from([
-            BillOpCode::RequestToAccept,
-            BillOpCode::RequestToPay,
-            BillOpCode::OfferToSell,
-            BillOpCode::RequestRecourse,
-        ]);
-
-        // fetches bill ids
-        ctx.bill_store
-            .expect_get_bill_ids_with_op_codes_since()
-            .with(eq(op_codes.clone()), eq(0))
-            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
-        // fetches bill chain accept
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .with(eq(TEST_BILL_ID.to_string()))
-            .returning(|id| {
-                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
-                chain.try_add_block(request_to_accept_block(id, chain.get_latest_block(), None));
-                Ok(chain)
-            });
-        // fetches bill chain pay
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .with(eq("4321".to_string()))
-            .returning(|id| {
-                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
-                chain.try_add_block(request_to_pay_block(id, chain.get_latest_block(), None));
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_accept_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
                 Ok(chain)
             });
-        let service = get_service(ctx);
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        // now is the same as block created time so no timeout should have happened
-        let res = service.check_bills_timeouts(1000).await;
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                1731593928,
+            )
+            .await;
         assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(!res.as_ref().unwrap().status.acceptance.accepted);
+        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .acceptance
+                .request_to_accept_timed_out
+        );
+        assert!(!res.as_ref().unwrap().status.acceptance.rejected_to_accept);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
     }
 
     #[tokio::test]
-    async fn check_bills_timeouts_does_nothing_if_notifications_are_already_sent() {
+    async fn get_detail_bill_req_to_accept_accepted() {
         let mut ctx = get_ctx();
-        let op_codes = HashSet::from([
-            BillOpCode::RequestToAccept,
-            BillOpCode::RequestToPay,
-            BillOpCode::OfferToSell,
-            BillOpCode::RequestRecourse,
-        ]);
-
-        // fetches bill ids
-        ctx.bill_store
-            .expect_get_bill_ids_with_op_codes_since()
-            .with(eq(op_codes.clone()), eq(0))
-            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
-
-        // fetches bill chain accept
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .with(eq(TEST_BILL_ID.to_string()))
-            .returning(|id| {
-                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
-                chain.try_add_block(request_to_accept_block(id, chain.get_latest_block(), None));
-                Ok(chain)
-            });
-
-        // fetches bill chain pay
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .with(eq("4321".to_string()))
-            .returning(|id| {
-                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
-                chain.try_add_block(request_to_pay_block(id, chain.get_latest_block(), None));
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_accept_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(chain.try_add_block(accept_block(TEST_BILL_ID, chain.get_latest_block())));
                 Ok(chain)
             });
-        // notification already sent
-        ctx.notification_service
-            .expect_check_bill_notification_sent()
-            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
-            .returning(|_, _, _| Ok(true));
-
-        // notification already sent
         ctx.notification_service
-            .expect_check_bill_notification_sent()
-            .with(eq("4321"), eq(2), eq(ActionType::PayBill))
-            .returning(|_, _, _| Ok(true));
-
-        let service = get_service(ctx);
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let res = service
-            .check_bills_timeouts(PAYMENT_DEADLINE_SECONDS + 1100)
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                1731593928,
+            )
             .await;
         assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(res.as_ref().unwrap().status.acceptance.accepted);
+        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .acceptance
+                .request_to_accept_timed_out
+        );
+        assert!(!res.as_ref().unwrap().status.acceptance.rejected_to_accept);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
     }
 
     #[tokio::test]
-    async fn check_bills_timeouts() {
+    async fn get_detail_bill_req_to_accept_rejected() {
         let mut ctx = get_ctx();
-        let op_codes = HashSet::from([
-            BillOpCode::RequestToAccept,
-            BillOpCode::RequestToPay,
-            BillOpCode::OfferToSell,
-            BillOpCode::RequestRecourse,
-        ]);
-
-        // fetches bill ids
-        ctx.bill_store
-            .expect_get_bill_ids_with_op_codes_since()
-            .with(eq(op_codes.clone()), eq(0))
-            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
-
-        // fetches bill chain accept
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .with(eq(TEST_BILL_ID.to_string()))
-            .returning(|id| {
-                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
-                chain.try_add_block(request_to_accept_block(id, chain.get_latest_block(), None));
-                Ok(chain)
-            });
-
-        // fetches bill chain pay
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
+        let now = util::date::now().timestamp() as u64;
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .with(eq("4321".to_string()))
-            .returning(|id| {
-                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
-                chain.try_add_block(request_to_pay_block(id, chain.get_latest_block(), None));
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_accept_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                assert!(
+                    chain
+                        .try_add_block(reject_accept_block(TEST_BILL_ID, chain.get_latest_block()))
+                );
                 Ok(chain)
             });
-
-        // notification not sent
-        ctx.notification_service
-            .expect_check_bill_notification_sent()
-            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
-            .returning(|_, _, _| Ok(false));
-
-        // notification not sent
-        ctx.notification_service
-            .expect_check_bill_notification_sent()
-            .with(eq("4321"), eq(2), eq(ActionType::PayBill))
-            .returning(|_, _, _| Ok(false));
-
-        // we should have at least two participants
-        let recipient_check = function(|r: &Vec<IdentityPublicData>| r.len() >= 2);
-
-        // send accept timeout notification
         ctx.notification_service
-            .expect_send_request_to_action_timed_out_event()
-            .with(
-                always(),
-                eq(TEST_BILL_ID),
-                always(),
-                eq(ActionType::AcceptBill),
-                recipient_check.clone(),
-            )
-            .returning(|_, _, _, _, _| Ok(()));
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        // send pay timeout notification
-        ctx.notification_service
-            .expect_send_request_to_action_timed_out_event()
-            .with(
-                always(),
-                eq("4321"),
-                always(),
-                eq(ActionType::PayBill),
-                recipient_check,
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
             )
-            .returning(|_, _, _, _, _| Ok(()));
-
-        // marks accept bill timeout as sent
-        ctx.notification_service
-            .expect_mark_bill_notification_sent()
-            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
-            .returning(|_, _, _| Ok(()));
-
-        // marks pay bill timeout as sent
-        ctx.notification_service
-            .expect_mark_bill_notification_sent()
-            .with(eq("4321"), eq(2), eq(ActionType::PayBill))
-            .returning(|_, _, _| Ok(()));
-
-        let service = get_service(ctx);
-
-        let res = service
-            .check_bills_timeouts(PAYMENT_DEADLINE_SECONDS + 1100)
-            .await;
-        assert!(res.is_ok());
-    }
-
-    #[tokio::test]
-    async fn get_endorsements_baseline() {
-        let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        ctx.bill_store.expect_exists().returning(|_| true);
-        ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
-
-        let service = get_service(ctx);
-
-        let res = service
-            .get_endorsements(TEST_BILL_ID, &identity.identity.node_id)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().len(), 0);
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(!res.as_ref().unwrap().status.acceptance.accepted);
+        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
+        assert!(
+            !res.as_ref()
+                .unwrap()
+                .status
+                .acceptance
+                .request_to_accept_timed_out
+        );
+        assert!(res.as_ref().unwrap().status.acceptance.rejected_to_accept);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
     }
 
     #[tokio::test]
-    async fn get_endorsements_multi() {
+    async fn get_detail_bill_req_to_accept_rejected_with_reason() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let mint_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let endorse_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let sell_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.drawer = drawer.clone();
-        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let now = util::date::now().timestamp() as u64;
         ctx.bill_store.expect_exists().returning(|_| true);
-        let endorse_endorsee_clone = endorse_endorsee.clone();
-        let mint_endorsee_clone = mint_endorsee.clone();
-        let sell_endorsee_clone = sell_endorsee.clone();
-
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
-                let now = util::date::now().timestamp() as u64;
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-
-                // add endorse block from payee to endorsee
-                let endorse_block = BillBlock::create_block_for_endorse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillEndorseBlockData {
-                        endorsee: endorse_endorsee.clone().into(),
-                        // endorsed by payee
-                        endorser: IdentityPublicData::new(get_baseline_identity().identity)
-                            .unwrap()
-                            .into(),
-                        signatory: None,
-                        signing_timestamp: now + 1,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 1,
-                )
-                .unwrap();
-                assert!(chain.try_add_block(endorse_block));
-
-                // add sell block from endorsee to sell endorsee
-                let sell_block = BillBlock::create_block_for_sell(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillSellBlockData {
-                        buyer: sell_endorsee.clone().into(),
-                        // endorsed by endorsee
-                        seller: endorse_endorsee.clone().into(),
-                        currency: "sat".to_string(),
-                        sum: 15000,
-                        payment_address: VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
-                        signatory: None,
-                        signing_timestamp: now + 2,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 2,
-                )
-                .unwrap();
-                assert!(chain.try_add_block(sell_block));
-
-                // add mint block from sell endorsee to mint endorsee
-                let mint_block = BillBlock::create_block_for_mint(
+                let req_to_accept_block =
+                    request_to_accept_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_accept_block));
+                let latest_block = chain.get_latest_block().clone();
+                let reject_block = BillBlock::create_block_for_reject_to_accept(
                     TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillMintBlockData {
-                        endorsee: mint_endorsee.clone().into(),
-                        // endorsed by sell endorsee
-                        endorser: sell_endorsee.clone().into(),
-                        currency: "sat".to_string(),
-                        sum: 15000,
+                    &latest_block,
+                    &BillRejectBlockData {
+                        rejecter: bill.drawee.clone().into(),
                         signatory: None,
-                        signing_timestamp: now + 3,
+                        signing_timestamp: latest_block.timestamp,
                         signing_address: empty_address(),
+                        reason: Some("goods not received".to_string()),
                     },
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    None,
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 3,
+                    latest_block.timestamp,
                 )
                 .unwrap();
-                assert!(chain.try_add_block(mint_block));
-
+                assert!(chain.try_add_block(reject_block));
                 Ok(chain)
             });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
 
-        let service = get_service(ctx);
-
-        let res = service
-            .get_endorsements(TEST_BILL_ID, &identity.identity.node_id)
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
+            )
             .await;
         assert!(res.is_ok());
-        // with duplicates
-        assert_eq!(res.as_ref().unwrap().len(), 3);
-        // mint was last, so it's first
-        assert_eq!(
-            res.as_ref().unwrap()[0].pay_to_the_order_of.node_id,
-            mint_endorsee_clone.node_id
-        );
+        let acceptance = &res.as_ref().unwrap().status.acceptance;
+        assert!(acceptance.rejected_to_accept);
         assert_eq!(
-            res.as_ref().unwrap()[1].pay_to_the_order_of.node_id,
-            sell_endorsee_clone.node_id
+            acceptance.rejected_to_accept_reason,
+            Some("goods not received".to_string())
         );
         assert_eq!(
-            res.as_ref().unwrap()[2].pay_to_the_order_of.node_id,
-            endorse_endorsee_clone.node_id
+            res.as_ref().unwrap().status.payment.rejected_to_pay_reason,
+            None
         );
     }
 
     #[tokio::test]
-    async fn get_past_endorsees_baseline() {
+    async fn get_detail_bill_req_to_accept_expired() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
-
+        let now = util::date::now().timestamp() as u64;
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let drawee_node_id = bill.drawee.node_id.clone();
         ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block = request_to_accept_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    Some(now - ACCEPT_DEADLINE_SECONDS * 2),
+                );
+                assert!(chain.try_add_block(req_to_pay_block));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
+
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            res.as_ref().unwrap().participants.drawee.node_id,
+            drawee_node_id
+        );
+        assert!(!res.as_ref().unwrap().status.acceptance.accepted);
+        assert!(res.as_ref().unwrap().status.acceptance.requested_to_accept);
+        assert!(
+            res.as_ref()
+                .unwrap()
+                .status
+                .acceptance
+                .request_to_accept_timed_out
+        );
+        assert!(!res.as_ref().unwrap().status.acceptance.rejected_to_accept);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_detail_bill_req_to_accept_cancelled() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        let now = util::date::now().timestamp() as u64;
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(request_to_accept_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    // expired
+                    Some(now - ACCEPT_DEADLINE_SECONDS * 2),
+                )));
+                assert!(chain.try_add_block(cancel_request_to_accept_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_get_active_bill_notification()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| None);
+
+        let res = get_service(ctx)
+            .get_detail(
+                TEST_BILL_ID,
+                &identity.identity,
+                &identity.identity.node_id,
+                now,
+            )
+            .await;
+        assert!(res.is_ok());
+        let acceptance = &res.as_ref().unwrap().status.acceptance;
+        assert!(!acceptance.requested_to_accept);
+        assert!(!acceptance.request_to_accept_timed_out);
+        assert!(acceptance.time_of_request_to_accept.is_none());
+        assert!(!acceptance.accepted);
+        assert!(!acceptance.rejected_to_accept);
+        assert!(res.as_ref().unwrap().current_waiting_state.is_none());
+    }
+
+    #[tokio::test]
+    async fn accept_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+
+        // Should send bill accepted event
+        ctx.notification_service
+            .expect_send_bill_is_accepted_event()
+            .returning(|_| Ok(()));
+
         let service = get_service(ctx);
 
         let res = service
-            .get_past_endorsees(TEST_BILL_ID, &identity.identity.node_id)
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
             .await;
         assert!(res.is_ok());
-        // if we're the drawee and drawer, there's no holder before us
-        assert_eq!(res.as_ref().unwrap().len(), 0);
+        assert!(res.as_ref().unwrap().blocks().len() == 2);
+        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::Accept);
     }
 
     #[tokio::test]
-    async fn get_past_endorsees_fails_if_not_my_bill() {
+    async fn validate_bill_action_baseline() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
-
-        ctx.bill_store.expect_exists().returning(|_| true);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // dry run doesn't persist, cache or notify anything
+        ctx.bill_blockchain_store.expect_add_block().never();
+        ctx.bill_store.expect_save_bill_to_cache().never();
+        ctx.bill_store.expect_invalidate_bill_in_cache().never();
+        ctx.notification_service
+            .expect_send_bill_is_accepted_event()
+            .never();
+
         let service = get_service(ctx);
 
         let res = service
-            .get_past_endorsees(TEST_BILL_ID, "some_other_node_id")
+            .validate_bill_action(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                1731593928,
+            )
             .await;
-        assert!(res.is_err());
+        assert!(res.is_ok());
     }
 
     #[tokio::test]
-    async fn get_past_endorsees_3_party() {
+    async fn validate_bill_action_fails_if_drawee_not_caller() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.drawer = drawer.clone();
         bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .validate_bill_action(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                1731593928,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::CallerIsNotDrawee))
+        ));
+    }
+
+    #[tokio::test]
+    async fn validate_bill_action_fails_with_only_a_view() {
+        let mut ctx = get_ctx();
+        ctx.bill_store
+            .expect_get_view()
+            .returning(|_| Ok(Some(get_view_for_test())));
+        ctx.bill_store.expect_exists().returning(|_| false);
+        ctx.bill_blockchain_store.expect_get_chain().never();
+        let identity = get_baseline_identity();
+        let service = get_service(ctx);
+
+        let res = service
+            .validate_bill_action(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                1731593928,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::BillIsReadOnlyView))
+        ));
+    }
+
+    #[tokio::test]
+    async fn accept_bill_as_company() {
+        let mut ctx = get_ctx();
+        let company = get_baseline_company_data();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(company.0.clone());
 
-        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+
+        // Should send bill accepted event
+        ctx.notification_service
+            .expect_send_bill_is_accepted_event()
+            .returning(|_| Ok(()));
+
         let service = get_service(ctx);
 
         let res = service
-            .get_past_endorsees(TEST_BILL_ID, &identity.identity.node_id)
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::from(company.1.0),
+                &BcrKeys::from_private_key(&company.1.1.private_key).unwrap(),
+                1731593928,
+                None,
+            )
             .await;
         assert!(res.is_ok());
-        // if it's a 3 party bill and we're the payee, the drawer is a previous holder
-        assert_eq!(res.as_ref().unwrap().len(), 1);
-        assert_eq!(
-            res.as_ref().unwrap()[0].pay_to_the_order_of.node_id,
-            drawer.node_id
+        assert!(res.as_ref().unwrap().blocks().len() == 2);
+        assert!(res.as_ref().unwrap().blocks()[1].op_code == BillOpCode::Accept);
+        // company is accepter
+        assert!(
+            res.as_ref().unwrap().blocks()[1]
+                .get_nodes_from_block(&BillKeys {
+                    private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+                    public_key: TEST_PUB_KEY_SECP.to_owned(),
+                })
+                .unwrap()[0]
+                == company.0
         );
     }
 
     #[tokio::test]
-    async fn get_past_endorsees_multi() {
+    async fn accept_bill_as_company_with_acting_signatory() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
+        let (company_id, (mut company, company_keys)) = get_baseline_company_data();
+        let identity = get_baseline_identity().identity;
+        let acting_signatory_node_id = identity.node_id.clone();
+        company.signatories = vec![
+            BcrKeys::new().get_public_key(),
+            acting_signatory_node_id.clone(),
+        ];
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let mint_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let endorse_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let sell_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawee = identity_public_data_only_node_id(company_id.clone());
 
-        bill.drawer = drawer.clone();
-        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let company_clone = company.clone();
+        ctx.company_store
+            .expect_get()
+            .returning(move |_| Ok(company_clone.clone()));
+        ctx.notification_service
+            .expect_send_bill_is_accepted_event()
+            .returning(|_| Ok(()));
 
-        ctx.bill_store.expect_exists().returning(|_| true);
-        let endorse_endorsee_clone = endorse_endorsee.clone();
-        let mint_endorsee_clone = mint_endorsee.clone();
-        let sell_endorsee_clone = sell_endorsee.clone();
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action_as_signatory(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::from(company),
+                &BcrKeys::from_private_key(&company_keys.private_key).unwrap(),
+                &acting_signatory_node_id,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 2);
+        let block_data: BillAcceptBlockData = chain.blocks()[1]
+            .get_decrypted_block_bytes(&bill_keys())
+            .unwrap();
+        assert_eq!(block_data.accepter.node_id, company_id);
+        assert_eq!(
+            block_data.signatory,
+            Some(BillSignatoryBlockData {
+                node_id: acting_signatory_node_id,
+                name: identity.name,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn accept_bill_as_company_fails_for_foreign_acting_signatory() {
+        let mut ctx = get_ctx();
+        let (company_id, (mut company, company_keys)) = get_baseline_company_data();
+        // a signatory of the company, whose keys the caller doesn't hold
+        let foreign_signatory = BcrKeys::new().get_public_key();
+        company.signatories = vec![
+            get_baseline_identity().identity.node_id,
+            foreign_signatory.clone(),
+        ];
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(company_id);
 
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let now = util::date::now().timestamp() as u64;
-                let mut chain = get_genesis_chain(Some(bill.clone()));
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let company_clone = company.clone();
+        ctx.company_store
+            .expect_get()
+            .returning(move |_| Ok(company_clone.clone()));
 
-                // add endorse block from payee to endorsee
-                let endorse_block = BillBlock::create_block_for_endorse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillEndorseBlockData {
-                        endorsee: endorse_endorsee.clone().into(),
-                        // endorsed by payee
-                        endorser: IdentityPublicData::new(get_baseline_identity().identity)
-                            .unwrap()
+        let res = get_service(ctx)
+            .execute_bill_action_as_signatory(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::from(company),
+                &BcrKeys::from_private_key(&company_keys.private_key).unwrap(),
+                &foreign_signatory,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::SignatoryCantSign(node_id))) if node_id == foreign_signatory
+        ));
+    }
+
+    #[tokio::test]
+    async fn accept_bill_as_company_fails_if_acting_signatory_not_signatory() {
+        let mut ctx = get_ctx();
+        let (company_id, (company, company_keys)) = get_baseline_company_data();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(company_id);
+
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let company_clone = company.clone();
+        ctx.company_store
+            .expect_get()
+            .returning(move |_| Ok(company_clone.clone()));
+
+        let service = get_service(ctx);
+
+        let non_signatory = BcrKeys::new().get_public_key();
+        let res = service
+            .execute_bill_action_as_signatory(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::from(company),
+                &BcrKeys::from_private_key(&company_keys.private_key).unwrap(),
+                &non_signatory,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::NotASignatory(node_id))) if node_id == non_signatory
+        ));
+    }
+
+    #[tokio::test]
+    async fn accept_bill_fails_if_drawee_not_caller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn accept_bill_fails_if_already_accepted() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let keys = identity.key_pair.clone();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let mut chain = get_genesis_chain(Some(bill.clone()));
+        chain.blocks_mut().push(
+            BillBlock::new(
+                TEST_BILL_ID.to_string(),
+                123456,
+                "prevhash".to_string(),
+                "hash".to_string(),
+                BillOpCode::Accept,
+                &keys,
+                None,
+                &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                1731593928,
+            )
+            .unwrap(),
+        );
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(chain.clone()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Accept,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    fn auto_accept_ctx(bill: BitcreditBill, max_sum: u64) -> MockBillContext {
+        let drawer_node_id = bill.drawer.node_id.clone();
+        auto_accept_ctx_with_chain(get_genesis_chain(Some(bill)), drawer_node_id, max_sum)
+    }
+
+    fn auto_accept_ctx_with_chain(
+        chain: BillBlockchain,
+        drawer_node_id: String,
+        max_sum: u64,
+    ) -> MockBillContext {
+        let mut ctx = get_ctx();
+        let node_id = get_baseline_identity().identity.node_id;
+        let queued_node_id = node_id.clone();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(chain.clone()));
+        ctx.auto_accept_store
+            .expect_get_queued_accepts()
+            .returning(move || {
+                Ok(vec![QueuedAutoAccept {
+                    bill_id: TEST_BILL_ID.to_owned(),
+                    node_id: queued_node_id.clone(),
+                    rule_id: "rule".to_owned(),
+                    timestamp: 1731593920,
+                }])
+            });
+        ctx.auto_accept_store
+            .expect_get_rules()
+            .with(eq(node_id.clone()))
+            .returning(move |node_id| {
+                Ok(vec![BillAutoAcceptRule {
+                    id: "rule".to_owned(),
+                    node_id: node_id.to_owned(),
+                    drawer_node_id: drawer_node_id.clone(),
+                    max_sum,
+                }])
+            });
+        // the bill is removed from the queue, whether it was accepted or not
+        ctx.auto_accept_store
+            .expect_remove_queued_accept()
+            .with(eq(TEST_BILL_ID), eq(node_id))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        ctx
+    }
+
+    #[tokio::test]
+    async fn process_auto_accepts_accepts_bill_matching_rule() {
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = identity_public_data_only_node_id(get_baseline_identity().identity.node_id);
+        let mut ctx = auto_accept_ctx(bill.clone(), bill.sum);
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_add_block()
+            .withf(|_, block| block.op_code == BillOpCode::Accept)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        ctx.notification_service
+            .expect_send_bill_is_accepted_event()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = get_service(ctx);
+        let res = service.process_auto_accepts(1731593928).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn process_auto_accepts_does_not_accept_bill_over_max_sum() {
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = identity_public_data_only_node_id(get_baseline_identity().identity.node_id);
+        let mut ctx = auto_accept_ctx(bill.clone(), bill.sum - 1);
+        ctx.bill_blockchain_store.expect_add_block().never();
+        ctx.notification_service
+            .expect_send_bill_is_accepted_event()
+            .never();
+
+        let service = get_service(ctx);
+        let res = service.process_auto_accepts(1731593928).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn process_auto_accepts_does_not_accept_bill_with_corrected_drawee() {
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = identity_public_data_only_node_id(get_baseline_identity().identity.node_id);
+        let mut chain = get_genesis_chain(Some(bill.clone()));
+        // the drawer corrected the drawee after the bill was queued for the initial drawee
+        let latest_block = chain.get_latest_block().clone();
+        let correct_drawee_block = BillBlock::create_block_for_correct_drawee(
+            TEST_BILL_ID.to_string(),
+            &latest_block,
+            &BillCorrectDraweeBlockData {
+                drawer: bill.drawer.clone().into(),
+                drawee: identity_public_data_only_node_id(BcrKeys::new().get_public_key()).into(),
+                signatory: None,
+                signing_timestamp: latest_block.timestamp + 1,
+                signing_address: empty_address(),
+            },
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            latest_block.timestamp + 1,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(correct_drawee_block));
+        let mut ctx = auto_accept_ctx_with_chain(chain, bill.drawer.node_id.clone(), bill.sum);
+        ctx.bill_blockchain_store.expect_add_block().never();
+        ctx.notification_service
+            .expect_send_bill_is_accepted_event()
+            .never();
+
+        let service = get_service(ctx);
+        let res = service.process_auto_accepts(1731593928).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn add_auto_accept_rule_baseline() {
+        let mut ctx = get_ctx();
+        ctx.auto_accept_store
+            .expect_save_rule()
+            .withf(|rule| rule.drawer_node_id == TEST_PUB_KEY_SECP && rule.max_sum == 1000)
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+        let res = service
+            .add_auto_accept_rule(
+                &get_baseline_identity().identity.node_id,
+                TEST_PUB_KEY_SECP,
+                1000,
+            )
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn add_auto_accept_rule_fails_for_invalid_drawer_node_id() {
+        let mut ctx = get_ctx();
+        ctx.auto_accept_store.expect_save_rule().never();
+        let service = get_service(ctx);
+        let res = service
+            .add_auto_accept_rule(&get_baseline_identity().identity.node_id, "drawer", 1000)
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidSecp256k1Key(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn request_pay_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.maturity_date = "2022-11-12".to_string(); // maturity date has to be in the past
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Request to pay event should be sent
+        ctx.notification_service
+            .expect_send_request_to_pay_event()
+            .returning(|_| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestToPay("sat".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 2);
+        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::RequestToPay);
+    }
+
+    #[tokio::test]
+    async fn request_acceptance_and_payment_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.maturity_date = "2022-11-12".to_string(); // maturity date has to be in the past
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Combined request event should be sent
+        ctx.notification_service
+            .expect_send_request_to_accept_and_pay_event()
+            .returning(|_| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestAcceptanceAndPayment("sat".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 3);
+        assert_eq!(chain.blocks()[1].op_code, BillOpCode::RequestToAccept);
+        assert_eq!(chain.blocks()[2].op_code, BillOpCode::RequestToPay);
+        assert!(chain.is_latest_block_combined_request_to_pay());
+    }
+
+    #[tokio::test]
+    async fn request_acceptance_and_payment_removes_stored_blocks_if_storing_fails() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.maturity_date = "2022-11-12".to_string(); // maturity date has to be in the past
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // the request to accept block is stored, the request to pay block isn't
+        let mut seq = mockall::Sequence::new();
+        ctx.bill_blockchain_store
+            .expect_add_block()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_add_block()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("test error"))));
+        // so the request to accept block is removed again
+        ctx.bill_blockchain_store
+            .expect_remove_blocks_from()
+            .with(eq(TEST_BILL_ID), eq(2))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        ctx.identity_chain_store.expect_add_block().never();
+        ctx.notification_service
+            .expect_send_request_to_accept_and_pay_event()
+            .never();
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestAcceptanceAndPayment("sat".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn request_acceptance_and_payment_only_requests_payment_if_accepted() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.maturity_date = "2022-11-12".to_string(); // maturity date has to be in the past
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(accept_block(TEST_BILL_ID, chain.get_latest_block())));
+                Ok(chain)
+            });
+        // Only the request to pay event should be sent
+        ctx.notification_service
+            .expect_send_request_to_pay_event()
+            .returning(|_| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestAcceptanceAndPayment("sat".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 3);
+        assert_eq!(chain.blocks()[1].op_code, BillOpCode::Accept);
+        assert_eq!(chain.blocks()[2].op_code, BillOpCode::RequestToPay);
+        assert!(!chain.is_latest_block_combined_request_to_pay());
+    }
+
+    #[tokio::test]
+    async fn request_pay_fails_if_payee_not_caller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestToPay("sat".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn request_acceptance_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Request to accept event should be sent
+        ctx.notification_service
+            .expect_send_request_to_accept_event()
+            .returning(|_| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestAcceptance,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 2);
+        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::RequestToAccept);
+    }
+
+    #[tokio::test]
+    async fn request_acceptance_fails_if_payee_not_caller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestAcceptance,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn mint_bitcredit_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                chain.try_add_block(accept_block(&bill.id, chain.get_latest_block()));
+                Ok(chain)
+            });
+        // Asset request to mint event is sent
+        ctx.notification_service
+            .expect_send_request_to_mint_event()
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Mint(
+                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    5000,
+                    "sat".to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 3);
+        assert!(res.unwrap().blocks()[2].op_code == BillOpCode::Mint);
+    }
+
+    #[tokio::test]
+    async fn mint_bitcredit_bill_fails_if_not_accepted() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Asset request to mint event is sent
+        ctx.notification_service
+            .expect_send_request_to_mint_event()
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Mint(
+                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    5000,
+                    "sat".to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn mint_bitcredit_bill_fails_if_payee_not_caller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Mint(empty_identity_public_data(), 5000, "sat".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn submit_mint_quote_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mint_keys = BcrKeys::new();
+        let mint = identity_public_data_only_node_id(mint_keys.get_public_key());
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let mint_clone = mint.clone();
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                chain.try_add_block(accept_block(&bill.id, chain.get_latest_block()));
+                chain.try_add_block(mint_block(&bill.id, chain.get_latest_block(), &mint_clone));
+                Ok(chain)
+            });
+        // the requester of the mint is notified about the quote
+        let requester_node_id = identity.identity.node_id.clone();
+        ctx.notification_service
+            .expect_send_new_quote_event()
+            .withf(move |_, requester| requester.node_id == requester_node_id)
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .submit_mint_quote(
+                TEST_BILL_ID,
+                4900,
+                "sat",
+                1731593928 + 3600,
+                &mint,
+                &mint_keys,
+                1731593928,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 4);
+        assert_eq!(chain.blocks()[3].op_code, BillOpCode::MintQuote);
+        let quote: BillMintQuoteBlockData = chain.blocks()[3]
+            .get_decrypted_block_bytes(&bill_keys())
+            .unwrap();
+        assert_eq!(quote.mint_request_block_id, chain.blocks()[2].id);
+        assert_eq!(quote.requester.node_id, identity.identity.node_id);
+        assert_eq!(quote.sum, 4900);
+    }
+
+    #[tokio::test]
+    async fn submit_mint_quote_fails_if_caller_is_not_mint() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                chain.try_add_block(accept_block(&bill.id, chain.get_latest_block()));
+                chain.try_add_block(mint_block(
+                    &bill.id,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                ));
+                Ok(chain)
+            });
+
+        let service = get_service(ctx);
+
+        let res = service
+            .submit_mint_quote(
+                TEST_BILL_ID,
+                4900,
+                "sat",
+                1731593928 + 3600,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::CallerIsNotMint))
+        ));
+    }
+
+    #[tokio::test]
+    async fn approve_mint_quote_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mint_keys = BcrKeys::new();
+        let mint = identity_public_data_only_node_id(mint_keys.get_public_key());
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let mint_node_id = mint.node_id.clone();
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                chain.try_add_block(accept_block(&bill.id, chain.get_latest_block()));
+                chain.try_add_block(mint_block(&bill.id, chain.get_latest_block(), &mint));
+                chain.try_add_block(mint_quote_block(
+                    &bill.id,
+                    chain.get_latest_block(),
+                    &mint_keys,
+                    1731593928 + 3600,
+                ));
+                Ok(chain)
+            });
+        // the mint is notified about the approval
+        ctx.notification_service
+            .expect_send_quote_is_approved_event()
+            .withf(move |_, mint| mint.node_id == mint_node_id)
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .approve_mint_quote(
+                TEST_BILL_ID,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 5);
+        assert_eq!(chain.blocks()[4].op_code, BillOpCode::ApproveMintQuote);
+    }
+
+    #[tokio::test]
+    async fn approve_mint_quote_fails_if_expired() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mint_keys = BcrKeys::new();
+        let mint = identity_public_data_only_node_id(mint_keys.get_public_key());
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                chain.try_add_block(accept_block(&bill.id, chain.get_latest_block()));
+                chain.try_add_block(mint_block(&bill.id, chain.get_latest_block(), &mint));
+                chain.try_add_block(mint_quote_block(
+                    &bill.id,
+                    chain.get_latest_block(),
+                    &mint_keys,
+                    1731593928,
+                ));
+                Ok(chain)
+            });
+
+        let service = get_service(ctx);
+
+        let res = service
+            .approve_mint_quote(
+                TEST_BILL_ID,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::MintQuoteExpired))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reject_mint_quote_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mint_keys = BcrKeys::new();
+        let mint = identity_public_data_only_node_id(mint_keys.get_public_key());
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                chain.try_add_block(accept_block(&bill.id, chain.get_latest_block()));
+                chain.try_add_block(mint_block(&bill.id, chain.get_latest_block(), &mint));
+                // expired quotes can still be rejected
+                chain.try_add_block(mint_quote_block(
+                    &bill.id,
+                    chain.get_latest_block(),
+                    &mint_keys,
+                    1731593928,
+                ));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_quote_is_rejected_event()
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .reject_mint_quote(
+                TEST_BILL_ID,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 5);
+        assert_eq!(chain.blocks()[4].op_code, BillOpCode::RejectMintQuote);
+    }
+
+    #[tokio::test]
+    async fn offer_to_sell_bitcredit_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Request to sell event should be sent
+        ctx.notification_service
+            .expect_send_offer_to_sell_event()
+            .returning(|_, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::OfferToSell(
+                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    15000,
+                    "sat".to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 2);
+        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::OfferToSell);
+    }
+
+    #[tokio::test]
+    async fn offer_to_sell_bitcredit_bill_fails_if_payee_not_caller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::OfferToSell(
+                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    15000,
+                    "sat".to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn sell_bitcredit_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let buyer_clone = buyer.clone();
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let offer_to_sell = BillBlock::create_block_for_offer_to_sell(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillOfferToSellBlockData {
+                        seller: bill.payee.clone().into(),
+                        buyer: buyer_clone.clone().into(),
+                        currency: "sat".to_owned(),
+                        sum: 15000,
+                        payment_address: "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk0".to_owned(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                        sell_deadline_seconds: None,
+                    },
+                    &BcrKeys::new(),
+                    None,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(offer_to_sell);
+                Ok(chain)
+            });
+        // Request to sell event should be sent
+        ctx.notification_service
+            .expect_send_bill_is_sold_event()
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Sell(
+                    buyer,
+                    15000,
+                    "sat".to_string(),
+                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 3);
+        assert!(res.as_ref().unwrap().blocks()[1].op_code == BillOpCode::OfferToSell);
+        assert!(res.as_ref().unwrap().blocks()[2].op_code == BillOpCode::Sell);
+    }
+
+    #[tokio::test]
+    async fn sell_bitcredit_bill_fails_if_sell_data_is_invalid() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let offer_to_sell = BillBlock::create_block_for_offer_to_sell(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillOfferToSellBlockData {
+                        seller: bill.payee.clone().into(),
+                        buyer: bill.payee.clone().into(), // buyer is seller, which is invalid
+                        currency: "sat".to_owned(),
+                        sum: 10000, // different sum
+                        payment_address: "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk0".to_owned(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                        sell_deadline_seconds: None,
+                    },
+                    &BcrKeys::new(),
+                    None,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(offer_to_sell);
+                Ok(chain)
+            });
+        // Sold event should be sent
+        ctx.notification_service
+            .expect_send_bill_is_sold_event()
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Sell(
+                    buyer,
+                    15000,
+                    "sat".to_string(),
+                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn sell_bitcredit_bill_fails_if_not_offer_to_sell_waiting_for_payment() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Request to sell event should be sent
+        ctx.notification_service
+            .expect_send_bill_is_sold_event()
+            .returning(|_, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Sell(
+                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    15000,
+                    "sat".to_string(),
+                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn sell_bitcredit_bill_fails_if_payee_not_caller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Sell(
+                    identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    15000,
+                    "sat".to_string(),
+                    VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn endorse_bitcredit_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Bill is endorsed event should be sent
+        ctx.notification_service
+            .expect_send_bill_is_endorsed_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Endorse(identity_public_data_only_node_id(
+                    BcrKeys::new().get_public_key(),
+                )),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 2);
+        assert!(res.unwrap().blocks()[1].op_code == BillOpCode::Endorse);
+    }
+
+    #[tokio::test]
+    async fn execute_bill_action_retry_with_idempotency_key_returns_existing_chain() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let endorser = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        let endorsee_clone = endorsee.clone();
+        let endorser_clone = endorser.clone();
+        // the first call already added the endorse block
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let endorse_block = BillBlock::create_block_for_endorse(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillEndorseBlockData {
+                        endorser: endorser_clone.clone().into(),
+                        endorsee: endorsee_clone.clone().into(),
+                        signatory: None,
+                        signing_timestamp: 1731593928,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    None,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593928,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(endorse_block));
+                Ok(chain)
+            });
+        ctx.bill_store
+            .expect_reserve_idempotency_key()
+            .with(eq(TEST_BILL_ID), eq(BillOpCode::Endorse), eq("key"))
+            .times(1)
+            .returning(|_, _, _| Ok(false));
+        ctx.bill_store
+            .expect_get_idempotent_block_id()
+            .with(eq(TEST_BILL_ID), eq(BillOpCode::Endorse), eq("key"))
+            .times(1)
+            .returning(|_, _, _| Ok(Some(2)));
+        // the retry neither adds a block, nor notifies again
+        ctx.bill_blockchain_store.expect_add_block().never();
+        ctx.bill_store.expect_save_idempotency_key().never();
+        ctx.notification_service
+            .expect_send_bill_is_endorsed_event()
+            .never();
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Endorse(endorsee),
+                &endorser,
+                &identity.key_pair,
+                1731593929,
+                Some("key".to_string()),
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().blocks().len(), 2);
+        assert_eq!(res.unwrap().blocks()[1].op_code, BillOpCode::Endorse);
+    }
+
+    #[tokio::test]
+    async fn execute_bill_action_with_idempotency_key_used_for_other_bill() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // the key was only used for an action on a different bill, so it's a new action here
+        ctx.bill_store
+            .expect_reserve_idempotency_key()
+            .with(eq(TEST_BILL_ID), eq(BillOpCode::Endorse), eq("key"))
+            .times(1)
+            .returning(|_, _, _| Ok(true));
+        ctx.bill_store.expect_release_idempotency_key().never();
+        ctx.bill_store
+            .expect_save_idempotency_key()
+            .with(eq(TEST_BILL_ID), eq(BillOpCode::Endorse), eq("key"), eq(2))
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+        ctx.notification_service
+            .expect_send_bill_is_endorsed_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Endorse(identity_public_data_only_node_id(
+                    BcrKeys::new().get_public_key(),
+                )),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                Some("key".to_string()),
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().blocks().len(), 2);
+        assert_eq!(res.unwrap().blocks()[1].op_code, BillOpCode::Endorse);
+    }
+
+    #[tokio::test]
+    async fn execute_bill_action_with_idempotency_key_in_progress() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        // another call reserved the key, but didn't add its block yet
+        ctx.bill_store
+            .expect_reserve_idempotency_key()
+            .returning(|_, _, _| Ok(false));
+        ctx.bill_store
+            .expect_get_idempotent_block_id()
+            .returning(|_, _, _| Ok(None));
+        ctx.bill_blockchain_store.expect_add_block().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Endorse(identity_public_data_only_node_id(
+                    BcrKeys::new().get_public_key(),
+                )),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                Some("key".to_string()),
+            )
+            .await;
+        assert!(matches!(res, Err(Error::InvalidOperation)));
+    }
+
+    #[tokio::test]
+    async fn execute_bill_action_with_idempotency_key_releases_key_on_failure() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        // the caller isn't the holder, so they can't endorse
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store
+            .expect_reserve_idempotency_key()
+            .returning(|_, _, _| Ok(true));
+        ctx.bill_store.expect_save_idempotency_key().never();
+        ctx.bill_store
+            .expect_release_idempotency_key()
+            .with(eq(TEST_BILL_ID), eq(BillOpCode::Endorse), eq("key"))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Endorse(identity_public_data_only_node_id(
+                    BcrKeys::new().get_public_key(),
+                )),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                Some("key".to_string()),
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn endorse_bitcredit_bill_fails_if_waiting_for_offer_to_sell() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(BcrKeys::new().get_public_key()),
+                    None,
+                )));
+                Ok(chain)
+            });
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Endorse(identity_public_data_only_node_id(
+                    BcrKeys::new().get_public_key(),
+                )),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+        match res {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => match e {
+                Error::Validation(ValidationError::BillIsOfferedToSellAndWaitingForPayment) => (),
+                _ => panic!("expected a different error"),
+            },
+        };
+    }
+
+    #[tokio::test]
+    async fn attach_files_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let expected_file_name = "invoice_00000000-0000-0000-0000-000000000000.pdf";
+        let file_bytes = String::from("hello world").as_bytes().to_vec();
+        ctx.file_upload_store
+            .expect_read_temp_upload_file()
+            .returning(move |_| Ok((expected_file_name.to_string(), file_bytes.clone())));
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        // the new file is only saved after the block was added
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("not found"))));
+        ctx.file_upload_store
+            .expect_remove_temp_upload_folder()
+            .returning(|_| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // Files attached event should be sent
+        ctx.notification_service
+            .expect_send_bill_files_attached_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .attach_files(
+                TEST_BILL_ID,
+                &[TEST_BILL_ID.to_string()],
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 2);
+        assert!(res.as_ref().unwrap().blocks()[1].op_code == BillOpCode::AttachFile);
+        let bill_keys = BillKeys {
+            private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+            public_key: TEST_PUB_KEY_SECP.to_owned(),
+        };
+        let attached_files = res.unwrap().get_attached_files(&bill_keys).unwrap();
+        assert_eq!(attached_files.len(), 1);
+        assert_eq!(attached_files[0].name, expected_file_name);
+    }
+
+    #[tokio::test]
+    async fn attach_files_does_not_save_files_if_adding_the_block_fails() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.file_upload_store
+            .expect_read_temp_upload_file()
+            .returning(|_| Ok(("invoice.pdf".to_string(), vec![0; 10])));
+        ctx.file_upload_store.expect_save_attached_file().never();
+        ctx.bill_blockchain_store
+            .expect_add_block()
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("test error"))));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .attach_files(
+                TEST_BILL_ID,
+                &[TEST_BILL_ID.to_string()],
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn attach_files_fails_for_empty_uploads() {
+        let ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let service = get_service(ctx);
+
+        let res = service
+            .attach_files(
+                TEST_BILL_ID,
+                &[],
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        match res {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => match e {
+                Error::Validation(ValidationError::FieldEmpty(Field::Files)) => (),
+                _ => panic!("expected a different error"),
+            },
+        };
+    }
+
+    #[tokio::test]
+    async fn attach_files_fails_if_not_holder() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .attach_files(
+                TEST_BILL_ID,
+                &[TEST_BILL_ID.to_string()],
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        match res {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => match e {
+                Error::Validation(ValidationError::CallerIsNotHolder) => (),
+                _ => panic!("expected a different error"),
+            },
+        };
+    }
+
+    /// attaches one uploaded file per given size to a bill, which already has a stored file with
+    /// the given size, with the test config limits of 3 files and 1000 bytes
+    async fn attach_files_with_sizes(
+        existing_size: usize,
+        file_sizes: &[usize],
+    ) -> Result<BillBlockchain> {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill.files = vec![File {
+            name: "invoice.pdf".to_string(),
+            hash: "some_hash".to_string(),
+        }];
+        let sizes: HashMap<String, usize> = file_sizes
+            .iter()
+            .enumerate()
+            .map(|(idx, size)| (format!("upload_{idx}"), *size))
+            .collect();
+        ctx.file_upload_store
+            .expect_read_temp_upload_file()
+            .returning(move |id| Ok((format!("{id}.pdf"), vec![0; sizes[id]])));
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .with(eq(TEST_BILL_ID), eq("invoice.pdf"))
+            .returning(move |_, _| Ok(vec![0; existing_size]));
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .returning(|_, _| Err(persistence::Error::Io(std::io::Error::other("not found"))));
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .returning(|_, _, _| Ok(()));
+        ctx.file_upload_store
+            .expect_remove_temp_upload_folder()
+            .returning(|_| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service
+            .expect_send_bill_files_attached_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let file_upload_ids: Vec<String> = (0..file_sizes.len())
+            .map(|idx| format!("upload_{idx}"))
+            .collect();
+        service
+            .attach_files(
+                TEST_BILL_ID,
+                &file_upload_ids,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn attach_files_up_to_limits() {
+        let chain = attach_files_with_sizes(800, &[100, 100]).await.unwrap();
+        assert_eq!(chain.get_attached_files(&bill_keys()).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn attach_files_fails_with_too_many_files() {
+        assert!(matches!(
+            attach_files_with_sizes(10, &[10, 10, 10]).await,
+            Err(Error::Validation(ValidationError::TooManyFiles(3)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn attach_files_fails_with_too_big_attachments() {
+        assert!(matches!(
+            attach_files_with_sizes(800, &[100, 101]).await,
+            Err(Error::Validation(ValidationError::AttachmentsTooBig(1000)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn attach_files_fails_for_already_attached_file() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        bill.files = vec![File {
+            name: "invoice.pdf".to_string(),
+            hash: "some_hash".to_string(),
+        }];
+        ctx.file_upload_store
+            .expect_read_temp_upload_file()
+            .returning(|_| Ok(("invoice.pdf".to_string(), vec![])));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .attach_files(
+                TEST_BILL_ID,
+                &[TEST_BILL_ID.to_string()],
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+            )
+            .await;
+        match res {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => match e {
+                Error::Validation(ValidationError::FileAlreadyAttached(_)) => (),
+                _ => panic!("expected a different error"),
+            },
+        };
+    }
+
+    #[tokio::test]
+    async fn endorse_bitcredit_bill_fails_if_payee_not_caller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Endorse(empty_identity_public_data()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_combined_bitcoin_key_for_bill_baseline() {
+        init_test_cfg();
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.key_pair.get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .get_combined_bitcoin_key_for_bill(
+                TEST_BILL_ID,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+            )
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn get_combined_bitcoin_key_for_bill_err() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let non_participant_keys = BcrKeys::new();
+        let res = service
+            .get_combined_bitcoin_key_for_bill(
+                TEST_BILL_ID,
+                &identity_public_data_only_node_id(non_participant_keys.get_public_key()),
+                &non_participant_keys,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_combined_bitcoin_key_for_bill_fails_for_unknown_bill() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| false);
+        ctx.bill_blockchain_store.expect_get_chain().never();
+        let identity = get_baseline_identity();
+        let service = get_service(ctx);
+
+        let res = service
+            .get_combined_bitcoin_key_for_bill(
+                TEST_BILL_ID,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+            )
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn get_payment_address_at_block_derives_from_payee_of_block() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |id| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                chain.try_add_block(offer_to_sell_block(
+                    id,
+                    chain.get_latest_block(),
+                    &buyer,
+                    None,
+                ));
+                Ok(chain)
+            });
+        // the seller of the offer to sell is paid
+        ctx.bitcoin_client
+            .expect_get_address_to_pay()
+            .with(always(), eq(TEST_PUB_KEY_SECP))
+            .times(2)
+            .returning(|_, _| Ok(VALID_PAYMENT_ADDRESS_TESTNET.to_string()));
+        let service = get_service(ctx);
+
+        // offer to sell block
+        let res = service
+            .get_payment_address_at_block(TEST_BILL_ID, 2, &identity.key_pair)
+            .await;
+        assert_eq!(res.unwrap(), VALID_PAYMENT_ADDRESS_TESTNET.to_string());
+        // issue block - the payee is the holder
+        let res = service
+            .get_payment_address_at_block(TEST_BILL_ID, 1, &identity.key_pair)
+            .await;
+        assert_eq!(res.unwrap(), VALID_PAYMENT_ADDRESS_TESTNET.to_string());
+    }
+
+    #[tokio::test]
+    async fn get_payment_address_at_block_err() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        // not a participant
+        let res = service
+            .get_payment_address_at_block(TEST_BILL_ID, 1, &BcrKeys::new())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+        // the block doesn't exist
+        let res = service
+            .get_payment_address_at_block(TEST_BILL_ID, 5, &identity.key_pair)
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn get_bill_payment_transactions_baseline() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // the payee is the holder, who is paid
+        ctx.bitcoin_client
+            .expect_get_address_to_pay()
+            .with(always(), eq(TEST_PUB_KEY_SECP))
+            .returning(|_, _| Ok(VALID_PAYMENT_ADDRESS_TESTNET.to_string()));
+        ctx.bitcoin_client
+            .expect_get_payment_transactions()
+            .with(eq(VALID_PAYMENT_ADDRESS_TESTNET))
+            .returning(|_| {
+                Ok(vec![PaymentTx {
+                    txid: "txid".to_string(),
+                    amount: 500,
+                    confirmations: 3,
+                }])
+            });
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bill_payment_transactions(TEST_BILL_ID, &identity.key_pair)
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].txid, "txid".to_string());
+        assert_eq!(res[0].amount, 500);
+        assert_eq!(res[0].confirmations, 3);
+    }
+
+    #[tokio::test]
+    async fn get_bill_payment_transactions_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(|_| Ok(get_genesis_chain(None)));
+        ctx.bitcoin_client.expect_get_payment_transactions().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bill_payment_transactions(TEST_BILL_ID, &BcrKeys::new())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn check_bills_payment_baseline() {
+        let mut ctx = get_ctx();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        // the mocked bitcoin client received 100 sat
+        bill.sum = 100;
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store
+            .expect_set_to_paid()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service.check_bills_payment().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_payment_is_skipped_while_jobs_are_paused() {
+        let mut ctx = get_ctx();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.sum = 100;
+        // the stores are only hit once - by the tick after resuming the jobs
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_payment()
+            .times(1)
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store
+            .expect_set_to_paid()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+        let job_control = JobControl::new();
+        let tick = || async {
+            service
+                .check_bills_payment()
+                .await
+                .expect("check bills payment job failed");
+        };
+
+        job_control.pause_jobs();
+        assert!(!job_control.run_tick(tick()).await);
+
+        job_control.resume_jobs();
+        assert!(job_control.run_tick(tick()).await);
+    }
+
+    #[tokio::test]
+    async fn check_bills_payment_waits_for_required_confirmations() {
+        let mut ctx = get_ctx();
+        let mut seq = mockall::Sequence::new();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.sum = 100;
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+
+        // the test config requires 3 confirmations - the payment gets confirmed by each new block
+        for confirmations in 1..=3 {
+            ctx.bitcoin_client
+                .expect_get_payment_confirmations()
+                .times(1)
+                .in_sequence(&mut seq)
+                .returning(move |_| Ok(confirmations));
+        }
+        ctx.bill_store
+            .expect_set_to_paid()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _| Ok(()));
+        let service = get_service(ctx);
+
+        for _ in 0..3 {
+            let res = service.check_bills_payment().await;
+            assert!(res.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn check_bills_payment_partially_paid() {
+        let mut ctx = get_ctx();
+        // the mocked bitcoin client received 100 sat, the baseline bill is 5000 sat
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store
+            .expect_set_paid_sum()
+            .with(eq(TEST_BILL_ID), always(), eq(100))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        ctx.bill_store.expect_set_to_paid().never();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service.check_bills_payment().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_payment_does_not_update_unchanged_paid_sum() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store.expect_get_paid_sum().returning(|_| Ok(100));
+        ctx.bill_store.expect_set_paid_sum().never();
+        ctx.bill_store.expect_set_to_paid().never();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service.check_bills_payment().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_payment_skips_held_bills() {
+        let mut ctx = get_ctx();
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_store.expect_get_hold().returning(|_| {
+            Ok(Some(BillHold {
+                reason: "disputed".to_string(),
+                timestamp: 1731593928,
+            }))
+        });
+        ctx.bill_store.expect_set_to_paid().never();
+        ctx.bill_blockchain_store.expect_get_chain().never();
+        let service = get_service(ctx);
+
+        let res = service.check_bills_payment().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn set_bill_hold_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store
+            .expect_set_hold()
+            .with(
+                eq(TEST_BILL_ID),
+                eq(BillHold {
+                    reason: "disputed".to_string(),
+                    timestamp: 1731593928,
+                }),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .set_bill_hold(
+                TEST_BILL_ID,
+                " disputed ",
+                &identity.identity.node_id,
+                1731593928,
+            )
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn set_bill_hold_fails_for_blank_reason() {
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_set_hold().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .set_bill_hold(
+                TEST_BILL_ID,
+                "  ",
+                &get_baseline_identity().identity.node_id,
+                1731593928,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn set_bill_hold_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store.expect_set_hold().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .set_bill_hold(
+                TEST_BILL_ID,
+                "disputed",
+                &BcrKeys::new().get_public_key(),
+                1731593928,
+            )
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn clear_bill_hold_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store
+            .expect_clear_hold()
+            .with(eq(TEST_BILL_ID))
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .clear_bill_hold(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn archive_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let node_id = identity.identity.node_id.clone();
+        ctx.bill_store
+            .expect_archive()
+            .with(eq(TEST_BILL_ID), eq(node_id))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .archive_bill(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn archive_bill_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store.expect_archive().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .archive_bill(TEST_BILL_ID, &BcrKeys::new().get_public_key())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn unarchive_bill_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let node_id = identity.identity.node_id.clone();
+        ctx.bill_store
+            .expect_unarchive()
+            .with(eq(TEST_BILL_ID), eq(node_id))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .unarchive_bill(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn set_bill_note_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let node_id = identity.identity.node_id.clone();
+        ctx.bill_store
+            .expect_set_note()
+            .with(eq(TEST_BILL_ID), eq(node_id), eq("called customer"))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        ctx.bill_store.expect_remove_note().never();
+        // the note is never cached with the bill
+        ctx.bill_store.expect_invalidate_bill_in_cache().never();
+        ctx.bill_store.expect_save_bill_to_cache().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .set_bill_note(
+                TEST_BILL_ID,
+                &identity.identity.node_id,
+                Some(" called customer ".to_string()),
+            )
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn set_bill_note_removes_empty_note() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let node_id = identity.identity.node_id.clone();
+        ctx.bill_store
+            .expect_remove_note()
+            .with(eq(TEST_BILL_ID), eq(node_id))
+            .times(2)
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_set_note().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .set_bill_note(TEST_BILL_ID, &identity.identity.node_id, None)
+            .await;
+        assert!(res.is_ok());
+        let res_empty = service
+            .set_bill_note(
+                TEST_BILL_ID,
+                &identity.identity.node_id,
+                Some("  ".to_string()),
+            )
+            .await;
+        assert!(res_empty.is_ok());
+    }
+
+    #[tokio::test]
+    async fn set_bill_note_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.bill_store.expect_set_note().never();
+        let service = get_service(ctx);
+
+        let res = service
+            .set_bill_note(
+                TEST_BILL_ID,
+                &BcrKeys::new().get_public_key(),
+                Some("called customer".to_string()),
+            )
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn check_bills_offer_to_sell_payment_baseline() {
+        let mut ctx = get_ctx();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
+        ctx.bitcoin_client
+            .expect_get_confirmed_paid_sum()
+            .returning(|_| Ok(15000));
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_sell_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        let buyer_node_id = BcrKeys::new().get_public_key();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(buyer_node_id.clone()),
+                    Some(util::date::now().timestamp() as u64),
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_bill_is_sold_event()
+            .returning(|_, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service.check_bills_offer_to_sell_payment().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_offer_to_sell_payment_company_is_seller() {
+        let mut ctx = get_ctx();
+        let mut identity = get_baseline_identity();
+        identity.key_pair = BcrKeys::new();
+        identity.identity.node_id = identity.key_pair.get_public_key();
+
+        let company = get_baseline_company_data();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::from(company.1.0.clone());
+
+        ctx.bitcoin_client
+            .expect_get_confirmed_paid_sum()
+            .returning(|_| Ok(15000));
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_sell_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        let company_clone = company.clone();
+        ctx.company_store.expect_get_all().returning(move || {
+            let mut map = HashMap::new();
+            map.insert(
+                company_clone.0.clone(),
+                (company_clone.1.0.clone(), company_clone.1.1.clone()),
+            );
+            Ok(map)
+        });
+        let buyer_node_id = BcrKeys::new().get_public_key();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &identity_public_data_only_node_id(buyer_node_id.clone()),
+                    Some(util::date::now().timestamp() as u64),
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_bill_is_sold_event()
+            .returning(|_, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service.check_bills_offer_to_sell_payment().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_offer_to_sell_payment_waits_for_confirmed_payment() {
+        // (confirmed sum, confirmations) - the test config requires 3 confirmations and funds in
+        // the mempool aren't part of the confirmed sum
+        for (confirmed_sum, confirmations) in [(0, 0), (15000, 2)] {
+            let mut ctx = get_ctx();
+            let mut bill = get_baseline_bill(TEST_BILL_ID);
+            bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
+            ctx.bitcoin_client
+                .expect_get_confirmed_paid_sum()
+                .returning(move |_| Ok(confirmed_sum));
+            ctx.bitcoin_client
+                .expect_get_payment_confirmations()
+                .returning(move |_| Ok(confirmations));
+            ctx.bill_store
+                .expect_get_bill_ids_waiting_for_sell_payment()
+                .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+            let buyer_node_id = BcrKeys::new().get_public_key();
+            ctx.bill_blockchain_store
+                .expect_get_chain()
+                .returning(move |_| {
+                    let mut chain = get_genesis_chain(Some(bill.clone()));
+                    assert!(chain.try_add_block(offer_to_sell_block(
+                        TEST_BILL_ID,
+                        chain.get_latest_block(),
+                        &identity_public_data_only_node_id(buyer_node_id.clone()),
+                        Some(util::date::now().timestamp() as u64),
+                    )));
+                    Ok(chain)
+                });
+            // the bill isn't sold yet
+            ctx.bill_blockchain_store.expect_add_block().never();
+            ctx.notification_service
+                .expect_send_bill_is_sold_event()
+                .never();
+
+            let res = get_service(ctx).check_bills_offer_to_sell_payment().await;
+            assert!(res.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn check_bills_timeouts_does_nothing_if_not_timed_out() {
+        let mut ctx = get_ctx();
+        let op_codes = HashSet::from([
+            BillOpCode::RequestToAccept,
+            BillOpCode::RequestToPay,
+            BillOpCode::OfferToSell,
+            BillOpCode::RequestRecourse,
+        ]);
+
+        // fetches bill ids
+        ctx.bill_store
+            .expect_get_bill_ids_with_op_codes_since()
+            .with(eq(op_codes.clone()), eq(0))
+            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
+        // fetches bill chain accept
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID.to_string()))
+            .returning(|id| {
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
+                chain.try_add_block(request_to_accept_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+        // fetches bill chain pay
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq("4321".to_string()))
+            .returning(|id| {
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
+                chain.try_add_block(request_to_pay_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+        let service = get_service(ctx);
+
+        // now is the same as block created time so no timeout should have happened
+        let res = service.check_bills_timeouts(1000).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_timeouts_uses_bill_payment_deadline() {
+        let mut ctx = get_ctx();
+        let op_codes = HashSet::from([
+            BillOpCode::RequestToAccept,
+            BillOpCode::RequestToPay,
+            BillOpCode::OfferToSell,
+            BillOpCode::RequestRecourse,
+        ]);
+
+        ctx.bill_store
+            .expect_get_bill_ids_with_op_codes_since()
+            .with(eq(op_codes.clone()), eq(0))
+            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string()]));
+
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID.to_string()))
+            .returning(|id| {
+                let mut bill = get_baseline_bill(id);
+                bill.payment_deadline_seconds = PAYMENT_DEADLINE_SECONDS * 7;
+                let mut chain = get_genesis_chain(Some(bill));
+                chain.try_add_block(request_to_pay_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+        // the global deadline passed, but not the one of the bill, so no notification is checked
+        // or sent
+        let service = get_service(ctx);
+
+        let res = service
+            .check_bills_timeouts(1731593920 + PAYMENT_DEADLINE_SECONDS + 1100)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_timeouts_does_nothing_if_notifications_are_already_sent() {
+        let mut ctx = get_ctx();
+        let op_codes = HashSet::from([
+            BillOpCode::RequestToAccept,
+            BillOpCode::RequestToPay,
+            BillOpCode::OfferToSell,
+            BillOpCode::RequestRecourse,
+        ]);
+
+        // fetches bill ids
+        ctx.bill_store
+            .expect_get_bill_ids_with_op_codes_since()
+            .with(eq(op_codes.clone()), eq(0))
+            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
+
+        // fetches bill chain accept
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID.to_string()))
+            .returning(|id| {
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
+                chain.try_add_block(request_to_accept_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+
+        // fetches bill chain pay
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq("4321".to_string()))
+            .returning(|id| {
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
+                chain.try_add_block(request_to_pay_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+        // notification already sent
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
+            .returning(|_, _, _| Ok(true));
+
+        // notification already sent
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq("4321"), eq(2), eq(ActionType::PayBill))
+            .returning(|_, _, _| Ok(true));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .check_bills_timeouts(PAYMENT_DEADLINE_SECONDS + 1100)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_timeouts() {
+        let mut ctx = get_ctx();
+        let op_codes = HashSet::from([
+            BillOpCode::RequestToAccept,
+            BillOpCode::RequestToPay,
+            BillOpCode::OfferToSell,
+            BillOpCode::RequestRecourse,
+        ]);
+
+        // fetches bill ids
+        ctx.bill_store
+            .expect_get_bill_ids_with_op_codes_since()
+            .with(eq(op_codes.clone()), eq(0))
+            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string(), "4321".to_string()]));
+
+        // fetches bill chain accept
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID.to_string()))
+            .returning(|id| {
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
+                chain.try_add_block(request_to_accept_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+
+        // fetches bill chain pay
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq("4321".to_string()))
+            .returning(|id| {
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
+                chain.try_add_block(request_to_pay_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+
+        // notification not sent
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
+            .returning(|_, _, _| Ok(false));
+
+        // notification not sent
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq("4321"), eq(2), eq(ActionType::PayBill))
+            .returning(|_, _, _| Ok(false));
+
+        // we should have at least two participants
+        let recipient_check = function(|r: &Vec<IdentityPublicData>| r.len() >= 2);
+
+        // send accept timeout notification
+        ctx.notification_service
+            .expect_send_request_to_action_timed_out_event()
+            .with(
+                always(),
+                eq(TEST_BILL_ID),
+                always(),
+                eq(ActionType::AcceptBill),
+                recipient_check.clone(),
+            )
+            .returning(|_, _, _, _, _| Ok(()));
+
+        // send pay timeout notification
+        ctx.notification_service
+            .expect_send_request_to_action_timed_out_event()
+            .with(
+                always(),
+                eq("4321"),
+                always(),
+                eq(ActionType::PayBill),
+                recipient_check,
+            )
+            .returning(|_, _, _, _, _| Ok(()));
+
+        // marks accept bill timeout as sent
+        ctx.notification_service
+            .expect_mark_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
+            .returning(|_, _, _| Ok(()));
+
+        // marks pay bill timeout as sent
+        ctx.notification_service
+            .expect_mark_bill_notification_sent()
+            .with(eq("4321"), eq(2), eq(ActionType::PayBill))
+            .returning(|_, _, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .check_bills_timeouts(PAYMENT_DEADLINE_SECONDS + 1100)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_timeouts_warns_expected_actor_once_before_timeout() {
+        let mut ctx = get_ctx();
+        let mut seq = mockall::Sequence::new();
+        let request_ts = 1731593921;
+        let deadline = request_ts + ACCEPT_DEADLINE_SECONDS;
+
+        ctx.bill_store
+            .expect_get_bill_ids_with_op_codes_since()
+            .returning(|_, _| Ok(vec![TEST_BILL_ID.to_string()]));
+        // the current identity is the drawee, who has to accept
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID.to_string()))
+            .returning(|id| {
+                let mut chain = get_genesis_chain(Some(get_baseline_bill(id)));
+                chain.try_add_block(request_to_accept_block(id, chain.get_latest_block(), None));
+                Ok(chain)
+            });
+
+        // warning not sent yet
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(-2), eq(ActionType::AcceptBill))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(false));
+
+        // warns the drawee
+        ctx.notification_service
+            .expect_send_request_to_action_timeout_approaching_event()
+            .withf(|_, bill_id, _, action, recipient| {
+                bill_id == TEST_BILL_ID
+                    && *action == ActionType::AcceptBill
+                    && recipient.node_id == get_baseline_identity().identity.node_id
+            })
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _, _| Ok(()));
+
+        // remembers the warning was sent
+        ctx.notification_service
+            .expect_mark_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(-2), eq(ActionType::AcceptBill))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(()));
+
+        // on the next run, the warning was already sent
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(-2), eq(ActionType::AcceptBill))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(true));
+
+        // after the deadline, the timeout is still sent
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(false));
+        ctx.notification_service
+            .expect_send_request_to_action_timed_out_event()
+            .with(
+                always(),
+                eq(TEST_BILL_ID),
+                always(),
+                eq(ActionType::AcceptBill),
+                always(),
+            )
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _, _| Ok(()));
+        ctx.notification_service
+            .expect_mark_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(2), eq(ActionType::AcceptBill))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service.check_bills_timeouts(deadline - 100).await;
+        assert!(res.is_ok());
+        let res = service.check_bills_timeouts(deadline - 50).await;
+        assert!(res.is_ok());
+        let res = service.check_bills_timeouts(deadline + 1).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_maturity_reminders_sends_reminder_once() {
+        let mut ctx = get_ctx();
+        let mut seq = mockall::Sequence::new();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let maturity_ts = util::date::date_string_to_timestamp(&bill.maturity_date, None).unwrap();
+        let now = maturity_ts - 86400;
+
+        ctx.company_store
+            .expect_get_all()
+            .returning(|| Ok(HashMap::new()));
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+
+        // reminder not sent yet
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(1), eq(ActionType::RequestPayment))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(false));
+
+        // sends the reminder to the holder
+        ctx.notification_service
+            .expect_send_maturity_approaching_event()
+            .withf(|_, bill_id, _, recipient| {
+                bill_id == TEST_BILL_ID
+                    && recipient.node_id == get_baseline_identity().identity.node_id
+            })
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _| Ok(()));
+
+        // remembers the reminder was sent
+        ctx.notification_service
+            .expect_mark_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(1), eq(ActionType::RequestPayment))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(()));
+
+        // on the next run, the reminder was already sent
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .with(eq(TEST_BILL_ID), eq(1), eq(ActionType::RequestPayment))
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _| Ok(true));
+
+        let service = get_service(ctx);
+
+        let res = service.check_bills_maturity_reminders(now, 259200).await;
+        assert!(res.is_ok());
+        let res = service
+            .check_bills_maturity_reminders(now + 3600, 259200)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_maturity_reminders_does_nothing_if_not_within_lead_time() {
+        let mut ctx = get_ctx();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let maturity_ts = util::date::date_string_to_timestamp(&bill.maturity_date, None).unwrap();
+
+        ctx.company_store
+            .expect_get_all()
+            .returning(|| Ok(HashMap::new()));
+        ctx.bill_store
+            .expect_get_ids()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service
+            .expect_check_bill_notification_sent()
+            .never();
+        ctx.notification_service
+            .expect_send_maturity_approaching_event()
+            .never();
+
+        let service = get_service(ctx);
+
+        // maturity is further away than the lead time
+        let res = service
+            .check_bills_maturity_reminders(maturity_ts - 4 * 86400, 259200)
+            .await;
+        assert!(res.is_ok());
+        // maturity has already passed
+        let res = service
+            .check_bills_maturity_reminders(maturity_ts + 86400, 259200)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn get_bill_history_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                Ok(chain)
+            });
+
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bill_history(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        let history = res.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].block_height, 1);
+        assert_eq!(history[0].op_code, BillOpCode::Issue);
+        assert_eq!(history[1].block_height, 2);
+        assert_eq!(history[1].op_code, BillOpCode::RequestToPay);
+        assert_eq!(history[1].summary, "Requested payment in sat".to_string());
+    }
+
+    #[tokio::test]
+    async fn get_bill_history_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .get_bill_history(TEST_BILL_ID, &BcrKeys::new().get_public_key())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn resend_bill_event_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let payee_node_id = bill.payee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_pay_block =
+                    request_to_pay_block(TEST_BILL_ID, chain.get_latest_block(), None);
+                assert!(chain.try_add_block(req_to_pay_block));
+                Ok(chain)
+            });
+        let expected_node_id = payee_node_id.clone();
+        // the event is reconstructed for the chain up to the given block
+        ctx.notification_service
+            .expect_resend_bill_event()
+            .withf(move |e, node_id| e.bill.id == TEST_BILL_ID && node_id == expected_node_id)
+            .returning(|_, _| Ok(()))
+            .times(1);
+
+        let service = get_service(ctx);
+
+        let res = service
+            .resend_bill_event(TEST_BILL_ID, 1, &payee_node_id, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn resend_bill_event_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let payee_node_id = bill.payee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service.expect_resend_bill_event().never();
+
+        let service = get_service(ctx);
+
+        let res = service
+            .resend_bill_event(
+                TEST_BILL_ID,
+                1,
+                &payee_node_id,
+                &BcrKeys::new().get_public_key(),
+            )
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn resend_bill_event_fails_for_missing_block() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let payee_node_id = bill.payee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service.expect_resend_bill_event().never();
+
+        let service = get_service(ctx);
+
+        for block_height in [0, 2] {
+            let res = service
+                .resend_bill_event(
+                    TEST_BILL_ID,
+                    block_height,
+                    &payee_node_id,
+                    &identity.identity.node_id,
+                )
+                .await;
+            assert!(matches!(res, Err(Error::NotFound)));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_endorsements_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .get_endorsements(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn get_endorsements_multi() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let mint_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let endorse_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let sell_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawer = drawer.clone();
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let endorse_endorsee_clone = endorse_endorsee.clone();
+        let mint_endorsee_clone = mint_endorsee.clone();
+        let sell_endorsee_clone = sell_endorsee.clone();
+
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let now = util::date::now().timestamp() as u64;
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                // add endorse block from payee to endorsee
+                let endorse_block = BillBlock::create_block_for_endorse(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillEndorseBlockData {
+                        endorsee: endorse_endorsee.clone().into(),
+                        // endorsed by payee
+                        endorser: IdentityPublicData::new(get_baseline_identity().identity)
+                            .unwrap()
+                            .into(),
+                        signatory: None,
+                        signing_timestamp: now + 1,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 1,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(endorse_block));
+
+                // add sell block from endorsee to sell endorsee
+                let sell_block = BillBlock::create_block_for_sell(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillSellBlockData {
+                        buyer: sell_endorsee.clone().into(),
+                        // endorsed by endorsee
+                        seller: endorse_endorsee.clone().into(),
+                        currency: "sat".to_string(),
+                        sum: 15000,
+                        payment_address: VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
+                        signatory: None,
+                        signing_timestamp: now + 2,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 2,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(sell_block));
+
+                // add mint block from sell endorsee to mint endorsee
+                let mint_block = BillBlock::create_block_for_mint(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillMintBlockData {
+                        endorsee: mint_endorsee.clone().into(),
+                        // endorsed by sell endorsee
+                        endorser: sell_endorsee.clone().into(),
+                        currency: "sat".to_string(),
+                        sum: 15000,
+                        signatory: None,
+                        signing_timestamp: now + 3,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 3,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(mint_block));
+
+                Ok(chain)
+            });
+
+        let service = get_service(ctx);
+
+        let res = service
+            .get_endorsements(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        // with duplicates
+        assert_eq!(res.as_ref().unwrap().len(), 3);
+        // mint was last, so it's first
+        assert_eq!(
+            res.as_ref().unwrap()[0].pay_to_the_order_of.node_id,
+            mint_endorsee_clone.node_id
+        );
+        assert_eq!(
+            res.as_ref().unwrap()[1].pay_to_the_order_of.node_id,
+            sell_endorsee_clone.node_id
+        );
+        assert_eq!(
+            res.as_ref().unwrap()[2].pay_to_the_order_of.node_id,
+            endorse_endorsee_clone.node_id
+        );
+    }
+
+    #[tokio::test]
+    async fn get_endorsement_count_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+                // two transfers, each with an offer to sell, which doesn't count
+                for _ in 0..2 {
+                    assert!(chain.try_add_block(offer_to_sell_block(
+                        TEST_BILL_ID,
+                        chain.get_latest_block(),
+                        &buyer,
+                        None
+                    )));
+                    assert!(chain.try_add_block(sell_block(
+                        TEST_BILL_ID,
+                        chain.get_latest_block(),
+                        &buyer
+                    )));
+                }
+                Ok(chain)
+            })
+            .times(1);
+        let service = get_service(ctx);
+
+        let res = service
+            .get_endorsement_count(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert_eq!(res.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn get_endorsement_count_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .get_endorsement_count(TEST_BILL_ID, &BcrKeys::new().get_public_key())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn get_past_endorsees_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .get_past_endorsees(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        // if we're the drawee and drawer, there's no holder before us
+        assert_eq!(res.as_ref().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn get_past_endorsees_fails_if_not_my_bill() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .get_past_endorsees(TEST_BILL_ID, "some_other_node_id")
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn get_past_endorsees_3_party() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawer = drawer.clone();
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .get_past_endorsees(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        // if it's a 3 party bill and we're the payee, the drawer is a previous holder
+        assert_eq!(res.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            res.as_ref().unwrap()[0].pay_to_the_order_of.node_id,
+            drawer.node_id
+        );
+    }
+
+    #[tokio::test]
+    async fn is_past_holder_3_party() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.drawer = drawer.clone();
+        bill.drawee = drawee.clone();
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        // the drawer is a previous holder, the drawee never held the bill
+        let res = service
+            .is_past_holder(TEST_BILL_ID, &drawer.node_id, &identity.identity.node_id)
+            .await;
+        assert!(res.unwrap());
+        let res = service
+            .is_past_holder(TEST_BILL_ID, &drawee.node_id, &identity.identity.node_id)
+            .await;
+        assert!(!res.unwrap());
+    }
+
+    #[tokio::test]
+    async fn is_past_holder_fails_if_not_my_bill() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .is_past_holder(
+                TEST_BILL_ID,
+                &identity.identity.node_id,
+                "some_other_node_id",
+            )
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn get_past_endorsees_multi() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let mint_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let endorse_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let sell_endorsee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+
+        bill.drawer = drawer.clone();
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        let endorse_endorsee_clone = endorse_endorsee.clone();
+        let mint_endorsee_clone = mint_endorsee.clone();
+        let sell_endorsee_clone = sell_endorsee.clone();
+
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let now = util::date::now().timestamp() as u64;
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                // add endorse block from payee to endorsee
+                let endorse_block = BillBlock::create_block_for_endorse(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillEndorseBlockData {
+                        endorsee: endorse_endorsee.clone().into(),
+                        // endorsed by payee
+                        endorser: IdentityPublicData::new(get_baseline_identity().identity)
+                            .unwrap()
+                            .into(),
+                        signatory: None,
+                        signing_timestamp: now + 1,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 1,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(endorse_block));
+
+                // add sell block from endorsee to sell endorsee
+                let sell_block = BillBlock::create_block_for_sell(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillSellBlockData {
+                        buyer: sell_endorsee.clone().into(),
+                        // endorsed by endorsee
+                        seller: endorse_endorsee.clone().into(),
+                        currency: "sat".to_string(),
+                        sum: 15000,
+                        payment_address: VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
+                        signatory: None,
+                        signing_timestamp: now + 2,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 2,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(sell_block));
+
+                // add mint block from sell endorsee to mint endorsee
+                let mint_block = BillBlock::create_block_for_mint(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillMintBlockData {
+                        endorsee: mint_endorsee.clone().into(),
+                        // endorsed by sell endorsee
+                        endorser: sell_endorsee.clone().into(),
+                        currency: "sat".to_string(),
+                        sum: 15000,
+                        signatory: None,
+                        signing_timestamp: now + 3,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 3,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(mint_block));
+
+                // add endorse block back to endorsee
+                let endorse_block_back = BillBlock::create_block_for_endorse(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillEndorseBlockData {
+                        endorsee: endorse_endorsee.clone().into(),
+                        // endorsed by payee
+                        endorser: mint_endorsee.clone().into(),
+                        signatory: None,
+                        signing_timestamp: now + 4,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 4,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(endorse_block_back));
+
+                // add endorse block back to payee (caller)
+                let endorse_block_last = BillBlock::create_block_for_endorse(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillEndorseBlockData {
+                        endorsee: IdentityPublicData::new(get_baseline_identity().identity)
+                            .unwrap()
                             .into(),
+                        // endorsed by payee
+                        endorser: endorse_endorsee.clone().into(),
+                        signatory: None,
+                        signing_timestamp: now + 5,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 5,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(endorse_block_last));
+
+                Ok(chain)
+            });
+        let service = get_service(ctx);
+
+        let res = service
+            .get_past_endorsees(TEST_BILL_ID, &identity.identity.node_id)
+            .await;
+        assert!(res.is_ok());
+        // if there are mint, sell and endorse blocks, they are considered
+        // but without duplicates
+        assert_eq!(res.as_ref().unwrap().len(), 4);
+        // endorse endorsee is the one directly before
+        assert_eq!(
+            res.as_ref().unwrap()[0].pay_to_the_order_of.node_id,
+            endorse_endorsee_clone.node_id
+        );
+        // mint endorsee is the one after that
+        assert_eq!(
+            res.as_ref().unwrap()[1].pay_to_the_order_of.node_id,
+            mint_endorsee_clone.node_id
+        );
+        // sell endorsee is the next one
+        assert_eq!(
+            res.as_ref().unwrap()[2].pay_to_the_order_of.node_id,
+            sell_endorsee_clone.node_id
+        );
+        // drawer is the last one, because endorse endorsee is already there
+        // and drawer != drawee
+        assert_eq!(
+            res.as_ref().unwrap()[3].pay_to_the_order_of.node_id,
+            drawer.node_id
+        );
+    }
+
+    #[tokio::test]
+    async fn past_payments_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+
+        let identity_clone = identity.identity.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        // paid
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                // req to pay
+                assert!(chain.try_add_block(request_to_pay_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    None,
+                )));
+                // paid
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    None,
+                )));
+                assert!(chain.try_add_block(sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                )));
+                // rejected
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    None,
+                )));
+                assert!(
+                    chain.try_add_block(reject_buy_block(TEST_BILL_ID, chain.get_latest_block(),))
+                );
+                // expired
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    None,
+                )));
+                // active
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    Some(1931593928),
+                )));
+
+                Ok(chain)
+            });
+
+        let service = get_service(ctx);
+
+        let res_past_payments = service
+            .get_past_payments(
+                TEST_BILL_ID,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1931593928,
+            )
+            .await;
+
+        assert!(res_past_payments.is_ok());
+        assert_eq!(res_past_payments.as_ref().unwrap().len(), 4);
+        match res_past_payments.as_ref().unwrap()[0] {
+            PastPaymentResult::Payment(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Paid(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+        match res_past_payments.as_ref().unwrap()[1] {
+            PastPaymentResult::Sell(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Paid(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+        match res_past_payments.as_ref().unwrap()[2] {
+            PastPaymentResult::Sell(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Rejected(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+        match res_past_payments.as_ref().unwrap()[3] {
+            PastPaymentResult::Sell(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Expired(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+    }
+
+    #[tokio::test]
+    async fn past_payments_recourse() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+
+        let identity_clone = identity.identity.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        // not paid
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                // req to pay
+                assert!(chain.try_add_block(request_to_pay_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    None,
+                )));
+                // reject payment
+                assert!(
+                    chain
+                        .try_add_block(
+                            reject_to_pay_block(TEST_BILL_ID, chain.get_latest_block(),)
+                        )
+                );
+                // req to recourse
+                assert!(chain.try_add_block(request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    None,
+                )));
+                // recourse - paid
+                assert!(chain.try_add_block(recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                )));
+                // req to recourse
+                assert!(chain.try_add_block(request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    None,
+                )));
+                // reject
+                assert!(chain.try_add_block(reject_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                )));
+                // expired
+                assert!(chain.try_add_block(request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    None,
+                )));
+                // active
+                assert!(chain.try_add_block(request_to_recourse_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    Some(1931593928),
+                )));
+
+                Ok(chain)
+            });
+
+        let service = get_service(ctx);
+
+        let res_past_payments = service
+            .get_past_payments(
+                TEST_BILL_ID,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1931593928,
+            )
+            .await;
+
+        assert!(res_past_payments.is_ok());
+        assert_eq!(res_past_payments.as_ref().unwrap().len(), 4);
+        match res_past_payments.as_ref().unwrap()[0] {
+            PastPaymentResult::Payment(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Rejected(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+        match res_past_payments.as_ref().unwrap()[1] {
+            PastPaymentResult::Recourse(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Paid(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+        match res_past_payments.as_ref().unwrap()[2] {
+            PastPaymentResult::Recourse(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Rejected(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+        match res_past_payments.as_ref().unwrap()[3] {
+            PastPaymentResult::Recourse(ref data) => {
+                assert!(matches!(data.status, PastPaymentStatus::Expired(_)));
+            }
+            _ => panic!("wrong result"),
+        };
+    }
+
+    #[tokio::test]
+    async fn reject_acceptance_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let payee = bill.payee.clone();
+        let now = util::date::now().timestamp() as u64;
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                // add req to accept block
+                let req_to_accept = BillBlock::create_block_for_request_to_accept(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillRequestToAcceptBlockData {
+                        requester: payee.clone().into(),
                         signatory: None,
                         signing_timestamp: now + 1,
                         signing_address: empty_address(),
@@ -3282,1018 +8860,1938 @@ pub mod tests {
                     now + 1,
                 )
                 .unwrap();
-                assert!(chain.try_add_block(endorse_block));
+                assert!(chain.try_add_block(req_to_accept));
+
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_request_to_action_rejected_event()
+            .with(always(), eq(ActionType::AcceptBill), eq(None))
+            .returning(|_, _, _| Ok(()));
+
+        let service = get_service(ctx);
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RejectAcceptance(None),
+                &IdentityPublicData::new(identity.identity).unwrap(),
+                &identity.key_pair,
+                now + 2,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(
+            res.as_ref().unwrap().blocks()[2].op_code,
+            BillOpCode::RejectToAccept
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_acceptance_with_reason() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let payee = bill.payee.clone();
+        let now = util::date::now().timestamp() as u64;
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_accept = BillBlock::create_block_for_request_to_accept(
+                    TEST_BILL_ID.to_string(),
+                    chain.get_latest_block(),
+                    &BillRequestToAcceptBlockData {
+                        requester: payee.clone().into(),
+                        signatory: None,
+                        signing_timestamp: now + 1,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    now + 1,
+                )
+                .unwrap();
+                assert!(chain.try_add_block(req_to_accept));
+
+                Ok(chain)
+            });
+        // the reason is passed on to the rejection notification
+        ctx.notification_service
+            .expect_send_request_to_action_rejected_event()
+            .with(
+                always(),
+                eq(ActionType::AcceptBill),
+                eq(Some("goods not received".to_string())),
+            )
+            .returning(|_, _, _| Ok(()));
+
+        let service = get_service(ctx);
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RejectAcceptance(Some("goods not received".to_string())),
+                &IdentityPublicData::new(identity.identity).unwrap(),
+                &identity.key_pair,
+                now + 2,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks()[2].op_code, BillOpCode::RejectToAccept);
+        assert_eq!(
+            chain
+                .get_reject_reason(&bill_keys(), BillOpCode::RejectToAccept)
+                .unwrap(),
+            Some("goods not received".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_buying_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+
+        let identity_clone = identity.identity.clone();
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    None,
+                )));
+
+                Ok(chain)
+            });
+
+        ctx.notification_service
+            .expect_send_request_to_action_rejected_event()
+            .with(always(), eq(ActionType::BuyBill), eq(None))
+            .returning(|_, _, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RejectBuying(None),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(
+            res.as_ref().unwrap().blocks()[2].op_code,
+            BillOpCode::RejectToBuy
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_offer_to_sell_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &buyer,
+                    None,
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_offer_to_sell_cancelled_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::CancelOfferToSell,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(
+            res.as_ref().unwrap().blocks()[2].op_code,
+            BillOpCode::CancelOfferToSell
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_offer_to_sell_fails_for_non_seller() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(offer_to_sell_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    &buyer,
+                    None,
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_offer_to_sell_cancelled_event()
+            .never();
+        let service = get_service(ctx);
+
+        let caller_keys = BcrKeys::new();
+        let mut caller = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        caller.node_id = caller_keys.get_public_key();
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::CancelOfferToSell,
+                &caller,
+                &caller_keys,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::CallerIsNotSeller))
+        ));
+    }
+
+    #[tokio::test]
+    async fn cancel_request_to_accept_and_request_again() {
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+
+        // cancel the outstanding request to accept
+        let mut ctx = get_ctx();
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(request_to_accept_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    None,
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_request_to_accept_cancelled_event()
+            .returning(|_| Ok(()));
+
+        let res = get_service(ctx)
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::CancelRequestToAccept,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let cancelled_chain = res.unwrap();
+        assert_eq!(
+            cancelled_chain.blocks()[2].op_code,
+            BillOpCode::CancelRequestToAccept
+        );
+
+        // the acceptance can be requested again afterwards
+        let mut ctx = get_ctx();
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(cancelled_chain.clone()));
+        ctx.notification_service
+            .expect_send_request_to_accept_event()
+            .returning(|_| Ok(()));
+
+        let res = get_service(ctx)
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RequestAcceptance,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593929,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().blocks().len(), 4);
+        assert_eq!(
+            res.as_ref().unwrap().blocks()[3].op_code,
+            BillOpCode::RequestToAccept
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_request_to_accept_fails_for_non_requester() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(request_to_accept_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    None,
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_request_to_accept_cancelled_event()
+            .never();
+        let service = get_service(ctx);
+
+        let caller_keys = BcrKeys::new();
+        let mut caller = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        caller.node_id = caller_keys.get_public_key();
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::CancelRequestToAccept,
+                &caller,
+                &caller_keys,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::CallerIsNotRequester))
+        ));
+    }
+
+    #[tokio::test]
+    async fn mark_paid_externally_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service
+            .expect_send_bill_is_paid_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::MarkPaidExternally("SEPA-2024-0001".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks()[1].op_code, BillOpCode::ExternalPayment);
+        assert_eq!(
+            chain.get_external_payment_reference(&bill_keys()).unwrap(),
+            Some("SEPA-2024-0001".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn acknowledge_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service
+            .expect_send_bill_acknowledged_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Acknowledge,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 2);
+        assert_eq!(chain.blocks()[1].op_code, BillOpCode::Acknowledge);
+        let acknowledgements = chain.get_acknowledgements(&bill_keys()).unwrap();
+        assert_eq!(acknowledgements.len(), 1);
+        assert_eq!(acknowledgements[0].node_id, identity.identity.node_id);
+    }
+
+    #[tokio::test]
+    async fn acknowledge_again_is_idempotent() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(
+                    chain.try_add_block(acknowledge_block(TEST_BILL_ID, chain.get_latest_block()))
+                );
+                Ok(chain)
+            });
+        ctx.bill_blockchain_store.expect_add_block().never();
+        ctx.notification_service
+            .expect_send_bill_acknowledged_event()
+            .never();
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Acknowledge,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 2);
+        assert_eq!(chain.get_acknowledgements(&bill_keys()).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn acknowledge_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::Acknowledge,
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(
+                ValidationError::CallerIsNotHolderOrDrawee
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn correct_drawee_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let new_drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service
+            .expect_send_drawee_corrected_event()
+            .returning(|_| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::CorrectDrawee(new_drawee.clone()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        let chain = res.unwrap();
+        assert_eq!(chain.blocks().len(), 2);
+        assert_eq!(chain.blocks()[1].op_code, BillOpCode::CorrectDrawee);
+        let first_version = chain.get_first_version_bill(&bill_keys()).unwrap();
+        assert_eq!(
+            chain
+                .get_bill_parties(&bill_keys(), &first_version)
+                .unwrap()
+                .drawee
+                .node_id,
+            new_drawee.node_id
+        );
+    }
+
+    #[tokio::test]
+    async fn correct_drawee_fails_after_request_to_accept() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
 
-                // add sell block from endorsee to sell endorsee
-                let sell_block = BillBlock::create_block_for_sell(
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                assert!(chain.try_add_block(request_to_accept_block(
+                    TEST_BILL_ID,
+                    chain.get_latest_block(),
+                    None
+                )));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_drawee_corrected_event()
+            .never();
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::CorrectDrawee(identity_public_data_only_node_id(
+                    BcrKeys::new().get_public_key(),
+                )),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(
+                ValidationError::DraweeCorrectionNotAllowed
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn mark_paid_externally_fails_if_paid_on_chain() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.notification_service
+            .expect_send_bill_is_paid_event()
+            .never();
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::MarkPaidExternally("SEPA-2024-0001".to_string()),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                &identity.key_pair,
+                1731593928,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::BillAlreadyPaid))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reject_payment() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        let payee = bill.payee.clone();
+        let now = util::date::now().timestamp() as u64;
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                // add req to pay
+                let req_to_pay = BillBlock::create_block_for_request_to_pay(
                     TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &BillSellBlockData {
-                        buyer: sell_endorsee.clone().into(),
-                        // endorsed by endorsee
-                        seller: endorse_endorsee.clone().into(),
+                    &BillRequestToPayBlockData {
+                        requester: payee.clone().into(),
                         currency: "sat".to_string(),
-                        sum: 15000,
-                        payment_address: VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
                         signatory: None,
-                        signing_timestamp: now + 2,
+                        signing_timestamp: now,
                         signing_address: empty_address(),
                     },
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    None,
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 2,
+                    now,
                 )
                 .unwrap();
-                assert!(chain.try_add_block(sell_block));
+                assert!(chain.try_add_block(req_to_pay));
 
-                // add mint block from sell endorsee to mint endorsee
-                let mint_block = BillBlock::create_block_for_mint(
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_request_to_action_rejected_event()
+            .with(always(), eq(ActionType::PayBill), eq(None))
+            .returning(|_, _, _| Ok(()));
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RejectPayment(None),
+                &IdentityPublicData::new(identity.identity).unwrap(),
+                &identity.key_pair,
+                now + 1,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(
+            res.as_ref().unwrap().blocks()[2].op_code,
+            BillOpCode::RejectToPay
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_recourse() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let payee = bill.payee.clone();
+        let now = util::date::now().timestamp() as u64;
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+
+                // add req to pay
+                let req_to_pay = BillBlock::create_block_for_request_recourse(
                     TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &BillMintBlockData {
-                        endorsee: mint_endorsee.clone().into(),
-                        // endorsed by sell endorsee
-                        endorser: sell_endorsee.clone().into(),
+                    &BillRequestRecourseBlockData {
+                        recourser: payee.clone().into(),
+                        recoursee: IdentityPublicData::new(get_baseline_identity().identity)
+                            .unwrap()
+                            .into(),
                         currency: "sat".to_string(),
                         sum: 15000,
+                        recourse_reason: BillRecourseReasonBlockData::Pay,
                         signatory: None,
-                        signing_timestamp: now + 3,
+                        signing_timestamp: now,
                         signing_address: empty_address(),
                     },
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    None,
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 3,
+                    now,
                 )
                 .unwrap();
-                assert!(chain.try_add_block(mint_block));
+                assert!(chain.try_add_block(req_to_pay));
 
-                // add endorse block back to endorsee
-                let endorse_block_back = BillBlock::create_block_for_endorse(
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_request_to_action_rejected_event()
+            .with(always(), eq(ActionType::RecourseBill), eq(None))
+            .returning(|_, _, _| Ok(()));
+
+        let service = get_service(ctx);
+
+        let res = service
+            .execute_bill_action(
+                TEST_BILL_ID,
+                BillAction::RejectPaymentForRecourse,
+                &IdentityPublicData::new(identity.identity).unwrap(),
+                &identity.key_pair,
+                now + 1,
+                None,
+            )
+            .await;
+        assert!(res.is_ok());
+        assert_eq!(
+            res.as_ref().unwrap().blocks()[2].op_code,
+            BillOpCode::RejectToPayRecourse
+        );
+    }
+
+    #[tokio::test]
+    async fn check_bills_in_recourse_payment_baseline() {
+        let mut ctx = get_ctx();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bitcoin_client
+            .expect_get_confirmed_paid_sum()
+            .returning(|_| Ok(15000));
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_recourse_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        let recoursee = BcrKeys::new().get_public_key();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let now = util::date::now().timestamp() as u64;
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_recourse = BillBlock::create_block_for_request_recourse(
                     TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &BillEndorseBlockData {
-                        endorsee: endorse_endorsee.clone().into(),
-                        // endorsed by payee
-                        endorser: mint_endorsee.clone().into(),
+                    &BillRequestRecourseBlockData {
+                        recourser: IdentityPublicData::new(get_baseline_identity().identity)
+                            .unwrap()
+                            .into(),
+                        recoursee: identity_public_data_only_node_id(recoursee.clone()).into(),
+                        currency: "sat".to_string(),
+                        sum: 15000,
+                        recourse_reason: BillRecourseReasonBlockData::Pay,
                         signatory: None,
-                        signing_timestamp: now + 4,
+                        signing_timestamp: now,
                         signing_address: empty_address(),
                     },
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
+                    None,
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 4,
+                    now,
                 )
                 .unwrap();
-                assert!(chain.try_add_block(endorse_block_back));
+                assert!(chain.try_add_block(req_to_recourse));
+                Ok(chain)
+            });
+        ctx.notification_service
+            .expect_send_bill_recourse_paid_event()
+            .returning(|_, _| Ok(()));
 
-                // add endorse block back to payee (caller)
-                let endorse_block_last = BillBlock::create_block_for_endorse(
+        let service = get_service(ctx);
+
+        let res = service.check_bills_in_recourse_payment().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn check_bills_in_recourse_payment_waits_for_confirmed_payment() {
+        // (confirmed sum, confirmations) - the test config requires 3 confirmations and funds in
+        // the mempool aren't part of the confirmed sum
+        for (confirmed_sum, confirmations) in [(0, 0), (15000, 2)] {
+            let mut ctx = get_ctx();
+            let mut bill = get_baseline_bill(TEST_BILL_ID);
+            bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+
+            ctx.bitcoin_client
+                .expect_get_confirmed_paid_sum()
+                .returning(move |_| Ok(confirmed_sum));
+            ctx.bitcoin_client
+                .expect_get_payment_confirmations()
+                .returning(move |_| Ok(confirmations));
+            ctx.bill_store
+                .expect_get_bill_ids_waiting_for_recourse_payment()
+                .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+            let recoursee = BcrKeys::new().get_public_key();
+            ctx.bill_blockchain_store
+                .expect_get_chain()
+                .returning(move |_| {
+                    let mut chain = get_genesis_chain(Some(bill.clone()));
+                    assert!(chain.try_add_block(request_to_recourse_block(
+                        TEST_BILL_ID,
+                        chain.get_latest_block(),
+                        &identity_public_data_only_node_id(recoursee.clone()),
+                        Some(util::date::now().timestamp() as u64),
+                    )));
+                    Ok(chain)
+                });
+            // the recourse isn't paid yet
+            ctx.bill_blockchain_store.expect_add_block().never();
+            ctx.notification_service
+                .expect_send_bill_recourse_paid_event()
+                .never();
+
+            let res = get_service(ctx).check_bills_in_recourse_payment().await;
+            assert!(res.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn check_bills_in_recourse_payment_company_is_recourser() {
+        let mut ctx = get_ctx();
+        let mut identity = get_baseline_identity();
+        identity.key_pair = BcrKeys::new();
+        identity.identity.node_id = identity.key_pair.get_public_key();
+
+        let company = get_baseline_company_data();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.payee = IdentityPublicData::from(company.1.0.clone());
+
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
+        ctx.bitcoin_client
+            .expect_get_confirmed_paid_sum()
+            .returning(|_| Ok(15000));
+        ctx.bill_store
+            .expect_get_bill_ids_waiting_for_recourse_payment()
+            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
+        let company_clone = company.clone();
+        ctx.company_store.expect_get_all().returning(move || {
+            let mut map = HashMap::new();
+            map.insert(
+                company_clone.0.clone(),
+                (company_clone.1.0.clone(), company_clone.1.1.clone()),
+            );
+            Ok(map)
+        });
+        let company_clone = company.1.0.clone();
+        let recoursee = BcrKeys::new().get_public_key();
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| {
+                let now = util::date::now().timestamp() as u64;
+                let mut chain = get_genesis_chain(Some(bill.clone()));
+                let req_to_recourse = BillBlock::create_block_for_request_recourse(
                     TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &BillEndorseBlockData {
-                        endorsee: IdentityPublicData::new(get_baseline_identity().identity)
-                            .unwrap()
-                            .into(),
-                        // endorsed by payee
-                        endorser: endorse_endorsee.clone().into(),
-                        signatory: None,
-                        signing_timestamp: now + 5,
+                    &BillRequestRecourseBlockData {
+                        recourser: IdentityPublicData::from(company_clone.clone()).into(),
+                        recoursee: identity_public_data_only_node_id(recoursee.clone()).into(),
+                        currency: "sat".to_string(),
+                        sum: 15000,
+                        recourse_reason: BillRecourseReasonBlockData::Pay,
+                        signatory: Some(BillSignatoryBlockData {
+                            node_id: get_baseline_identity().identity.node_id.clone(),
+                            name: get_baseline_identity().identity.name.clone(),
+                        }),
+                        signing_timestamp: now,
                         signing_address: empty_address(),
                     },
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
                     Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 5,
+                    now,
                 )
                 .unwrap();
-                assert!(chain.try_add_block(endorse_block_last));
-
+                assert!(chain.try_add_block(req_to_recourse));
                 Ok(chain)
             });
+        ctx.notification_service
+            .expect_send_bill_recourse_paid_event()
+            .returning(|_, _| Ok(()));
         let service = get_service(ctx);
 
-        let res = service
-            .get_past_endorsees(TEST_BILL_ID, &identity.identity.node_id)
-            .await;
+        let res = service.check_bills_in_recourse_payment().await;
         assert!(res.is_ok());
-        // if there are mint, sell and endorse blocks, they are considered
-        // but without duplicates
-        assert_eq!(res.as_ref().unwrap().len(), 4);
-        // endorse endorsee is the one directly before
-        assert_eq!(
-            res.as_ref().unwrap()[0].pay_to_the_order_of.node_id,
-            endorse_endorsee_clone.node_id
-        );
-        // mint endorsee is the one after that
-        assert_eq!(
-            res.as_ref().unwrap()[1].pay_to_the_order_of.node_id,
-            mint_endorsee_clone.node_id
-        );
-        // sell endorsee is the next one
-        assert_eq!(
-            res.as_ref().unwrap()[2].pay_to_the_order_of.node_id,
-            sell_endorsee_clone.node_id
-        );
-        // drawer is the last one, because endorse endorsee is already there
-        // and drawer != drawee
-        assert_eq!(
-            res.as_ref().unwrap()[3].pay_to_the_order_of.node_id,
-            drawer.node_id
-        );
     }
 
     #[tokio::test]
-    async fn past_payments_baseline() {
+    async fn request_recourse_accept_baseline() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let bill = get_baseline_bill(TEST_BILL_ID);
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let recoursee = bill.payee.clone();
+        let endorsee_caller = IdentityPublicData::new(identity.identity.clone()).unwrap();
 
-        let identity_clone = identity.identity.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
-        // paid
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(true));
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-
-                // req to pay
-                assert!(chain.try_add_block(request_to_pay_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    None,
-                )));
-                // paid
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
+                let endorse_block = BillBlock::create_block_for_endorse(
+                    TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    &BillEndorseBlockData {
+                        endorser: bill.payee.clone().into(),
+                        endorsee: endorsee_caller.clone().into(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::new(),
                     None,
-                )));
-                assert!(chain.try_add_block(sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
-                )));
-                // rejected
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(endorse_block);
+                let req_to_accept = BillBlock::create_block_for_request_to_accept(
+                    TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    &BillRequestToAcceptBlockData {
+                        requester: bill.payee.clone().into(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::new(),
                     None,
-                )));
-                assert!(
-                    chain.try_add_block(reject_buy_block(TEST_BILL_ID, chain.get_latest_block(),))
-                );
-                // expired
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(req_to_accept);
+                let reject_accept = BillBlock::create_block_for_reject_to_accept(
+                    TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    &BillRejectBlockData {
+                        rejecter: bill.drawee.clone().into(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                        reason: None,
+                    },
+                    &BcrKeys::new(),
                     None,
-                )));
-                // active
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
-                    Some(1931593928),
-                )));
-
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(reject_accept);
                 Ok(chain)
             });
-
+        // Request to recourse event should be sent
+        ctx.notification_service
+            .expect_send_recourse_action_event()
+            .returning(|_, _, _| Ok(()));
         let service = get_service(ctx);
 
-        let res_past_payments = service
-            .get_past_payments(
+        let res = service
+            .execute_bill_action(
                 TEST_BILL_ID,
+                BillAction::RequestRecourse(recoursee, RecourseReason::Accept),
                 &IdentityPublicData::new(identity.identity.clone()).unwrap(),
                 &identity.key_pair,
-                1931593928,
+                1731593928,
+                None,
             )
             .await;
-
-        assert!(res_past_payments.is_ok());
-        assert_eq!(res_past_payments.as_ref().unwrap().len(), 4);
-        match res_past_payments.as_ref().unwrap()[0] {
-            PastPaymentResult::Payment(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Paid(_)));
-            }
-            _ => panic!("wrong result"),
-        };
-        match res_past_payments.as_ref().unwrap()[1] {
-            PastPaymentResult::Sell(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Paid(_)));
-            }
-            _ => panic!("wrong result"),
-        };
-        match res_past_payments.as_ref().unwrap()[2] {
-            PastPaymentResult::Sell(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Rejected(_)));
-            }
-            _ => panic!("wrong result"),
-        };
-        match res_past_payments.as_ref().unwrap()[3] {
-            PastPaymentResult::Sell(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Expired(_)));
-            }
-            _ => panic!("wrong result"),
-        };
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 5);
+        assert!(res.unwrap().blocks()[4].op_code == BillOpCode::RequestRecourse);
     }
 
     #[tokio::test]
-    async fn past_payments_recourse() {
+    async fn request_recourse_payment_baseline() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let bill = get_baseline_bill(TEST_BILL_ID);
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let recoursee = bill.payee.clone();
+        let endorsee_caller = IdentityPublicData::new(identity.identity.clone()).unwrap();
 
-        let identity_clone = identity.identity.clone();
-        ctx.bill_store.expect_exists().returning(|_| true);
-        // not paid
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .returning(|_, _| Ok(()));
         ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-
-                // req to pay
-                assert!(chain.try_add_block(request_to_pay_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    None,
-                )));
-                // reject payment
-                assert!(
-                    chain
-                        .try_add_block(
-                            reject_to_pay_block(TEST_BILL_ID, chain.get_latest_block(),)
-                        )
-                );
-                // req to recourse
-                assert!(chain.try_add_block(request_to_recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
-                    None,
-                )));
-                // recourse - paid
-                assert!(chain.try_add_block(recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
-                )));
-                // req to recourse
-                assert!(chain.try_add_block(request_to_recourse_block(
-                    TEST_BILL_ID,
+                let endorse_block = BillBlock::create_block_for_endorse(
+                    TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    &BillEndorseBlockData {
+                        endorser: bill.payee.clone().into(),
+                        endorsee: endorsee_caller.clone().into(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::new(),
                     None,
-                )));
-                // reject
-                assert!(chain.try_add_block(reject_recourse_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                )));
-                // expired
-                assert!(chain.try_add_block(request_to_recourse_block(
-                    TEST_BILL_ID,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(endorse_block);
+                let req_to_pay = BillBlock::create_block_for_request_to_pay(
+                    TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
+                    &BillRequestToPayBlockData {
+                        requester: bill.payee.clone().into(),
+                        currency: "sat".to_string(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                    },
+                    &BcrKeys::new(),
                     None,
-                )));
-                // active
-                assert!(chain.try_add_block(request_to_recourse_block(
-                    TEST_BILL_ID,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(req_to_pay);
+                let reject_pay = BillBlock::create_block_for_reject_to_pay(
+                    TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
-                    Some(1931593928),
-                )));
-
+                    &BillRejectBlockData {
+                        rejecter: bill.drawee.clone().into(),
+                        signatory: None,
+                        signing_timestamp: 1731593927,
+                        signing_address: empty_address(),
+                        reason: None,
+                    },
+                    &BcrKeys::new(),
+                    None,
+                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                    1731593927,
+                )
+                .unwrap();
+                chain.try_add_block(reject_pay);
                 Ok(chain)
             });
-
+        // Request to recourse event should be sent
+        ctx.notification_service
+            .expect_send_recourse_action_event()
+            .returning(|_, _, _| Ok(()));
         let service = get_service(ctx);
 
-        let res_past_payments = service
-            .get_past_payments(
+        let res = service
+            .execute_bill_action(
                 TEST_BILL_ID,
+                BillAction::RequestRecourse(
+                    recoursee,
+                    RecourseReason::Pay(15000, "sat".to_string()),
+                ),
                 &IdentityPublicData::new(identity.identity.clone()).unwrap(),
                 &identity.key_pair,
-                1931593928,
+                1731593928,
+                None,
             )
             .await;
-
-        assert!(res_past_payments.is_ok());
-        assert_eq!(res_past_payments.as_ref().unwrap().len(), 4);
-        match res_past_payments.as_ref().unwrap()[0] {
-            PastPaymentResult::Payment(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Rejected(_)));
-            }
-            _ => panic!("wrong result"),
-        };
-        match res_past_payments.as_ref().unwrap()[1] {
-            PastPaymentResult::Recourse(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Paid(_)));
-            }
-            _ => panic!("wrong result"),
-        };
-        match res_past_payments.as_ref().unwrap()[2] {
-            PastPaymentResult::Recourse(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Rejected(_)));
-            }
-            _ => panic!("wrong result"),
-        };
-        match res_past_payments.as_ref().unwrap()[3] {
-            PastPaymentResult::Recourse(ref data) => {
-                assert!(matches!(data.status, PastPaymentStatus::Expired(_)));
-            }
-            _ => panic!("wrong result"),
-        };
+        assert!(res.is_ok());
+        assert!(res.as_ref().unwrap().blocks().len() == 5);
+        assert!(res.unwrap().blocks()[4].op_code == BillOpCode::RequestRecourse);
     }
 
     #[tokio::test]
-    async fn reject_acceptance_baseline() {
+    async fn recourse_bitcredit_bill_baseline() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
-        let bill = get_baseline_bill(TEST_BILL_ID);
-        let payee = bill.payee.clone();
-        let now = util::date::now().timestamp() as u64;
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        let recoursee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let recoursee_clone = recoursee.clone();
+        let identity_clone = identity.identity.clone();
 
         ctx.bill_store
             .expect_save_bill_to_cache()
             .returning(|_, _| Ok(()));
+        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
         ctx.bill_blockchain_store
             .expect_get_chain()
             .returning(move |_| {
                 let mut chain = get_genesis_chain(Some(bill.clone()));
-
-                // add req to accept block
-                let req_to_accept = BillBlock::create_block_for_request_to_accept(
+                let req_to_recourse = BillBlock::create_block_for_request_recourse(
                     TEST_BILL_ID.to_string(),
                     chain.get_latest_block(),
-                    &BillRequestToAcceptBlockData {
-                        requester: payee.clone().into(),
+                    &BillRequestRecourseBlockData {
+                        recourser: IdentityPublicData::new(identity_clone.clone())
+                            .unwrap()
+                            .into(),
+                        recoursee: recoursee_clone.clone().into(),
+                        sum: 15000,
+                        currency: "sat".to_string(),
+                        recourse_reason: BillRecourseReasonBlockData::Pay,
                         signatory: None,
-                        signing_timestamp: now + 1,
+                        signing_timestamp: 1731593927,
                         signing_address: empty_address(),
                     },
+                    &BcrKeys::new(),
+                    None,
                     &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now + 1,
+                    1731593927,
                 )
                 .unwrap();
-                assert!(chain.try_add_block(req_to_accept));
-
+                chain.try_add_block(req_to_recourse);
                 Ok(chain)
             });
+        // Recourse paid event should be sent
         ctx.notification_service
-            .expect_send_request_to_action_rejected_event()
-            .with(always(), eq(ActionType::AcceptBill))
+            .expect_send_bill_recourse_paid_event()
             .returning(|_, _| Ok(()));
 
         let service = get_service(ctx);
+
         let res = service
             .execute_bill_action(
                 TEST_BILL_ID,
-                BillAction::RejectAcceptance,
-                &IdentityPublicData::new(identity.identity).unwrap(),
+                BillAction::Recourse(
+                    recoursee,
+                    15000,
+                    "sat".to_string(),
+                    RecourseReason::Pay(15000, "sat".into()),
+                ),
+                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
                 &identity.key_pair,
-                now + 2,
+                1731593928,
+                None,
             )
             .await;
         assert!(res.is_ok());
-        assert_eq!(
-            res.as_ref().unwrap().blocks()[2].op_code,
-            BillOpCode::RejectToAccept
+        assert_eq!(res.as_ref().unwrap().blocks().len(), 3);
+        assert_eq!(res.unwrap().blocks()[2].op_code, BillOpCode::Recourse);
+    }
+
+    #[test]
+    fn check_req_for_expiration_baseline() {
+        let ctx = get_ctx();
+        let service = get_service(ctx);
+        let mut bill_payment = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        bill_payment.status.payment = BillPaymentStatus {
+            time_of_request_to_pay: Some(1531593928),
+            requested_to_pay: true,
+            paid: false,
+            paid_sum: 0,
+            request_to_pay_timed_out: false,
+            rejected_to_pay: false,
+            rejected_to_pay_reason: None,
+            external_payment_reference: None,
+        };
+
+        assert!(
+            service
+                .check_requests_for_expiration(&bill_payment, 1731593928)
+                .unwrap()
+        );
+        assert!(
+            !service
+                .check_requests_for_expiration(&bill_payment, 1431593928)
+                .unwrap()
+        );
+        bill_payment.data.maturity_date = "2018-07-15".into(); // before ts
+        assert!(
+            !service
+                .check_requests_for_expiration(&bill_payment, 1531593929)
+                .unwrap()
+        );
+        // 2 days after req to pay, but not yet 2 days after end of day maturity date
+        assert!(
+            !service
+                .check_requests_for_expiration(&bill_payment, 1531780429)
+                .unwrap()
+        );
+        // the bill's payment deadline is used instead of the global one
+        bill_payment.data.maturity_date = "2018-07-14".into();
+        bill_payment.data.payment_deadline_seconds = PAYMENT_DEADLINE_SECONDS * 7;
+        assert!(
+            !service
+                .check_requests_for_expiration(
+                    &bill_payment,
+                    1531593928 + PAYMENT_DEADLINE_SECONDS * 2
+                )
+                .unwrap()
+        );
+        assert!(
+            service
+                .check_requests_for_expiration(
+                    &bill_payment,
+                    1531593928 + PAYMENT_DEADLINE_SECONDS * 8
+                )
+                .unwrap()
+        );
+
+        let mut bill_acceptance = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        bill_acceptance.status.acceptance = BillAcceptanceStatus {
+            time_of_request_to_accept: Some(1531593928),
+            requested_to_accept: true,
+            accepted: false,
+            request_to_accept_timed_out: false,
+            rejected_to_accept: false,
+            rejected_to_accept_reason: None,
+        };
+
+        assert!(
+            service
+                .check_requests_for_expiration(&bill_acceptance, 1731593928)
+                .unwrap()
+        );
+
+        let mut bill_sell = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        bill_sell.status.sell = BillSellStatus {
+            time_of_last_offer_to_sell: Some(1531593928),
+            offered_to_sell: true,
+            sold: false,
+            offer_to_sell_timed_out: false,
+            rejected_offer_to_sell: false,
+            rejected_offer_to_sell_reason: None,
+            sell_deadline_seconds: None,
+        };
+
+        assert!(
+            service
+                .check_requests_for_expiration(&bill_sell, 1731593928)
+                .unwrap()
+        );
+
+        let mut bill_recourse = get_baseline_cached_bill(TEST_BILL_ID.to_string());
+        bill_recourse.status.recourse = BillRecourseStatus {
+            time_of_last_request_to_recourse: Some(1531593928),
+            requested_to_recourse: true,
+            recoursed: false,
+            request_to_recourse_timed_out: false,
+            rejected_request_to_recourse: false,
+        };
+
+        assert!(
+            service
+                .check_requests_for_expiration(&bill_recourse, 1731593928)
+                .unwrap()
         );
     }
 
     #[tokio::test]
-    async fn reject_buying_baseline() {
+    async fn preview_notification_baseline() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
         let bill = get_baseline_bill(TEST_BILL_ID);
-
-        let identity_clone = identity.identity.clone();
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
         ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-
-                assert!(chain.try_add_block(offer_to_sell_block(
-                    TEST_BILL_ID,
-                    chain.get_latest_block(),
-                    &IdentityPublicData::new(identity_clone.clone()).unwrap(),
-                    None,
-                )));
-
-                Ok(chain)
-            });
-
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // nothing is sent for a preview
         ctx.notification_service
-            .expect_send_request_to_action_rejected_event()
-            .with(always(), eq(ActionType::BuyBill))
-            .returning(|_, _| Ok(()));
-        let service = get_service(ctx);
+            .expect_send_bill_is_signed_event()
+            .never();
 
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::RejectBuying,
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+        let res = get_service(ctx)
+            .preview_notification(TEST_BILL_ID, &ActionType::PayBill, "de-AT")
             .await;
         assert!(res.is_ok());
-        assert_eq!(
-            res.as_ref().unwrap().blocks()[2].op_code,
-            BillOpCode::RejectToBuy
+        let preview = res.unwrap();
+        assert_eq!(preview.language, "de");
+        assert!(preview.in_app_text.contains(TEST_BILL_ID));
+        assert!(preview.email_body.contains("5000 sat"));
+        assert!(
+            preview
+                .in_app_text
+                .contains(&get_baseline_identity().identity.name)
         );
+        assert!(!preview.push_text.is_empty());
     }
 
     #[tokio::test]
-    async fn reject_payment() {
+    async fn preview_notification_fails_for_unknown_bill() {
         let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| false);
+
+        let res = get_service(ctx)
+            .preview_notification(TEST_BILL_ID, &ActionType::PayBill, "en")
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    fn get_bill_with_file_for_bundle() -> (BitcreditBill, Vec<u8>) {
         let identity = get_baseline_identity();
-        let bill = get_baseline_bill(TEST_BILL_ID);
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
-        let payee = bill.payee.clone();
-        let now = util::date::now().timestamp() as u64;
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let file_bytes = String::from("hello world").as_bytes().to_vec();
+        bill.files = vec![File {
+            name: "invoice.pdf".to_string(),
+            hash: util::sha256_hash(&file_bytes),
+        }];
+        (bill, file_bytes)
+    }
 
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+    async fn export_bundle_for_test() -> Vec<u8> {
+        let mut ctx = get_ctx();
+        let (bill, file_bytes) = get_bill_with_file_for_bundle();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-
-                // add req to pay
-                let req_to_pay = BillBlock::create_block_for_request_to_pay(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestToPayBlockData {
-                        requester: payee.clone().into(),
-                        currency: "sat".to_string(),
-                        signatory: None,
-                        signing_timestamp: now,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now,
-                )
-                .unwrap();
-                assert!(chain.try_add_block(req_to_pay));
-
-                Ok(chain)
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        ctx.file_upload_store
+            .expect_open_attached_file()
+            .with(eq(TEST_BILL_ID), eq("invoice.pdf"))
+            .returning(move |_, _| {
+                Ok(util::crypto::encrypt_ecies(&file_bytes, TEST_PUB_KEY_SECP).unwrap())
             });
-        ctx.notification_service
-            .expect_send_request_to_action_rejected_event()
-            .with(always(), eq(ActionType::PayBill))
-            .returning(|_, _| Ok(()));
-        let service = get_service(ctx);
 
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::RejectPayment,
-                &IdentityPublicData::new(identity.identity).unwrap(),
-                &identity.key_pair,
-                now + 1,
-            )
-            .await;
-        assert!(res.is_ok());
-        assert_eq!(
-            res.as_ref().unwrap().blocks()[2].op_code,
-            BillOpCode::RejectToPay
-        );
+        get_service(ctx)
+            .export_bill_bundle(TEST_BILL_ID, &get_baseline_identity().key_pair)
+            .await
+            .unwrap()
     }
 
     #[tokio::test]
-    async fn reject_recourse() {
-        let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let bill = get_baseline_bill(TEST_BILL_ID);
-        let payee = bill.payee.clone();
-        let now = util::date::now().timestamp() as u64;
+    async fn export_and_import_bill_bundle_baseline() {
+        let bundle = export_bundle_for_test().await;
+        assert_eq!(bundle[0], bundle::BILL_BUNDLE_VERSION);
 
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| false);
         ctx.bill_store
-            .expect_save_bill_to_cache()
+            .expect_save_keys()
+            .withf(|id, keys| id == TEST_BILL_ID && keys.private_key == TEST_PRIVATE_KEY_SECP)
+            .times(1)
             .returning(|_, _| Ok(()));
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .withf(|encrypted, id, name| {
+                id == TEST_BILL_ID
+                    && name == "invoice.pdf"
+                    && util::crypto::decrypt_ecies(encrypted, TEST_PRIVATE_KEY_SECP).unwrap()
+                        == "hello world".as_bytes()
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
         ctx.bill_blockchain_store
-            .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-
-                // add req to pay
-                let req_to_pay = BillBlock::create_block_for_request_recourse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestRecourseBlockData {
-                        recourser: payee.clone().into(),
-                        recoursee: IdentityPublicData::new(get_baseline_identity().identity)
-                            .unwrap()
-                            .into(),
-                        currency: "sat".to_string(),
-                        sum: 15000,
-                        recourse_reason: BillRecourseReasonBlockData::Pay,
-                        signatory: None,
-                        signing_timestamp: now,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now,
-                )
-                .unwrap();
-                assert!(chain.try_add_block(req_to_pay));
-
-                Ok(chain)
-            });
-        ctx.notification_service
-            .expect_send_request_to_action_rejected_event()
-            .with(always(), eq(ActionType::RecourseBill))
+            .expect_replace_blocks_from()
+            .withf(|id, blocks| id == TEST_BILL_ID && blocks.len() == 1)
+            .times(1)
             .returning(|_, _| Ok(()));
+        // the imported bill is cached and with it indexed by its participants
+        ctx.bill_store
+            .expect_save_bill_to_cache()
+            .withf(|id, bill| {
+                id == TEST_BILL_ID
+                    && bill
+                        .participants
+                        .all_participant_node_ids
+                        .contains(&get_baseline_identity().identity.node_id)
+                    && bill.data.tampered_files.is_empty()
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        // the files are checked when importing, so they aren't decrypted again
+        ctx.file_upload_store.expect_open_attached_file().never();
 
-        let service = get_service(ctx);
-
-        let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::RejectPaymentForRecourse,
-                &IdentityPublicData::new(identity.identity).unwrap(),
-                &identity.key_pair,
-                now + 1,
-            )
+        let res = get_service(ctx)
+            .import_bill_bundle(&bundle, &get_baseline_identity().key_pair)
             .await;
         assert!(res.is_ok());
-        assert_eq!(
-            res.as_ref().unwrap().blocks()[2].op_code,
-            BillOpCode::RejectToPayRecourse
-        );
+        assert_eq!(res.unwrap(), TEST_BILL_ID.to_string());
     }
 
     #[tokio::test]
-    async fn check_bills_in_recourse_payment_baseline() {
+    async fn import_bill_bundle_fails_for_file_not_matching_the_chain() {
+        let mut bundle = bundle::BillBundle::from_bytes(&export_bundle_for_test().await).unwrap();
+        bundle.payload.files[0].encrypted_content = util::base58_encode(
+            &util::crypto::encrypt_ecies(
+                "tampered".as_bytes(),
+                &get_baseline_identity().identity.node_id,
+            )
+            .unwrap(),
+        );
+        bundle.signature = util::crypto::signature(
+            &bundle.payload.hash().unwrap(),
+            &get_baseline_identity().key_pair.get_private_key_string(),
+        )
+        .unwrap();
+
         let mut ctx = get_ctx();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        ctx.bill_store.expect_exists().returning(|_| false);
+        ctx.bill_store.expect_save_keys().never();
+        ctx.file_upload_store.expect_save_attached_file().never();
+        let res = get_service(ctx)
+            .import_bill_bundle(
+                &bundle.to_bytes().unwrap(),
+                &get_baseline_identity().key_pair,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidBillBundle))
+        ));
+    }
 
+    #[tokio::test]
+    async fn import_bill_bundle_undoes_import_if_chain_can_not_be_stored() {
+        let bundle = export_bundle_for_test().await;
+
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| false);
+        ctx.bill_store.expect_save_keys().returning(|_, _| Ok(()));
+        ctx.file_upload_store
+            .expect_save_attached_file()
+            .returning(|_, _, _| Ok(()));
+        ctx.bill_blockchain_store
+            .expect_replace_blocks_from()
+            .returning(|_, _| Err(persistence::Error::InsertFailed("failed".to_string())));
         ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
-        ctx.bill_store
-            .expect_get_bill_ids_waiting_for_recourse_payment()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
-        let recoursee = BcrKeys::new().get_public_key();
+            .expect_remove_keys()
+            .with(eq(TEST_BILL_ID))
+            .times(1)
+            .returning(|_| Ok(()));
+        ctx.file_upload_store
+            .expect_delete_attached_files()
+            .with(eq(TEST_BILL_ID))
+            .times(1)
+            .returning(|_| Ok(()));
+        ctx.bill_store.expect_save_bill_to_cache().never();
+        let res = get_service(ctx)
+            .import_bill_bundle(&bundle, &get_baseline_identity().key_pair)
+            .await;
+        assert!(res.is_err());
+    }
+
+    fn get_view_for_test() -> BillView {
+        let view_keys = BcrKeys::new();
+        let blocks = get_genesis_chain(None).blocks().to_owned();
+        let view_data = blocks
+            .iter()
+            .map(|b| {
+                b.get_view_data(&bill_keys(), &view_keys.get_public_key())
+                    .unwrap()
+            })
+            .collect();
+        BillView {
+            bill_id: TEST_BILL_ID.to_owned(),
+            view_key: BillViewKey::new(TEST_PUB_KEY_SECP, &view_keys),
+            blocks,
+            view_data,
+        }
+    }
+
+    #[tokio::test]
+    async fn export_and_import_bill_view_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let now = util::date::now().timestamp() as u64;
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_recourse = BillBlock::create_block_for_request_recourse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestRecourseBlockData {
-                        recourser: IdentityPublicData::new(get_baseline_identity().identity)
-                            .unwrap()
-                            .into(),
-                        recoursee: identity_public_data_only_node_id(recoursee.clone()).into(),
-                        currency: "sat".to_string(),
-                        sum: 15000,
-                        recourse_reason: BillRecourseReasonBlockData::Pay,
-                        signatory: None,
-                        signing_timestamp: now,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now,
-                )
-                .unwrap();
-                assert!(chain.try_add_block(req_to_recourse));
-                Ok(chain)
-            });
-        ctx.notification_service
-            .expect_send_bill_recourse_paid_event()
-            .returning(|_, _| Ok(()));
-
-        let service = get_service(ctx);
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let view = get_service(ctx)
+            .export_bill_view(TEST_BILL_ID, &identity.key_pair)
+            .await
+            .unwrap();
+        assert_eq!(view.bill_id, TEST_BILL_ID.to_string());
+        assert_eq!(view.view_key.public_key, TEST_PUB_KEY_SECP.to_string());
+        // the bill private key is never part of the view
+        assert_ne!(view.view_key.private_key, TEST_PRIVATE_KEY_SECP.to_string());
+        assert!(
+            !serde_json::to_string(&view)
+                .unwrap()
+                .contains(TEST_PRIVATE_KEY_SECP)
+        );
+        assert_eq!(view.blocks.len(), 1);
+        assert_eq!(view.view_data.len(), 1);
 
-        let res = service.check_bills_in_recourse_payment().await;
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| false);
+        ctx.bill_store
+            .expect_save_view()
+            .withf(|view| view.bill_id == TEST_BILL_ID)
+            .times(1)
+            .returning(|_| Ok(()));
+        let res = get_service(ctx).import_bill_view(view).await;
         assert!(res.is_ok());
+        assert_eq!(res.unwrap(), TEST_BILL_ID.to_string());
     }
 
     #[tokio::test]
-    async fn check_bills_in_recourse_payment_company_is_recourser() {
+    async fn export_bill_view_fails_for_non_participant() {
         let mut ctx = get_ctx();
-        let mut identity = get_baseline_identity();
-        identity.key_pair = BcrKeys::new();
-        identity.identity.node_id = identity.key_pair.get_public_key();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(|_| Ok(get_genesis_chain(None)));
 
-        let company = get_baseline_company_data();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.payee = IdentityPublicData::from(company.1.0.clone());
+        let res = get_service(ctx)
+            .export_bill_view(TEST_BILL_ID, &BcrKeys::new())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn import_bill_view_fails_for_wrong_view_key() {
+        let ctx = get_ctx();
+        let mut view = get_view_for_test();
+        let keys = BcrKeys::new();
+        view.view_key = BillViewKey {
+            public_key: keys.get_public_key(),
+            private_key: keys.get_private_key_string(),
+        };
+
+        let res = get_service(ctx).import_bill_view(view).await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidBillView))
+        ));
+    }
 
+    #[tokio::test]
+    async fn get_bill_view_detail_for_observer() {
+        let mut ctx = get_ctx();
         ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+            .expect_get_view()
+            .with(eq(TEST_BILL_ID))
+            .returning(|_| Ok(Some(get_view_for_test())));
+        // the observer is not a participant of the bill
+        let mut observer = get_baseline_identity().identity;
+        observer.node_id = BcrKeys::new().get_public_key();
+
+        let res = get_service(ctx)
+            .get_bill_view_detail(TEST_BILL_ID, &observer, 1731593928)
+            .await;
+        assert!(res.is_ok());
+        let bill = res.unwrap();
+        assert_eq!(bill.id, TEST_BILL_ID.to_string());
+        assert_eq!(
+            bill.participants.drawee.node_id,
+            get_baseline_bill(TEST_BILL_ID).drawee.node_id
+        );
+    }
+
+    #[tokio::test]
+    async fn get_bill_view_detail_fails_without_view() {
+        let ctx = get_ctx();
+        let res = get_service(ctx)
+            .get_bill_view_detail(TEST_BILL_ID, &get_baseline_identity().identity, 1731593928)
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn execute_bill_action_fails_with_only_a_view() {
+        let mut ctx = get_ctx();
         ctx.bill_store
-            .expect_get_bill_ids_waiting_for_recourse_payment()
-            .returning(|| Ok(vec![TEST_BILL_ID.to_string()]));
-        let company_clone = company.clone();
-        ctx.company_store.expect_get_all().returning(move || {
-            let mut map = HashMap::new();
-            map.insert(
-                company_clone.0.clone(),
-                (company_clone.1.0.clone(), company_clone.1.1.clone()),
-            );
-            Ok(map)
-        });
-        let company_clone = company.1.0.clone();
-        let recoursee = BcrKeys::new().get_public_key();
+            .expect_get_view()
+            .returning(|_| Ok(Some(get_view_for_test())));
+        ctx.bill_store.expect_exists().returning(|_| false);
+        let identity = get_baseline_identity();
+        let service = get_service(ctx);
+
+        for action in [BillAction::Accept, BillAction::RequestAcceptance] {
+            let res = service
+                .execute_bill_action(
+                    TEST_BILL_ID,
+                    action,
+                    &IdentityPublicData::new(identity.identity.clone()).unwrap(),
+                    &identity.key_pair,
+                    1731593928,
+                    None,
+                )
+                .await;
+            assert!(matches!(
+                res,
+                Err(Error::Validation(ValidationError::BillIsReadOnlyView))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_and_parse_bill_qr_payload_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let payee_node_id = bill.payee.node_id.clone();
+        let sum = bill.sum;
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let now = util::date::now().timestamp() as u64;
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_recourse = BillBlock::create_block_for_request_recourse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestRecourseBlockData {
-                        recourser: IdentityPublicData::from(company_clone.clone()).into(),
-                        recoursee: identity_public_data_only_node_id(recoursee.clone()).into(),
-                        currency: "sat".to_string(),
-                        sum: 15000,
-                        recourse_reason: BillRecourseReasonBlockData::Pay,
-                        signatory: Some(BillSignatoryBlockData {
-                            node_id: get_baseline_identity().identity.node_id.clone(),
-                            name: get_baseline_identity().identity.name.clone(),
-                        }),
-                        signing_timestamp: now,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    Some(&BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap()),
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    now,
-                )
-                .unwrap();
-                assert!(chain.try_add_block(req_to_recourse));
-                Ok(chain)
-            });
-        ctx.notification_service
-            .expect_send_bill_recourse_paid_event()
-            .returning(|_, _| Ok(()));
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         let service = get_service(ctx);
 
-        let res = service.check_bills_in_recourse_payment().await;
+        let payload = service
+            .get_bill_qr_payload(TEST_BILL_ID, &identity.key_pair)
+            .await
+            .unwrap();
+        let res = service.parse_bill_qr_payload(&payload).await;
         assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            BillQrPayload {
+                bill_id: TEST_BILL_ID.to_string(),
+                sum,
+                currency: "sat".to_string(),
+                drawee_node_id: identity.identity.node_id.clone(),
+                payee_node_id,
+                relay: crate::get_config().nostr_relay(),
+                signer_node_id: identity.identity.node_id.clone(),
+            }
+        );
     }
 
     #[tokio::test]
-    async fn request_recourse_accept_baseline() {
+    async fn get_unnotified_participants_returns_participants_not_marked_as_sent() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
         bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let recoursee = bill.payee.clone();
-        let endorsee_caller = IdentityPublicData::new(identity.identity.clone()).unwrap();
-
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
+        let drawee_node_id = bill.drawee.node_id.clone();
+        let payee_node_id = bill.payee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let endorse_block = BillBlock::create_block_for_endorse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillEndorseBlockData {
-                        endorser: bill.payee.clone().into(),
-                        endorsee: endorsee_caller.clone().into(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(endorse_block);
-                let req_to_accept = BillBlock::create_block_for_request_to_accept(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestToAcceptBlockData {
-                        requester: bill.payee.clone().into(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(req_to_accept);
-                let reject_accept = BillBlock::create_block_for_reject_to_accept(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRejectBlockData {
-                        rejecter: bill.drawee.clone().into(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(reject_accept);
-                Ok(chain)
-            });
-        // Request to recourse event should be sent
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         ctx.notification_service
-            .expect_send_recourse_action_event()
-            .returning(|_, _, _| Ok(()));
+            .expect_get_notified_bill_participants()
+            .with(eq(TEST_BILL_ID), eq(1))
+            .returning(move |_, _| Ok(vec![payee_node_id.clone()]));
         let service = get_service(ctx);
 
         let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::RequestRecourse(recoursee, RecourseReason::Accept),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
-            .await;
-        assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 5);
-        assert!(res.unwrap().blocks()[4].op_code == BillOpCode::RequestRecourse);
+            .get_unnotified_participants(TEST_BILL_ID, 1)
+            .await
+            .unwrap();
+        // the payee was notified and the drawer signed the block
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].node_id, drawee_node_id);
     }
 
     #[tokio::test]
-    async fn request_recourse_payment_baseline() {
+    async fn get_unnotified_participants_fails_for_missing_block() {
         let mut ctx = get_ctx();
-        let identity = get_baseline_identity();
-        let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let recoursee = bill.payee.clone();
-        let endorsee_caller = IdentityPublicData::new(identity.identity.clone()).unwrap();
-
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let endorse_block = BillBlock::create_block_for_endorse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillEndorseBlockData {
-                        endorser: bill.payee.clone().into(),
-                        endorsee: endorsee_caller.clone().into(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(endorse_block);
-                let req_to_pay = BillBlock::create_block_for_request_to_pay(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestToPayBlockData {
-                        requester: bill.payee.clone().into(),
-                        currency: "sat".to_string(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(req_to_pay);
-                let reject_pay = BillBlock::create_block_for_reject_to_pay(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRejectBlockData {
-                        rejecter: bill.drawee.clone().into(),
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(reject_pay);
-                Ok(chain)
-            });
-        // Request to recourse event should be sent
+            .returning(|_| Ok(get_genesis_chain(None)));
         ctx.notification_service
-            .expect_send_recourse_action_event()
-            .returning(|_, _, _| Ok(()));
+            .expect_get_notified_bill_participants()
+            .never();
+        let service = get_service(ctx);
+
+        let res = service.get_unnotified_participants(TEST_BILL_ID, 2).await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn resolve_bill_participants_baseline() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        let payee_node_id = bill.payee.node_id.clone();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         let service = get_service(ctx);
 
         let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::RequestRecourse(
-                    recoursee,
-                    RecourseReason::Pay(15000, "sat".to_string()),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+            .resolve_bill_participants(TEST_BILL_ID, &identity.key_pair)
+            .await
+            .unwrap();
+        // the names come from the genesis block, no contacts are involved
+        let drawee = res
+            .iter()
+            .find(|p| p.node_id == identity.identity.node_id)
+            .unwrap();
+        assert_eq!(drawee.name, identity.identity.name);
+        assert_eq!(drawee.block_height, 1);
+        let payee = res.iter().find(|p| p.node_id == payee_node_id).unwrap();
+        assert_eq!(payee.name, "payee");
+        assert_eq!(payee.block_height, 1);
+    }
+
+    #[tokio::test]
+    async fn resolve_bill_participants_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        let service = get_service(ctx);
+
+        let res = service
+            .resolve_bill_participants(TEST_BILL_ID, &BcrKeys::new())
             .await;
-        assert!(res.is_ok());
-        assert!(res.as_ref().unwrap().blocks().len() == 5);
-        assert!(res.unwrap().blocks()[4].op_code == BillOpCode::RequestRecourse);
+        assert!(matches!(res, Err(Error::NotFound)));
     }
 
     #[tokio::test]
-    async fn recourse_bitcredit_bill_baseline() {
+    async fn get_bill_qr_payload_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+
+        let res = get_service(ctx)
+            .get_bill_qr_payload(TEST_BILL_ID, &BcrKeys::new())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn parse_bill_qr_payload_fails_if_tampered() {
         let mut ctx = get_ctx();
         let identity = get_baseline_identity();
         let mut bill = get_baseline_bill(TEST_BILL_ID);
-        bill.drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        bill.payee = IdentityPublicData::new(identity.identity.clone()).unwrap();
-        let recoursee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
-        let recoursee_clone = recoursee.clone();
-        let identity_clone = identity.identity.clone();
-
-        ctx.bill_store
-            .expect_save_bill_to_cache()
-            .returning(|_, _| Ok(()));
-        ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
         ctx.bill_blockchain_store
             .expect_get_chain()
-            .returning(move |_| {
-                let mut chain = get_genesis_chain(Some(bill.clone()));
-                let req_to_recourse = BillBlock::create_block_for_request_recourse(
-                    TEST_BILL_ID.to_string(),
-                    chain.get_latest_block(),
-                    &BillRequestRecourseBlockData {
-                        recourser: IdentityPublicData::new(identity_clone.clone())
-                            .unwrap()
-                            .into(),
-                        recoursee: recoursee_clone.clone().into(),
-                        sum: 15000,
-                        currency: "sat".to_string(),
-                        recourse_reason: BillRecourseReasonBlockData::Pay,
-                        signatory: None,
-                        signing_timestamp: 1731593927,
-                        signing_address: empty_address(),
-                    },
-                    &BcrKeys::new(),
-                    None,
-                    &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-                    1731593927,
-                )
-                .unwrap();
-                chain.try_add_block(req_to_recourse);
-                Ok(chain)
-            });
-        // Recourse paid event should be sent
-        ctx.notification_service
-            .expect_send_bill_recourse_paid_event()
-            .returning(|_, _| Ok(()));
-
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
         let service = get_service(ctx);
 
+        let payload = service
+            .get_bill_qr_payload(TEST_BILL_ID, &identity.key_pair)
+            .await
+            .unwrap();
+        // flip a byte of the signed content
+        let mut bytes = URL_SAFE_NO_PAD.decode(&payload).unwrap();
+        bytes[5] ^= 1;
         let res = service
-            .execute_bill_action(
-                TEST_BILL_ID,
-                BillAction::Recourse(
-                    recoursee,
-                    15000,
-                    "sat".to_string(),
-                    RecourseReason::Pay(15000, "sat".into()),
-                ),
-                &IdentityPublicData::new(identity.identity.clone()).unwrap(),
-                &identity.key_pair,
-                1731593928,
-            )
+            .parse_bill_qr_payload(&URL_SAFE_NO_PAD.encode(&bytes))
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidBillQrPayload))
+        ));
+    }
+
+    #[tokio::test]
+    async fn export_bill_bundle_never_includes_note() {
+        let mut ctx = get_ctx();
+        let identity = get_baseline_identity();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawee = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
+        // the local note isn't even loaded for the export
+        ctx.bill_store.expect_get_note().never();
+
+        let res = get_service(ctx)
+            .export_bill_bundle(TEST_BILL_ID, &identity.key_pair)
             .await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().blocks().len(), 3);
-        assert_eq!(res.unwrap().blocks()[2].op_code, BillOpCode::Recourse);
     }
 
-    #[test]
-    fn check_req_for_expiration_baseline() {
-        let ctx = get_ctx();
-        let service = get_service(ctx);
-        let mut bill_payment = get_baseline_cached_bill(TEST_BILL_ID.to_string());
-        bill_payment.status.payment = BillPaymentStatus {
-            time_of_request_to_pay: Some(1531593928),
-            requested_to_pay: true,
-            paid: false,
-            request_to_pay_timed_out: false,
-            rejected_to_pay: false,
-        };
+    #[tokio::test]
+    async fn export_bill_bundle_fails_for_non_participant() {
+        let mut ctx = get_ctx();
+        let bill = get_baseline_bill(TEST_BILL_ID);
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(get_genesis_chain(Some(bill.clone()))));
 
-        assert!(
-            service
-                .check_requests_for_expiration(&bill_payment, 1731593928)
-                .unwrap()
-        );
-        assert!(
-            !service
-                .check_requests_for_expiration(&bill_payment, 1431593928)
-                .unwrap()
-        );
-        bill_payment.data.maturity_date = "2018-07-15".into(); // before ts
-        assert!(
-            !service
-                .check_requests_for_expiration(&bill_payment, 1531593929)
-                .unwrap()
-        );
-        // 2 days after req to pay, but not yet 2 days after end of day maturity date
-        assert!(
-            !service
-                .check_requests_for_expiration(&bill_payment, 1531780429)
-                .unwrap()
-        );
+        let res = get_service(ctx)
+            .export_bill_bundle(TEST_BILL_ID, &BcrKeys::new())
+            .await;
+        assert!(matches!(res, Err(Error::NotFound)));
+    }
 
-        let mut bill_acceptance = get_baseline_cached_bill(TEST_BILL_ID.to_string());
-        bill_acceptance.status.acceptance = BillAcceptanceStatus {
-            time_of_request_to_accept: Some(1531593928),
-            requested_to_accept: true,
-            accepted: false,
-            request_to_accept_timed_out: false,
-            rejected_to_accept: false,
-        };
+    #[tokio::test]
+    async fn import_bill_bundle_fails_for_unknown_version() {
+        let mut bundle = export_bundle_for_test().await;
+        bundle[0] = bundle::BILL_BUNDLE_VERSION + 1;
 
-        assert!(
-            service
-                .check_requests_for_expiration(&bill_acceptance, 1731593928)
-                .unwrap()
-        );
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_save_keys().never();
+        let res = get_service(ctx)
+            .import_bill_bundle(&bundle, &get_baseline_identity().key_pair)
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(
+                ValidationError::UnknownBillBundleVersion(_)
+            ))
+        ));
+    }
 
-        let mut bill_sell = get_baseline_cached_bill(TEST_BILL_ID.to_string());
-        bill_sell.status.sell = BillSellStatus {
-            time_of_last_offer_to_sell: Some(1531593928),
-            offered_to_sell: true,
-            sold: false,
-            offer_to_sell_timed_out: false,
-            rejected_offer_to_sell: false,
-        };
+    #[tokio::test]
+    async fn import_bill_bundle_fails_for_other_identity() {
+        let bundle = export_bundle_for_test().await;
 
-        assert!(
-            service
-                .check_requests_for_expiration(&bill_sell, 1731593928)
-                .unwrap()
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_save_keys().never();
+        let res = get_service(ctx)
+            .import_bill_bundle(&bundle, &BcrKeys::new())
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidBillBundle))
+        ));
+    }
+
+    #[tokio::test]
+    async fn import_bill_bundle_fails_for_tampered_chain() {
+        let mut bundle = bundle::BillBundle::from_bytes(&export_bundle_for_test().await).unwrap();
+        bundle.payload.blocks[0].timestamp += 1;
+        // re-sign, so only the chain validation fails
+        bundle.signature = util::crypto::signature(
+            &bundle.payload.hash().unwrap(),
+            &get_baseline_identity().key_pair.get_private_key_string(),
+        )
+        .unwrap();
+
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_save_keys().never();
+        let res = get_service(ctx)
+            .import_bill_bundle(
+                &bundle.to_bytes().unwrap(),
+                &get_baseline_identity().key_pair,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidBillBundle))
+        ));
+    }
+
+    #[tokio::test]
+    async fn import_bill_bundle_fails_if_bill_exists() {
+        let bundle = export_bundle_for_test().await;
+
+        let mut ctx = get_ctx();
+        ctx.bill_store.expect_exists().returning(|_| true);
+        ctx.bill_store.expect_save_keys().never();
+        let res = get_service(ctx)
+            .import_bill_bundle(&bundle, &get_baseline_identity().key_pair)
+            .await;
+        assert!(matches!(res, Err(Error::InvalidOperation)));
+    }
+
+    #[tokio::test]
+    async fn ingest_bill_event_persists_exported_chain() {
+        let mut ctx = get_ctx();
+        let mut bill = get_baseline_bill(TEST_BILL_ID);
+        bill.drawer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let chain = get_genesis_chain(Some(bill));
+        // the bill is not known yet
+        ctx.bill_blockchain_store
+            .expect_get_chain()
+            .returning(|_| Err(persistence::Error::NoBillBlock));
+        ctx.bill_blockchain_store
+            .expect_add_block()
+            .with(eq(TEST_BILL_ID), always())
+            .times(1)
+            .returning(|_, _| Ok(()));
+        ctx.bill_store
+            .expect_save_keys()
+            .with(eq(TEST_BILL_ID), always())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let event = Event::new_bill(
+            "node_id",
+            BillChainEventPayload {
+                event_type: BillEventType::BillBlock,
+                bill_id: TEST_BILL_ID.to_owned(),
+                blocks: chain.blocks().clone(),
+                keys: Some(bill_keys()),
+                ..Default::default()
+            },
         );
+        // the event was shared as a file
+        let exported = serde_json::to_string(&EventEnvelope::try_from(event).unwrap()).unwrap();
 
-        let mut bill_recourse = get_baseline_cached_bill(TEST_BILL_ID.to_string());
-        bill_recourse.status.recourse = BillRecourseStatus {
-            time_of_last_request_to_recourse: Some(1531593928),
-            requested_to_recourse: true,
-            recoursed: false,
-            request_to_recourse_timed_out: false,
-            rejected_request_to_recourse: false,
-        };
+        let res = get_service(ctx)
+            .ingest_bill_event(serde_json::from_str(&exported).unwrap())
+            .await;
+        assert_eq!(res.unwrap(), TEST_BILL_ID.to_owned());
+    }
 
-        assert!(
-            service
-                .check_requests_for_expiration(&bill_recourse, 1731593928)
-                .unwrap()
+    #[tokio::test]
+    async fn ingest_bill_event_fails_for_event_without_blocks() {
+        let event = Event::new_bill(
+            "node_id",
+            BillChainEventPayload {
+                event_type: BillEventType::BillBlock,
+                bill_id: TEST_BILL_ID.to_owned(),
+                ..Default::default()
+            },
         );
+        let res = get_service(get_ctx())
+            .ingest_bill_event(event.try_into().unwrap())
+            .await;
+        assert!(matches!(res, Err(Error::Notification(_))));
     }
 }
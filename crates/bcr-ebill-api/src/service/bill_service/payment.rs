@@ -1,8 +1,11 @@
 use super::Result;
 use super::service::BillService;
+use crate::external::bitcoin::RecommendedFees;
+use crate::get_config;
 use crate::service::bill_service::{BillAction, BillServiceApi};
+use crate::util::log_context::LogContext;
 use bcr_ebill_core::{
-    bill::RecourseReason,
+    bill::{FeeEstimate, RecourseReason},
     blockchain::{
         Blockchain,
         bill::{
@@ -15,16 +18,85 @@ use bcr_ebill_core::{
     identity::{Identity, IdentityWithAll},
     util::BcrKeys,
 };
-use log::{debug, info};
+use log::{debug, info, warn};
 use std::collections::HashMap;
 
+// The confirmation windows of the fee tiers recommended by the fee estimator
+const FASTEST_FEE_CONFIRMATION_SECONDS: u64 = 600; // 10 minutes
+const HALF_HOUR_FEE_CONFIRMATION_SECONDS: u64 = 1800; // 30 minutes
+const HOUR_FEE_CONFIRMATION_SECONDS: u64 = 3600; // 1 hour
+const ECONOMY_FEE_CONFIRMATION_SECONDS: u64 = 86400; // 1 day
+
+/// Picks the cheapest recommended fee rate that is still expected to confirm before the deadline,
+/// falling back to the fastest one, if the deadline is closer than the next block
+pub(super) fn fee_estimate_for_deadline(
+    fees: &RecommendedFees,
+    seconds_until_deadline: u64,
+) -> FeeEstimate {
+    let tiers = [
+        (fees.economy_fee, ECONOMY_FEE_CONFIRMATION_SECONDS),
+        (fees.hour_fee, HOUR_FEE_CONFIRMATION_SECONDS),
+        (fees.half_hour_fee, HALF_HOUR_FEE_CONFIRMATION_SECONDS),
+    ];
+    let (fee_rate_sat_vb, estimated_confirmation_seconds) = tiers
+        .into_iter()
+        .find(|(_, confirmation_seconds)| *confirmation_seconds <= seconds_until_deadline)
+        .unwrap_or((fees.fastest_fee, FASTEST_FEE_CONFIRMATION_SECONDS));
+    FeeEstimate {
+        fee_rate_sat_vb,
+        estimated_confirmation_seconds,
+    }
+}
+
 impl BillService {
+    /// Suggests a miner fee for paying before the given deadline - the estimate is optional, so
+    /// if the fee estimator can't be reached, the waiting state is still shown without it
+    pub(super) async fn get_fee_estimate(
+        &self,
+        deadline_timestamp: u64,
+        current_timestamp: u64,
+    ) -> Option<FeeEstimate> {
+        match self.bitcoin_client.get_recommended_fees().await {
+            Ok(fees) => Some(fee_estimate_for_deadline(
+                &fees,
+                deadline_timestamp.saturating_sub(current_timestamp),
+            )),
+            Err(e) => {
+                warn!("Could not fetch recommended fees: {e}");
+                None
+            }
+        }
+    }
+
+    /// Checks, if the given address received at least the given sum in confirmed outputs, with
+    /// the configured number of confirmations - funds in the mempool don't count
+    async fn is_paid_with_required_confirmations(&self, address: &str, sum: u64) -> Result<bool> {
+        let paid_sum = self.bitcoin_client.get_confirmed_paid_sum(address).await?;
+        if paid_sum == 0 || paid_sum < sum {
+            return Ok(false);
+        }
+        // a confirmed sum has at least one confirmation, so we only have to check, if more are
+        // required
+        let required = get_config().payment_confirmations_required;
+        if required > 1 {
+            let confirmations = self
+                .bitcoin_client
+                .get_payment_confirmations(address)
+                .await?;
+            if confirmations < required {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
     pub(super) async fn check_bill_payment(
         &self,
         bill_id: &str,
         identity: &Identity,
     ) -> Result<()> {
-        info!("Checking bill payment for {bill_id}");
+        let log_ctx = LogContext::bill(bill_id).with_node_id(&identity.node_id);
+        info!("{log_ctx} Checking bill payment");
         let chain = self.blockchain_store.get_chain(bill_id).await?;
         let bill_keys = self.store.get_keys(bill_id).await?;
         let contacts = self.contact_store.get_map().await?;
@@ -34,7 +106,7 @@ impl BillService {
 
         if chain.block_with_operation_code_exists(BillOpCode::RequestRecourse) {
             // if the bill is in recourse, we don't have to check payment anymore
-            debug!("bill {bill_id} is in recourse - not checking for payment");
+            debug!("{log_ctx} bill is in recourse - not checking for payment");
             return Ok(());
         }
 
@@ -45,13 +117,42 @@ impl BillService {
         let address_to_pay = self
             .bitcoin_client
             .get_address_to_pay(&bill_keys.public_key, holder_public_key)?;
-        if let Ok((paid, sum)) = self
+        if let Ok(paid_sum) = self
             .bitcoin_client
-            .check_if_paid(&address_to_pay, bill.sum)
+            .get_confirmed_paid_sum(&address_to_pay)
             .await
+            .inspect_err(|e| {
+                warn!(
+                    "{log_ctx} Skipping bill payment check, the paid sum couldn't be fetched: {e}"
+                )
+            })
         {
-            if paid && sum > 0 {
-                debug!("bill {bill_id} is paid - setting to paid and invalidating cache");
+            if paid_sum > 0 && paid_sum != self.store.get_paid_sum(bill_id).await? {
+                debug!("{log_ctx} bill received {paid_sum} of {} so far", bill.sum);
+                self.store
+                    .set_paid_sum(bill_id, &address_to_pay, paid_sum)
+                    .await?;
+                // invalidate bill cache, so the paid sum is updated on next fetch
+                self.store.invalidate_bill_in_cache(bill_id).await?;
+            }
+            // the bill is only paid, once the accumulated confirmed sum covers the whole bill
+            if paid_sum > 0 && paid_sum >= bill.sum {
+                // a confirmed sum has at least one confirmation, so we only have to check, if more
+                // are required
+                let required = get_config().payment_confirmations_required;
+                if required > 1 {
+                    let confirmations = self
+                        .bitcoin_client
+                        .get_payment_confirmations(&address_to_pay)
+                        .await?;
+                    if confirmations < required {
+                        debug!(
+                            "{log_ctx} bill is paid with {confirmations} of {required} required confirmations - not setting to paid yet"
+                        );
+                        return Ok(());
+                    }
+                }
+                debug!("{log_ctx} bill is paid - setting to paid and invalidating cache");
                 self.store.set_to_paid(bill_id, &address_to_pay).await?;
                 // invalidate bill cache, so payment state is updated on next fetch
                 self.store.invalidate_bill_in_cache(bill_id).await?;
@@ -66,7 +167,8 @@ impl BillService {
         identity: &IdentityWithAll,
         now: u64,
     ) -> Result<()> {
-        info!("Checking bill recourse payment for {bill_id}");
+        let log_ctx = LogContext::bill(bill_id).with_node_id(&identity.identity.node_id);
+        info!("{log_ctx} Checking bill recourse payment");
         let bill_keys = self.store.get_keys(bill_id).await?;
         let chain = self.blockchain_store.get_chain(bill_id).await?;
         let contacts = self.contact_store.get_map().await?;
@@ -78,14 +180,17 @@ impl BillService {
                 .bitcoin_client
                 .get_address_to_pay(&bill_keys.public_key, &payment_info.recourser.node_id)?;
             // check if paid
-            if let Ok((paid, sum)) = self
-                .bitcoin_client
-                .check_if_paid(&payment_address, payment_info.sum)
+            if let Ok(paid) = self
+                .is_paid_with_required_confirmations(&payment_address, payment_info.sum)
                 .await
-            {
-                if paid && sum > 0 {
+                .inspect_err(|e| {
+                    warn!(
+                        "{log_ctx} Skipping recourse payment check, the payment state couldn't be fetched: {e}"
+                    )
+                })
+                && paid {
                     debug!(
-                        "bill {bill_id} is recourse-paid - creating recourse block if we're recourser"
+                        "{log_ctx} bill is recourse-paid - creating recourse block if we're recourser"
                     );
                     // If we are the recourser and a bill issuer and it's paid, we add a Recourse block
                     if payment_info.recourser.node_id == identity.identity.node_id {
@@ -112,6 +217,7 @@ impl BillService {
                                     &signer_identity,
                                     &identity.key_pair,
                                     now,
+                                    None,
                                 )
                                 .await?;
                         }
@@ -123,8 +229,7 @@ impl BillService {
                     // If a local company is the recourser, create the recourse block as that company
                     if let Some(recourser_company) =
                         local_companies.get(&payment_info.recourser.node_id)
-                    {
-                        if recourser_company
+                        && recourser_company
                             .0
                             .signatories
                             .iter()
@@ -151,12 +256,11 @@ impl BillService {
                                     // signer keys (company keys)
                                     &BcrKeys::from_private_key(&recourser_company.1.private_key)?,
                                     now,
+                                    None,
                                 )
                                 .await?;
                         }
-                    }
                 }
-            }
         }
         Ok(())
     }
@@ -167,7 +271,8 @@ impl BillService {
         identity: &IdentityWithAll,
         now: u64,
     ) -> Result<()> {
-        info!("Checking bill offer to sell payment for {bill_id}");
+        let log_ctx = LogContext::bill(bill_id).with_node_id(&identity.identity.node_id);
+        info!("{log_ctx} Checking bill offer to sell payment");
         let bill_keys = self.store.get_keys(bill_id).await?;
         let chain = self.blockchain_store.get_chain(bill_id).await?;
         let contacts = self.contact_store.get_map().await?;
@@ -175,13 +280,19 @@ impl BillService {
             chain.is_last_offer_to_sell_block_waiting_for_payment(&bill_keys, now)
         {
             // check if paid
-            if let Ok((paid, sum)) = self
-                .bitcoin_client
-                .check_if_paid(&payment_info.payment_address, payment_info.sum)
+            if let Ok(paid) = self
+                .is_paid_with_required_confirmations(
+                    &payment_info.payment_address,
+                    payment_info.sum,
+                )
                 .await
-            {
-                if paid && sum > 0 {
-                    debug!("bill {bill_id} got bought - creating sell block if we're seller");
+                .inspect_err(|e| {
+                    warn!(
+                        "{log_ctx} Skipping offer to sell payment check, the payment state couldn't be fetched: {e}"
+                    )
+                })
+                && paid {
+                    debug!("{log_ctx} bill got bought - creating sell block if we're seller");
                     // If we are the seller and a bill issuer and it's paid, we add a Sell block
                     if payment_info.seller.node_id == identity.identity.node_id {
                         if let Some(signer_identity) =
@@ -203,6 +314,7 @@ impl BillService {
                                     &signer_identity,
                                     &identity.key_pair,
                                     now,
+                                    None,
                                 )
                                 .await?;
                         }
@@ -213,8 +325,7 @@ impl BillService {
                         self.company_store.get_all().await?;
                     // If a local company is the seller, create the sell block as that company
                     if let Some(seller_company) = local_companies.get(&payment_info.seller.node_id)
-                    {
-                        if seller_company
+                        && seller_company
                             .0
                             .signatories
                             .iter()
@@ -238,12 +349,11 @@ impl BillService {
                                     // signer keys (company keys)
                                     &BcrKeys::from_private_key(&seller_company.1.private_key)?,
                                     now,
+                                    None,
                                 )
                                 .await?;
                         }
-                    }
                 }
-            }
         }
         Ok(())
     }
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
-use super::{BillAction, Result, service::BillService};
+use super::{BillAction, Result, error::Error, service::BillService};
 use bcr_ebill_core::{
+    ValidationError,
     bill::{BillKeys, RecourseReason},
     blockchain::bill::BillBlockchain,
     contact::Contact,
@@ -23,7 +24,7 @@ impl BillService {
             .get_last_version_bill(blockchain, bill_keys, identity, contacts)
             .await?;
 
-        let chain_event = BillChainEvent::new(
+        let mut chain_event = BillChainEvent::new(
             &last_version_bill,
             blockchain,
             bill_keys,
@@ -47,6 +48,19 @@ impl BillService {
                     .send_request_to_pay_event(&chain_event)
                     .await?;
             }
+            BillAction::RequestAcceptanceAndPayment(_) => {
+                // if the bill was already accepted, only the request to pay block was added
+                if blockchain.is_latest_block_combined_request_to_pay() {
+                    chain_event = chain_event.with_new_block_count(2);
+                    self.notification_service
+                        .send_request_to_accept_and_pay_event(&chain_event)
+                        .await?;
+                } else {
+                    self.notification_service
+                        .send_request_to_pay_event(&chain_event)
+                        .await?;
+                }
+            }
             BillAction::RequestRecourse(recoursee, recourse_reason) => {
                 let action_type = match recourse_reason {
                     RecourseReason::Accept => ActionType::AcceptBill,
@@ -81,24 +95,100 @@ impl BillService {
                     .send_bill_is_endorsed_event(&chain_event)
                     .await?;
             }
-            BillAction::RejectAcceptance => {
+            BillAction::RejectAcceptance(reason) => {
                 self.notification_service
-                    .send_request_to_action_rejected_event(&chain_event, ActionType::AcceptBill)
+                    .send_request_to_action_rejected_event(
+                        &chain_event,
+                        ActionType::AcceptBill,
+                        reason.clone(),
+                    )
                     .await?;
             }
-            BillAction::RejectBuying => {
+            BillAction::RejectBuying(reason) => {
                 self.notification_service
-                    .send_request_to_action_rejected_event(&chain_event, ActionType::BuyBill)
+                    .send_request_to_action_rejected_event(
+                        &chain_event,
+                        ActionType::BuyBill,
+                        reason.clone(),
+                    )
                     .await?;
             }
-            BillAction::RejectPayment => {
+            BillAction::RejectPayment(reason) => {
                 self.notification_service
-                    .send_request_to_action_rejected_event(&chain_event, ActionType::PayBill)
+                    .send_request_to_action_rejected_event(
+                        &chain_event,
+                        ActionType::PayBill,
+                        reason.clone(),
+                    )
                     .await?;
             }
             BillAction::RejectPaymentForRecourse => {
                 self.notification_service
-                    .send_request_to_action_rejected_event(&chain_event, ActionType::RecourseBill)
+                    .send_request_to_action_rejected_event(
+                        &chain_event,
+                        ActionType::RecourseBill,
+                        None,
+                    )
+                    .await?;
+            }
+            BillAction::CancelOfferToSell => {
+                self.notification_service
+                    .send_offer_to_sell_cancelled_event(&chain_event)
+                    .await?;
+            }
+            BillAction::CancelRequestToAccept => {
+                self.notification_service
+                    .send_request_to_accept_cancelled_event(&chain_event)
+                    .await?;
+            }
+            BillAction::AttachFile(_) => {
+                self.notification_service
+                    .send_bill_files_attached_event(&chain_event)
+                    .await?;
+            }
+            BillAction::MarkPaidExternally(_) => {
+                self.notification_service
+                    .send_bill_is_paid_event(&chain_event)
+                    .await?;
+            }
+            BillAction::Acknowledge => {
+                self.notification_service
+                    .send_bill_acknowledged_event(&chain_event)
+                    .await?;
+            }
+            BillAction::CorrectDrawee(_) => {
+                self.notification_service
+                    .send_drawee_corrected_event(&chain_event)
+                    .await?;
+            }
+            BillAction::SubmitMintQuote(_, _, _) => {
+                let (_, quote) = blockchain
+                    .get_open_mint_quote(bill_keys)?
+                    .ok_or(Error::Validation(ValidationError::BillHasNoOpenMintQuote))?;
+                self.notification_service
+                    .send_new_quote_event(&chain_event, &quote.requester.into())
+                    .await?;
+            }
+            BillAction::ApproveMintQuote => {
+                let mint_request =
+                    blockchain
+                        .get_last_mint_request(bill_keys)?
+                        .ok_or(Error::Validation(
+                            ValidationError::BillWasNotRequestedToMint,
+                        ))?;
+                self.notification_service
+                    .send_quote_is_approved_event(&chain_event, &mint_request.mint.into())
+                    .await?;
+            }
+            BillAction::RejectMintQuote => {
+                let mint_request =
+                    blockchain
+                        .get_last_mint_request(bill_keys)?
+                        .ok_or(Error::Validation(
+                            ValidationError::BillWasNotRequestedToMint,
+                        ))?;
+                self.notification_service
+                    .send_quote_is_rejected_event(&chain_event, &mint_request.mint.into())
                     .await?;
             }
         };
@@ -0,0 +1,141 @@
+use super::Result;
+use crate::util;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use bcr_ebill_core::ValidationError;
+use borsh_derive::{BorshDeserialize, BorshSerialize};
+
+/// The version of the bill QR payload format, stored as the first byte of a payload
+pub const BILL_QR_PAYLOAD_VERSION: u8 = 1;
+
+/// The compact identity of a bill, e.g. to share it at a point of sale - the full chain can be
+/// fetched from the given relay
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct BillQrPayload {
+    pub bill_id: String,
+    pub sum: u64,
+    pub currency: String,
+    pub drawee_node_id: String,
+    pub payee_node_id: String,
+    pub relay: String,
+    /// The node id of the participant, who created and signed the payload
+    pub signer_node_id: String,
+}
+
+/// A payload, signed by its signer. It's serialized with borsh and URL-safe base64 encoded without
+/// padding, since that's more compact than json and doesn't need any characters, which are
+/// awkward in QR codes
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct SignedBillQrPayload {
+    pub payload: BillQrPayload,
+    /// The raw signature bytes, instead of base58, to keep the payload small
+    pub signature: Vec<u8>,
+}
+
+impl BillQrPayload {
+    /// The hash of the payload, which is signed by the signer
+    pub fn hash(&self) -> Result<String> {
+        let bytes = borsh::to_vec(self).map_err(|_| ValidationError::InvalidBillQrPayload)?;
+        Ok(util::sha256_hash(&bytes))
+    }
+
+    /// Signs the payload with the given private key and returns the encoded, signed payload
+    pub fn sign(self, private_key: &str) -> Result<String> {
+        let signature = util::crypto::signature(&self.hash()?, private_key)?;
+        let signed = SignedBillQrPayload {
+            payload: self,
+            signature: util::base58_decode(&signature)
+                .map_err(|_| ValidationError::InvalidBillQrPayload)?,
+        };
+        let mut bytes = vec![BILL_QR_PAYLOAD_VERSION];
+        bytes.extend(borsh::to_vec(&signed).map_err(|_| ValidationError::InvalidBillQrPayload)?);
+        Ok(URL_SAFE_NO_PAD.encode(&bytes))
+    }
+
+    /// Decodes the given signed payload and returns it, if the signature of its signer is valid
+    pub fn verify(encoded: &str) -> Result<Self> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| ValidationError::InvalidBillQrPayload)?;
+        let signed: SignedBillQrPayload = match bytes.split_first() {
+            Some((&BILL_QR_PAYLOAD_VERSION, rest)) => {
+                borsh::from_slice(rest).map_err(|_| ValidationError::InvalidBillQrPayload)?
+            }
+            _ => return Err(ValidationError::InvalidBillQrPayload.into()),
+        };
+        let signature = util::base58_encode(&signed.signature);
+        if !util::crypto::verify(
+            &signed.payload.hash()?,
+            &signature,
+            &signed.payload.signer_node_id,
+        )
+        .unwrap_or(false)
+        {
+            return Err(ValidationError::InvalidBillQrPayload.into());
+        }
+        Ok(signed.payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::bill_service::Error;
+    use crate::util::BcrKeys;
+
+    fn payload(signer_node_id: &str) -> BillQrPayload {
+        BillQrPayload {
+            bill_id: "some_bill".to_string(),
+            sum: 15000,
+            currency: "sat".to_string(),
+            drawee_node_id: "drawee".to_string(),
+            payee_node_id: "payee".to_string(),
+            relay: "ws://localhost:8080".to_string(),
+            signer_node_id: signer_node_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn qr_payload_roundtrip() {
+        let keys = BcrKeys::new();
+        let encoded = payload(&keys.get_public_key())
+            .sign(&keys.get_private_key_string())
+            .unwrap();
+        assert!(
+            encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+        assert_eq!(
+            BillQrPayload::verify(&encoded).unwrap(),
+            payload(&keys.get_public_key())
+        );
+    }
+
+    #[test]
+    fn qr_payload_with_other_signer_fails() {
+        let keys = BcrKeys::new();
+        let encoded = payload(&BcrKeys::new().get_public_key())
+            .sign(&keys.get_private_key_string())
+            .unwrap();
+        assert!(matches!(
+            BillQrPayload::verify(&encoded),
+            Err(Error::Validation(ValidationError::InvalidBillQrPayload))
+        ));
+    }
+
+    #[test]
+    fn invalid_qr_payload_fails() {
+        assert!(matches!(
+            BillQrPayload::verify(""),
+            Err(Error::Validation(ValidationError::InvalidBillQrPayload))
+        ));
+        assert!(matches!(
+            BillQrPayload::verify("not base64 +/="),
+            Err(Error::Validation(ValidationError::InvalidBillQrPayload))
+        ));
+        assert!(matches!(
+            BillQrPayload::verify(&URL_SAFE_NO_PAD.encode([BILL_QR_PAYLOAD_VERSION + 1, 1, 2])),
+            Err(Error::Validation(ValidationError::InvalidBillQrPayload))
+        ));
+    }
+}
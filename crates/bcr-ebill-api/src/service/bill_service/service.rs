@@ -1,45 +1,70 @@
+use super::bundle::{BillBundle, BillBundleFile, BillBundlePayload};
+use super::data_fetching::waiting_state_from_chain;
 use super::error::Error;
-use super::{BillAction, BillServiceApi, Result};
-use crate::blockchain::Blockchain;
-use crate::blockchain::bill::block::BillIdentityBlockData;
-use crate::blockchain::bill::{BillBlockchain, BillOpCode};
+use super::qr_payload::BillQrPayload;
+use super::{BillAction, BillIssueValidator, BillServiceApi, NoOpBillIssueValidator, Result};
+use crate::blockchain::bill::block::{
+    BillIdentityBlockData, BillOfferToSellBlockData, BillRecourseBlockData,
+    BillRequestRecourseBlockData, BillRequestToPayBlockData, BillSellBlockData,
+    BillSignatoryBlockData,
+};
+use crate::blockchain::bill::{
+    BillBlock, BillBlockchain, BillOpCode, OfferToSellWaitingForPayment, RecourseWaitingForPayment,
+};
+use crate::blockchain::{Blockchain, ChainVerificationReport};
+use crate::constants::MAX_CONCURRENT_BILL_CHAIN_LOADS;
 use crate::data::{
     File,
     bill::{
-        BillCombinedBitcoinKey, BillKeys, BillRole, BillsBalance, BillsBalanceOverview,
-        BillsFilterRole, BitcreditBill, BitcreditBillResult, Endorsement, LightBitcreditBillResult,
-        LightSignedBy, PastEndorsee,
+        BillCombinedBitcoinKey, BillCurrentWaitingState, BillInconsistency, BillInconsistencyKind,
+        BillKeys, BillRole, BillsBalance, BillsBalanceOverview, BillsBalanceOverviewWithFiat,
+        BillsBalanceWithFiat, BillsFilterRole, BillsFilterStatus, BitcreditBill,
+        BitcreditBillResult, Endorsement, ExposureSummary, LightBitcreditBillResult, LightSignedBy,
+        PastEndorsee, ResolvedParticipant, SuggestedAction,
     },
     contact::{ContactType, IdentityPublicData, LightIdentityPublicData},
-    identity::Identity,
+    identity::{Identity, IdentityWithAll},
 };
 use crate::external::bitcoin::BitcoinClientApi;
+use crate::external::fiat::FiatRateClientApi;
 use crate::get_config;
-use crate::persistence::bill::BillChainStoreApi;
-use crate::persistence::bill::BillStoreApi;
+use crate::persistence::bill::{BillAutoAcceptStoreApi, BillChainStoreApi, BillTemplateStoreApi};
+use crate::persistence::bill::{BillStoreApi, bill_keys_from_bytes, bill_keys_to_bytes};
 use crate::persistence::company::{CompanyChainStoreApi, CompanyStoreApi};
 use crate::persistence::contact::ContactStoreApi;
 use crate::persistence::file_upload::FileUploadStoreApi;
 use crate::persistence::identity::{IdentityChainStoreApi, IdentityStoreApi};
 use crate::util::BcrKeys;
+use crate::util::job::next_bill_window;
+use crate::util::log_context::LogContext;
 use crate::{external, util};
 use async_trait::async_trait;
-use bcr_ebill_core::bill::validation::get_deadline_base_for_req_to_pay;
+use bcr_ebill_core::bill::validation::{
+    get_deadline_base_for_req_to_pay, validate_bill_attachments,
+};
 use bcr_ebill_core::bill::{
-    BillIssueData, BillValidateActionData, PastPaymentDataPayment, PastPaymentDataRecourse,
-    PastPaymentDataSell, PastPaymentResult, PastPaymentStatus,
+    BillAutoAcceptRule, BillHistoryEntry, BillHold, BillIssueData, BillTemplate,
+    BillTemplateOverrides, BillValidateActionData, BillView, BillViewKey, FieldDiff,
+    PastPaymentDataPayment, PastPaymentDataRecourse, PastPaymentDataSell, PastPaymentResult,
+    PastPaymentStatus, PaymentTx, QueuedAutoAccept,
 };
 use bcr_ebill_core::constants::{
     ACCEPT_DEADLINE_SECONDS, PAYMENT_DEADLINE_SECONDS, RECOURSE_DEADLINE_SECONDS,
 };
 use bcr_ebill_core::contact::Contact;
-use bcr_ebill_core::notification::ActionType;
+use bcr_ebill_core::notification::{ActionType, NotificationPreview};
 use bcr_ebill_core::util::currency;
-use bcr_ebill_core::{ServiceTraitBounds, Validate};
-use bcr_ebill_transport::NotificationServiceApi;
-use log::{debug, error, info};
+use bcr_ebill_core::{Field, ServiceTraitBounds, Validate, ValidationError};
+use bcr_ebill_transport::handler::BillChainSync;
+use bcr_ebill_transport::template::{NotificationTemplateData, render_notification_preview};
+use bcr_ebill_transport::{BillChainEvent, EventEnvelope, NotificationServiceApi};
+use futures::{StreamExt, stream};
+use log::{debug, error, info, warn};
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+/// The content hash of attached files by bill id and file name
+type FileHashCache = HashMap<(String, String), Option<String>>;
 
 /// The bill service is responsible for all bill-related logic and for syncing them with the
 /// network
@@ -50,26 +75,42 @@ pub struct BillService {
     pub identity_store: Arc<dyn IdentityStoreApi>,
     pub file_upload_store: Arc<dyn FileUploadStoreApi>,
     pub bitcoin_client: Arc<dyn BitcoinClientApi>,
+    pub fiat_rate_client: Arc<dyn FiatRateClientApi>,
     pub notification_service: Arc<dyn NotificationServiceApi>,
     pub identity_blockchain_store: Arc<dyn IdentityChainStoreApi>,
     pub company_blockchain_store: Arc<dyn CompanyChainStoreApi>,
     pub contact_store: Arc<dyn ContactStoreApi>,
     pub company_store: Arc<dyn CompanyStoreApi>,
+    pub template_store: Arc<dyn BillTemplateStoreApi>,
+    pub auto_accept_store: Arc<dyn BillAutoAcceptStoreApi>,
+    /// Checks deployment specific rules before a bill is issued
+    pub issue_validator: Arc<dyn BillIssueValidator>,
+    /// The position of each bill check job in its list of bills, if the number of bills per
+    /// tick is limited
+    job_cursors: Arc<Mutex<HashMap<&'static str, usize>>>,
+    /// The content hash of each locally stored attached file by bill id and file name, taken
+    /// when the file is stored, or checked for the first time, so the files don't have to be
+    /// decrypted on every recalculation of a bill - `None`, if the file can't be decrypted
+    pub(super) file_hashes: Arc<Mutex<FileHashCache>>,
 }
 impl ServiceTraitBounds for BillService {}
 
 impl BillService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         store: Arc<dyn BillStoreApi>,
         blockchain_store: Arc<dyn BillChainStoreApi>,
         identity_store: Arc<dyn IdentityStoreApi>,
         file_upload_store: Arc<dyn FileUploadStoreApi>,
         bitcoin_client: Arc<dyn BitcoinClientApi>,
+        fiat_rate_client: Arc<dyn FiatRateClientApi>,
         notification_service: Arc<dyn NotificationServiceApi>,
         identity_blockchain_store: Arc<dyn IdentityChainStoreApi>,
         company_blockchain_store: Arc<dyn CompanyChainStoreApi>,
         contact_store: Arc<dyn ContactStoreApi>,
         company_store: Arc<dyn CompanyStoreApi>,
+        template_store: Arc<dyn BillTemplateStoreApi>,
+        auto_accept_store: Arc<dyn BillAutoAcceptStoreApi>,
     ) -> Self {
         Self {
             store,
@@ -77,266 +118,143 @@ impl BillService {
             identity_store,
             file_upload_store,
             bitcoin_client,
+            fiat_rate_client,
             notification_service,
             identity_blockchain_store,
             company_blockchain_store,
             contact_store,
             company_store,
+            template_store,
+            auto_accept_store,
+            issue_validator: Arc::new(NoOpBillIssueValidator),
+            job_cursors: Arc::new(Mutex::new(HashMap::new())),
+            file_hashes: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    /// Recalculates the full bill and updates it in the cache
-    pub(super) async fn recalculate_and_persist_bill(
-        &self,
-        bill_id: &str,
-        chain: &BillBlockchain,
-        bill_keys: &BillKeys,
-        local_identity: &Identity,
-        current_identity_node_id: &str,
-        current_timestamp: u64,
-    ) -> Result<()> {
-        let calculated_bill = self
-            .calculate_full_bill(
-                chain,
-                bill_keys,
-                local_identity,
-                current_identity_node_id,
-                current_timestamp,
-            )
-            .await?;
-        self.store
-            .save_bill_to_cache(bill_id, &calculated_bill)
-            .await?;
-        Ok(())
+    /// Checks the given rules before issuing a bill, instead of accepting all bills
+    pub fn with_issue_validator(mut self, issue_validator: Arc<dyn BillIssueValidator>) -> Self {
+        self.issue_validator = issue_validator;
+        self
     }
 
-    /// If it's our identity, we take the fields from there, otherwise we check contacts,
-    /// companies, or leave them empty
-    pub(super) async fn extend_bill_chain_identity_data_from_contacts_or_identity(
-        &self,
-        chain_identity: BillIdentityBlockData,
-        identity: &Identity,
-        contacts: &HashMap<String, Contact>,
-    ) -> IdentityPublicData {
-        let (email, nostr_relay) = match chain_identity.node_id {
-            ref v if *v == identity.node_id => {
-                (Some(identity.email.clone()), identity.nostr_relay.clone())
-            }
-            ref other_node_id => {
-                if let Some(contact) = contacts.get(other_node_id) {
-                    (
-                        Some(contact.email.clone()),
-                        contact.nostr_relays.first().cloned(),
-                    )
-                } else if chain_identity.t == ContactType::Company {
-                    if let Ok(company) = self.company_store.get(other_node_id).await {
-                        (
-                            Some(company.email.clone()),
-                            identity.nostr_relay.clone(), // if it's a local company, we take our relay
-                        )
-                    } else {
-                        (None, None)
-                    }
-                } else {
-                    (None, None)
-                }
-            }
-        };
-        IdentityPublicData {
-            t: chain_identity.t,
-            node_id: chain_identity.node_id,
-            name: chain_identity.name,
-            postal_address: chain_identity.postal_address,
-            email,
-            nostr_relay,
+    /// Returns the bills the given bill check job processes in this tick - all of them, or the
+    /// next `job_runner_bills_per_tick` after the ones processed in the previous tick
+    fn bills_for_tick(&self, job: &'static str, bill_ids: Vec<String>) -> Vec<String> {
+        let limit = get_config().job_runner_bills_per_tick;
+        match self.job_cursors.lock() {
+            Ok(mut cursors) => next_bill_window(bill_ids, limit, cursors.entry(job).or_default()),
+            Err(_) => bill_ids,
         }
     }
 
-    async fn check_bill_timeouts(&self, bill_id: &str, now: u64) -> Result<()> {
-        let chain = self.blockchain_store.get_chain(bill_id).await?;
-        let bill_keys = self.store.get_keys(bill_id).await?;
-        let latest_ts = chain.get_latest_block().timestamp;
-        let contacts = self.contact_store.get_map().await?;
-
-        if let Some(action) = match chain.get_latest_block().op_code {
-            BillOpCode::RequestToPay | BillOpCode::OfferToSell
-                if (latest_ts + PAYMENT_DEADLINE_SECONDS <= now) =>
-            {
-                Some(ActionType::PayBill)
-            }
-            BillOpCode::RequestToAccept if (latest_ts + ACCEPT_DEADLINE_SECONDS <= now) => {
-                Some(ActionType::AcceptBill)
-            }
-            BillOpCode::RequestRecourse if (latest_ts + RECOURSE_DEADLINE_SECONDS <= now) => {
-                Some(ActionType::RecourseBill)
-            }
-            _ => None,
-        } {
-            // did we already send the notification
-            let sent = self
-                .notification_service
-                .check_bill_notification_sent(
-                    bill_id,
-                    chain.block_height() as i32,
-                    action.to_owned(),
-                )
-                .await?;
-
-            if !sent {
-                let identity = self.identity_store.get().await?;
-                let current_identity = IdentityPublicData::new(identity.clone());
-                let participants = chain.get_all_nodes_from_bill(&bill_keys)?;
-                let mut recipient_options = vec![current_identity];
-                let bill = self
-                    .get_last_version_bill(&chain, &bill_keys, &identity, &contacts)
-                    .await?;
-
-                for node_id in participants {
-                    let contact: Option<IdentityPublicData> =
-                        self.contact_store.get(&node_id).await?.map(|c| c.into());
-                    recipient_options.push(contact);
-                }
-
-                let recipients = recipient_options
-                    .into_iter()
-                    .flatten()
-                    .collect::<Vec<IdentityPublicData>>();
-
-                self.notification_service
-                    .send_request_to_action_timed_out_event(
-                        &identity.node_id,
-                        bill_id,
-                        Some(bill.sum),
-                        action.to_owned(),
-                        recipients,
-                    )
-                    .await?;
-
-                // remember we have sent the notification
-                self.notification_service
-                    .mark_bill_notification_sent(bill_id, chain.block_height() as i32, action)
-                    .await?;
-            }
+    /// Returns the content hash of the given attached file, if it was stored, or checked already
+    pub(super) fn known_file_hash(&self, bill_id: &str, file_name: &str) -> Option<Option<String>> {
+        match self.file_hashes.lock() {
+            Ok(file_hashes) => file_hashes
+                .get(&(bill_id.to_owned(), file_name.to_owned()))
+                .cloned(),
+            Err(_) => None,
         }
-        Ok(())
     }
-}
 
-#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
-#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
-impl BillServiceApi for BillService {
-    async fn get_bill_balances(
-        &self,
-        _currency: &str,
-        current_identity_node_id: &str,
-    ) -> Result<BillsBalanceOverview> {
-        let bills = self.get_bills(current_identity_node_id).await?;
+    /// Remembers the content hash of the given attached file, replacing the one of a file stored
+    /// with the same name before
+    pub(super) fn remember_file_hash(&self, bill_id: &str, file_name: &str, hash: Option<String>) {
+        if let Ok(mut file_hashes) = self.file_hashes.lock() {
+            file_hashes.insert((bill_id.to_owned(), file_name.to_owned()), hash);
+        }
+    }
 
+    /// Aggregates the balances per role and the exposure of the given node id over the given
+    /// bills in a single pass
+    fn summarize_bills(
+        bills: &[BitcreditBillResult],
+        node_id: &str,
+        current_timestamp: u64,
+    ) -> ExposureSummary {
         let mut payer_sum = 0;
         let mut payee_sum = 0;
         let mut contingent_sum = 0;
+        let mut accepted_count = 0;
+        let mut awaiting_acceptance_count = 0;
+        let mut overdue_count = 0;
 
         for bill in bills {
-            if let Ok(sum) = currency::parse_sum(&bill.data.sum) {
-                if let Some(bill_role) = bill.get_bill_role_for_node_id(current_identity_node_id) {
-                    match bill_role {
-                        BillRole::Payee => payee_sum += sum,
-                        BillRole::Payer => payer_sum += sum,
-                        BillRole::Contingent => contingent_sum += sum,
-                    };
+            if let Ok(sum) = currency::parse_sum(&bill.data.sum)
+                && let Some(bill_role) = bill.get_bill_role_for_node_id(node_id)
+            {
+                match bill_role {
+                    BillRole::Payee => payee_sum += sum,
+                    BillRole::Payer => payer_sum += sum,
+                    BillRole::Contingent => contingent_sum += sum,
+                };
+
+                let acceptance = &bill.status.acceptance;
+                if acceptance.accepted {
+                    accepted_count += 1;
+                } else if acceptance.requested_to_accept
+                    && !acceptance.rejected_to_accept
+                    && !acceptance.request_to_accept_timed_out
+                {
+                    awaiting_acceptance_count += 1;
+                }
+                if !bill.status.payment.paid && bill.data.time_of_maturity < current_timestamp {
+                    overdue_count += 1;
                 }
             }
         }
 
-        Ok(BillsBalanceOverview {
-            payee: BillsBalance {
-                sum: currency::sum_to_string(payee_sum),
-            },
-            payer: BillsBalance {
-                sum: currency::sum_to_string(payer_sum),
+        ExposureSummary {
+            balances: BillsBalanceOverview {
+                payee: BillsBalance {
+                    sum: currency::sum_to_string(payee_sum),
+                },
+                payer: BillsBalance {
+                    sum: currency::sum_to_string(payer_sum),
+                },
+                contingent: BillsBalance {
+                    sum: currency::sum_to_string(contingent_sum),
+                },
             },
-            contingent: BillsBalance {
-                sum: currency::sum_to_string(contingent_sum),
-            },
-        })
+            net_position: (payee_sum as i64 - payer_sum as i64).to_string(),
+            accepted_count,
+            awaiting_acceptance_count,
+            overdue_count,
+        }
     }
 
-    async fn search_bills(
+    /// Returns the bills with the given ids from the cache, recalculating and caching the ones,
+    /// which aren't cached, or which need to be recalculated. Only bills where the current node id
+    /// is a participant are returned
+    async fn get_bills_by_ids(
         &self,
-        _currency: &str,
-        search_term: &Option<String>,
-        date_range_from: Option<u64>,
-        date_range_to: Option<u64>,
-        role: &BillsFilterRole,
+        bill_ids: Vec<String>,
         current_identity_node_id: &str,
-    ) -> Result<Vec<LightBitcreditBillResult>> {
-        debug!(
-            "searching bills with {search_term:?} from {date_range_from:?} to {date_range_to:?} and {role:?}"
-        );
-        let bills = self.get_bills(current_identity_node_id).await?;
-        let mut result = vec![];
-
-        // for now we do the search here - with the quick-fetch table, we can search in surrealDB
-        // directly
-        for bill in bills {
-            // if the bill wasn't issued between from and to, we kick them out
-            if let Ok(issue_date_ts) =
-                util::date::date_string_to_timestamp(&bill.data.issue_date, None)
-            {
-                if let Some(from) = date_range_from {
-                    if from > issue_date_ts {
-                        continue;
-                    }
-                }
-                if let Some(to) = date_range_to {
-                    if to < issue_date_ts {
-                        continue;
-                    }
-                }
-            }
-
-            let bill_role = match bill.get_bill_role_for_node_id(current_identity_node_id) {
-                Some(bill_role) => bill_role,
-                None => continue, // node is not in bill - don't add
-            };
-
-            match role {
-                BillsFilterRole::All => (), // we take all
-                BillsFilterRole::Payer => {
-                    if bill_role != BillRole::Payer {
-                        // payer selected, but node not payer
-                        continue;
-                    }
-                }
-                BillsFilterRole::Payee => {
-                    if bill_role != BillRole::Payee {
-                        // payee selected, but node not payee
-                        continue;
-                    }
-                }
-                BillsFilterRole::Contingent => {
-                    if bill_role != BillRole::Contingent {
-                        // contingent selected, but node not
-                        // contingent
-                        continue;
-                    }
-                }
-            };
-
-            if let Some(st) = search_term {
-                if !bill.search_bill_for_search_term(st) {
-                    continue;
-                }
-            }
-
-            result.push(bill.into());
-        }
+    ) -> Result<Vec<BitcreditBillResult>> {
+        let bills = self
+            .load_bills_by_ids(bill_ids, current_identity_node_id)
+            .await?;
+        // only return bills where the current node id is a participant
+        Ok(bills
+            .into_iter()
+            .filter(|b| Self::is_participant(b, current_identity_node_id))
+            .collect())
+    }
 
-        Ok(result)
+    fn is_participant(bill: &BitcreditBillResult, node_id: &str) -> bool {
+        bill.participants
+            .all_participant_node_ids
+            .iter()
+            .any(|p| p == node_id)
     }
 
-    async fn get_bills(&self, current_identity_node_id: &str) -> Result<Vec<BitcreditBillResult>> {
-        let bill_ids = self.store.get_ids().await?;
+    /// Loads the bills with the given ids, regardless of who participates in them
+    async fn load_bills_by_ids(
+        &self,
+        bill_ids: Vec<String>,
+        current_identity_node_id: &str,
+    ) -> Result<Vec<BitcreditBillResult>> {
         let identity = self.identity_store.get().await?;
         let current_timestamp = util::date::now().timestamp() as u64;
 
@@ -353,8 +271,8 @@ impl BillServiceApi for BillService {
             // accept/pay/recourse/sell is expired, we need to recalculate the bill
             if self.check_requests_for_expiration(bill, current_timestamp)? {
                 debug!(
-                    "Bill cache hit, but needs to recalculate because of request deadline {} - recalculating",
-                    &bill.id
+                    "{} Bill cache hit, but needs to recalculate because of request deadline - recalculating",
+                    LogContext::bill(&bill.id).with_node_id(current_identity_node_id)
                 );
                 *bill = self
                     .recalculate_and_cache_bill(
@@ -367,20 +285,35 @@ impl BillServiceApi for BillService {
             }
         }
 
-        for bill_id in bill_ids.iter() {
-            // if bill was not in cache - recalculate and cache it
-            if !bills.iter().any(|bill| *bill_id == bill.id) {
-                debug!("Bill {bill_id} was not in the cache - recalculate");
-                let calculated_bill = self
-                    .recalculate_and_cache_bill(
-                        bill_id,
+        // if bills were not in cache - recalculate and cache them, loading their chains concurrently
+        let uncached_bill_ids: Vec<String> = bill_ids
+            .iter()
+            .filter(|bill_id| !bills.iter().any(|bill| **bill_id == bill.id))
+            .cloned()
+            .collect();
+        // results are returned in the order of the bill ids, even though they're loaded concurrently
+        let calculation_results: Vec<Result<BitcreditBillResult>> = stream::iter(uncached_bill_ids)
+            .map(|bill_id| {
+                let identity = identity.clone();
+                async move {
+                    debug!(
+                        "{} Bill was not in the cache - recalculate",
+                        LogContext::bill(&bill_id).with_node_id(current_identity_node_id)
+                    );
+                    self.recalculate_and_cache_bill(
+                        &bill_id,
                         &identity,
                         current_identity_node_id,
                         current_timestamp,
                     )
-                    .await?;
-                bills.push(calculated_bill);
-            }
+                    .await
+                }
+            })
+            .buffered(MAX_CONCURRENT_BILL_CHAIN_LOADS)
+            .collect()
+            .await;
+        for calculation_result in calculation_results {
+            bills.push(calculation_result?);
         }
 
         // fetch active notifications for bills
@@ -391,55 +324,1092 @@ impl BillServiceApi for BillService {
         for bill in bills.iter_mut() {
             bill.data.active_notification = active_notifications.get(&bill.id).cloned();
         }
+        Ok(bills)
+    }
 
-        // only return bills where the current node id is a participant
-        Ok(bills
-            .into_iter()
-            .filter(|b| {
-                b.participants
-                    .all_participant_node_ids
-                    .iter()
-                    .any(|p| p == current_identity_node_id)
-            })
-            .collect())
+    /// Checks if the given bill was manually put on hold and should be skipped by the jobs - if
+    /// the hold state can't be checked, the bill is skipped as well
+    async fn is_on_hold(&self, bill_id: &str) -> bool {
+        let log_ctx = LogContext::bill(bill_id);
+        match self.store.get_hold(bill_id).await {
+            Ok(Some(hold)) => {
+                debug!(
+                    "{log_ctx} Skipping bill, since it's on hold: {}",
+                    hold.reason
+                );
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                error!("{log_ctx} Could not check hold state of bill, skipping it: {e}");
+                true
+            }
+        }
     }
 
-    async fn get_combined_bitcoin_key_for_bill(
+    /// Fails, if only a read-only view of the bill exists - an observer only holds the view key
+    /// of the bill, which can't be used to act on it
+    async fn check_bill_is_not_read_only_view(
         &self,
         bill_id: &str,
-        caller_public_data: &IdentityPublicData,
-        caller_keys: &BcrKeys,
-    ) -> Result<BillCombinedBitcoinKey> {
-        let chain = self.blockchain_store.get_chain(bill_id).await?;
-        let bill_keys = self.store.get_keys(bill_id).await?;
-
-        // if caller is not part of the bill, they can't access it
-        if !chain
-            .get_all_nodes_from_bill(&bill_keys)?
-            .iter()
-            .any(|p| p == &caller_public_data.node_id)
-        {
-            debug!("caller is not a participant of bill {bill_id}");
-            return Err(Error::NotFound);
+        log_ctx: &LogContext<'_>,
+    ) -> Result<()> {
+        if self.store.get_view(bill_id).await?.is_some() && !self.store.exists(bill_id).await {
+            debug!("{log_ctx} Only a read-only view of the bill exists");
+            return Err(Error::Validation(ValidationError::BillIsReadOnlyView));
         }
-
-        // The first key is always the bill key
-        let private_key = self.bitcoin_client.get_combined_private_key(
-            &BcrKeys::from_private_key(&bill_keys.private_key)?
-                .get_bitcoin_private_key(get_config().bitcoin_network()),
-            &caller_keys.get_bitcoin_private_key(get_config().bitcoin_network()),
-        )?;
-        return Ok(BillCombinedBitcoinKey { private_key });
+        Ok(())
     }
 
-    async fn get_detail(
+    /// Runs the validation of the given bill action on the given chain, without changing anything
+    async fn validate_bill_action_on_chain(
         &self,
-        bill_id: &str,
-        identity: &Identity,
+        blockchain: &BillBlockchain,
+        bill_keys: &BillKeys,
+        bill: &BitcreditBill,
+        bill_action: &BillAction,
+        signer_node_id: &str,
+        timestamp: u64,
+    ) -> Result<()> {
+        let is_paid = self.store.is_paid(&bill.id).await?;
+        BillValidateActionData {
+            blockchain: blockchain.clone(),
+            drawee_node_id: bill.drawee.node_id.clone(),
+            payee_node_id: bill.payee.node_id.clone(),
+            endorsee_node_id: bill.endorsee.clone().map(|e| e.node_id),
+            maturity_date: bill.maturity_date.clone(),
+            bill_keys: bill_keys.clone(),
+            timestamp,
+            signer_node_id: signer_node_id.to_owned(),
+            bill_action: bill_action.clone(),
+            is_paid,
+        }
+        .validate()?;
+        Ok(())
+    }
+
+    /// Executes the given bill action - if an acting signatory is given, it's recorded in the
+    /// created blocks instead of the local identity
+    async fn execute_bill_action_with_signatory(
+        &self,
+        bill_id: &str,
+        bill_action: BillAction,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        acting_signatory_node_id: Option<&str>,
+        timestamp: u64,
+        idempotency_key: Option<String>,
+    ) -> Result<BillBlockchain> {
+        let log_ctx = LogContext::bill(bill_id).with_node_id(&signer_public_data.node_id);
+        debug!("{log_ctx} Executing bill action {:?}", &bill_action);
+        self.check_bill_is_not_read_only_view(bill_id, &log_ctx)
+            .await?;
+        let Some(key) = idempotency_key else {
+            return self
+                .execute_bill_action_for_key(
+                    bill_id,
+                    bill_action,
+                    signer_public_data,
+                    signer_keys,
+                    acting_signatory_node_id,
+                    timestamp,
+                    None,
+                )
+                .await;
+        };
+
+        // the key is reserved before executing the action, so concurrent retries don't both
+        // add a block - a retried action, which was already executed, doesn't add another block
+        let op_code = bill_action.op_code();
+        if !self
+            .store
+            .reserve_idempotency_key(bill_id, &op_code, &key)
+            .await?
+        {
+            return match self
+                .store
+                .get_idempotent_block_id(bill_id, &op_code, &key)
+                .await?
+            {
+                Some(block_id) => {
+                    info!(
+                        "{log_ctx} Bill action {:?} with idempotency key {key} was already executed in block {block_id}",
+                        &bill_action
+                    );
+                    Ok(self.blockchain_store.get_chain(bill_id).await?)
+                }
+                None => {
+                    info!(
+                        "{log_ctx} Bill action {:?} with idempotency key {key} is still being executed",
+                        &bill_action
+                    );
+                    Err(Error::InvalidOperation)
+                }
+            };
+        }
+
+        let res = self
+            .execute_bill_action_for_key(
+                bill_id,
+                bill_action,
+                signer_public_data,
+                signer_keys,
+                acting_signatory_node_id,
+                timestamp,
+                Some(&key),
+            )
+            .await;
+        // a failed action can be retried with the same key, unless its blocks were added already
+        if res.is_err()
+            && let Err(e) = self
+                .store
+                .release_idempotency_key(bill_id, &op_code, &key)
+                .await
+        {
+            error!("{log_ctx} Could not release idempotency key {key}: {e}");
+        }
+        res
+    }
+
+    /// Executes the given bill action and records the created block for the given idempotency
+    /// key, which has to be reserved already
+    async fn execute_bill_action_for_key(
+        &self,
+        bill_id: &str,
+        bill_action: BillAction,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        acting_signatory_node_id: Option<&str>,
+        timestamp: u64,
+        idempotency_key: Option<&str>,
+    ) -> Result<BillBlockchain> {
+        let log_ctx = LogContext::bill(bill_id).with_node_id(&signer_public_data.node_id);
+        // fetch data
+        let identity = self.identity_store.get_full().await?;
+        let contacts = self.contact_store.get_map().await?;
+        let acting_signatory = match acting_signatory_node_id {
+            Some(node_id) => Some(
+                self.get_acting_signatory(signer_public_data, node_id, &identity.identity)
+                    .await?,
+            ),
+            None => None,
+        };
+        let mut blockchain = self.blockchain_store.get_chain(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let bill = self
+            .get_last_version_bill(&blockchain, &bill_keys, &identity.identity, &contacts)
+            .await?;
+
+        // validate
+        self.validate_bill_action_on_chain(
+            &blockchain,
+            &bill_keys,
+            &bill,
+            &bill_action,
+            &signer_public_data.node_id,
+            timestamp,
+        )
+        .await?;
+
+        // acknowledging a bill again doesn't add another block
+        if matches!(bill_action, BillAction::Acknowledge)
+            && blockchain
+                .get_acknowledgements(&bill_keys)?
+                .iter()
+                .any(|a| a.node_id == signer_public_data.node_id)
+        {
+            info!("{log_ctx} Bill was already acknowledged");
+            return Ok(blockchain);
+        }
+
+        // create and sign blocks
+        self.create_blocks_for_bill_action(
+            &bill,
+            &mut blockchain,
+            &bill_keys,
+            &bill_action,
+            signer_public_data,
+            signer_keys,
+            &identity,
+            acting_signatory.as_ref(),
+            timestamp,
+        )
+        .await?;
+
+        // the blocks are persisted, so a retry with the same key must not add them again
+        if let Some(key) = idempotency_key {
+            self.store
+                .save_idempotency_key(
+                    bill_id,
+                    &bill_action.op_code(),
+                    key,
+                    blockchain.get_latest_block().id,
+                )
+                .await?;
+        }
+
+        // Calculate bill and persist it to cache
+        self.recalculate_and_persist_bill(
+            bill_id,
+            &blockchain,
+            &bill_keys,
+            &identity.identity,
+            &signer_public_data.node_id,
+            timestamp,
+        )
+        .await?;
+
+        // notify and propagate blocks
+        self.notify_for_block_action(
+            &blockchain,
+            &bill_keys,
+            &bill_action,
+            &identity.identity,
+            &contacts,
+        )
+        .await?;
+
+        debug!("{log_ctx} Executed bill action {:?}", &bill_action);
+
+        Ok(blockchain)
+    }
+
+    /// Returns the block data of the given signatory, if it's a current signatory of the signing
+    /// company - blocks are signed with the keys of the local identity, so it's the only
+    /// signatory, which can be recorded
+    async fn get_acting_signatory(
+        &self,
+        signer_public_data: &IdentityPublicData,
+        acting_signatory_node_id: &str,
+        identity: &Identity,
+    ) -> Result<BillSignatoryBlockData> {
+        let not_a_signatory = || {
+            Error::Validation(ValidationError::NotASignatory(
+                acting_signatory_node_id.to_owned(),
+            ))
+        };
+        if signer_public_data.t != ContactType::Company {
+            return Err(not_a_signatory());
+        }
+        let company = self.company_store.get(&signer_public_data.node_id).await?;
+        if !company
+            .signatories
+            .iter()
+            .any(|s| s == acting_signatory_node_id)
+        {
+            return Err(not_a_signatory());
+        }
+
+        if identity.node_id != acting_signatory_node_id {
+            return Err(Error::Validation(ValidationError::SignatoryCantSign(
+                acting_signatory_node_id.to_owned(),
+            )));
+        }
+        Ok(BillSignatoryBlockData {
+            node_id: acting_signatory_node_id.to_owned(),
+            name: identity.name.clone(),
+        })
+    }
+
+    /// Checks that the bill exists and the given identity is a participant of it and returns the
+    /// chain and the keys of the bill
+    async fn check_caller_is_participant(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<(BillBlockchain, BillKeys)> {
+        if !self.store.exists(bill_id).await {
+            return Err(Error::NotFound);
+        }
+
+        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        Self::check_is_participant_of_chain(&chain, &bill_keys, bill_id, current_identity_node_id)?;
+        Ok((chain, bill_keys))
+    }
+
+    /// Checks that the given identity is a participant of the given chain of the bill
+    fn check_is_participant_of_chain(
+        chain: &BillBlockchain,
+        bill_keys: &BillKeys,
+        bill_id: &str,
+        node_id: &str,
+    ) -> Result<()> {
+        if !chain
+            .get_all_nodes_from_bill(bill_keys)?
+            .iter()
+            .any(|p| p == node_id)
+        {
+            debug!(
+                "{} {node_id} is not a participant of the bill",
+                LogContext::bill(bill_id)
+            );
+            return Err(Error::NotFound);
+        }
+        Ok(())
+    }
+
+    /// Removes the attached files and, if they were saved already, the keys of a bill, whose
+    /// import failed
+    async fn undo_bill_import(&self, bill_id: &str, keys_saved: bool) {
+        if keys_saved && let Err(e) = self.store.remove_keys(bill_id).await {
+            error!(
+                "{} Could not remove keys of failed bill import: {e}",
+                LogContext::bill(bill_id)
+            );
+        }
+        if let Err(e) = self.file_upload_store.delete_attached_files(bill_id).await {
+            error!(
+                "{} Could not remove files of failed bill import: {e}",
+                LogContext::bill(bill_id)
+            );
+        }
+    }
+
+    /// Recalculates the full bill and updates it in the cache
+    pub(super) async fn recalculate_and_persist_bill(
+        &self,
+        bill_id: &str,
+        chain: &BillBlockchain,
+        bill_keys: &BillKeys,
+        local_identity: &Identity,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<()> {
+        let calculated_bill = self
+            .calculate_full_bill(
+                chain,
+                bill_keys,
+                local_identity,
+                current_identity_node_id,
+                current_timestamp,
+            )
+            .await?;
+        self.store
+            .save_bill_to_cache(bill_id, &calculated_bill)
+            .await?;
+        Ok(())
+    }
+
+    /// If it's our identity, we take the fields from there, otherwise we check contacts,
+    /// companies, or leave them empty
+    pub(super) async fn extend_bill_chain_identity_data_from_contacts_or_identity(
+        &self,
+        chain_identity: BillIdentityBlockData,
+        identity: &Identity,
+        contacts: &HashMap<String, Contact>,
+    ) -> IdentityPublicData {
+        let (email, nostr_relay) = match chain_identity.node_id {
+            ref v if *v == identity.node_id => {
+                (Some(identity.email.clone()), identity.nostr_relay.clone())
+            }
+            ref other_node_id => {
+                if let Some(contact) = contacts.get(other_node_id) {
+                    (
+                        Some(contact.email.clone()),
+                        contact.nostr_relays.first().cloned(),
+                    )
+                } else if chain_identity.t == ContactType::Company {
+                    if let Ok(company) = self.company_store.get(other_node_id).await {
+                        (
+                            Some(company.email.clone()),
+                            identity.nostr_relay.clone(), // if it's a local company, we take our relay
+                        )
+                    } else {
+                        (None, None)
+                    }
+                } else {
+                    (None, None)
+                }
+            }
+        };
+        IdentityPublicData {
+            t: chain_identity.t,
+            node_id: chain_identity.node_id,
+            name: chain_identity.name,
+            postal_address: chain_identity.postal_address,
+            email,
+            nostr_relay,
+        }
+    }
+
+    /// The timestamp at which the request of the latest block of the chain times out and the
+    /// action it's waiting for - None, if the latest block isn't a request, which can time out
+    fn get_request_deadline(
+        chain: &BillBlockchain,
+        bill_keys: &BillKeys,
+    ) -> Result<Option<(u64, ActionType)>> {
+        let latest_ts = chain.get_latest_block().timestamp;
+        Ok(match chain.get_latest_block().op_code {
+            BillOpCode::RequestToPay => Some((
+                latest_ts.saturating_add(chain.get_payment_deadline_seconds(bill_keys)?),
+                ActionType::PayBill,
+            )),
+            BillOpCode::OfferToSell => Some((
+                latest_ts.saturating_add(chain.get_sell_deadline_seconds(bill_keys)?),
+                ActionType::PayBill,
+            )),
+            BillOpCode::RequestToAccept => {
+                Some((latest_ts + ACCEPT_DEADLINE_SECONDS, ActionType::AcceptBill))
+            }
+            BillOpCode::RequestRecourse => Some((
+                latest_ts + RECOURSE_DEADLINE_SECONDS,
+                ActionType::RecourseBill,
+            )),
+            _ => None,
+        })
+    }
+
+    async fn check_bill_timeouts(&self, bill_id: &str, now: u64) -> Result<()> {
+        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let contacts = self.contact_store.get_map().await?;
+
+        let (deadline, action) = match Self::get_request_deadline(&chain, &bill_keys)? {
+            Some(request_deadline) => request_deadline,
+            None => return Ok(()),
+        };
+
+        if deadline > now {
+            let warning_seconds = get_config().request_timeout_warning_seconds;
+            if warning_seconds > 0 && deadline <= now + warning_seconds {
+                self.check_bill_timeout_warning(bill_id, &chain, &bill_keys, action, now)
+                    .await?;
+            }
+        } else {
+            // did we already send the notification
+            let sent = self
+                .notification_service
+                .check_bill_notification_sent(
+                    bill_id,
+                    chain.block_height() as i32,
+                    action.to_owned(),
+                )
+                .await?;
+
+            if !sent {
+                let identity = self.identity_store.get().await?;
+                let current_identity = IdentityPublicData::new(identity.clone());
+                let participants = chain.get_all_nodes_from_bill(&bill_keys)?;
+                let mut recipient_options = vec![current_identity];
+                let bill = self
+                    .get_last_version_bill(&chain, &bill_keys, &identity, &contacts)
+                    .await?;
+
+                for node_id in participants {
+                    let contact: Option<IdentityPublicData> =
+                        self.contact_store.get(&node_id).await?.map(|c| c.into());
+                    recipient_options.push(contact);
+                }
+
+                let recipients = recipient_options
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<IdentityPublicData>>();
+
+                self.notification_service
+                    .send_request_to_action_timed_out_event(
+                        &identity.node_id,
+                        bill_id,
+                        Some(bill.sum),
+                        action.to_owned(),
+                        recipients,
+                    )
+                    .await?;
+
+                // remember we have sent the notification
+                self.notification_service
+                    .mark_bill_notification_sent(bill_id, chain.block_height() as i32, action)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Warns the expected actor of the open request, if it's the current identity, that the
+    /// request is about to time out
+    async fn check_bill_timeout_warning(
+        &self,
+        bill_id: &str,
+        chain: &BillBlockchain,
+        bill_keys: &BillKeys,
+        action: ActionType,
+        now: u64,
+    ) -> Result<()> {
+        // the warning is remembered with the negated block height, so it doesn't suppress the
+        // timeout notification for the same block
+        let block_height = -(chain.block_height() as i32);
+        let sent = self
+            .notification_service
+            .check_bill_notification_sent(bill_id, block_height, action.to_owned())
+            .await?;
+        if sent {
+            return Ok(());
+        }
+
+        let (actor_node_id, sum) = match chain.get_latest_block().op_code {
+            BillOpCode::RequestToAccept | BillOpCode::RequestToPay => {
+                if self.store.is_paid(bill_id).await? {
+                    return Ok(());
+                }
+                let bill_first_version = chain.get_first_version_bill(bill_keys)?;
+                let bill_parties = chain.get_bill_parties(bill_keys, &bill_first_version)?;
+                (bill_parties.drawee.node_id, bill_first_version.sum)
+            }
+            BillOpCode::OfferToSell => {
+                match chain.is_last_offer_to_sell_block_waiting_for_payment(bill_keys, now)? {
+                    OfferToSellWaitingForPayment::Yes(payment_info) => {
+                        (payment_info.buyer.node_id, payment_info.sum)
+                    }
+                    OfferToSellWaitingForPayment::No => return Ok(()),
+                }
+            }
+            BillOpCode::RequestRecourse => {
+                match chain.is_last_request_to_recourse_block_waiting_for_payment(bill_keys, now)? {
+                    RecourseWaitingForPayment::Yes(payment_info) => {
+                        (payment_info.recoursee.node_id, payment_info.sum)
+                    }
+                    RecourseWaitingForPayment::No => return Ok(()),
+                }
+            }
+            _ => return Ok(()),
+        };
+
+        // only the expected actor is warned
+        let identity = self.identity_store.get().await?;
+        if actor_node_id != identity.node_id {
+            return Ok(());
+        }
+
+        if let Some(recipient) = IdentityPublicData::new(identity.clone()) {
+            self.notification_service
+                .send_request_to_action_timeout_approaching_event(
+                    &identity.node_id,
+                    bill_id,
+                    Some(sum),
+                    action.to_owned(),
+                    &recipient,
+                )
+                .await?;
+
+            // remember we have sent the notification
+            self.notification_service
+                .mark_bill_notification_sent(bill_id, block_height, action)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn check_bill_maturity_reminder(
+        &self,
+        bill_id: &str,
+        now: u64,
+        lead_time_seconds: u64,
+        identity: &Identity,
+        local_node_ids: &HashSet<String>,
+    ) -> Result<()> {
+        if self.store.is_paid(bill_id).await? {
+            return Ok(());
+        }
+        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        // payment was already requested, so there is nothing to prepare for anymore
+        if chain.block_with_operation_code_exists(BillOpCode::RequestToPay) {
+            return Ok(());
+        }
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let bill_first_version = chain.get_first_version_bill(&bill_keys)?;
+        let maturity_ts =
+            util::date::date_string_to_timestamp(&bill_first_version.maturity_date, None)?;
+        if maturity_ts <= now || maturity_ts > now + lead_time_seconds {
+            return Ok(());
+        }
+
+        // only the holder sends the reminder, so not every participant reminds them
+        let bill_parties = chain.get_bill_parties(&bill_keys, &bill_first_version)?;
+        let holder = bill_parties.endorsee.unwrap_or(bill_parties.payee);
+        if !local_node_ids.contains(&holder.node_id) {
+            return Ok(());
+        }
+
+        // the maturity date never changes, so we remember the reminder for the first block, to
+        // not send it again when blocks are added
+        let block_height = chain.get_first_block().id as i32;
+        let sent = self
+            .notification_service
+            .check_bill_notification_sent(bill_id, block_height, ActionType::RequestPayment)
+            .await?;
+
+        if !sent {
+            let contacts = self.contact_store.get_map().await?;
+            let recipient = self
+                .extend_bill_chain_identity_data_from_contacts_or_identity(
+                    holder, identity, &contacts,
+                )
+                .await;
+            self.notification_service
+                .send_maturity_approaching_event(
+                    &identity.node_id,
+                    bill_id,
+                    Some(bill_first_version.sum),
+                    &recipient,
+                )
+                .await?;
+
+            // remember we have sent the notification
+            self.notification_service
+                .mark_bill_notification_sent(bill_id, block_height, ActionType::RequestPayment)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Checks the auto-accept rules of the drawee for the given queued bill again and accepts the
+    /// bill as the drawee, if it still matches one of them
+    async fn process_auto_accept(
+        &self,
+        queued: &QueuedAutoAccept,
+        identity: &IdentityWithAll,
+        now: u64,
+    ) -> Result<()> {
+        let log_ctx = LogContext::bill(&queued.bill_id).with_node_id(&queued.node_id);
+        let bill_keys = self.store.get_keys(&queued.bill_id).await?;
+        let chain = self.blockchain_store.get_chain(&queued.bill_id).await?;
+        let bill = chain.get_first_version_bill(&bill_keys)?;
+        // the drawee might have been corrected since the bill was issued
+        let bill_parties = chain.get_bill_parties(&bill_keys, &bill)?;
+        let rules = self.auto_accept_store.get_rules(&queued.node_id).await?;
+        let rule = match rules.iter().find(|rule| {
+            bill_parties.drawee.node_id == queued.node_id
+                && rule.matches(&bill_parties.drawer.node_id, bill.sum)
+        }) {
+            Some(rule) => rule,
+            None => {
+                info!(
+                    "{log_ctx} Bill doesn't match an auto-accept rule anymore - not accepting it"
+                );
+                return Ok(());
+            }
+        };
+
+        let (signer_public_data, signer_keys) = if queued.node_id == identity.identity.node_id {
+            let signer_public_data = IdentityPublicData::new(identity.identity.clone())
+                .ok_or(Error::InvalidOperation)?;
+            (signer_public_data, identity.key_pair.clone())
+        } else {
+            let company = self.company_store.get(&queued.node_id).await?;
+            if !company.signatories.contains(&identity.identity.node_id) {
+                return Err(Error::InvalidOperation);
+            }
+            let company_keys = self.company_store.get_key_pair(&queued.node_id).await?;
+            (
+                IdentityPublicData::from(company),
+                BcrKeys::from_private_key(&company_keys.private_key)?,
+            )
+        };
+
+        // the accept action is validated like any other action of the drawee
+        self.execute_bill_action(
+            &queued.bill_id,
+            BillAction::Accept,
+            &signer_public_data,
+            &signer_keys,
+            now,
+            None,
+        )
+        .await?;
+        info!(
+            "{log_ctx} Bill from drawer {} with sum {} was accepted automatically by auto-accept rule {}",
+            bill.drawer.node_id, bill.sum, rule.id
+        );
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl BillServiceApi for BillService {
+    async fn get_bill_balances(
+        &self,
+        _currency: &str,
+        current_identity_node_id: &str,
+    ) -> Result<BillsBalanceOverview> {
+        let bills = self.get_bills(current_identity_node_id, false).await?;
+        let current_timestamp = util::date::now().timestamp() as u64;
+        Ok(Self::summarize_bills(&bills, current_identity_node_id, current_timestamp).balances)
+    }
+
+    async fn get_company_bill_balances(
+        &self,
+        company_id: &str,
+        caller_node_id: &str,
+    ) -> Result<BillsBalanceOverview> {
+        if !self.company_store.exists(company_id).await {
+            return Err(Error::NotFound);
+        }
+        let company = self.company_store.get(company_id).await?;
+        if !company.signatories.iter().any(|s| s == caller_node_id) {
+            return Err(Error::Validation(ValidationError::CallerMustBeSignatory));
+        }
+        let bills = self.get_bills(company_id, false).await?;
+        let current_timestamp = util::date::now().timestamp() as u64;
+        Ok(Self::summarize_bills(&bills, company_id, current_timestamp).balances)
+    }
+
+    async fn get_total_exposure(&self, current_identity_node_id: &str) -> Result<ExposureSummary> {
+        let bills = self.get_bills(current_identity_node_id, false).await?;
+        let current_timestamp = util::date::now().timestamp() as u64;
+        Ok(Self::summarize_bills(
+            &bills,
+            current_identity_node_id,
+            current_timestamp,
+        ))
+    }
+
+    async fn get_bill_balances_with_fiat(
+        &self,
+        currency: &str,
+        fiat_code: &str,
+        current_identity_node_id: &str,
+    ) -> Result<BillsBalanceOverviewWithFiat> {
+        let balances = self
+            .get_bill_balances(currency, current_identity_node_id)
+            .await?;
+
+        // if the rate can't be fetched, we still return the sat balances
+        let fiat_rate = match self.fiat_rate_client.get_btc_rate(fiat_code).await {
+            Ok(rate) => Some(rate),
+            Err(e) => {
+                error!("Could not fetch btc rate for {fiat_code}: {e}");
+                None
+            }
+        };
+
+        let with_fiat = |balance: BillsBalance| -> BillsBalanceWithFiat {
+            let fiat_sum = fiat_rate.as_ref().and_then(|rate| {
+                currency::parse_sum(&balance.sum)
+                    .ok()
+                    .map(|sum| currency::sat_to_fiat(sum, rate.rate))
+            });
+            BillsBalanceWithFiat {
+                sum: balance.sum,
+                fiat_sum,
+            }
+        };
+
+        Ok(BillsBalanceOverviewWithFiat {
+            payee: with_fiat(balances.payee),
+            payer: with_fiat(balances.payer),
+            contingent: with_fiat(balances.contingent),
+            fiat_rate,
+        })
+    }
+
+    async fn search_bills(
+        &self,
+        _currency: &str,
+        search_term: &Option<String>,
+        date_range_from: Option<u64>,
+        date_range_to: Option<u64>,
+        role: &BillsFilterRole,
+        status: &BillsFilterStatus,
+        current_identity_node_id: &str,
+    ) -> Result<Vec<LightBitcreditBillResult>> {
+        debug!(
+            "searching bills with {search_term:?} from {date_range_from:?} to {date_range_to:?} and {role:?} and {status:?}"
+        );
+        let bills = self.get_bills(current_identity_node_id, false).await?;
+        let now = util::date::now().timestamp() as u64;
+        let mut result = vec![];
+
+        // for now we do the search here - with the quick-fetch table, we can search in surrealDB
+        // directly
+        for bill in bills {
+            // if the bill wasn't issued between from and to, we kick them out
+            if let Ok(issue_date_ts) =
+                util::date::date_string_to_timestamp(&bill.data.issue_date, None)
+            {
+                if let Some(from) = date_range_from
+                    && from > issue_date_ts
+                {
+                    continue;
+                }
+                if let Some(to) = date_range_to
+                    && to < issue_date_ts
+                {
+                    continue;
+                }
+            }
+
+            let bill_role = match bill.get_bill_role_for_node_id(current_identity_node_id) {
+                Some(bill_role) => bill_role,
+                None => continue, // node is not in bill - don't add
+            };
+
+            match role {
+                BillsFilterRole::All => (), // we take all
+                BillsFilterRole::Payer => {
+                    if bill_role != BillRole::Payer {
+                        // payer selected, but node not payer
+                        continue;
+                    }
+                }
+                BillsFilterRole::Payee => {
+                    if bill_role != BillRole::Payee {
+                        // payee selected, but node not payee
+                        continue;
+                    }
+                }
+                BillsFilterRole::Contingent => {
+                    if bill_role != BillRole::Contingent {
+                        // contingent selected, but node not
+                        // contingent
+                        continue;
+                    }
+                }
+            };
+
+            if !bill.matches_status(status, current_identity_node_id, now) {
+                continue;
+            }
+
+            result.push(bill);
+        }
+
+        if let Some(st) = search_term {
+            // exact matches first - only if there are none, we fall back to fuzzy matching
+            let (exact_matches, other): (Vec<_>, Vec<_>) = result
+                .into_iter()
+                .partition(|bill| bill.search_bill_for_search_term(st));
+            result = if exact_matches.is_empty() {
+                other
+                    .into_iter()
+                    .filter(|bill| bill.fuzzy_search_bill_for_search_term(st))
+                    .collect()
+            } else {
+                exact_matches
+            };
+        }
+
+        Ok(result.into_iter().map(|bill| bill.into()).collect())
+    }
+
+    async fn get_bills(
+        &self,
+        current_identity_node_id: &str,
+        include_archived: bool,
+    ) -> Result<Vec<BitcreditBillResult>> {
+        let mut bill_ids = self.store.get_ids().await?;
+        if !include_archived {
+            let archived_bill_ids = self
+                .store
+                .get_archived_bill_ids(current_identity_node_id)
+                .await?;
+            bill_ids.retain(|id| !archived_bill_ids.contains(id));
+        }
+        self.get_bills_by_ids(bill_ids, current_identity_node_id)
+            .await
+    }
+
+    async fn get_bills_by_participant(
+        &self,
+        participant_node_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<Vec<LightBitcreditBillResult>> {
+        let mut bill_ids = self
+            .store
+            .get_bill_ids_by_participant(participant_node_id)
+            .await?;
+        // bills, which were never cached aren't indexed yet, so we have to calculate them
+        for bill_id in self.store.get_unindexed_bill_ids().await? {
+            if !bill_ids.contains(&bill_id) {
+                bill_ids.push(bill_id);
+            }
+        }
+        let bills = self
+            .get_bills_by_ids(bill_ids, current_identity_node_id)
+            .await?;
+        Ok(bills
+            .into_iter()
+            .filter(|b| Self::is_participant(b, participant_node_id))
+            .map(|b| b.into())
+            .collect())
+    }
+
+    async fn get_bills_by_maturity(
+        &self,
+        current_identity_node_id: &str,
+        maturity_from: Option<u64>,
+        maturity_to: Option<u64>,
+    ) -> Result<Vec<LightBitcreditBillResult>> {
+        let bills = self.get_bills(current_identity_node_id, false).await?;
+        Ok(bills
+            .into_iter()
+            .filter(|b| {
+                match util::date::date_string_to_timestamp(&b.data.maturity_date, None) {
+                    Ok(maturity_ts) => {
+                        maturity_from.is_none_or(|from| from <= maturity_ts)
+                            && maturity_to.is_none_or(|to| maturity_ts <= to)
+                    }
+                    // bills with a malformed maturity date can't be in any range
+                    Err(_) => false,
+                }
+            })
+            .map(|b| b.into())
+            .collect())
+    }
+
+    async fn get_bills_awaiting_others(
+        &self,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Vec<LightBitcreditBillResult>> {
+        let bills = self.get_bills(current_identity_node_id, false).await?;
+        Ok(bills
+            .into_iter()
+            .filter(|b| b.is_awaiting_response_to(current_identity_node_id, current_timestamp))
+            .map(|b| b.into())
+            .collect())
+    }
+
+    async fn get_bills_grouped(
+        &self,
+        node_ids: &[String],
+    ) -> Result<HashMap<String, Vec<LightBitcreditBillResult>>> {
+        let mut grouped: HashMap<String, Vec<LightBitcreditBillResult>> = HashMap::new();
+        let Some(first_node_id) = node_ids.first() else {
+            return Ok(grouped);
+        };
+        // the light bills don't depend on the caller, so every bill is only loaded once
+        let bill_ids = self.store.get_ids().await?;
+        let bills = self.load_bills_by_ids(bill_ids, first_node_id).await?;
+        for node_id in node_ids {
+            let archived_bill_ids = self.store.get_archived_bill_ids(node_id).await?;
+            let node_bills = bills
+                .iter()
+                .filter(|b| !archived_bill_ids.contains(&b.id) && Self::is_participant(b, node_id))
+                .map(|b| b.clone().into())
+                .collect();
+            grouped.insert(node_id.to_owned(), node_bills);
+        }
+        Ok(grouped)
+    }
+
+    async fn get_combined_bitcoin_key_for_bill(
+        &self,
+        bill_id: &str,
+        caller_public_data: &IdentityPublicData,
+        caller_keys: &BcrKeys,
+    ) -> Result<BillCombinedBitcoinKey> {
+        let (_, bill_keys) = self
+            .check_caller_is_participant(bill_id, &caller_public_data.node_id)
+            .await?;
+
+        // The first key is always the bill key
+        let private_key = self.bitcoin_client.get_combined_private_key(
+            &BcrKeys::from_private_key(&bill_keys.private_key)?
+                .get_bitcoin_private_key(get_config().bitcoin_network()),
+            &caller_keys.get_bitcoin_private_key(get_config().bitcoin_network()),
+        )?;
+        return Ok(BillCombinedBitcoinKey { private_key });
+    }
+
+    async fn get_payment_address_at_block(
+        &self,
+        bill_id: &str,
+        block_height: u64,
+        caller_keys: &BcrKeys,
+    ) -> Result<String> {
+        let caller_node_id = caller_keys.get_public_key();
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, &caller_node_id)
+            .await?;
+
+        let block = match chain.blocks().iter().find(|b| b.id == block_height) {
+            Some(block) => block,
+            None => return Err(Error::NotFound),
+        };
+
+        // the address is derived from the party, who is paid at this block
+        let payee_node_id = match block.op_code {
+            BillOpCode::OfferToSell => {
+                let block_data: BillOfferToSellBlockData =
+                    block.get_decrypted_block_bytes(&bill_keys)?;
+                block_data.seller.node_id
+            }
+            BillOpCode::Sell => {
+                let block_data: BillSellBlockData = block.get_decrypted_block_bytes(&bill_keys)?;
+                block_data.seller.node_id
+            }
+            BillOpCode::RequestToPay => {
+                let block_data: BillRequestToPayBlockData =
+                    block.get_decrypted_block_bytes(&bill_keys)?;
+                block_data.requester.node_id
+            }
+            BillOpCode::RequestRecourse => {
+                let block_data: BillRequestRecourseBlockData =
+                    block.get_decrypted_block_bytes(&bill_keys)?;
+                block_data.recourser.node_id
+            }
+            BillOpCode::Recourse => {
+                let block_data: BillRecourseBlockData =
+                    block.get_decrypted_block_bytes(&bill_keys)?;
+                block_data.recourser.node_id
+            }
+            _ => {
+                // the holder at this block is the one of the chain up to this block
+                let chain_at_block = BillBlockchain::new_from_blocks(
+                    chain
+                        .blocks()
+                        .iter()
+                        .take_while(|b| b.id <= block_height)
+                        .cloned()
+                        .collect(),
+                )?;
+                let bill_first_version = chain_at_block.get_first_version_bill(&bill_keys)?;
+                let bill_parties =
+                    chain_at_block.get_bill_parties(&bill_keys, &bill_first_version)?;
+                bill_parties.endorsee.unwrap_or(bill_parties.payee).node_id
+            }
+        };
+
+        let address = self
+            .bitcoin_client
+            .get_address_to_pay(&bill_keys.public_key, &payee_node_id)?;
+        Ok(address)
+    }
+
+    async fn get_bill_payment_transactions(
+        &self,
+        bill_id: &str,
+        caller_keys: &BcrKeys,
+    ) -> Result<Vec<PaymentTx>> {
+        let caller_node_id = caller_keys.get_public_key();
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, &caller_node_id)
+            .await?;
+
+        // the same address the payment check uses - the one of the current holder
+        let bill_first_version = chain.get_first_version_bill(&bill_keys)?;
+        let bill_parties = chain.get_bill_parties(&bill_keys, &bill_first_version)?;
+        let holder = bill_parties.endorsee.unwrap_or(bill_parties.payee);
+        let address = self
+            .bitcoin_client
+            .get_address_to_pay(&bill_keys.public_key, &holder.node_id)?;
+        let transactions = self
+            .bitcoin_client
+            .get_payment_transactions(&address)
+            .await?;
+        Ok(transactions)
+    }
+
+    async fn get_detail(
+        &self,
+        bill_id: &str,
+        identity: &Identity,
         current_identity_node_id: &str,
         current_timestamp: u64,
     ) -> Result<BitcreditBillResult> {
-        let res = self
+        let mut res = self
             .get_full_bill(
                 bill_id,
                 identity,
@@ -448,23 +1418,230 @@ impl BillServiceApi for BillService {
             )
             .await?;
         // if currently active identity is not part of the bill, we can't access it
-        if !res
-            .participants
-            .all_participant_node_ids
-            .iter()
-            .any(|p| p == current_identity_node_id)
+        if !Self::is_participant(&res, current_identity_node_id) {
+            return Err(Error::NotFound);
+        }
+        // the note is local to the current identity, so it's never cached with the bill
+        res.data.note = self
+            .store
+            .get_note(bill_id, current_identity_node_id)
+            .await?;
+        Ok(res)
+    }
+
+    async fn get_detail_up_to_corrupted_block(
+        &self,
+        bill_id: &str,
+        identity: &Identity,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<BitcreditBillResult> {
+        if !self.store.exists(bill_id).await {
+            return Err(Error::NotFound);
+        }
+        let blocks = self.blockchain_store.get_blocks(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let (chain, report) = BillBlockchain::new_from_valid_prefix(blocks, &bill_keys)?;
+        if !report.is_valid() {
+            warn!(
+                "{} Showing bill up to corrupted block at height {:?}",
+                LogContext::bill(bill_id).with_node_id(current_identity_node_id),
+                report.first_invalid_block_height
+            );
+        }
+        // if currently active identity is not part of the bill, we can't access it
+        Self::check_is_participant_of_chain(&chain, &bill_keys, bill_id, current_identity_node_id)?;
+        self.calculate_full_bill(
+            &chain,
+            &bill_keys,
+            identity,
+            current_identity_node_id,
+            current_timestamp,
+        )
+        .await
+    }
+
+    async fn diff_bill_cache(
+        &self,
+        bill_id: &str,
+        local_identity: &Identity,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Vec<FieldDiff>> {
+        // if there is no such bill, we return an error
+        if !self.store.exists(bill_id).await {
+            return Err(Error::NotFound);
+        }
+
+        let mut cached_bill = match self.store.get_bill_from_cache(bill_id).await? {
+            Some(bill) => bill,
+            None => return Ok(vec![]),
+        };
+        // participants are compared with current contact data, same as for the calculated bill
+        let contacts = self.contact_store.get_map().await?;
+        self.extend_bill_identities_from_contacts_or_identity(
+            &mut cached_bill,
+            local_identity,
+            &contacts,
+        )
+        .await;
+
+        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let calculated_bill = self
+            .calculate_full_bill(
+                &chain,
+                &bill_keys,
+                local_identity,
+                current_identity_node_id,
+                current_timestamp,
+            )
+            .await?;
+        Ok(cached_bill.diff_status_and_participants(&calculated_bill))
+    }
+
+    async fn get_bill_waiting_state(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Option<BillCurrentWaitingState>> {
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        // the local payment state is only relevant while there is a request to pay
+        let paid = chain.get_latest_block_for_waiting_state().op_code == BillOpCode::RequestToPay
+            && self.store.is_paid(bill_id).await?;
+
+        waiting_state_from_chain(
+            &chain,
+            &bill_keys,
+            current_timestamp,
+            paid,
+            self.bitcoin_client.as_ref(),
+        )
+    }
+
+    async fn get_suggested_action(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        current_timestamp: u64,
+    ) -> Result<Option<SuggestedAction>> {
+        let identity = self.identity_store.get().await?;
+        let bill = self
+            .get_detail(
+                bill_id,
+                &identity,
+                current_identity_node_id,
+                current_timestamp,
+            )
+            .await?;
+        Ok(bill.get_suggested_action(current_identity_node_id, current_timestamp))
+    }
+
+    async fn get_next_timeout(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        now: u64,
+    ) -> Result<Option<u64>> {
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        let deadline = match Self::get_request_deadline(&chain, &bill_keys)? {
+            Some((deadline, _)) => deadline,
+            None => return Ok(None),
+        };
+        // an expired request isn't outstanding anymore
+        if deadline <= now {
+            return Ok(None);
+        }
+        // a paid request to pay doesn't time out anymore
+        if chain.get_latest_block().op_code == BillOpCode::RequestToPay
+            && self.store.is_paid(bill_id).await?
         {
+            return Ok(None);
+        }
+        Ok(Some(deadline))
+    }
+
+    async fn get_bill_keys(&self, bill_id: &str) -> Result<BillKeys> {
+        if !self.store.exists(bill_id).await {
             return Err(Error::NotFound);
         }
-        Ok(res)
+        let keys = self.store.get_keys(bill_id).await?;
+        Ok(keys)
+    }
+
+    async fn verify_bill_chain(&self, bill_id: &str) -> Result<ChainVerificationReport> {
+        if !self.store.exists(bill_id).await {
+            return Err(Error::NotFound);
+        }
+        // load the raw blocks, since loading the chain already fails for invalid chains
+        let blocks = self.blockchain_store.get_blocks(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let report = BillBlockchain::verify_blocks(&blocks, &bill_keys);
+        if !report.is_valid() {
+            info!(
+                "Chain of bill {bill_id} is invalid at block height {:?}: {:?}",
+                report.first_invalid_block_height, report.reason
+            );
+        }
+        Ok(report)
     }
 
-    async fn get_bill_keys(&self, bill_id: &str) -> Result<BillKeys> {
+    async fn repair_bill_chain(&self, bill_id: &str) -> Result<ChainVerificationReport> {
         if !self.store.exists(bill_id).await {
             return Err(Error::NotFound);
         }
-        let keys = self.store.get_keys(bill_id).await?;
-        Ok(keys)
+        let blocks = self.blockchain_store.get_blocks(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let report = BillBlockchain::verify_blocks(&blocks, &bill_keys);
+        let corrupted_height = match report.first_invalid_block_height {
+            Some(height) => height,
+            None => return Ok(report),
+        };
+
+        // keep the valid prefix and take the blocks from the corrupted block on from the relays
+        let fetched_blocks = self.notification_service.fetch_bill_blocks(bill_id).await?;
+        let replacement_blocks: Vec<BillBlock> = fetched_blocks
+            .into_iter()
+            .filter(|b| b.id >= corrupted_height)
+            .collect();
+        let mut repaired_blocks: Vec<BillBlock> = blocks
+            .into_iter()
+            .take(corrupted_height as usize - 1)
+            .collect();
+        repaired_blocks.extend(replacement_blocks.iter().cloned());
+
+        let repaired_report = BillBlockchain::verify_blocks(&repaired_blocks, &bill_keys);
+        if repaired_report.valid_blocks < corrupted_height {
+            warn!(
+                "{} Could not repair corrupted block at height {corrupted_height} from relays",
+                LogContext::bill(bill_id)
+            );
+            return Ok(report);
+        }
+
+        // only persist the replacement blocks that are valid - atomically, so a failure doesn't
+        // leave a truncated chain behind
+        let valid_replacements = repaired_report.valid_blocks - (corrupted_height - 1);
+        let valid_replacement_blocks: Vec<BillBlock> = replacement_blocks
+            .into_iter()
+            .take(valid_replacements as usize)
+            .collect();
+        self.blockchain_store
+            .replace_blocks_from(bill_id, &valid_replacement_blocks)
+            .await?;
+        self.store.invalidate_bill_in_cache(bill_id).await?;
+        info!(
+            "{} Repaired bill chain from block at height {corrupted_height}",
+            LogContext::bill(bill_id)
+        );
+        Ok(repaired_report)
     }
 
     async fn open_and_decrypt_attached_file(
@@ -494,6 +1671,7 @@ impl BillServiceApi for BillService {
         self.file_upload_store
             .save_attached_file(&encrypted, bill_id, file_name)
             .await?;
+        self.remember_file_hash(bill_id, file_name, Some(file_hash.clone()));
         info!("Saved file {file_name} with hash {file_hash} for bill {bill_id}");
         Ok(File {
             name: file_name.to_owned(),
@@ -505,6 +1683,80 @@ impl BillServiceApi for BillService {
         self.issue_bill(data).await
     }
 
+    async fn save_bill_template(&self, mut template: BillTemplate) -> Result<String> {
+        if template.id.is_empty() {
+            template.id = util::get_uuid_v4().to_string();
+        }
+        self.template_store.save(&template).await?;
+        Ok(template.id)
+    }
+
+    async fn list_bill_templates(&self) -> Result<Vec<BillTemplate>> {
+        Ok(self.template_store.get_all().await?)
+    }
+
+    async fn delete_bill_template(&self, template_id: &str) -> Result<()> {
+        self.template_store.delete(template_id).await?;
+        Ok(())
+    }
+
+    async fn add_auto_accept_rule(
+        &self,
+        node_id: &str,
+        drawer_node_id: &str,
+        max_sum: u64,
+    ) -> Result<BillAutoAcceptRule> {
+        if util::crypto::validate_pub_key(drawer_node_id).is_err() {
+            return Err(Error::Validation(ValidationError::InvalidSecp256k1Key(
+                drawer_node_id.to_owned(),
+            )));
+        }
+        if max_sum == 0 {
+            return Err(Error::Validation(ValidationError::InvalidSum));
+        }
+        let rule = BillAutoAcceptRule {
+            id: util::get_uuid_v4().to_string(),
+            node_id: node_id.to_owned(),
+            drawer_node_id: drawer_node_id.to_owned(),
+            max_sum,
+        };
+        self.auto_accept_store.save_rule(&rule).await?;
+        info!(
+            "Added auto-accept rule {} of {node_id} for bills from drawer {drawer_node_id} up to sum {max_sum}",
+            rule.id
+        );
+        Ok(rule)
+    }
+
+    async fn get_auto_accept_rules(&self, node_id: &str) -> Result<Vec<BillAutoAcceptRule>> {
+        Ok(self.auto_accept_store.get_rules(node_id).await?)
+    }
+
+    async fn remove_auto_accept_rule(&self, node_id: &str, rule_id: &str) -> Result<()> {
+        let rules = self.auto_accept_store.get_rules(node_id).await?;
+        if !rules.iter().any(|rule| rule.id == rule_id) {
+            return Err(Error::NotFound);
+        }
+        self.auto_accept_store.delete_rule(rule_id).await?;
+        info!("Removed auto-accept rule {rule_id} of {node_id}");
+        Ok(())
+    }
+
+    async fn issue_from_template(
+        &self,
+        template_id: &str,
+        overrides: BillTemplateOverrides,
+        drawer_keys: BcrKeys,
+        timestamp: u64,
+    ) -> Result<BitcreditBill> {
+        let template = match self.template_store.get(template_id).await? {
+            Some(template) => template,
+            None => return Err(Error::NotFound),
+        };
+        self.issue_bill(template.to_issue_data(overrides, drawer_keys, timestamp))
+            .await
+    }
+
     async fn execute_bill_action(
         &self,
         bill_id: &str,
@@ -512,82 +1764,264 @@ impl BillServiceApi for BillService {
         signer_public_data: &IdentityPublicData,
         signer_keys: &BcrKeys,
         timestamp: u64,
+        idempotency_key: Option<String>,
     ) -> Result<BillBlockchain> {
-        debug!(
-            "Executing bill action {:?} for bill {bill_id}",
-            &bill_action
-        );
+        self.execute_bill_action_with_signatory(
+            bill_id,
+            bill_action,
+            signer_public_data,
+            signer_keys,
+            None,
+            timestamp,
+            idempotency_key,
+        )
+        .await
+    }
+
+    async fn execute_bill_action_as_signatory(
+        &self,
+        bill_id: &str,
+        bill_action: BillAction,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        acting_signatory_node_id: &str,
+        timestamp: u64,
+        idempotency_key: Option<String>,
+    ) -> Result<BillBlockchain> {
+        self.execute_bill_action_with_signatory(
+            bill_id,
+            bill_action,
+            signer_public_data,
+            signer_keys,
+            Some(acting_signatory_node_id),
+            timestamp,
+            idempotency_key,
+        )
+        .await
+    }
+
+    async fn attach_files(
+        &self,
+        bill_id: &str,
+        file_upload_ids: &[String],
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<BillBlockchain> {
+        let log_ctx = LogContext::bill(bill_id).with_node_id(&signer_public_data.node_id);
+        debug!("{log_ctx} attaching files to bill");
+        if file_upload_ids.is_empty() {
+            return Err(ValidationError::FieldEmpty(Field::Files).into());
+        }
+        for file_upload_id in file_upload_ids.iter() {
+            util::validate_file_upload_id(Some(file_upload_id))?;
+        }
+
+        // validate before saving any files, the files themselves aren't relevant for validation
+        self.validate_bill_action(
+            bill_id,
+            BillAction::AttachFile(vec![]),
+            signer_public_data,
+            timestamp,
+        )
+        .await?;
+
+        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let existing_files: Vec<File> = chain
+            .get_first_version_bill(&bill_keys)?
+            .files
+            .into_iter()
+            .chain(chain.get_attached_files(&bill_keys)?)
+            .collect();
+        let mut file_names: HashSet<String> =
+            existing_files.iter().map(|f| f.name.clone()).collect();
+
+        let mut uploads: Vec<(String, Vec<u8>)> = vec![];
+        for file_upload_id in file_upload_ids.iter() {
+            let (file_name, file_bytes) = self
+                .file_upload_store
+                .read_temp_upload_file(file_upload_id)
+                .await
+                .map_err(|_| Error::NoFileForFileUploadId)?;
+            // we don't overwrite already attached files
+            if !file_names.insert(file_name.to_owned()) {
+                return Err(ValidationError::FileAlreadyAttached(file_name).into());
+            }
+            uploads.push((file_name, file_bytes));
+        }
+
+        // the size of already attached files is taken from the stored, encrypted files, which
+        // are slightly bigger - files, which aren't available locally are skipped
+        let mut total_bytes: usize = uploads.iter().map(|(_, bytes)| bytes.len()).sum();
+        for file in existing_files.iter() {
+            if let Ok(encrypted) = self
+                .file_upload_store
+                .open_attached_file(bill_id, &file.name)
+                .await
+            {
+                total_bytes += encrypted.len();
+            }
+        }
+        validate_bill_attachments(
+            file_names.len(),
+            total_bytes,
+            get_config().max_files_per_bill,
+            get_config().max_total_attachment_bytes,
+        )?;
+
+        // the files are only saved once the block was added, so a failed action doesn't leave
+        // files behind, which aren't attached to the bill
+        let files: Vec<File> = uploads
+            .iter()
+            .map(|(file_name, file_bytes)| File {
+                name: file_name.to_owned(),
+                hash: util::sha256_hash(file_bytes),
+            })
+            .collect();
+
+        let chain = self
+            .execute_bill_action(
+                bill_id,
+                BillAction::AttachFile(files),
+                signer_public_data,
+                signer_keys,
+                timestamp,
+                None,
+            )
+            .await?;
+
+        for (file_name, file_bytes) in uploads.iter() {
+            self.encrypt_and_save_uploaded_file(
+                file_name,
+                file_bytes,
+                bill_id,
+                &bill_keys.public_key,
+            )
+            .await?;
+        }
+
+        // clean up temporary file uploads, logging any errors
+        for file_upload_id in file_upload_ids.iter() {
+            if let Err(e) = self
+                .file_upload_store
+                .remove_temp_upload_folder(file_upload_id)
+                .await
+            {
+                error!(
+                    "{log_ctx} Error while cleaning up temporary file uploads for {}: {e}",
+                    &file_upload_id
+                );
+            }
+        }
+
+        Ok(chain)
+    }
+
+    async fn validate_bill_action(
+        &self,
+        bill_id: &str,
+        bill_action: BillAction,
+        signer_public_data: &IdentityPublicData,
+        timestamp: u64,
+    ) -> Result<()> {
+        let log_ctx = LogContext::bill(bill_id).with_node_id(&signer_public_data.node_id);
+        debug!("{log_ctx} Validating bill action {:?}", &bill_action);
+        self.check_bill_is_not_read_only_view(bill_id, &log_ctx)
+            .await?;
         // fetch data
         let identity = self.identity_store.get_full().await?;
         let contacts = self.contact_store.get_map().await?;
-        let mut blockchain = self.blockchain_store.get_chain(bill_id).await?;
+        let blockchain = self.blockchain_store.get_chain(bill_id).await?;
         let bill_keys = self.store.get_keys(bill_id).await?;
         let bill = self
             .get_last_version_bill(&blockchain, &bill_keys, &identity.identity, &contacts)
             .await?;
-        let is_paid = self.store.is_paid(bill_id).await?;
-
-        // validate
-        BillValidateActionData {
-            blockchain: blockchain.clone(),
-            drawee_node_id: bill.drawee.node_id.clone(),
-            payee_node_id: bill.payee.node_id.clone(),
-            endorsee_node_id: bill.endorsee.clone().map(|e| e.node_id),
-            maturity_date: bill.maturity_date.clone(),
-            bill_keys: bill_keys.clone(),
-            timestamp,
-            signer_node_id: signer_public_data.node_id.clone(),
-            bill_action: bill_action.clone(),
-            is_paid,
-        }
-        .validate()?;
 
-        // create and sign blocks
-        self.create_blocks_for_bill_action(
-            &bill,
-            &mut blockchain,
+        self.validate_bill_action_on_chain(
+            &blockchain,
             &bill_keys,
+            &bill,
             &bill_action,
+            &signer_public_data.node_id,
+            timestamp,
+        )
+        .await
+    }
+
+    async fn submit_mint_quote(
+        &self,
+        bill_id: &str,
+        sum: u64,
+        currency: &str,
+        expiry_timestamp: u64,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<BillBlockchain> {
+        self.execute_bill_action(
+            bill_id,
+            BillAction::SubmitMintQuote(sum, currency.to_owned(), expiry_timestamp),
             signer_public_data,
             signer_keys,
-            &identity,
             timestamp,
+            None,
         )
-        .await?;
+        .await
+    }
 
-        // Calculate bill and persist it to cache
-        self.recalculate_and_persist_bill(
+    async fn approve_mint_quote(
+        &self,
+        bill_id: &str,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<BillBlockchain> {
+        self.execute_bill_action(
             bill_id,
-            &blockchain,
-            &bill_keys,
-            &identity.identity,
-            &signer_public_data.node_id,
+            BillAction::ApproveMintQuote,
+            signer_public_data,
+            signer_keys,
             timestamp,
+            None,
         )
-        .await?;
+        .await
+    }
 
-        // notify and propagate blocks
-        self.notify_for_block_action(
-            &blockchain,
-            &bill_keys,
-            &bill_action,
-            &identity.identity,
-            &contacts,
+    async fn reject_mint_quote(
+        &self,
+        bill_id: &str,
+        signer_public_data: &IdentityPublicData,
+        signer_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<BillBlockchain> {
+        self.execute_bill_action(
+            bill_id,
+            BillAction::RejectMintQuote,
+            signer_public_data,
+            signer_keys,
+            timestamp,
+            None,
         )
-        .await?;
-
-        debug!("Executed bill action {:?} for bill {bill_id}", &bill_action);
-
-        Ok(blockchain)
+        .await
     }
 
     async fn check_bills_payment(&self) -> Result<()> {
         let identity = self.identity_store.get().await?;
-        let bill_ids_waiting_for_payment = self.store.get_bill_ids_waiting_for_payment().await?;
+        let bill_ids_waiting_for_payment = self.bills_for_tick(
+            "check_bills_payment",
+            self.store.get_bill_ids_waiting_for_payment().await?,
+        );
 
         for bill_id in bill_ids_waiting_for_payment {
+            if self.is_on_hold(&bill_id).await {
+                continue;
+            }
             if let Err(e) = self.check_bill_payment(&bill_id, &identity).await {
-                error!("Checking bill payment for {bill_id} failed: {e}");
+                error!(
+                    "{} Checking bill payment failed: {e}",
+                    LogContext::bill(&bill_id).with_node_id(&identity.node_id)
+                );
             }
         }
         Ok(())
@@ -595,16 +2029,24 @@ impl BillServiceApi for BillService {
 
     async fn check_bills_offer_to_sell_payment(&self) -> Result<()> {
         let identity = self.identity_store.get_full().await?;
-        let bill_ids_waiting_for_offer_to_sell_payment =
-            self.store.get_bill_ids_waiting_for_sell_payment().await?;
+        let bill_ids_waiting_for_offer_to_sell_payment = self.bills_for_tick(
+            "check_bills_offer_to_sell_payment",
+            self.store.get_bill_ids_waiting_for_sell_payment().await?,
+        );
         let now = external::time::TimeApi::get_atomic_time().await.timestamp;
 
         for bill_id in bill_ids_waiting_for_offer_to_sell_payment {
+            if self.is_on_hold(&bill_id).await {
+                continue;
+            }
             if let Err(e) = self
                 .check_bill_offer_to_sell_payment(&bill_id, &identity, now)
                 .await
             {
-                error!("Checking offer to sell payment for {bill_id} failed: {e}");
+                error!(
+                    "{} Checking offer to sell payment failed: {e}",
+                    LogContext::bill(&bill_id).with_node_id(&identity.identity.node_id)
+                );
             }
         }
         Ok(())
@@ -612,18 +2054,26 @@ impl BillServiceApi for BillService {
 
     async fn check_bills_in_recourse_payment(&self) -> Result<()> {
         let identity = self.identity_store.get_full().await?;
-        let bill_ids_waiting_for_recourse_payment = self
-            .store
-            .get_bill_ids_waiting_for_recourse_payment()
-            .await?;
+        let bill_ids_waiting_for_recourse_payment = self.bills_for_tick(
+            "check_bills_in_recourse_payment",
+            self.store
+                .get_bill_ids_waiting_for_recourse_payment()
+                .await?,
+        );
         let now = external::time::TimeApi::get_atomic_time().await.timestamp;
 
         for bill_id in bill_ids_waiting_for_recourse_payment {
+            if self.is_on_hold(&bill_id).await {
+                continue;
+            }
             if let Err(e) = self
                 .check_bill_in_recourse_payment(&bill_id, &identity, now)
                 .await
             {
-                error!("Checking recourse payment for {bill_id} failed: {e}");
+                error!(
+                    "{} Checking recourse payment failed: {e}",
+                    LogContext::bill(&bill_id).with_node_id(&identity.identity.node_id)
+                );
             }
         }
         Ok(())
@@ -637,46 +2087,113 @@ impl BillServiceApi for BillService {
             BillOpCode::RequestRecourse,
         ]);
 
-        let bill_ids_to_check = self
-            .store
-            .get_bill_ids_with_op_codes_since(op_codes, 0)
-            .await?;
+        let bill_ids_to_check = self.bills_for_tick(
+            "check_bills_timeouts",
+            self.store
+                .get_bill_ids_with_op_codes_since(op_codes, 0)
+                .await?,
+        );
 
         for bill_id in bill_ids_to_check {
+            if self.is_on_hold(&bill_id).await {
+                continue;
+            }
             if let Err(e) = self.check_bill_timeouts(&bill_id, now).await {
-                error!("Checking bill timeouts for {bill_id} failed: {e}");
+                error!(
+                    "{} Checking bill timeouts failed: {e}",
+                    LogContext::bill(&bill_id)
+                );
             }
         }
 
         Ok(())
     }
 
-    async fn get_past_endorsees(
-        &self,
-        bill_id: &str,
-        current_identity_node_id: &str,
-    ) -> Result<Vec<PastEndorsee>> {
-        if !self.store.exists(bill_id).await {
-            return Err(Error::NotFound);
+    async fn check_bills_maturity_reminders(&self, now: u64, lead_time_seconds: u64) -> Result<()> {
+        let identity = self.identity_store.get().await?;
+        let mut local_node_ids: HashSet<String> =
+            self.company_store.get_all().await?.into_keys().collect();
+        local_node_ids.insert(identity.node_id.clone());
+
+        let bill_ids = self.bills_for_tick(
+            "check_bills_maturity_reminders",
+            self.store.get_ids().await?,
+        );
+        for bill_id in bill_ids {
+            if self.is_on_hold(&bill_id).await {
+                continue;
+            }
+            if let Err(e) = self
+                .check_bill_maturity_reminder(
+                    &bill_id,
+                    now,
+                    lead_time_seconds,
+                    &identity,
+                    &local_node_ids,
+                )
+                .await
+            {
+                error!(
+                    "{} Checking bill maturity reminder failed: {e}",
+                    LogContext::bill(&bill_id).with_node_id(&identity.node_id)
+                );
+            }
         }
 
-        let chain = self.blockchain_store.get_chain(bill_id).await?;
-        let bill_keys = self.store.get_keys(bill_id).await?;
+        Ok(())
+    }
 
-        let bill_participants = chain.get_all_nodes_from_bill(&bill_keys)?;
-        // active identity is not part of the bill
-        if !bill_participants
-            .iter()
-            .any(|p| p == current_identity_node_id)
-        {
-            debug!("caller is not a participant of bill {bill_id}");
-            return Err(Error::NotFound);
+    async fn process_auto_accepts(&self, now: u64) -> Result<()> {
+        if !get_config().auto_accept_enabled {
+            return Ok(());
+        }
+        let queued_accepts = self.auto_accept_store.get_queued_accepts().await?;
+        if queued_accepts.is_empty() {
+            return Ok(());
+        }
+        let identity = self.identity_store.get_full().await?;
+        for queued in queued_accepts {
+            if let Err(e) = self.process_auto_accept(&queued, &identity, now).await {
+                error!(
+                    "{} Accepting bill automatically failed: {e}",
+                    LogContext::bill(&queued.bill_id).with_node_id(&queued.node_id)
+                );
+            }
+            // failed accepts aren't retried - the drawee can still accept the bill manually
+            self.auto_accept_store
+                .remove_queued_accept(&queued.bill_id, &queued.node_id)
+                .await?;
         }
+        Ok(())
+    }
+
+    async fn get_past_endorsees(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<Vec<PastEndorsee>> {
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
 
         let res = chain.get_past_endorsees_for_bill(&bill_keys, current_identity_node_id)?;
         Ok(res)
     }
 
+    async fn is_past_holder(
+        &self,
+        bill_id: &str,
+        candidate_node_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<bool> {
+        let past_endorsees = self
+            .get_past_endorsees(bill_id, current_identity_node_id)
+            .await?;
+        Ok(past_endorsees
+            .iter()
+            .any(|e| e.pay_to_the_order_of.node_id == candidate_node_id))
+    }
+
     async fn get_past_payments(
         &self,
         bill_id: &str,
@@ -708,60 +2225,64 @@ impl BillServiceApi for BillService {
         )?;
 
         // Request to Pay
-        if holder.node_id == caller_public_data.node_id {
-            if let Some(req_to_pay) =
+        if holder.node_id == caller_public_data.node_id
+            && let Some(req_to_pay) =
                 chain.get_last_version_block_with_op_code(BillOpCode::RequestToPay)
-            {
-                let address_to_pay = self
-                    .bitcoin_client
-                    .get_address_to_pay(&bill_keys.public_key, &holder.node_id)?;
-                let link_to_pay = self.bitcoin_client.generate_link_to_pay(
-                    &address_to_pay,
-                    bill.sum,
-                    &format!("Payment in relation to a bill {}", bill.id.clone()),
-                );
-                let mempool_link_for_address_to_pay = self
-                    .bitcoin_client
-                    .get_mempool_link_for_address(&address_to_pay);
-
-                let deadline_base =
-                    get_deadline_base_for_req_to_pay(req_to_pay.timestamp, &bill.maturity_date)?;
-                let is_expired = util::date::check_if_deadline_has_passed(
-                    deadline_base,
-                    timestamp,
-                    PAYMENT_DEADLINE_SECONDS,
-                );
-                let is_rejected = chain.block_with_operation_code_exists(BillOpCode::RejectToPay);
-
-                if is_paid || is_rejected || is_expired {
-                    result.push(PastPaymentResult::Payment(PastPaymentDataPayment {
-                        time_of_request: req_to_pay.timestamp,
-                        payer: bill_parties.drawee.clone().into(),
-                        payee: holder.clone().into(),
-                        currency: bill.currency.clone(),
-                        sum: currency::sum_to_string(bill.sum),
-                        link_to_pay,
-                        address_to_pay,
-                        private_key_to_spend: private_key_to_spend.clone(),
-                        mempool_link_for_address_to_pay,
-                        status: if is_paid {
-                            PastPaymentStatus::Paid(req_to_pay.timestamp)
-                        } else if is_rejected {
-                            let ts = if let Some(reject_to_pay_block) =
-                                chain.get_last_version_block_with_op_code(BillOpCode::RejectToPay)
-                            {
-                                reject_to_pay_block.timestamp
-                            } else {
-                                req_to_pay.timestamp
-                            };
-                            PastPaymentStatus::Rejected(ts)
+        {
+            let address_to_pay = self
+                .bitcoin_client
+                .get_address_to_pay(&bill_keys.public_key, &holder.node_id)?;
+            let link_to_pay = self.bitcoin_client.generate_link_to_pay(
+                &address_to_pay,
+                bill.sum,
+                &format!("Payment in relation to a bill {}", bill.id.clone()),
+            );
+            let mempool_link_for_address_to_pay = self
+                .bitcoin_client
+                .get_mempool_link_for_address(&address_to_pay);
+
+            let payment_deadline_seconds = bill
+                .payment_deadline_seconds
+                .unwrap_or(PAYMENT_DEADLINE_SECONDS);
+            let deadline_base =
+                get_deadline_base_for_req_to_pay(req_to_pay.timestamp, &bill.maturity_date)?;
+            let is_expired = util::date::check_if_deadline_has_passed(
+                deadline_base,
+                timestamp,
+                payment_deadline_seconds,
+            );
+            let is_rejected = chain.block_with_operation_code_exists(BillOpCode::RejectToPay);
+
+            if is_paid || is_rejected || is_expired {
+                result.push(PastPaymentResult::Payment(PastPaymentDataPayment {
+                    time_of_request: req_to_pay.timestamp,
+                    payer: bill_parties.drawee.clone().into(),
+                    payee: holder.clone().into(),
+                    currency: bill.currency.clone(),
+                    sum: currency::sum_to_string(bill.sum),
+                    link_to_pay,
+                    address_to_pay,
+                    private_key_to_spend: private_key_to_spend.clone(),
+                    mempool_link_for_address_to_pay,
+                    status: if is_paid {
+                        PastPaymentStatus::Paid(req_to_pay.timestamp)
+                    } else if is_rejected {
+                        let ts = if let Some(reject_to_pay_block) =
+                            chain.get_last_version_block_with_op_code(BillOpCode::RejectToPay)
+                        {
+                            reject_to_pay_block.timestamp
                         } else {
-                            PastPaymentStatus::Expired(
-                                req_to_pay.timestamp + PAYMENT_DEADLINE_SECONDS,
-                            )
-                        },
-                    }));
-                }
+                            req_to_pay.timestamp
+                        };
+                        PastPaymentStatus::Rejected(ts)
+                    } else {
+                        PastPaymentStatus::Expired(
+                            req_to_pay
+                                .timestamp
+                                .saturating_add(payment_deadline_seconds),
+                        )
+                    },
+                }));
             }
         }
 
@@ -838,22 +2359,9 @@ impl BillServiceApi for BillService {
         bill_id: &str,
         current_identity_node_id: &str,
     ) -> Result<Vec<Endorsement>> {
-        if !self.store.exists(bill_id).await {
-            return Err(Error::NotFound);
-        }
-
-        let chain = self.blockchain_store.get_chain(bill_id).await?;
-        let bill_keys = self.store.get_keys(bill_id).await?;
-
-        let bill_participants = chain.get_all_nodes_from_bill(&bill_keys)?;
-        // active identity is not part of the bill
-        if !bill_participants
-            .iter()
-            .any(|p| p == current_identity_node_id)
-        {
-            debug!("caller is not a participant of bill {bill_id}");
-            return Err(Error::NotFound);
-        }
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
 
         let mut result: Vec<Endorsement> = vec![];
         // iterate from the back to the front, collecting all endorsement blocks
@@ -884,8 +2392,555 @@ impl BillServiceApi for BillService {
         Ok(result)
     }
 
+    async fn get_endorsement_count(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<u64> {
+        let (chain, _) = self
+            .check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        // only the op codes are needed, so the block data doesn't have to be decrypted
+        Ok(chain.get_endorsements_count())
+    }
+
+    async fn get_bill_history(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<Vec<BillHistoryEntry>> {
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        let mut result = Vec::with_capacity(chain.block_height());
+        for block in chain.blocks() {
+            result.push(block.get_history_entry(&bill_keys)?);
+        }
+        Ok(result)
+    }
+
+    async fn resend_bill_event(
+        &self,
+        bill_id: &str,
+        block_height: usize,
+        node_id: &str,
+        current_identity_node_id: &str,
+    ) -> Result<()> {
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+        let log_ctx = LogContext::bill(bill_id).with_node_id(current_identity_node_id);
+        if block_height == 0 || block_height > chain.block_height() {
+            debug!("{log_ctx} block {block_height} of the bill doesn't exist");
+            return Err(Error::NotFound);
+        }
+
+        // the event is reconstructed from the chain, as it was when the block was added
+        let chain_at_block =
+            BillBlockchain::new_from_blocks(chain.blocks()[..block_height].to_vec())?;
+        Self::check_is_participant_of_chain(&chain_at_block, &bill_keys, bill_id, node_id)?;
+
+        let identity = self.identity_store.get().await?;
+        let contacts = self.contact_store.get_map().await?;
+        let bill = self
+            .get_last_version_bill(&chain_at_block, &bill_keys, &identity, &contacts)
+            .await?;
+        let event = BillChainEvent::new(
+            &bill,
+            &chain_at_block,
+            &bill_keys,
+            true,
+            current_identity_node_id,
+        )?;
+        self.notification_service
+            .resend_bill_event(&event, node_id)
+            .await?;
+        info!("{log_ctx} Resent event for block {block_height} of the bill to {node_id}");
+        Ok(())
+    }
+
+    async fn get_unnotified_participants(
+        &self,
+        bill_id: &str,
+        block_height: usize,
+    ) -> Result<Vec<ResolvedParticipant>> {
+        if !self.store.exists(bill_id).await {
+            return Err(Error::NotFound);
+        }
+        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        if block_height == 0 || block_height > chain.block_height() {
+            debug!(
+                "{} block {block_height} of the bill doesn't exist",
+                LogContext::bill(bill_id)
+            );
+            return Err(Error::NotFound);
+        }
+
+        let chain_at_block =
+            BillBlockchain::new_from_blocks(chain.blocks()[..block_height].to_vec())?;
+        let (signer_node_id, _) = chain_at_block
+            .get_latest_block()
+            .verify_and_get_signer(&bill_keys)?;
+        let notified: HashSet<String> = self
+            .notification_service
+            .get_notified_bill_participants(bill_id, block_height as i32)
+            .await?
+            .into_iter()
+            .collect();
+        Ok(chain_at_block
+            .get_resolved_participants(&bill_keys)?
+            .into_iter()
+            .filter(|p| p.node_id != signer_node_id && !notified.contains(&p.node_id))
+            .collect())
+    }
+
     async fn clear_bill_cache(&self) -> Result<()> {
         self.store.clear_bill_cache().await?;
         Ok(())
     }
+
+    async fn find_inconsistent_bills(&self) -> Result<Vec<BillInconsistency>> {
+        let chain_ids: HashSet<String> = self.store.get_ids().await?.into_iter().collect();
+        let key_ids: HashSet<String> = self.store.get_key_ids().await?.into_iter().collect();
+
+        let mut inconsistencies: Vec<BillInconsistency> = key_ids
+            .difference(&chain_ids)
+            .map(|bill_id| BillInconsistency {
+                bill_id: bill_id.to_owned(),
+                kind: BillInconsistencyKind::KeysWithoutChain,
+            })
+            .collect();
+        for bill_id in chain_ids.iter() {
+            let kind = if !key_ids.contains(bill_id) {
+                BillInconsistencyKind::ChainWithoutKeys
+            } else if let Err(e) = self.blockchain_store.get_chain(bill_id).await {
+                warn!(
+                    "{} Could not load bill chain: {e}",
+                    LogContext::bill(bill_id)
+                );
+                BillInconsistencyKind::InvalidChain
+            } else {
+                continue;
+            };
+            inconsistencies.push(BillInconsistency {
+                bill_id: bill_id.to_owned(),
+                kind,
+            });
+        }
+        inconsistencies.sort_by(|a, b| a.bill_id.cmp(&b.bill_id));
+        Ok(inconsistencies)
+    }
+
+    async fn set_bill_hold(
+        &self,
+        bill_id: &str,
+        reason: &str,
+        current_identity_node_id: &str,
+        timestamp: u64,
+    ) -> Result<()> {
+        if reason.trim().is_empty() {
+            return Err(ValidationError::FieldEmpty(Field::HoldReason).into());
+        }
+        self.check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        self.store
+            .set_hold(
+                bill_id,
+                &BillHold {
+                    reason: reason.trim().to_owned(),
+                    timestamp,
+                },
+            )
+            .await?;
+        // the hold is part of the bill status, so the cached bill is outdated
+        self.store.invalidate_bill_in_cache(bill_id).await?;
+        info!(
+            "{} Bill was put on hold",
+            LogContext::bill(bill_id).with_node_id(current_identity_node_id)
+        );
+        Ok(())
+    }
+
+    async fn clear_bill_hold(&self, bill_id: &str, current_identity_node_id: &str) -> Result<()> {
+        self.check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        self.store.clear_hold(bill_id).await?;
+        self.store.invalidate_bill_in_cache(bill_id).await?;
+        info!(
+            "{} Hold of bill was cleared",
+            LogContext::bill(bill_id).with_node_id(current_identity_node_id)
+        );
+        Ok(())
+    }
+
+    async fn archive_bill(&self, bill_id: &str, current_identity_node_id: &str) -> Result<()> {
+        self.check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        self.store
+            .archive(bill_id, current_identity_node_id)
+            .await?;
+        info!(
+            "{} Bill was archived",
+            LogContext::bill(bill_id).with_node_id(current_identity_node_id)
+        );
+        Ok(())
+    }
+
+    async fn unarchive_bill(&self, bill_id: &str, current_identity_node_id: &str) -> Result<()> {
+        self.check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        self.store
+            .unarchive(bill_id, current_identity_node_id)
+            .await?;
+        info!(
+            "{} Bill was unarchived",
+            LogContext::bill(bill_id).with_node_id(current_identity_node_id)
+        );
+        Ok(())
+    }
+
+    async fn set_bill_note(
+        &self,
+        bill_id: &str,
+        current_identity_node_id: &str,
+        note: Option<String>,
+    ) -> Result<()> {
+        self.check_caller_is_participant(bill_id, current_identity_node_id)
+            .await?;
+
+        match note.as_deref().map(str::trim) {
+            Some(note) if !note.is_empty() => {
+                self.store
+                    .set_note(bill_id, current_identity_node_id, note)
+                    .await?;
+                info!(
+                    "{} Note of bill was set",
+                    LogContext::bill(bill_id).with_node_id(current_identity_node_id)
+                );
+            }
+            _ => {
+                self.store
+                    .remove_note(bill_id, current_identity_node_id)
+                    .await?;
+                info!(
+                    "{} Note of bill was removed",
+                    LogContext::bill(bill_id).with_node_id(current_identity_node_id)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn export_bill_bundle(&self, bill_id: &str, caller_keys: &BcrKeys) -> Result<Vec<u8>> {
+        let caller_node_id = caller_keys.get_public_key();
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, &caller_node_id)
+            .await?;
+        let bill = chain.get_first_version_bill(&bill_keys)?;
+
+        let mut files = Vec::with_capacity(bill.files.len());
+        for file in bill.files.iter() {
+            let decrypted = self
+                .open_and_decrypt_attached_file(bill_id, &file.name, &bill_keys.private_key)
+                .await?;
+            files.push(BillBundleFile {
+                name: file.name.clone(),
+                encrypted_content: util::base58_encode(&util::crypto::encrypt_ecies(
+                    &decrypted,
+                    &caller_node_id,
+                )?),
+            });
+        }
+
+        let payload = BillBundlePayload {
+            bill_id: bill_id.to_owned(),
+            signer_node_id: caller_node_id.clone(),
+            blocks: chain.blocks().to_owned(),
+            encrypted_keys: util::base58_encode(&util::crypto::encrypt_ecies(
+                &bill_keys_to_bytes(&bill_keys)?,
+                &caller_node_id,
+            )?),
+            files,
+        };
+        let signature =
+            util::crypto::signature(&payload.hash()?, &caller_keys.get_private_key_string())?;
+        BillBundle { payload, signature }.to_bytes()
+    }
+
+    async fn import_bill_bundle(&self, bundle: &[u8], caller_keys: &BcrKeys) -> Result<String> {
+        let BillBundle { payload, signature } = BillBundle::from_bytes(bundle)?;
+        let caller_node_id = caller_keys.get_public_key();
+        let private_key = caller_keys.get_private_key_string();
+
+        // only bundles exported by the caller can be imported, since they're encrypted to them
+        if payload.signer_node_id != caller_node_id
+            || !util::crypto::verify(&payload.hash()?, &signature, &payload.signer_node_id)
+                .unwrap_or(false)
+        {
+            return Err(ValidationError::InvalidBillBundle.into());
+        }
+
+        let bill_id = payload.bill_id;
+        if payload.blocks.iter().any(|b| b.bill_id != bill_id) {
+            return Err(ValidationError::InvalidBillBundle.into());
+        }
+        // validates every block of the chain
+        let chain = BillBlockchain::new_from_blocks_with_max(
+            payload.blocks,
+            get_config().max_bill_chain_blocks,
+        )
+        .map_err(|_| ValidationError::InvalidBillBundle)?;
+
+        let bill_keys = bill_keys_from_bytes(&util::crypto::decrypt_ecies(
+            &util::base58_decode(&payload.encrypted_keys)
+                .map_err(|_| ValidationError::InvalidBillBundle)?,
+            &private_key,
+        )?)?;
+        let bill_participants = chain.get_all_nodes_from_bill(&bill_keys)?;
+        if !bill_participants.iter().any(|p| p == &caller_node_id) {
+            return Err(ValidationError::InvalidBillBundle.into());
+        }
+
+        if self.store.exists(&bill_id).await {
+            debug!(
+                "{} bill from bundle already exists",
+                LogContext::bill(&bill_id).with_node_id(&caller_node_id)
+            );
+            return Err(Error::InvalidOperation);
+        }
+
+        // the attached files have to match the hashes recorded in the chain
+        let mut recorded_files = chain.get_first_version_bill(&bill_keys)?.files;
+        recorded_files.extend(chain.get_attached_files(&bill_keys)?);
+        let mut files = Vec::with_capacity(payload.files.len());
+        for file in payload.files.iter() {
+            let decrypted = util::crypto::decrypt_ecies(
+                &util::base58_decode(&file.encrypted_content)
+                    .map_err(|_| ValidationError::InvalidBillBundle)?,
+                &private_key,
+            )?;
+            let hash = util::sha256_hash(&decrypted);
+            if !recorded_files
+                .iter()
+                .any(|recorded| recorded.name == file.name && recorded.hash == hash)
+            {
+                debug!(
+                    "{} file {} from bundle doesn't match the chain",
+                    LogContext::bill(&bill_id).with_node_id(&caller_node_id),
+                    &file.name
+                );
+                return Err(ValidationError::InvalidBillBundle.into());
+            }
+            files.push((
+                file.name.clone(),
+                hash,
+                util::crypto::encrypt_ecies(&decrypted, &bill_keys.public_key)?,
+            ));
+        }
+
+        // the bill is only known once its chain is stored, so it's stored last and in one go -
+        // if a step fails, the steps before it are undone
+        for (name, _, encrypted) in files.iter() {
+            if let Err(e) = self
+                .file_upload_store
+                .save_attached_file(encrypted, &bill_id, name)
+                .await
+            {
+                self.undo_bill_import(&bill_id, false).await;
+                return Err(e.into());
+            }
+        }
+        if let Err(e) = self.store.save_keys(&bill_id, &bill_keys).await {
+            self.undo_bill_import(&bill_id, false).await;
+            return Err(e.into());
+        }
+        if let Err(e) = self
+            .blockchain_store
+            .replace_blocks_from(&bill_id, chain.blocks())
+            .await
+        {
+            self.undo_bill_import(&bill_id, true).await;
+            return Err(e.into());
+        }
+        for (name, hash, _) in files.into_iter() {
+            self.remember_file_hash(&bill_id, &name, Some(hash));
+        }
+
+        // seed the cache and with it the participant index, so the bill is listed right away
+        let identity = self.identity_store.get().await?;
+        if let Err(e) = self
+            .recalculate_and_persist_bill(
+                &bill_id,
+                &chain,
+                &bill_keys,
+                &identity,
+                &caller_node_id,
+                util::date::now().timestamp() as u64,
+            )
+            .await
+        {
+            error!(
+                "{} Error saving imported bill to cache: {e}",
+                LogContext::bill(&bill_id).with_node_id(&caller_node_id)
+            );
+        }
+        info!(
+            "{} Imported bill from bundle",
+            LogContext::bill(&bill_id).with_node_id(&caller_node_id)
+        );
+        Ok(bill_id)
+    }
+
+    async fn export_bill_view(&self, bill_id: &str, caller_keys: &BcrKeys) -> Result<BillView> {
+        let caller_node_id = caller_keys.get_public_key();
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, &caller_node_id)
+            .await?;
+        // the block data is re-encrypted to a fresh viewing keypair, so the bill keys aren't shared
+        let view_keys = BcrKeys::new();
+        let view_data = chain
+            .blocks()
+            .iter()
+            .map(|block| block.get_view_data(&bill_keys, &view_keys.get_public_key()))
+            .collect::<std::result::Result<Vec<String>, _>>()?;
+        info!(
+            "{} Exported read-only view of bill",
+            LogContext::bill(bill_id).with_node_id(&caller_node_id)
+        );
+        Ok(BillView {
+            bill_id: bill_id.to_owned(),
+            view_key: BillViewKey::new(&bill_keys.public_key, &view_keys),
+            blocks: chain.blocks().to_owned(),
+            view_data,
+        })
+    }
+
+    async fn import_bill_view(&self, view: BillView) -> Result<String> {
+        let bill_id = view.bill_id.clone();
+        if view.blocks.iter().any(|b| b.bill_id != bill_id) {
+            return Err(ValidationError::InvalidBillView.into());
+        }
+        // validates every block of the chain and that the view key can decrypt it
+        let chain = BillBlockchain::new_for_view(view.blocks.clone(), view.view_data.clone())
+            .map_err(|_| ValidationError::InvalidBillView)?;
+        chain
+            .get_first_version_bill(&view.view_key.decryption_keys())
+            .map_err(|_| ValidationError::InvalidBillView)?;
+
+        // participants already have the whole bill
+        if self.store.exists(&bill_id).await {
+            debug!(
+                "{} bill from view already exists",
+                LogContext::bill(&bill_id)
+            );
+            return Err(Error::InvalidOperation);
+        }
+
+        self.store.save_view(&view).await?;
+        info!(
+            "{} Imported read-only view of bill",
+            LogContext::bill(&bill_id)
+        );
+        Ok(bill_id)
+    }
+
+    async fn get_bill_view_detail(
+        &self,
+        bill_id: &str,
+        identity: &Identity,
+        current_timestamp: u64,
+    ) -> Result<BitcreditBillResult> {
+        let view = match self.store.get_view(bill_id).await? {
+            Some(view) => view,
+            None => return Err(Error::NotFound),
+        };
+        let chain = BillBlockchain::new_for_view(view.blocks, view.view_data)
+            .map_err(|_| ValidationError::InvalidBillView)?;
+        self.calculate_full_bill(
+            &chain,
+            &view.view_key.decryption_keys(),
+            identity,
+            &identity.node_id,
+            current_timestamp,
+        )
+        .await
+    }
+
+    async fn get_bill_qr_payload(&self, bill_id: &str, caller_keys: &BcrKeys) -> Result<String> {
+        let caller_node_id = caller_keys.get_public_key();
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, &caller_node_id)
+            .await?;
+        let bill = chain.get_first_version_bill(&bill_keys)?;
+        // the drawee might have been corrected since the bill was issued
+        let drawee = chain.get_bill_parties(&bill_keys, &bill)?.drawee;
+        BillQrPayload {
+            bill_id: bill_id.to_owned(),
+            sum: bill.sum,
+            currency: bill.currency,
+            drawee_node_id: drawee.node_id,
+            payee_node_id: bill.payee.node_id,
+            relay: get_config().nostr_relay(),
+            signer_node_id: caller_node_id,
+        }
+        .sign(&caller_keys.get_private_key_string())
+    }
+
+    async fn parse_bill_qr_payload(&self, payload: &str) -> Result<BillQrPayload> {
+        BillQrPayload::verify(payload)
+    }
+
+    async fn resolve_bill_participants(
+        &self,
+        bill_id: &str,
+        caller_keys: &BcrKeys,
+    ) -> Result<Vec<ResolvedParticipant>> {
+        let (chain, bill_keys) = self
+            .check_caller_is_participant(bill_id, &caller_keys.get_public_key())
+            .await?;
+        Ok(chain.get_resolved_participants(&bill_keys)?)
+    }
+
+    async fn ingest_bill_event(&self, envelope: EventEnvelope) -> Result<String> {
+        let bill_id = BillChainSync::new(self.blockchain_store.clone(), self.store.clone())
+            .with_max_chain_blocks(get_config().max_bill_chain_blocks)
+            .ingest_bill_event(envelope)
+            .await?;
+        info!("{} Ingested bill event", LogContext::bill(&bill_id));
+        Ok(bill_id)
+    }
+
+    async fn preview_notification(
+        &self,
+        bill_id: &str,
+        action: &ActionType,
+        language: &str,
+    ) -> Result<NotificationPreview> {
+        if !self.store.exists(bill_id).await {
+            return Err(Error::NotFound);
+        }
+
+        let chain = self.blockchain_store.get_chain(bill_id).await?;
+        let bill_keys = self.store.get_keys(bill_id).await?;
+        let bill = chain.get_first_version_bill(&bill_keys)?;
+        // counterparties receive the notification for an action we perform
+        let identity = self.identity_store.get().await?;
+
+        Ok(render_notification_preview(
+            action,
+            language,
+            &NotificationTemplateData {
+                bill_id: bill.id,
+                sum: format!("{} {}", currency::sum_to_string(bill.sum), bill.currency),
+                counterparty: identity.name,
+            },
+        ))
+    }
 }
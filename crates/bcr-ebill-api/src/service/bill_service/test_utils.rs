@@ -1,42 +1,43 @@
 use super::*;
 use crate::{
-    data::identity::IdentityWithAll,
     external,
     service::{
         company_service::tests::get_valid_company_block,
         contact_service::tests::get_baseline_contact,
     },
     tests::tests::{
-        MockBillChainStoreApiMock, MockBillStoreApiMock, MockCompanyChainStoreApiMock,
-        MockCompanyStoreApiMock, MockContactStoreApiMock, MockFileUploadStoreApiMock,
-        MockIdentityChainStoreApiMock, MockIdentityStoreApiMock, MockNotificationService,
-        TEST_BILL_ID, TEST_PRIVATE_KEY_SECP, TEST_PUB_KEY_SECP, VALID_PAYMENT_ADDRESS_TESTNET,
-        empty_address, empty_bitcredit_bill, empty_identity, empty_identity_public_data,
-        identity_public_data_only_node_id,
+        MockBillAutoAcceptStoreApiMock, MockBillChainStoreApiMock, MockBillStoreApiMock,
+        MockBillTemplateStoreApiMock, MockCompanyChainStoreApiMock, MockCompanyStoreApiMock,
+        MockContactStoreApiMock, MockFileUploadStoreApiMock, MockIdentityChainStoreApiMock,
+        MockIdentityStoreApiMock, MockNotificationService, TEST_PRIVATE_KEY_SECP,
+        TEST_PUB_KEY_SECP, VALID_PAYMENT_ADDRESS_TESTNET, empty_address, empty_identity,
+        identity_public_data_only_node_id, init_test_cfg,
     },
     util,
 };
 use bcr_ebill_core::{
     bill::{
         BillAcceptanceStatus, BillData, BillParticipants, BillPaymentStatus, BillRecourseStatus,
-        BillSellStatus, BillStatus,
+        BillSellStatus, BillStatus, SettlementStatus,
     },
     blockchain::{
         Blockchain,
         bill::{
-            BillBlock,
+            BillBlock, BillOpCode,
             block::{
-                BillAcceptBlockData, BillIssueBlockData, BillOfferToSellBlockData,
-                BillRecourseBlockData, BillRecourseReasonBlockData, BillRejectBlockData,
-                BillRequestRecourseBlockData, BillRequestToAcceptBlockData,
-                BillRequestToPayBlockData, BillSellBlockData,
+                BillAcceptBlockData, BillAcknowledgeBlockData, BillMintBlockData,
+                BillMintQuoteBlockData, BillOfferToSellBlockData, BillRecourseBlockData,
+                BillRecourseReasonBlockData, BillRejectBlockData, BillRequestRecourseBlockData,
+                BillRequestToAcceptBlockData, BillRequestToPayBlockData, BillSellBlockData,
             },
         },
         identity::IdentityBlockchain,
     },
+    constants::PAYMENT_DEADLINE_SECONDS,
 };
 use core::str;
-use external::bitcoin::MockBitcoinClientApi;
+use external::bitcoin::{MockBitcoinClientApi, RecommendedFees};
+use external::fiat::MockFiatRateClientApi;
 use service::BillService;
 use std::{collections::HashMap, sync::Arc};
 use util::crypto::BcrKeys;
@@ -49,23 +50,15 @@ pub struct MockBillContext {
     pub identity_chain_store: MockIdentityChainStoreApiMock,
     pub company_chain_store: MockCompanyChainStoreApiMock,
     pub company_store: MockCompanyStoreApiMock,
+    pub bill_template_store: MockBillTemplateStoreApiMock,
+    pub auto_accept_store: MockBillAutoAcceptStoreApiMock,
     pub file_upload_store: MockFileUploadStoreApiMock,
     pub notification_service: MockNotificationService,
+    pub fiat_rate_client: MockFiatRateClientApi,
+    pub bitcoin_client: MockBitcoinClientApi,
 }
 
-pub fn get_baseline_identity() -> IdentityWithAll {
-    let keys = BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap();
-    let mut identity = empty_identity();
-    identity.name = "drawer".to_owned();
-    identity.node_id = keys.get_public_key();
-    identity.postal_address.country = Some("AT".to_owned());
-    identity.postal_address.city = Some("Vienna".to_owned());
-    identity.postal_address.address = Some("Hayekweg 5".to_owned());
-    IdentityWithAll {
-        identity,
-        key_pair: keys,
-    }
-}
+pub use crate::test_utils::{get_baseline_bill, get_baseline_identity, get_genesis_chain};
 
 pub fn get_baseline_cached_bill(id: String) -> BitcreditBillResult {
     BitcreditBillResult {
@@ -95,7 +88,12 @@ pub fn get_baseline_cached_bill(id: String) -> BitcreditBillResult {
             currency: "sat".to_string(),
             sum: "15000".to_string(),
             files: vec![],
+            tampered_files: vec![],
+            acknowledgements: vec![],
+            payment_deadline_seconds: PAYMENT_DEADLINE_SECONDS,
+            fiat_amount: None,
             active_notification: None,
+            note: None,
         },
         status: BillStatus {
             acceptance: BillAcceptanceStatus {
@@ -104,13 +102,17 @@ pub fn get_baseline_cached_bill(id: String) -> BitcreditBillResult {
                 accepted: false,
                 request_to_accept_timed_out: false,
                 rejected_to_accept: false,
+                rejected_to_accept_reason: None,
             },
             payment: BillPaymentStatus {
                 time_of_request_to_pay: None,
                 requested_to_pay: false,
                 paid: false,
+                paid_sum: 0,
                 request_to_pay_timed_out: false,
                 rejected_to_pay: false,
+                rejected_to_pay_reason: None,
+                external_payment_reference: None,
             },
             sell: BillSellStatus {
                 time_of_last_offer_to_sell: None,
@@ -118,6 +120,8 @@ pub fn get_baseline_cached_bill(id: String) -> BitcreditBillResult {
                 offered_to_sell: false,
                 offer_to_sell_timed_out: false,
                 rejected_offer_to_sell: false,
+                rejected_offer_to_sell_reason: None,
+                sell_deadline_seconds: None,
             },
             recourse: BillRecourseStatus {
                 time_of_last_request_to_recourse: None,
@@ -128,41 +132,36 @@ pub fn get_baseline_cached_bill(id: String) -> BitcreditBillResult {
             },
             redeemed_funds_available: false,
             has_requested_funds: false,
+            hold: None,
+            last_op_code: BillOpCode::Issue,
+            block_height: 1,
+            settlement_status: SettlementStatus::Active,
         },
         current_waiting_state: None,
     }
 }
 
-pub fn get_baseline_bill(bill_id: &str) -> BitcreditBill {
-    let mut bill = empty_bitcredit_bill();
-    let keys = BcrKeys::new();
-
-    bill.maturity_date = "2099-10-15".to_string();
-    bill.payee = empty_identity_public_data();
-    bill.payee.name = "payee".to_owned();
-    bill.payee.node_id = keys.get_public_key();
-    bill.drawee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
-    bill.id = bill_id.to_owned();
-    bill
-}
-
-pub fn get_genesis_chain(bill: Option<BitcreditBill>) -> BillBlockchain {
-    let bill = bill.unwrap_or(get_baseline_bill(TEST_BILL_ID));
-    BillBlockchain::new(
-        &BillIssueBlockData::from(bill, None, 1731593920),
-        get_baseline_identity().key_pair,
-        None,
-        BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
-        1731593920,
-    )
-    .unwrap()
+pub fn get_recommended_fees() -> RecommendedFees {
+    RecommendedFees {
+        fastest_fee: 20,
+        half_hour_fee: 15,
+        hour_fee: 10,
+        economy_fee: 5,
+        minimum_fee: 1,
+    }
 }
 
 pub fn get_service(mut ctx: MockBillContext) -> BillService {
-    let mut bitcoin_client = MockBitcoinClientApi::new();
+    let mut bitcoin_client = ctx.bitcoin_client;
     bitcoin_client
         .expect_check_if_paid()
         .returning(|_, _| Ok((true, 100)));
+    bitcoin_client
+        .expect_get_confirmed_paid_sum()
+        .returning(|_| Ok(100));
+    bitcoin_client
+        .expect_get_payment_confirmations()
+        .returning(|_| Ok(6));
     bitcoin_client
         .expect_get_combined_private_key()
         .returning(|_, _| Ok(String::from("123412341234")));
@@ -176,6 +175,9 @@ pub fn get_service(mut ctx: MockBillContext) -> BillService {
                 "http://blockstream.info/testnet/address/1Jfn2nZcJ4T7bhE8FdMRz8T3P3YV4LsWn2",
             )
         });
+    bitcoin_client
+        .expect_get_recommended_fees()
+        .returning(|| Ok(get_recommended_fees()));
     bitcoin_client.expect_generate_link_to_pay().returning(|_,_,_| String::from("bitcoin:1Jfn2nZcJ4T7bhE8FdMRz8T3P3YV4LsWn2?amount=0.01&message=Payment in relation to bill some bill"));
     ctx.contact_store.expect_get().returning(|node_id| {
         let mut contact = get_baseline_contact();
@@ -230,6 +232,16 @@ pub fn get_service(mut ctx: MockBillContext) -> BillService {
         .expect_save_bill_to_cache()
         .returning(|_, _| Ok(()));
     ctx.bill_store.expect_is_paid().returning(|_| Ok(false));
+    ctx.bill_store.expect_get_hold().returning(|_| Ok(None));
+    ctx.bill_store.expect_get_note().returning(|_, _| Ok(None));
+    ctx.bill_store.expect_get_view().returning(|_| Ok(None));
+    ctx.bill_store
+        .expect_get_archived_bill_ids()
+        .returning(|_| Ok(vec![]));
+    ctx.bill_store.expect_get_paid_sum().returning(|_| Ok(0));
+    ctx.bill_store
+        .expect_set_paid_sum()
+        .returning(|_, _, _| Ok(()));
     ctx.identity_store
         .expect_get()
         .returning(|| Ok(get_baseline_identity().identity));
@@ -242,15 +254,20 @@ pub fn get_service(mut ctx: MockBillContext) -> BillService {
         Arc::new(ctx.identity_store),
         Arc::new(ctx.file_upload_store),
         Arc::new(bitcoin_client),
+        Arc::new(ctx.fiat_rate_client),
         Arc::new(ctx.notification_service),
         Arc::new(ctx.identity_chain_store),
         Arc::new(ctx.company_chain_store),
         Arc::new(ctx.contact_store),
         Arc::new(ctx.company_store),
+        Arc::new(ctx.bill_template_store),
+        Arc::new(ctx.auto_accept_store),
     )
 }
 
 pub fn get_ctx() -> MockBillContext {
+    // the service reads limits, like the maximum number of files of a bill, from the config
+    init_test_cfg();
     MockBillContext {
         bill_store: MockBillStoreApiMock::new(),
         bill_blockchain_store: MockBillChainStoreApiMock::new(),
@@ -260,7 +277,11 @@ pub fn get_ctx() -> MockBillContext {
         company_chain_store: MockCompanyChainStoreApiMock::new(),
         contact_store: MockContactStoreApiMock::new(),
         company_store: MockCompanyStoreApiMock::new(),
+        bill_template_store: MockBillTemplateStoreApiMock::new(),
+        auto_accept_store: MockBillAutoAcceptStoreApiMock::new(),
         notification_service: MockNotificationService::new(),
+        fiat_rate_client: MockFiatRateClientApi::new(),
+        bitcoin_client: MockBitcoinClientApi::new(),
     }
 }
 
@@ -342,6 +363,7 @@ pub fn reject_recourse_block(id: &str, first_block: &BillBlock) -> BillBlock {
             signatory: None,
             signing_timestamp: first_block.timestamp,
             signing_address: empty_address(),
+            reason: None,
         },
         &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
         None,
@@ -389,6 +411,7 @@ pub fn reject_accept_block(id: &str, first_block: &BillBlock) -> BillBlock {
             signatory: None,
             signing_timestamp: first_block.timestamp,
             signing_address: empty_address(),
+            reason: None,
         },
         &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
         None,
@@ -422,6 +445,7 @@ pub fn offer_to_sell_block(
             signatory: None,
             signing_timestamp: timestamp,
             signing_address: empty_address(),
+            sell_deadline_seconds: None,
         },
         &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
         None,
@@ -445,6 +469,55 @@ pub fn reject_buy_block(id: &str, first_block: &BillBlock) -> BillBlock {
             signatory: None,
             signing_timestamp: first_block.timestamp,
             signing_address: empty_address(),
+            reason: None,
+        },
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        None,
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        first_block.timestamp,
+    )
+    .expect("block could not be created")
+}
+
+pub fn cancel_offer_to_sell_block(id: &str, first_block: &BillBlock) -> BillBlock {
+    BillBlock::create_block_for_cancel_offer_to_sell(
+        id.to_string(),
+        first_block,
+        &BillRejectBlockData {
+            rejecter: identity_public_data_only_node_id(
+                BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP)
+                    .unwrap()
+                    .get_public_key(),
+            )
+            .into(),
+            signatory: None,
+            signing_timestamp: first_block.timestamp,
+            signing_address: empty_address(),
+            reason: None,
+        },
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        None,
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        first_block.timestamp,
+    )
+    .expect("block could not be created")
+}
+
+pub fn cancel_request_to_accept_block(id: &str, first_block: &BillBlock) -> BillBlock {
+    BillBlock::create_block_for_cancel_request_to_accept(
+        id.to_string(),
+        first_block,
+        &BillRejectBlockData {
+            rejecter: identity_public_data_only_node_id(
+                BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP)
+                    .unwrap()
+                    .get_public_key(),
+            )
+            .into(),
+            signatory: None,
+            signing_timestamp: first_block.timestamp,
+            signing_address: empty_address(),
+            reason: None,
         },
         &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
         None,
@@ -504,6 +577,88 @@ pub fn accept_block(id: &str, first_block: &BillBlock) -> BillBlock {
     .expect("block could not be created")
 }
 
+pub fn mint_block(id: &str, first_block: &BillBlock, mint: &IdentityPublicData) -> BillBlock {
+    BillBlock::create_block_for_mint(
+        id.to_string(),
+        first_block,
+        &BillMintBlockData {
+            endorser: identity_public_data_only_node_id(
+                BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP)
+                    .unwrap()
+                    .get_public_key(),
+            )
+            .into(),
+            endorsee: mint.to_owned().into(),
+            currency: "sat".to_string(),
+            sum: 5000,
+            signatory: None,
+            signing_timestamp: first_block.timestamp + 1,
+            signing_address: empty_address(),
+        },
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        None,
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        first_block.timestamp + 1,
+    )
+    .expect("block could not be created")
+}
+
+pub fn mint_quote_block(
+    id: &str,
+    mint_block: &BillBlock,
+    mint_keys: &BcrKeys,
+    expiry_timestamp: u64,
+) -> BillBlock {
+    BillBlock::create_block_for_mint_quote(
+        id.to_string(),
+        mint_block,
+        &BillMintQuoteBlockData {
+            mint: identity_public_data_only_node_id(mint_keys.get_public_key()).into(),
+            requester: identity_public_data_only_node_id(
+                BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP)
+                    .unwrap()
+                    .get_public_key(),
+            )
+            .into(),
+            mint_request_block_id: mint_block.id,
+            currency: "sat".to_string(),
+            sum: 4900,
+            expiry_timestamp,
+            signatory: None,
+            signing_timestamp: mint_block.timestamp + 1,
+            signing_address: empty_address(),
+        },
+        mint_keys,
+        None,
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        mint_block.timestamp + 1,
+    )
+    .expect("block could not be created")
+}
+
+pub fn acknowledge_block(id: &str, first_block: &BillBlock) -> BillBlock {
+    BillBlock::create_block_for_acknowledge(
+        id.to_string(),
+        first_block,
+        &BillAcknowledgeBlockData {
+            acknowledger: identity_public_data_only_node_id(
+                BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP)
+                    .unwrap()
+                    .get_public_key(),
+            )
+            .into(),
+            signatory: None,
+            signing_timestamp: first_block.timestamp + 1,
+            signing_address: empty_address(),
+        },
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        None,
+        &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+        first_block.timestamp + 1,
+    )
+    .expect("block could not be created")
+}
+
 pub fn request_to_pay_block(id: &str, first_block: &BillBlock, ts: Option<u64>) -> BillBlock {
     let timestamp = ts.unwrap_or(first_block.timestamp + 1);
     BillBlock::create_block_for_request_to_pay(
@@ -543,6 +698,7 @@ pub fn reject_to_pay_block(id: &str, first_block: &BillBlock) -> BillBlock {
             signatory: None,
             signing_timestamp: first_block.timestamp + 1,
             signing_address: empty_address(),
+            reason: None,
         },
         &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
         None,
@@ -2,7 +2,7 @@ use super::Result;
 use crate::blockchain::Blockchain;
 use crate::blockchain::company::{
     CompanyAddSignatoryBlockData, CompanyBlock, CompanyBlockchain, CompanyCreateBlockData,
-    CompanyRemoveSignatoryBlockData, CompanyUpdateBlockData, SignatoryType,
+    CompanyRemoveSignatoryBlockData, CompanyUpdateBlockData, SignatoryChange, SignatoryType,
 };
 use crate::blockchain::identity::{
     IdentityAddSignatoryBlockData, IdentityBlock, IdentityCreateCompanyBlockData,
@@ -12,6 +12,7 @@ use crate::data::{
     File, OptionalPostalAddress, PostalAddress,
     company::{Company, CompanyKeys},
     contact::{Contact, ContactType},
+    identity::{ControlledIdentity, IdentityType},
 };
 use crate::persistence::company::{CompanyChainStoreApi, CompanyStoreApi};
 use crate::persistence::identity::IdentityChainStoreApi;
@@ -37,6 +38,10 @@ pub trait CompanyServiceApi: Send + Sync {
     /// Get a list of companies
     async fn get_list_of_companies(&self) -> Result<Vec<Company>>;
 
+    /// Lists the identities the local user controls - the personal identity first, followed by
+    /// all companies they're a signatory of, sorted by name
+    async fn get_controlled_node_ids(&self) -> Result<Vec<ControlledIdentity>>;
+
     /// Get a company by id
     async fn get_company_by_id(&self, id: &str) -> Result<Company>;
 
@@ -90,6 +95,10 @@ pub trait CompanyServiceApi: Send + Sync {
         timestamp: u64,
     ) -> Result<()>;
 
+    /// Returns all signatory additions and removals of the given company from the company chain,
+    /// only callable by a current signatory of the company
+    async fn get_signatory_history(&self, id: &str) -> Result<Vec<SignatoryChange>>;
+
     /// Encrypts and saves the given uploaded file, returning the file name, as well as the hash of
     /// the unencrypted file
     async fn encrypt_and_save_uploaded_file(
@@ -139,11 +148,14 @@ impl CompanyService {
         }
     }
 
+    /// If an allowed content type is given, e.g. `image/`, the uploaded file has to be of a
+    /// content type starting with it
     async fn process_upload_file(
         &self,
         upload_id: &Option<String>,
         id: &str,
         public_key: &str,
+        allowed_content_type: Option<&str>,
     ) -> Result<Option<File>> {
         if let Some(upload_id) = upload_id {
             debug!("processing upload file for company {id}: {upload_id:?}");
@@ -152,6 +164,13 @@ impl CompanyService {
                 .read_temp_upload_file(upload_id)
                 .await
                 .map_err(|_| crate::service::Error::NoFileForFileUploadId)?;
+            if let Some(allowed_content_type) = allowed_content_type {
+                let is_allowed = util::file::detect_content_type_for_bytes(file_bytes)
+                    .is_some_and(|content_type| content_type.starts_with(allowed_content_type));
+                if !is_allowed {
+                    return Err(ValidationError::InvalidContentType.into());
+                }
+            }
             let file = self
                 .encrypt_and_save_uploaded_file(file_name, file_bytes, id, public_key)
                 .await?;
@@ -193,6 +212,31 @@ impl CompanyServiceApi for CompanyService {
         Ok(companies)
     }
 
+    async fn get_controlled_node_ids(&self) -> Result<Vec<ControlledIdentity>> {
+        let identity = self.identity_store.get().await?;
+        let mut companies: Vec<ControlledIdentity> = self
+            .store
+            .get_all()
+            .await?
+            .into_iter()
+            .filter(|(_id, (company, _keys))| company.signatories.contains(&identity.node_id))
+            .map(|(id, (company, _keys))| ControlledIdentity {
+                node_id: id,
+                t: IdentityType::Company,
+                name: company.name,
+            })
+            .collect();
+        companies.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut controlled = vec![ControlledIdentity {
+            node_id: identity.node_id,
+            t: IdentityType::Person,
+            name: identity.name,
+        }];
+        controlled.extend(companies);
+        Ok(controlled)
+    }
+
     async fn get_company_and_keys_by_id(&self, id: &str) -> Result<(Company, CompanyKeys)> {
         if !self.store.exists(id).await {
             return Err(crate::service::Error::NotFound);
@@ -221,6 +265,7 @@ impl CompanyServiceApi for CompanyService {
         timestamp: u64,
     ) -> Result<Company> {
         debug!("creating company");
+        util::country::validate_country_code(&postal_address.country)?;
         let keys = BcrKeys::new();
         let private_key = keys.get_private_key_string();
         let public_key = keys.get_public_key();
@@ -234,20 +279,23 @@ impl CompanyServiceApi for CompanyService {
 
         let full_identity = self.identity_store.get_full().await?;
 
-        // Save the files locally with the identity public key
-        let proof_of_registration_file = self
+        // Save the files locally with the identity public key - the logo first, so no file is
+        // saved, if it's not an image
+        let logo_file = self
             .process_upload_file(
-                &proof_of_registration_file_upload_id,
+                &logo_file_upload_id,
                 &id,
                 &full_identity.key_pair.get_public_key(),
+                Some("image/"),
             )
             .await?;
 
-        let logo_file = self
+        let proof_of_registration_file = self
             .process_upload_file(
-                &logo_file_upload_id,
+                &proof_of_registration_file_upload_id,
                 &id,
                 &full_identity.key_pair.get_public_key(),
+                None,
             )
             .await?;
 
@@ -410,6 +458,7 @@ impl CompanyServiceApi for CompanyService {
                 &logo_file_upload_id,
                 id,
                 &full_identity.key_pair.get_public_key(),
+                Some("image/"),
             )
             .await?;
         // only override the picture, if there is a new one
@@ -421,6 +470,7 @@ impl CompanyServiceApi for CompanyService {
                 &proof_of_registration_file_upload_id,
                 id,
                 &full_identity.key_pair.get_public_key(),
+                None,
             )
             .await?;
         // only override the document, if there is a new one
@@ -454,14 +504,13 @@ impl CompanyServiceApi for CompanyService {
             .await?;
         debug!("company with id {id} updated");
 
-        if let Some(upload_id) = logo_file_upload_id {
-            if let Err(e) = self
+        if let Some(upload_id) = logo_file_upload_id
+            && let Err(e) = self
                 .file_upload_store
                 .remove_temp_upload_folder(&upload_id)
                 .await
-            {
-                error!("Error while cleaning up temporary file uploads for {upload_id}: {e}");
-            }
+        {
+            error!("Error while cleaning up temporary file uploads for {upload_id}: {e}");
         }
 
         Ok(())
@@ -630,6 +679,24 @@ impl CompanyServiceApi for CompanyService {
         Ok(())
     }
 
+    async fn get_signatory_history(&self, id: &str) -> Result<Vec<SignatoryChange>> {
+        debug!("getting signatory history for company with id: {id}");
+        if !self.store.exists(id).await {
+            return Err(super::Error::NotFound);
+        }
+        let node_id = self.identity_store.get().await?.node_id;
+        let company = self.store.get(id).await?;
+        if !company.signatories.contains(&node_id) {
+            return Err(super::Error::Validation(
+                ValidationError::CallerMustBeSignatory,
+            ));
+        }
+        let company_keys = self.store.get_key_pair(id).await?;
+        let chain = self.company_blockchain_store.get_chain(id).await?;
+        let changes = chain.get_signatory_changes(&company_keys)?;
+        Ok(changes)
+    }
+
     async fn encrypt_and_save_uploaded_file(
         &self,
         file_name: &str,
@@ -669,9 +736,9 @@ impl CompanyServiceApi for CompanyService {
 pub mod tests {
     use super::*;
     use crate::{
-        blockchain::{Blockchain, identity::IdentityBlockchain},
+        blockchain::{Blockchain, company::SignatoryChangeType, identity::IdentityBlockchain},
         data::identity::IdentityWithAll,
-        service::contact_service::tests::get_baseline_contact,
+        service::{Error, contact_service::tests::get_baseline_contact},
         tests::tests::{
             MockCompanyChainStoreApiMock, MockCompanyStoreApiMock, MockContactStoreApiMock,
             MockFileUploadStoreApiMock, MockIdentityChainStoreApiMock, MockIdentityStoreApiMock,
@@ -683,6 +750,13 @@ pub mod tests {
     use std::collections::HashMap;
     use util::BcrKeys;
 
+    /// The PNG signature, padded to the length needed to detect the content type
+    fn get_png_bytes() -> Vec<u8> {
+        let mut bytes = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        bytes.resize(256, 0);
+        bytes
+    }
+
     fn get_service(
         mock_storage: MockCompanyStoreApiMock,
         mock_file_upload_storage: MockFileUploadStoreApiMock,
@@ -789,6 +863,61 @@ pub mod tests {
         assert_eq!(res.as_ref().unwrap()[0].id, TEST_PUB_KEY_SECP.to_string());
     }
 
+    #[tokio::test]
+    async fn get_controlled_node_ids_baseline() {
+        let (
+            mut storage,
+            file_upload_store,
+            mut identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        ) = get_storages();
+        identity_store.expect_get().returning(|| {
+            let mut identity = empty_identity();
+            identity.node_id = TEST_PUB_KEY_SECP.to_owned();
+            identity.name = "personal".to_owned();
+            Ok(identity)
+        });
+        storage.expect_get_all().returning(|| {
+            let mut map = HashMap::new();
+            let (_, (mut company, keys)) = get_baseline_company_data();
+            company.id = TEST_NODE_ID_SECP.to_owned();
+            map.insert(TEST_NODE_ID_SECP.to_owned(), (company, keys));
+            // the local identity is not a signatory of this company anymore
+            let (_, (mut other_company, other_keys)) = get_baseline_company_data();
+            other_company.id = BcrKeys::new().get_public_key();
+            other_company.signatories = vec![BcrKeys::new().get_public_key()];
+            map.insert(other_company.id.clone(), (other_company, other_keys));
+            Ok(map)
+        });
+        let service = get_service(
+            storage,
+            file_upload_store,
+            identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        );
+
+        let res = service.get_controlled_node_ids().await.unwrap();
+        assert_eq!(
+            res,
+            vec![
+                ControlledIdentity {
+                    node_id: TEST_PUB_KEY_SECP.to_owned(),
+                    t: IdentityType::Person,
+                    name: "personal".to_owned(),
+                },
+                ControlledIdentity {
+                    node_id: TEST_NODE_ID_SECP.to_owned(),
+                    t: IdentityType::Company,
+                    name: "some_name".to_owned(),
+                },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn get_list_of_companies_propagates_persistence_errors() {
         let (
@@ -925,7 +1054,7 @@ pub mod tests {
         });
         file_upload_store
             .expect_read_temp_upload_file()
-            .returning(|_| Ok(("some_file".to_string(), "hello_world".as_bytes().to_vec())));
+            .returning(|_| Ok(("some_file".to_string(), get_png_bytes())));
         file_upload_store
             .expect_remove_temp_upload_folder()
             .returning(|_| Ok(()));
@@ -985,6 +1114,57 @@ pub mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn create_company_fails_if_logo_is_not_an_image() {
+        let (
+            storage,
+            mut file_upload_store,
+            mut identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        ) = get_storages();
+        identity_store.expect_get_full().returning(|| {
+            Ok(IdentityWithAll {
+                identity: empty_identity(),
+                key_pair: BcrKeys::new(),
+            })
+        });
+        file_upload_store
+            .expect_read_temp_upload_file()
+            .returning(|_| Ok(("some_file".to_string(), vec![0; 256])));
+        // no file is saved
+        file_upload_store.expect_save_attached_file().never();
+
+        let service = get_service(
+            storage,
+            file_upload_store,
+            identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        );
+
+        let res = service
+            .create_company(
+                "name".to_string(),
+                Some("AT".to_string()),
+                Some("Vienna".to_string()),
+                empty_address(),
+                "company@example.com".to_string(),
+                Some("some_number".to_string()),
+                Some("2012-01-01".to_string()),
+                Some("some_file_id".to_string()),
+                Some("some_other_file_id".to_string()),
+                1731593928,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidContentType))
+        ));
+    }
+
     #[tokio::test]
     async fn create_company_propagates_persistence_errors() {
         let (
@@ -1033,6 +1213,46 @@ pub mod tests {
         assert!(res.is_err());
     }
 
+    #[tokio::test]
+    async fn create_company_fails_for_invalid_country_code() {
+        let (
+            storage,
+            file_upload_store,
+            identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        ) = get_storages();
+        let service = get_service(
+            storage,
+            file_upload_store,
+            identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        );
+        let mut postal_address = empty_address();
+        postal_address.country = "Austria".to_string();
+        let res = service
+            .create_company(
+                "name".to_string(),
+                Some("AT".to_string()),
+                Some("Vienna".to_string()),
+                postal_address,
+                "company@example.com".to_string(),
+                Some("some_number".to_string()),
+                Some("2012-01-01".to_string()),
+                None,
+                None,
+                1731593928,
+            )
+            .await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidCountryCode(_)))
+        ));
+    }
+
     #[tokio::test]
     async fn edit_company_baseline() {
         let keys = BcrKeys::new();
@@ -1075,7 +1295,7 @@ pub mod tests {
         });
         file_upload_store
             .expect_read_temp_upload_file()
-            .returning(|_| Ok(("some_file".to_string(), "hello_world".as_bytes().to_vec())));
+            .returning(|_| Ok(("some_file".to_string(), get_png_bytes())));
         file_upload_store
             .expect_remove_temp_upload_folder()
             .returning(|_| Ok(()));
@@ -1191,6 +1411,122 @@ pub mod tests {
         assert!(res.is_err());
     }
 
+    #[tokio::test]
+    async fn get_signatory_history_baseline() {
+        let (
+            mut storage,
+            file_upload_store,
+            mut identity_store,
+            contact_store,
+            identity_chain_store,
+            mut company_chain_store,
+        ) = get_storages();
+        let (id, (company, company_keys)) = get_baseline_company_data();
+        let identity_keys = BcrKeys::new();
+        let mut chain = CompanyBlockchain::new(
+            &CompanyCreateBlockData::from(company),
+            &identity_keys,
+            &company_keys,
+            1731593928,
+        )
+        .unwrap();
+        let add_block = CompanyBlock::create_block_for_add_signatory(
+            id.clone(),
+            chain.get_latest_block(),
+            &CompanyAddSignatoryBlockData {
+                signatory: "new_signatory".to_string(),
+                t: SignatoryType::Solo,
+            },
+            &identity_keys,
+            &company_keys,
+            TEST_PUB_KEY_SECP,
+            1731593929,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(add_block));
+        let remove_block = CompanyBlock::create_block_for_remove_signatory(
+            id.clone(),
+            chain.get_latest_block(),
+            &CompanyRemoveSignatoryBlockData {
+                signatory: "new_signatory".to_string(),
+            },
+            &identity_keys,
+            &company_keys,
+            1731593930,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(remove_block));
+
+        storage.expect_exists().returning(|_| true);
+        storage
+            .expect_get()
+            .returning(|_| Ok(get_baseline_company_data().1.0));
+        storage
+            .expect_get_key_pair()
+            .returning(|_| Ok(get_baseline_company_data().1.1));
+        identity_store.expect_get().returning(|| {
+            let mut identity = empty_identity();
+            identity.node_id = TEST_PUB_KEY_SECP.to_owned();
+            Ok(identity)
+        });
+        company_chain_store
+            .expect_get_chain()
+            .returning(move |_| Ok(chain.clone()));
+        let service = get_service(
+            storage,
+            file_upload_store,
+            identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        );
+        let res = service.get_signatory_history(&id).await.unwrap();
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[1].change_type, SignatoryChangeType::Add);
+        assert_eq!(res[1].signatory, "new_signatory".to_string());
+        assert_eq!(res[1].changed_by, identity_keys.get_public_key());
+        assert_eq!(res[2].change_type, SignatoryChangeType::Remove);
+        assert_eq!(res[2].signatory, "new_signatory".to_string());
+        assert_eq!(res[2].timestamp, 1731593930);
+    }
+
+    #[tokio::test]
+    async fn get_signatory_history_fails_if_caller_is_not_signatory() {
+        let (
+            mut storage,
+            file_upload_store,
+            mut identity_store,
+            contact_store,
+            identity_chain_store,
+            mut company_chain_store,
+        ) = get_storages();
+        storage.expect_exists().returning(|_| true);
+        storage.expect_get().returning(|_| {
+            let mut data = get_baseline_company_data().1.0;
+            data.signatories = vec!["some_other_dude".to_string()];
+            Ok(data)
+        });
+        identity_store.expect_get().returning(|| {
+            let mut identity = empty_identity();
+            identity.node_id = TEST_PUB_KEY_SECP.to_owned();
+            Ok(identity)
+        });
+        company_chain_store.expect_get_chain().never();
+        let service = get_service(
+            storage,
+            file_upload_store,
+            identity_store,
+            contact_store,
+            identity_chain_store,
+            company_chain_store,
+        );
+        let res = service.get_signatory_history("some_id").await;
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::CallerMustBeSignatory))
+        ));
+    }
+
     #[tokio::test]
     async fn edit_company_propagates_persistence_errors() {
         let (
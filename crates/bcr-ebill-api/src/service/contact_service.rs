@@ -1,7 +1,9 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use bcr_ebill_core::ValidationError;
+use bcr_ebill_core::{ServiceTraitBounds, ValidationError};
+use bcr_ebill_transport::NotificationJsonTransportApi;
 #[cfg(test)]
 use mockall::automock;
 
@@ -18,11 +20,46 @@ use crate::{
 };
 
 use super::Result;
-use log::{debug, info};
+use log::{debug, info, warn};
+
+/// A contact after refreshing its Nostr metadata
+#[derive(Debug, Clone)]
+pub struct RefreshedContact {
+    pub contact: Contact,
+    /// Set, if no profile of the contact could be found, so the contact might be outdated
+    pub stale: bool,
+}
+
+/// The result of importing contacts from a CSV file
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub imported: Vec<ImportedRow>,
+    pub rejected: Vec<RejectedRow>,
+}
+
+/// A row of an imported file, which was imported as a contact
+#[derive(Debug, Clone)]
+pub struct ImportedRow {
+    /// The number of the row in the file, starting with 1 for the header row
+    pub row: usize,
+    pub contact: Contact,
+}
+
+/// A row of an imported file, which was not imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedRow {
+    /// The number of the row in the file, starting with 1 for the header row
+    pub row: usize,
+    pub reason: String,
+}
+
+#[cfg(test)]
+impl ServiceTraitBounds for MockContactServiceApi {}
 
 #[cfg_attr(test, automock)]
-#[async_trait]
-pub trait ContactServiceApi: Send + Sync {
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait ContactServiceApi: ServiceTraitBounds {
     /// Searches contacts for the search term
     async fn search(&self, search_term: &str) -> Result<Vec<Contact>>;
     /// Returns all contacts in short form
@@ -68,6 +105,34 @@ pub trait ContactServiceApi: Send + Sync {
         proof_document_file_upload_id: Option<String>,
     ) -> Result<Contact>;
 
+    /// Merges the contact with `merge_node_id` into the contact with `keep_node_id`, filling
+    /// missing data and files of the kept contact from the merged one, and deletes the merged
+    /// contact. Only contacts that are stored under the same node id in a different notation
+    /// can be merged, since bills reference their participants by node id.
+    async fn merge_contacts(&self, keep_node_id: &str, merge_node_id: &str) -> Result<()>;
+
+    /// Returns groups of contacts, which are likely duplicates, because they share the same
+    /// node id, or the same name
+    async fn find_duplicate_contacts(&self) -> Result<Vec<Vec<Contact>>>;
+
+    /// Imports the contacts from the given vCard file and returns the imported contacts. Entries
+    /// without a resolvable node id (see `util::vcard::NODE_ID_PROPERTY`) and entries for
+    /// already existing contacts are skipped.
+    async fn import_vcard(&self, bytes: &[u8]) -> Result<Vec<Contact>>;
+
+    /// Imports the contacts from the given CSV file (see `util::csv::parse_contacts_csv`). Valid
+    /// rows are imported, even if other rows are rejected, because they are invalid, or because
+    /// the contact already exists. Fails only, if a required column is missing.
+    async fn import_contacts_csv(&self, bytes: &[u8]) -> Result<ImportReport>;
+
+    /// Exports the contacts with the given node ids as a vCard file
+    async fn export_vcard(&self, node_ids: &[String]) -> Result<Vec<u8>>;
+
+    /// Fetches the latest Nostr profile of the contact with the given node_id and updates the
+    /// stored relays of the contact. If no profile can be found, the stored contact is returned
+    /// unchanged and marked as stale.
+    async fn refresh_contact_metadata(&self, node_id: &str) -> Result<RefreshedContact>;
+
     /// Returns whether a given npub (as hex) is in our contact list.
     #[allow(dead_code)]
     async fn is_known_npub(&self, npub: &str) -> Result<bool>;
@@ -87,6 +152,7 @@ pub struct ContactService {
     store: Arc<dyn ContactStoreApi>,
     file_upload_store: Arc<dyn FileUploadStoreApi>,
     identity_store: Arc<dyn IdentityStoreApi>,
+    transports: Vec<Arc<dyn NotificationJsonTransportApi>>,
 }
 
 impl ContactService {
@@ -94,11 +160,13 @@ impl ContactService {
         store: Arc<dyn ContactStoreApi>,
         file_upload_store: Arc<dyn FileUploadStoreApi>,
         identity_store: Arc<dyn IdentityStoreApi>,
+        transports: Vec<Arc<dyn NotificationJsonTransportApi>>,
     ) -> Self {
         Self {
             store,
             file_upload_store,
             identity_store,
+            transports,
         }
     }
 
@@ -143,7 +211,22 @@ impl ContactService {
     }
 }
 
-#[async_trait]
+/// Node ids are hex encoded public keys, so they are compared case-insensitively
+fn normalize_node_id(node_id: &str) -> String {
+    node_id.trim().to_lowercase()
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+impl ServiceTraitBounds for ContactService {}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl ContactServiceApi for ContactService {
     async fn search(&self, search_term: &str) -> Result<Vec<Contact>> {
         let contacts = self.store.search(search_term).await?;
@@ -303,6 +386,7 @@ impl ContactServiceApi for ContactService {
                 ValidationError::InvalidSecp256k1Key(node_id.to_owned()),
             ));
         }
+        util::country::validate_country_code(&postal_address.country)?;
 
         let identity_public_key = self.identity_store.get_key_pair().await?.get_public_key();
         let avatar_file = self
@@ -329,7 +413,7 @@ impl ContactServiceApi for ContactService {
             identification_number,
             avatar_file,
             proof_document_file,
-            nostr_relays: vec![get_config().nostr_relay.clone()], // Use the configured relay for now
+            nostr_relays: get_config().nostr_relays.clone(), // Use the configured relays for now
         };
 
         self.store.insert(node_id, contact.clone()).await?;
@@ -337,6 +421,241 @@ impl ContactServiceApi for ContactService {
         Ok(contact)
     }
 
+    async fn merge_contacts(&self, keep_node_id: &str, merge_node_id: &str) -> Result<()> {
+        debug!("merging contact {merge_node_id} into {keep_node_id}");
+        if keep_node_id == merge_node_id {
+            return Ok(());
+        }
+        let (mut contact, merged) = match (
+            self.store.get(keep_node_id).await?,
+            self.store.get(merge_node_id).await?,
+        ) {
+            (Some(contact), Some(merged)) => (contact, merged),
+            _ => return Err(super::Error::NotFound),
+        };
+        if normalize_node_id(&contact.node_id) != normalize_node_id(&merged.node_id) {
+            return Err(super::Error::Validation(
+                ValidationError::ContactsHaveDifferentNodeIds,
+            ));
+        }
+
+        if contact.email.trim().is_empty() {
+            contact.email = merged.email;
+        }
+        contact.date_of_birth_or_registration = contact
+            .date_of_birth_or_registration
+            .or(merged.date_of_birth_or_registration);
+        contact.country_of_birth_or_registration = contact
+            .country_of_birth_or_registration
+            .or(merged.country_of_birth_or_registration);
+        contact.city_of_birth_or_registration = contact
+            .city_of_birth_or_registration
+            .or(merged.city_of_birth_or_registration);
+        contact.identification_number = contact
+            .identification_number
+            .or(merged.identification_number);
+        for relay in merged.nostr_relays {
+            if !contact.nostr_relays.contains(&relay) {
+                contact.nostr_relays.push(relay);
+            }
+        }
+
+        // move the files of the merged contact over, if the kept contact doesn't have them
+        for (file, merged_file) in [
+            (&mut contact.avatar_file, merged.avatar_file),
+            (&mut contact.proof_document_file, merged.proof_document_file),
+        ] {
+            if let (true, Some(merged_file)) = (file.is_none(), merged_file) {
+                let encrypted = self
+                    .file_upload_store
+                    .open_attached_file(merge_node_id, &merged_file.name)
+                    .await?;
+                self.file_upload_store
+                    .save_attached_file(&encrypted, keep_node_id, &merged_file.name)
+                    .await?;
+                *file = Some(merged_file);
+            }
+        }
+
+        self.store.update(keep_node_id, contact).await?;
+        self.file_upload_store
+            .delete_attached_files(merge_node_id)
+            .await?;
+        self.store.delete(merge_node_id).await?;
+        info!("merged contact {merge_node_id} into {keep_node_id}");
+        Ok(())
+    }
+
+    async fn find_duplicate_contacts(&self) -> Result<Vec<Vec<Contact>>> {
+        let mut contacts: Vec<Contact> = self.store.get_map().await?.into_values().collect();
+        contacts.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+
+        let mut by_node_id: HashMap<String, Vec<Contact>> = HashMap::new();
+        let mut by_name: HashMap<String, Vec<Contact>> = HashMap::new();
+        for contact in contacts {
+            by_node_id
+                .entry(normalize_node_id(&contact.node_id))
+                .or_default()
+                .push(contact.clone());
+            by_name
+                .entry(normalize_name(&contact.name))
+                .or_default()
+                .push(contact);
+        }
+
+        let mut duplicates: Vec<Vec<Contact>> = vec![];
+        for group in by_node_id.into_values().chain(by_name.into_values()) {
+            // a group can be found both by node id and by name
+            let is_known_group = duplicates.iter().any(|d| {
+                d.len() == group.len()
+                    && d.iter()
+                        .zip(group.iter())
+                        .all(|(a, b)| a.node_id == b.node_id)
+            });
+            if group.len() > 1 && !is_known_group {
+                duplicates.push(group);
+            }
+        }
+        duplicates.sort_by(|a, b| a[0].node_id.cmp(&b[0].node_id));
+        Ok(duplicates)
+    }
+
+    async fn import_vcard(&self, bytes: &[u8]) -> Result<Vec<Contact>> {
+        let parsed = util::vcard::parse_vcards(&String::from_utf8_lossy(bytes));
+        let existing = self.store.get_map().await?;
+
+        let mut imported = vec![];
+        let mut already_existing = 0;
+        for mut contact in parsed.contacts {
+            if existing.contains_key(&contact.node_id)
+                || imported
+                    .iter()
+                    .any(|c: &Contact| c.node_id == contact.node_id)
+            {
+                already_existing += 1;
+                continue;
+            }
+            if contact.nostr_relays.is_empty() {
+                contact.nostr_relays = get_config().nostr_relays.clone(); // Use the configured relays for now
+            }
+            self.store.insert(&contact.node_id, contact.clone()).await?;
+            imported.push(contact);
+        }
+        info!(
+            "imported {} contacts from vCard, skipped {} entries without a resolvable node id and {already_existing} existing contacts",
+            imported.len(),
+            parsed.skipped
+        );
+        Ok(imported)
+    }
+
+    async fn import_contacts_csv(&self, bytes: &[u8]) -> Result<ImportReport> {
+        let rows = util::csv::parse_contacts_csv(&String::from_utf8_lossy(bytes))?;
+        let existing = self.store.get_map().await?;
+
+        let mut report = ImportReport::default();
+        for row in rows {
+            let mut contact = match row.contact {
+                Ok(contact) => contact,
+                Err(reason) => {
+                    report.rejected.push(RejectedRow {
+                        row: row.row,
+                        reason,
+                    });
+                    continue;
+                }
+            };
+            if existing.contains_key(&contact.node_id)
+                || report
+                    .imported
+                    .iter()
+                    .any(|i| i.contact.node_id == contact.node_id)
+            {
+                report.rejected.push(RejectedRow {
+                    row: row.row,
+                    reason: format!("Contact {} already exists", contact.node_id),
+                });
+                continue;
+            }
+            contact.nostr_relays = get_config().nostr_relays.clone(); // Use the configured relays for now
+            if let Err(e) = self.store.insert(&contact.node_id, contact.clone()).await {
+                warn!("Could not import contact from CSV row {}: {e}", row.row);
+                report.rejected.push(RejectedRow {
+                    row: row.row,
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+            report.imported.push(ImportedRow {
+                row: row.row,
+                contact,
+            });
+        }
+        info!(
+            "imported {} contacts from CSV, rejected {} rows",
+            report.imported.len(),
+            report.rejected.len()
+        );
+        Ok(report)
+    }
+
+    async fn export_vcard(&self, node_ids: &[String]) -> Result<Vec<u8>> {
+        let mut contacts = vec![];
+        for node_id in node_ids {
+            match self.store.get(node_id).await? {
+                Some(contact) => contacts.push(contact),
+                None => return Err(super::Error::NotFound),
+            }
+        }
+        debug!("exporting {} contacts as vCard", contacts.len());
+        Ok(util::vcard::contacts_to_vcard(&contacts).into_bytes())
+    }
+
+    async fn refresh_contact_metadata(&self, node_id: &str) -> Result<RefreshedContact> {
+        debug!("refreshing metadata of contact {node_id}");
+        let mut contact = match self.store.get(node_id).await? {
+            Some(contact) => contact,
+            None => {
+                return Err(super::Error::NotFound);
+            }
+        };
+
+        // all transports query the same relays, so we only try the next one, if a fetch failed
+        let mut profile = None;
+        for transport in self.transports.iter() {
+            match transport.fetch_profile(node_id).await {
+                Ok(fetched) => {
+                    profile = fetched;
+                    break;
+                }
+                Err(e) => warn!("Could not fetch Nostr profile of contact {node_id}: {e}"),
+            }
+        }
+
+        let profile = match profile {
+            Some(profile) => profile,
+            None => {
+                info!("No Nostr profile found for contact {node_id} - contact might be stale");
+                return Ok(RefreshedContact {
+                    contact,
+                    stale: true,
+                });
+            }
+        };
+
+        // the name is managed locally by the user, so we only take the relays from the profile
+        if !profile.relays.is_empty() && profile.relays != contact.nostr_relays {
+            contact.nostr_relays = profile.relays;
+            self.store.update(node_id, contact.clone()).await?;
+            debug!("updated relays of contact {node_id}");
+        }
+
+        Ok(RefreshedContact {
+            contact,
+            stale: false,
+        })
+    }
+
     async fn is_known_npub(&self, npub: &str) -> Result<bool> {
         let node_id_list: Vec<String> = self.store.get_map().await?.into_keys().collect();
         Ok(node_id_list
@@ -365,9 +684,11 @@ pub mod tests {
     use super::*;
     use crate::tests::tests::{
         MockContactStoreApiMock, MockFileUploadStoreApiMock, MockIdentityStoreApiMock,
-        TEST_NODE_ID_SECP, TEST_NODE_ID_SECP_AS_NPUB_HEX, empty_address, empty_optional_address,
-        init_test_cfg,
+        MockNotificationJsonTransport, TEST_NODE_ID_SECP, TEST_NODE_ID_SECP_AS_NPUB_HEX,
+        empty_address, empty_optional_address, init_test_cfg,
     };
+    use bcr_ebill_transport::NostrProfile;
+    use mockall::predicate::eq;
     use std::collections::HashMap;
     use util::BcrKeys;
 
@@ -392,11 +713,26 @@ pub mod tests {
         mock_storage: MockContactStoreApiMock,
         mock_file_upload_storage: MockFileUploadStoreApiMock,
         mock_identity_storage: MockIdentityStoreApiMock,
+    ) -> ContactService {
+        get_service_with_transport(
+            mock_storage,
+            mock_file_upload_storage,
+            mock_identity_storage,
+            MockNotificationJsonTransport::new(),
+        )
+    }
+
+    fn get_service_with_transport(
+        mock_storage: MockContactStoreApiMock,
+        mock_file_upload_storage: MockFileUploadStoreApiMock,
+        mock_identity_storage: MockIdentityStoreApiMock,
+        mock_transport: MockNotificationJsonTransport,
     ) -> ContactService {
         ContactService::new(
             Arc::new(mock_storage),
             Arc::new(mock_file_upload_storage),
             Arc::new(mock_identity_storage),
+            vec![Arc::new(mock_transport)],
         )
     }
 
@@ -512,6 +848,36 @@ pub mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn add_contact_fails_for_invalid_country_code() {
+        init_test_cfg();
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        store.expect_insert().never();
+        let mut postal_address = empty_address();
+        postal_address.country = "deutschland".to_string();
+        let result = get_service(store, file_upload_store, identity_store)
+            .add_contact(
+                TEST_NODE_ID_SECP,
+                ContactType::Person,
+                "some_name".to_string(),
+                "some_email@example.com".to_string(),
+                postal_address,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::service::Error::Validation(
+                ValidationError::InvalidCountryCode(_)
+            ))
+        ));
+    }
+
     #[tokio::test]
     async fn is_known_npub_calls_store() {
         let (mut store, file_upload_store, identity_store) = get_storages();
@@ -527,4 +893,383 @@ pub mod tests {
         assert!(result.is_ok());
         assert!(result.as_ref().unwrap());
     }
+
+    #[tokio::test]
+    async fn merge_contacts_baseline() {
+        let (mut store, mut file_upload_store, identity_store) = get_storages();
+        let merge_node_id = TEST_NODE_ID_SECP.to_uppercase();
+        let merge_node_id_clone = merge_node_id.clone();
+        store
+            .expect_get()
+            .with(eq(TEST_NODE_ID_SECP))
+            .returning(|_| Ok(Some(get_baseline_contact())));
+        store
+            .expect_get()
+            .with(eq(merge_node_id.clone()))
+            .returning(move |_| {
+                let mut contact = get_baseline_contact();
+                contact.node_id = merge_node_id_clone.clone();
+                contact.name = "Some  Name".to_string();
+                contact.identification_number = Some("123456".to_string());
+                contact.avatar_file = Some(File {
+                    name: "avatar.png".to_string(),
+                    hash: "some_hash".to_string(),
+                });
+                contact.nostr_relays = vec!["ws://localhost:8080".to_string()];
+                Ok(Some(contact))
+            });
+        file_upload_store
+            .expect_open_attached_file()
+            .with(eq(merge_node_id.clone()), eq("avatar.png"))
+            .returning(|_, _| Ok(vec![1, 2, 3]))
+            .times(1);
+        file_upload_store
+            .expect_save_attached_file()
+            .withf(|bytes, id, file_name| {
+                bytes == [1, 2, 3] && id == TEST_NODE_ID_SECP && file_name == "avatar.png"
+            })
+            .returning(|_, _, _| Ok(()))
+            .times(1);
+        file_upload_store
+            .expect_delete_attached_files()
+            .with(eq(merge_node_id.clone()))
+            .returning(|_| Ok(()))
+            .times(1);
+        store
+            .expect_update()
+            .withf(|node_id, contact| {
+                node_id == TEST_NODE_ID_SECP
+                    && contact.name == "some_name"
+                    && contact.identification_number == Some("123456".to_string())
+                    && contact.avatar_file.as_ref().map(|f| f.name.as_str()) == Some("avatar.png")
+                    && contact.nostr_relays == vec!["ws://localhost:8080".to_string()]
+            })
+            .returning(|_, _| Ok(()))
+            .times(1);
+        store
+            .expect_delete()
+            .with(eq(merge_node_id.clone()))
+            .returning(|_| Ok(()))
+            .times(1);
+
+        let result = get_service(store, file_upload_store, identity_store)
+            .merge_contacts(TEST_NODE_ID_SECP, &merge_node_id)
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn merge_contacts_rejects_different_node_id_referenced_by_active_bill() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        // the other contact has the same name, but a different node id, with which it's
+        // referenced as a participant in an active bill - merging it would orphan that reference
+        let bill_participant_node_id = BcrKeys::new().get_public_key();
+        let bill_participant_node_id_clone = bill_participant_node_id.clone();
+        store
+            .expect_get()
+            .with(eq(TEST_NODE_ID_SECP))
+            .returning(|_| Ok(Some(get_baseline_contact())));
+        store
+            .expect_get()
+            .with(eq(bill_participant_node_id.clone()))
+            .returning(move |_| {
+                let mut contact = get_baseline_contact();
+                contact.node_id = bill_participant_node_id_clone.clone();
+                Ok(Some(contact))
+            });
+        // no update, or delete expected
+
+        let service = get_service(store, file_upload_store, identity_store);
+        let result = service
+            .merge_contacts(TEST_NODE_ID_SECP, &bill_participant_node_id)
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::service::Error::Validation(
+                ValidationError::ContactsHaveDifferentNodeIds
+            ))
+        ));
+        let result = service
+            .merge_contacts(&bill_participant_node_id, TEST_NODE_ID_SECP)
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::service::Error::Validation(
+                ValidationError::ContactsHaveDifferentNodeIds
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn merge_contacts_fails_if_contact_does_not_exist() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        store
+            .expect_get()
+            .with(eq(TEST_NODE_ID_SECP))
+            .returning(|_| Ok(Some(get_baseline_contact())));
+        store
+            .expect_get()
+            .with(eq(TEST_NODE_ID_SECP.to_uppercase()))
+            .returning(|_| Ok(None));
+        let result = get_service(store, file_upload_store, identity_store)
+            .merge_contacts(TEST_NODE_ID_SECP, &TEST_NODE_ID_SECP.to_uppercase())
+            .await;
+        assert!(matches!(result, Err(crate::service::Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn find_duplicate_contacts_groups_by_node_id_and_name() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        let other_node_id = BcrKeys::new().get_public_key();
+        let unrelated_node_id = BcrKeys::new().get_public_key();
+        let other_node_id_clone = other_node_id.clone();
+        let unrelated_node_id_clone = unrelated_node_id.clone();
+        store.expect_get_map().returning(move || {
+            let mut map = HashMap::new();
+            // same node id in a different notation
+            let mut upper = get_baseline_contact();
+            upper.node_id = TEST_NODE_ID_SECP.to_uppercase();
+            upper.name = "another name".to_string();
+            map.insert(upper.node_id.clone(), upper);
+            map.insert(TEST_NODE_ID_SECP.to_string(), get_baseline_contact());
+            // same name with different spacing and casing
+            let mut same_name = get_baseline_contact();
+            same_name.node_id = other_node_id_clone.clone();
+            same_name.name = " Some_Name ".to_string();
+            map.insert(same_name.node_id.clone(), same_name);
+            let mut unrelated = get_baseline_contact();
+            unrelated.node_id = unrelated_node_id_clone.clone();
+            unrelated.name = "unrelated".to_string();
+            map.insert(unrelated.node_id.clone(), unrelated);
+            Ok(map)
+        });
+        let result = get_service(store, file_upload_store, identity_store)
+            .find_duplicate_contacts()
+            .await;
+        assert!(result.is_ok());
+        let duplicates = result.unwrap();
+        assert_eq!(duplicates.len(), 2);
+        let mut groups: Vec<Vec<String>> = duplicates
+            .iter()
+            .map(|group| group.iter().map(|c| c.node_id.clone()).collect())
+            .collect();
+        groups.iter_mut().for_each(|group| group.sort());
+        let mut expected_by_node_id = vec![
+            TEST_NODE_ID_SECP.to_string(),
+            TEST_NODE_ID_SECP.to_uppercase(),
+        ];
+        expected_by_node_id.sort();
+        let mut expected_by_name = vec![TEST_NODE_ID_SECP.to_string(), other_node_id.clone()];
+        expected_by_name.sort();
+        assert!(groups.contains(&expected_by_node_id));
+        assert!(groups.contains(&expected_by_name));
+        assert!(
+            !groups
+                .iter()
+                .any(|group| group.contains(&unrelated_node_id))
+        );
+    }
+
+    #[tokio::test]
+    async fn import_vcard_inserts_new_contacts_and_skips_existing_ones() {
+        init_test_cfg();
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        let new_node_id = BcrKeys::new().get_public_key();
+        let mut new_contact = get_baseline_contact();
+        new_contact.node_id = new_node_id.clone();
+        new_contact.name = "new contact".to_string();
+        let vcard = util::vcard::contacts_to_vcard(&[get_baseline_contact(), new_contact.clone()]);
+        store.expect_get_map().returning(|| {
+            let mut map = HashMap::new();
+            map.insert(TEST_NODE_ID_SECP.to_string(), get_baseline_contact());
+            Ok(map)
+        });
+        store
+            .expect_insert()
+            .with(eq(new_node_id.clone()), mockall::predicate::always())
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let result = get_service(store, file_upload_store, identity_store)
+            .import_vcard(vcard.as_bytes())
+            .await;
+        assert!(result.is_ok());
+        let imported = result.unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].node_id, new_node_id);
+        assert_eq!(imported[0].name, "new contact");
+        assert_eq!(imported[0].nostr_relays, get_config().nostr_relays);
+    }
+
+    #[tokio::test]
+    async fn import_contacts_csv_imports_valid_rows_and_reports_rejected_ones() {
+        init_test_cfg();
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        let new_node_id = BcrKeys::new().get_public_key();
+        let csv = format!(
+            "name,node_id,email,country,city,zip,address\n\
+            New Contact,{new_node_id},new@example.com,AT,Vienna,1010,Street 1\n\
+            Existing Contact,{TEST_NODE_ID_SECP},existing@example.com,AT,Vienna,,Street 2\n\
+            Invalid Node Id,not a key,invalid@example.com,AT,Vienna,,Street 3\n\
+            \n\
+            Missing City,{},missing@example.com,AT,,,Street 4\n\
+            Too Few Columns,{}\n\
+            Duplicate Row,{new_node_id},new@example.com,AT,Vienna,1010,Street 1\n",
+            BcrKeys::new().get_public_key(),
+            BcrKeys::new().get_public_key(),
+        );
+        store.expect_get_map().returning(|| {
+            let mut map = HashMap::new();
+            map.insert(TEST_NODE_ID_SECP.to_string(), get_baseline_contact());
+            Ok(map)
+        });
+        store
+            .expect_insert()
+            .with(eq(new_node_id.clone()), mockall::predicate::always())
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let report = get_service(store, file_upload_store, identity_store)
+            .import_contacts_csv(csv.as_bytes())
+            .await
+            .unwrap();
+
+        assert_eq!(report.imported.len(), 1);
+        assert_eq!(report.imported[0].row, 2);
+        assert_eq!(report.imported[0].contact.node_id, new_node_id);
+        assert_eq!(report.imported[0].contact.name, "New Contact");
+        assert_eq!(
+            report.imported[0].contact.postal_address.zip,
+            Some("1010".to_string())
+        );
+        assert_eq!(
+            report.imported[0].contact.nostr_relays,
+            get_config().nostr_relays
+        );
+
+        // the empty row 5 is skipped
+        let rejected_rows: Vec<usize> = report.rejected.iter().map(|r| r.row).collect();
+        assert_eq!(rejected_rows, vec![3, 4, 6, 7, 8]);
+        assert!(report.rejected[0].reason.contains("already exists"));
+        assert_eq!(
+            report.rejected[1].reason,
+            ValidationError::InvalidSecp256k1Key("not a key".to_string()).to_string()
+        );
+        assert_eq!(
+            report.rejected[2].reason,
+            ValidationError::FieldEmpty(bcr_ebill_core::Field::City).to_string()
+        );
+        assert!(report.rejected[3].reason.contains("columns"));
+        assert!(report.rejected[4].reason.contains("already exists"));
+    }
+
+    #[tokio::test]
+    async fn import_contacts_csv_fails_if_column_is_missing() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        store.expect_insert().never();
+        let result = get_service(store, file_upload_store, identity_store)
+            .import_contacts_csv(b"name,email\nJane,jane@example.com\n")
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::service::Error::Validation(
+                ValidationError::MissingCsvColumn(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn export_vcard_roundtrip() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        store
+            .expect_get()
+            .with(eq(TEST_NODE_ID_SECP))
+            .returning(|_| Ok(Some(get_baseline_contact())));
+        let result = get_service(store, file_upload_store, identity_store)
+            .export_vcard(&[TEST_NODE_ID_SECP.to_string()])
+            .await;
+        assert!(result.is_ok());
+        let exported = String::from_utf8(result.unwrap()).unwrap();
+        let parsed = util::vcard::parse_vcards(&exported);
+        assert_eq!(parsed.skipped, 0);
+        assert_eq!(parsed.contacts.len(), 1);
+        let contact = get_baseline_contact();
+        assert_eq!(parsed.contacts[0].node_id, contact.node_id);
+        assert_eq!(parsed.contacts[0].name, contact.name);
+        assert_eq!(parsed.contacts[0].email, contact.email);
+        assert_eq!(parsed.contacts[0].postal_address, contact.postal_address);
+    }
+
+    #[tokio::test]
+    async fn export_vcard_fails_if_contact_does_not_exist() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        store.expect_get().returning(|_| Ok(None));
+        let result = get_service(store, file_upload_store, identity_store)
+            .export_vcard(&[TEST_NODE_ID_SECP.to_string()])
+            .await;
+        assert!(matches!(result, Err(crate::service::Error::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn refresh_contact_metadata_updates_relays() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        let mut transport = MockNotificationJsonTransport::new();
+        store
+            .expect_get()
+            .returning(|_| Ok(Some(get_baseline_contact())));
+        transport
+            .expect_fetch_profile()
+            .with(eq(TEST_NODE_ID_SECP))
+            .returning(|_| {
+                Ok(Some(NostrProfile {
+                    name: Some("some_name".to_string()),
+                    relays: vec!["wss://relay.example.com".to_string()],
+                }))
+            });
+        store
+            .expect_update()
+            .withf(|node_id, contact| {
+                node_id == TEST_NODE_ID_SECP
+                    && contact.nostr_relays == vec!["wss://relay.example.com".to_string()]
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let result =
+            get_service_with_transport(store, file_upload_store, identity_store, transport)
+                .refresh_contact_metadata(TEST_NODE_ID_SECP)
+                .await
+                .unwrap();
+        assert!(!result.stale);
+        assert_eq!(
+            result.contact.nostr_relays,
+            vec!["wss://relay.example.com".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_contact_metadata_returns_stale_contact_if_no_profile_found() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        let mut transport = MockNotificationJsonTransport::new();
+        store
+            .expect_get()
+            .returning(|_| Ok(Some(get_baseline_contact())));
+        transport.expect_fetch_profile().returning(|_| Ok(None));
+        store.expect_update().never();
+        let result =
+            get_service_with_transport(store, file_upload_store, identity_store, transport)
+                .refresh_contact_metadata(TEST_NODE_ID_SECP)
+                .await
+                .unwrap();
+        assert!(result.stale);
+        assert_eq!(result.contact.node_id, TEST_NODE_ID_SECP);
+        assert!(result.contact.nostr_relays.is_empty());
+    }
+
+    #[tokio::test]
+    async fn refresh_contact_metadata_fails_if_contact_does_not_exist() {
+        let (mut store, file_upload_store, identity_store) = get_storages();
+        store.expect_get().returning(|_| Ok(None));
+        let result = get_service(store, file_upload_store, identity_store)
+            .refresh_contact_metadata(TEST_NODE_ID_SECP)
+            .await;
+        assert!(matches!(result, Err(crate::service::Error::NotFound)));
+    }
 }
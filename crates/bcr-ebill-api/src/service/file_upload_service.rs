@@ -21,6 +21,10 @@ pub trait FileUploadServiceApi: Send + Sync {
 
     /// returns a temp upload file
     async fn get_temp_file(&self, file_upload_id: &str) -> Result<Option<(String, Vec<u8>)>>;
+
+    /// removes temp uploads, which haven't been used within the given ttl, e.g. because the
+    /// upload was abandoned
+    async fn cleanup_stale_temp_uploads(&self, ttl_seconds: u64) -> Result<()>;
 }
 
 #[derive(Clone)]
@@ -108,12 +112,31 @@ impl FileUploadServiceApi for FileUploadService {
         let (file_name, file_bytes) = file;
         return Ok(Some((file_name, file_bytes)));
     }
+
+    async fn cleanup_stale_temp_uploads(&self, ttl_seconds: u64) -> Result<()> {
+        let stale_uploads = self
+            .file_upload_store
+            .list_temp_upload_folders_older_than(ttl_seconds)
+            .await?;
+        for file_upload_id in stale_uploads.iter() {
+            debug!("removing stale temp upload {file_upload_id}");
+            if let Err(e) = self
+                .file_upload_store
+                .remove_temp_upload_folder(file_upload_id)
+                .await
+            {
+                error!("Could not remove stale temp upload {file_upload_id}: {e}");
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::tests::tests::MockFileUploadStoreApiMock;
+    use mockall::predicate::eq;
     use std::sync::Arc;
     use util::file::MockUploadFileHandler;
 
@@ -360,4 +383,38 @@ mod tests {
         let res = service.get_temp_file("1234").await;
         assert!(res.is_err());
     }
+
+    #[tokio::test]
+    async fn cleanup_stale_temp_uploads_removes_only_stale_uploads() {
+        let mut storage = MockFileUploadStoreApiMock::new();
+        storage
+            .expect_list_temp_upload_folders_older_than()
+            .with(eq(3600))
+            .returning(|_| Ok(vec!["old_id".to_string()]));
+        storage
+            .expect_remove_temp_upload_folder()
+            .with(eq("old_id"))
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = get_service(storage);
+
+        let res = service.cleanup_stale_temp_uploads(3600).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn cleanup_stale_temp_uploads_continues_on_removal_errors() {
+        let mut storage = MockFileUploadStoreApiMock::new();
+        storage
+            .expect_list_temp_upload_folders_older_than()
+            .returning(|_| Ok(vec!["old_id".to_string(), "other_old_id".to_string()]));
+        storage
+            .expect_remove_temp_upload_folder()
+            .times(2)
+            .returning(|_| Err(persistence::Error::Io(std::io::Error::other("test error"))));
+        let service = get_service(storage);
+
+        let res = service.cleanup_stale_temp_uploads(3600).await;
+        assert!(res.is_ok());
+    }
 }
@@ -0,0 +1,199 @@
+#![cfg(not(target_arch = "wasm32"))]
+use std::{sync::Arc, time::Duration};
+
+use crate::persistence::backup::BackupStoreApi;
+use bcr_ebill_transport::NotificationServiceApi;
+use log::warn;
+#[cfg(test)]
+use mockall::automock;
+use tokio::time::timeout;
+use tokio_with_wasm::alias as tokio;
+
+/// The state of a single dependency of the application
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyStatus {
+    pub name: String,
+    pub ready: bool,
+    pub error: Option<String>,
+}
+
+/// The state of all dependencies - the application is ready, if the database is reachable and
+/// at least one relay is connected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    pub ready: bool,
+    pub database: DependencyStatus,
+    pub relays: Vec<DependencyStatus>,
+}
+
+/// Checks the connectivity of the dependencies of the application
+#[cfg_attr(test, automock)]
+#[async_trait::async_trait]
+pub trait HealthServiceApi: Send + Sync {
+    /// Checks the database and relay connections, each check is cancelled after a short timeout
+    async fn check(&self) -> HealthReport;
+}
+
+pub struct HealthService {
+    store: Arc<dyn BackupStoreApi>,
+    notification_service: Arc<dyn NotificationServiceApi>,
+    timeout: Duration,
+}
+
+impl HealthService {
+    // the maximum time a single check may take, so the health check never hangs
+    const CHECK_TIMEOUT_MILLIS: u64 = 2000;
+
+    pub fn new(
+        store: Arc<dyn BackupStoreApi>,
+        notification_service: Arc<dyn NotificationServiceApi>,
+    ) -> Self {
+        Self {
+            store,
+            notification_service,
+            timeout: Duration::from_millis(Self::CHECK_TIMEOUT_MILLIS),
+        }
+    }
+
+    async fn check_database(&self) -> DependencyStatus {
+        let error = match timeout(self.timeout, self.store.ping()).await {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some("timed out".to_string()),
+        };
+        if let Some(ref e) = error {
+            warn!("Health check of the database failed: {e}");
+        }
+        DependencyStatus {
+            name: "database".to_string(),
+            ready: error.is_none(),
+            error,
+        }
+    }
+
+    async fn check_relays(&self) -> Vec<DependencyStatus> {
+        match timeout(
+            self.timeout,
+            self.notification_service.get_relay_connections(),
+        )
+        .await
+        {
+            Ok(Ok(connections)) => connections
+                .into_iter()
+                .map(|c| DependencyStatus {
+                    error: (!c.connected).then(|| "not connected".to_string()),
+                    name: c.relay,
+                    ready: c.connected,
+                })
+                .collect(),
+            Ok(Err(e)) => {
+                warn!("Health check of the relays failed: {e}");
+                vec![DependencyStatus {
+                    name: "relays".to_string(),
+                    ready: false,
+                    error: Some(e.to_string()),
+                }]
+            }
+            Err(_) => {
+                warn!("Health check of the relays timed out");
+                vec![DependencyStatus {
+                    name: "relays".to_string(),
+                    ready: false,
+                    error: Some("timed out".to_string()),
+                }]
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthServiceApi for HealthService {
+    async fn check(&self) -> HealthReport {
+        let database = self.check_database().await;
+        let relays = self.check_relays().await;
+        let ready = database.ready && relays.iter().any(|r| r.ready);
+        HealthReport {
+            ready,
+            database,
+            relays,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::tests::{MockBackupStoreApiMock, MockNotificationService};
+    use bcr_ebill_transport::RelayConnection;
+
+    fn get_service(
+        store: MockBackupStoreApiMock,
+        notification_service: MockNotificationService,
+    ) -> HealthService {
+        HealthService::new(Arc::new(store), Arc::new(notification_service))
+    }
+
+    fn relays(connected: bool) -> Vec<RelayConnection> {
+        vec![
+            RelayConnection {
+                relay: "ws://relay1".to_string(),
+                connected,
+            },
+            RelayConnection {
+                relay: "ws://relay2".to_string(),
+                connected: false,
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn check_is_ready_if_db_and_a_relay_are_reachable() {
+        let mut store = MockBackupStoreApiMock::new();
+        store.expect_ping().returning(|| Ok(()));
+        let mut notification_service = MockNotificationService::new();
+        notification_service
+            .expect_get_relay_connections()
+            .returning(|| Ok(relays(true)));
+
+        let report = get_service(store, notification_service).check().await;
+        assert!(report.ready);
+        assert!(report.database.ready);
+        assert_eq!(report.relays.len(), 2);
+        assert!(report.relays[0].ready);
+        assert!(!report.relays[1].ready);
+        assert_eq!(report.relays[1].error, Some("not connected".to_string()));
+    }
+
+    #[tokio::test]
+    async fn check_is_not_ready_if_db_is_unreachable() {
+        let mut store = MockBackupStoreApiMock::new();
+        store.expect_ping().returning(|| {
+            Err(bcr_ebill_persistence::Error::SurrealConnection(
+                "connection refused".to_string(),
+            ))
+        });
+        let mut notification_service = MockNotificationService::new();
+        notification_service
+            .expect_get_relay_connections()
+            .returning(|| Ok(relays(true)));
+
+        let report = get_service(store, notification_service).check().await;
+        assert!(!report.ready);
+        assert!(!report.database.ready);
+        assert!(report.database.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn check_is_not_ready_if_no_relay_is_connected() {
+        let mut store = MockBackupStoreApiMock::new();
+        store.expect_ping().returning(|| Ok(()));
+        let mut notification_service = MockNotificationService::new();
+        notification_service
+            .expect_get_relay_connections()
+            .returning(|| Ok(relays(false)));
+
+        let report = get_service(store, notification_service).check().await;
+        assert!(!report.ready);
+        assert!(report.database.ready);
+    }
+}
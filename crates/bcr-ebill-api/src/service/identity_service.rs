@@ -10,11 +10,35 @@ use crate::data::{
 };
 use crate::persistence::file_upload::FileUploadStoreApi;
 use crate::persistence::identity::IdentityChainStoreApi;
+use crate::util::ValidationError;
+use argon2::Argon2;
 use async_trait::async_trait;
 use bcr_ebill_core::identity::ActiveIdentityState;
 use log::{debug, info};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+/// The version of the identity backup container format, stored as the first byte of a backup
+pub const IDENTITY_BACKUP_VERSION: u8 = 1;
+/// The length of the random salt, stored after the version, used to derive the backup key
+const IDENTITY_BACKUP_SALT_LEN: usize = 16;
+
+/// The content of an identity backup, which is encrypted with a passphrase-derived key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IdentityBackup {
+    seed_phrase: String,
+    identity: Identity,
+}
+
+/// Derives a key pair from the given passphrase and salt using argon2
+fn backup_keys_from_passphrase(passphrase: &str, salt: &[u8]) -> Result<BcrKeys> {
+    let mut secret = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut secret)
+        .map_err(|_| ValidationError::InvalidIdentityBackup)?;
+    Ok(BcrKeys::from_secret_bytes(&secret)?)
+}
+
 #[async_trait]
 pub trait IdentityServiceApi: Send + Sync {
     /// Updates the identity
@@ -55,6 +79,19 @@ pub trait IdentityServiceApi: Send + Sync {
     /// Recovers the private keys in the identity from a seed phrase
     async fn recover_from_seedphrase(&self, seed: &str) -> Result<()>;
 
+    /// Creates a backup of the seed phrase and identity data, encrypted with a key derived from
+    /// the given passphrase
+    async fn export_identity_backup(&self, passphrase: &str) -> Result<Vec<u8>>;
+
+    /// Restores the seed phrase and identity data from a backup created with
+    /// `export_identity_backup`. Fails if a different identity exists, unless `force` is set
+    async fn restore_identity_backup(
+        &self,
+        bytes: &[u8],
+        passphrase: &str,
+        force: bool,
+    ) -> Result<()>;
+
     /// opens and decrypts the attached file from the identity
     async fn open_and_decrypt_file(
         &self,
@@ -159,18 +196,18 @@ impl IdentityServiceApi for IdentityService {
         let mut identity = self.store.get().await?;
         let mut changed = false;
 
-        if let Some(ref name_to_set) = name {
-            if identity.name != name_to_set.trim() {
-                identity.name = name_to_set.trim().to_owned();
-                changed = true;
-            }
+        if let Some(ref name_to_set) = name
+            && identity.name != name_to_set.trim()
+        {
+            identity.name = name_to_set.trim().to_owned();
+            changed = true;
         }
 
-        if let Some(ref email_to_set) = email {
-            if identity.email != email_to_set.trim() {
-                identity.email = email_to_set.trim().to_owned();
-                changed = true;
-            }
+        if let Some(ref email_to_set) = email
+            && identity.email != email_to_set.trim()
+        {
+            identity.email = email_to_set.trim().to_owned();
+            changed = true;
         }
 
         util::update_optional_field(
@@ -291,6 +328,9 @@ impl IdentityServiceApi for IdentityService {
         timestamp: u64,
     ) -> Result<()> {
         debug!("creating identity");
+        if let Some(ref country) = postal_address.country {
+            util::country::validate_country_code(country)?;
+        }
         let keys = self.store.get_or_create_key_pair().await?;
         let node_id = keys.get_public_key();
 
@@ -321,7 +361,7 @@ impl IdentityServiceApi for IdentityService {
             identification_number,
             profile_picture_file,
             identity_document_file,
-            nostr_relay: Some(get_config().nostr_relay.to_owned()),
+            nostr_relay: Some(get_config().nostr_relay()),
         };
 
         // create new identity chain and persist it
@@ -347,6 +387,72 @@ impl IdentityServiceApi for IdentityService {
         Ok(res)
     }
 
+    async fn export_identity_backup(&self, passphrase: &str) -> Result<Vec<u8>> {
+        let backup = IdentityBackup {
+            seed_phrase: self.store.get_seedphrase().await?,
+            identity: self.store.get().await?,
+        };
+        let payload =
+            serde_json::to_vec(&backup).map_err(|_| ValidationError::InvalidIdentityBackup)?;
+
+        let salt = util::crypto::random_bytes::<IDENTITY_BACKUP_SALT_LEN>();
+        let backup_keys = backup_keys_from_passphrase(passphrase, &salt)?;
+        let encrypted = util::crypto::encrypt_ecies(&payload, &backup_keys.get_public_key())?;
+
+        let mut bytes = vec![IDENTITY_BACKUP_VERSION];
+        bytes.extend_from_slice(&salt);
+        bytes.extend(encrypted);
+        debug!("exported identity backup for {}", backup.identity.node_id);
+        Ok(bytes)
+    }
+
+    async fn restore_identity_backup(
+        &self,
+        bytes: &[u8],
+        passphrase: &str,
+        force: bool,
+    ) -> Result<()> {
+        let (salt, encrypted) = match bytes.split_first() {
+            Some((&IDENTITY_BACKUP_VERSION, rest)) if rest.len() > IDENTITY_BACKUP_SALT_LEN => {
+                rest.split_at(IDENTITY_BACKUP_SALT_LEN)
+            }
+            Some((&IDENTITY_BACKUP_VERSION, _)) | None => {
+                return Err(ValidationError::InvalidIdentityBackup.into());
+            }
+            Some((version, _)) => {
+                return Err(ValidationError::UnknownIdentityBackupVersion(*version).into());
+            }
+        };
+
+        let backup_keys = backup_keys_from_passphrase(passphrase, salt)?;
+        let payload = util::crypto::decrypt_ecies(encrypted, &backup_keys.get_private_key_string())
+            .map_err(|_| ValidationError::InvalidIdentityBackup)?;
+        let backup: IdentityBackup =
+            serde_json::from_slice(&payload).map_err(|_| ValidationError::InvalidIdentityBackup)?;
+
+        // the seed phrase has to match the node id of the identity
+        let key_pair = BcrKeys::from_seedphrase(&backup.seed_phrase)
+            .map_err(|_| ValidationError::InvalidIdentityBackup)?;
+        if key_pair.get_public_key() != backup.identity.node_id {
+            return Err(ValidationError::InvalidIdentityBackup.into());
+        }
+
+        // restoring the same identity again is fine, replacing a different one is only done if forced
+        if !force && self.store.exists().await {
+            let existing = self.store.get().await?;
+            if existing.node_id != backup.identity.node_id {
+                return Err(ValidationError::IdentityAlreadyExists.into());
+            }
+        }
+
+        self.store
+            .save_key_pair(&key_pair, &backup.seed_phrase)
+            .await?;
+        self.store.save(&backup.identity).await?;
+        info!("restored identity {} from backup", backup.identity.node_id);
+        Ok(())
+    }
+
     async fn open_and_decrypt_file(
         &self,
         id: &str,
@@ -394,6 +500,7 @@ impl IdentityServiceApi for IdentityService {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::service::Error;
     use crate::tests::tests::{
         MockFileUploadStoreApiMock, MockIdentityChainStoreApiMock, MockIdentityStoreApiMock,
         empty_identity, empty_optional_address, init_test_cfg,
@@ -452,6 +559,38 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn create_identity_fails_for_invalid_country_code() {
+        init_test_cfg();
+        let mut storage = MockIdentityStoreApiMock::new();
+        storage.expect_save().never();
+        let mut chain_storage = MockIdentityChainStoreApiMock::new();
+        chain_storage.expect_add_block().never();
+
+        let service = get_service_with_chain_storage(storage, chain_storage);
+        let mut postal_address = empty_optional_address();
+        postal_address.country = Some("Germany".to_string());
+        let res = service
+            .create_identity(
+                "name".to_string(),
+                "email".to_string(),
+                postal_address,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                1731593928,
+            )
+            .await;
+
+        assert!(matches!(
+            res,
+            Err(Error::Validation(ValidationError::InvalidCountryCode(_)))
+        ));
+    }
+
     #[tokio::test]
     async fn update_identity_calls_storage() {
         let keys = BcrKeys::new();
@@ -664,4 +803,136 @@ mod tests {
             .await
             .expect("could not recover from seedphrase")
     }
+
+    const BACKUP_SEED: &str = "forward paper connect economy twelve debate cart isolate accident creek bind predict captain rifle glory cradle hip whisper wealth save buddy place develop dolphin";
+
+    fn backup_identity() -> Identity {
+        let mut identity = empty_identity();
+        identity.node_id = BcrKeys::from_seedphrase(BACKUP_SEED)
+            .unwrap()
+            .get_public_key();
+        identity
+    }
+
+    async fn create_backup(passphrase: &str) -> Vec<u8> {
+        let mut storage = MockIdentityStoreApiMock::new();
+        storage
+            .expect_get_seedphrase()
+            .returning(|| Ok(BACKUP_SEED.to_string()));
+        storage.expect_get().returning(|| Ok(backup_identity()));
+        get_service(storage)
+            .export_identity_backup(passphrase)
+            .await
+            .expect("could not export backup")
+    }
+
+    #[tokio::test]
+    async fn identity_backup_roundtrip() {
+        let backup = create_backup("secret passphrase").await;
+        assert_eq!(backup[0], IDENTITY_BACKUP_VERSION);
+
+        let mut storage = MockIdentityStoreApiMock::new();
+        storage.expect_exists().returning(|| false);
+        storage
+            .expect_save_key_pair()
+            .with(
+                eq(BcrKeys::from_seedphrase(BACKUP_SEED).unwrap()),
+                eq(BACKUP_SEED),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+        storage
+            .expect_save()
+            .with(eq(backup_identity()))
+            .times(1)
+            .returning(|_| Ok(()));
+        get_service(storage)
+            .restore_identity_backup(&backup, "secret passphrase", false)
+            .await
+            .expect("could not restore backup");
+    }
+
+    #[tokio::test]
+    async fn identity_backup_restore_is_idempotent_for_same_identity() {
+        let backup = create_backup("secret passphrase").await;
+
+        let mut storage = MockIdentityStoreApiMock::new();
+        storage.expect_exists().returning(|| true);
+        storage.expect_get().returning(|| Ok(backup_identity()));
+        storage
+            .expect_save_key_pair()
+            .times(2)
+            .returning(|_, _| Ok(()));
+        storage.expect_save().times(2).returning(|_| Ok(()));
+        let service = get_service(storage);
+        for _ in 0..2 {
+            service
+                .restore_identity_backup(&backup, "secret passphrase", false)
+                .await
+                .expect("could not restore backup");
+        }
+    }
+
+    #[tokio::test]
+    async fn identity_backup_restore_fails_with_wrong_passphrase() {
+        let backup = create_backup("secret passphrase").await;
+
+        let mut storage = MockIdentityStoreApiMock::new();
+        storage.expect_save_key_pair().never();
+        storage.expect_save().never();
+        let result = get_service(storage)
+            .restore_identity_backup(&backup, "wrong passphrase", false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Validation(ValidationError::InvalidIdentityBackup))
+        ));
+    }
+
+    #[tokio::test]
+    async fn identity_backup_restore_fails_with_unknown_version() {
+        let mut backup = create_backup("secret passphrase").await;
+        backup[0] = IDENTITY_BACKUP_VERSION + 1;
+
+        let mut storage = MockIdentityStoreApiMock::new();
+        storage.expect_save().never();
+        let result = get_service(storage)
+            .restore_identity_backup(&backup, "secret passphrase", false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Validation(ValidationError::UnknownIdentityBackupVersion(v))) if v == IDENTITY_BACKUP_VERSION + 1
+        ));
+    }
+
+    #[tokio::test]
+    async fn identity_backup_restore_only_overwrites_other_identity_if_forced() {
+        let backup = create_backup("secret passphrase").await;
+
+        let mut storage = MockIdentityStoreApiMock::new();
+        storage.expect_exists().returning(|| true);
+        storage.expect_get().returning(|| {
+            let mut identity = empty_identity();
+            identity.node_id = BcrKeys::new().get_public_key();
+            Ok(identity)
+        });
+        storage
+            .expect_save_key_pair()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        storage.expect_save().times(1).returning(|_| Ok(()));
+        let service = get_service(storage);
+
+        let result = service
+            .restore_identity_backup(&backup, "secret passphrase", false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Validation(ValidationError::IdentityAlreadyExists))
+        ));
+        service
+            .restore_identity_backup(&backup, "secret passphrase", true)
+            .await
+            .expect("could not force restore backup");
+    }
 }
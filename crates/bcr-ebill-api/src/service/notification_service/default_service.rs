@@ -2,20 +2,29 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use bcr_ebill_core::blockchain::bill::BillBlock;
 use bcr_ebill_core::contact::ContactType;
 use bcr_ebill_persistence::nostr::{NostrQueuedMessage, NostrQueuedMessageStoreApi};
-use bcr_ebill_transport::{BillChainEvent, BillChainEventPayload, Error, Event, EventEnvelope};
-use log::{error, warn};
+use bcr_ebill_transport::{
+    BillChainEvent, BillChainEventPayload, Error, Event, EventEnvelope, EventType,
+    QueuedMessageInfo, RelayConnection, RelayStatus,
+};
+use log::{debug, error, warn};
+use tokio_with_wasm::alias as tokio;
 
 use super::NotificationJsonTransportApi;
+use super::webhook::{WebhookDispatcher, WebhookPayload};
 use super::{NotificationServiceApi, Result};
 use crate::data::{
     bill::BitcreditBill,
     contact::IdentityPublicData,
     notification::{Notification, NotificationType},
 };
-use crate::persistence::notification::{NotificationFilter, NotificationStoreApi};
+use crate::persistence::notification::{
+    NotificationFilter, NotificationPage, NotificationStoreApi,
+};
 use crate::service::contact_service::ContactServiceApi;
+use crate::util;
 use bcr_ebill_core::notification::{ActionType, BillEventType};
 use bcr_ebill_core::{PostalAddress, ServiceTraitBounds};
 
@@ -28,13 +37,17 @@ pub struct DefaultNotificationService {
     contact_service: Arc<dyn ContactServiceApi>,
     queued_message_store: Arc<dyn NostrQueuedMessageStoreApi>,
     nostr_relay: String,
+    max_retry_delay_seconds: u64,
+    /// The number of retries, after which a message is moved to the dead-letter state
+    max_retries: i32,
+    webhook_dispatcher: Option<Arc<WebhookDispatcher>>,
 }
 
 impl ServiceTraitBounds for DefaultNotificationService {}
 
 impl DefaultNotificationService {
-    // the number of times we want to retry sending a block message
-    const NOSTR_MAX_RETRIES: i32 = 10;
+    // the delay before the first retry, which is doubled with every failed retry
+    const NOSTR_RETRY_BASE_DELAY_SECONDS: u64 = 30;
 
     pub fn new(
         notification_transport: Vec<Arc<dyn NotificationJsonTransportApi>>,
@@ -42,6 +55,8 @@ impl DefaultNotificationService {
         contact_service: Arc<dyn ContactServiceApi>,
         queued_message_store: Arc<dyn NostrQueuedMessageStoreApi>,
         nostr_relay: &str,
+        max_retry_delay_seconds: u64,
+        max_retries: i32,
     ) -> Self {
         Self {
             notification_transport: notification_transport
@@ -52,9 +67,27 @@ impl DefaultNotificationService {
             contact_service,
             queued_message_store,
             nostr_relay: nostr_relay.to_string(),
+            max_retry_delay_seconds,
+            max_retries,
+            webhook_dispatcher: None,
         }
     }
 
+    /// Additionally posts all bill events to the webhook endpoints of the given dispatcher
+    pub fn with_webhook_dispatcher(mut self, webhook_dispatcher: Arc<WebhookDispatcher>) -> Self {
+        self.webhook_dispatcher = Some(webhook_dispatcher);
+        self
+    }
+
+    /// Returns the delay until the next retry of a message, which already failed to be retried
+    /// `retry_count` times, doubling the base delay with every failure up to the max delay
+    fn retry_delay_seconds(&self, retry_count: i32) -> u64 {
+        2u64.checked_pow(retry_count.max(0) as u32)
+            .and_then(|factor| factor.checked_mul(Self::NOSTR_RETRY_BASE_DELAY_SECONDS))
+            .unwrap_or(u64::MAX)
+            .min(self.max_retry_delay_seconds)
+    }
+
     fn get_local_identity(&self, node_id: &str) -> Option<IdentityPublicData> {
         if self.notification_transport.contains_key(node_id) {
             Some(IdentityPublicData {
@@ -85,34 +118,75 @@ impl DefaultNotificationService {
         }
     }
 
+    /// Posts the action of the given events to the configured webhooks in the background, so slow
+    /// or failing endpoints don't block the bill action
+    fn dispatch_webhook(&self, sender: &str, events: &[Event<BillChainEventPayload>]) {
+        let (Some(dispatcher), Some(first_event)) = (&self.webhook_dispatcher, events.first())
+        else {
+            return;
+        };
+        let event_type = events
+            .iter()
+            .map(|e| &e.data.event_type)
+            .find(|event_type| event_type.is_action_event())
+            .cloned()
+            .unwrap_or(BillEventType::BillBlock);
+        let payload = WebhookPayload {
+            bill_id: first_event.data.bill_id.clone(),
+            event_type,
+            timestamp: util::date::now().timestamp() as u64,
+            sender: sender.to_owned(),
+        };
+        let dispatcher = dispatcher.clone();
+        tokio::spawn(async move {
+            dispatcher.dispatch(&payload).await;
+        });
+    }
+
     async fn send_all_events(
         &self,
         sender: &str,
         events: Vec<Event<BillChainEventPayload>>,
+    ) -> Result<()> {
+        self.dispatch_webhook(sender, &events);
+        self.send_events(sender, events).await
+    }
+
+    /// Sends the given events via the transport of the sender, queueing failed sends for retry
+    async fn send_events(
+        &self,
+        sender: &str,
+        events: Vec<Event<BillChainEventPayload>>,
     ) -> Result<()> {
         if let Some(node) = self.notification_transport.get(sender) {
             for event_to_process in events.into_iter() {
                 if let Some(identity) = self.resolve_identity(&event_to_process.node_id).await {
-                    if let Err(e) = node
+                    match node
                         .send(&identity, event_to_process.clone().try_into()?)
                         .await
                     {
-                        error!(
-                            "Failed to send block notification, will add it to retry queue: {}",
-                            e
-                        );
-                        let queue_message = NostrQueuedMessage {
-                            id: uuid::Uuid::new_v4().to_string(),
-                            sender_id: sender.to_owned(),
-                            node_id: event_to_process.node_id.clone(),
-                            payload: serde_json::to_value(event_to_process)?,
-                        };
-                        if let Err(e) = self
-                            .queued_message_store
-                            .add_message(queue_message, Self::NOSTR_MAX_RETRIES)
-                            .await
-                        {
-                            error!("Failed to add block notification to retry queue: {}", e);
+                        Ok(()) => self.mark_participant_notified(&event_to_process).await,
+                        Err(e) => {
+                            error!(
+                                "Failed to send block notification, will add it to retry queue: {}",
+                                e
+                            );
+                            let queue_message = NostrQueuedMessage {
+                                id: uuid::Uuid::new_v4().to_string(),
+                                sender_id: sender.to_owned(),
+                                node_id: event_to_process.node_id.clone(),
+                                payload: serde_json::to_value(event_to_process)?,
+                                retry_count: 0,
+                                next_retry_at: util::date::now().timestamp() as u64
+                                    + self.retry_delay_seconds(0),
+                            };
+                            if let Err(e) = self
+                                .queued_message_store
+                                .add_message(queue_message, self.max_retries)
+                                .await
+                            {
+                                error!("Failed to add block notification to retry queue: {}", e);
+                            }
                         }
                     }
                 } else {
@@ -128,17 +202,31 @@ impl DefaultNotificationService {
         Ok(())
     }
 
+    /// Tracks that the block of the given event was sent to its recipient - failures are only
+    /// logged, since the event itself was sent
+    async fn mark_participant_notified(&self, event: &Event<BillChainEventPayload>) {
+        let Some(block) = event.data.blocks.last() else {
+            return;
+        };
+        if let Err(e) = self
+            .notification_store
+            .set_bill_participant_notified(&event.data.bill_id, block.id as i32, &event.node_id)
+            .await
+        {
+            error!("Failed to mark bill participant as notified: {}", e);
+        }
+    }
+
     async fn send_retry_message(
         &self,
         sender: &str,
         node_id: &str,
         message: EventEnvelope,
     ) -> Result<()> {
-        if let Some(node) = self.notification_transport.get(sender) {
-            if let Ok(Some(identity)) = self.contact_service.get_identity_by_node_id(node_id).await
-            {
-                node.send(&identity, message).await?;
-            }
+        if let Some(node) = self.notification_transport.get(sender)
+            && let Ok(Some(identity)) = self.contact_service.get_identity_by_node_id(node_id).await
+        {
+            node.send(&identity, message).await?;
         }
         Ok(())
     }
@@ -211,6 +299,22 @@ impl NotificationServiceApi for DefaultNotificationService {
         Ok(())
     }
 
+    async fn send_request_to_accept_and_pay_event(&self, event: &BillChainEvent) -> Result<()> {
+        let all_events = event.generate_action_messages(
+            HashMap::from_iter(vec![(
+                event.bill.drawee.node_id.clone(),
+                (
+                    BillEventType::BillAcceptanceAndPaymentRequested,
+                    ActionType::PayBill,
+                ),
+            )]),
+            None,
+            None,
+        );
+        self.send_all_events(&event.sender(), all_events).await?;
+        Ok(())
+    }
+
     async fn send_bill_is_paid_event(&self, event: &BillChainEvent) -> Result<()> {
         let all_events = event.generate_action_messages(
             HashMap::from_iter(vec![(
@@ -237,6 +341,43 @@ impl NotificationServiceApi for DefaultNotificationService {
         Ok(())
     }
 
+    async fn send_bill_files_attached_event(&self, event: &BillChainEvent) -> Result<()> {
+        let all_events = event.generate_action_messages(HashMap::new(), None, None);
+        self.send_all_events(&event.sender(), all_events).await?;
+        Ok(())
+    }
+
+    async fn send_offer_to_sell_cancelled_event(&self, event: &BillChainEvent) -> Result<()> {
+        let all_events = event.generate_action_messages(HashMap::new(), None, None);
+        self.send_all_events(&event.sender(), all_events).await?;
+        Ok(())
+    }
+
+    async fn send_request_to_accept_cancelled_event(&self, event: &BillChainEvent) -> Result<()> {
+        let all_events = event.generate_action_messages(HashMap::new(), None, None);
+        self.send_all_events(&event.sender(), all_events).await?;
+        Ok(())
+    }
+
+    async fn send_bill_acknowledged_event(&self, event: &BillChainEvent) -> Result<()> {
+        let all_events = event.generate_action_messages(HashMap::new(), None, None);
+        self.send_all_events(&event.sender(), all_events).await?;
+        Ok(())
+    }
+
+    async fn send_drawee_corrected_event(&self, event: &BillChainEvent) -> Result<()> {
+        let all_events = event.generate_action_messages(
+            HashMap::from_iter(vec![(
+                event.bill.drawee.node_id.clone(),
+                (BillEventType::BillSigned, ActionType::AcceptBill),
+            )]),
+            None,
+            None,
+        );
+        self.send_all_events(&event.sender(), all_events).await?;
+        Ok(())
+    }
+
     async fn send_offer_to_sell_event(
         &self,
         event: &BillChainEvent,
@@ -314,13 +455,17 @@ impl NotificationServiceApi for DefaultNotificationService {
         &self,
         event: &BillChainEvent,
         rejected_action: ActionType,
+        reason: Option<String>,
     ) -> Result<()> {
         if let Some(event_type) = rejected_action.get_rejected_event_type() {
-            let all_events = event.generate_action_messages(
+            let mut all_events = event.generate_action_messages(
                 HashMap::new(),
                 Some(event_type),
                 Some(rejected_action),
             );
+            for e in all_events.iter_mut() {
+                e.data.reason = reason.clone();
+            }
 
             self.send_all_events(&event.sender(), all_events).await?;
         }
@@ -335,23 +480,23 @@ impl NotificationServiceApi for DefaultNotificationService {
         timed_out_action: ActionType,
         recipients: Vec<IdentityPublicData>,
     ) -> Result<()> {
-        if let Some(node) = self.notification_transport.get(sender_node_id) {
-            if let Some(event_type) = timed_out_action.get_timeout_event_type() {
-                // only send to a recipient once
-                let unique: HashMap<String, IdentityPublicData> =
-                    HashMap::from_iter(recipients.iter().map(|r| (r.node_id.clone(), r.clone())));
-
-                let payload = BillChainEventPayload {
-                    event_type,
-                    bill_id: bill_id.to_owned(),
-                    action_type: Some(ActionType::CheckBill),
-                    sum,
-                    ..Default::default()
-                };
-                for (_, recipient) in unique {
-                    let event = Event::new_bill(&recipient.node_id, payload.clone());
-                    node.send(&recipient, event.try_into()?).await?;
-                }
+        if let Some(node) = self.notification_transport.get(sender_node_id)
+            && let Some(event_type) = timed_out_action.get_timeout_event_type()
+        {
+            // only send to a recipient once
+            let unique: HashMap<String, IdentityPublicData> =
+                HashMap::from_iter(recipients.iter().map(|r| (r.node_id.clone(), r.clone())));
+
+            let payload = BillChainEventPayload {
+                event_type,
+                bill_id: bill_id.to_owned(),
+                action_type: Some(ActionType::CheckBill),
+                sum,
+                ..Default::default()
+            };
+            for (_, recipient) in unique {
+                let event = Event::new_bill(&recipient.node_id, payload.clone());
+                node.send(&recipient, event.try_into()?).await?;
             }
         }
         Ok(())
@@ -377,20 +522,104 @@ impl NotificationServiceApi for DefaultNotificationService {
         Ok(())
     }
 
-    async fn send_new_quote_event(&self, _bill: &BitcreditBill) -> Result<()> {
-        // @TODO: How do we know the quoting participants
+    async fn send_maturity_approaching_event(
+        &self,
+        sender_node_id: &str,
+        bill_id: &str,
+        sum: Option<u64>,
+        recipient: &IdentityPublicData,
+    ) -> Result<()> {
+        if let Some(node) = self.notification_transport.get(sender_node_id) {
+            let payload = BillChainEventPayload {
+                event_type: BillEventType::BillMaturityApproaching,
+                bill_id: bill_id.to_owned(),
+                action_type: Some(ActionType::RequestPayment),
+                sum,
+                ..Default::default()
+            };
+            let event = Event::new_bill(&recipient.node_id, payload);
+            node.send(recipient, event.try_into()?).await?;
+        }
+        Ok(())
+    }
+
+    async fn send_request_to_action_timeout_approaching_event(
+        &self,
+        sender_node_id: &str,
+        bill_id: &str,
+        sum: Option<u64>,
+        action: ActionType,
+        recipient: &IdentityPublicData,
+    ) -> Result<()> {
+        if let Some(node) = self.notification_transport.get(sender_node_id) {
+            let payload = BillChainEventPayload {
+                event_type: BillEventType::BillActionTimeoutApproaching,
+                bill_id: bill_id.to_owned(),
+                action_type: Some(action),
+                sum,
+                ..Default::default()
+            };
+            let event = Event::new_bill(&recipient.node_id, payload);
+            node.send(recipient, event.try_into()?).await?;
+        }
+        Ok(())
+    }
+
+    async fn send_new_quote_event(
+        &self,
+        event: &BillChainEvent,
+        requester: &IdentityPublicData,
+    ) -> Result<()> {
+        let all_events = event.generate_action_messages(
+            HashMap::from_iter(vec![(
+                requester.node_id.clone(),
+                (BillEventType::BillNewQuote, ActionType::CheckQuote),
+            )]),
+            None,
+            None,
+        );
+        self.send_all_events(&event.sender(), all_events).await?;
+        Ok(())
+    }
+
+    async fn send_quote_is_approved_event(
+        &self,
+        event: &BillChainEvent,
+        mint: &IdentityPublicData,
+    ) -> Result<()> {
+        let all_events = event.generate_action_messages(
+            HashMap::from_iter(vec![(
+                mint.node_id.clone(),
+                (BillEventType::BillQuoteApproved, ActionType::CheckBill),
+            )]),
+            None,
+            None,
+        );
+        self.send_all_events(&event.sender(), all_events).await?;
         Ok(())
     }
 
-    async fn send_quote_is_approved_event(&self, _bill: &BitcreditBill) -> Result<()> {
-        // @TODO: How do we address a mint ???
+    async fn send_quote_is_rejected_event(
+        &self,
+        event: &BillChainEvent,
+        mint: &IdentityPublicData,
+    ) -> Result<()> {
+        let all_events = event.generate_action_messages(
+            HashMap::from_iter(vec![(
+                mint.node_id.clone(),
+                (BillEventType::BillQuoteRejected, ActionType::CheckBill),
+            )]),
+            None,
+            None,
+        );
+        self.send_all_events(&event.sender(), all_events).await?;
         Ok(())
     }
 
     async fn get_client_notifications(
         &self,
         filter: NotificationFilter,
-    ) -> Result<Vec<Notification>> {
+    ) -> Result<NotificationPage> {
         let result = self.notification_store.list(filter).await.map_err(|e| {
             error!("Failed to get client notifications: {}", e);
             Error::Persistence("Failed to get client notifications".to_string())
@@ -410,6 +639,64 @@ impl NotificationServiceApi for DefaultNotificationService {
         Ok(())
     }
 
+    async fn mark_all_as_done(
+        &self,
+        node_id: &str,
+        notification_type: Option<NotificationType>,
+    ) -> Result<u64> {
+        let marked = self
+            .notification_store
+            .mark_all_as_done(node_id, notification_type)
+            .await
+            .map_err(|e| {
+                error!("Failed to mark all notifications as done: {}", e);
+                Error::Persistence("Failed to mark all notifications as done".to_string())
+            })?;
+        debug!("marked {marked} notifications of {node_id} as done");
+        Ok(marked)
+    }
+
+    async fn mark_bill_notifications_done(&self, bill_id: &str, node_id: &str) -> Result<u64> {
+        let mut marked = 0;
+        let mut after = None;
+        loop {
+            let page = self
+                .notification_store
+                .list(NotificationFilter {
+                    active: Some(true),
+                    reference_id: Some(bill_id.to_owned()),
+                    after,
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| {
+                    error!("Failed to get notifications for bill {bill_id}: {e}");
+                    Error::Persistence("Failed to get notifications for bill".to_string())
+                })?;
+            // only the ones of the given identity, so e.g. a company's notifications stay active
+            for notification in page
+                .notifications
+                .iter()
+                .filter(|n| n.node_id.as_deref() == Some(node_id))
+            {
+                self.notification_store
+                    .mark_as_done(&notification.id)
+                    .await
+                    .map_err(|e| {
+                        error!("Failed to mark notification as done: {e}");
+                        Error::Persistence("Failed to mark notification as done".to_string())
+                    })?;
+                marked += 1;
+            }
+            match page.next_cursor {
+                Some(cursor) => after = Some(cursor),
+                None => break,
+            }
+        }
+        debug!("marked {marked} notifications of {node_id} for bill {bill_id} as done");
+        Ok(marked)
+    }
+
     async fn get_active_bill_notification(&self, bill_id: &str) -> Option<Notification> {
         self.notification_store
             .get_latest_by_reference(bill_id, NotificationType::Bill)
@@ -465,11 +752,26 @@ impl NotificationServiceApi for DefaultNotificationService {
         Ok(())
     }
 
+    async fn get_notified_bill_participants(
+        &self,
+        bill_id: &str,
+        block_height: i32,
+    ) -> Result<Vec<String>> {
+        self.notification_store
+            .get_notified_bill_participants(bill_id, block_height)
+            .await
+            .map_err(|e| {
+                error!("Failed to get notified bill participants: {}", e);
+                Error::Persistence("Failed to get notified bill participants".to_string())
+            })
+    }
+
     async fn send_retry_messages(&self) -> Result<()> {
-        let mut failed_ids = vec![];
+        let now = util::date::now().timestamp() as u64;
+        let mut failed = vec![];
         while let Ok(Some(queued_message)) = self
             .queued_message_store
-            .get_retry_messages(1)
+            .get_retry_messages(1, now)
             .await
             .map(|r| r.first().cloned())
         {
@@ -483,24 +785,154 @@ impl NotificationServiceApi for DefaultNotificationService {
                     .await
                 {
                     error!("Failed to send retry message: {}", e);
-                    failed_ids.push(queued_message.id.clone());
-                } else if let Err(e) = self
-                    .queued_message_store
-                    .succeed_retry(&queued_message.id)
-                    .await
-                {
-                    error!("Failed to mark retry message as sent: {}", e);
+                    failed.push((queued_message.id.clone(), queued_message.retry_count + 1));
+                } else {
+                    if let Ok(event) = Event::<BillChainEventPayload>::try_from(message) {
+                        self.mark_participant_notified(&event).await;
+                    }
+                    if let Err(e) = self
+                        .queued_message_store
+                        .succeed_retry(&queued_message.id)
+                        .await
+                    {
+                        error!("Failed to mark retry message as sent: {}", e);
+                    }
                 }
             }
         }
 
-        for failed in failed_ids {
-            if let Err(e) = self.queued_message_store.fail_retry(&failed).await {
+        for (id, retry_count) in failed {
+            let next_retry_at = now + self.retry_delay_seconds(retry_count);
+            if retry_count >= self.max_retries {
+                warn!(
+                    "Retry message {id} exhausted all retries, moving it to the dead-letter state"
+                );
+            }
+            if let Err(e) = self
+                .queued_message_store
+                .fail_retry(&id, next_retry_at)
+                .await
+            {
                 error!("Failed to store failed retry attemt: {}", e);
             }
         }
         Ok(())
     }
+
+    async fn resend_bill_event(&self, event: &BillChainEvent, node_id: &str) -> Result<()> {
+        let events: Vec<Event<BillChainEventPayload>> = event
+            .generate_action_messages(HashMap::new(), None, None)
+            .into_iter()
+            .filter(|e| e.node_id == node_id)
+            .collect();
+        if events.is_empty() {
+            warn!(
+                "Not resending event for bill {}, since {node_id} is not a participant",
+                event.bill.id
+            );
+            return Ok(());
+        }
+        self.send_events(&event.sender(), events).await
+    }
+
+    async fn get_relay_status(&self) -> Result<Vec<RelayStatus>> {
+        // all local identities publish to the same relays, so we combine their statistics
+        let mut combined: HashMap<String, RelayStatus> = HashMap::new();
+        for transport in self.notification_transport.values() {
+            for status in transport.get_relay_status() {
+                let entry = combined
+                    .entry(status.relay.clone())
+                    .or_insert_with(|| RelayStatus::new(&status.relay));
+                entry.success_count += status.success_count;
+                entry.failure_count += status.failure_count;
+                if status.last_error.is_some() {
+                    entry.last_error = status.last_error;
+                }
+            }
+        }
+        let mut result: Vec<RelayStatus> = combined.into_values().collect();
+        result.sort_by(|a, b| a.relay.cmp(&b.relay));
+        Ok(result)
+    }
+
+    async fn get_relay_connections(&self) -> Result<Vec<RelayConnection>> {
+        let mut combined: HashMap<String, bool> = HashMap::new();
+        for transport in self.notification_transport.values() {
+            for connection in transport.get_relay_connections().await {
+                let entry = combined.entry(connection.relay).or_insert(false);
+                *entry = *entry || connection.connected;
+            }
+        }
+        let mut result: Vec<RelayConnection> = combined
+            .into_iter()
+            .map(|(relay, connected)| RelayConnection { relay, connected })
+            .collect();
+        result.sort_by(|a, b| a.relay.cmp(&b.relay));
+        Ok(result)
+    }
+
+    async fn fetch_bill_blocks(&self, bill_id: &str) -> Result<Vec<BillBlock>> {
+        let mut blocks: Vec<BillBlock> = vec![];
+        for transport in self.notification_transport.values() {
+            let envelopes = match transport.fetch_received_events().await {
+                Ok(envelopes) => envelopes,
+                Err(e) => {
+                    warn!(
+                        "Failed to fetch received events of {}: {e}",
+                        transport.get_sender_key()
+                    );
+                    continue;
+                }
+            };
+            for envelope in envelopes {
+                if envelope.event_type != EventType::Bill {
+                    continue;
+                }
+                if let Ok(event) = Event::<BillChainEventPayload>::try_from(envelope)
+                    && event.data.bill_id == bill_id
+                {
+                    blocks.extend(event.data.blocks);
+                }
+            }
+        }
+        // the same block is usually part of several events
+        blocks.sort_by(|a, b| a.id.cmp(&b.id).then_with(|| a.hash.cmp(&b.hash)));
+        blocks.dedup_by(|a, b| a.hash == b.hash);
+        Ok(blocks)
+    }
+
+    async fn list_pending_retry_messages(&self, limit: u64) -> Result<Vec<QueuedMessageInfo>> {
+        let now = util::date::now().timestamp() as u64;
+        let messages = self
+            .queued_message_store
+            .get_pending_messages(limit)
+            .await
+            .map_err(|e| {
+                error!("Failed to get pending retry messages: {}", e);
+                Error::Persistence("Failed to get pending retry messages".to_string())
+            })?;
+        Ok(messages
+            .into_iter()
+            .map(|m| QueuedMessageInfo {
+                id: m.id,
+                sender_id: m.sender_id,
+                node_id: m.node_id,
+                retry_count: m.retry_count,
+                next_retry_at: m.next_retry_at,
+                age_seconds: now.saturating_sub(m.created),
+            })
+            .collect())
+    }
+
+    async fn count_dead_letter_messages(&self) -> Result<u64> {
+        self.queued_message_store
+            .count_dead_letter_messages()
+            .await
+            .map_err(|e| {
+                error!("Failed to count dead-lettered retry messages: {}", e);
+                Error::Persistence("Failed to count dead-lettered retry messages".to_string())
+            })
+    }
 }
 
 #[cfg(test)]
@@ -510,32 +942,28 @@ mod tests {
     use bcr_ebill_core::bill::BillKeys;
     use bcr_ebill_core::blockchain::Blockchain;
     use bcr_ebill_core::blockchain::bill::block::{
-        BillAcceptBlockData, BillOfferToSellBlockData, BillRecourseBlockData,
-        BillRecourseReasonBlockData, BillRequestToAcceptBlockData, BillRequestToPayBlockData,
+        BillAcceptBlockData, BillMintBlockData, BillMintQuoteBlockData, BillOfferToSellBlockData,
+        BillRecourseBlockData, BillRecourseReasonBlockData, BillRequestToAcceptBlockData,
+        BillRequestToPayBlockData,
     };
     use bcr_ebill_core::blockchain::bill::{BillBlock, BillBlockchain};
     use bcr_ebill_core::util::date::now;
     use bcr_ebill_transport::{EventEnvelope, EventType, PushApi};
-    use mockall::{mock, predicate::eq};
+    use mockall::{
+        mock,
+        predicate::{always, eq, function},
+    };
     use std::sync::Arc;
 
+    use crate::persistence::notification::NotificationCursor;
     use crate::service::bill_service::test_utils::{get_baseline_identity, get_genesis_chain};
     use crate::service::contact_service::MockContactServiceApi;
     use crate::service::notification_service::create_nostr_consumer;
+    use crate::util::BcrKeys;
     use async_broadcast::Receiver;
+    use bcr_ebill_persistence::nostr::NostrQueuedMessageInfo;
     use serde_json::Value;
 
-    impl ServiceTraitBounds for MockNotificationJsonTransport {}
-    mock! {
-        pub NotificationJsonTransport {}
-        #[async_trait]
-        impl NotificationJsonTransportApi for NotificationJsonTransport {
-            fn get_sender_key(&self) -> String;
-            async fn send(&self, recipient: &IdentityPublicData, event: EventEnvelope) -> bcr_ebill_transport::Result<()>;
-        }
-
-    }
-
     mock! {
         pub PushService {}
         #[async_trait]
@@ -550,11 +978,21 @@ mod tests {
     };
     use super::*;
     use crate::tests::tests::{
-        MockBillChainStoreApiMock, MockBillStoreApiMock, MockNostrEventOffsetStoreApiMock,
-        MockNostrQueuedMessageStore, MockNotificationStoreApiMock, TEST_BILL_ID,
-        TEST_PRIVATE_KEY_SECP, TEST_PUB_KEY_SECP,
+        MockBillAutoAcceptStoreApiMock, MockBillChainStoreApiMock, MockBillStoreApiMock,
+        MockNostrEventOffsetStoreApiMock, MockNostrQueuedMessageStore,
+        MockNotificationJsonTransport, MockNotificationStoreApiMock, TEST_BILL_ID,
+        TEST_PRIVATE_KEY_SECP, TEST_PUB_KEY_SECP, init_test_cfg,
     };
 
+    /// Returns a notification store, which allows tracking the participants events were sent to
+    fn get_notification_store() -> MockNotificationStoreApiMock {
+        let mut mock_store = MockNotificationStoreApiMock::new();
+        mock_store
+            .expect_set_bill_participant_notified()
+            .returning(|_, _, _| Ok(()));
+        mock_store
+    }
+
     fn check_chain_payload(event: &EventEnvelope, bill_event_type: BillEventType) -> bool {
         let valid_event_type = event.event_type == EventType::Bill;
         let event: Event<BillChainEventPayload> = event.clone().try_into().unwrap();
@@ -582,6 +1020,7 @@ mod tests {
                 payment_address: "Address".to_string(),
                 signing_timestamp: timestamp,
                 signing_address: PostalAddress::default(),
+                sell_deadline_seconds: None,
             },
             &keys,
             None,
@@ -652,29 +1091,31 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         service
-            .send_request_to_action_rejected_event(&event, ActionType::PayBill)
+            .send_request_to_action_rejected_event(&event, ActionType::PayBill, None)
             .await
             .expect("failed to send event");
 
         service
-            .send_request_to_action_rejected_event(&event, ActionType::AcceptBill)
+            .send_request_to_action_rejected_event(&event, ActionType::AcceptBill, None)
             .await
             .expect("failed to send event");
 
         service
-            .send_request_to_action_rejected_event(&event, ActionType::BuyBill)
+            .send_request_to_action_rejected_event(&event, ActionType::BuyBill, None)
             .await
             .expect("failed to send event");
 
         service
-            .send_request_to_action_rejected_event(&event, ActionType::RecourseBill)
+            .send_request_to_action_rejected_event(&event, ActionType::RecourseBill, None)
             .await
             .expect("failed to send event");
     }
@@ -700,6 +1141,7 @@ mod tests {
                 payment_address: "Address".to_string(),
                 signing_timestamp: timestamp,
                 signing_address: PostalAddress::default(),
+                sell_deadline_seconds: None,
             },
             &keys,
             None,
@@ -738,14 +1180,16 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         service
-            .send_request_to_action_rejected_event(&event, ActionType::CheckBill)
+            .send_request_to_action_rejected_event(&event, ActionType::CheckBill, None)
             .await
             .expect("failed to send event");
     }
@@ -778,10 +1222,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(MockContactServiceApi::new()),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         service
@@ -824,10 +1270,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(MockContactServiceApi::new()),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         service
@@ -843,44 +1291,117 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_send_recourse_action_event() {
-        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
-        let payee = get_identity_public_data("payee", "payee@example.com", None);
-        let buyer = get_identity_public_data("buyer", "buyer@example.com", None);
-        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
-        let mut chain = get_genesis_chain(Some(bill.clone()));
-        let timestamp = now().timestamp() as u64;
-        let keys = get_baseline_identity().key_pair;
-        let block = BillBlock::create_block_for_offer_to_sell(
-            bill.id.to_owned(),
-            chain.get_latest_block(),
-            &BillOfferToSellBlockData {
-                seller: payee.clone().into(),
-                buyer: buyer.clone().into(),
-                sum: 100,
-                currency: "USD".to_string(),
-                signatory: None,
-                payment_address: "Address".to_string(),
-                signing_timestamp: timestamp,
-                signing_address: PostalAddress::default(),
-            },
-            &keys,
-            None,
-            &keys,
-            timestamp,
-        )
-        .unwrap();
+    async fn test_send_maturity_approaching_event() {
+        let holder = get_identity_public_data("holder", "holder@example.com", None);
 
-        chain.try_add_block(block);
+        let mut mock = MockNotificationJsonTransport::new();
+        mock.expect_get_sender_key()
+            .returning(|| "node_id".to_string());
 
-        let event = BillChainEvent::new(
-            &bill,
-            &chain,
-            &BillKeys {
-                private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
-                public_key: TEST_PUB_KEY_SECP.to_owned(),
-            },
-            true,
+        // expect to send the reminder to the holder only
+        mock.expect_send()
+            .withf(|r, e| {
+                r.node_id == "holder"
+                    && check_chain_payload(e, BillEventType::BillMaturityApproaching)
+            })
+            .returning(|_, _| Ok(()))
+            .times(1);
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(mock)],
+            Arc::new(get_notification_store()),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        service
+            .send_maturity_approaching_event("node_id", "bill_id", Some(100), &holder)
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_send_request_to_action_timeout_approaching_event() {
+        let drawee = get_identity_public_data("drawee", "drawee@example.com", None);
+
+        let mut mock = MockNotificationJsonTransport::new();
+        mock.expect_get_sender_key()
+            .returning(|| "node_id".to_string());
+
+        // expect to send the warning to the expected actor only
+        mock.expect_send()
+            .withf(|r, e| {
+                r.node_id == "drawee"
+                    && check_chain_payload(e, BillEventType::BillActionTimeoutApproaching)
+            })
+            .returning(|_, _| Ok(()))
+            .times(1);
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(mock)],
+            Arc::new(get_notification_store()),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        service
+            .send_request_to_action_timeout_approaching_event(
+                "node_id",
+                "bill_id",
+                Some(100),
+                ActionType::AcceptBill,
+                &drawee,
+            )
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_send_recourse_action_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let buyer = get_identity_public_data("buyer", "buyer@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let mut chain = get_genesis_chain(Some(bill.clone()));
+        let timestamp = now().timestamp() as u64;
+        let keys = get_baseline_identity().key_pair;
+        let block = BillBlock::create_block_for_offer_to_sell(
+            bill.id.to_owned(),
+            chain.get_latest_block(),
+            &BillOfferToSellBlockData {
+                seller: payee.clone().into(),
+                buyer: buyer.clone().into(),
+                sum: 100,
+                currency: "USD".to_string(),
+                signatory: None,
+                payment_address: "Address".to_string(),
+                signing_timestamp: timestamp,
+                signing_address: PostalAddress::default(),
+                sell_deadline_seconds: None,
+            },
+            &keys,
+            None,
+            &keys,
+            timestamp,
+        )
+        .unwrap();
+
+        chain.try_add_block(block);
+
+        let event = BillChainEvent::new(
+            &bill,
+            &chain,
+            &BillKeys {
+                private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+                public_key: TEST_PUB_KEY_SECP.to_owned(),
+            },
+            true,
             "node_id",
         )
         .unwrap();
@@ -927,10 +1448,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         service
@@ -965,6 +1488,7 @@ mod tests {
                 payment_address: "Address".to_string(),
                 signing_timestamp: timestamp,
                 signing_address: PostalAddress::default(),
+                sell_deadline_seconds: None,
             },
             &keys,
             None,
@@ -1003,10 +1527,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(MockContactServiceApi::new()),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         service
@@ -1050,10 +1576,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(queue_mock),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let event = BillChainEvent::new(
@@ -1082,6 +1610,8 @@ mod tests {
     ) -> (DefaultNotificationService, BillChainEvent) {
         let mut mock_contact_service = MockContactServiceApi::new();
         let mut mock = MockNotificationJsonTransport::new();
+        mock.expect_get_sender_key()
+            .returning(|| "node_id".to_string());
         for p in participants.into_iter() {
             let clone1 = p.clone();
             mock_contact_service
@@ -1089,9 +1619,6 @@ mod tests {
                 .with(eq(p.0.node_id.clone()))
                 .returning(move |_| Ok(Some(clone1.0.clone())));
 
-            mock.expect_get_sender_key()
-                .returning(|| "node_id".to_string());
-
             let clone2 = p.clone();
             mock.expect_send()
                 .withf(move |r, e| {
@@ -1106,10 +1633,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         (
@@ -1280,32 +1809,298 @@ mod tests {
         let (service, event) = setup_chain_expectation(
             vec![
                 (payee, BillEventType::BillBlock, None),
-                (
-                    payer,
-                    BillEventType::BillPaymentRequested,
-                    Some(ActionType::PayBill),
-                ),
+                (
+                    payer,
+                    BillEventType::BillPaymentRequested,
+                    Some(ActionType::PayBill),
+                ),
+            ],
+            &bill,
+            &chain,
+            true,
+        );
+
+        service
+            .send_request_to_pay_event(&event)
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_resend_bill_event_only_sends_to_given_node() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+
+        // only the payer is expected to receive the event
+        let (service, event) = setup_chain_expectation(
+            vec![(payer.clone(), BillEventType::BillBlock, None)],
+            &bill,
+            &chain,
+            true,
+        );
+
+        service
+            .resend_bill_event(&event, &payer.node_id)
+            .await
+            .expect("failed to resend event");
+    }
+
+    #[tokio::test]
+    async fn test_sent_events_mark_participants_notified() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+
+        let mut mock_contact_service = MockContactServiceApi::new();
+        let payer_clone = payer.clone();
+        mock_contact_service
+            .expect_get_identity_by_node_id()
+            .returning(move |_| Ok(Some(payer_clone.clone())));
+        let mut mock_transport = MockNotificationJsonTransport::new();
+        mock_transport
+            .expect_get_sender_key()
+            .returning(|| "node_id".to_string());
+        mock_transport.expect_send().returning(|_, _| Ok(()));
+        let mut mock_store = MockNotificationStoreApiMock::new();
+        mock_store
+            .expect_set_bill_participant_notified()
+            .with(eq(TEST_BILL_ID), eq(1), eq(payer.node_id.clone()))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(mock_transport)],
+            Arc::new(mock_store),
+            Arc::new(mock_contact_service),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+        let event = BillChainEvent::new(
+            &bill,
+            &chain,
+            &BillKeys {
+                private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+                public_key: TEST_PUB_KEY_SECP.to_owned(),
+            },
+            true,
+            "node_id",
+        )
+        .unwrap();
+
+        service
+            .resend_bill_event(&event, &payer.node_id)
+            .await
+            .expect("failed to resend event");
+    }
+
+    #[tokio::test]
+    async fn test_resend_bill_event_ignores_non_participants() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+
+        let (service, event) = setup_chain_expectation(vec![], &bill, &chain, true);
+
+        service
+            .resend_bill_event(&event, "unknown")
+            .await
+            .expect("failed to resend event");
+    }
+
+    #[tokio::test]
+    async fn test_send_request_to_accept_and_pay_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let mut chain = get_genesis_chain(Some(bill.clone()));
+        let timestamp = now().timestamp() as u64;
+        let keys = get_baseline_identity().key_pair;
+        let req_to_accept_block = BillBlock::create_block_for_request_to_accept(
+            bill.id.to_owned(),
+            chain.get_latest_block(),
+            &BillRequestToAcceptBlockData {
+                requester: payee.clone().into(),
+                signatory: None,
+                signing_timestamp: timestamp,
+                signing_address: PostalAddress::default(),
+            },
+            &keys,
+            None,
+            &keys,
+            timestamp,
+        )
+        .unwrap();
+        chain.try_add_block(req_to_accept_block);
+        let req_to_pay_block = BillBlock::create_block_for_request_to_pay(
+            bill.id.to_owned(),
+            chain.get_latest_block(),
+            &BillRequestToPayBlockData {
+                requester: payee.clone().into(),
+                currency: "USD".to_string(),
+                signatory: None,
+                signing_timestamp: timestamp,
+                signing_address: PostalAddress::default(),
+            },
+            &keys,
+            None,
+            &keys,
+            timestamp,
+        )
+        .unwrap();
+        chain.try_add_block(req_to_pay_block);
+
+        let (service, event) = setup_chain_expectation(
+            vec![
+                (payee, BillEventType::BillBlock, None),
+                (
+                    payer,
+                    BillEventType::BillAcceptanceAndPaymentRequested,
+                    Some(ActionType::PayBill),
+                ),
+            ],
+            &bill,
+            &chain,
+            true,
+        );
+
+        service
+            .send_request_to_accept_and_pay_event(&event.with_new_block_count(2))
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_send_bill_is_paid_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+        let (service, event) = setup_chain_expectation(
+            vec![
+                (payee, BillEventType::BillPaid, Some(ActionType::CheckBill)),
+                (payer, BillEventType::BillBlock, None),
+            ],
+            &bill,
+            &chain,
+            false,
+        );
+
+        service
+            .send_bill_is_paid_event(&event)
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_send_bill_is_endorsed_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let endorsee = get_identity_public_data("endorsee", "endorsee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, Some(&endorsee));
+        let chain = get_genesis_chain(Some(bill.clone()));
+
+        let (service, event) = setup_chain_expectation(
+            vec![
+                (payee, BillEventType::BillBlock, None),
+                (payer, BillEventType::BillBlock, None),
+                (
+                    endorsee,
+                    BillEventType::BillAcceptanceRequested,
+                    Some(ActionType::AcceptBill),
+                ),
+            ],
+            &bill,
+            &chain,
+            false,
+        );
+
+        service
+            .send_bill_is_endorsed_event(&event)
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_send_bill_files_attached_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+        let (service, event) = setup_chain_expectation(
+            vec![
+                (payee, BillEventType::BillBlock, None),
+                (payer, BillEventType::BillBlock, None),
+            ],
+            &bill,
+            &chain,
+            false,
+        );
+
+        service
+            .send_bill_files_attached_event(&event)
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_send_offer_to_sell_cancelled_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+        let (service, event) = setup_chain_expectation(
+            vec![
+                (payee, BillEventType::BillBlock, None),
+                (payer, BillEventType::BillBlock, None),
+            ],
+            &bill,
+            &chain,
+            false,
+        );
+
+        service
+            .send_offer_to_sell_cancelled_event(&event)
+            .await
+            .expect("failed to send event");
+    }
+
+    #[tokio::test]
+    async fn test_send_request_to_accept_cancelled_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+        let (service, event) = setup_chain_expectation(
+            vec![
+                (payee, BillEventType::BillBlock, None),
+                (payer, BillEventType::BillBlock, None),
             ],
             &bill,
             &chain,
-            true,
+            false,
         );
 
         service
-            .send_request_to_pay_event(&event)
+            .send_request_to_accept_cancelled_event(&event)
             .await
             .expect("failed to send event");
     }
 
     #[tokio::test]
-    async fn test_send_bill_is_paid_event() {
+    async fn test_send_bill_acknowledged_event() {
         let payer = get_identity_public_data("drawee", "drawee@example.com", None);
         let payee = get_identity_public_data("payee", "payee@example.com", None);
         let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
         let chain = get_genesis_chain(Some(bill.clone()));
         let (service, event) = setup_chain_expectation(
             vec![
-                (payee, BillEventType::BillPaid, Some(ActionType::CheckBill)),
+                (payee, BillEventType::BillBlock, None),
                 (payer, BillEventType::BillBlock, None),
             ],
             &bill,
@@ -1314,26 +2109,23 @@ mod tests {
         );
 
         service
-            .send_bill_is_paid_event(&event)
+            .send_bill_acknowledged_event(&event)
             .await
             .expect("failed to send event");
     }
 
     #[tokio::test]
-    async fn test_send_bill_is_endorsed_event() {
+    async fn test_send_drawee_corrected_event() {
         let payer = get_identity_public_data("drawee", "drawee@example.com", None);
         let payee = get_identity_public_data("payee", "payee@example.com", None);
-        let endorsee = get_identity_public_data("endorsee", "endorsee@example.com", None);
-        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, Some(&endorsee));
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
         let chain = get_genesis_chain(Some(bill.clone()));
-
         let (service, event) = setup_chain_expectation(
             vec![
                 (payee, BillEventType::BillBlock, None),
-                (payer, BillEventType::BillBlock, None),
                 (
-                    endorsee,
-                    BillEventType::BillAcceptanceRequested,
+                    payer,
+                    BillEventType::BillSigned,
                     Some(ActionType::AcceptBill),
                 ),
             ],
@@ -1343,7 +2135,7 @@ mod tests {
         );
 
         service
-            .send_bill_is_endorsed_event(&event)
+            .send_drawee_corrected_event(&event)
             .await
             .expect("failed to send event");
     }
@@ -1369,6 +2161,7 @@ mod tests {
                 payment_address: "Address".to_string(),
                 signing_timestamp: timestamp,
                 signing_address: PostalAddress::default(),
+                sell_deadline_seconds: None,
             },
             &keys,
             None,
@@ -1400,6 +2193,78 @@ mod tests {
             .expect("failed to send event");
     }
 
+    #[tokio::test]
+    async fn test_send_new_quote_event() {
+        let payer = get_identity_public_data("drawee", "drawee@example.com", None);
+        let payee = get_identity_public_data("payee", "payee@example.com", None);
+        let mint =
+            get_identity_public_data(&BcrKeys::new().get_public_key(), "mint@example.com", None);
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let mut chain = get_genesis_chain(Some(bill.clone()));
+        let timestamp = now().timestamp() as u64;
+        let keys = get_baseline_identity().key_pair;
+        let block = BillBlock::create_block_for_mint(
+            bill.id.to_owned(),
+            chain.get_latest_block(),
+            &BillMintBlockData {
+                endorser: payee.clone().into(),
+                endorsee: mint.clone().into(),
+                sum: 100,
+                currency: "sat".to_string(),
+                signatory: None,
+                signing_timestamp: timestamp,
+                signing_address: PostalAddress::default(),
+            },
+            &keys,
+            None,
+            &keys,
+            timestamp,
+        )
+        .unwrap();
+        chain.try_add_block(block);
+        let block = BillBlock::create_block_for_mint_quote(
+            bill.id.to_owned(),
+            chain.get_latest_block(),
+            &BillMintQuoteBlockData {
+                mint: mint.clone().into(),
+                requester: payee.clone().into(),
+                mint_request_block_id: chain.get_latest_block().id,
+                sum: 90,
+                currency: "sat".to_string(),
+                expiry_timestamp: timestamp + 3600,
+                signatory: None,
+                signing_timestamp: timestamp,
+                signing_address: PostalAddress::default(),
+            },
+            &keys,
+            None,
+            &keys,
+            timestamp,
+        )
+        .unwrap();
+        chain.try_add_block(block);
+
+        let (service, event) = setup_chain_expectation(
+            vec![
+                (
+                    payee.clone(),
+                    BillEventType::BillNewQuote,
+                    Some(ActionType::CheckQuote),
+                ),
+                (payer, BillEventType::BillBlock, None),
+                (mint, BillEventType::BillBlock, None),
+            ],
+            &bill,
+            &chain,
+            true,
+        );
+
+        service
+            .send_new_quote_event(&event, &payee)
+            .await
+            .expect("failed to send event");
+    }
+
     #[tokio::test]
     async fn test_send_bill_is_sold_event() {
         let payer = get_identity_public_data("drawee", "drawee@example.com", None);
@@ -1421,6 +2286,7 @@ mod tests {
                 payment_address: "Address".to_string(),
                 signing_timestamp: timestamp,
                 signing_address: PostalAddress::default(),
+                sell_deadline_seconds: None,
             },
             &keys,
             None,
@@ -1526,14 +2392,25 @@ mod tests {
         let mut mock_store = MockNotificationStoreApiMock::new();
         let result = Notification::new_bill_notification("bill_id", "node_id", "desc", None);
         let returning = result.clone();
+        let cursor = NotificationCursor {
+            datetime: now(),
+            id: "last_id".to_string(),
+        };
         let filter = NotificationFilter {
             active: Some(true),
+            limit: Some(1),
+            after: Some(cursor),
             ..Default::default()
         };
         mock_store
             .expect_list()
             .with(eq(filter.clone()))
-            .returning(move |_| Ok(vec![returning.clone()]));
+            .returning(move |_| {
+                Ok(NotificationPage {
+                    notifications: vec![returning.clone()],
+                    next_cursor: Some(NotificationCursor::new(&returning)),
+                })
+            });
 
         let mut mock_transport = MockNotificationJsonTransport::new();
         mock_transport
@@ -1546,14 +2423,17 @@ mod tests {
             Arc::new(MockContactServiceApi::new()),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let res = service
             .get_client_notifications(filter)
             .await
             .expect("could not get notifications");
-        assert!(!res.is_empty());
-        assert_eq!(res[0].id, result.id);
+        assert!(!res.notifications.is_empty());
+        assert_eq!(res.notifications[0].id, result.id);
+        assert_eq!(res.next_cursor.map(|c| c.id), Some(result.id));
     }
 
     #[tokio::test]
@@ -1575,6 +2455,8 @@ mod tests {
             Arc::new(MockContactServiceApi::new()),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         service
@@ -1583,6 +2465,85 @@ mod tests {
             .expect("could not mark notification as done");
     }
 
+    #[tokio::test]
+    async fn mark_all_notifications_done_for_node_id() {
+        let mut mock_store = MockNotificationStoreApiMock::new();
+        mock_store
+            .expect_mark_all_as_done()
+            .with(eq("node_id"), eq(Some(NotificationType::Bill)))
+            .returning(|_, _| Ok(3));
+
+        let mut mock_transport = MockNotificationJsonTransport::new();
+        mock_transport
+            .expect_get_sender_key()
+            .returning(|| "node_id".to_string());
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(mock_transport)],
+            Arc::new(mock_store),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        let marked = service
+            .mark_all_as_done("node_id", Some(NotificationType::Bill))
+            .await
+            .expect("could not mark all notifications as done");
+        assert_eq!(marked, 3);
+    }
+
+    #[tokio::test]
+    async fn mark_bill_notifications_done_only_for_node_id() {
+        let mut mock_store = MockNotificationStoreApiMock::new();
+        let own = Notification::new_bill_notification("bill_id", "node_id", "desc", None);
+        let own_other = Notification::new_bill_notification("bill_id", "node_id", "desc", None);
+        let company = Notification::new_bill_notification("bill_id", "company", "desc", None);
+        let own_id = own.id.clone();
+        let own_other_id = own_other.id.clone();
+        mock_store
+            .expect_list()
+            .with(eq(NotificationFilter {
+                active: Some(true),
+                reference_id: Some("bill_id".to_string()),
+                ..Default::default()
+            }))
+            .returning(move |_| {
+                Ok(NotificationPage {
+                    notifications: vec![own.clone(), company.clone(), own_other.clone()],
+                    next_cursor: None,
+                })
+            });
+        mock_store
+            .expect_mark_as_done()
+            .withf(move |id| id == own_id || id == own_other_id)
+            .times(2)
+            .returning(|_| Ok(()));
+
+        let mut mock_transport = MockNotificationJsonTransport::new();
+        mock_transport
+            .expect_get_sender_key()
+            .returning(|| "node_id".to_string());
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(mock_transport)],
+            Arc::new(mock_store),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        let marked = service
+            .mark_bill_notifications_done("bill_id", "node_id")
+            .await
+            .expect("could not mark bill notifications as done");
+        assert_eq!(marked, 2);
+    }
+
     fn setup_service_expectation(
         node_id: &str,
         event_type: BillEventType,
@@ -1603,10 +2564,12 @@ mod tests {
             .returning(|_, _| Ok(()));
         DefaultNotificationService::new(
             vec![Arc::new(mock)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(MockContactServiceApi::new()),
             Arc::new(MockNostrQueuedMessageStore::new()),
             "ws://test.relay",
+            3600,
+            10,
         )
     }
 
@@ -1630,6 +2593,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_nostr_consumer() {
+        init_test_cfg();
         let clients = vec![Arc::new(get_mock_nostr_client().await)];
         let contact_service = Arc::new(MockContactServiceApi::new());
         let store = Arc::new(MockNostrEventOffsetStoreApiMock::new());
@@ -1637,6 +2601,7 @@ mod tests {
         let push_service = Arc::new(MockPushService::new());
         let bill_store = Arc::new(MockBillStoreApiMock::new());
         let bill_blockchain_store = Arc::new(MockBillChainStoreApiMock::new());
+        let bill_auto_accept_store = Arc::new(MockBillAutoAcceptStoreApiMock::new());
         let _ = create_nostr_consumer(
             clients,
             contact_service,
@@ -1645,6 +2610,8 @@ mod tests {
             push_service,
             bill_blockchain_store,
             bill_store,
+            bill_auto_accept_store,
+            None,
         )
         .await;
     }
@@ -1667,6 +2634,8 @@ mod tests {
             sender_id: sender_id.to_string(),
             node_id: node_id.to_string(),
             payload: payload.clone(),
+            retry_count: 0,
+            next_retry_at: 0,
         };
 
         let identity = get_identity_public_data(node_id, "test@example.com", None);
@@ -1687,13 +2656,13 @@ mod tests {
         let mut mock_queue = MockNostrQueuedMessageStore::new();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(move |_| Ok(vec![queued_message.clone()]))
+            .with(eq(1), always())
+            .returning(move |_, _| Ok(vec![queued_message.clone()]))
             .once();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(|_| Ok(vec![]));
+            .with(eq(1), always())
+            .returning(|_, _| Ok(vec![]));
         mock_queue
             .expect_succeed_retry()
             .with(eq(message_id))
@@ -1701,10 +2670,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock_transport)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(mock_queue),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let result = service.send_retry_messages().await;
@@ -1729,6 +2700,8 @@ mod tests {
             sender_id: sender_id.to_string(),
             node_id: node_id.to_string(),
             payload: payload.clone(),
+            retry_count: 0,
+            next_retry_at: 0,
         };
 
         let identity = get_identity_public_data(node_id, "test@example.com", None);
@@ -1752,24 +2725,26 @@ mod tests {
         let mut mock_queue = MockNostrQueuedMessageStore::new();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(move |_| Ok(vec![queued_message.clone()]))
+            .with(eq(1), always())
+            .returning(move |_, _| Ok(vec![queued_message.clone()]))
             .once();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(|_| Ok(vec![]));
+            .with(eq(1), always())
+            .returning(|_, _| Ok(vec![]));
         mock_queue
             .expect_fail_retry()
-            .with(eq(message_id))
-            .returning(|_| Ok(()));
+            .with(eq(message_id), always())
+            .returning(|_, _| Ok(()));
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock_transport)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(mock_queue),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let result = service.send_retry_messages().await;
@@ -1805,6 +2780,8 @@ mod tests {
             sender_id: sender_id.to_string(),
             node_id: node_id1.to_string(),
             payload: payload1.clone(),
+            retry_count: 0,
+            next_retry_at: 0,
         };
 
         let queued_message2 = NostrQueuedMessage {
@@ -1812,6 +2789,8 @@ mod tests {
             sender_id: sender_id.to_string(),
             node_id: node_id2.to_string(),
             payload: payload2.clone(),
+            retry_count: 0,
+            next_retry_at: 0,
         };
 
         let identity1 = get_identity_public_data(node_id1, "test1@example.com", None);
@@ -1848,18 +2827,18 @@ mod tests {
         // Return first message, then second message
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(move |_| Ok(vec![queued_message1.clone()]))
+            .with(eq(1), always())
+            .returning(move |_, _| Ok(vec![queued_message1.clone()]))
             .times(1);
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(move |_| Ok(vec![queued_message2.clone()]))
+            .with(eq(1), always())
+            .returning(move |_, _| Ok(vec![queued_message2.clone()]))
             .times(1);
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(|_| Ok(vec![]))
+            .with(eq(1), always())
+            .returning(|_, _| Ok(vec![]))
             .times(1);
 
         mock_queue
@@ -1868,15 +2847,17 @@ mod tests {
             .returning(|_| Ok(()));
         mock_queue
             .expect_fail_retry()
-            .with(eq(message_id2))
-            .returning(|_| Ok(()));
+            .with(eq(message_id2), always())
+            .returning(|_, _| Ok(()));
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock_transport)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(mock_queue),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let result = service.send_retry_messages().await;
@@ -1896,18 +2877,20 @@ mod tests {
             sender_id: sender.to_string(),
             node_id: node_id.to_string(),
             payload: invalid_payload,
+            retry_count: 0,
+            next_retry_at: 0,
         };
 
         let mut mock_queue = MockNostrQueuedMessageStore::new();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(move |_| Ok(vec![queued_message.clone()]))
+            .with(eq(1), always())
+            .returning(move |_, _| Ok(vec![queued_message.clone()]))
             .times(1);
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(|_| Ok(vec![]))
+            .with(eq(1), always())
+            .returning(|_, _| Ok(vec![]))
             .times(1);
 
         let mut mock_transport = MockNotificationJsonTransport::new();
@@ -1917,10 +2900,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock_transport)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(MockContactServiceApi::new()),
             Arc::new(mock_queue),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let result = service.send_retry_messages().await;
@@ -1945,6 +2930,8 @@ mod tests {
             sender_id: sender.to_string(),
             node_id: node_id.to_string(),
             payload: payload.clone(),
+            retry_count: 0,
+            next_retry_at: 0,
         };
 
         let identity = get_identity_public_data(node_id, "test@example.com", None);
@@ -1967,19 +2954,19 @@ mod tests {
         let mut mock_queue = MockNostrQueuedMessageStore::new();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(move |_| Ok(vec![queued_message.clone()]))
+            .with(eq(1), always())
+            .returning(move |_, _| Ok(vec![queued_message.clone()]))
             .times(1);
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(|_| Ok(vec![]))
+            .with(eq(1), always())
+            .returning(|_, _| Ok(vec![]))
             .times(1);
 
         mock_queue
             .expect_fail_retry()
-            .with(eq(message_id))
-            .returning(|_| {
+            .with(eq(message_id), always())
+            .returning(|_, _| {
                 Err(bcr_ebill_persistence::Error::InsertFailed(
                     "Failed to update retry status".to_string(),
                 ))
@@ -1987,10 +2974,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock_transport)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(mock_queue),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let result = service.send_retry_messages().await;
@@ -2015,6 +3004,8 @@ mod tests {
             sender_id: sender.to_string(),
             node_id: node_id.to_string(),
             payload: payload.clone(),
+            retry_count: 0,
+            next_retry_at: 0,
         };
 
         let identity = get_identity_public_data(node_id, "test@example.com", None);
@@ -2035,13 +3026,13 @@ mod tests {
         let mut mock_queue = MockNostrQueuedMessageStore::new();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(move |_| Ok(vec![queued_message.clone()]))
+            .with(eq(1), always())
+            .returning(move |_, _| Ok(vec![queued_message.clone()]))
             .times(1);
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(|_| Ok(vec![]))
+            .with(eq(1), always())
+            .returning(|_, _| Ok(vec![]))
             .times(1);
 
         mock_queue
@@ -2055,10 +3046,12 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock_transport)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(mock_contact_service),
             Arc::new(mock_queue),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let result = service.send_retry_messages().await;
@@ -2070,8 +3063,8 @@ mod tests {
         let mut mock_queue = MockNostrQueuedMessageStore::new();
         mock_queue
             .expect_get_retry_messages()
-            .with(eq(1))
-            .returning(|_| Ok(vec![]))
+            .with(eq(1), always())
+            .returning(|_, _| Ok(vec![]))
             .times(1);
         let mut mock_transport = MockNotificationJsonTransport::new();
         mock_transport
@@ -2080,13 +3073,347 @@ mod tests {
 
         let service = DefaultNotificationService::new(
             vec![Arc::new(mock_transport)],
-            Arc::new(MockNotificationStoreApiMock::new()),
+            Arc::new(get_notification_store()),
             Arc::new(MockContactServiceApi::new()),
             Arc::new(mock_queue),
             "ws://test.relay",
+            3600,
+            10,
         );
 
         let result = service.send_retry_messages().await;
         assert!(result.is_ok());
     }
+
+    fn get_retry_service(
+        mock_transport: MockNotificationJsonTransport,
+        mock_contact_service: MockContactServiceApi,
+        mock_queue: MockNostrQueuedMessageStore,
+        max_retry_delay_seconds: u64,
+    ) -> DefaultNotificationService {
+        DefaultNotificationService::new(
+            vec![Arc::new(mock_transport)],
+            Arc::new(get_notification_store()),
+            Arc::new(mock_contact_service),
+            Arc::new(mock_queue),
+            "ws://test.relay",
+            max_retry_delay_seconds,
+            10,
+        )
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_and_is_capped() {
+        let mut mock_transport = MockNotificationJsonTransport::new();
+        mock_transport
+            .expect_get_sender_key()
+            .returning(|| "node_id".to_string());
+        let service = get_retry_service(
+            mock_transport,
+            MockContactServiceApi::new(),
+            MockNostrQueuedMessageStore::new(),
+            3600,
+        );
+        let delays: Vec<u64> = (0..9).map(|n| service.retry_delay_seconds(n)).collect();
+        assert_eq!(delays, vec![30, 60, 120, 240, 480, 960, 1920, 3600, 3600]);
+        // no overflow for huge retry counts
+        assert_eq!(service.retry_delay_seconds(i32::MAX), 3600);
+    }
+
+    #[tokio::test]
+    async fn test_send_retry_messages_schedules_next_retry_with_backoff() {
+        let node_id = "test_node_id";
+        let sender = "node_id";
+        let payload = serde_json::to_value(EventEnvelope {
+            node_id: node_id.to_string(),
+            version: "1.0".to_string(),
+            event_type: EventType::Bill,
+            data: serde_json::Value::Null,
+        })
+        .unwrap();
+
+        // simulate a message failing over and over again
+        for (retry_count, expected_delay) in [(0, 60), (1, 120), (3, 480), (6, 1000), (9, 1000)] {
+            let queued_message = NostrQueuedMessage {
+                id: "test_message_id".to_string(),
+                sender_id: sender.to_string(),
+                node_id: node_id.to_string(),
+                payload: payload.clone(),
+                retry_count,
+                next_retry_at: 0,
+            };
+            let identity = get_identity_public_data(node_id, "test@example.com", None);
+
+            let mut mock_contact_service = MockContactServiceApi::new();
+            mock_contact_service
+                .expect_get_identity_by_node_id()
+                .returning(move |_| Ok(Some(identity.clone())));
+            let mut mock_transport = MockNotificationJsonTransport::new();
+            mock_transport
+                .expect_get_sender_key()
+                .returning(|| sender.to_string());
+            mock_transport
+                .expect_send()
+                .returning(|_, _| Err(Error::Network("Failed to send".to_string())));
+
+            let now = now().timestamp() as u64;
+            let mut mock_queue = MockNostrQueuedMessageStore::new();
+            mock_queue
+                .expect_get_retry_messages()
+                .with(eq(1), function(move |ts: &u64| *ts >= now))
+                .returning(move |_, _| Ok(vec![queued_message.clone()]))
+                .times(1);
+            mock_queue
+                .expect_get_retry_messages()
+                .returning(|_, _| Ok(vec![]))
+                .times(1);
+            mock_queue
+                .expect_fail_retry()
+                .with(
+                    eq("test_message_id"),
+                    function(move |next_retry_at: &u64| {
+                        // allow for the clock to move on during the test
+                        (now + expected_delay..=now + expected_delay + 5).contains(next_retry_at)
+                    }),
+                )
+                .returning(|_, _| Ok(()))
+                .times(1);
+
+            let service = get_retry_service(mock_transport, mock_contact_service, mock_queue, 1000);
+            let result = service.send_retry_messages().await;
+            assert!(result.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_relay_status_combines_all_transports() {
+        let mut first = MockNotificationJsonTransport::new();
+        first
+            .expect_get_sender_key()
+            .returning(|| "node_id1".to_string());
+        first.expect_get_relay_status().returning(|| {
+            vec![RelayStatus {
+                relay: "ws://relay1".to_string(),
+                success_count: 2,
+                failure_count: 1,
+                last_error: Some("timeout".to_string()),
+            }]
+        });
+        let mut second = MockNotificationJsonTransport::new();
+        second
+            .expect_get_sender_key()
+            .returning(|| "node_id2".to_string());
+        second.expect_get_relay_status().returning(|| {
+            vec![
+                RelayStatus {
+                    relay: "ws://relay1".to_string(),
+                    success_count: 3,
+                    failure_count: 0,
+                    last_error: None,
+                },
+                RelayStatus {
+                    relay: "ws://relay2".to_string(),
+                    success_count: 0,
+                    failure_count: 4,
+                    last_error: Some("connection refused".to_string()),
+                },
+            ]
+        });
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(first), Arc::new(second)],
+            Arc::new(get_notification_store()),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        let status = service
+            .get_relay_status()
+            .await
+            .expect("could not get relay status");
+        assert_eq!(status.len(), 2);
+        assert_eq!(status[0].relay, "ws://relay1");
+        assert_eq!(status[0].success_count, 5);
+        assert_eq!(status[0].failure_count, 1);
+        assert_eq!(status[0].last_error, Some("timeout".to_string()));
+        assert!(status[0].is_healthy());
+        assert_eq!(status[1].relay, "ws://relay2");
+        assert!(!status[1].is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_get_relay_connections_combines_all_transports() {
+        let mut first = MockNotificationJsonTransport::new();
+        first
+            .expect_get_sender_key()
+            .returning(|| "node_id1".to_string());
+        first.expect_get_relay_connections().returning(|| {
+            vec![
+                RelayConnection {
+                    relay: "ws://relay1".to_string(),
+                    connected: false,
+                },
+                RelayConnection {
+                    relay: "ws://relay2".to_string(),
+                    connected: false,
+                },
+            ]
+        });
+        let mut second = MockNotificationJsonTransport::new();
+        second
+            .expect_get_sender_key()
+            .returning(|| "node_id2".to_string());
+        second.expect_get_relay_connections().returning(|| {
+            vec![RelayConnection {
+                relay: "ws://relay1".to_string(),
+                connected: true,
+            }]
+        });
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(first), Arc::new(second)],
+            Arc::new(get_notification_store()),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        let connections = service
+            .get_relay_connections()
+            .await
+            .expect("could not get relay connections");
+        assert_eq!(
+            connections,
+            vec![
+                RelayConnection {
+                    relay: "ws://relay1".to_string(),
+                    connected: true,
+                },
+                RelayConnection {
+                    relay: "ws://relay2".to_string(),
+                    connected: false,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_pending_retry_messages() {
+        let timestamp = now().timestamp() as u64;
+        let mut mock_queue = MockNostrQueuedMessageStore::new();
+        mock_queue
+            .expect_get_pending_messages()
+            .with(eq(10))
+            .returning(move |_| {
+                Ok(vec![
+                    NostrQueuedMessageInfo {
+                        id: "message1".to_string(),
+                        sender_id: "sender".to_string(),
+                        node_id: "node_id1".to_string(),
+                        retry_count: 0,
+                        next_retry_at: timestamp + 60,
+                        created: timestamp - 60,
+                    },
+                    NostrQueuedMessageInfo {
+                        id: "message2".to_string(),
+                        sender_id: "sender".to_string(),
+                        node_id: "node_id2".to_string(),
+                        retry_count: 3,
+                        next_retry_at: timestamp + 480,
+                        created: timestamp - 3600,
+                    },
+                ])
+            });
+        mock_queue
+            .expect_count_dead_letter_messages()
+            .returning(|| Ok(2));
+        let mut mock_transport = MockNotificationJsonTransport::new();
+        mock_transport
+            .expect_get_sender_key()
+            .returning(|| "sender".to_string());
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(mock_transport)],
+            Arc::new(get_notification_store()),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(mock_queue),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        let messages = service
+            .list_pending_retry_messages(10)
+            .await
+            .expect("could not list pending retry messages");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].id, "message1");
+        assert_eq!(messages[0].node_id, "node_id1");
+        assert_eq!(messages[0].retry_count, 0);
+        assert_eq!(messages[0].next_retry_at, timestamp + 60);
+        assert!(messages[0].age_seconds >= 60);
+        assert_eq!(messages[1].node_id, "node_id2");
+        assert_eq!(messages[1].retry_count, 3);
+        assert!(messages[1].age_seconds >= 3600);
+        assert_eq!(
+            service
+                .count_dead_letter_messages()
+                .await
+                .expect("could not count dead-lettered messages"),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_bill_blocks_collects_blocks_of_bill() {
+        let chain = get_genesis_chain(None);
+        let block = chain.get_first_block().clone();
+        let bill_event = |bill_id: &str, blocks: Vec<BillBlock>| -> EventEnvelope {
+            Event::new_bill(
+                "node_id",
+                BillChainEventPayload {
+                    event_type: BillEventType::BillBlock,
+                    bill_id: bill_id.to_string(),
+                    blocks,
+                    ..Default::default()
+                },
+            )
+            .try_into()
+            .unwrap()
+        };
+        // the same block is received twice and another bill's block once
+        let envelopes = vec![
+            bill_event(TEST_BILL_ID, vec![block.clone()]),
+            bill_event("other_bill", vec![block.clone()]),
+            bill_event(TEST_BILL_ID, vec![block.clone()]),
+        ];
+        let mut mock_transport = MockNotificationJsonTransport::new();
+        mock_transport
+            .expect_get_sender_key()
+            .returning(|| "node_id".to_string());
+        mock_transport
+            .expect_fetch_received_events()
+            .returning(move || Ok(envelopes.clone()));
+
+        let service = DefaultNotificationService::new(
+            vec![Arc::new(mock_transport)],
+            Arc::new(get_notification_store()),
+            Arc::new(MockContactServiceApi::new()),
+            Arc::new(MockNostrQueuedMessageStore::new()),
+            "ws://test.relay",
+            3600,
+            10,
+        );
+
+        let blocks = service
+            .fetch_bill_blocks(TEST_BILL_ID)
+            .await
+            .expect("could not fetch bill blocks");
+        assert_eq!(blocks, vec![block]);
+    }
 }
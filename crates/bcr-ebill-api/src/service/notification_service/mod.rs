@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::Config;
 use crate::persistence::identity::IdentityStoreApi;
 use crate::persistence::nostr::NostrEventOffsetStoreApi;
 use crate::persistence::notification::NotificationStoreApi;
-use bcr_ebill_persistence::bill::{BillChainStoreApi, BillStoreApi};
+use crate::{Config, get_config};
+use bcr_ebill_persistence::bill::{BillAutoAcceptStoreApi, BillChainStoreApi, BillStoreApi};
 use bcr_ebill_persistence::company::CompanyStoreApi;
 use bcr_ebill_persistence::nostr::NostrQueuedMessageStoreApi;
 use bcr_ebill_transport::handler::{
@@ -19,12 +20,15 @@ pub mod test_utils;
 
 pub mod default_service;
 mod nostr;
+pub mod webhook;
 
 pub use bcr_ebill_transport::NotificationJsonTransportApi;
 use log::{debug, error};
-pub use nostr::{NostrClient, NostrConfig, NostrConsumer};
+pub use nostr::{NostrClient, NostrConfig, NostrConsumer, NostrConsumerConfig, NostrEventKinds};
 
 use super::contact_service::ContactServiceApi;
+use crate::external::webhook::WebhookClient;
+use webhook::{WebhookDispatcher, WebhookEventHandler};
 
 /// Creates a new nostr client configured with the current identity user.
 pub async fn create_nostr_clients(
@@ -44,11 +48,12 @@ pub async fn create_nostr_clients(
         Ok(identity) => identity.get_nostr_name(),
         _ => "New user".to_owned(),
     };
-    let mut configs: Vec<NostrConfig> = vec![NostrConfig::new(
-        keys,
-        vec![config.nostr_relay.clone()],
-        nostr_name,
-    )];
+    let event_kinds = NostrEventKinds::from_config(config);
+    let mut configs: Vec<NostrConfig> = vec![
+        NostrConfig::new(keys, config.nostr_relays.clone(), nostr_name)
+            .with_event_kinds(event_kinds)
+            .with_max_bill_chain_blocks(config.max_bill_chain_blocks),
+    ];
 
     // optionally collect all company accounts
     let companies = match company_store.get_all().await {
@@ -61,11 +66,11 @@ pub async fn create_nostr_clients(
 
     for (_, (company, keys)) in companies.iter() {
         if let Ok(keys) = keys.clone().try_into() {
-            configs.push(NostrConfig::new(
-                keys,
-                vec![config.nostr_relay.clone()],
-                company.name.clone(),
-            ));
+            configs.push(
+                NostrConfig::new(keys, config.nostr_relays.clone(), company.name.clone())
+                    .with_event_kinds(event_kinds)
+                    .with_max_bill_chain_blocks(config.max_bill_chain_blocks),
+            );
         }
     }
 
@@ -82,16 +87,47 @@ pub async fn create_nostr_clients(
     Ok(clients)
 }
 
-/// Creates a new notification service that will send events via the given Nostr json transport.
+/// Creates a new webhook dispatcher, signing with the current identity key, if there are webhook
+/// endpoints configured.
+pub async fn create_webhook_dispatcher(
+    config: &Config,
+    identity_store: Arc<dyn IdentityStoreApi>,
+) -> Result<Option<Arc<WebhookDispatcher>>> {
+    if config.webhook_urls.is_empty() {
+        return Ok(None);
+    }
+    let keys = identity_store.get_or_create_key_pair().await.map_err(|e| {
+        error!(
+            "Failed to get or create key pair for webhook dispatcher: {}",
+            e
+        );
+        Error::Crypto("Failed to get or create key pair".to_string())
+    })?;
+    debug!(
+        "initializing webhook dispatcher for {} endpoints",
+        config.webhook_urls.len()
+    );
+    Ok(WebhookDispatcher::new(
+        config.webhook_urls.clone(),
+        Arc::new(WebhookClient::new()),
+        keys,
+    )
+    .map(Arc::new))
+}
+
+/// Creates a new notification service that will send events via the given Nostr json transport
+/// and, if given, the webhook dispatcher.
 pub async fn create_notification_service(
     clients: Vec<Arc<NostrClient>>,
     notification_store: Arc<dyn NotificationStoreApi>,
     contact_service: Arc<dyn ContactServiceApi>,
     queued_message_store: Arc<dyn NostrQueuedMessageStoreApi>,
     nostr_relay: &str,
+    max_retry_delay_seconds: u64,
+    max_retries: i32,
+    webhook_dispatcher: Option<Arc<WebhookDispatcher>>,
 ) -> Result<Arc<dyn NotificationServiceApi>> {
-    #[allow(clippy::arc_with_non_send_sync)]
-    Ok(Arc::new(DefaultNotificationService::new(
+    let mut service = DefaultNotificationService::new(
         clients
             .iter()
             .map(|c| c.clone() as Arc<dyn NotificationJsonTransportApi>)
@@ -100,12 +136,20 @@ pub async fn create_notification_service(
         contact_service,
         queued_message_store,
         nostr_relay,
-    )))
+        max_retry_delay_seconds,
+        max_retries,
+    );
+    if let Some(webhook_dispatcher) = webhook_dispatcher {
+        service = service.with_webhook_dispatcher(webhook_dispatcher);
+    }
+    #[allow(clippy::arc_with_non_send_sync)]
+    Ok(Arc::new(service))
 }
 
 /// Creates a new nostr consumer that will listen for incoming events and handle them
 /// with the given handlers. The consumer is just set up here and needs to be started
-/// via the run method later.
+/// via the run method later. If a webhook dispatcher is given, received bill events are
+/// dispatched to the webhook endpoints as well.
 pub async fn create_nostr_consumer(
     clients: Vec<Arc<NostrClient>>,
     contact_service: Arc<dyn ContactServiceApi>,
@@ -114,21 +158,43 @@ pub async fn create_nostr_consumer(
     push_service: Arc<dyn PushApi>,
     bill_blockchain_store: Arc<dyn BillChainStoreApi>,
     bill_store: Arc<dyn BillStoreApi>,
+    bill_auto_accept_store: Arc<dyn BillAutoAcceptStoreApi>,
+    webhook_dispatcher: Option<Arc<WebhookDispatcher>>,
 ) -> Result<NostrConsumer> {
+    let config = get_config();
+    let mut bill_chain_event_handler = BillChainEventHandler::new(
+        notification_store,
+        push_service,
+        bill_blockchain_store,
+        bill_store,
+    )
+    .with_max_chain_blocks(config.max_bill_chain_blocks);
+    if config.auto_accept_enabled {
+        bill_chain_event_handler =
+            bill_chain_event_handler.with_auto_accept(bill_auto_accept_store);
+    }
     // register the logging event handler for all events for now. Later we will probably
     // setup the handlers outside and pass them to the consumer via this functions arguments.
-    let handlers: Vec<Box<dyn NotificationHandlerApi>> = vec![
+    let mut handlers: Vec<Box<dyn NotificationHandlerApi>> = vec![
         Box::new(LoggingEventHandler {
             event_types: EventType::all(),
         }),
-        Box::new(BillChainEventHandler::new(
-            notification_store,
-            push_service,
-            bill_blockchain_store,
-            bill_store,
-        )),
+        Box::new(bill_chain_event_handler),
     ];
+    if let Some(dispatcher) = webhook_dispatcher {
+        handlers.push(Box::new(WebhookEventHandler::new(dispatcher)));
+    }
     debug!("initializing nostr consumer for {} clients", clients.len());
-    let consumer = NostrConsumer::new(clients, contact_service, handlers, nostr_event_offset_store);
+    let consumer = NostrConsumer::new(
+        clients,
+        contact_service,
+        handlers,
+        nostr_event_offset_store,
+        NostrConsumerConfig {
+            max_concurrent_events: config.nostr_max_concurrent_events,
+            event_timeout: Duration::from_secs(config.nostr_event_timeout_seconds),
+            event_types: EventType::all(),
+        },
+    );
     Ok(consumer)
 }
@@ -1,37 +1,124 @@
 use async_trait::async_trait;
 use bcr_ebill_core::{contact::IdentityPublicData, util::crypto};
-use bcr_ebill_transport::event::EventEnvelope;
+use bcr_ebill_transport::event::{EventEnvelope, EventType};
 use bcr_ebill_transport::handler::NotificationHandlerApi;
 use log::{error, info, trace, warn};
 use nostr_sdk::{
-    Client, EventBuilder, EventId, Filter, Kind, Metadata, Options, PublicKey,
-    RelayPoolNotification, SecretKey, Tag, Timestamp, ToBech32, UnsignedEvent,
-    nips::{nip04, nip59::UnwrappedGift},
+    Client, Event, EventBuilder, EventId, Filter, JsonUtil, Kind, Metadata, Options, PublicKey,
+    RelayPoolNotification, RelayUrl, SecretKey, Tag, Timestamp, ToBech32, UnsignedEvent,
+    nips::{
+        nip04,
+        nip59::UnwrappedGift,
+        nip65::{self, RelayMetadata},
+    },
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use crate::Config;
+use crate::constants::{
+    DEFAULT_MAX_BILL_CHAIN_BLOCKS, DEFAULT_NOSTR_BILL_EVENT_KIND, DEFAULT_NOSTR_COMPANY_EVENT_KIND,
+    DEFAULT_NOSTR_EVENT_TIMEOUT_SECONDS, DEFAULT_NOSTR_IDENTITY_EVENT_KIND,
+    DEFAULT_NOSTR_MAX_CONCURRENT_EVENTS, NOSTR_EVENT_TIME_SLACK,
+    NOSTR_EVENTS_FETCH_TIMEOUT_SECONDS, NOSTR_LEGACY_EVENT_KIND,
+    NOSTR_PROFILE_FETCH_TIMEOUT_SECONDS,
+};
+use crate::service::contact_service::ContactServiceApi;
 use crate::util::BcrKeys;
-use crate::{constants::NOSTR_EVENT_TIME_SLACK, service::contact_service::ContactServiceApi};
 use bcr_ebill_core::ServiceTraitBounds;
 use bcr_ebill_persistence::{NostrEventOffset, NostrEventOffsetStoreApi};
-use bcr_ebill_transport::{Error, NotificationJsonTransportApi, Result};
+use bcr_ebill_transport::{
+    Error, NostrProfile, NotificationJsonTransportApi, RelayConnection, RelayStatus, Result,
+};
 
+use tokio::sync::Semaphore;
 use tokio::task::spawn;
+use tokio::time::timeout;
 use tokio_with_wasm::alias as tokio;
 
+/// The Nostr event kinds events are published with, depending on their event type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NostrEventKinds {
+    pub bill: u16,
+    pub company: u16,
+    pub identity: u16,
+}
+
+impl Default for NostrEventKinds {
+    fn default() -> Self {
+        Self {
+            bill: DEFAULT_NOSTR_BILL_EVENT_KIND,
+            company: DEFAULT_NOSTR_COMPANY_EVENT_KIND,
+            identity: DEFAULT_NOSTR_IDENTITY_EVENT_KIND,
+        }
+    }
+}
+
+impl NostrEventKinds {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            bill: config.nostr_bill_event_kind,
+            company: config.nostr_company_event_kind,
+            identity: config.nostr_identity_event_kind,
+        }
+    }
+
+    /// The kind events of the given event type are published with
+    pub fn kind_for(&self, event_type: &EventType) -> Kind {
+        Kind::from(match event_type {
+            EventType::Bill => self.bill,
+            EventType::Company => self.company,
+            EventType::Identity => self.identity,
+        })
+    }
+
+    /// The kinds of the given event types, without duplicates - the legacy kind is always
+    /// included, so we still receive events from nodes publishing with it
+    pub fn kinds_for(&self, event_types: &[EventType]) -> Vec<Kind> {
+        let mut kinds: Vec<Kind> = vec![Kind::from(NOSTR_LEGACY_EVENT_KIND)];
+        for kind in event_types.iter().map(|t| self.kind_for(t)) {
+            if !kinds.contains(&kind) {
+                kinds.push(kind);
+            }
+        }
+        kinds
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NostrConfig {
     keys: BcrKeys,
     relays: Vec<String>,
     name: String,
+    event_kinds: NostrEventKinds,
+    max_bill_chain_blocks: usize,
 }
 
 impl NostrConfig {
     pub fn new(keys: BcrKeys, relays: Vec<String>, name: String) -> Self {
         assert!(!relays.is_empty());
-        Self { keys, relays, name }
+        Self {
+            keys,
+            relays,
+            name,
+            event_kinds: NostrEventKinds::default(),
+            max_bill_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
+        }
+    }
+
+    /// Publish and subscribe events with the given kinds instead of the default ones
+    pub fn with_event_kinds(mut self, event_kinds: NostrEventKinds) -> Self {
+        self.event_kinds = event_kinds;
+        self
+    }
+
+    /// Reject received bill events with more than the given number of blocks, instead of the
+    /// default maximum
+    pub fn with_max_bill_chain_blocks(mut self, max_bill_chain_blocks: usize) -> Self {
+        self.max_bill_chain_blocks = max_bill_chain_blocks;
+        self
     }
 
     #[allow(dead_code)]
@@ -44,7 +131,9 @@ impl NostrConfig {
     }
 }
 
-/// A wrapper around nostr_sdk that implements the NotificationJsonTransportApi.
+/// A wrapper around nostr_sdk that implements the NotificationJsonTransportApi. Events are
+/// published to all configured relays and a send is successful, if at least one relay accepted
+/// the event.
 ///
 /// # Example:
 /// ```no_run
@@ -52,6 +141,8 @@ impl NostrConfig {
 ///     keys: BcrKeys::new(),
 ///     relays: vec!["wss://relay.example.com".to_string()],
 ///     name: "My Company".to_string(),
+///     event_kinds: NostrEventKinds::default(),
+///     max_bill_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
 /// };
 /// let transport = NostrClient::new(&config).await.unwrap();
 /// transport.send(&recipient, event).await.unwrap();
@@ -63,6 +154,10 @@ impl NostrConfig {
 pub struct NostrClient {
     pub keys: BcrKeys,
     pub client: Client,
+    relays: Vec<String>,
+    relay_status: Arc<Mutex<HashMap<String, RelayStatus>>>,
+    event_kinds: NostrEventKinds,
+    max_bill_chain_blocks: usize,
 }
 
 impl NostrClient {
@@ -88,7 +183,31 @@ impl NostrClient {
             error!("Failed to set and send user metadata with Nostr client: {e}");
             Error::Network("Failed to send user metadata with Nostr client".to_string())
         })?;
-        Ok(Self { keys, client })
+        // publish our relays, so contacts can find out where to send events to us
+        let relay_list = config
+            .relays
+            .iter()
+            .filter_map(|relay| RelayUrl::parse(relay).ok())
+            .map(|url| (url, None));
+        if let Err(e) = client
+            .send_event_builder(EventBuilder::relay_list(relay_list))
+            .await
+        {
+            warn!("Failed to send relay list with Nostr client: {e}");
+        }
+        let relay_status = config
+            .relays
+            .iter()
+            .map(|relay| (relay_key(relay), RelayStatus::new(&relay_key(relay))))
+            .collect();
+        Ok(Self {
+            keys,
+            client,
+            relays: config.relays.clone(),
+            relay_status: Arc::new(Mutex::new(relay_status)),
+            event_kinds: config.event_kinds,
+            max_bill_chain_blocks: config.max_bill_chain_blocks,
+        })
     }
 
     pub fn get_node_id(&self) -> String {
@@ -103,7 +222,74 @@ impl NostrClient {
         true
     }
 
-    /// Subscribe to some nostr events with a filter
+    /// The filter for events of the given event types sent to us since the given time
+    pub fn subscription_filter(&self, event_types: &[EventType], since: Timestamp) -> Filter {
+        let filter = Filter::new()
+            .pubkey(self.get_nostr_keys().public_key())
+            .since(since);
+        if self.use_nip04() {
+            filter.kinds(self.event_kinds.kinds_for(event_types))
+        } else {
+            filter.kind(Kind::GiftWrap)
+        }
+    }
+
+    /// Returns the relays to publish an event for the given recipient to - all of our relays
+    /// and the relay of the recipient, which is added to the relay pool, if it isn't there yet
+    async fn get_target_relays(&self, recipient: &IdentityPublicData) -> Vec<String> {
+        let mut relays = self.relays.clone();
+        if let Some(relay) = &recipient.nostr_relay
+            && !relays.iter().any(|r| relay_key(r) == relay_key(relay))
+        {
+            match self.client.add_relay(relay).await {
+                Ok(_) => {
+                    if let Err(e) = self.client.connect_relay(relay).await {
+                        warn!("Failed to connect to recipient relay {relay}: {e}");
+                    }
+                    relays.push(relay.to_owned());
+                }
+                Err(e) => warn!("Failed to add recipient relay {relay}: {e}"),
+            }
+        }
+        relays
+    }
+
+    /// Records the results of publishing an event and fails, if no relay accepted the event
+    fn record_send_result(
+        &self,
+        succeeded: Vec<String>,
+        failed: Vec<(String, String)>,
+    ) -> Result<()> {
+        if let Ok(mut status) = self.relay_status.lock() {
+            for relay in succeeded.iter() {
+                let key = relay_key(relay);
+                status
+                    .entry(key.clone())
+                    .or_insert_with(|| RelayStatus::new(&key))
+                    .success_count += 1;
+            }
+            for (relay, error) in failed.iter() {
+                let key = relay_key(relay);
+                let entry = status
+                    .entry(key.clone())
+                    .or_insert_with(|| RelayStatus::new(&key));
+                entry.failure_count += 1;
+                entry.last_error = Some(error.to_owned());
+            }
+        }
+        for (relay, error) in failed.iter() {
+            warn!("Relay {relay} did not accept Nostr message: {error}");
+        }
+        if succeeded.is_empty() {
+            error!("Error sending Nostr message: no relay accepted the message");
+            return Err(Error::Network(
+                "No relay accepted the Nostr message".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Subscribe to some nostr events with a filter on all relays of the client
     pub async fn subscribe(&self, subscription: Filter) -> Result<()> {
         self.client
             .subscribe(subscription, None)
@@ -131,20 +317,29 @@ impl NostrClient {
         &self,
         note: RelayPoolNotification,
     ) -> Option<(EventEnvelope, PublicKey, EventId, Timestamp)> {
-        let mut result: Option<(EventEnvelope, PublicKey, EventId, Timestamp)> = None;
-        if let RelayPoolNotification::Event { event, .. } = note {
-            if event.kind == Kind::GiftWrap {
-                result = match self.client.unwrap_gift_wrap(&event).await {
-                    Ok(UnwrappedGift { rumor, sender }) => extract_event_envelope(rumor)
-                        .map(|e| (e, sender, event.id, event.created_at)),
-                    Err(e) => {
-                        error!("Unwrapping gift wrap failed: {e}");
-                        None
-                    }
-                }
+        match note {
+            RelayPoolNotification::Event { event, .. } => self.unwrap_nip17_event(&event).await,
+            _ => None,
+        }
+    }
+
+    async fn unwrap_nip17_event(
+        &self,
+        event: &Event,
+    ) -> Option<(EventEnvelope, PublicKey, EventId, Timestamp)> {
+        if event.kind != Kind::GiftWrap {
+            return None;
+        }
+        match self.client.unwrap_gift_wrap(event).await {
+            Ok(UnwrappedGift { rumor, sender }) => {
+                extract_event_envelope(rumor, self.max_bill_chain_blocks)
+                    .map(|e| (e, sender, event.id, event.created_at))
+            }
+            Err(e) => {
+                error!("Unwrapping gift wrap failed: {e}");
+                None
             }
         }
-        result
     }
 
     /// Unwrap envelope from private direct message
@@ -152,30 +347,39 @@ impl NostrClient {
         &self,
         note: RelayPoolNotification,
     ) -> Option<(EventEnvelope, PublicKey, EventId, Timestamp)> {
-        let mut result: Option<(EventEnvelope, PublicKey, EventId, Timestamp)> = None;
-        if let RelayPoolNotification::Event { event, .. } = note {
-            if event.kind == Kind::EncryptedDirectMessage {
-                match nip04::decrypt(
-                    self.keys.get_nostr_keys().secret_key(),
-                    &event.pubkey,
-                    &event.content,
-                ) {
-                    Ok(decrypted) => {
-                        result = extract_text_envelope(&decrypted)
-                            .map(|e| (e, event.pubkey, event.id, event.created_at));
-                    }
-                    Err(e) => {
-                        error!("Decrypting event failed: {e}");
-                    }
-                }
-            } else {
-                info!(
-                    "Received event with kind {} but expected GiftWrap",
-                    event.kind
-                );
+        match note {
+            RelayPoolNotification::Event { event, .. } => self.unwrap_nip04_event(&event),
+            _ => None,
+        }
+    }
+
+    fn unwrap_nip04_event(
+        &self,
+        event: &Event,
+    ) -> Option<(EventEnvelope, PublicKey, EventId, Timestamp)> {
+        if !self
+            .event_kinds
+            .kinds_for(&EventType::all())
+            .contains(&event.kind)
+        {
+            info!(
+                "Received event with kind {} but expected the legacy or one of the configured kinds",
+                event.kind
+            );
+            return None;
+        }
+        match nip04::decrypt(
+            self.keys.get_nostr_keys().secret_key(),
+            &event.pubkey,
+            &event.content,
+        ) {
+            Ok(decrypted) => extract_text_envelope(&decrypted, self.max_bill_chain_blocks)
+                .map(|e| (e, event.pubkey, event.id, event.created_at)),
+            Err(e) => {
+                error!("Decrypting event failed: {e}");
+                None
             }
         }
-        result
     }
 
     pub async fn send_nip04_message(
@@ -189,14 +393,26 @@ impl NostrClient {
                 Error::Crypto("Failed to parse Nostr npub".to_string())
             })?;
             let message = serde_json::to_string(&event)?;
-            let event =
-                create_nip04_event(self.get_nostr_keys().secret_key(), &public_key, &message)?;
-            if let Some(relay) = &recipient.nostr_relay {
-                if let Err(e) = self.client.send_event_builder_to(vec![relay], event).await {
-                    error!("Error sending Nostr message: {e}")
-                };
-            } else if let Err(e) = self.client.send_event_builder(event).await {
-                error!("Error sending Nostr message: {e}")
+            let event = create_nip04_event(
+                self.event_kinds.kind_for(&event.event_type),
+                self.get_nostr_keys().secret_key(),
+                &public_key,
+                &message,
+            )?;
+            let relays = self.get_target_relays(recipient).await;
+            match self.client.send_event_builder_to(&relays, event).await {
+                Ok(output) => self.record_send_result(
+                    output.success.iter().map(|r| r.to_string()).collect(),
+                    output
+                        .failed
+                        .iter()
+                        .map(|(r, e)| (r.to_string(), e.to_string()))
+                        .collect(),
+                )?,
+                Err(e) => self.record_send_result(
+                    vec![],
+                    relays.into_iter().map(|r| (r, e.to_string())).collect(),
+                )?,
             }
         } else {
             error!(
@@ -218,20 +434,24 @@ impl NostrClient {
                 Error::Crypto("Failed to parse Nostr npub".to_string())
             })?;
             let message = serde_json::to_string(&event)?;
-            if let Some(relay) = &recipient.nostr_relay {
-                if let Err(e) = self
-                    .client
-                    .send_private_msg_to(vec![relay], public_key, message, None)
-                    .await
-                {
-                    error!("Error sending Nostr message: {e}")
-                };
-            } else if let Err(e) = self
+            let relays = self.get_target_relays(recipient).await;
+            match self
                 .client
-                .send_private_msg(public_key, message, None)
+                .send_private_msg_to(&relays, public_key, message, None)
                 .await
             {
-                error!("Error sending Nostr message: {e}")
+                Ok(output) => self.record_send_result(
+                    output.success.iter().map(|r| r.to_string()).collect(),
+                    output
+                        .failed
+                        .iter()
+                        .map(|(r, e)| (r.to_string(), e.to_string()))
+                        .collect(),
+                )?,
+                Err(e) => self.record_send_result(
+                    vec![],
+                    relays.into_iter().map(|r| (r, e.to_string())).collect(),
+                )?,
             }
         } else {
             error!(
@@ -263,6 +483,141 @@ impl NotificationJsonTransportApi for NostrClient {
         }
         Ok(())
     }
+    fn get_relay_status(&self) -> Vec<RelayStatus> {
+        let mut relays: Vec<RelayStatus> = match self.relay_status.lock() {
+            Ok(status) => status.values().cloned().collect(),
+            Err(_) => vec![],
+        };
+        relays.sort_by(|a, b| a.relay.cmp(&b.relay));
+        relays
+    }
+    async fn get_relay_connections(&self) -> Vec<RelayConnection> {
+        let pool: HashMap<String, bool> = self
+            .client
+            .relays()
+            .await
+            .into_iter()
+            .map(|(url, relay)| (relay_key(url.as_str()), relay.is_connected()))
+            .collect();
+        self.relays
+            .iter()
+            .map(|relay| RelayConnection {
+                relay: relay_key(relay),
+                connected: pool.get(&relay_key(relay)).copied().unwrap_or(false),
+            })
+            .collect()
+    }
+    async fn fetch_profile(&self, node_id: &str) -> Result<Option<NostrProfile>> {
+        let npub = crypto::get_nostr_npub_as_hex_from_node_id(node_id).map_err(|e| {
+            error!("Failed to get Nostr npub when fetching a profile: {e}");
+            Error::Crypto("Failed to get Nostr npub".to_string())
+        })?;
+        let public_key = PublicKey::from_str(&npub).map_err(|e| {
+            error!("Failed to parse Nostr npub when fetching a profile: {e}");
+            Error::Crypto("Failed to parse Nostr npub".to_string())
+        })?;
+        let filter = Filter::new()
+            .author(public_key)
+            .kinds(vec![Kind::Metadata, Kind::RelayList]);
+        let events: Vec<Event> = self
+            .client
+            .fetch_events(
+                filter,
+                Duration::from_secs(NOSTR_PROFILE_FETCH_TIMEOUT_SECONDS),
+            )
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch Nostr profile of {node_id}: {e}");
+                Error::Network("Failed to fetch Nostr profile".to_string())
+            })?
+            .into_iter()
+            .collect();
+        Ok(extract_profile(&events))
+    }
+    async fn fetch_received_events(&self) -> Result<Vec<EventEnvelope>> {
+        let filter = self.subscription_filter(&EventType::all(), Timestamp::zero());
+        let events: Vec<Event> = self
+            .client
+            .fetch_events(
+                filter,
+                Duration::from_secs(NOSTR_EVENTS_FETCH_TIMEOUT_SECONDS),
+            )
+            .await
+            .map_err(|e| {
+                error!("Failed to fetch received Nostr events: {e}");
+                Error::Network("Failed to fetch received Nostr events".to_string())
+            })?
+            .into_iter()
+            .collect();
+        let mut envelopes = Vec::with_capacity(events.len());
+        for event in events.iter() {
+            let unwrapped = if self.use_nip04() {
+                self.unwrap_nip04_event(event)
+            } else {
+                self.unwrap_nip17_event(event).await
+            };
+            if let Some((envelope, _, _, _)) = unwrapped {
+                envelopes.push(envelope);
+            }
+        }
+        Ok(envelopes)
+    }
+}
+
+/// Creates a profile from the latest metadata and relay list events, since both are replaceable
+/// and relays might still return outdated ones
+fn extract_profile(events: &[Event]) -> Option<NostrProfile> {
+    let latest = |kind: Kind| {
+        events
+            .iter()
+            .filter(|e| e.kind == kind)
+            .max_by_key(|e| e.created_at)
+    };
+    let metadata = latest(Kind::Metadata);
+    let relay_list = latest(Kind::RelayList);
+    if metadata.is_none() && relay_list.is_none() {
+        return None;
+    }
+    let name = metadata
+        .and_then(|e| Metadata::from_json(&e.content).ok())
+        .and_then(|m| m.name);
+    // events are sent to the relays the node reads from
+    let relays = relay_list
+        .map(|e| {
+            nip65::extract_relay_list(e)
+                .filter(|(_, m)| !matches!(m, Some(RelayMetadata::Write)))
+                .map(|(url, _)| url.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(NostrProfile { name, relays })
+}
+
+/// Relay urls are compared without a trailing slash, since the relay pool normalizes them
+fn relay_key(relay: &str) -> String {
+    relay.trim_end_matches('/').to_owned()
+}
+
+/// Limits for processing inbound events in the Nostr consumer
+#[derive(Clone, Debug)]
+pub struct NostrConsumerConfig {
+    /// The maximum number of events that are handled concurrently
+    pub max_concurrent_events: usize,
+    /// The time after which a slow event stops counting towards the concurrently handled events -
+    /// its handling isn't cancelled, so it isn't left half done
+    pub event_timeout: Duration,
+    /// The event types, whose kinds are subscribed to
+    pub event_types: Vec<EventType>,
+}
+
+impl Default for NostrConsumerConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_events: DEFAULT_NOSTR_MAX_CONCURRENT_EVENTS,
+            event_timeout: Duration::from_secs(DEFAULT_NOSTR_EVENT_TIMEOUT_SECONDS),
+            event_types: EventType::all(),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -271,6 +626,7 @@ pub struct NostrConsumer {
     event_handlers: Arc<Vec<Box<dyn NotificationHandlerApi>>>,
     contact_service: Arc<dyn ContactServiceApi>,
     offset_store: Arc<dyn NostrEventOffsetStoreApi>,
+    config: NostrConsumerConfig,
 }
 
 impl NostrConsumer {
@@ -280,6 +636,7 @@ impl NostrConsumer {
         contact_service: Arc<dyn ContactServiceApi>,
         event_handlers: Vec<Box<dyn NotificationHandlerApi>>,
         offset_store: Arc<dyn NostrEventOffsetStoreApi>,
+        config: NostrConsumerConfig,
     ) -> Self {
         let clients = clients
             .into_iter()
@@ -291,6 +648,7 @@ impl NostrConsumer {
             event_handlers: Arc::new(event_handlers),
             contact_service,
             offset_store,
+            config,
         }
     }
 
@@ -301,6 +659,15 @@ impl NostrConsumer {
         let event_handlers = self.event_handlers.clone();
         let contact_service = self.contact_service.clone();
         let offset_store = self.offset_store.clone();
+        let event_timeout = self.config.event_timeout;
+        let event_types = self.config.event_types.clone();
+        // bounds the number of events handled concurrently over all clients
+        let semaphore = Arc::new(Semaphore::new(self.config.max_concurrent_events.max(1)));
+        // the same event is delivered once per relay, so we keep track of the events currently
+        // in progress, since they are only marked as processed after they were handled
+        let in_progress: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+        // events of the same bill are handled one after another, in the order they were received
+        let event_locks: EventLocks = Arc::new(Mutex::new(HashMap::new()));
 
         let mut tasks = Vec::new();
         let local_node_ids = clients.keys().cloned().collect::<Vec<String>>();
@@ -312,18 +679,18 @@ impl NostrConsumer {
             let client_id = node_id.clone();
             let contact_service = contact_service.clone();
             let local_node_ids = local_node_ids.clone();
+            let semaphore = semaphore.clone();
+            let in_progress = in_progress.clone();
+            let event_locks = event_locks.clone();
+            let event_types = event_types.clone();
 
             // Spawn a task for each client
             let task = spawn(async move {
                 // continue where we left off
                 let offset_ts = get_offset(&offset_store, &node_id).await;
-                let public_key = current_client.keys.get_nostr_keys().public_key();
-                let filter = Filter::new()
-                    .pubkey(public_key)
-                    .kind(Kind::EncryptedDirectMessage)
-                    .since(offset_ts);
+                let filter = current_client.subscription_filter(&event_types, offset_ts);
 
-                // subscribe only to private messages sent to our pubkey
+                // subscribe only to private messages sent to our pubkey on all relays
                 current_client
                     .subscribe(filter)
                     .await
@@ -340,25 +707,62 @@ impl NostrConsumer {
                         let client_id = client_id.clone();
                         let contact_service = contact_service.clone();
                         let local_node_ids = local_node_ids.clone();
+                        let semaphore = semaphore.clone();
+                        let in_progress = in_progress.clone();
+                        let event_locks = event_locks.clone();
 
                         async move {
                             if let Some((envelope, sender, event_id, time)) =
                                 client.unwrap_envelope(note).await
-                            {
-                                if !offset_store.is_processed(&event_id.to_hex()).await? {
+                                && !offset_store.is_processed(&event_id.to_hex()).await?
+                                    && start_processing(&in_progress, &event_id.to_hex())
+                                {
                                     let sender_npub = sender.to_bech32();
                                     let sender_node_id = sender.to_hex();
                                     trace!("Received event: {envelope:?} from {sender_npub:?} (hex: {sender_node_id}) on client {client_id}");
-                                    // We use hex here, so we can compare it with our node_ids
-                                    if valid_sender(&sender_node_id, &local_node_ids, &contact_service).await {
-                                        trace!("Processing event: {envelope:?}");
-                                        handle_event(envelope, &node_id, &event_handlers).await?;
-                                    }
-
-                                    // store the new event offset
-                                    add_offset(&offset_store, event_id, time, true, &node_id).await;
-                                }
-                            };
+                                    // wait for the previous event of the same bill to be handled,
+                                    // so the events of a bill are handled in the order they were received
+                                    let ordering = match ordering_key(&envelope) {
+                                        Some(key) => {
+                                            let lock = get_event_lock(&event_locks, &key);
+                                            let guard = lock.clone().lock_owned().await;
+                                            Some((key, lock, guard))
+                                        }
+                                        None => None,
+                                    };
+                                    // wait for a free slot, so we don't take on more events than configured
+                                    let permit = semaphore.acquire_owned().await?;
+                                    let handling = spawn(async move {
+                                        // We use hex here, so we can compare it with our node_ids
+                                        let success = if valid_sender(&sender_node_id, &local_node_ids, &contact_service).await {
+                                            trace!("Processing event: {envelope:?}");
+                                            handle_event(envelope, &node_id, &event_handlers).await
+                                        } else {
+                                            true
+                                        };
+
+                                        // failed events are stored as such, so the offset isn't
+                                        // advanced past them and they are processed again, when
+                                        // they are delivered again
+                                        if !success {
+                                            warn!("Event {} was not handled successfully and will be reprocessed", event_id.to_hex());
+                                        }
+                                        add_offset(&offset_store, event_id, time, success, &node_id).await;
+                                        finish_processing(&in_progress, &event_id.to_hex());
+                                        if let Some((key, lock, guard)) = ordering {
+                                            drop(guard);
+                                            release_event_lock(&event_locks, &key, lock);
+                                        }
+                                    });
+                                    // a slow handler isn't cancelled, since that could leave its
+                                    // changes half done, but it stops taking up a slot after the timeout
+                                    spawn(async move {
+                                        let _permit = permit;
+                                        if timeout(event_timeout, handling).await.is_err() {
+                                            warn!("Handling event {} takes longer than {event_timeout:?}", event_id.to_hex());
+                                        }
+                                    });
+                                };
                             Ok(false)
                         }
                     })
@@ -380,6 +784,54 @@ impl NostrConsumer {
     }
 }
 
+/// Locks per bill id, which serialize the handling of the events of a bill
+type EventLocks = Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>>;
+
+/// The key of the events, which have to be handled one after another - bill events are handled
+/// in order per bill, all other events are handled independently
+fn ordering_key(envelope: &EventEnvelope) -> Option<String> {
+    match envelope.event_type {
+        EventType::Bill => envelope
+            .data
+            .get("bill_id")
+            .and_then(|bill_id| bill_id.as_str())
+            .map(|bill_id| bill_id.to_owned()),
+        _ => None,
+    }
+}
+
+fn get_event_lock(event_locks: &EventLocks, key: &str) -> Arc<tokio::sync::Mutex<()>> {
+    match event_locks.lock() {
+        Ok(mut locks) => locks.entry(key.to_owned()).or_default().clone(),
+        Err(_) => Arc::new(tokio::sync::Mutex::new(())),
+    }
+}
+
+/// Removes the lock of the given key, if no other event is waiting for it
+fn release_event_lock(event_locks: &EventLocks, key: &str, lock: Arc<tokio::sync::Mutex<()>>) {
+    if let Ok(mut locks) = event_locks.lock() {
+        // one reference is held by the map and one by us
+        if Arc::strong_count(&lock) <= 2 {
+            locks.remove(key);
+        }
+    }
+}
+
+/// Marks the event as in progress and returns whether it wasn't in progress already, e.g.
+/// because it was delivered by another relay
+fn start_processing(in_progress: &Mutex<HashSet<String>>, event_id: &str) -> bool {
+    match in_progress.lock() {
+        Ok(mut events) => events.insert(event_id.to_owned()),
+        Err(_) => true,
+    }
+}
+
+fn finish_processing(in_progress: &Mutex<HashSet<String>>, event_id: &str) {
+    if let Ok(mut events) = in_progress.lock() {
+        events.remove(event_id);
+    }
+}
+
 async fn valid_sender(
     node_id: &str,
     local_node_ids: &[String],
@@ -429,22 +881,25 @@ async fn add_offset(
     .ok();
 }
 
-fn extract_text_envelope(message: &str) -> Option<EventEnvelope> {
-    match serde_json::from_str::<EventEnvelope>(message) {
+fn extract_text_envelope(message: &str, max_bill_chain_blocks: usize) -> Option<EventEnvelope> {
+    match EventEnvelope::from_json(message, max_bill_chain_blocks) {
         Ok(envelope) => Some(envelope),
         Err(e) => {
-            error!("Json deserializing event envelope failed: {e}");
+            error!("Parsing event envelope failed: {e}");
             None
         }
     }
 }
 
-fn extract_event_envelope(rumor: UnsignedEvent) -> Option<EventEnvelope> {
+fn extract_event_envelope(
+    rumor: UnsignedEvent,
+    max_bill_chain_blocks: usize,
+) -> Option<EventEnvelope> {
     if rumor.kind == Kind::PrivateDirectMessage {
-        match serde_json::from_str::<EventEnvelope>(rumor.content.as_str()) {
+        match EventEnvelope::from_json(rumor.content.as_str(), max_bill_chain_blocks) {
             Ok(envelope) => Some(envelope),
             Err(e) => {
-                error!("Json deserializing event envelope failed: {e}");
+                error!("Parsing event envelope failed: {e}");
                 None
             }
         }
@@ -454,12 +909,13 @@ fn extract_event_envelope(rumor: UnsignedEvent) -> Option<EventEnvelope> {
 }
 
 fn create_nip04_event(
+    kind: Kind,
     secret_key: &SecretKey,
     public_key: &PublicKey,
     message: &str,
 ) -> Result<EventBuilder> {
     Ok(EventBuilder::new(
-        Kind::EncryptedDirectMessage,
+        kind,
         nip04::encrypt(secret_key, public_key, message).map_err(|e| {
             error!("Failed to encrypt direct private message: {e}");
             Error::Crypto("Failed to encrypt direct private message".to_string())
@@ -468,19 +924,24 @@ fn create_nip04_event(
     .tag(Tag::public_key(*public_key)))
 }
 
-/// Handle extracted event with given handlers.
+/// Handle extracted event with given handlers. Returns whether all subscribed handlers
+/// handled the event successfully.
 async fn handle_event(
     event: EventEnvelope,
     node_id: &str,
     handlers: &Arc<Vec<Box<dyn NotificationHandlerApi>>>,
-) -> Result<()> {
+) -> bool {
     let event_type = &event.event_type;
     let mut times = 0;
+    let mut success = true;
     for handler in handlers.iter() {
         if handler.handles_event(event_type) {
             match handler.handle_event(event.to_owned(), node_id).await {
                 Ok(_) => times += 1,
-                Err(e) => error!("Nostr event handler failed: {e}"),
+                Err(e) => {
+                    error!("Nostr event handler failed: {e}");
+                    success = false;
+                }
             }
         }
     }
@@ -489,7 +950,7 @@ async fn handle_event(
     } else {
         trace!("{event_type:?} event handled successfully {times} times");
     }
-    Ok(())
+    success
 }
 
 #[cfg(test)]
@@ -497,13 +958,19 @@ mod tests {
     use std::{sync::Arc, time::Duration};
 
     use bcr_ebill_core::{ServiceTraitBounds, notification::BillEventType};
-    use bcr_ebill_transport::event::{Event, EventType};
+    use bcr_ebill_transport::event::{Event, EventEnvelope, EventType};
     use bcr_ebill_transport::handler::NotificationHandlerApi;
     use mockall::predicate;
+    use nostr_sdk::{Kind, Timestamp};
     use tokio::time;
 
     use super::super::test_utils::get_mock_relay;
-    use super::{NostrClient, NostrConfig, NostrConsumer};
+    use super::{
+        NostrClient, NostrConfig, NostrConsumer, NostrConsumerConfig, NostrEventKinds,
+        finish_processing, get_event_lock, handle_event, ordering_key, release_event_lock,
+        start_processing,
+    };
+    use crate::constants::DEFAULT_MAX_BILL_CHAIN_BLOCKS;
     use crate::persistence::nostr::NostrEventOffset;
     use crate::service::{
         contact_service::MockContactServiceApi,
@@ -539,6 +1006,8 @@ mod tests {
             keys: keys1.clone(),
             relays: vec![url.to_string()],
             name: "BcrDamus1".to_string(),
+            event_kinds: NostrEventKinds::default(),
+            max_bill_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
         };
         let client1 = NostrClient::new(&config1)
             .await
@@ -548,6 +1017,8 @@ mod tests {
             keys: keys2.clone(),
             relays: vec![url.to_string()],
             name: "BcrDamus2".to_string(),
+            event_kinds: NostrEventKinds::default(),
+            max_bill_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
         };
         let client2 = NostrClient::new(&config2)
             .await
@@ -617,6 +1088,7 @@ mod tests {
             Arc::new(contact_service),
             vec![Box::new(handler)],
             Arc::new(offset_store),
+            NostrConsumerConfig::default(),
         );
 
         // run in a local set
@@ -641,4 +1113,306 @@ mod tests {
             })
             .await;
     }
+
+    #[tokio::test]
+    async fn test_handle_event_fails_if_a_handler_fails() {
+        let event = create_test_event(&BillEventType::BillSigned);
+
+        let mut ok_handler = MockNotificationHandler::new();
+        ok_handler.expect_handles_event().returning(|_| true);
+        ok_handler.expect_handle_event().returning(|_, _| Ok(()));
+
+        let mut failing_handler = MockNotificationHandler::new();
+        failing_handler.expect_handles_event().returning(|_| true);
+        failing_handler
+            .expect_handle_event()
+            .returning(|_, _| Err(bcr_ebill_transport::Error::Blockchain("test".to_string())));
+
+        let handlers: Arc<Vec<Box<dyn NotificationHandlerApi>>> =
+            Arc::new(vec![Box::new(ok_handler), Box::new(failing_handler)]);
+        let success = handle_event(
+            event.try_into().expect("could not convert event"),
+            "node_id",
+            &handlers,
+        )
+        .await;
+        assert!(!success);
+    }
+
+    #[tokio::test]
+    async fn test_handle_event_succeeds_if_all_handlers_succeed() {
+        let event = create_test_event(&BillEventType::BillSigned);
+
+        let mut handler = MockNotificationHandler::new();
+        handler.expect_handles_event().returning(|_| true);
+        handler
+            .expect_handle_event()
+            .returning(|_, _| Ok(()))
+            .once();
+
+        let mut other_handler = MockNotificationHandler::new();
+        other_handler.expect_handles_event().returning(|_| false);
+        other_handler.expect_handle_event().never();
+
+        let handlers: Arc<Vec<Box<dyn NotificationHandlerApi>>> =
+            Arc::new(vec![Box::new(handler), Box::new(other_handler)]);
+        let success = handle_event(
+            event.try_into().expect("could not convert event"),
+            "node_id",
+            &handlers,
+        )
+        .await;
+        assert!(success);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_profile_of_other_node() {
+        let relay = get_mock_relay().await;
+        let url = relay.url();
+        let client1 = NostrClient::new(&NostrConfig {
+            keys: BcrKeys::new(),
+            relays: vec![url.to_string()],
+            name: "BcrDamus1".to_string(),
+            event_kinds: NostrEventKinds::default(),
+            max_bill_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
+        })
+        .await
+        .expect("failed to create nostr client 1");
+        let client2 = NostrClient::new(&NostrConfig {
+            keys: BcrKeys::new(),
+            relays: vec![url.to_string()],
+            name: "BcrDamus2".to_string(),
+            event_kinds: NostrEventKinds::default(),
+            max_bill_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
+        })
+        .await
+        .expect("failed to create nostr client 2");
+
+        // clients publish their metadata and relay list on creation
+        let profile = client2
+            .fetch_profile(&client1.get_node_id())
+            .await
+            .expect("failed to fetch profile")
+            .expect("profile of client 1 exists");
+        assert_eq!(profile.name, Some("BcrDamus1".to_string()));
+        assert_eq!(profile.relays.len(), 1);
+
+        let unknown = client2
+            .fetch_profile(&BcrKeys::new().get_public_key())
+            .await
+            .expect("failed to fetch profile");
+        assert!(unknown.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_events_are_published_with_configured_kind() {
+        let relay = get_mock_relay().await;
+        let url = relay.url();
+        let event_kinds = NostrEventKinds {
+            bill: 9001,
+            company: 9002,
+            identity: 9003,
+        };
+        let client1 = NostrClient::new(
+            &NostrConfig::new(
+                BcrKeys::new(),
+                vec![url.to_string()],
+                "BcrDamus1".to_string(),
+            )
+            .with_event_kinds(event_kinds),
+        )
+        .await
+        .expect("failed to create nostr client 1");
+        let keys2 = BcrKeys::new();
+        let client2 = NostrClient::new(
+            &NostrConfig::new(
+                keys2.clone(),
+                vec![url.to_string()],
+                "BcrDamus2".to_string(),
+            )
+            .with_event_kinds(event_kinds),
+        )
+        .await
+        .expect("failed to create nostr client 2");
+
+        let contact =
+            get_identity_public_data(&keys2.get_public_key(), "payee@example.com", Some(&url));
+        let mut event = create_test_event(&BillEventType::BillSigned);
+        event.node_id = contact.node_id.to_owned();
+        client1
+            .send(&contact, event.try_into().expect("could not convert event"))
+            .await
+            .expect("failed to send event");
+
+        let filter = client2.subscription_filter(&[EventType::Bill], Timestamp::zero());
+        let published: Vec<nostr_sdk::Event> = client2
+            .client
+            .fetch_events(filter.clone(), Duration::from_secs(5))
+            .await
+            .expect("failed to fetch events")
+            .into_iter()
+            .collect();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].kind, Kind::from(9001));
+        assert!(filter.match_event(&published[0]));
+
+        // subscriptions for other event types don't match
+        let company_filter = client2.subscription_filter(&[EventType::Company], Timestamp::zero());
+        assert!(!company_filter.match_event(&published[0]));
+
+        // and the receiver unwraps events with the configured kinds
+        let received = client2
+            .fetch_received_events()
+            .await
+            .expect("failed to fetch received events");
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].event_type, EventType::Bill);
+    }
+
+    #[tokio::test]
+    async fn test_events_with_legacy_kind_are_still_received() {
+        let relay = get_mock_relay().await;
+        let url = relay.url();
+        // the sender publishes with the default legacy kind
+        let client1 = NostrClient::new(&NostrConfig::new(
+            BcrKeys::new(),
+            vec![url.to_string()],
+            "BcrDamus1".to_string(),
+        ))
+        .await
+        .expect("failed to create nostr client 1");
+        let keys2 = BcrKeys::new();
+        let client2 = NostrClient::new(
+            &NostrConfig::new(
+                keys2.clone(),
+                vec![url.to_string()],
+                "BcrDamus2".to_string(),
+            )
+            .with_event_kinds(NostrEventKinds {
+                bill: 9001,
+                company: 9002,
+                identity: 9003,
+            }),
+        )
+        .await
+        .expect("failed to create nostr client 2");
+
+        let contact =
+            get_identity_public_data(&keys2.get_public_key(), "payee@example.com", Some(&url));
+        let mut event = create_test_event(&BillEventType::BillSigned);
+        event.node_id = contact.node_id.to_owned();
+        client1
+            .send(&contact, event.try_into().expect("could not convert event"))
+            .await
+            .expect("failed to send event");
+
+        let filter = client2.subscription_filter(&[EventType::Bill], Timestamp::zero());
+        let published: Vec<nostr_sdk::Event> = client2
+            .client
+            .fetch_events(filter.clone(), Duration::from_secs(5))
+            .await
+            .expect("failed to fetch events")
+            .into_iter()
+            .collect();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].kind, Kind::EncryptedDirectMessage);
+
+        let received = client2
+            .fetch_received_events()
+            .await
+            .expect("failed to fetch received events");
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].event_type, EventType::Bill);
+    }
+
+    #[tokio::test]
+    async fn test_relay_status_is_tracked_per_relay() {
+        let relay = get_mock_relay().await;
+        let url = relay.url();
+        let client = NostrClient::new(&NostrConfig {
+            keys: BcrKeys::new(),
+            relays: vec![url.to_string()],
+            name: "BcrDamus1".to_string(),
+            event_kinds: NostrEventKinds::default(),
+            max_bill_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
+        })
+        .await
+        .expect("failed to create nostr client");
+
+        // all configured relays are listed, even without any sent events
+        let status = client.get_relay_status();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].success_count, 0);
+        assert!(status[0].is_healthy());
+
+        // one relay accepting the message is enough
+        let result = client.record_send_result(
+            vec![url.to_string()],
+            vec![(
+                "ws://dead.relay".to_string(),
+                "connection refused".to_string(),
+            )],
+        );
+        assert!(result.is_ok());
+        // no relay accepting the message fails
+        let result = client.record_send_result(
+            vec![],
+            vec![("ws://dead.relay/".to_string(), "timeout".to_string())],
+        );
+        assert!(result.is_err());
+
+        let status = client.get_relay_status();
+        assert_eq!(status.len(), 2);
+        let dead = status
+            .iter()
+            .find(|s| s.relay == "ws://dead.relay")
+            .expect("dead relay is tracked");
+        assert_eq!(dead.success_count, 0);
+        assert_eq!(dead.failure_count, 2);
+        assert_eq!(dead.last_error, Some("timeout".to_string()));
+        assert!(!dead.is_healthy());
+        let alive = status
+            .iter()
+            .find(|s| s.relay != "ws://dead.relay")
+            .expect("configured relay is tracked");
+        assert_eq!(alive.success_count, 1);
+        assert_eq!(alive.failure_count, 0);
+        assert!(alive.is_healthy());
+    }
+
+    #[test]
+    fn test_events_delivered_by_multiple_relays_are_processed_once() {
+        let in_progress = std::sync::Mutex::new(std::collections::HashSet::new());
+        assert!(start_processing(&in_progress, "event_id"));
+        // delivered by a second relay while still being processed
+        assert!(!start_processing(&in_progress, "event_id"));
+        assert!(start_processing(&in_progress, "other_event_id"));
+        finish_processing(&in_progress, "event_id");
+        assert!(start_processing(&in_progress, "event_id"));
+    }
+
+    #[test]
+    fn test_bill_events_are_ordered_by_bill_id() {
+        let mut envelope: EventEnvelope = create_test_event(&BillEventType::BillSigned)
+            .try_into()
+            .expect("could not convert event");
+        assert_eq!(ordering_key(&envelope), None);
+        envelope.data["bill_id"] = serde_json::Value::from("bill_id");
+        assert_eq!(ordering_key(&envelope), Some("bill_id".to_string()));
+        envelope.event_type = EventType::Company;
+        assert_eq!(ordering_key(&envelope), None);
+    }
+
+    #[test]
+    fn test_event_lock_is_shared_until_released() {
+        let event_locks = Arc::new(std::sync::Mutex::new(std::collections::HashMap::new()));
+        let lock = get_event_lock(&event_locks, "bill_id");
+        let waiting = get_event_lock(&event_locks, "bill_id");
+        assert!(Arc::ptr_eq(&lock, &waiting));
+        // another event is still waiting for the lock
+        release_event_lock(&event_locks, "bill_id", lock);
+        assert_eq!(event_locks.lock().unwrap().len(), 1);
+        release_event_lock(&event_locks, "bill_id", waiting);
+        assert!(event_locks.lock().unwrap().is_empty());
+    }
 }
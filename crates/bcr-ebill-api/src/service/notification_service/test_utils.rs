@@ -2,11 +2,12 @@ use crate::{
     data::{bill::BitcreditBill, contact::IdentityPublicData},
     persistence::DbContext,
     tests::tests::{
-        MockBackupStoreApiMock, MockBillChainStoreApiMock, MockBillStoreApiMock,
-        MockCompanyChainStoreApiMock, MockCompanyStoreApiMock, MockContactStoreApiMock,
-        MockFileUploadStoreApiMock, MockIdentityChainStoreApiMock, MockIdentityStoreApiMock,
-        MockNostrEventOffsetStoreApiMock, MockNostrQueuedMessageStore,
-        MockNotificationStoreApiMock, empty_bitcredit_bill, identity_public_data_only_node_id,
+        MockBackupStoreApiMock, MockBillAutoAcceptStoreApiMock, MockBillChainStoreApiMock,
+        MockBillStoreApiMock, MockBillTemplateStoreApiMock, MockCompanyChainStoreApiMock,
+        MockCompanyStoreApiMock, MockContactStoreApiMock, MockFileUploadStoreApiMock,
+        MockIdentityChainStoreApiMock, MockIdentityStoreApiMock, MockNostrEventOffsetStoreApiMock,
+        MockNostrQueuedMessageStore, MockNotificationStoreApiMock, empty_bitcredit_bill,
+        identity_public_data_only_node_id,
     },
     util::BcrKeys,
 };
@@ -143,6 +144,8 @@ pub fn get_mock_db_context() -> DbContext {
         contact_store: Arc::new(MockContactStoreApiMock::new()),
         bill_store: Arc::new(MockBillStoreApiMock::new()),
         bill_blockchain_store: Arc::new(MockBillChainStoreApiMock::new()),
+        bill_template_store: Arc::new(MockBillTemplateStoreApiMock::new()),
+        bill_auto_accept_store: Arc::new(MockBillAutoAcceptStoreApiMock::new()),
         identity_store: Arc::new(MockIdentityStoreApiMock::new()),
         identity_chain_store: Arc::new(MockIdentityChainStoreApiMock::new()),
         company_store: Arc::new(MockCompanyStoreApiMock::new()),
@@ -0,0 +1,308 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bcr_ebill_core::ServiceTraitBounds;
+use bcr_ebill_core::notification::BillEventType;
+use bcr_ebill_transport::handler::NotificationHandlerApi;
+use bcr_ebill_transport::{BillChainEventPayload, Event, EventEnvelope, EventType};
+use log::{debug, error, warn};
+use serde::{Deserialize, Serialize};
+use tokio_with_wasm::alias as tokio;
+
+use crate::external::webhook::WebhookClientApi;
+use crate::util::{self, BcrKeys, crypto};
+
+/// The payload posted to the configured webhook endpoints for every bill event. It only contains
+/// identifiers - receivers fetch the details via the API
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookPayload {
+    pub bill_id: String,
+    pub event_type: BillEventType,
+    pub timestamp: u64,
+    pub sender: String,
+}
+
+/// Posts signed bill events to a list of configured HTTP endpoints. The body is signed with the
+/// local node key - the signature is a schnorr signature over the base58 encoded sha256 hash of
+/// the body, which can be verified with the node id sent along
+pub struct WebhookDispatcher {
+    urls: Vec<String>,
+    client: Arc<dyn WebhookClientApi>,
+    keys: BcrKeys,
+    retry_base_delay_millis: u64,
+}
+
+impl WebhookDispatcher {
+    // the number of attempts to deliver an event to a single endpoint
+    const MAX_ATTEMPTS: u32 = 5;
+    // the delay before the first retry, which is doubled with every failed retry
+    const RETRY_BASE_DELAY_MILLIS: u64 = 1000;
+
+    /// Creates a new dispatcher - returns None if no endpoints are configured, since there is
+    /// nothing to dispatch to
+    pub fn new(
+        urls: Vec<String>,
+        client: Arc<dyn WebhookClientApi>,
+        keys: BcrKeys,
+    ) -> Option<Self> {
+        if urls.is_empty() {
+            return None;
+        }
+        Some(Self {
+            urls,
+            client,
+            keys,
+            retry_base_delay_millis: Self::RETRY_BASE_DELAY_MILLIS,
+        })
+    }
+
+    /// Sends the given payload to all configured endpoints, retrying with backoff on failure
+    pub async fn dispatch(&self, payload: &WebhookPayload) {
+        let body = match serde_json::to_string(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("Failed to serialize webhook payload: {e}");
+                return;
+            }
+        };
+        let signature = match self.sign(&body) {
+            Ok(signature) => signature,
+            Err(e) => {
+                error!("Failed to sign webhook payload: {e}");
+                return;
+            }
+        };
+        let signer = self.keys.get_public_key();
+
+        for url in self.urls.iter() {
+            self.deliver(url, &body, &signature, &signer).await;
+        }
+    }
+
+    fn sign(&self, body: &str) -> crypto::Result<String> {
+        let hash = util::sha256_hash(body.as_bytes());
+        crypto::signature(&hash, &self.keys.get_private_key_string())
+    }
+
+    async fn deliver(&self, url: &str, body: &str, signature: &str, signer: &str) {
+        for attempt in 0..Self::MAX_ATTEMPTS {
+            match self.client.post(url, body, signature, signer).await {
+                Ok(()) => {
+                    debug!("Delivered webhook event to {url}");
+                    return;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to deliver webhook event to {url} (attempt {}): {e}",
+                        attempt + 1
+                    );
+                }
+            }
+            if attempt + 1 < Self::MAX_ATTEMPTS {
+                let delay = self.retry_base_delay_millis * 2u64.pow(attempt);
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+        }
+        error!(
+            "Giving up delivering webhook event to {url} after {} attempts",
+            Self::MAX_ATTEMPTS
+        );
+    }
+}
+
+/// Dispatches received bill events to the configured webhook endpoints, so they are notified
+/// about events from other participants as well, not only about the ones we send
+pub struct WebhookEventHandler {
+    dispatcher: Arc<WebhookDispatcher>,
+}
+
+impl WebhookEventHandler {
+    pub fn new(dispatcher: Arc<WebhookDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
+
+impl ServiceTraitBounds for WebhookEventHandler {}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NotificationHandlerApi for WebhookEventHandler {
+    fn handles_event(&self, event_type: &EventType) -> bool {
+        event_type == &EventType::Bill
+    }
+
+    async fn handle_event(
+        &self,
+        event: EventEnvelope,
+        _node_id: &str,
+    ) -> bcr_ebill_transport::Result<()> {
+        let decoded = match Event::<BillChainEventPayload>::try_from(event) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                warn!("Could not decode bill event for webhook dispatch: {e}");
+                return Ok(());
+            }
+        };
+        // the sender is the signer of the latest block the event carries
+        let sender = decoded
+            .data
+            .blocks
+            .last()
+            .map(|b| b.public_key.clone())
+            .unwrap_or_default();
+        let payload = WebhookPayload {
+            bill_id: decoded.data.bill_id,
+            event_type: decoded.data.event_type,
+            timestamp: util::date::now().timestamp() as u64,
+            sender,
+        };
+        // delivery retries with backoff, so it must not hold up the event processing
+        let dispatcher = self.dispatcher.clone();
+        tokio::spawn(async move {
+            dispatcher.dispatch(&payload).await;
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::webhook::{self, MockWebhookClientApi};
+    use crate::test_utils::{TEST_BILL_ID, get_genesis_chain};
+    use bcr_ebill_core::blockchain::Blockchain;
+    use mockall::Sequence;
+
+    fn payload() -> WebhookPayload {
+        WebhookPayload {
+            bill_id: "1234".to_string(),
+            event_type: BillEventType::BillAccepted,
+            timestamp: 1731593928,
+            sender: "sender".to_string(),
+        }
+    }
+
+    fn dispatcher(urls: Vec<String>, client: MockWebhookClientApi) -> WebhookDispatcher {
+        WebhookDispatcher {
+            urls,
+            client: Arc::new(client),
+            keys: BcrKeys::new(),
+            retry_base_delay_millis: 0,
+        }
+    }
+
+    #[test]
+    fn no_dispatcher_without_urls() {
+        assert!(
+            WebhookDispatcher::new(
+                vec![],
+                Arc::new(MockWebhookClientApi::new()),
+                BcrKeys::new()
+            )
+            .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_sends_signed_payload_to_all_urls() {
+        let keys = BcrKeys::new();
+        let public_key = keys.get_public_key();
+        let private_key = keys.get_private_key_string();
+        let mut client = MockWebhookClientApi::new();
+        client
+            .expect_post()
+            .withf(move |url, body, signature, signer| {
+                let hash = util::sha256_hash(body.as_bytes());
+                let payload: WebhookPayload = serde_json::from_str(body).unwrap();
+                (url == "http://one" || url == "http://two")
+                    && signer == public_key
+                    && payload.bill_id == "1234"
+                    && !body.contains(&private_key)
+                    && crypto::verify(&hash, signature, signer).unwrap()
+            })
+            .times(2)
+            .returning(|_, _, _, _| Ok(()));
+        let mut dispatcher = dispatcher(
+            vec!["http://one".to_string(), "http://two".to_string()],
+            client,
+        );
+        dispatcher.keys = keys;
+
+        dispatcher.dispatch(&payload()).await;
+    }
+
+    #[tokio::test]
+    async fn dispatch_retries_on_failure() {
+        let mut client = MockWebhookClientApi::new();
+        let mut seq = Sequence::new();
+        client
+            .expect_post()
+            .times(2)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _| Err(webhook::Error::UnexpectedStatus(500).into()));
+        client
+            .expect_post()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_, _, _, _| Ok(()));
+
+        dispatcher(vec!["http://one".to_string()], client)
+            .dispatch(&payload())
+            .await;
+    }
+
+    #[tokio::test]
+    async fn dispatch_gives_up_after_max_attempts() {
+        let mut client = MockWebhookClientApi::new();
+        client
+            .expect_post()
+            .times(WebhookDispatcher::MAX_ATTEMPTS as usize)
+            .returning(|_, _, _, _| Err(webhook::Error::UnexpectedStatus(503).into()));
+
+        dispatcher(vec!["http://one".to_string()], client)
+            .dispatch(&payload())
+            .await;
+    }
+
+    #[tokio::test]
+    async fn handler_dispatches_received_bill_events() {
+        let chain = get_genesis_chain(None);
+        let signer = chain.get_first_block().public_key.clone();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut client = MockWebhookClientApi::new();
+        client
+            .expect_post()
+            .times(1)
+            .returning(move |_, body, _, _| {
+                tx.send(serde_json::from_str::<WebhookPayload>(body).unwrap())
+                    .unwrap();
+                Ok(())
+            });
+        let handler =
+            WebhookEventHandler::new(Arc::new(dispatcher(vec!["http://one".to_string()], client)));
+        assert!(handler.handles_event(&EventType::Bill));
+
+        let event = Event::new_bill(
+            "node_id",
+            BillChainEventPayload {
+                event_type: BillEventType::BillAccepted,
+                bill_id: TEST_BILL_ID.to_string(),
+                action_type: None,
+                sum: None,
+                reason: None,
+                keys: None,
+                blocks: vec![chain.get_first_block().clone()],
+            },
+        );
+        handler
+            .handle_event(event.try_into().unwrap(), "node_id")
+            .await
+            .unwrap();
+
+        let payload = rx.recv().await.unwrap();
+        assert_eq!(payload.bill_id, TEST_BILL_ID);
+        assert_eq!(payload.event_type, BillEventType::BillAccepted);
+        assert_eq!(payload.sender, signer);
+    }
+}
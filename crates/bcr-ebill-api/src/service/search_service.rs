@@ -4,7 +4,11 @@ use super::{
     contact_service::ContactServiceApi,
 };
 use crate::data::GeneralSearchResult;
-use crate::data::{GeneralSearchFilterItemType, bill::BillsFilterRole};
+use crate::data::{
+    GeneralSearchFilterItemType,
+    bill::{BillsFilterRole, BillsFilterStatus},
+};
+use crate::util::search;
 use async_trait::async_trait;
 use log::debug;
 use std::sync::Arc;
@@ -68,6 +72,7 @@ impl SearchServiceApi for SearchService {
                     None,
                     None,
                     &BillsFilterRole::All,
+                    &BillsFilterStatus::All,
                     current_identity_node_id,
                 )
                 .await?
@@ -75,14 +80,36 @@ impl SearchServiceApi for SearchService {
             vec![]
         };
 
+        // exact matches first - only if there are none, we fall back to normalized and fuzzy
+        // matching on all entries
         let contacts = if item_types.contains(&GeneralSearchFilterItemType::Contact) {
-            self.contact_service.search(&search_term_lc).await?
+            let exact_matches = self.contact_service.search(&search_term_lc).await?;
+            if exact_matches.is_empty() {
+                self.contact_service
+                    .get_contacts()
+                    .await?
+                    .into_iter()
+                    .filter(|contact| matches_normalized_or_fuzzy(&contact.name, search_term))
+                    .collect()
+            } else {
+                exact_matches
+            }
         } else {
             vec![]
         };
 
         let companies = if item_types.contains(&GeneralSearchFilterItemType::Company) {
-            self.company_service.search(&search_term_lc).await?
+            let exact_matches = self.company_service.search(&search_term_lc).await?;
+            if exact_matches.is_empty() {
+                self.company_service
+                    .get_list_of_companies()
+                    .await?
+                    .into_iter()
+                    .filter(|company| matches_normalized_or_fuzzy(&company.name, search_term))
+                    .collect()
+            } else {
+                exact_matches
+            }
         } else {
             vec![]
         };
@@ -94,3 +121,7 @@ impl SearchServiceApi for SearchService {
         })
     }
 }
+
+fn matches_normalized_or_fuzzy(value: &str, search_term: &str) -> bool {
+    search::matches_exact(value, search_term) || search::matches_fuzzy(value, search_term)
+}
@@ -0,0 +1,220 @@
+//! Deterministic fixtures for testing against the E-Bill API.
+//!
+//! Enabled with the `test-utils` feature. All builders return the same data on every call, so
+//! identities, keys and bills are reproducible across tests and test runs. Keys are derived
+//! from a seed via [`deterministic_keys`], so separate participants can be created without
+//! hardcoding private keys.
+use crate::{
+    data::{
+        OptionalPostalAddress, PostalAddress,
+        bill::BitcreditBill,
+        contact::{ContactType, IdentityPublicData},
+        identity::{Identity, IdentityWithAll},
+    },
+    util::BcrKeys,
+};
+use bcr_ebill_core::blockchain::bill::{BillBlockchain, block::BillIssueBlockData};
+use bcr_ebill_core::constants::PAYMENT_DEADLINE_SECONDS;
+use bitcoin::hashes::{Hash, sha256};
+
+/// The public key of [`TEST_PRIVATE_KEY_SECP`]
+pub const TEST_PUB_KEY_SECP: &str =
+    "02295fb5f4eeb2f21e01eaf3a2d9a3be10f39db870d28f02146130317973a40ac0";
+
+/// A valid bill id
+pub const TEST_BILL_ID: &str =
+    "KmtMUia3ezhshD9EyzvpT62DUPLr66M5LESy6j8ErCtv1USUDtoTA8JkXnCCGEtZxp41aKne5wVcCjoaFbjDqD4aFk";
+
+/// The private key used for the baseline identity and bill keys
+pub const TEST_PRIVATE_KEY_SECP: &str =
+    "d1ff7427912d3b81743d3b67ffa1e65df2156d3dab257316cbc8d0f35eeeabe9";
+
+/// A valid node id
+pub const TEST_NODE_ID_SECP: &str =
+    "03205b8dec12bc9e879f5b517aa32192a2550e88adcee3e54ec2c7294802568fef";
+
+/// [`TEST_NODE_ID_SECP`] as a hex encoded Nostr public key
+pub const TEST_NODE_ID_SECP_AS_NPUB_HEX: &str =
+    "205b8dec12bc9e879f5b517aa32192a2550e88adcee3e54ec2c7294802568fef";
+
+/// A valid bitcoin testnet address
+pub const VALID_PAYMENT_ADDRESS_TESTNET: &str = "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk0";
+
+/// The timestamp the baseline bill is issued at
+pub const TEST_ISSUE_TIMESTAMP: u64 = 1731593920;
+
+/// Derives a key pair from the given seed. The same seed always results in the same keys and
+/// different seeds result in different keys.
+pub fn deterministic_keys(seed: u64) -> BcrKeys {
+    let private_key = sha256::Hash::hash(format!("bcr-ebill-test-key-{seed}").as_bytes());
+    BcrKeys::from_private_key(&private_key.to_string()).expect("sha256 hash is a valid private key")
+}
+
+/// The keys of the baseline identity, based on [`TEST_PRIVATE_KEY_SECP`]
+pub fn get_baseline_keys() -> BcrKeys {
+    BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).expect("test private key is valid")
+}
+
+pub fn empty_address() -> PostalAddress {
+    PostalAddress {
+        country: "AT".to_string(),
+        city: "Vienna".to_string(),
+        zip: None,
+        address: "Some Address 1".to_string(),
+    }
+}
+
+pub fn empty_optional_address() -> OptionalPostalAddress {
+    OptionalPostalAddress {
+        country: None,
+        city: None,
+        zip: None,
+        address: None,
+    }
+}
+
+pub fn empty_identity() -> Identity {
+    Identity {
+        node_id: "".to_string(),
+        name: "some name".to_string(),
+        email: "some@example.com".to_string(),
+        postal_address: empty_optional_address(),
+        date_of_birth: None,
+        country_of_birth: None,
+        city_of_birth: None,
+        identification_number: None,
+        nostr_relay: None,
+        profile_picture_file: None,
+        identity_document_file: None,
+    }
+}
+
+pub fn empty_identity_public_data() -> IdentityPublicData {
+    IdentityPublicData {
+        t: ContactType::Person,
+        node_id: "".to_string(),
+        name: "some@example.com".to_string(),
+        postal_address: empty_address(),
+        email: None,
+        nostr_relay: None,
+    }
+}
+
+/// A bill participant with the given node id and placeholder data
+pub fn identity_public_data_only_node_id(node_id: String) -> IdentityPublicData {
+    IdentityPublicData {
+        t: ContactType::Person,
+        node_id,
+        name: "some name".to_string(),
+        postal_address: empty_address(),
+        email: None,
+        nostr_relay: None,
+    }
+}
+
+pub fn empty_bitcredit_bill() -> BitcreditBill {
+    BitcreditBill {
+        id: "".to_string(),
+        country_of_issuing: "AT".to_string(),
+        city_of_issuing: "Vienna".to_string(),
+        drawee: empty_identity_public_data(),
+        drawer: empty_identity_public_data(),
+        payee: empty_identity_public_data(),
+        endorsee: None,
+        currency: "sat".to_string(),
+        sum: 5000,
+        maturity_date: "2099-11-12".to_string(),
+        issue_date: "2099-08-12".to_string(),
+        city_of_payment: "Vienna".to_string(),
+        country_of_payment: "AT".to_string(),
+        language: "DE".to_string(),
+        files: vec![],
+        payment_deadline_seconds: PAYMENT_DEADLINE_SECONDS,
+        fiat_amount: None,
+    }
+}
+
+/// The baseline identity with the keys from [`get_baseline_keys`]
+pub fn get_baseline_identity() -> IdentityWithAll {
+    let keys = get_baseline_keys();
+    let mut identity = empty_identity();
+    identity.name = "drawer".to_owned();
+    identity.node_id = keys.get_public_key();
+    identity.postal_address.country = Some("AT".to_owned());
+    identity.postal_address.city = Some("Vienna".to_owned());
+    identity.postal_address.address = Some("Hayekweg 5".to_owned());
+    IdentityWithAll {
+        identity,
+        key_pair: keys,
+    }
+}
+
+/// A bill with the given id, drawn by the baseline identity on itself, with the payee's keys
+/// derived from seed `1`
+pub fn get_baseline_bill(bill_id: &str) -> BitcreditBill {
+    let mut bill = empty_bitcredit_bill();
+    bill.id = bill_id.to_owned();
+    bill.maturity_date = "2099-10-15".to_string();
+    bill.drawer = identity_public_data_only_node_id(get_baseline_keys().get_public_key());
+    bill.drawee = IdentityPublicData::new(get_baseline_identity().identity)
+        .expect("baseline identity has a postal address");
+    bill.payee = empty_identity_public_data();
+    bill.payee.name = "payee".to_owned();
+    bill.payee.node_id = deterministic_keys(1).get_public_key();
+    bill
+}
+
+/// A chain, only consisting of the issue block of the given bill, or the baseline bill for
+/// [`TEST_BILL_ID`], signed by the baseline identity. The bill keys are based on
+/// [`TEST_PRIVATE_KEY_SECP`].
+pub fn get_genesis_chain(bill: Option<BitcreditBill>) -> BillBlockchain {
+    let bill = bill.unwrap_or(get_baseline_bill(TEST_BILL_ID));
+    BillBlockchain::new(
+        &BillIssueBlockData::from(bill, None, TEST_ISSUE_TIMESTAMP),
+        get_baseline_keys(),
+        None,
+        get_baseline_keys(),
+        TEST_ISSUE_TIMESTAMP,
+    )
+    .expect("baseline bill is valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bcr_ebill_core::blockchain::Blockchain;
+
+    #[test]
+    fn deterministic_keys_are_reproducible() {
+        assert_eq!(
+            deterministic_keys(1).get_private_key_string(),
+            deterministic_keys(1).get_private_key_string()
+        );
+        assert_ne!(
+            deterministic_keys(1).get_public_key(),
+            deterministic_keys(2).get_public_key()
+        );
+    }
+
+    #[test]
+    fn baseline_keys_match_constants() {
+        assert_eq!(get_baseline_keys().get_public_key(), TEST_PUB_KEY_SECP);
+    }
+
+    #[test]
+    fn genesis_chain_is_valid_and_reproducible() {
+        let chain = get_genesis_chain(None);
+        assert!(chain.is_chain_valid());
+        assert_eq!(
+            chain
+                .get_first_version_bill(&crate::data::bill::BillKeys {
+                    private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+                    public_key: TEST_PUB_KEY_SECP.to_owned(),
+                })
+                .unwrap()
+                .payee
+                .node_id,
+            get_baseline_bill(TEST_BILL_ID).payee.node_id
+        );
+    }
+}
@@ -4,15 +4,18 @@ pub mod tests {
     use crate::{CONFIG, data::bill::BillKeys};
     use async_trait::async_trait;
     use bcr_ebill_core::{
-        OptionalPostalAddress, PostalAddress, ServiceTraitBounds,
-        bill::{BitcreditBill, BitcreditBillResult},
+        ServiceTraitBounds,
+        bill::{
+            BillAutoAcceptRule, BillHold, BillTemplate, BillView, BitcreditBill,
+            BitcreditBillResult, QueuedAutoAccept,
+        },
         blockchain::{
             bill::{BillBlock, BillBlockchain, BillOpCode},
             company::{CompanyBlock, CompanyBlockchain},
             identity::IdentityBlock,
         },
         company::{Company, CompanyKeys},
-        contact::{Contact, ContactType, IdentityPublicData},
+        contact::{Contact, IdentityPublicData},
         identity::{ActiveIdentityState, Identity, IdentityWithAll},
         notification::{ActionType, Notification, NotificationType},
         util::crypto::BcrKeys,
@@ -20,17 +23,27 @@ pub mod tests {
     use bcr_ebill_persistence::{
         BackupStoreApi, ContactStoreApi, NostrEventOffset, NostrEventOffsetStoreApi,
         NotificationStoreApi, Result,
-        bill::{BillChainStoreApi, BillStoreApi},
+        bill::{BillAutoAcceptStoreApi, BillChainStoreApi, BillStoreApi, BillTemplateStoreApi},
         company::{CompanyChainStoreApi, CompanyStoreApi},
         file_upload::FileUploadStoreApi,
         identity::{IdentityChainStoreApi, IdentityStoreApi},
-        nostr::{NostrQueuedMessage, NostrQueuedMessageStoreApi},
-        notification::NotificationFilter,
+        nostr::{NostrQueuedMessage, NostrQueuedMessageInfo, NostrQueuedMessageStoreApi},
+        notification::{NotificationFilter, NotificationPage},
+    };
+    use bcr_ebill_transport::{
+        BillChainEvent, EventEnvelope, NostrProfile, NotificationJsonTransportApi,
+        NotificationServiceApi, QueuedMessageInfo, RelayConnection, RelayStatus,
     };
-    use bcr_ebill_transport::{BillChainEvent, NotificationServiceApi};
     use std::collections::{HashMap, HashSet};
     use std::path::Path;
 
+    pub use crate::test_utils::{
+        TEST_BILL_ID, TEST_NODE_ID_SECP, TEST_NODE_ID_SECP_AS_NPUB_HEX, TEST_PRIVATE_KEY_SECP,
+        TEST_PUB_KEY_SECP, VALID_PAYMENT_ADDRESS_TESTNET, empty_address, empty_bitcredit_bill,
+        empty_identity, empty_identity_public_data, empty_optional_address,
+        identity_public_data_only_node_id,
+    };
+
     // Need to wrap mocks, because traits are in a different crate
     mockall::mock! {
         pub ContactStoreApiMock {}
@@ -54,6 +67,7 @@ pub mod tests {
             async fn backup(&self) -> Result<Vec<u8>>;
             async fn restore(&self, file_path: &Path) -> Result<()>;
             async fn drop_db(&self, name: &str) -> Result<()>;
+            async fn ping(&self) -> Result<()>;
         }
     }
 
@@ -69,10 +83,52 @@ pub mod tests {
             async fn clear_bill_cache(&self) -> Result<()>;
             async fn exists(&self, id: &str) -> bool;
             async fn get_ids(&self) -> Result<Vec<String>>;
+            async fn get_bill_ids_by_participant(&self, node_id: &str) -> Result<Vec<String>>;
+            async fn get_unindexed_bill_ids(&self) -> Result<Vec<String>>;
             async fn save_keys(&self, id: &str, keys: &BillKeys) -> Result<()>;
+            async fn remove_keys(&self, id: &str) -> Result<()>;
             async fn get_keys(&self, id: &str) -> Result<BillKeys>;
+            async fn get_key_ids(&self) -> Result<Vec<String>>;
             async fn is_paid(&self, id: &str) -> Result<bool>;
             async fn set_to_paid(&self, id: &str, payment_address: &str) -> Result<()>;
+            async fn get_paid_sum(&self, id: &str) -> Result<u64>;
+            async fn set_paid_sum(&self, id: &str, payment_address: &str, paid_sum: u64) -> Result<()>;
+            async fn get_hold(&self, id: &str) -> Result<Option<BillHold>>;
+            async fn set_hold(&self, id: &str, hold: &BillHold) -> Result<()>;
+            async fn clear_hold(&self, id: &str) -> Result<()>;
+            async fn get_idempotent_block_id(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+            ) -> Result<Option<u64>>;
+            async fn reserve_idempotency_key(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+            ) -> Result<bool>;
+            async fn release_idempotency_key(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+            ) -> Result<()>;
+            async fn save_idempotency_key(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+                block_id: u64,
+            ) -> Result<()>;
+            async fn get_archived_bill_ids(&self, node_id: &str) -> Result<Vec<String>>;
+            async fn archive(&self, id: &str, node_id: &str) -> Result<()>;
+            async fn unarchive(&self, id: &str, node_id: &str) -> Result<()>;
+            async fn get_note(&self, id: &str, node_id: &str) -> Result<Option<String>>;
+            async fn set_note(&self, id: &str, node_id: &str, note: &str) -> Result<()>;
+            async fn remove_note(&self, id: &str, node_id: &str) -> Result<()>;
+            async fn get_view(&self, id: &str) -> Result<Option<BillView>>;
+            async fn save_view(&self, view: &BillView) -> Result<()>;
             async fn get_bill_ids_waiting_for_payment(&self) -> Result<Vec<String>>;
             async fn get_bill_ids_waiting_for_sell_payment(&self) -> Result<Vec<String>>;
             async fn get_bill_ids_waiting_for_recourse_payment(&self) -> Result<Vec<String>>;
@@ -92,6 +148,35 @@ pub mod tests {
             async fn get_latest_block(&self, id: &str) -> Result<BillBlock>;
             async fn add_block(&self, id: &str, block: &BillBlock) -> Result<()>;
             async fn get_chain(&self, id: &str) -> Result<BillBlockchain>;
+            async fn get_blocks(&self, id: &str) -> Result<Vec<BillBlock>>;
+            async fn remove_blocks_from(&self, id: &str, block_id: u64) -> Result<()>;
+            async fn replace_blocks_from(&self, id: &str, blocks: &[BillBlock]) -> Result<()>;
+        }
+    }
+
+    mockall::mock! {
+        pub BillTemplateStoreApiMock {}
+
+        #[async_trait]
+        impl BillTemplateStoreApi for BillTemplateStoreApiMock {
+            async fn get_all(&self) -> Result<Vec<BillTemplate>>;
+            async fn get(&self, id: &str) -> Result<Option<BillTemplate>>;
+            async fn save(&self, template: &BillTemplate) -> Result<()>;
+            async fn delete(&self, id: &str) -> Result<()>;
+        }
+    }
+
+    mockall::mock! {
+        pub BillAutoAcceptStoreApiMock {}
+
+        #[async_trait]
+        impl BillAutoAcceptStoreApi for BillAutoAcceptStoreApiMock {
+            async fn get_rules(&self, node_id: &str) -> Result<Vec<BillAutoAcceptRule>>;
+            async fn save_rule(&self, rule: &BillAutoAcceptRule) -> Result<()>;
+            async fn delete_rule(&self, id: &str) -> Result<()>;
+            async fn queue_accept(&self, queued: &QueuedAutoAccept) -> Result<()>;
+            async fn get_queued_accepts(&self) -> Result<Vec<QueuedAutoAccept>>;
+            async fn remove_queued_accept(&self, bill_id: &str, node_id: &str) -> Result<()>;
         }
     }
 
@@ -136,6 +221,7 @@ pub mod tests {
             async fn save_key_pair(&self, key_pair: &BcrKeys, seed: &str) -> Result<()>;
             async fn get_key_pair(&self) -> Result<BcrKeys>;
             async fn get_or_create_key_pair(&self) -> Result<BcrKeys>;
+            async fn get_or_create_data_key(&self) -> Result<BcrKeys>;
             async fn get_seedphrase(&self) -> Result<String>;
             async fn get_current_identity(&self) -> Result<ActiveIdentityState>;
             async fn set_current_identity(&self, identity_state: &ActiveIdentityState) -> Result<()>;
@@ -169,9 +255,11 @@ pub mod tests {
         #[async_trait]
         impl NostrQueuedMessageStoreApi for NostrQueuedMessageStore {
             async fn add_message(&self, message: NostrQueuedMessage, max_retries: i32) -> Result<()>;
-            async fn get_retry_messages(&self, limit: u64) -> Result<Vec<NostrQueuedMessage>>;
-            async fn fail_retry(&self, id: &str) -> Result<()>;
+            async fn get_retry_messages(&self, limit: u64, now: u64) -> Result<Vec<NostrQueuedMessage>>;
+            async fn fail_retry(&self, id: &str, next_retry_at: u64) -> Result<()>;
             async fn succeed_retry(&self, id: &str) -> Result<()>;
+            async fn get_pending_messages(&self, limit: u64) -> Result<Vec<NostrQueuedMessageInfo>>;
+            async fn count_dead_letter_messages(&self) -> Result<u64>;
         }
     }
 
@@ -181,7 +269,7 @@ pub mod tests {
         #[async_trait]
         impl NotificationStoreApi for NotificationStoreApiMock {
             async fn add(&self, notification: Notification) -> Result<Notification>;
-            async fn list(&self, filter: NotificationFilter) -> Result<Vec<Notification>>;
+            async fn list(&self, filter: NotificationFilter) -> Result<NotificationPage>;
             async fn get_latest_by_references(
                 &self,
                 reference: &[String],
@@ -195,6 +283,11 @@ pub mod tests {
             #[allow(unused)]
             async fn list_by_type(&self, notification_type: NotificationType) -> Result<Vec<Notification>>;
             async fn mark_as_done(&self, notification_id: &str) -> Result<()>;
+            async fn mark_all_as_done(
+                &self,
+                node_id: &str,
+                notification_type: Option<NotificationType>,
+            ) -> Result<u64>;
             #[allow(unused)]
             async fn delete(&self, notification_id: &str) -> Result<()>;
             async fn set_bill_notification_sent(
@@ -209,6 +302,17 @@ pub mod tests {
                 block_height: i32,
                 action_type: ActionType,
             ) -> Result<bool>;
+            async fn set_bill_participant_notified(
+                &self,
+                bill_id: &str,
+                block_height: i32,
+                node_id: &str,
+            ) -> Result<()>;
+            async fn get_notified_bill_participants(
+                &self,
+                bill_id: &str,
+                block_height: i32,
+            ) -> Result<Vec<String>>;
         }
     }
 
@@ -219,6 +323,7 @@ pub mod tests {
         impl FileUploadStoreApi for FileUploadStoreApiMock {
             async fn create_temp_upload_folder(&self, file_upload_id: &str) -> Result<()>;
             async fn remove_temp_upload_folder(&self, file_upload_id: &str) -> Result<()>;
+            async fn list_temp_upload_folders_older_than(&self, ttl_seconds: u64) -> Result<Vec<String>>;
             async fn write_temp_upload_file(
                 &self,
                 file_upload_id: &str,
@@ -247,8 +352,14 @@ pub mod tests {
             async fn send_bill_is_accepted_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
             async fn send_request_to_accept_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
             async fn send_request_to_pay_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
+            async fn send_request_to_accept_and_pay_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
             async fn send_bill_is_paid_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
             async fn send_bill_is_endorsed_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
+            async fn send_bill_files_attached_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
+            async fn send_offer_to_sell_cancelled_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
+            async fn send_request_to_accept_cancelled_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
+            async fn send_bill_acknowledged_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
+            async fn send_drawee_corrected_event(&self, event: &BillChainEvent) -> bcr_ebill_transport::Result<()>;
             async fn send_offer_to_sell_event(
                 &self,
                 event: &BillChainEvent,
@@ -268,6 +379,7 @@ pub mod tests {
                 &self,
                 event: &BillChainEvent,
                 rejected_action: ActionType,
+                reason: Option<String>,
             ) -> bcr_ebill_transport::Result<()>;
             async fn send_request_to_action_timed_out_event(
                 &self,
@@ -283,14 +395,40 @@ pub mod tests {
                 action: ActionType,
                 recoursee: &IdentityPublicData,
             ) -> bcr_ebill_transport::Result<()>;
+            async fn send_maturity_approaching_event(
+                &self,
+                sender_node_id: &str,
+                bill_id: &str,
+                sum: Option<u64>,
+                recipient: &IdentityPublicData,
+            ) -> bcr_ebill_transport::Result<()>;
+            async fn send_request_to_action_timeout_approaching_event(
+                &self,
+                sender_node_id: &str,
+                bill_id: &str,
+                sum: Option<u64>,
+                action: ActionType,
+                recipient: &IdentityPublicData,
+            ) -> bcr_ebill_transport::Result<()>;
             async fn send_request_to_mint_event(&self, sender_node_id: &str, bill: &BitcreditBill) -> bcr_ebill_transport::Result<()>;
-            async fn send_new_quote_event(&self, quote: &BitcreditBill) -> bcr_ebill_transport::Result<()>;
-            async fn send_quote_is_approved_event(&self, quote: &BitcreditBill) -> bcr_ebill_transport::Result<()>;
+            async fn send_new_quote_event(&self, event: &BillChainEvent, requester: &IdentityPublicData) -> bcr_ebill_transport::Result<()>;
+            async fn send_quote_is_approved_event(&self, event: &BillChainEvent, mint: &IdentityPublicData) -> bcr_ebill_transport::Result<()>;
+            async fn send_quote_is_rejected_event(&self, event: &BillChainEvent, mint: &IdentityPublicData) -> bcr_ebill_transport::Result<()>;
             async fn get_client_notifications(
                 &self,
                 filter: NotificationFilter,
-            ) -> bcr_ebill_transport::Result<Vec<Notification>>;
+            ) -> bcr_ebill_transport::Result<NotificationPage>;
             async fn mark_notification_as_done(&self, notification_id: &str) -> bcr_ebill_transport::Result<()>;
+            async fn mark_all_as_done(
+                &self,
+                node_id: &str,
+                notification_type: Option<NotificationType>,
+            ) -> bcr_ebill_transport::Result<u64>;
+            async fn mark_bill_notifications_done(
+                &self,
+                bill_id: &str,
+                node_id: &str,
+            ) -> bcr_ebill_transport::Result<u64>;
             async fn get_active_bill_notification(&self, bill_id: &str) -> Option<Notification>;
             async fn get_active_bill_notifications(&self, bill_ids: &[String]) -> HashMap<String, Notification>;
             async fn check_bill_notification_sent(
@@ -305,114 +443,79 @@ pub mod tests {
                 block_height: i32,
                 action: ActionType,
             ) -> bcr_ebill_transport::Result<()>;
+            async fn get_notified_bill_participants(
+                &self,
+                bill_id: &str,
+                block_height: i32,
+            ) -> bcr_ebill_transport::Result<Vec<String>>;
             async fn send_retry_messages(&self) -> bcr_ebill_transport::Result<()>;
+            async fn resend_bill_event(&self, event: &BillChainEvent, node_id: &str) -> bcr_ebill_transport::Result<()>;
+            async fn get_relay_status(&self) -> bcr_ebill_transport::Result<Vec<RelayStatus>>;
+            async fn get_relay_connections(&self) -> bcr_ebill_transport::Result<Vec<RelayConnection>>;
+            async fn fetch_bill_blocks(&self, bill_id: &str) -> bcr_ebill_transport::Result<Vec<BillBlock>>;
+            async fn list_pending_retry_messages(&self, limit: u64) -> bcr_ebill_transport::Result<Vec<QueuedMessageInfo>>;
+            async fn count_dead_letter_messages(&self) -> bcr_ebill_transport::Result<u64>;
         }
     }
 
-    pub fn init_test_cfg() {
-        match CONFIG.get() {
-            Some(_) => (),
-            None => {
-                crate::init(crate::Config {
-                    bitcoin_network: "mainnet".to_string(),
-                    nostr_relay: "ws://localhost:8080".to_string(),
-                    surreal_db_connection: "ws://localhost:8800".to_string(),
-                    data_dir: ".".to_string(),
-                })
-                .unwrap();
-            }
-        }
-    }
-
-    pub fn empty_address() -> PostalAddress {
-        PostalAddress {
-            country: "AT".to_string(),
-            city: "Vienna".to_string(),
-            zip: None,
-            address: "Some Address 1".to_string(),
-        }
-    }
-
-    pub fn empty_optional_address() -> OptionalPostalAddress {
-        OptionalPostalAddress {
-            country: None,
-            city: None,
-            zip: None,
-            address: None,
-        }
-    }
-
-    pub fn empty_identity() -> Identity {
-        Identity {
-            node_id: "".to_string(),
-            name: "some name".to_string(),
-            email: "some@example.com".to_string(),
-            postal_address: empty_optional_address(),
-            date_of_birth: None,
-            country_of_birth: None,
-            city_of_birth: None,
-            identification_number: None,
-            nostr_relay: None,
-            profile_picture_file: None,
-            identity_document_file: None,
-        }
-    }
-
-    pub fn empty_identity_public_data() -> IdentityPublicData {
-        IdentityPublicData {
-            t: ContactType::Person,
-            node_id: "".to_string(),
-            name: "some@example.com".to_string(),
-            postal_address: empty_address(),
-            email: None,
-            nostr_relay: None,
+    impl ServiceTraitBounds for MockNotificationJsonTransport {}
+    mockall::mock! {
+        pub NotificationJsonTransport {}
+        #[async_trait]
+        impl NotificationJsonTransportApi for NotificationJsonTransport {
+            fn get_sender_key(&self) -> String;
+            async fn send(&self, recipient: &IdentityPublicData, event: EventEnvelope) -> bcr_ebill_transport::Result<()>;
+            fn get_relay_status(&self) -> Vec<RelayStatus>;
+            async fn get_relay_connections(&self) -> Vec<RelayConnection>;
+            async fn fetch_profile(&self, node_id: &str) -> bcr_ebill_transport::Result<Option<NostrProfile>>;
+            async fn fetch_received_events(&self) -> bcr_ebill_transport::Result<Vec<EventEnvelope>>;
         }
     }
 
-    pub fn identity_public_data_only_node_id(node_id: String) -> IdentityPublicData {
-        IdentityPublicData {
-            t: ContactType::Person,
-            node_id,
-            name: "some name".to_string(),
-            postal_address: empty_address(),
-            email: None,
-            nostr_relay: None,
+    pub fn test_config() -> crate::Config {
+        crate::Config {
+            bitcoin_network: "mainnet".to_string(),
+            nostr_relays: vec!["ws://localhost:8080".to_string()],
+            surreal_db_connection: "ws://localhost:8800".to_string(),
+            data_dir: ".".to_string(),
+            nostr_max_concurrent_events: 10,
+            nostr_event_timeout_seconds: 30,
+            nostr_max_retry_delay_seconds: 3600,
+            nostr_max_retries: 10,
+            nostr_bill_event_kind: 4,
+            nostr_company_event_kind: 4,
+            nostr_identity_event_kind: 4,
+            fiat_rate_provider_url: "http://localhost:8090".to_string(),
+            fee_estimator_url: "http://localhost:8091".to_string(),
+            bitcoin_explorer_connect_timeout_seconds: 10,
+            bitcoin_explorer_read_timeout_seconds: 30,
+            bitcoin_explorer_max_retries: 2,
+            webhook_urls: vec![],
+            temp_upload_ttl_seconds: 86400,
+            encrypt_temp_uploads: true,
+            file_storage_shard_prefix_length: 2,
+            max_bill_chain_blocks: 10000,
+            max_files_per_bill: 3,
+            max_total_attachment_bytes: 1000,
+            sell_deadline_seconds: None,
+            bill_cache_max_size: 1000,
+            maturity_reminder_lead_time_seconds: 259200,
+            payment_confirmations_required: 3,
+            mempool_base_url: None,
+            request_timeout_warning_seconds: 43200,
+            job_runner_stagger_seconds: 0,
+            job_runner_max_concurrent_jobs: 0,
+            job_runner_bills_per_tick: 0,
+            auto_accept_enabled: true,
         }
     }
 
-    pub fn empty_bitcredit_bill() -> BitcreditBill {
-        BitcreditBill {
-            id: "".to_string(),
-            country_of_issuing: "AT".to_string(),
-            city_of_issuing: "Vienna".to_string(),
-            drawee: empty_identity_public_data(),
-            drawer: empty_identity_public_data(),
-            payee: empty_identity_public_data(),
-            endorsee: None,
-            currency: "sat".to_string(),
-            sum: 5000,
-            maturity_date: "2099-11-12".to_string(),
-            issue_date: "2099-08-12".to_string(),
-            city_of_payment: "Vienna".to_string(),
-            country_of_payment: "AT".to_string(),
-            language: "DE".to_string(),
-            files: vec![],
+    pub fn init_test_cfg() {
+        match CONFIG.get() {
+            Some(_) => (),
+            None => {
+                crate::init(test_config()).unwrap();
+            }
         }
     }
-
-    pub const TEST_PUB_KEY_SECP: &str =
-        "02295fb5f4eeb2f21e01eaf3a2d9a3be10f39db870d28f02146130317973a40ac0";
-
-    pub const TEST_BILL_ID: &str = "KmtMUia3ezhshD9EyzvpT62DUPLr66M5LESy6j8ErCtv1USUDtoTA8JkXnCCGEtZxp41aKne5wVcCjoaFbjDqD4aFk";
-
-    pub const TEST_PRIVATE_KEY_SECP: &str =
-        "d1ff7427912d3b81743d3b67ffa1e65df2156d3dab257316cbc8d0f35eeeabe9";
-
-    pub const TEST_NODE_ID_SECP: &str =
-        "03205b8dec12bc9e879f5b517aa32192a2550e88adcee3e54ec2c7294802568fef";
-
-    pub const TEST_NODE_ID_SECP_AS_NPUB_HEX: &str =
-        "205b8dec12bc9e879f5b517aa32192a2550e88adcee3e54ec2c7294802568fef";
-
-    pub const VALID_PAYMENT_ADDRESS_TESTNET: &str = "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk0";
 }
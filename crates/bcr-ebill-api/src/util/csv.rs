@@ -0,0 +1,227 @@
+use crate::data::{
+    PostalAddress,
+    contact::{Contact, ContactType},
+};
+use bcr_ebill_core::{Field, Validate, ValidationError, util::crypto};
+
+/// The columns of a contact CSV file are identified by the header row, so they can be in any
+/// order. All columns except `type` are required - rows without a type are imported as persons.
+const NAME_COLUMN: &str = "name";
+const NODE_ID_COLUMN: &str = "node_id";
+const EMAIL_COLUMN: &str = "email";
+const COUNTRY_COLUMN: &str = "country";
+const CITY_COLUMN: &str = "city";
+const ZIP_COLUMN: &str = "zip";
+const ADDRESS_COLUMN: &str = "address";
+const TYPE_COLUMN: &str = "type";
+
+/// A data row of a contact CSV file
+#[derive(Debug, Clone)]
+pub struct CsvContactRow {
+    /// The number of the row in the file, starting with 1 for the header row
+    pub row: usize,
+    /// The contact, or the reason why the row is invalid
+    pub contact: Result<Contact, String>,
+}
+
+/// The positions of the columns in the header row
+struct Columns {
+    name: usize,
+    node_id: usize,
+    email: usize,
+    country: usize,
+    city: usize,
+    zip: usize,
+    address: usize,
+    t: Option<usize>,
+    count: usize,
+}
+
+/// Parses the rows of the given contact CSV file, skipping empty rows. Fields are separated by
+/// commas, or by semicolons, if the header row doesn't contain a comma. Fails, if a required
+/// column is missing in the header row.
+pub fn parse_contacts_csv(input: &str) -> Result<Vec<CsvContactRow>, ValidationError> {
+    let input = input.trim_start_matches('\u{feff}');
+    let header_line = input.lines().next().unwrap_or_default();
+    let delimiter = if !header_line.contains(',') && header_line.contains(';') {
+        ';'
+    } else {
+        ','
+    };
+    let mut records = parse_records(input, delimiter).into_iter();
+
+    let header: Vec<String> = records
+        .next()
+        .unwrap_or_default()
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let position = |name: &str| header.iter().position(|h| h == name);
+    let required = |name: &str| {
+        position(name).ok_or_else(|| ValidationError::MissingCsvColumn(name.to_owned()))
+    };
+    let columns = Columns {
+        name: required(NAME_COLUMN)?,
+        node_id: required(NODE_ID_COLUMN)?,
+        email: required(EMAIL_COLUMN)?,
+        country: required(COUNTRY_COLUMN)?,
+        city: required(CITY_COLUMN)?,
+        zip: required(ZIP_COLUMN)?,
+        address: required(ADDRESS_COLUMN)?,
+        t: position(TYPE_COLUMN),
+        count: header.len(),
+    };
+
+    Ok(records
+        .enumerate()
+        .filter(|(_, record)| record.iter().any(|field| !field.trim().is_empty()))
+        .map(|(idx, record)| CsvContactRow {
+            // the header is row 1
+            row: idx + 2,
+            contact: contact_from_record(&record, &columns),
+        })
+        .collect())
+}
+
+fn contact_from_record(record: &[String], columns: &Columns) -> Result<Contact, String> {
+    if record.len() != columns.count {
+        return Err(format!(
+            "Expected {} columns, but got {}",
+            columns.count,
+            record.len()
+        ));
+    }
+    let get = |idx: usize| record[idx].trim().to_owned();
+
+    let node_id = get(columns.node_id);
+    if crypto::validate_pub_key(&node_id).is_err() {
+        return Err(ValidationError::InvalidSecp256k1Key(node_id).to_string());
+    }
+
+    let t = match columns.t.map(get) {
+        Some(t) if !t.is_empty() => {
+            parse_contact_type(&t).ok_or_else(|| ValidationError::InvalidContactType.to_string())?
+        }
+        _ => ContactType::Person,
+    };
+
+    let name = get(columns.name);
+    if name.is_empty() {
+        return Err(ValidationError::FieldEmpty(Field::Name).to_string());
+    }
+
+    let zip = get(columns.zip);
+    let postal_address = PostalAddress {
+        country: get(columns.country),
+        city: get(columns.city),
+        zip: if zip.is_empty() { None } else { Some(zip) },
+        address: get(columns.address),
+    };
+    postal_address.validate().map_err(|e| e.to_string())?;
+
+    Ok(Contact {
+        t,
+        node_id,
+        name,
+        email: get(columns.email),
+        postal_address,
+        date_of_birth_or_registration: None,
+        country_of_birth_or_registration: None,
+        city_of_birth_or_registration: None,
+        identification_number: None,
+        avatar_file: None,
+        proof_document_file: None,
+        nostr_relays: vec![],
+    })
+}
+
+/// Accepts the numeric contact types of the API, as well as their names
+fn parse_contact_type(value: &str) -> Option<ContactType> {
+    match value.to_lowercase().as_str() {
+        "0" | "person" => Some(ContactType::Person),
+        "1" | "company" => Some(ContactType::Company),
+        _ => None,
+    }
+}
+
+/// Splits the input into records of fields as described in RFC 4180 - quoted fields can contain
+/// delimiters, line breaks and quotes, which are escaped by doubling them
+fn parse_records(input: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            '\r' => (),
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    // the last line doesn't need a line break
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::tests::TEST_NODE_ID_SECP;
+
+    #[test]
+    fn parse_records_handles_quoted_fields() {
+        let records = parse_records("a,\"b, c\",\"say \"\"hi\"\"\"\r\n\"multi\nline\",,x", ',');
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], vec!["a", "b, c", "say \"hi\""]);
+        assert_eq!(records[1], vec!["multi\nline", "", "x"]);
+    }
+
+    #[test]
+    fn parse_contacts_csv_maps_columns_by_header() {
+        let input = format!(
+            "City;Zip;Address;Country;Email;Node_Id;Name;Type\n\
+            Vienna;;Some Street 1;AT;jane@example.com;{TEST_NODE_ID_SECP};Jane Doe;company\n"
+        );
+        let rows = parse_contacts_csv(&input).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].row, 2);
+        let contact = rows[0].contact.as_ref().unwrap();
+        assert_eq!(contact.t, ContactType::Company);
+        assert_eq!(contact.node_id, TEST_NODE_ID_SECP);
+        assert_eq!(contact.name, "Jane Doe");
+        assert_eq!(contact.email, "jane@example.com");
+        assert_eq!(contact.postal_address.country, "AT");
+        assert_eq!(contact.postal_address.city, "Vienna");
+        assert_eq!(contact.postal_address.zip, None);
+        assert_eq!(contact.postal_address.address, "Some Street 1");
+    }
+
+    #[test]
+    fn parse_contacts_csv_fails_without_required_column() {
+        let result = parse_contacts_csv("name,node_id,email,country,city,address\n");
+        assert_eq!(
+            result.unwrap_err(),
+            ValidationError::MissingCsvColumn(ZIP_COLUMN.to_owned())
+        );
+    }
+}
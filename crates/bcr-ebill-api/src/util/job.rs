@@ -0,0 +1,210 @@
+use futures::future::join_all;
+use std::future::Future;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio_with_wasm::alias as tokio;
+
+/// How the jobs of a job runner tick are scheduled, so they don't all hit the database at once.
+/// It's shared by the web and the wasm job runner, since it only relies on timers, which work on
+/// both targets.
+#[derive(Debug, Clone, Copy)]
+pub struct JobSchedule {
+    /// The delay between the starts of consecutive jobs within a tick
+    stagger: Duration,
+    /// The maximum number of jobs running at the same time
+    max_concurrent_jobs: usize,
+}
+
+impl JobSchedule {
+    /// A `max_concurrent_jobs` of 0 means, that all jobs can run at the same time
+    pub fn new(stagger: Duration, max_concurrent_jobs: usize) -> Self {
+        Self {
+            stagger,
+            max_concurrent_jobs,
+        }
+    }
+
+    /// The delay after the start of a tick, after which the job with the given index starts
+    pub fn start_offset(&self, job_index: usize) -> Duration {
+        self.stagger * job_index as u32
+    }
+
+    /// Runs the given jobs and waits for all of them to finish - each job starts at its offset
+    /// within the tick, or later, if `max_concurrent_jobs` are already running
+    pub async fn run<F: Future<Output = ()>>(&self, jobs: Vec<F>) {
+        let permits = match self.max_concurrent_jobs {
+            0 => jobs.len().max(1),
+            max => max,
+        };
+        let semaphore = Semaphore::new(permits);
+        join_all(jobs.into_iter().enumerate().map(|(idx, job)| {
+            let semaphore = &semaphore;
+            async move {
+                let offset = self.start_offset(idx);
+                if !offset.is_zero() {
+                    tokio::time::sleep(offset).await;
+                }
+                // the semaphore is never closed, so acquiring can't fail
+                let _permit = semaphore.acquire().await;
+                job.await;
+            }
+        }))
+        .await;
+    }
+}
+
+/// Lets operators pause the job runner at runtime, e.g. during maintenance, without stopping the
+/// process. Clones share the same state, so the runner and the controls can each hold one.
+#[derive(Debug, Clone, Default)]
+pub struct JobControl {
+    paused: Arc<AtomicBool>,
+}
+
+impl JobControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ticks of the job runner are skipped until the jobs are resumed
+    pub fn pause_jobs(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume_jobs(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Runs the given tick of the job runner, unless the jobs are paused - returns whether the
+    /// tick was run
+    pub async fn run_tick<F: Future<Output = ()>>(&self, tick: F) -> bool {
+        if self.is_paused() {
+            return false;
+        }
+        tick.await;
+        true
+    }
+}
+
+/// Returns the next window of at most `limit` of the given bills, starting at the cursor and
+/// wrapping around, and moves the cursor behind the window. This way, every bill is eventually
+/// processed, even if a job only processes a limited number of bills per tick. A `limit` of 0
+/// means all bills.
+pub fn next_bill_window(
+    mut bill_ids: Vec<String>,
+    limit: usize,
+    cursor: &mut usize,
+) -> Vec<String> {
+    if limit == 0 || bill_ids.len() <= limit {
+        *cursor = 0;
+        return bill_ids;
+    }
+    // sorted, so the window is stable between ticks
+    bill_ids.sort();
+    let len = bill_ids.len();
+    let start = *cursor % len;
+    *cursor = (start + limit) % len;
+    bill_ids
+        .into_iter()
+        .cycle()
+        .skip(start)
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn start_offset_staggers_jobs() {
+        let schedule = JobSchedule::new(Duration::from_millis(50), 0);
+        assert_eq!(schedule.start_offset(0), Duration::ZERO);
+        assert_eq!(schedule.start_offset(1), Duration::from_millis(50));
+        assert_eq!(schedule.start_offset(2), Duration::from_millis(100));
+        // without a stagger, all jobs start right away
+        let schedule = JobSchedule::new(Duration::ZERO, 0);
+        assert_eq!(schedule.start_offset(5), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn run_starts_jobs_in_order() {
+        let schedule = JobSchedule::new(Duration::from_millis(50), 0);
+        let starts = Arc::new(Mutex::new(vec![]));
+        let jobs = (0..3)
+            .map(|idx| {
+                let starts = starts.clone();
+                async move {
+                    starts.lock().unwrap().push(idx);
+                }
+            })
+            .collect();
+        schedule.run(jobs).await;
+
+        assert_eq!(*starts.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn run_limits_concurrent_jobs() {
+        let schedule = JobSchedule::new(Duration::ZERO, 2);
+        let running = Arc::new(Mutex::new((0, 0)));
+        let jobs = (0..5)
+            .map(|_| {
+                let running = running.clone();
+                async move {
+                    {
+                        let mut running = running.lock().unwrap();
+                        running.0 += 1;
+                        running.1 = running.1.max(running.0);
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.lock().unwrap().0 -= 1;
+                }
+            })
+            .collect();
+        schedule.run(jobs).await;
+
+        // at most 2 jobs ran at the same time
+        assert_eq!(running.lock().unwrap().1, 2);
+    }
+
+    #[test]
+    fn next_bill_window_rotates_through_bills() {
+        let mut cursor = 0;
+        let bill_ids = ids(&["c", "a", "b", "d", "e"]);
+        assert_eq!(
+            next_bill_window(bill_ids.clone(), 2, &mut cursor),
+            ids(&["a", "b"])
+        );
+        assert_eq!(
+            next_bill_window(bill_ids.clone(), 2, &mut cursor),
+            ids(&["c", "d"])
+        );
+        assert_eq!(
+            next_bill_window(bill_ids.clone(), 2, &mut cursor),
+            ids(&["e", "a"])
+        );
+        assert_eq!(cursor, 1);
+    }
+
+    #[test]
+    fn next_bill_window_without_limit_returns_all() {
+        let mut cursor = 3;
+        let bill_ids = ids(&["c", "a", "b"]);
+        assert_eq!(next_bill_window(bill_ids.clone(), 0, &mut cursor), bill_ids);
+        assert_eq!(cursor, 0);
+        assert_eq!(next_bill_window(bill_ids.clone(), 5, &mut cursor), bill_ids);
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+
+/// The structured context of a log line - the bill and the node an operation relates to. It's
+/// rendered as `[bill_id=.. node_id=..]` at the start of the message, so the fields are part of
+/// every log line, no matter if it's written by env_logger, or by console_log in the browser.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogContext<'a> {
+    bill_id: Option<&'a str>,
+    node_id: Option<&'a str>,
+}
+
+impl<'a> LogContext<'a> {
+    /// The context of an operation on the given bill
+    pub fn bill(bill_id: &'a str) -> Self {
+        Self {
+            bill_id: Some(bill_id),
+            node_id: None,
+        }
+    }
+
+    /// The context of an operation of the given node, which doesn't relate to a single bill
+    pub fn node(node_id: &'a str) -> Self {
+        Self {
+            bill_id: None,
+            node_id: Some(node_id),
+        }
+    }
+
+    /// Adds the node, the operation is executed by, or for
+    pub fn with_node_id(mut self, node_id: &'a str) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+}
+
+impl fmt::Display for LogContext<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = [("bill_id", self.bill_id), ("node_id", self.node_id)]
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|v| format!("{key}={v}")))
+            .collect();
+        write!(f, "[{}]", fields.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_context_renders_set_fields() {
+        assert_eq!(LogContext::default().to_string(), "[]");
+        assert_eq!(LogContext::bill("bill").to_string(), "[bill_id=bill]");
+        assert_eq!(LogContext::node("node").to_string(), "[node_id=node]");
+        assert_eq!(
+            LogContext::bill("bill").with_node_id("node").to_string(),
+            "[bill_id=bill node_id=node]"
+        );
+    }
+}
@@ -1,11 +1,17 @@
+pub mod csv;
 pub mod file;
+pub mod job;
+pub mod log_context;
 pub mod numbers_to_words;
+pub mod vcard;
 
 pub use bcr_ebill_core::ValidationError;
-pub use bcr_ebill_core::constants::VALID_CURRENCIES;
+pub use bcr_ebill_core::constants::{VALID_CURRENCIES, VALID_FIAT_CURRENCIES};
+pub use bcr_ebill_core::util::country;
 pub use bcr_ebill_core::util::crypto;
 pub use bcr_ebill_core::util::currency;
 pub use bcr_ebill_core::util::date;
+pub use bcr_ebill_core::util::search;
 pub use bcr_ebill_core::util::validate_file_upload_id;
 
 pub use bcr_ebill_core::util::BcrKeys;
@@ -35,6 +35,189 @@ const ORDERS: [&str; 7] = [
     "quintillion",
 ];
 
+const ONES_DE: [&str; 20] = [
+    "null",
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+];
+const TENS_DE: [&str; 10] = [
+    "null", "zehn", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig",
+    "neunzig",
+];
+// singular and plural of the orders from a million upwards
+const ORDERS_DE: [(&str, &str); 5] = [
+    ("Million", "Millionen"),
+    ("Milliarde", "Milliarden"),
+    ("Billion", "Billionen"),
+    ("Billiarde", "Billiarden"),
+    ("Trillion", "Trillionen"),
+];
+const SAT_PER_BTC: u64 = 100_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    English,
+    German,
+}
+
+impl Language {
+    /// A language like `de-AT` is matched by its primary language `de` - falls back to English
+    fn from_code(language: &str) -> Self {
+        match language
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "de" => Language::German,
+            _ => Language::English,
+        }
+    }
+}
+
+/// Spells out the given sum in the given currency and language, as required for the legal text
+/// of a bill (e.g. `one thousand satoshis`). Sums in `btc` are given in sat and spelled out as
+/// whole bitcoins and remaining satoshis. English and German are supported, other languages
+/// fall back to English.
+pub fn sum_to_words(sum: u64, currency: &str, language: &str) -> String {
+    let language = Language::from_code(language);
+    match currency.to_lowercase().as_str() {
+        "sat" => with_unit(sum, "satoshi", "satoshis", "Satoshi", "Satoshi", language),
+        "btc" => {
+            let whole = sum / SAT_PER_BTC;
+            let rest = sum % SAT_PER_BTC;
+            let mut parts = vec![];
+            if whole > 0 || rest == 0 {
+                parts.push(with_unit(
+                    whole, "bitcoin", "bitcoins", "Bitcoin", "Bitcoin", language,
+                ));
+            }
+            if rest > 0 {
+                parts.push(with_unit(
+                    rest, "satoshi", "satoshis", "Satoshi", "Satoshi", language,
+                ));
+            }
+            let separator = match language {
+                Language::English => " and ",
+                Language::German => " und ",
+            };
+            parts.join(separator)
+        }
+        other => format!(
+            "{} {}",
+            encode_for_language(sum, language),
+            other.to_uppercase()
+        ),
+    }
+}
+
+fn with_unit(
+    num: u64,
+    singular_en: &str,
+    plural_en: &str,
+    singular_de: &str,
+    plural_de: &str,
+    language: Language,
+) -> String {
+    let unit = match (language, num) {
+        (Language::English, 1) => singular_en,
+        (Language::English, _) => plural_en,
+        (Language::German, 1) => singular_de,
+        (Language::German, _) => plural_de,
+    };
+    format!("{} {unit}", encode_for_language(num, language))
+}
+
+/// Encodes the given number in the given language, in the form used in front of a unit
+fn encode_for_language(num: u64, language: Language) -> String {
+    match language {
+        Language::English => encode(&num),
+        Language::German => encode_de_with_one(num, "ein"),
+    }
+}
+
+pub fn encode_de(num: &u64) -> String {
+    encode_de_with_one(*num, "eins")
+}
+
+/// Encodes the given number in German, using the given word for a trailing one, since it's
+/// `eins` when standing alone and `ein` in front of a noun
+fn encode_de_with_one(num: u64, one: &str) -> String {
+    if num == 0 {
+        return ONES_DE[0].to_string();
+    }
+    let mut parts = vec![];
+    // orders from a million upwards are separate words, declined by their count
+    for (i, (singular, plural)) in ORDERS_DE.iter().enumerate().rev() {
+        let group = (num / 1000u64.pow(i as u32 + 2)) % 1000;
+        match group {
+            0 => (),
+            1 => parts.push(format!("eine {singular}")),
+            _ => parts.push(format!(
+                "{} {plural}",
+                encode_below_thousand_de(group, "ein")
+            )),
+        }
+    }
+    // everything below a million is written as one word
+    let thousands = (num / 1000) % 1000;
+    let rest = num % 1000;
+    let mut low = String::new();
+    if thousands > 0 {
+        low.push_str(&encode_below_thousand_de(thousands, "ein"));
+        low.push_str("tausend");
+    }
+    if rest > 0 {
+        low.push_str(&encode_below_thousand_de(rest, one));
+    }
+    if !low.is_empty() {
+        parts.push(low);
+    }
+    parts.join(" ")
+}
+
+fn encode_below_thousand_de(num: u64, one: &str) -> String {
+    let hundreds = num / 100;
+    let rest = (num % 100) as usize;
+    let mut result = String::new();
+    if hundreds > 0 {
+        result.push_str(&encode_below_thousand_de(hundreds, "ein"));
+        result.push_str("hundert");
+    }
+    match rest {
+        0 => (),
+        1 => result.push_str(one),
+        2..=19 => result.push_str(ONES_DE[rest]),
+        _ => {
+            let ones = rest % 10;
+            if ones > 0 {
+                result.push_str(if ones == 1 { "ein" } else { ONES_DE[ones] });
+                result.push_str("und");
+            }
+            result.push_str(TENS_DE[rest / 10]);
+        }
+    }
+    result
+}
+
 pub fn encode(num: &u64) -> String {
     match num {
         0..=19 => ONES[*num as usize].to_string(),
@@ -93,4 +276,86 @@ mod tests {
         let result = encode(&123_324_324);
         assert_eq!("one hundred twenty-three million three hundred twenty-four thousand three hundred twenty-four".to_string(), result);
     }
+
+    #[test]
+    fn numbers_to_letters_german() {
+        let cases = [
+            (0, "null"),
+            (1, "eins"),
+            (17, "siebzehn"),
+            (21, "einundzwanzig"),
+            (30, "dreißig"),
+            (101, "einhunderteins"),
+            (1_000, "eintausend"),
+            (21_501, "einundzwanzigtausendfünfhunderteins"),
+            (1_000_000, "eine Million"),
+            (2_500_000, "zwei Millionen fünfhunderttausend"),
+            (3_001_000_001, "drei Milliarden eine Million eins"),
+        ];
+        for (num, expected) in cases {
+            assert_eq!(encode_de(&num), expected);
+        }
+    }
+
+    #[test]
+    fn sum_to_words_english() {
+        let cases = [
+            (0, "sat", "en", "zero satoshis"),
+            (1, "sat", "en", "one satoshi"),
+            (1_000, "sat", "en", "one thousand satoshis"),
+            (1_000, "SAT", "en-UK", "one thousand satoshis"),
+            (0, "btc", "en", "zero bitcoins"),
+            (100_000_000, "btc", "en", "one bitcoin"),
+            (
+                250_000_000,
+                "btc",
+                "en",
+                "two bitcoins and fifty million satoshis",
+            ),
+            (1_500, "btc", "en", "one thousand five hundred satoshis"),
+            (12, "eur", "en", "twelve EUR"),
+            (
+                u64::MAX,
+                "sat",
+                "en",
+                "eighteen quintillion four hundred forty-six quadrillion seven hundred forty-four trillion seventy-three billion seven hundred nine million five hundred fifty-one thousand six hundred fifteen satoshis",
+            ),
+        ];
+        for (sum, currency, language, expected) in cases {
+            assert_eq!(sum_to_words(sum, currency, language), expected);
+        }
+    }
+
+    #[test]
+    fn sum_to_words_german() {
+        let cases = [
+            (0, "sat", "de", "null Satoshi"),
+            (1, "sat", "de", "ein Satoshi"),
+            (1_000, "sat", "de-AT", "eintausend Satoshi"),
+            (121, "sat", "de", "einhunderteinundzwanzig Satoshi"),
+            (100_000_001, "btc", "de", "ein Bitcoin und ein Satoshi"),
+            (300_000_000, "btc", "de", "drei Bitcoin"),
+            (
+                u64::MAX,
+                "sat",
+                "de",
+                "achtzehn Trillionen vierhundertsechsundvierzig Billiarden siebenhundertvierundvierzig Billionen dreiundsiebzig Milliarden siebenhundertneun Millionen fünfhunderteinundfünfzigtausendsechshundertfünfzehn Satoshi",
+            ),
+        ];
+        for (sum, currency, language, expected) in cases {
+            assert_eq!(sum_to_words(sum, currency, language), expected);
+        }
+    }
+
+    #[test]
+    fn sum_to_words_falls_back_to_english() {
+        assert_eq!(
+            sum_to_words(1_000, "sat", "fr"),
+            "one thousand satoshis".to_string()
+        );
+        assert_eq!(
+            sum_to_words(1_000, "sat", ""),
+            "one thousand satoshis".to_string()
+        );
+    }
 }
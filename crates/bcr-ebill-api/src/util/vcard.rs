@@ -0,0 +1,327 @@
+use crate::data::{
+    PostalAddress,
+    contact::{Contact, ContactType},
+};
+use bcr_ebill_core::util::crypto;
+
+/// The node id of a contact is a secp256k1 public key, which has no standard vCard property, so
+/// it's exported as the custom property `X-BCR-NODE-ID:<hex encoded public key>`. On import,
+/// entries without this property, or with a value that is not a valid public key, can't be
+/// resolved to a node and are skipped.
+pub const NODE_ID_PROPERTY: &str = "X-BCR-NODE-ID";
+/// Contact data without a standard vCard property is exported with custom properties as well
+const COUNTRY_OF_BIRTH_OR_REGISTRATION_PROPERTY: &str = "X-BCR-COUNTRY-OF-BIRTH-OR-REGISTRATION";
+const CITY_OF_BIRTH_OR_REGISTRATION_PROPERTY: &str = "X-BCR-CITY-OF-BIRTH-OR-REGISTRATION";
+const IDENTIFICATION_NUMBER_PROPERTY: &str = "X-BCR-IDENTIFICATION-NUMBER";
+const NOSTR_RELAY_PROPERTY: &str = "X-BCR-NOSTR-RELAY";
+
+/// The result of parsing a vCard file
+#[derive(Debug, Clone)]
+pub struct VCardImport {
+    pub contacts: Vec<Contact>,
+    /// The number of entries, which were skipped, because they had no resolvable node id
+    pub skipped: usize,
+}
+
+/// Serializes the given contacts as vCard 4.0 entries. Attached files are not exported.
+pub fn contacts_to_vcard(contacts: &[Contact]) -> String {
+    let mut lines: Vec<String> = vec![];
+    for contact in contacts {
+        lines.push("BEGIN:VCARD".to_owned());
+        lines.push("VERSION:4.0".to_owned());
+        lines.push(format!(
+            "KIND:{}",
+            match contact.t {
+                ContactType::Person => "individual",
+                ContactType::Company => "org",
+            }
+        ));
+        lines.push(format!("FN:{}", escape(&contact.name)));
+        lines.push(format!("EMAIL:{}", escape(&contact.email)));
+        // ADR: post office box;extended address;street;locality;region;postal code;country
+        lines.push(format!(
+            "ADR:;;{};{};;{};{}",
+            escape(&contact.postal_address.address),
+            escape(&contact.postal_address.city),
+            escape(contact.postal_address.zip.as_deref().unwrap_or_default()),
+            escape(&contact.postal_address.country),
+        ));
+        if let Some(ref date) = contact.date_of_birth_or_registration {
+            lines.push(format!("BDAY:{}", escape(date)));
+        }
+        if let Some(ref country) = contact.country_of_birth_or_registration {
+            lines.push(format!(
+                "{COUNTRY_OF_BIRTH_OR_REGISTRATION_PROPERTY}:{}",
+                escape(country)
+            ));
+        }
+        if let Some(ref city) = contact.city_of_birth_or_registration {
+            lines.push(format!(
+                "{CITY_OF_BIRTH_OR_REGISTRATION_PROPERTY}:{}",
+                escape(city)
+            ));
+        }
+        if let Some(ref identification_number) = contact.identification_number {
+            lines.push(format!(
+                "{IDENTIFICATION_NUMBER_PROPERTY}:{}",
+                escape(identification_number)
+            ));
+        }
+        for relay in contact.nostr_relays.iter() {
+            lines.push(format!("{NOSTR_RELAY_PROPERTY}:{}", escape(relay)));
+        }
+        lines.push(format!("{NODE_ID_PROPERTY}:{}", escape(&contact.node_id)));
+        lines.push("END:VCARD".to_owned());
+    }
+    // vCard requires CRLF line endings
+    lines
+        .into_iter()
+        .map(|line| format!("{line}\r\n"))
+        .collect()
+}
+
+/// Parses all vCard entries in the given input, skipping entries without a resolvable node id
+pub fn parse_vcards(input: &str) -> VCardImport {
+    let mut contacts = vec![];
+    let mut skipped = 0;
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for line in unfold_lines(input) {
+        let Some((name, value)) = split_property(&line) else {
+            continue;
+        };
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", _) if value.eq_ignore_ascii_case("VCARD") => current = Some(vec![]),
+            ("END", Some(properties)) if value.eq_ignore_ascii_case("VCARD") => {
+                match contact_from_properties(properties) {
+                    Some(contact) => contacts.push(contact),
+                    None => skipped += 1,
+                }
+                current = None;
+            }
+            (_, Some(properties)) => properties.push((name, value)),
+            (_, None) => (),
+        }
+    }
+    VCardImport { contacts, skipped }
+}
+
+fn contact_from_properties(properties: &[(String, String)]) -> Option<Contact> {
+    let get = |name: &str| {
+        properties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let get_unescaped = |name: &str| get(name).map(unescape);
+
+    let node_id = get_unescaped(NODE_ID_PROPERTY)?.trim().to_owned();
+    if crypto::validate_pub_key(&node_id).is_err() {
+        return None;
+    }
+
+    let t = match get("KIND") {
+        Some(kind) if kind.eq_ignore_ascii_case("org") => ContactType::Company,
+        _ => ContactType::Person,
+    };
+    // fall back to the structured name, if there is no formatted name
+    let name = get_unescaped("FN")
+        .or_else(|| {
+            get("N").map(|n| {
+                let parts = split_components(n);
+                // N: family name;given name;additional names;prefixes;suffixes
+                [parts.get(1), parts.first()]
+                    .into_iter()
+                    .flatten()
+                    .filter(|p| !p.is_empty())
+                    .cloned()
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            })
+        })
+        .unwrap_or_default();
+
+    let adr = get("ADR").map(split_components).unwrap_or_default();
+    let adr_component = |idx: usize| adr.get(idx).cloned().unwrap_or_default();
+    let zip = adr_component(5);
+    let postal_address = PostalAddress {
+        address: adr_component(2),
+        city: adr_component(3),
+        zip: if zip.is_empty() { None } else { Some(zip) },
+        country: adr_component(6),
+    };
+
+    Some(Contact {
+        t,
+        node_id,
+        name,
+        email: get_unescaped("EMAIL").unwrap_or_default(),
+        postal_address,
+        date_of_birth_or_registration: get_unescaped("BDAY"),
+        country_of_birth_or_registration: get_unescaped(COUNTRY_OF_BIRTH_OR_REGISTRATION_PROPERTY),
+        city_of_birth_or_registration: get_unescaped(CITY_OF_BIRTH_OR_REGISTRATION_PROPERTY),
+        identification_number: get_unescaped(IDENTIFICATION_NUMBER_PROPERTY),
+        avatar_file: None,
+        proof_document_file: None,
+        nostr_relays: properties
+            .iter()
+            .filter(|(n, _)| n == NOSTR_RELAY_PROPERTY)
+            .map(|(_, value)| unescape(value))
+            .collect(),
+    })
+}
+
+/// Joins folded lines - a line starting with a space, or tab continues the previous line
+fn unfold_lines(input: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    for line in input.lines() {
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_owned()),
+        }
+    }
+    lines
+}
+
+/// Splits a content line into its upper-cased property name and its raw value, dropping the
+/// group prefix and all parameters (e.g. `item1.EMAIL;TYPE=work:a@b.c` => `EMAIL`, `a@b.c`)
+fn split_property(line: &str) -> Option<(String, String)> {
+    let (name_and_params, value) = line.split_once(':')?;
+    let name = name_and_params.split(';').next()?;
+    let name = name.rsplit('.').next()?;
+    Some((name.trim().to_uppercase(), value.to_owned()))
+}
+
+/// Splits a structured value (e.g. ADR, or N) into its unescaped components
+fn split_components(value: &str) -> Vec<String> {
+    let mut components = vec![];
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push('\\');
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            ';' => components.push(unescape(&std::mem::take(&mut current))),
+            _ => current.push(c),
+        }
+    }
+    components.push(unescape(&current));
+    components
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(escaped) => result.push(escaped),
+            None => (),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::tests::{TEST_NODE_ID_SECP, empty_address};
+
+    fn get_contact() -> Contact {
+        Contact {
+            t: ContactType::Company,
+            node_id: TEST_NODE_ID_SECP.to_owned(),
+            name: "Müller, Meier; Partner".to_owned(),
+            email: "office@example.com".to_owned(),
+            postal_address: PostalAddress {
+                zip: Some("1020".to_owned()),
+                ..empty_address()
+            },
+            date_of_birth_or_registration: Some("2001-09-11".to_owned()),
+            country_of_birth_or_registration: Some("AT".to_owned()),
+            city_of_birth_or_registration: Some("Vienna".to_owned()),
+            identification_number: Some("FN 123456a".to_owned()),
+            avatar_file: None,
+            proof_document_file: None,
+            nostr_relays: vec!["ws://localhost:8080".to_owned()],
+        }
+    }
+
+    #[test]
+    fn vcard_roundtrip() {
+        let contact = get_contact();
+        let vcard = contacts_to_vcard(std::slice::from_ref(&contact));
+        let imported = parse_vcards(&vcard);
+        assert_eq!(imported.skipped, 0);
+        assert_eq!(imported.contacts.len(), 1);
+        let parsed = &imported.contacts[0];
+        assert_eq!(parsed.t, contact.t);
+        assert_eq!(parsed.node_id, contact.node_id);
+        assert_eq!(parsed.name, contact.name);
+        assert_eq!(parsed.email, contact.email);
+        assert_eq!(parsed.postal_address, contact.postal_address);
+        assert_eq!(
+            parsed.date_of_birth_or_registration,
+            contact.date_of_birth_or_registration
+        );
+        assert_eq!(
+            parsed.country_of_birth_or_registration,
+            contact.country_of_birth_or_registration
+        );
+        assert_eq!(
+            parsed.city_of_birth_or_registration,
+            contact.city_of_birth_or_registration
+        );
+        assert_eq!(parsed.identification_number, contact.identification_number);
+        assert_eq!(parsed.nostr_relays, contact.nostr_relays);
+    }
+
+    #[test]
+    fn parse_vcards_maps_standard_fields_and_skips_entries_without_node_id() {
+        let input = "BEGIN:VCARD\r\n\
+            VERSION:3.0\r\n\
+            N:Doe;Jane;;;\r\n\
+            item1.EMAIL;TYPE=INTERNET:jane@example.com\r\n\
+            ADR;TYPE=HOME:;;Some Street 1;Vien\r\n na;;1010;AT\r\n\
+            X-BCR-NODE-ID:03205b8dec12bc9e879f5b517aa32192a2550e88adcee3e54ec2c7294802568fef\r\n\
+            END:VCARD\r\n\
+            BEGIN:VCARD\r\n\
+            VERSION:3.0\r\n\
+            FN:No Node Id\r\n\
+            END:VCARD\r\n\
+            BEGIN:VCARD\r\n\
+            VERSION:3.0\r\n\
+            FN:Invalid Node Id\r\n\
+            X-BCR-NODE-ID:not a key\r\n\
+            END:VCARD\r\n";
+        let imported = parse_vcards(input);
+        assert_eq!(imported.skipped, 2);
+        assert_eq!(imported.contacts.len(), 1);
+        let contact = &imported.contacts[0];
+        assert_eq!(contact.t, ContactType::Person);
+        assert_eq!(contact.node_id, TEST_NODE_ID_SECP);
+        assert_eq!(contact.name, "Jane Doe");
+        assert_eq!(contact.email, "jane@example.com");
+        assert_eq!(contact.postal_address.address, "Some Street 1");
+        assert_eq!(contact.postal_address.city, "Vienna");
+        assert_eq!(contact.postal_address.zip, Some("1010".to_owned()));
+        assert_eq!(contact.postal_address.country, "AT");
+    }
+}
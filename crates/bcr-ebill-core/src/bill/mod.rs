@@ -1,8 +1,15 @@
-use crate::{blockchain::bill::BillBlockchain, util::BcrKeys};
+use crate::{
+    blockchain::bill::{BillBlock, BillBlockchain, BillOpCode},
+    constants::ACCEPT_DEADLINE_SECONDS,
+    util::{self, BcrKeys},
+};
 
 use super::{
     File, PostalAddress,
-    contact::{IdentityPublicData, LightIdentityPublicData, LightIdentityPublicDataWithAddress},
+    contact::{
+        ContactType, IdentityPublicData, LightIdentityPublicData,
+        LightIdentityPublicDataWithAddress,
+    },
     notification::Notification,
 };
 use borsh_derive::{BorshDeserialize, BorshSerialize};
@@ -16,6 +23,8 @@ pub enum BillAction {
     Accept,
     // currency
     RequestToPay(String),
+    // currency - requests acceptance, if the bill wasn't accepted yet, and payment in one step
+    RequestAcceptanceAndPayment(String),
     // buyer, sum, currency
     OfferToSell(IdentityPublicData, u64, String),
     // buyer, sum, currency, payment_address
@@ -28,10 +37,65 @@ pub enum BillAction {
     Recourse(IdentityPublicData, u64, String, RecourseReason),
     // mint, sum, currency
     Mint(IdentityPublicData, u64, String),
-    RejectAcceptance,
-    RejectPayment,
-    RejectBuying,
+    // reason
+    RejectAcceptance(Option<String>),
+    // reason
+    RejectPayment(Option<String>),
+    // reason
+    RejectBuying(Option<String>),
     RejectPaymentForRecourse,
+    // withdraws an offer to sell, which wasn't paid yet
+    CancelOfferToSell,
+    // withdraws a request to accept, which wasn't answered yet
+    CancelRequestToAccept,
+    // files to attach
+    AttachFile(Vec<File>),
+    // reference of a payment made outside of Bitcoin, e.g. a bank transfer - this is an
+    // attestation of the holder, not a cryptographic proof of payment
+    MarkPaidExternally(String),
+    // attests, that the holder, or the drawee has seen the bill - this carries no obligation
+    Acknowledge,
+    // new drawee - the drawer corrects the drawee of a bill, which didn't progress yet
+    CorrectDrawee(IdentityPublicData),
+    // sum, currency, expiry timestamp - the mint answers a request to mint with a quote
+    SubmitMintQuote(u64, String, u64),
+    // the requester approves the open quote of the mint
+    ApproveMintQuote,
+    // the requester rejects the open quote of the mint
+    RejectMintQuote,
+}
+
+impl BillAction {
+    /// The op code of the block the action results in - for actions resulting in multiple
+    /// blocks, the op code of the last one
+    pub fn op_code(&self) -> BillOpCode {
+        match self {
+            BillAction::RequestAcceptance => BillOpCode::RequestToAccept,
+            BillAction::Accept => BillOpCode::Accept,
+            BillAction::RequestToPay(_) | BillAction::RequestAcceptanceAndPayment(_) => {
+                BillOpCode::RequestToPay
+            }
+            BillAction::OfferToSell(_, _, _) => BillOpCode::OfferToSell,
+            BillAction::Sell(_, _, _, _) => BillOpCode::Sell,
+            BillAction::Endorse(_) => BillOpCode::Endorse,
+            BillAction::RequestRecourse(_, _) => BillOpCode::RequestRecourse,
+            BillAction::Recourse(_, _, _, _) => BillOpCode::Recourse,
+            BillAction::Mint(_, _, _) => BillOpCode::Mint,
+            BillAction::RejectAcceptance(_) => BillOpCode::RejectToAccept,
+            BillAction::RejectPayment(_) => BillOpCode::RejectToPay,
+            BillAction::RejectBuying(_) => BillOpCode::RejectToBuy,
+            BillAction::RejectPaymentForRecourse => BillOpCode::RejectToPayRecourse,
+            BillAction::CancelOfferToSell => BillOpCode::CancelOfferToSell,
+            BillAction::CancelRequestToAccept => BillOpCode::CancelRequestToAccept,
+            BillAction::AttachFile(_) => BillOpCode::AttachFile,
+            BillAction::MarkPaidExternally(_) => BillOpCode::ExternalPayment,
+            BillAction::Acknowledge => BillOpCode::Acknowledge,
+            BillAction::CorrectDrawee(_) => BillOpCode::CorrectDrawee,
+            BillAction::SubmitMintQuote(_, _, _) => BillOpCode::MintQuote,
+            BillAction::ApproveMintQuote => BillOpCode::ApproveMintQuote,
+            BillAction::RejectMintQuote => BillOpCode::RejectMintQuote,
+        }
+    }
 }
 
 #[repr(u8)]
@@ -51,7 +115,10 @@ pub struct BillIssueData {
     pub maturity_date: String,
     pub drawee: String,
     pub payee: String,
+    /// The sum in the given currency - either in sat, or a fiat sum with up to 2 decimals
     pub sum: String,
+    /// Either `sat`, or one of `VALID_FIAT_CURRENCIES`, in which case the sat equivalent of the
+    /// sum is computed at issuance
     pub currency: String,
     pub country_of_payment: String,
     pub city_of_payment: String,
@@ -60,6 +127,105 @@ pub struct BillIssueData {
     pub drawer_public_data: IdentityPublicData,
     pub drawer_keys: BcrKeys,
     pub timestamp: u64,
+    /// Seconds the drawee has to pay after a request to pay, defaults to PAYMENT_DEADLINE_SECONDS
+    pub payment_deadline_seconds: Option<u64>,
+}
+
+/// The reusable part of `BillIssueData`, to issue recurring bills quickly - everything except
+/// the signing keys and the timestamp
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillTemplate {
+    pub id: String,
+    pub name: String,
+    pub t: u64,
+    pub country_of_issuing: String,
+    pub city_of_issuing: String,
+    pub issue_date: String,
+    pub maturity_date: String,
+    pub drawee: String,
+    pub payee: String,
+    pub sum: String,
+    pub currency: String,
+    pub country_of_payment: String,
+    pub city_of_payment: String,
+    pub language: String,
+    pub file_upload_ids: Vec<String>,
+    pub drawer_public_data: IdentityPublicData,
+    pub payment_deadline_seconds: Option<u64>,
+}
+
+/// The values, which replace the values of a template, when a bill is issued from it
+#[derive(Debug, Clone, Default)]
+pub struct BillTemplateOverrides {
+    pub sum: Option<String>,
+    pub issue_date: Option<String>,
+    pub maturity_date: Option<String>,
+    pub file_upload_ids: Option<Vec<String>>,
+}
+
+impl BillTemplate {
+    /// The data to issue a bill from this template, with the given overrides applied
+    pub fn to_issue_data(
+        &self,
+        overrides: BillTemplateOverrides,
+        drawer_keys: BcrKeys,
+        timestamp: u64,
+    ) -> BillIssueData {
+        BillIssueData {
+            t: self.t,
+            country_of_issuing: self.country_of_issuing.clone(),
+            city_of_issuing: self.city_of_issuing.clone(),
+            issue_date: overrides
+                .issue_date
+                .unwrap_or_else(|| self.issue_date.clone()),
+            maturity_date: overrides
+                .maturity_date
+                .unwrap_or_else(|| self.maturity_date.clone()),
+            drawee: self.drawee.clone(),
+            payee: self.payee.clone(),
+            sum: overrides.sum.unwrap_or_else(|| self.sum.clone()),
+            currency: self.currency.clone(),
+            country_of_payment: self.country_of_payment.clone(),
+            city_of_payment: self.city_of_payment.clone(),
+            language: self.language.clone(),
+            file_upload_ids: overrides
+                .file_upload_ids
+                .unwrap_or_else(|| self.file_upload_ids.clone()),
+            drawer_public_data: self.drawer_public_data.clone(),
+            drawer_keys,
+            timestamp,
+            payment_deadline_seconds: self.payment_deadline_seconds,
+        }
+    }
+}
+
+/// A rule of a drawee to accept bills automatically, if they were drawn by the given trusted
+/// drawer and their sum doesn't exceed the given maximum sum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillAutoAcceptRule {
+    pub id: String,
+    /// The node id of the drawee (identity or company) the rule applies to
+    pub node_id: String,
+    pub drawer_node_id: String,
+    pub max_sum: u64,
+}
+
+impl BillAutoAcceptRule {
+    /// Checks, if a bill drawn by the given drawer with the given sum is covered by this rule
+    pub fn matches(&self, drawer_node_id: &str, sum: u64) -> bool {
+        self.drawer_node_id == drawer_node_id && sum <= self.max_sum
+    }
+}
+
+/// A bill, which was requested to be accepted and matched an auto-accept rule of the drawee
+/// when it was received, waiting to be accepted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedAutoAccept {
+    pub bill_id: String,
+    /// The node id of the drawee (identity or company), which accepts the bill
+    pub node_id: String,
+    pub rule_id: String,
+    pub timestamp: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -96,6 +262,18 @@ pub struct BitcreditBill {
     pub city_of_payment: String,
     pub language: String,
     pub files: Vec<File>,
+    pub payment_deadline_seconds: u64,
+    /// Set, if the bill was issued in a fiat currency - the sum is the sat equivalent
+    pub fiat_amount: Option<BillFiatAmount>,
+}
+
+/// The fiat sum a bill was issued with, and the BTC rate its sat equivalent was computed with
+#[derive(BorshSerialize, BorshDeserialize, Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BillFiatAmount {
+    pub currency: String,
+    pub sum: String,
+    pub btc_rate: f64,
+    pub rate_timestamp: u64,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
@@ -104,6 +282,45 @@ pub struct BillKeys {
     pub public_key: String,
 }
 
+/// The read-only key of a bill. The private key belongs to a separate viewing keypair, which the
+/// data of the exported blocks is encrypted to - it's unrelated to the bill keys, so an observer
+/// holding only the view key can neither sign blocks, nor derive payment keys
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BillViewKey {
+    /// The public key of the bill, which payment addresses are derived from
+    pub public_key: String,
+    /// The private key of the viewing keypair
+    pub private_key: String,
+}
+
+impl BillViewKey {
+    pub fn new(bill_public_key: &str, view_keys: &BcrKeys) -> Self {
+        Self {
+            public_key: bill_public_key.to_owned(),
+            private_key: view_keys.get_private_key_string(),
+        }
+    }
+
+    /// The keys to decrypt the view data of the blocks with
+    pub fn decryption_keys(&self) -> BillKeys {
+        BillKeys {
+            public_key: self.public_key.clone(),
+            private_key: self.private_key.clone(),
+        }
+    }
+}
+
+/// A read-only view of a bill, which a participant can share with an observer, e.g. an auditor
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BillView {
+    pub bill_id: String,
+    pub view_key: BillViewKey,
+    /// The blocks of the bill at the time it was exported
+    pub blocks: Vec<BillBlock>,
+    /// The data of each block, encrypted to the viewing keypair instead of the bill key
+    pub view_data: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RecourseReason {
     Accept,
@@ -136,6 +353,7 @@ pub struct BillWaitingForSellState {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimate>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -148,6 +366,7 @@ pub struct BillWaitingForPaymentState {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimate>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -160,6 +379,25 @@ pub struct BillWaitingForRecourseState {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimate>,
+}
+
+/// A suggested miner fee for paying a waiting bill before its deadline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeEstimate {
+    pub fee_rate_sat_vb: u64,
+    /// The time within which a payment with the suggested fee rate is expected to confirm
+    pub estimated_confirmation_seconds: u64,
+}
+
+/// A transaction, which funded the payment address of a bill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentTx {
+    pub txid: String,
+    /// The sum in sat the transaction sent to the payment address
+    pub amount: u64,
+    /// 0, if the transaction is still in the mempool
+    pub confirmations: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -170,6 +408,56 @@ pub struct BillStatus {
     pub recourse: BillRecourseStatus,
     pub redeemed_funds_available: bool,
     pub has_requested_funds: bool,
+    /// Set, if the bill was manually put on hold - held bills are skipped by the background jobs
+    pub hold: Option<BillHold>,
+    /// The op code of the latest block in the chain
+    pub last_op_code: BillOpCode,
+    /// The number of blocks in the chain
+    pub block_height: u64,
+    /// Whether the bill is still active, or reached a terminal state
+    pub settlement_status: SettlementStatus,
+}
+
+impl BillStatus {
+    /// Whether the drawee rejected to accept or to pay the bill, or let the request time out
+    pub fn is_dishonoured(&self) -> bool {
+        self.acceptance.rejected_to_accept
+            || self.acceptance.request_to_accept_timed_out
+            || self.payment.rejected_to_pay
+            || self.payment.request_to_pay_timed_out
+    }
+
+    /// Derives the settlement status from the status flags - `recourse_possible` tells, whether
+    /// the holder has past holders left to recourse against
+    pub fn derive_settlement_status(&self, recourse_possible: bool) -> SettlementStatus {
+        if self.payment.paid || self.recourse.recoursed {
+            return SettlementStatus::Settled;
+        }
+        if self.recourse.rejected_request_to_recourse
+            || self.recourse.request_to_recourse_timed_out
+            || (self.is_dishonoured() && !recourse_possible)
+        {
+            return SettlementStatus::Defaulted;
+        }
+        SettlementStatus::Active
+    }
+}
+
+/// The terminal state of a bill, derived from its chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettlementStatus {
+    /// The bill is still in circulation, or waiting for a payment, or a recourse
+    Active,
+    /// The bill was paid, or the holder was paid via recourse
+    Settled,
+    /// The bill wasn't honoured and there is no recourse left for the holder
+    Defaulted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillHold {
+    pub reason: String,
+    pub timestamp: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -179,6 +467,8 @@ pub struct BillAcceptanceStatus {
     pub accepted: bool,
     pub request_to_accept_timed_out: bool,
     pub rejected_to_accept: bool,
+    /// The reason the drawee gave for rejecting to accept, if any
+    pub rejected_to_accept_reason: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -186,8 +476,16 @@ pub struct BillPaymentStatus {
     pub time_of_request_to_pay: Option<u64>,
     pub requested_to_pay: bool,
     pub paid: bool,
+    /// The confirmed sum received on the payment address so far, which can be less than the
+    /// sum of the bill, if it was paid partially
+    pub paid_sum: u64,
     pub request_to_pay_timed_out: bool,
     pub rejected_to_pay: bool,
+    /// The reason the drawee gave for rejecting to pay, if any
+    pub rejected_to_pay_reason: Option<String>,
+    /// The reference of a payment outside of Bitcoin, if the holder attested it. Such a bill is
+    /// considered paid, but there is no cryptographic proof of the payment
+    pub external_payment_reference: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -197,6 +495,10 @@ pub struct BillSellStatus {
     pub offered_to_sell: bool,
     pub offer_to_sell_timed_out: bool,
     pub rejected_offer_to_sell: bool,
+    /// The reason the buyer gave for rejecting the last offer to sell, if any
+    pub rejected_offer_to_sell_reason: Option<String>,
+    /// The deadline for paying the last offer to sell, if any
+    pub sell_deadline_seconds: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -222,7 +524,16 @@ pub struct BillData {
     pub currency: String,
     pub sum: String,
     pub files: Vec<File>,
+    /// Names of attached files, whose locally stored content doesn't match the hash recorded in the chain
+    pub tampered_files: Vec<String>,
+    pub payment_deadline_seconds: u64,
+    pub fiat_amount: Option<BillFiatAmount>,
     pub active_notification: Option<Notification>,
+    /// The private note of the current identity on the bill - it's only stored locally and never
+    /// part of the chain, or shared with other participants
+    pub note: Option<String>,
+    /// Who attested to have seen the bill and when, in the order of the chain
+    pub acknowledgements: Vec<BillAcknowledgement>,
 }
 
 #[derive(Debug, Clone)]
@@ -268,71 +579,251 @@ impl BitcreditBillResult {
         Some(BillRole::Contingent)
     }
 
-    // Search in the participants for the search term
-    pub fn search_bill_for_search_term(&self, search_term: &str) -> bool {
-        let search_term_lc = search_term.to_lowercase();
-        if self
-            .participants
-            .payee
-            .name
-            .to_lowercase()
-            .contains(&search_term_lc)
+    /// Returns true, if the bill is past its maturity date at the given timestamp and not paid
+    pub fn is_overdue(&self, now: u64) -> bool {
+        !self.status.payment.paid && self.data.time_of_maturity < now
+    }
+
+    /// Returns true, if the given node_id is expected to act on the bill - to accept it as the
+    /// drawee, or to pay it as the payer of an open payment, sell, or recourse request
+    pub fn is_awaiting_action_by(&self, node_id: &str) -> bool {
+        let acceptance = &self.status.acceptance;
+        if self.participants.drawee.node_id == node_id
+            && acceptance.requested_to_accept
+            && !acceptance.accepted
+            && !acceptance.rejected_to_accept
+            && !acceptance.request_to_accept_timed_out
         {
             return true;
         }
 
-        if self
-            .participants
-            .drawer
-            .name
-            .to_lowercase()
-            .contains(&search_term_lc)
+        match self.current_waiting_state {
+            Some(BillCurrentWaitingState::Payment(ref state)) => state.payer.node_id == node_id,
+            Some(BillCurrentWaitingState::Sell(ref state)) => state.buyer.node_id == node_id,
+            Some(BillCurrentWaitingState::Recourse(ref state)) => {
+                state.recoursee.node_id == node_id
+            }
+            None => false,
+        }
+    }
+
+    /// Returns true, if the given node_id started a request on the bill, which is still waiting
+    /// for a counterparty at the given timestamp - as the holder, who requested acceptance, or
+    /// as the requester of an open payment, sell, or recourse request
+    pub fn is_awaiting_response_to(&self, node_id: &str, now: u64) -> bool {
+        let acceptance = &self.status.acceptance;
+        if self.get_bill_role_for_node_id(node_id) == Some(BillRole::Payee)
+            && acceptance.requested_to_accept
+            && !acceptance.accepted
+            && !acceptance.rejected_to_accept
+            && !acceptance.request_to_accept_timed_out
+            && acceptance
+                .time_of_request_to_accept
+                .is_some_and(|time_of_request| {
+                    !util::date::check_if_deadline_has_passed(
+                        time_of_request,
+                        now,
+                        ACCEPT_DEADLINE_SECONDS,
+                    )
+                })
         {
             return true;
         }
 
-        if self
-            .participants
-            .drawee
-            .name
-            .to_lowercase()
-            .contains(&search_term_lc)
+        match self.current_waiting_state {
+            Some(BillCurrentWaitingState::Payment(ref state)) => state.payee.node_id == node_id,
+            Some(BillCurrentWaitingState::Sell(ref state)) => state.seller.node_id == node_id,
+            Some(BillCurrentWaitingState::Recourse(ref state)) => {
+                state.recourser.node_id == node_id
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the most relevant action the given node_id should take next on the bill at the
+    /// given timestamp, or None, if there is nothing pending for them. Requests waiting for the
+    /// node_id take precedence over the follow-up actions of the holder.
+    pub fn get_suggested_action(&self, node_id: &str, now: u64) -> Option<SuggestedAction> {
+        let acceptance = &self.status.acceptance;
+        if self.participants.drawee.node_id == node_id
+            && acceptance.requested_to_accept
+            && !acceptance.accepted
+            && !acceptance.rejected_to_accept
+            && !acceptance.request_to_accept_timed_out
         {
-            return true;
+            return Some(SuggestedAction::Accept);
         }
 
-        if let Some(ref endorsee) = self.participants.endorsee {
-            if endorsee.name.to_lowercase().contains(&search_term_lc) {
-                return true;
+        match self.current_waiting_state {
+            Some(BillCurrentWaitingState::Payment(ref state)) => {
+                return (state.payer.node_id == node_id).then_some(SuggestedAction::Pay);
             }
+            Some(BillCurrentWaitingState::Sell(ref state)) => {
+                return (state.buyer.node_id == node_id).then_some(SuggestedAction::Buy);
+            }
+            Some(BillCurrentWaitingState::Recourse(ref state)) => {
+                return (state.recoursee.node_id == node_id)
+                    .then_some(SuggestedAction::PayRecourse);
+            }
+            None => (),
+        };
+
+        // the follow-up actions are only relevant for the holder of an unpaid bill, which can
+        // still be acted on
+        if self.status.payment.paid
+            || self.status.recourse.rejected_request_to_recourse
+            || self.get_bill_role_for_node_id(node_id) != Some(BillRole::Payee)
+        {
+            return None;
+        }
+
+        let payment = &self.status.payment;
+        if acceptance.rejected_to_accept
+            || acceptance.request_to_accept_timed_out
+            || payment.rejected_to_pay
+            || payment.request_to_pay_timed_out
+        {
+            return Some(SuggestedAction::RequestRecourse);
+        }
+
+        if !payment.requested_to_pay && self.data.time_of_maturity <= now {
+            return Some(SuggestedAction::RequestToPay);
+        }
+
+        None
+    }
+
+    /// Returns true, if the bill has the given status for the given node_id at the given
+    /// timestamp
+    pub fn matches_status(&self, status: &BillsFilterStatus, node_id: &str, now: u64) -> bool {
+        match status {
+            BillsFilterStatus::All => true,
+            BillsFilterStatus::Paid => self.status.payment.paid,
+            BillsFilterStatus::Unpaid => !self.status.payment.paid,
+            BillsFilterStatus::Overdue => self.is_overdue(now),
+            BillsFilterStatus::AwaitingAction => self.is_awaiting_action_by(node_id),
+        }
+    }
+
+    // Search in the participants for the search term
+    pub fn search_bill_for_search_term(&self, search_term: &str) -> bool {
+        self.searchable_names()
+            .into_iter()
+            .any(|name| util::search::matches_exact(name, search_term))
+    }
+
+    // Search in the participants for names within a small edit distance of the search term
+    pub fn fuzzy_search_bill_for_search_term(&self, search_term: &str) -> bool {
+        self.searchable_names()
+            .into_iter()
+            .any(|name| util::search::matches_fuzzy(name, search_term))
+    }
+
+    // The names of all participants, which are relevant for search
+    fn searchable_names(&self) -> Vec<&str> {
+        let mut names = vec![
+            self.participants.payee.name.as_str(),
+            self.participants.drawer.name.as_str(),
+            self.participants.drawee.name.as_str(),
+        ];
+
+        if let Some(ref endorsee) = self.participants.endorsee {
+            names.push(endorsee.name.as_str());
         }
 
         if let Some(BillCurrentWaitingState::Sell(ref sell_waiting_state)) =
             self.current_waiting_state
         {
-            if sell_waiting_state
-                .buyer
-                .name
-                .to_lowercase()
-                .contains(&search_term_lc)
-            {
-                return true;
-            }
+            names.push(sell_waiting_state.buyer.name.as_str());
+            names.push(sell_waiting_state.seller.name.as_str());
+        }
 
-            if sell_waiting_state
-                .seller
-                .name
-                .to_lowercase()
-                .contains(&search_term_lc)
-            {
-                return true;
-            }
+        names
+    }
+
+    /// Returns the status and participant fields, in which this bill differs from the given one,
+    /// e.g. to find out in which way a cached bill is stale
+    pub fn diff_status_and_participants(&self, current: &BitcreditBillResult) -> Vec<FieldDiff> {
+        let mut diffs = vec![];
+        macro_rules! diff {
+            ($($field:ident).+) => {
+                push_field_diff(
+                    &mut diffs,
+                    stringify!($($field).+),
+                    &self.$($field).+,
+                    &current.$($field).+,
+                );
+            };
         }
 
-        false
+        diff!(status.acceptance.time_of_request_to_accept);
+        diff!(status.acceptance.requested_to_accept);
+        diff!(status.acceptance.accepted);
+        diff!(status.acceptance.request_to_accept_timed_out);
+        diff!(status.acceptance.rejected_to_accept);
+        diff!(status.acceptance.rejected_to_accept_reason);
+        diff!(status.payment.time_of_request_to_pay);
+        diff!(status.payment.requested_to_pay);
+        diff!(status.payment.paid);
+        diff!(status.payment.paid_sum);
+        diff!(status.payment.request_to_pay_timed_out);
+        diff!(status.payment.rejected_to_pay);
+        diff!(status.payment.rejected_to_pay_reason);
+        diff!(status.payment.external_payment_reference);
+        diff!(status.sell.time_of_last_offer_to_sell);
+        diff!(status.sell.sold);
+        diff!(status.sell.offered_to_sell);
+        diff!(status.sell.offer_to_sell_timed_out);
+        diff!(status.sell.rejected_offer_to_sell);
+        diff!(status.sell.rejected_offer_to_sell_reason);
+        diff!(status.recourse.time_of_last_request_to_recourse);
+        diff!(status.recourse.recoursed);
+        diff!(status.recourse.requested_to_recourse);
+        diff!(status.recourse.request_to_recourse_timed_out);
+        diff!(status.recourse.rejected_request_to_recourse);
+        diff!(status.redeemed_funds_available);
+        diff!(status.has_requested_funds);
+        diff!(status.hold);
+        diff!(status.last_op_code);
+        diff!(status.block_height);
+        diff!(participants.drawee);
+        diff!(participants.drawer);
+        diff!(participants.payee);
+        diff!(participants.endorsee);
+        diff!(participants.endorsements_count);
+        diff!(participants.all_participant_node_ids);
+
+        diffs
     }
 }
 
+// The fields don't all implement PartialEq, so they are compared by their debug representation
+fn push_field_diff<T: std::fmt::Debug>(
+    diffs: &mut Vec<FieldDiff>,
+    field: &str,
+    cached: &T,
+    current: &T,
+) {
+    let cached = format!("{cached:?}");
+    let current = format!("{current:?}");
+    if cached != current {
+        diffs.push(FieldDiff {
+            field: field.to_owned(),
+            cached,
+            current,
+        });
+    }
+}
+
+/// A field, in which a cached bill differs from the bill calculated from its chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// The path of the field, e.g. `status.payment.paid`
+    pub field: String,
+    pub cached: String,
+    pub current: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct LightBitcreditBillResult {
     pub id: String,
@@ -346,6 +837,8 @@ pub struct LightBitcreditBillResult {
     pub issue_date: String,
     pub time_of_drawing: u64,
     pub time_of_maturity: u64,
+    pub last_op_code: BillOpCode,
+    pub block_height: u64,
 }
 
 impl From<BitcreditBillResult> for LightBitcreditBillResult {
@@ -362,6 +855,8 @@ impl From<BitcreditBillResult> for LightBitcreditBillResult {
             issue_date: value.data.issue_date,
             time_of_drawing: value.data.time_of_drawing,
             time_of_maturity: value.data.time_of_maturity,
+            last_op_code: value.status.last_op_code,
+            block_height: value.status.block_height,
         }
     }
 }
@@ -378,6 +873,41 @@ pub struct BillsBalance {
     pub sum: String,
 }
 
+/// The total sat exposure of a node across all roles in its bills
+#[derive(Debug, Clone)]
+pub struct ExposureSummary {
+    pub balances: BillsBalanceOverview,
+    /// What's owed to the node minus what it owes - negative, if it owes more
+    pub net_position: String,
+    pub accepted_count: u64,
+    pub awaiting_acceptance_count: u64,
+    /// Unpaid bills, whose maturity date has passed
+    pub overdue_count: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BillsBalanceOverviewWithFiat {
+    pub payee: BillsBalanceWithFiat,
+    pub payer: BillsBalanceWithFiat,
+    pub contingent: BillsBalanceWithFiat,
+    /// The rate the fiat sums were converted with - `None` if it couldn't be fetched
+    pub fiat_rate: Option<FiatRate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BillsBalanceWithFiat {
+    pub sum: String,
+    pub fiat_sum: Option<String>,
+}
+
+/// The price of one BTC in the given fiat currency, fetched at the given timestamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiatRate {
+    pub fiat_code: String,
+    pub rate: f64,
+    pub timestamp: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BillRole {
     Payee,
@@ -385,6 +915,23 @@ pub enum BillRole {
     Contingent,
 }
 
+/// The next action a participant should take on a bill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SuggestedAction {
+    /// The drawee should accept the bill
+    Accept,
+    /// The payer should pay the bill
+    Pay,
+    /// The buyer should pay for the bill, which was offered to them
+    Buy,
+    /// The recoursee should pay the recourse
+    PayRecourse,
+    /// The holder should request payment, since the bill matured
+    RequestToPay,
+    /// The holder should request recourse, since acceptance, or payment was rejected, or timed out
+    RequestRecourse,
+}
+
 #[derive(Debug)]
 pub struct BillCombinedBitcoinKey {
     pub private_key: String,
@@ -398,6 +945,30 @@ pub enum BillsFilterRole {
     Contingent,
 }
 
+/// Filters bills by their status, as computed from their chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BillsFilterStatus {
+    All,
+    Paid,
+    Unpaid,
+    /// Past the maturity date and not paid
+    Overdue,
+    /// The current identity is expected to act, i.e. to accept, or to pay the bill
+    AwaitingAction,
+}
+
+/// The display data of a bill participant, as it's embedded in the bill chain - e.g. to show
+/// names for participants, who aren't in the contacts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedParticipant {
+    pub t: ContactType,
+    pub node_id: String,
+    pub name: String,
+    pub postal_address: PostalAddress,
+    /// The height of the latest block the data was taken from
+    pub block_height: u64,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct PastEndorsee {
     pub pay_to_the_order_of: LightIdentityPublicData,
@@ -406,6 +977,42 @@ pub struct PastEndorsee {
     pub signing_address: PostalAddress,
 }
 
+/// A mismatch between the persisted keys and chain of a bill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillInconsistency {
+    pub bill_id: String,
+    pub kind: BillInconsistencyKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BillInconsistencyKind {
+    /// There are keys for the bill, but no chain
+    KeysWithoutChain,
+    /// There is a chain for the bill, but no keys
+    ChainWithoutKeys,
+    /// There are blocks for the bill, but they can't be loaded as a valid chain
+    InvalidChain,
+}
+
+/// A single block of a bill's chain as a human-readable event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillHistoryEntry {
+    pub block_height: u64,
+    pub op_code: BillOpCode,
+    pub acting_node_id: String,
+    pub timestamp: u64,
+    pub summary: String,
+}
+
+/// An attestation of the holder, or the drawee, that they have seen the bill - this is not an
+/// acceptance and carries no payment obligation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BillAcknowledgement {
+    pub node_id: String,
+    pub name: String,
+    pub timestamp: u64,
+}
+
 #[derive(Debug)]
 pub struct Endorsement {
     pub pay_to_the_order_of: LightIdentityPublicDataWithAddress,
@@ -1,20 +1,36 @@
 use crate::{
-    Validate, ValidationError,
+    Field, Validate, ValidationError,
     blockchain::{
         Block, Blockchain,
         bill::{
             BillOpCode, OfferToSellWaitingForPayment, RecourseWaitingForPayment,
-            block::BillRecourseReasonBlockData,
+            block::{BillRecourseReasonBlockData, BillRequestToAcceptBlockData},
         },
     },
-    constants::{ACCEPT_DEADLINE_SECONDS, PAYMENT_DEADLINE_SECONDS, RECOURSE_DEADLINE_SECONDS},
+    constants::{
+        ACCEPT_DEADLINE_SECONDS, MAX_PAYMENT_DEADLINE_SECONDS, MAX_REJECT_REASON_CHARACTERS,
+        RECOURSE_DEADLINE_SECONDS,
+    },
     util::{self, date::start_of_day_as_timestamp},
 };
 
 use super::{BillAction, BillIssueData, BillType, BillValidateActionData, RecourseReason};
 
-pub fn validate_bill_issue(data: &BillIssueData) -> Result<(u64, BillType), ValidationError> {
-    let sum = util::currency::parse_sum(&data.sum).map_err(|_| ValidationError::InvalidSum)?;
+/// Validates the data of a bill to issue and returns the parsed sum, its currency and the bill
+/// type - the sum is in the smallest unit of the currency, so for fiat bills it's in cents and
+/// sums given in btc are converted to sat
+pub fn validate_bill_issue(
+    data: &BillIssueData,
+) -> Result<(u64, String, BillType), ValidationError> {
+    let (sum, currency) = util::currency::parse_sum_in_currency(&data.sum, &data.currency)?;
+    util::currency::validate_sum(sum)?;
+
+    if let Some(payment_deadline_seconds) = data.payment_deadline_seconds {
+        validate_payment_deadline_seconds(payment_deadline_seconds)?;
+    }
+
+    util::country::validate_country_code(&data.country_of_issuing)?;
+    util::country::validate_country_code(&data.country_of_payment)?;
 
     for file_upload_id in &data.file_upload_ids {
         util::validate_file_upload_id(Some(file_upload_id))?;
@@ -56,7 +72,7 @@ pub fn validate_bill_issue(data: &BillIssueData) -> Result<(u64, BillType), Vali
     if data.drawee == data.payee {
         return Err(ValidationError::DraweeCantBePayee);
     }
-    Ok((sum, bill_type))
+    Ok((sum, currency, bill_type))
 }
 
 impl Validate for BillValidateActionData {
@@ -92,26 +108,28 @@ impl Validate for BillValidateActionData {
         if let Some(req_to_recourse) = self
             .blockchain
             .get_last_version_block_with_op_code(BillOpCode::RequestRecourse)
+            && BillOpCode::RequestRecourse == *self.blockchain.get_latest_block().op_code()
+            && util::date::check_if_deadline_has_passed(
+                req_to_recourse.timestamp,
+                self.timestamp,
+                RECOURSE_DEADLINE_SECONDS,
+            )
         {
-            if BillOpCode::RequestRecourse == *self.blockchain.get_latest_block().op_code()
-                && util::date::check_if_deadline_has_passed(
-                    req_to_recourse.timestamp,
-                    self.timestamp,
-                    RECOURSE_DEADLINE_SECONDS,
-                )
-            {
-                return Err(ValidationError::BillRequestToRecourseExpired);
-            }
+            return Err(ValidationError::BillRequestToRecourseExpired);
         }
 
-        // If the bill was paid, no further actions are allowed
-        if self.is_paid {
+        // If the bill was paid, or marked as paid externally, no further actions are allowed
+        if self.is_paid
+            || self
+                .blockchain
+                .block_with_operation_code_exists(BillOpCode::ExternalPayment)
+        {
             return Err(ValidationError::BillAlreadyPaid);
         }
 
         match &self.bill_action {
             BillAction::Accept => {
-                self.bill_is_blocked()?;
+                self.bill_is_blocked_for_acceptance()?;
                 self.bill_can_only_be_recoursed()?;
                 // not already accepted
                 if self
@@ -135,10 +153,11 @@ impl Validate for BillValidateActionData {
                 {
                     return Err(ValidationError::BillAlreadyAccepted);
                 }
-                // not already requested to accept
+                // not already requested to accept, unless the request was cancelled
                 if self
                     .blockchain
-                    .block_with_operation_code_exists(BillOpCode::RequestToAccept)
+                    .get_last_active_request_to_accept_block()
+                    .is_some()
                 {
                     return Err(ValidationError::BillAlreadyRequestedToAccept);
                 }
@@ -162,6 +181,32 @@ impl Validate for BillValidateActionData {
                     return Err(ValidationError::CallerIsNotHolder);
                 }
             }
+            BillAction::RequestAcceptanceAndPayment(_) => {
+                self.bill_is_blocked()?;
+                self.bill_can_only_be_recoursed()?;
+                // if not already accepted, the acceptance is requested as well, so it must not
+                // have been requested already
+                if !self
+                    .blockchain
+                    .block_with_operation_code_exists(BillOpCode::Accept)
+                    && self
+                        .blockchain
+                        .get_last_active_request_to_accept_block()
+                        .is_some()
+                {
+                    return Err(ValidationError::BillAlreadyRequestedToAccept);
+                }
+                // maturity date must have started
+                let maturity_date_start =
+                    util::date::date_string_to_timestamp(&self.maturity_date, None)?;
+                if self.timestamp < maturity_date_start {
+                    return Err(ValidationError::BillRequestedToPayBeforeMaturityDate);
+                }
+                // the caller has to be the bill holder
+                if self.signer_node_id != holder_node_id {
+                    return Err(ValidationError::CallerIsNotHolder);
+                }
+            }
             BillAction::RequestRecourse(recoursee, recourse_reason) => {
                 // not blocked
                 self.bill_is_blocked()?;
@@ -185,9 +230,8 @@ impl Validate for BillValidateActionData {
 
                 match recourse_reason {
                     RecourseReason::Accept => {
-                        if let Some(req_to_accept) = self
-                            .blockchain
-                            .get_last_version_block_with_op_code(BillOpCode::RequestToAccept)
+                        if let Some(req_to_accept) =
+                            self.blockchain.get_last_active_request_to_accept_block()
                         {
                             // only if the request to accept expired or was rejected
                             if !util::date::check_if_deadline_has_passed(
@@ -225,7 +269,8 @@ impl Validate for BillValidateActionData {
                             if !util::date::check_if_deadline_has_passed(
                                 deadline_base,
                                 self.timestamp,
-                                PAYMENT_DEADLINE_SECONDS,
+                                self.blockchain
+                                    .get_payment_deadline_seconds(&self.bill_keys)?,
                             ) && !self
                                 .blockchain
                                 .block_with_operation_code_exists(BillOpCode::RejectToPay)
@@ -337,8 +382,9 @@ impl Validate for BillValidateActionData {
                     return Err(ValidationError::CallerIsNotHolder);
                 }
             }
-            BillAction::RejectAcceptance => {
-                self.bill_is_blocked()?;
+            BillAction::RejectAcceptance(reason) => {
+                validate_reject_reason(reason.as_deref())?;
+                self.bill_is_blocked_for_acceptance()?;
                 self.bill_can_only_be_recoursed()?;
                 // if the op was already rejected, can't reject again - checked above
                 // caller has to be the drawee
@@ -353,7 +399,8 @@ impl Validate for BillValidateActionData {
                     return Err(ValidationError::BillAlreadyAccepted);
                 }
             }
-            BillAction::RejectBuying => {
+            BillAction::RejectBuying(reason) => {
+                validate_reject_reason(reason.as_deref())?;
                 // not in recourse
                 self.bill_waiting_for_recourse_payment()?;
                 // not waiting for req to pay
@@ -379,7 +426,51 @@ impl Validate for BillValidateActionData {
                     return Err(ValidationError::BillWasNotOfferedToSell);
                 }
             }
-            BillAction::RejectPayment => {
+            BillAction::CancelOfferToSell => {
+                // there has to be an offer to sell block that is not expired, sold, rejected,
+                // or cancelled already
+                if let OfferToSellWaitingForPayment::Yes(payment_info) = self
+                    .blockchain
+                    .is_last_offer_to_sell_block_waiting_for_payment(
+                        &self.bill_keys,
+                        self.timestamp,
+                    )?
+                {
+                    // caller has to be seller of the offer to sell
+                    if self.signer_node_id != payment_info.seller.node_id {
+                        return Err(ValidationError::CallerIsNotSeller);
+                    }
+                } else {
+                    return Err(ValidationError::BillIsNotOfferToSellWaitingForPayment);
+                }
+            }
+            BillAction::CancelRequestToAccept => {
+                self.bill_is_blocked()?;
+                // rejected or expired requests to accept are checked here
+                self.bill_can_only_be_recoursed()?;
+                // not already accepted
+                if self
+                    .blockchain
+                    .block_with_operation_code_exists(BillOpCode::Accept)
+                {
+                    return Err(ValidationError::BillAlreadyAccepted);
+                }
+                // there has to be a request to accept that wasn't cancelled already
+                if let Some(req_to_accept) =
+                    self.blockchain.get_last_active_request_to_accept_block()
+                {
+                    // caller has to be the requester
+                    let data: BillRequestToAcceptBlockData =
+                        req_to_accept.get_decrypted_block_bytes(&self.bill_keys)?;
+                    if self.signer_node_id != data.requester.node_id {
+                        return Err(ValidationError::CallerIsNotRequester);
+                    }
+                } else {
+                    return Err(ValidationError::BillWasNotRequestedToAccept);
+                }
+            }
+            BillAction::RejectPayment(reason) => {
+                validate_reject_reason(reason.as_deref())?;
                 // not waiting for offer to sell
                 self.bill_waiting_for_offer_to_sell()?;
                 // not in recourse
@@ -422,6 +513,109 @@ impl Validate for BillValidateActionData {
                     return Err(ValidationError::BillWasNotRequestedToRecourse);
                 }
             }
+            BillAction::AttachFile(_) => {
+                self.bill_is_blocked()?;
+                // the caller has to be the bill holder
+                if self.signer_node_id != holder_node_id {
+                    return Err(ValidationError::CallerIsNotHolder);
+                }
+            }
+            BillAction::MarkPaidExternally(reference) => {
+                // not offered to sell
+                self.bill_waiting_for_offer_to_sell()?;
+                // not in recourse
+                self.bill_waiting_for_recourse_payment()?;
+                // not paid on-chain, or marked as paid externally already - checked above
+                if reference.trim().is_empty() {
+                    return Err(ValidationError::FieldEmpty(Field::ExternalPaymentReference));
+                }
+                // the holder acknowledges having received the payment
+                if self.signer_node_id != holder_node_id {
+                    return Err(ValidationError::CallerIsNotHolder);
+                }
+            }
+            BillAction::Acknowledge => {
+                self.bill_is_blocked()?;
+                // only the parties, who have to act on the bill can acknowledge it - acknowledging
+                // it again is handled by the caller, since it doesn't add another block
+                if self.signer_node_id != holder_node_id
+                    && self.signer_node_id != self.drawee_node_id
+                {
+                    return Err(ValidationError::CallerIsNotHolderOrDrawee);
+                }
+            }
+            BillAction::CorrectDrawee(new_drawee) => {
+                // only possible before the bill progressed - files and acknowledgements don't
+                // change the obligations of the bill
+                if self.blockchain.blocks().iter().any(|b| {
+                    !matches!(
+                        b.op_code,
+                        BillOpCode::Issue
+                            | BillOpCode::CorrectDrawee
+                            | BillOpCode::AttachFile
+                            | BillOpCode::Acknowledge
+                    )
+                }) {
+                    return Err(ValidationError::DraweeCorrectionNotAllowed);
+                }
+                if new_drawee.node_id == self.payee_node_id {
+                    return Err(ValidationError::DraweeCantBePayee);
+                }
+                // the caller has to be the drawer
+                let drawer_node_id = self
+                    .blockchain
+                    .get_first_version_bill(&self.bill_keys)?
+                    .drawer
+                    .node_id;
+                if self.signer_node_id != drawer_node_id {
+                    return Err(ValidationError::CallerIsNotDrawer);
+                }
+            }
+            BillAction::SubmitMintQuote(sum, currency, expiry_timestamp) => {
+                // there has to be a request to mint, which wasn't quoted yet, or whose quote was
+                // rejected
+                if let Some(mint_request) = self
+                    .blockchain
+                    .get_mint_request_waiting_for_quote(&self.bill_keys)?
+                {
+                    // the caller has to be the mint
+                    if self.signer_node_id != mint_request.mint.node_id {
+                        return Err(ValidationError::CallerIsNotMint);
+                    }
+                } else {
+                    return Err(ValidationError::BillWasNotRequestedToMint);
+                }
+                util::currency::validate_currency(currency)?;
+                util::currency::validate_sum(*sum)?;
+                if *expiry_timestamp <= self.timestamp {
+                    return Err(ValidationError::MintQuoteExpired);
+                }
+            }
+            BillAction::ApproveMintQuote => {
+                // there has to be an open quote, which didn't expire
+                if let Some((_, quote)) = self.blockchain.get_open_mint_quote(&self.bill_keys)? {
+                    // the caller has to be the requester of the request to mint
+                    if self.signer_node_id != quote.requester.node_id {
+                        return Err(ValidationError::CallerIsNotMintRequester);
+                    }
+                    if self.timestamp >= quote.expiry_timestamp {
+                        return Err(ValidationError::MintQuoteExpired);
+                    }
+                } else {
+                    return Err(ValidationError::BillHasNoOpenMintQuote);
+                }
+            }
+            BillAction::RejectMintQuote => {
+                // there has to be an open quote - expired quotes can be rejected as well
+                if let Some((_, quote)) = self.blockchain.get_open_mint_quote(&self.bill_keys)? {
+                    // the caller has to be the requester of the request to mint
+                    if self.signer_node_id != quote.requester.node_id {
+                        return Err(ValidationError::CallerIsNotMintRequester);
+                    }
+                } else {
+                    return Err(ValidationError::BillHasNoOpenMintQuote);
+                }
+            }
         };
         Ok(())
     }
@@ -446,6 +640,46 @@ pub fn get_deadline_base_for_req_to_pay(
     Ok(deadline_base)
 }
 
+/// validates the optional reason of a rejection, which is recorded in the bill chain
+pub fn validate_reject_reason(reason: Option<&str>) -> Result<(), ValidationError> {
+    if let Some(reason) = reason
+        && reason.chars().count() > MAX_REJECT_REASON_CHARACTERS
+    {
+        return Err(ValidationError::RejectReasonTooLong(
+            MAX_REJECT_REASON_CHARACTERS,
+        ));
+    }
+    Ok(())
+}
+
+/// validates the payment deadline a bill is issued with - it's bounded, so adding it to a
+/// timestamp can't overflow
+pub fn validate_payment_deadline_seconds(
+    payment_deadline_seconds: u64,
+) -> Result<(), ValidationError> {
+    if payment_deadline_seconds == 0 || payment_deadline_seconds > MAX_PAYMENT_DEADLINE_SECONDS {
+        return Err(ValidationError::InvalidPaymentDeadline);
+    }
+    Ok(())
+}
+
+/// validates, that a bill with the given number of files and the given total size of them stays
+/// within the given limits
+pub fn validate_bill_attachments(
+    file_count: usize,
+    total_bytes: usize,
+    max_files: usize,
+    max_total_bytes: usize,
+) -> Result<(), ValidationError> {
+    if file_count > max_files {
+        return Err(ValidationError::TooManyFiles(max_files));
+    }
+    if total_bytes > max_total_bytes {
+        return Err(ValidationError::AttachmentsTooBig(max_total_bytes));
+    }
+    Ok(())
+}
+
 impl BillValidateActionData {
     /// if the bill was rejected to accept, rejected to pay, or either of them expired, it can only
     /// be recoursed from that point on
@@ -485,16 +719,16 @@ impl BillValidateActionData {
                         && util::date::check_if_deadline_has_passed(
                             deadline_base,
                             self.timestamp,
-                            PAYMENT_DEADLINE_SECONDS,
+                            self.blockchain
+                                .get_payment_deadline_seconds(&self.bill_keys)?,
                         )
                     {
                         return Err(ValidationError::BillPaymentExpired);
                     }
                 }
 
-                if let Some(req_to_accept_block) = self
-                    .blockchain
-                    .get_last_version_block_with_op_code(BillOpCode::RequestToAccept)
+                if let Some(req_to_accept_block) =
+                    self.blockchain.get_last_active_request_to_accept_block()
                 {
                     let accepted = self
                         .blockchain
@@ -528,6 +762,17 @@ impl BillValidateActionData {
         Ok(())
     }
 
+    /// the drawee can still accept, or reject to accept, while the request to pay of a combined
+    /// request to accept and pay is outstanding
+    fn bill_is_blocked_for_acceptance(&self) -> Result<(), ValidationError> {
+        if !self.blockchain.is_latest_block_combined_request_to_pay() {
+            self.bill_waiting_for_req_to_pay()?;
+        }
+        self.bill_waiting_for_offer_to_sell()?;
+        self.bill_waiting_for_recourse_payment()?;
+        Ok(())
+    }
+
     fn bill_waiting_for_offer_to_sell(&self) -> Result<(), ValidationError> {
         if let OfferToSellWaitingForPayment::Yes(_) = self
             .blockchain
@@ -552,22 +797,22 @@ impl BillValidateActionData {
     }
 
     fn bill_waiting_for_req_to_pay(&self) -> Result<(), ValidationError> {
-        if self.blockchain.get_latest_block().op_code == BillOpCode::RequestToPay {
-            if let Some(req_to_pay) = self
+        if self.blockchain.get_latest_block_for_waiting_state().op_code == BillOpCode::RequestToPay
+            && let Some(req_to_pay) = self
                 .blockchain
                 .get_last_version_block_with_op_code(BillOpCode::RequestToPay)
+        {
+            let deadline_base =
+                get_deadline_base_for_req_to_pay(req_to_pay.timestamp, &self.maturity_date)?;
+            if !self.is_paid
+                && !util::date::check_if_deadline_has_passed(
+                    deadline_base,
+                    self.timestamp,
+                    self.blockchain
+                        .get_payment_deadline_seconds(&self.bill_keys)?,
+                )
             {
-                let deadline_base =
-                    get_deadline_base_for_req_to_pay(req_to_pay.timestamp, &self.maturity_date)?;
-                if !self.is_paid
-                    && !util::date::check_if_deadline_has_passed(
-                        deadline_base,
-                        self.timestamp,
-                        PAYMENT_DEADLINE_SECONDS,
-                    )
-                {
-                    return Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment);
-                }
+                return Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment);
             }
         }
         Ok(())
@@ -581,17 +826,19 @@ mod tests {
         blockchain::bill::{
             BillBlock, BillBlockchain,
             block::{
-                BillAcceptBlockData, BillEndorseBlockData, BillIssueBlockData,
-                BillOfferToSellBlockData, BillRecourseBlockData, BillRejectBlockData,
-                BillRequestRecourseBlockData, BillRequestToAcceptBlockData,
-                BillRequestToPayBlockData, tests::valid_bill_issue_block_data,
+                BillAcceptBlockData, BillEndorseBlockData, BillExternalPaymentBlockData,
+                BillIssueBlockData, BillOfferToSellBlockData, BillRecourseBlockData,
+                BillRejectBlockData, BillRequestRecourseBlockData, BillRequestToAcceptBlockData,
+                BillRequestToPayBlockData, BillSellBlockData, tests::valid_bill_issue_block_data,
             },
         },
+        constants::PAYMENT_DEADLINE_SECONDS,
         contact::IdentityPublicData,
         tests::tests::{
             OTHER_TEST_PUB_KEY_SECP, OTHER_VALID_PAYMENT_ADDRESS_TESTNET, TEST_BILL_ID,
-            TEST_PRIVATE_KEY_SECP, TEST_PUB_KEY_SECP, VALID_PAYMENT_ADDRESS_TESTNET, valid_address,
-            valid_identity_public_data, valid_other_identity_public_data,
+            TEST_NODE_ID_SECP, TEST_PRIVATE_KEY_SECP, TEST_PUB_KEY_SECP,
+            VALID_PAYMENT_ADDRESS_TESTNET, valid_address, valid_identity_public_data,
+            valid_other_identity_public_data,
         },
         util::{BcrKeys, date::now},
     };
@@ -617,17 +864,47 @@ mod tests {
             drawer_public_data: valid_identity_public_data(),
             drawer_keys: BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
             timestamp: 1731593928,
+            payment_deadline_seconds: None,
         }
     }
 
     #[test]
     fn test_valid_bill_issue_data() {
         let result = validate_bill_issue(&valid_bill_issue_data());
-        assert_eq!(result, Ok((500, BillType::PromissoryNote)));
+        assert_eq!(result, Ok((500, "sat".into(), BillType::PromissoryNote)));
+    }
+
+    #[test]
+    fn test_valid_fiat_bill_issue_data() {
+        let result = validate_bill_issue(&BillIssueData {
+            sum: "100.50".into(),
+            currency: "EUR".into(),
+            ..valid_bill_issue_data()
+        });
+        assert_eq!(result, Ok((10050, "EUR".into(), BillType::PromissoryNote)));
+    }
+
+    #[test]
+    fn test_valid_btc_bill_issue_data() {
+        let result = validate_bill_issue(&BillIssueData {
+            sum: "0.00000001".into(),
+            currency: "btc".into(),
+            ..valid_bill_issue_data()
+        });
+        assert_eq!(result, Ok((1, "sat".into(), BillType::PromissoryNote)));
     }
 
     #[rstest]
     #[case::invalid_sum( BillIssueData { sum: "invalidsum".into(), ..valid_bill_issue_data() }, ValidationError::InvalidSum)]
+    #[case::zero_sum( BillIssueData { sum: "0".into(), ..valid_bill_issue_data() }, ValidationError::InvalidSum)]
+    #[case::decimal_sat_sum( BillIssueData { sum: "100.50".into(), ..valid_bill_issue_data() }, ValidationError::InvalidSum)]
+    #[case::sub_sat_btc_sum( BillIssueData { sum: "0.000000001".into(), currency: "btc".into(), ..valid_bill_issue_data() }, ValidationError::InvalidSum)]
+    #[case::invalid_fiat_sum( BillIssueData { sum: "100.505".into(), currency: "EUR".into(), ..valid_bill_issue_data() }, ValidationError::InvalidSum)]
+    #[case::invalid_currency( BillIssueData { currency: "invalidcurrency".into(), ..valid_bill_issue_data() }, ValidationError::InvalidCurrency)]
+    #[case::invalid_country_of_issuing( BillIssueData { country_of_issuing: "Austria".into(), ..valid_bill_issue_data() }, ValidationError::InvalidCountryCode("Austria".into()))]
+    #[case::invalid_country_of_payment( BillIssueData { country_of_payment: "fr".into(), ..valid_bill_issue_data() }, ValidationError::InvalidCountryCode("fr".into()))]
+    #[case::invalid_payment_deadline( BillIssueData { payment_deadline_seconds: Some(0), ..valid_bill_issue_data() }, ValidationError::InvalidPaymentDeadline)]
+    #[case::payment_deadline_too_long( BillIssueData { payment_deadline_seconds: Some(u64::MAX), ..valid_bill_issue_data() }, ValidationError::InvalidPaymentDeadline)]
     #[case::invalid_file_id( BillIssueData { file_upload_ids: vec!["".into()], ..valid_bill_issue_data() }, ValidationError::InvalidFileUploadId)]
     #[case::invalid_issue_date( BillIssueData { issue_date: "invaliddate".into(), ..valid_bill_issue_data() }, ValidationError::InvalidDate)]
     #[case::invalid_maturity_date( BillIssueData { maturity_date: "invaliddate".into(), ..valid_bill_issue_data() }, ValidationError::InvalidDate)]
@@ -645,6 +922,22 @@ mod tests {
         assert_eq!(validate_bill_issue(&input), Err(expected));
     }
 
+    #[rstest]
+    #[case::no_files(0, 0, Ok(()))]
+    #[case::at_limits(10, 1000, Ok(()))]
+    #[case::too_many_files(11, 1000, Err(ValidationError::TooManyFiles(10)))]
+    #[case::too_big(10, 1001, Err(ValidationError::AttachmentsTooBig(1000)))]
+    fn test_validate_bill_attachments(
+        #[case] file_count: usize,
+        #[case] total_bytes: usize,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(
+            validate_bill_attachments(file_count, total_bytes, 10, 1000),
+            expected
+        );
+    }
+
     fn valid_bill_blockchain_issue(issue_block_data: BillIssueBlockData) -> BillBlockchain {
         let chain = BillBlockchain::new(
             &issue_block_data,
@@ -765,6 +1058,7 @@ mod tests {
                 signatory: None,
                 signing_timestamp: chain.get_latest_block().timestamp + 1,
                 signing_address: valid_address(),
+                sell_deadline_seconds: None,
             },
             &keys(),
             None,
@@ -811,6 +1105,7 @@ mod tests {
                 signatory: None,
                 signing_timestamp: chain.get_latest_block().timestamp + 1,
                 signing_address: valid_address(),
+                reason: None,
             },
             &keys(),
             None,
@@ -832,6 +1127,7 @@ mod tests {
                 signatory: None,
                 signing_timestamp: chain.get_latest_block().timestamp + 1,
                 signing_address: valid_address(),
+                reason: None,
             },
             &keys(),
             None,
@@ -853,6 +1149,99 @@ mod tests {
                 signatory: None,
                 signing_timestamp: chain.get_latest_block().timestamp + 1,
                 signing_address: valid_address(),
+                reason: None,
+            },
+            &keys(),
+            None,
+            &keys(),
+            chain.get_latest_block().timestamp + 1,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(block));
+        assert!(chain.is_chain_valid());
+        chain
+    }
+
+    fn add_sell_block(mut chain: BillBlockchain) -> BillBlockchain {
+        let block = BillBlock::create_block_for_sell(
+            TEST_BILL_ID.into(),
+            chain.get_latest_block(),
+            &BillSellBlockData {
+                buyer: valid_identity_public_data().into(),
+                seller: valid_other_identity_public_data().into(),
+                sum: 500,
+                currency: "sat".into(),
+                payment_address: VALID_PAYMENT_ADDRESS_TESTNET.into(),
+                signatory: None,
+                signing_timestamp: chain.get_latest_block().timestamp + 1,
+                signing_address: valid_address(),
+            },
+            &keys(),
+            None,
+            &keys(),
+            chain.get_latest_block().timestamp + 1,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(block));
+        assert!(chain.is_chain_valid());
+        chain
+    }
+
+    fn add_external_payment_block(mut chain: BillBlockchain) -> BillBlockchain {
+        let block = BillBlock::create_block_for_external_payment(
+            TEST_BILL_ID.into(),
+            chain.get_latest_block(),
+            &BillExternalPaymentBlockData {
+                payer: valid_identity_public_data().into(),
+                holder: valid_other_identity_public_data().into(),
+                reference: "SEPA-2024-0001".into(),
+                signatory: None,
+                signing_timestamp: chain.get_latest_block().timestamp + 1,
+                signing_address: valid_address(),
+            },
+            &keys(),
+            None,
+            &keys(),
+            chain.get_latest_block().timestamp + 1,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(block));
+        assert!(chain.is_chain_valid());
+        chain
+    }
+
+    fn add_cancel_offer_to_sell_block(mut chain: BillBlockchain) -> BillBlockchain {
+        let block = BillBlock::create_block_for_cancel_offer_to_sell(
+            TEST_BILL_ID.into(),
+            chain.get_latest_block(),
+            &BillRejectBlockData {
+                rejecter: valid_other_identity_public_data().into(),
+                signatory: None,
+                signing_timestamp: chain.get_latest_block().timestamp + 1,
+                signing_address: valid_address(),
+                reason: None,
+            },
+            &keys(),
+            None,
+            &keys(),
+            chain.get_latest_block().timestamp + 1,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(block));
+        assert!(chain.is_chain_valid());
+        chain
+    }
+
+    fn add_cancel_req_to_accept_block(mut chain: BillBlockchain) -> BillBlockchain {
+        let block = BillBlock::create_block_for_cancel_request_to_accept(
+            TEST_BILL_ID.into(),
+            chain.get_latest_block(),
+            &BillRejectBlockData {
+                rejecter: valid_identity_public_data().into(),
+                signatory: None,
+                signing_timestamp: chain.get_latest_block().timestamp + 1,
+                signing_address: valid_address(),
+                reason: None,
             },
             &keys(),
             None,
@@ -874,6 +1263,7 @@ mod tests {
                 signatory: None,
                 signing_timestamp: chain.get_latest_block().timestamp + 1,
                 signing_address: valid_address(),
+                reason: None,
             },
             &keys(),
             None,
@@ -1028,6 +1418,21 @@ mod tests {
         assert_eq!(input.validate(), expected);
     }
 
+    #[rstest]
+    #[case::req_to_accept_and_pay(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: BillAction::RequestAcceptanceAndPayment("sat".into()), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    #[case::req_to_accept_and_pay_already_accepted(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: BillAction::RequestAcceptanceAndPayment("sat".into()), ..valid_bill_validate_action_data(add_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    #[case::req_to_accept_and_pay_already_req_to_accepted(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: BillAction::RequestAcceptanceAndPayment("sat".into()), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAlreadyRequestedToAccept))]
+    #[case::req_to_accept_and_pay_not_holder(BillValidateActionData { bill_action: BillAction::RequestAcceptanceAndPayment("sat".into()), signer_node_id: TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::CallerIsNotHolder))]
+    #[case::req_to_accept_and_pay_before_maturity_date(BillValidateActionData { maturity_date: "2099-01-01".into(), signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: BillAction::RequestAcceptanceAndPayment("sat".into()), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillRequestedToPayBeforeMaturityDate))]
+    #[case::accept_after_req_to_accept_and_pay(BillValidateActionData { ..valid_bill_validate_action_data(add_req_to_pay_block(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Ok(()))]
+    #[case::req_to_pay_after_req_to_accept_and_pay_blocked(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: BillAction::RequestToPay("sat".into()), ..valid_bill_validate_action_data(add_accept_block(add_req_to_pay_block(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
+    fn test_validate_bill_req_to_accept_and_pay(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
     #[rstest]
     #[case::req_to_recourse_not_rejected_but_expired(BillValidateActionData { timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), endorsee_node_id: Some(TEST_PUB_KEY_SECP.into()), bill_action: BillAction::RequestRecourse(valid_other_identity_public_data(), RecourseReason::Accept), ..valid_bill_validate_action_data(add_req_to_accept_block(add_endorse_block(add_endorse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),), valid_other_identity_public_data(), valid_identity_public_data()), valid_identity_public_data(), valid_other_identity_public_data()))) }, Ok(()))]
     #[case::req_to_recourse_not_expired_but_rejected(BillValidateActionData { endorsee_node_id: Some(TEST_PUB_KEY_SECP.into()), bill_action: BillAction::RequestRecourse(valid_other_identity_public_data(), RecourseReason::Accept), ..valid_bill_validate_action_data(add_reject_accept_block(add_req_to_accept_block(add_endorse_block(add_endorse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),), valid_other_identity_public_data(), valid_identity_public_data()), valid_identity_public_data(), valid_other_identity_public_data())))) }, Ok(()))]
@@ -1232,7 +1637,8 @@ mod tests {
     }
 
     #[rstest]
-    #[case::reject_to_accept(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    #[case::reject_to_accept(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    #[case::reject_to_accept_with_reason(BillValidateActionData { bill_action: BillAction::RejectAcceptance(Some("goods not received".into())), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
     fn test_validate_bill_reject_accept_valid(
         #[case] input: BillValidateActionData,
         #[case] expected: Result<(), ValidationError>,
@@ -1241,19 +1647,20 @@ mod tests {
     }
 
     #[rstest]
-    #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
-    #[case::last_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRecoursedToTheEnd))]
-    #[case::expired_req_to_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillRequestToRecourseExpired))]
-    #[case::active_req_to_pay_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
-    #[case::active_offer_to_sell_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
-    #[case::active_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsInRecourseAndWaitingForPayment))]
-    #[case::rejected_to_accept_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
-    #[case::rejected_to_pay_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
-    #[case::payment_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance, timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
-    #[case::acceptance_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance, timestamp: now().timestamp() as u64 + (ACCEPT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAcceptanceExpired))]
-    #[case::reject_to_accept_already_rejected(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
-    #[case::reject_to_accept_not_drawee(BillValidateActionData { bill_action: BillAction::RejectAcceptance, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::CallerIsNotDrawee))]
-    #[case::reject_to_accept_accepted(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAlreadyAccepted))]
+    #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
+    #[case::last_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRecoursedToTheEnd))]
+    #[case::expired_req_to_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillRequestToRecourseExpired))]
+    #[case::active_req_to_pay_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
+    #[case::active_offer_to_sell_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
+    #[case::active_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsInRecourseAndWaitingForPayment))]
+    #[case::rejected_to_accept_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
+    #[case::rejected_to_pay_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
+    #[case::payment_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
+    #[case::acceptance_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), timestamp: now().timestamp() as u64 + (ACCEPT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAcceptanceExpired))]
+    #[case::reject_to_accept_already_rejected(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
+    #[case::reject_to_accept_not_drawee(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::CallerIsNotDrawee))]
+    #[case::reject_to_accept_accepted(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAlreadyAccepted))]
+    #[case::reject_to_accept_reason_too_long(BillValidateActionData { bill_action: BillAction::RejectAcceptance(Some("a".repeat(MAX_REJECT_REASON_CHARACTERS + 1))), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::RejectReasonTooLong(MAX_REJECT_REASON_CHARACTERS)))]
     fn test_validate_bill_reject_accept_errors(
         #[case] input: BillValidateActionData,
         #[case] expected: Result<(), ValidationError>,
@@ -1262,7 +1669,8 @@ mod tests {
     }
 
     #[rstest]
-    #[case::reject_to_buy_not_buyer(BillValidateActionData { bill_action: BillAction::RejectBuying, signer_node_id: TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    #[case::reject_to_buy_not_buyer(BillValidateActionData { bill_action: BillAction::RejectBuying(None), signer_node_id: TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    #[case::reject_to_buy_with_reason(BillValidateActionData { bill_action: BillAction::RejectBuying(Some("price too high".into())), signer_node_id: TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
     fn test_validate_bill_reject_buying_valid(
         #[case] input: BillValidateActionData,
         #[case] expected: Result<(), ValidationError>,
@@ -1271,18 +1679,19 @@ mod tests {
     }
 
     #[rstest]
-    #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
-    #[case::last_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRecoursedToTheEnd))]
-    #[case::expired_req_to_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying, timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillRequestToRecourseExpired))]
-    #[case::active_req_to_pay_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
-    #[case::active_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsInRecourseAndWaitingForPayment))]
-    #[case::rejected_to_accept_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
-    #[case::rejected_to_pay_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
-    #[case::payment_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying, timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
-    #[case::acceptance_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying, timestamp: now().timestamp() as u64 + (ACCEPT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAcceptanceExpired))]
-    #[case::reject_to_buy_already_rejected(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_reject_buy_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::RequestAlreadyRejected))]
-    #[case::reject_to_buy_not_offered_to_sell(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillWasNotOfferedToSell))]
-    #[case::reject_to_buy_not_buyer(BillValidateActionData { bill_action: BillAction::RejectBuying, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::CallerIsNotBuyer))]
+    #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
+    #[case::last_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRecoursedToTheEnd))]
+    #[case::expired_req_to_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying(None), timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillRequestToRecourseExpired))]
+    #[case::active_req_to_pay_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
+    #[case::active_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsInRecourseAndWaitingForPayment))]
+    #[case::rejected_to_accept_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
+    #[case::rejected_to_pay_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
+    #[case::payment_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying(None), timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
+    #[case::acceptance_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectBuying(None), timestamp: now().timestamp() as u64 + (ACCEPT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAcceptanceExpired))]
+    #[case::reject_to_buy_already_rejected(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_reject_buy_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::RequestAlreadyRejected))]
+    #[case::reject_to_buy_not_offered_to_sell(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillWasNotOfferedToSell))]
+    #[case::reject_to_buy_not_buyer(BillValidateActionData { bill_action: BillAction::RejectBuying(None), signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::CallerIsNotBuyer))]
+    #[case::reject_to_buy_reason_too_long(BillValidateActionData { bill_action: BillAction::RejectBuying(Some("a".repeat(MAX_REJECT_REASON_CHARACTERS + 1))), signer_node_id: TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::RejectReasonTooLong(MAX_REJECT_REASON_CHARACTERS)))]
     fn test_validate_bill_reject_buying_errors(
         #[case] input: BillValidateActionData,
         #[case] expected: Result<(), ValidationError>,
@@ -1291,7 +1700,56 @@ mod tests {
     }
 
     #[rstest]
-    #[case::reject_to_pay_expired(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    #[case::cancel_offer_to_sell(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    fn test_validate_bill_cancel_offer_to_sell_valid(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
+    #[case::cancel_offer_to_sell_not_offered_to_sell(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillIsNotOfferToSellWaitingForPayment))]
+    #[case::cancel_offer_to_sell_after_sell(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_sell_block(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Err(ValidationError::BillIsNotOfferToSellWaitingForPayment))]
+    #[case::cancel_offer_to_sell_expired(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsNotOfferToSellWaitingForPayment))]
+    #[case::cancel_offer_to_sell_after_reject_to_buy(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_reject_buy_block(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Err(ValidationError::BillIsNotOfferToSellWaitingForPayment))]
+    #[case::cancel_offer_to_sell_already_cancelled(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_cancel_offer_to_sell_block(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Err(ValidationError::BillIsNotOfferToSellWaitingForPayment))]
+    #[case::cancel_offer_to_sell_not_seller(BillValidateActionData { bill_action: BillAction::CancelOfferToSell, signer_node_id: TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::CallerIsNotSeller))]
+    fn test_validate_bill_cancel_offer_to_sell_errors(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::cancel_req_to_accept(BillValidateActionData { bill_action: BillAction::CancelRequestToAccept, ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    #[case::req_to_accept_after_cancel(BillValidateActionData { bill_action: BillAction::RequestAcceptance, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_cancel_req_to_accept_block(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Ok(()))]
+    fn test_validate_bill_cancel_req_to_accept_valid(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::cancel_req_to_accept_not_requested(BillValidateActionData { bill_action: BillAction::CancelRequestToAccept, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillWasNotRequestedToAccept))]
+    #[case::cancel_req_to_accept_already_cancelled(BillValidateActionData { bill_action: BillAction::CancelRequestToAccept, ..valid_bill_validate_action_data(add_cancel_req_to_accept_block(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Err(ValidationError::BillWasNotRequestedToAccept))]
+    #[case::cancel_req_to_accept_accepted(BillValidateActionData { bill_action: BillAction::CancelRequestToAccept, ..valid_bill_validate_action_data(add_accept_block(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Err(ValidationError::BillAlreadyAccepted))]
+    #[case::cancel_req_to_accept_rejected(BillValidateActionData { bill_action: BillAction::CancelRequestToAccept, ..valid_bill_validate_action_data(add_reject_accept_block(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)))) }, Err(ValidationError::BillWasRejectedToAccept))]
+    #[case::cancel_req_to_accept_expired(BillValidateActionData { bill_action: BillAction::CancelRequestToAccept, timestamp: now().timestamp() as u64 + (ACCEPT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAcceptanceExpired))]
+    #[case::cancel_req_to_accept_not_requester(BillValidateActionData { bill_action: BillAction::CancelRequestToAccept, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::CallerIsNotRequester))]
+    fn test_validate_bill_cancel_req_to_accept_errors(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::reject_to_pay_expired(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    #[case::reject_to_pay_with_reason(BillValidateActionData { bill_action: BillAction::RejectPayment(Some("amount disputed".into())), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
     fn test_validate_bill_reject_payment_valid(
         #[case] input: BillValidateActionData,
         #[case] expected: Result<(), ValidationError>,
@@ -1300,20 +1758,21 @@ mod tests {
     }
 
     #[rstest]
-    #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
-    #[case::last_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRecoursedToTheEnd))]
-    #[case::expired_req_to_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment, timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillRequestToRecourseExpired))]
-    #[case::active_offer_to_sell_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
-    #[case::active_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsInRecourseAndWaitingForPayment))]
-    #[case::rejected_to_accept_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
-    #[case::rejected_to_pay_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
-    #[case::payment_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment, timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
-    #[case::acceptance_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment, timestamp: now().timestamp() as u64 + (ACCEPT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAcceptanceExpired))]
-    #[case::reject_to_pay_already_rejected(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
-    #[case::reject_to_pay_not_drawee(BillValidateActionData { bill_action: BillAction::RejectPayment, signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::CallerIsNotDrawee))]
-    #[case::reject_to_pay_paid(BillValidateActionData { is_paid: true, bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillAlreadyPaid))]
-    #[case::reject_to_pay_not_req_to_pay(BillValidateActionData { bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillWasNotRequestedToPay))]
-    #[case::reject_to_pay_expired(BillValidateActionData { timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), bill_action: BillAction::RejectPayment, ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
+    #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
+    #[case::last_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRecoursedToTheEnd))]
+    #[case::expired_req_to_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment(None), timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillRequestToRecourseExpired))]
+    #[case::active_offer_to_sell_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
+    #[case::active_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsInRecourseAndWaitingForPayment))]
+    #[case::rejected_to_accept_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToAccept))]
+    #[case::rejected_to_pay_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
+    #[case::payment_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment(None), timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
+    #[case::acceptance_expired_only_recourse(BillValidateActionData { bill_action: BillAction::RejectPayment(None), timestamp: now().timestamp() as u64 + (ACCEPT_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAcceptanceExpired))]
+    #[case::reject_to_pay_already_rejected(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_reject_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToPay))]
+    #[case::reject_to_pay_not_drawee(BillValidateActionData { bill_action: BillAction::RejectPayment(None), signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::CallerIsNotDrawee))]
+    #[case::reject_to_pay_reason_too_long(BillValidateActionData { bill_action: BillAction::RejectPayment(Some("a".repeat(MAX_REJECT_REASON_CHARACTERS + 1))), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::RejectReasonTooLong(MAX_REJECT_REASON_CHARACTERS)))]
+    #[case::reject_to_pay_paid(BillValidateActionData { is_paid: true, bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillAlreadyPaid))]
+    #[case::reject_to_pay_not_req_to_pay(BillValidateActionData { bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillWasNotRequestedToPay))]
+    #[case::reject_to_pay_expired(BillValidateActionData { timestamp: now().timestamp() as u64 + (PAYMENT_DEADLINE_SECONDS * 2), bill_action: BillAction::RejectPayment(None), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillPaymentExpired))]
     fn test_validate_bill_reject_payment_errors(
         #[case] input: BillValidateActionData,
         #[case] expected: Result<(), ValidationError>,
@@ -1334,8 +1793,8 @@ mod tests {
     #[case::rejected_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPaymentForRecourse, ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
     #[case::last_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPaymentForRecourse, ..valid_bill_validate_action_data(add_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRecoursedToTheEnd))]
     #[case::expired_req_to_recourse_blocked(BillValidateActionData { bill_action: BillAction::RejectPaymentForRecourse, timestamp: now().timestamp() as u64 + (RECOURSE_DEADLINE_SECONDS * 2), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillRequestToRecourseExpired))]
-    #[case::active_offer_to_sell_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance, ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
-    #[case::active_req_to_pay_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying, ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
+    #[case::active_offer_to_sell_blocked(BillValidateActionData { bill_action: BillAction::RejectAcceptance(None), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
+    #[case::active_req_to_pay_blocked(BillValidateActionData { bill_action: BillAction::RejectBuying(None), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
     #[case::reject_to_recourse_already_rejected(BillValidateActionData { bill_action: BillAction::RejectPaymentForRecourse, ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
     #[case::reject_to_recourse_not_req_to_recourse(BillValidateActionData { bill_action: BillAction::RejectPaymentForRecourse, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillWasNotRequestedToRecourse))]
     #[case::reject_to_recourse_not_recoursee(BillValidateActionData { bill_action: BillAction::RejectPaymentForRecourse, signer_node_id: TEST_PUB_KEY_SECP.into(), ..valid_bill_validate_action_data(add_req_to_recourse_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::CallerIsNotRecoursee))]
@@ -1346,4 +1805,104 @@ mod tests {
     ) {
         assert_eq!(input.validate(), expected);
     }
+
+    fn attach_file_action() -> BillAction {
+        BillAction::AttachFile(vec![crate::File {
+            name: "delivery_note.pdf".into(),
+            hash: "HgTkJ2iK5KC4yCPMa7GWAoxW6yxBaH8kVAsRBYg6oVc".into(),
+        }])
+    }
+
+    #[rstest]
+    #[case::attach_file(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: attach_file_action(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    #[case::attach_file_after_reject_to_accept(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: attach_file_action(), ..valid_bill_validate_action_data(add_reject_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    fn test_validate_bill_attach_file_valid(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::rejected_recourse_blocked(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: attach_file_action(), ..valid_bill_validate_action_data(add_reject_recourse_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillWasRejectedToRecourse))]
+    #[case::active_req_to_pay_blocked(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: attach_file_action(), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsRequestedToPayAndWaitingForPayment))]
+    #[case::active_offer_to_sell_blocked(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: attach_file_action(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
+    #[case::attach_file_not_holder(BillValidateActionData { signer_node_id: TEST_PUB_KEY_SECP.into(), bill_action: attach_file_action(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::CallerIsNotHolder))]
+    fn test_validate_bill_attach_file_errors(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    fn mark_paid_externally_action() -> BillAction {
+        BillAction::MarkPaidExternally("SEPA-2024-0001".into())
+    }
+
+    #[rstest]
+    #[case::mark_paid_externally(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: mark_paid_externally_action(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    #[case::mark_paid_externally_after_req_to_pay(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: mark_paid_externally_action(), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Ok(()))]
+    fn test_validate_bill_mark_paid_externally_valid(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::paid_on_chain(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: mark_paid_externally_action(), is_paid: true, ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAlreadyPaid))]
+    #[case::already_paid_externally(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: mark_paid_externally_action(), ..valid_bill_validate_action_data(add_external_payment_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAlreadyPaid))]
+    #[case::other_action_after_paid_externally(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: attach_file_action(), ..valid_bill_validate_action_data(add_external_payment_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAlreadyPaid))]
+    #[case::active_offer_to_sell_blocked(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: mark_paid_externally_action(), ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
+    #[case::empty_reference(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: BillAction::MarkPaidExternally(" ".into()), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::FieldEmpty(Field::ExternalPaymentReference)))]
+    #[case::not_holder(BillValidateActionData { signer_node_id: TEST_PUB_KEY_SECP.into(), bill_action: mark_paid_externally_action(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::CallerIsNotHolder))]
+    fn test_validate_bill_mark_paid_externally_errors(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::holder(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: BillAction::Acknowledge, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    #[case::drawee(BillValidateActionData { signer_node_id: TEST_PUB_KEY_SECP.into(), bill_action: BillAction::Acknowledge, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    #[case::drawee_with_active_offer_to_sell_blocked(BillValidateActionData { signer_node_id: TEST_PUB_KEY_SECP.into(), bill_action: BillAction::Acknowledge, ..valid_bill_validate_action_data(add_offer_to_sell_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillIsOfferedToSellAndWaitingForPayment))]
+    #[case::not_a_participant(BillValidateActionData { signer_node_id: TEST_NODE_ID_SECP.into(), bill_action: BillAction::Acknowledge, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::CallerIsNotHolderOrDrawee))]
+    #[case::paid(BillValidateActionData { signer_node_id: TEST_PUB_KEY_SECP.into(), bill_action: BillAction::Acknowledge, is_paid: true, ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::BillAlreadyPaid))]
+    fn test_validate_bill_acknowledge(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    fn correct_drawee_action() -> BillAction {
+        BillAction::CorrectDrawee(IdentityPublicData {
+            node_id: TEST_NODE_ID_SECP.into(),
+            ..valid_identity_public_data()
+        })
+    }
+
+    #[rstest]
+    #[case::correct_drawee(BillValidateActionData { bill_action: correct_drawee_action(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Ok(()))]
+    fn test_validate_bill_correct_drawee_valid(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
+
+    #[rstest]
+    #[case::after_req_to_accept(BillValidateActionData { bill_action: correct_drawee_action(), ..valid_bill_validate_action_data(add_req_to_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::DraweeCorrectionNotAllowed))]
+    #[case::after_accept(BillValidateActionData { bill_action: correct_drawee_action(), ..valid_bill_validate_action_data(add_accept_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::DraweeCorrectionNotAllowed))]
+    #[case::after_req_to_pay(BillValidateActionData { bill_action: correct_drawee_action(), ..valid_bill_validate_action_data(add_req_to_pay_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::DraweeCorrectionNotAllowed))]
+    #[case::after_paid_externally(BillValidateActionData { bill_action: correct_drawee_action(), ..valid_bill_validate_action_data(add_external_payment_block(valid_bill_blockchain_issue( valid_bill_issue_block_data(),))) }, Err(ValidationError::BillAlreadyPaid))]
+    #[case::new_drawee_is_payee(BillValidateActionData { bill_action: BillAction::CorrectDrawee(valid_other_identity_public_data()), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::DraweeCantBePayee))]
+    #[case::not_drawer(BillValidateActionData { signer_node_id: OTHER_TEST_PUB_KEY_SECP.into(), bill_action: correct_drawee_action(), ..valid_bill_validate_action_data(valid_bill_blockchain_issue( valid_bill_issue_block_data(),)) }, Err(ValidationError::CallerIsNotDrawer))]
+    fn test_validate_bill_correct_drawee_errors(
+        #[case] input: BillValidateActionData,
+        #[case] expected: Result<(), ValidationError>,
+    ) {
+        assert_eq!(input.validate(), expected);
+    }
 }
@@ -1,16 +1,19 @@
 use super::super::{Error, Result};
 use super::BillOpCode;
 use super::BillOpCode::{
-    Accept, Endorse, Issue, Mint, OfferToSell, Recourse, RejectToAccept, RejectToBuy, RejectToPay,
-    RejectToPayRecourse, RequestRecourse, RequestToAccept, RequestToPay, Sell,
+    Accept, Acknowledge, ApproveMintQuote, AttachFile, CancelOfferToSell, CancelRequestToAccept,
+    CorrectDrawee, Endorse, ExternalPayment, Issue, Mint, MintQuote, OfferToSell, Recourse,
+    RejectMintQuote, RejectToAccept, RejectToBuy, RejectToPay, RejectToPayRecourse,
+    RequestRecourse, RequestToAccept, RequestToPay, Sell,
 };
 
-use crate::bill::{BillAction, RecourseReason};
+use crate::bill::validation::{validate_payment_deadline_seconds, validate_reject_reason};
+use crate::bill::{BillAction, BillHistoryEntry, RecourseReason};
 use crate::blockchain::{Block, FIRST_BLOCK_ID};
 use crate::util::BcrKeys;
 use crate::util::{self, crypto};
 use crate::{
-    bill::{BillKeys, BitcreditBill},
+    bill::{BillFiatAmount, BillKeys, BitcreditBill},
     contact::{ContactType, IdentityPublicData},
 };
 
@@ -48,12 +51,34 @@ pub struct BillBlockDataToHash {
     op_code: BillOpCode,
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+#[derive(BorshSerialize, Debug, Clone, PartialEq)]
 pub struct BillRejectBlockData {
     pub rejecter: BillIdentityBlockData,
     pub signatory: Option<BillSignatoryBlockData>,
     pub signing_timestamp: u64,
     pub signing_address: PostalAddress,
+    /// Why the request was rejected - only set for rejections of acceptance, payment, or buying
+    pub reason: Option<String>,
+}
+
+/// Blocks recorded before reject reasons were supported don't have the trailing `reason` field,
+/// so it's only read if there are bytes left
+impl borsh::BorshDeserialize for BillRejectBlockData {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let rejecter = BillIdentityBlockData::deserialize_reader(reader)?;
+        let signatory = Option::<BillSignatoryBlockData>::deserialize_reader(reader)?;
+        let signing_timestamp = u64::deserialize_reader(reader)?;
+        let signing_address = PostalAddress::deserialize_reader(reader)?;
+        let reason = deserialize_trailing_option::<String, R>(reader, "reason")?;
+
+        Ok(Self {
+            rejecter,
+            signatory,
+            signing_timestamp,
+            signing_address,
+            reason,
+        })
+    }
 }
 
 impl Validate for BillRejectBlockData {
@@ -66,11 +91,13 @@ impl Validate for BillRejectBlockData {
 
         self.signing_address.validate()?;
 
+        validate_reject_reason(self.reason.as_deref())?;
+
         Ok(())
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+#[derive(BorshSerialize, Debug, Clone, PartialEq)]
 pub struct BillIssueBlockData {
     pub id: String,
     pub country_of_issuing: String,
@@ -89,6 +116,80 @@ pub struct BillIssueBlockData {
     pub signatory: Option<BillSignatoryBlockData>,
     pub signing_timestamp: u64,
     pub signing_address: PostalAddress,
+    /// Not set for bills issued before the payment deadline was configurable
+    pub payment_deadline_seconds: Option<u64>,
+    /// Only set for bills issued in a fiat currency - `sum` is the sat equivalent
+    pub fiat_amount: Option<BillFiatAmount>,
+}
+
+/// Reads an optional trailing field, which is `None` if there are no bytes left
+fn deserialize_trailing_option<T: borsh::BorshDeserialize, R: borsh::io::Read>(
+    reader: &mut R,
+    field: &str,
+) -> borsh::io::Result<Option<T>> {
+    let mut option_tag = [0u8; 1];
+    if reader.read(&mut option_tag)? == 0 {
+        return Ok(None);
+    }
+    match option_tag[0] {
+        0 => Ok(None),
+        1 => Ok(Some(T::deserialize_reader(reader)?)),
+        _ => Err(borsh::io::Error::new(
+            borsh::io::ErrorKind::InvalidData,
+            format!("invalid option tag for {field}"),
+        )),
+    }
+}
+
+/// Bills issued before the payment deadline was configurable, or before fiat bills were
+/// supported, don't have the trailing `payment_deadline_seconds` and `fiat_amount` fields, so
+/// they're only read if there are bytes left
+impl borsh::BorshDeserialize for BillIssueBlockData {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let id = String::deserialize_reader(reader)?;
+        let country_of_issuing = String::deserialize_reader(reader)?;
+        let city_of_issuing = String::deserialize_reader(reader)?;
+        let drawee = BillIdentityBlockData::deserialize_reader(reader)?;
+        let drawer = BillIdentityBlockData::deserialize_reader(reader)?;
+        let payee = BillIdentityBlockData::deserialize_reader(reader)?;
+        let currency = String::deserialize_reader(reader)?;
+        let sum = u64::deserialize_reader(reader)?;
+        let maturity_date = String::deserialize_reader(reader)?;
+        let issue_date = String::deserialize_reader(reader)?;
+        let country_of_payment = String::deserialize_reader(reader)?;
+        let city_of_payment = String::deserialize_reader(reader)?;
+        let language = String::deserialize_reader(reader)?;
+        let files = Vec::<File>::deserialize_reader(reader)?;
+        let signatory = Option::<BillSignatoryBlockData>::deserialize_reader(reader)?;
+        let signing_timestamp = u64::deserialize_reader(reader)?;
+        let signing_address = PostalAddress::deserialize_reader(reader)?;
+
+        let payment_deadline_seconds =
+            deserialize_trailing_option::<u64, R>(reader, "payment_deadline_seconds")?;
+        let fiat_amount = deserialize_trailing_option::<BillFiatAmount, R>(reader, "fiat_amount")?;
+
+        Ok(Self {
+            id,
+            country_of_issuing,
+            city_of_issuing,
+            drawee,
+            drawer,
+            payee,
+            currency,
+            sum,
+            maturity_date,
+            issue_date,
+            country_of_payment,
+            city_of_payment,
+            language,
+            files,
+            signatory,
+            signing_timestamp,
+            signing_address,
+            payment_deadline_seconds,
+            fiat_amount,
+        })
+    }
 }
 
 impl Validate for BillIssueBlockData {
@@ -137,6 +238,18 @@ impl Validate for BillIssueBlockData {
 
         self.signing_address.validate()?;
 
+        if let Some(payment_deadline_seconds) = self.payment_deadline_seconds {
+            validate_payment_deadline_seconds(payment_deadline_seconds)?;
+        }
+
+        if let Some(ref fiat_amount) = self.fiat_amount {
+            util::currency::validate_fiat_currency(&fiat_amount.currency)?;
+            util::currency::validate_sum(util::currency::parse_fiat_sum(&fiat_amount.sum)?)?;
+            if !fiat_amount.btc_rate.is_finite() || fiat_amount.btc_rate <= 0.0 {
+                return Err(ValidationError::FiatRateUnavailable);
+            }
+        }
+
         Ok(())
     }
 }
@@ -166,6 +279,8 @@ impl BillIssueBlockData {
             signatory,
             signing_timestamp: timestamp,
             signing_address, // address of the issuer
+            payment_deadline_seconds: Some(value.payment_deadline_seconds),
+            fiat_amount: value.fiat_amount,
         }
     }
 }
@@ -272,7 +387,7 @@ impl Validate for BillMintBlockData {
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+#[derive(BorshSerialize, Debug, Clone, PartialEq)]
 pub struct BillOfferToSellBlockData {
     pub seller: BillIdentityBlockData,
     pub buyer: BillIdentityBlockData,
@@ -282,6 +397,47 @@ pub struct BillOfferToSellBlockData {
     pub signatory: Option<BillSignatoryBlockData>,
     pub signing_timestamp: u64,
     pub signing_address: PostalAddress, // address of the seller
+    /// Not set for offers to sell created before the sell deadline was configurable, or without
+    /// a configured sell deadline - they expire with the payment deadline of the bill
+    pub sell_deadline_seconds: Option<u64>,
+}
+
+/// Offers to sell created before the sell deadline was configurable don't have the trailing
+/// `sell_deadline_seconds` field, so it's only read if there are bytes left
+impl borsh::BorshDeserialize for BillOfferToSellBlockData {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let seller = BillIdentityBlockData::deserialize_reader(reader)?;
+        let buyer = BillIdentityBlockData::deserialize_reader(reader)?;
+        let currency = String::deserialize_reader(reader)?;
+        let sum = u64::deserialize_reader(reader)?;
+        let payment_address = String::deserialize_reader(reader)?;
+        let signatory = Option::<BillSignatoryBlockData>::deserialize_reader(reader)?;
+        let signing_timestamp = u64::deserialize_reader(reader)?;
+        let signing_address = PostalAddress::deserialize_reader(reader)?;
+        let sell_deadline_seconds =
+            deserialize_trailing_option::<u64, R>(reader, "sell_deadline_seconds")?;
+
+        Ok(Self {
+            seller,
+            buyer,
+            currency,
+            sum,
+            payment_address,
+            signatory,
+            signing_timestamp,
+            signing_address,
+            sell_deadline_seconds,
+        })
+    }
+}
+
+impl BillOfferToSellBlockData {
+    /// Returns the deadline for paying the offer - the payment deadline of the bill, if no sell
+    /// deadline was recorded with the offer
+    pub fn get_sell_deadline_seconds(&self, payment_deadline_seconds: u64) -> u64 {
+        self.sell_deadline_seconds
+            .unwrap_or(payment_deadline_seconds)
+    }
 }
 
 impl Validate for BillOfferToSellBlockData {
@@ -306,6 +462,10 @@ impl Validate for BillOfferToSellBlockData {
 
         self.signing_address.validate()?;
 
+        if let Some(sell_deadline_seconds) = self.sell_deadline_seconds {
+            validate_payment_deadline_seconds(sell_deadline_seconds)?;
+        }
+
         Ok(())
     }
 }
@@ -451,6 +611,181 @@ impl Validate for BillRecourseBlockData {
 }
 
 /// Legal data for parties within a bill transaction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BillAttachFileBlockData {
+    pub attacher: BillIdentityBlockData,
+    pub files: Vec<File>,
+    pub signatory: Option<BillSignatoryBlockData>,
+    pub signing_timestamp: u64,
+    pub signing_address: PostalAddress, // address of the attacher
+}
+
+impl Validate for BillAttachFileBlockData {
+    fn validate(&self) -> std::result::Result<(), ValidationError> {
+        self.attacher.validate()?;
+
+        if self.files.is_empty() {
+            return Err(ValidationError::FieldEmpty(Field::Files));
+        }
+
+        for file in self.files.iter() {
+            if file.name.trim().is_empty() || file.hash.trim().is_empty() {
+                return Err(ValidationError::FieldEmpty(Field::Files));
+            }
+        }
+
+        if let Some(ref signatory) = self.signatory {
+            signatory.validate()?;
+        }
+
+        self.signing_address.validate()?;
+
+        Ok(())
+    }
+}
+
+/// The holder attests, that the bill was paid outside of Bitcoin, e.g. via bank transfer. This is
+/// not a cryptographic proof of the payment - only the signature of the holder over the reference
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BillExternalPaymentBlockData {
+    pub payer: BillIdentityBlockData,
+    pub holder: BillIdentityBlockData,
+    pub reference: String,
+    pub signatory: Option<BillSignatoryBlockData>,
+    pub signing_timestamp: u64,
+    pub signing_address: PostalAddress, // address of the holder
+}
+
+impl Validate for BillExternalPaymentBlockData {
+    fn validate(&self) -> std::result::Result<(), ValidationError> {
+        self.payer.validate()?;
+        self.holder.validate()?;
+
+        if self.reference.trim().is_empty() {
+            return Err(ValidationError::FieldEmpty(Field::ExternalPaymentReference));
+        }
+
+        if let Some(ref signatory) = self.signatory {
+            signatory.validate()?;
+        }
+
+        self.signing_address.validate()?;
+
+        Ok(())
+    }
+}
+
+/// The holder, or the drawee attests, that they have seen the bill at the signing timestamp
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BillAcknowledgeBlockData {
+    pub acknowledger: BillIdentityBlockData,
+    pub signatory: Option<BillSignatoryBlockData>,
+    pub signing_timestamp: u64,
+    pub signing_address: PostalAddress, // address of the acknowledger
+}
+
+impl Validate for BillAcknowledgeBlockData {
+    fn validate(&self) -> std::result::Result<(), ValidationError> {
+        self.acknowledger.validate()?;
+
+        if let Some(ref signatory) = self.signatory {
+            signatory.validate()?;
+        }
+
+        self.signing_address.validate()?;
+
+        Ok(())
+    }
+}
+
+/// The drawer replaces the drawee the bill was issued with, before the bill progressed
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BillCorrectDraweeBlockData {
+    pub drawer: BillIdentityBlockData,
+    pub drawee: BillIdentityBlockData, // the new drawee
+    pub signatory: Option<BillSignatoryBlockData>,
+    pub signing_timestamp: u64,
+    pub signing_address: PostalAddress, // address of the drawer
+}
+
+impl Validate for BillCorrectDraweeBlockData {
+    fn validate(&self) -> std::result::Result<(), ValidationError> {
+        self.drawer.validate()?;
+        self.drawee.validate()?;
+
+        if let Some(ref signatory) = self.signatory {
+            signatory.validate()?;
+        }
+
+        self.signing_address.validate()?;
+
+        Ok(())
+    }
+}
+
+/// The mint answers a request to mint with a quote, which the requester can approve, or reject
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BillMintQuoteBlockData {
+    pub mint: BillIdentityBlockData,
+    pub requester: BillIdentityBlockData,
+    pub mint_request_block_id: u64, // the mint block of the quoted request to mint
+    pub currency: String,
+    pub sum: u64,
+    pub expiry_timestamp: u64,
+    pub signatory: Option<BillSignatoryBlockData>,
+    pub signing_timestamp: u64,
+    pub signing_address: PostalAddress, // address of the mint
+}
+
+impl Validate for BillMintQuoteBlockData {
+    fn validate(&self) -> std::result::Result<(), ValidationError> {
+        self.mint.validate()?;
+        self.requester.validate()?;
+
+        util::currency::validate_currency(&self.currency)?;
+        util::currency::validate_sum(self.sum)?;
+
+        if let Some(ref signatory) = self.signatory {
+            signatory.validate()?;
+        }
+
+        self.signing_address.validate()?;
+
+        Ok(())
+    }
+}
+
+/// The requester of a request to mint approves the quote of the mint
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct BillApproveMintQuoteBlockData {
+    pub approver: BillIdentityBlockData,
+    pub mint: BillIdentityBlockData,
+    pub quote_block_id: u64, // the approved quote block
+    pub currency: String,
+    pub sum: u64,
+    pub signatory: Option<BillSignatoryBlockData>,
+    pub signing_timestamp: u64,
+    pub signing_address: PostalAddress, // address of the approver
+}
+
+impl Validate for BillApproveMintQuoteBlockData {
+    fn validate(&self) -> std::result::Result<(), ValidationError> {
+        self.approver.validate()?;
+        self.mint.validate()?;
+
+        util::currency::validate_currency(&self.currency)?;
+        util::currency::validate_sum(self.sum)?;
+
+        if let Some(ref signatory) = self.signatory {
+            signatory.validate()?;
+        }
+
+        self.signing_address.validate()?;
+
+        Ok(())
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
 pub struct BillIdentityBlockData {
     pub t: ContactType,
@@ -783,6 +1118,56 @@ impl BillBlock {
         Ok(block)
     }
 
+    /// The seller withdraws their offer to sell - the block data is the same as for rejections,
+    /// with the seller as the rejecter
+    pub fn create_block_for_cancel_offer_to_sell(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillRejectBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::CancelOfferToSell,
+        )?;
+        Ok(block)
+    }
+
+    /// The requester withdraws their request to accept - the block data is the same as for
+    /// rejections, with the requester as the rejecter
+    pub fn create_block_for_cancel_request_to_accept(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillRejectBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::CancelRequestToAccept,
+        )?;
+        Ok(block)
+    }
+
     pub fn create_block_for_reject_to_pay_recourse(
         bill_id: String,
         previous_block: &Self,
@@ -1013,6 +1398,167 @@ impl BillBlock {
         Ok(block)
     }
 
+    pub fn create_block_for_attach_file(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillAttachFileBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::AttachFile,
+        )?;
+        Ok(block)
+    }
+
+    pub fn create_block_for_external_payment(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillExternalPaymentBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::ExternalPayment,
+        )?;
+        Ok(block)
+    }
+
+    pub fn create_block_for_acknowledge(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillAcknowledgeBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::Acknowledge,
+        )?;
+        Ok(block)
+    }
+
+    pub fn create_block_for_correct_drawee(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillCorrectDraweeBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::CorrectDrawee,
+        )?;
+        Ok(block)
+    }
+
+    pub fn create_block_for_mint_quote(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillMintQuoteBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::MintQuote,
+        )?;
+        Ok(block)
+    }
+
+    pub fn create_block_for_approve_mint_quote(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillApproveMintQuoteBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::ApproveMintQuote,
+        )?;
+        Ok(block)
+    }
+
+    pub fn create_block_for_reject_mint_quote(
+        bill_id: String,
+        previous_block: &Self,
+        data: &BillRejectBlockData,
+        identity_keys: &BcrKeys,
+        company_keys: Option<&BcrKeys>,
+        bill_keys: &BcrKeys,
+        timestamp: u64,
+    ) -> Result<Self> {
+        let block = Self::encrypt_data_create_block_and_validate(
+            bill_id,
+            previous_block,
+            data,
+            identity_keys,
+            company_keys,
+            bill_keys,
+            None,
+            timestamp,
+            BillOpCode::RejectMintQuote,
+        )?;
+        Ok(block)
+    }
+
     fn encrypt_data_create_block_and_validate<T: borsh::BorshSerialize>(
         bill_id: String,
         previous_block: &Self,
@@ -1034,18 +1580,17 @@ impl BillBlock {
         let mut key = None;
 
         // in case there are keys to encrypt, encrypt them using the receiver's identity pub key
-        if op_code == BillOpCode::Endorse
+        if (op_code == BillOpCode::Endorse
             || op_code == BillOpCode::Sell
-            || op_code == BillOpCode::Mint
+            || op_code == BillOpCode::Mint)
+            && let Some(new_holder_public_key) = public_key_for_keys
         {
-            if let Some(new_holder_public_key) = public_key_for_keys {
-                let key_bytes = to_vec(&bill_keys.get_private_key_string())?;
-                let encrypted_key = util::base58_encode(&util::crypto::encrypt_ecies(
-                    &key_bytes,
-                    new_holder_public_key,
-                )?);
-                key = Some(encrypted_key);
-            }
+            let key_bytes = to_vec(&bill_keys.get_private_key_string())?;
+            let encrypted_key = util::base58_encode(&util::crypto::encrypt_ecies(
+                &key_bytes,
+                new_holder_public_key,
+            )?);
+            key = Some(encrypted_key);
         }
 
         let data = BillBlockData {
@@ -1072,6 +1617,24 @@ impl BillBlock {
         Ok(new_block)
     }
 
+    /// Returns the data of the block encrypted to the given viewing public key, instead of the
+    /// bill key - the encrypted bill key is left out, so the result only allows reading the block
+    pub fn get_view_data(&self, bill_keys: &BillKeys, view_public_key: &str) -> Result<String> {
+        let bytes = util::base58_decode(&self.data)?;
+        let block_data: BillBlockData = from_slice(&bytes)?;
+        let decoded_data_bytes = util::base58_decode(&block_data.data)?;
+        let decrypted_bytes =
+            util::crypto::decrypt_ecies(&decoded_data_bytes, &bill_keys.private_key)?;
+        let view_block_data = BillBlockData {
+            data: util::base58_encode(&util::crypto::encrypt_ecies(
+                &decrypted_bytes,
+                view_public_key,
+            )?),
+            key: None,
+        };
+        Ok(util::base58_encode(&to_vec(&view_block_data)?))
+    }
+
     /// Decrypts the block data using the bill's private key, returning the raw bytes
     pub fn get_decrypted_block_bytes<T: borsh::BorshDeserialize>(
         &self,
@@ -1096,72 +1659,126 @@ impl BillBlock {
     /// only if they are non-empty.
     ///
     pub fn get_nodes_from_block(&self, bill_keys: &BillKeys) -> Result<Vec<String>> {
-        let mut nodes = HashSet::new();
+        let nodes: HashSet<String> = self
+            .get_participants_from_block(bill_keys)?
+            .into_iter()
+            .map(|participant| participant.node_id)
+            .collect();
+        Ok(nodes.into_iter().collect())
+    }
+
+    /// Extracts the identity data of the participants involved in a block operation, as it was
+    /// embedded in the block - a participant can be contained more than once
+    pub fn get_participants_from_block(
+        &self,
+        bill_keys: &BillKeys,
+    ) -> Result<Vec<BillIdentityBlockData>> {
+        let mut participants = vec![];
         match self.op_code {
             Issue => {
                 let bill: BillIssueBlockData = self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(bill.drawer.node_id);
-                nodes.insert(bill.payee.node_id);
-                nodes.insert(bill.drawee.node_id);
+                participants.push(bill.drawer);
+                participants.push(bill.payee);
+                participants.push(bill.drawee);
             }
             Endorse => {
                 let block_data_decrypted: BillEndorseBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.endorsee.node_id);
-                nodes.insert(block_data_decrypted.endorser.node_id);
+                participants.push(block_data_decrypted.endorsee);
+                participants.push(block_data_decrypted.endorser);
             }
             Mint => {
                 let block_data_decrypted: BillMintBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.endorsee.node_id);
-                nodes.insert(block_data_decrypted.endorser.node_id);
+                participants.push(block_data_decrypted.endorsee);
+                participants.push(block_data_decrypted.endorser);
             }
             RequestToAccept => {
                 let block_data_decrypted: BillRequestToAcceptBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.requester.node_id);
+                participants.push(block_data_decrypted.requester);
             }
             Accept => {
                 let block_data_decrypted: BillAcceptBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.accepter.node_id);
+                participants.push(block_data_decrypted.accepter);
             }
             RequestToPay => {
                 let block_data_decrypted: BillRequestToPayBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.requester.node_id);
+                participants.push(block_data_decrypted.requester);
             }
             OfferToSell => {
                 let block_data_decrypted: BillOfferToSellBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.buyer.node_id);
-                nodes.insert(block_data_decrypted.seller.node_id);
+                participants.push(block_data_decrypted.buyer);
+                participants.push(block_data_decrypted.seller);
             }
             Sell => {
                 let block_data_decrypted: BillSellBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.buyer.node_id);
-                nodes.insert(block_data_decrypted.seller.node_id);
+                participants.push(block_data_decrypted.buyer);
+                participants.push(block_data_decrypted.seller);
             }
-            RejectToAccept | RejectToBuy | RejectToPay | RejectToPayRecourse => {
+            RejectToAccept
+            | RejectToBuy
+            | RejectToPay
+            | RejectToPayRecourse
+            | CancelOfferToSell
+            | CancelRequestToAccept
+            | RejectMintQuote => {
                 let block_data_decrypted: BillRejectBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.rejecter.node_id);
+                participants.push(block_data_decrypted.rejecter);
             }
             RequestRecourse => {
                 let block_data_decrypted: BillRequestRecourseBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.recourser.node_id);
-                nodes.insert(block_data_decrypted.recoursee.node_id);
+                participants.push(block_data_decrypted.recourser);
+                participants.push(block_data_decrypted.recoursee);
             }
             Recourse => {
                 let block_data_decrypted: BillRecourseBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
-                nodes.insert(block_data_decrypted.recourser.node_id);
-                nodes.insert(block_data_decrypted.recoursee.node_id);
+                participants.push(block_data_decrypted.recourser);
+                participants.push(block_data_decrypted.recoursee);
+            }
+            AttachFile => {
+                let block_data_decrypted: BillAttachFileBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                participants.push(block_data_decrypted.attacher);
+            }
+            ExternalPayment => {
+                let block_data_decrypted: BillExternalPaymentBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                participants.push(block_data_decrypted.payer);
+                participants.push(block_data_decrypted.holder);
+            }
+            Acknowledge => {
+                let block_data_decrypted: BillAcknowledgeBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                participants.push(block_data_decrypted.acknowledger);
+            }
+            CorrectDrawee => {
+                let block_data_decrypted: BillCorrectDraweeBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                participants.push(block_data_decrypted.drawer);
+                participants.push(block_data_decrypted.drawee);
+            }
+            MintQuote => {
+                let block_data_decrypted: BillMintQuoteBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                participants.push(block_data_decrypted.mint);
+                participants.push(block_data_decrypted.requester);
+            }
+            ApproveMintQuote => {
+                let block_data_decrypted: BillApproveMintQuoteBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                participants.push(block_data_decrypted.approver);
+                participants.push(block_data_decrypted.mint);
             }
         }
-        Ok(nodes.into_iter().collect())
+        Ok(participants)
     }
 
     /// If the block is a holder-changing block with a financial beneficiary(sell, recourse),
@@ -1204,6 +1821,186 @@ impl BillBlock {
         }
     }
 
+    /// Decrypts the block and returns it as a history entry, with the node id of the acting
+    /// party and a short summary of what happened
+    pub fn get_history_entry(&self, bill_keys: &BillKeys) -> Result<BillHistoryEntry> {
+        let (acting_node_id, summary) = match self.op_code {
+            Issue => {
+                let bill: BillIssueBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    bill.drawer.node_id,
+                    format!(
+                        "Issued to {} for {} {}",
+                        bill.payee.node_id, bill.sum, bill.currency
+                    ),
+                )
+            }
+            Accept => {
+                let block: BillAcceptBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (block.accepter.node_id, "Accepted".to_string())
+            }
+            Endorse => {
+                let block: BillEndorseBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.endorser.node_id,
+                    format!("Endorsed to {}", block.endorsee.node_id),
+                )
+            }
+            RequestToAccept => {
+                let block: BillRequestToAcceptBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                (block.requester.node_id, "Requested acceptance".to_string())
+            }
+            RequestToPay => {
+                let block: BillRequestToPayBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.requester.node_id,
+                    format!("Requested payment in {}", block.currency),
+                )
+            }
+            OfferToSell => {
+                let block: BillOfferToSellBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.seller.node_id,
+                    format!(
+                        "Offer to sell {} {} to {}",
+                        block.sum, block.currency, block.buyer.node_id
+                    ),
+                )
+            }
+            Sell => {
+                let block: BillSellBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.seller.node_id,
+                    format!(
+                        "Sold for {} {} to {}",
+                        block.sum, block.currency, block.buyer.node_id
+                    ),
+                )
+            }
+            Mint => {
+                let block: BillMintBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.endorser.node_id,
+                    format!(
+                        "Minted for {} {} to {}",
+                        block.sum, block.currency, block.endorsee.node_id
+                    ),
+                )
+            }
+            RejectToAccept
+            | RejectToBuy
+            | RejectToPay
+            | RejectToPayRecourse
+            | CancelOfferToSell
+            | CancelRequestToAccept
+            | RejectMintQuote => {
+                let block: BillRejectBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                let summary = match self.op_code {
+                    RejectToAccept => "Rejected acceptance",
+                    RejectToBuy => "Rejected to buy",
+                    RejectToPay => "Rejected payment",
+                    CancelOfferToSell => "Cancelled offer to sell",
+                    CancelRequestToAccept => "Cancelled request to accept",
+                    RejectMintQuote => "Rejected mint quote",
+                    _ => "Rejected recourse payment",
+                };
+                let summary = match block.reason {
+                    Some(reason) => format!("{summary}: {reason}"),
+                    None => summary.to_string(),
+                };
+                (block.rejecter.node_id, summary)
+            }
+            RequestRecourse => {
+                let block: BillRequestRecourseBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                let reason = match block.recourse_reason {
+                    BillRecourseReasonBlockData::Accept => "acceptance",
+                    BillRecourseReasonBlockData::Pay => "payment",
+                };
+                (
+                    block.recourser.node_id,
+                    format!(
+                        "Requested recourse for {reason} of {} {} from {}",
+                        block.sum, block.currency, block.recoursee.node_id
+                    ),
+                )
+            }
+            Recourse => {
+                let block: BillRecourseBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.recourser.node_id,
+                    format!(
+                        "Recourse of {} {} paid by {}",
+                        block.sum, block.currency, block.recoursee.node_id
+                    ),
+                )
+            }
+            AttachFile => {
+                let block: BillAttachFileBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                let file_names: Vec<String> = block.files.into_iter().map(|f| f.name).collect();
+                (
+                    block.attacher.node_id,
+                    format!("Attached files {}", file_names.join(", ")),
+                )
+            }
+            ExternalPayment => {
+                let block: BillExternalPaymentBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.holder.node_id,
+                    format!(
+                        "Marked as paid externally by {} with reference {}",
+                        block.payer.node_id, block.reference
+                    ),
+                )
+            }
+            Acknowledge => {
+                let block: BillAcknowledgeBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.acknowledger.node_id,
+                    "Acknowledged having seen the bill".to_string(),
+                )
+            }
+            CorrectDrawee => {
+                let block: BillCorrectDraweeBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.drawer.node_id,
+                    format!("Corrected the drawee to {}", block.drawee.node_id),
+                )
+            }
+            MintQuote => {
+                let block: BillMintQuoteBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.mint.node_id,
+                    format!(
+                        "Quoted {} {} for minting to {}",
+                        block.sum, block.currency, block.requester.node_id
+                    ),
+                )
+            }
+            ApproveMintQuote => {
+                let block: BillApproveMintQuoteBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                (
+                    block.approver.node_id,
+                    format!(
+                        "Approved mint quote of {} {} from {}",
+                        block.sum, block.currency, block.mint.node_id
+                    ),
+                )
+            }
+        };
+        Ok(BillHistoryEntry {
+            block_height: self.id,
+            op_code: self.op_code.clone(),
+            acting_node_id,
+            timestamp: self.timestamp,
+            summary,
+        })
+    }
+
     /// If the block is holder-changing block (issue, endorse, sell, mint, recourse), returns
     /// the new holder and signer data from the block
     pub fn get_holder_from_block(&self, bill_keys: &BillKeys) -> Result<Option<HolderFromBlock>> {
@@ -1347,7 +2144,7 @@ impl BillBlock {
                 (
                     data.rejecter.node_id,
                     data.signatory.map(|s| s.node_id),
-                    Some(BillAction::RejectAcceptance),
+                    Some(BillAction::RejectAcceptance(data.reason)),
                 )
             }
             RejectToBuy => {
@@ -1356,7 +2153,7 @@ impl BillBlock {
                 (
                     data.rejecter.node_id,
                     data.signatory.map(|s| s.node_id),
-                    Some(BillAction::RejectBuying),
+                    Some(BillAction::RejectBuying(data.reason)),
                 )
             }
             RejectToPay => {
@@ -1365,7 +2162,7 @@ impl BillBlock {
                 (
                     data.rejecter.node_id,
                     data.signatory.map(|s| s.node_id),
-                    Some(BillAction::RejectPayment),
+                    Some(BillAction::RejectPayment(data.reason)),
                 )
             }
             RejectToPayRecourse => {
@@ -1377,6 +2174,24 @@ impl BillBlock {
                     Some(BillAction::RejectPaymentForRecourse),
                 )
             }
+            CancelOfferToSell => {
+                let data: BillRejectBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.rejecter.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::CancelOfferToSell),
+                )
+            }
+            CancelRequestToAccept => {
+                let data: BillRejectBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.rejecter.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::CancelRequestToAccept),
+                )
+            }
             RequestRecourse => {
                 let data: BillRequestRecourseBlockData =
                     self.get_decrypted_block_bytes(bill_keys)?;
@@ -1403,16 +2218,85 @@ impl BillBlock {
                 };
                 data.validate()?;
                 (
-                    data.recourser.node_id,
+                    data.recourser.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::Recourse(
+                        data.recoursee.into(),
+                        data.sum,
+                        data.currency,
+                        reason,
+                    )),
+                )
+            }
+            AttachFile => {
+                let data: BillAttachFileBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.attacher.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::AttachFile(data.files)),
+                )
+            }
+            ExternalPayment => {
+                let data: BillExternalPaymentBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.holder.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::MarkPaidExternally(data.reference)),
+                )
+            }
+            Acknowledge => {
+                let data: BillAcknowledgeBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.acknowledger.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::Acknowledge),
+                )
+            }
+            CorrectDrawee => {
+                let data: BillCorrectDraweeBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.drawer.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::CorrectDrawee(data.drawee.into())),
+                )
+            }
+            MintQuote => {
+                let data: BillMintQuoteBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.mint.node_id,
                     data.signatory.map(|s| s.node_id),
-                    Some(BillAction::Recourse(
-                        data.recoursee.into(),
+                    Some(BillAction::SubmitMintQuote(
                         data.sum,
                         data.currency,
-                        reason,
+                        data.expiry_timestamp,
                     )),
                 )
             }
+            ApproveMintQuote => {
+                let data: BillApproveMintQuoteBlockData =
+                    self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.approver.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::ApproveMintQuote),
+                )
+            }
+            RejectMintQuote => {
+                let data: BillRejectBlockData = self.get_decrypted_block_bytes(bill_keys)?;
+                data.validate()?;
+                (
+                    data.rejecter.node_id,
+                    data.signatory.map(|s| s.node_id),
+                    Some(BillAction::RejectMintQuote),
+                )
+            }
         };
         if !self.verify_signer(&signer, &signatory, bill_keys) {
             return Err(Error::BlockSignatureDoesNotMatchSigner);
@@ -1461,6 +2345,7 @@ pub mod tests {
     use super::*;
     use crate::{
         blockchain::bill::tests::get_baseline_identity,
+        constants::{MAX_REJECT_REASON_CHARACTERS, PAYMENT_DEADLINE_SECONDS},
         tests::tests::{
             OTHER_TEST_PUB_KEY_SECP, TEST_BILL_ID, TEST_NODE_ID_SECP, TEST_PRIVATE_KEY_SECP,
             TEST_PUB_KEY_SECP, VALID_PAYMENT_ADDRESS_TESTNET, empty_bitcredit_bill,
@@ -1713,6 +2598,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: seller.postal_address,
+                sell_deadline_seconds: None,
             },
             &get_baseline_identity().key_pair,
             None,
@@ -1763,6 +2649,284 @@ pub mod tests {
         assert!(res.as_ref().unwrap().contains(&seller.node_id));
     }
 
+    #[test]
+    fn get_history_entry_issue() {
+        let block = get_first_block();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        let entry = res.unwrap();
+        assert_eq!(entry.block_height, block.id);
+        assert_eq!(entry.op_code, BillOpCode::Issue);
+        assert_eq!(entry.timestamp, 1731593928);
+        assert!(entry.summary.starts_with("Issued to"));
+    }
+
+    #[test]
+    fn get_history_entry_sell() {
+        let buyer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let seller =
+            identity_public_data_only_node_id(get_baseline_identity().key_pair.get_public_key());
+        let block = BillBlock::create_block_for_sell(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillSellBlockData {
+                buyer: buyer.clone().into(),
+                seller: seller.clone().into(),
+                sum: 15000,
+                currency: "sat".to_string(),
+                payment_address: VALID_PAYMENT_ADDRESS_TESTNET.to_string(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: seller.postal_address.clone(),
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        let entry = res.unwrap();
+        assert_eq!(entry.block_height, 2);
+        assert_eq!(entry.op_code, BillOpCode::Sell);
+        assert_eq!(entry.acting_node_id, seller.node_id);
+        assert_eq!(
+            entry.summary,
+            format!("Sold for 15000 sat to {}", buyer.node_id)
+        );
+    }
+
+    #[test]
+    fn get_history_entry_reject_to_pay() {
+        let rejecter = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_reject_to_pay(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillRejectBlockData {
+                rejecter: rejecter.clone().into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: rejecter.postal_address,
+                reason: None,
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().acting_node_id, rejecter.node_id);
+        assert_eq!(
+            res.as_ref().unwrap().summary,
+            "Rejected payment".to_string()
+        );
+    }
+
+    #[test]
+    fn get_history_entry_cancel_offer_to_sell() {
+        let seller = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_cancel_offer_to_sell(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillRejectBlockData {
+                rejecter: seller.clone().into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: seller.postal_address,
+                reason: None,
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().acting_node_id, seller.node_id);
+        assert_eq!(res.as_ref().unwrap().op_code, BillOpCode::CancelOfferToSell);
+        assert_eq!(
+            res.as_ref().unwrap().summary,
+            "Cancelled offer to sell".to_string()
+        );
+    }
+
+    #[test]
+    fn get_history_entry_cancel_request_to_accept() {
+        let requester = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_cancel_request_to_accept(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillRejectBlockData {
+                rejecter: requester.clone().into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: requester.postal_address,
+                reason: None,
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().acting_node_id, requester.node_id);
+        assert_eq!(
+            res.as_ref().unwrap().op_code,
+            BillOpCode::CancelRequestToAccept
+        );
+        assert_eq!(
+            res.as_ref().unwrap().summary,
+            "Cancelled request to accept".to_string()
+        );
+    }
+
+    #[test]
+    fn get_history_entry_attach_file() {
+        let attacher = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_attach_file(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillAttachFileBlockData {
+                attacher: attacher.clone().into(),
+                files: vec![
+                    File {
+                        name: "delivery_note.pdf".into(),
+                        hash: "hash1".into(),
+                    },
+                    File {
+                        name: "invoice.pdf".into(),
+                        hash: "hash2".into(),
+                    },
+                ],
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: attacher.postal_address,
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().acting_node_id, attacher.node_id);
+        assert_eq!(
+            res.as_ref().unwrap().summary,
+            "Attached files delivery_note.pdf, invoice.pdf".to_string()
+        );
+        let nodes = block.get_nodes_from_block(&get_bill_keys());
+        assert!(nodes.is_ok());
+        assert_eq!(nodes.as_ref().unwrap().len(), 1);
+        assert!(nodes.as_ref().unwrap().contains(&attacher.node_id));
+    }
+
+    #[test]
+    fn get_history_entry_acknowledge() {
+        let acknowledger = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_acknowledge(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillAcknowledgeBlockData {
+                acknowledger: acknowledger.clone().into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: acknowledger.postal_address.clone(),
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().acting_node_id, acknowledger.node_id);
+        assert_eq!(res.as_ref().unwrap().op_code, BillOpCode::Acknowledge);
+        let nodes = block.get_nodes_from_block(&get_bill_keys());
+        assert!(nodes.is_ok());
+        assert_eq!(nodes.as_ref().unwrap().len(), 1);
+        assert!(nodes.as_ref().unwrap().contains(&acknowledger.node_id));
+    }
+
+    #[test]
+    fn get_history_entry_correct_drawee() {
+        let drawer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_correct_drawee(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillCorrectDraweeBlockData {
+                drawer: drawer.clone().into(),
+                drawee: drawee.clone().into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: drawer.postal_address.clone(),
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().acting_node_id, drawer.node_id);
+        assert_eq!(
+            res.as_ref().unwrap().summary,
+            format!("Corrected the drawee to {}", drawee.node_id)
+        );
+        let nodes = block.get_nodes_from_block(&get_bill_keys());
+        assert!(nodes.is_ok());
+        assert_eq!(nodes.as_ref().unwrap().len(), 2);
+        assert!(nodes.as_ref().unwrap().contains(&drawer.node_id));
+        assert!(nodes.as_ref().unwrap().contains(&drawee.node_id));
+    }
+
+    #[test]
+    fn get_history_entry_external_payment() {
+        let payer = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let holder = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_external_payment(
+            TEST_BILL_ID.to_string(),
+            &get_first_block(),
+            &BillExternalPaymentBlockData {
+                payer: payer.clone().into(),
+                holder: holder.clone().into(),
+                reference: "SEPA-2024-0001".into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: holder.postal_address.clone(),
+            },
+            &get_baseline_identity().key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        let res = block.get_history_entry(&get_bill_keys());
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().acting_node_id, holder.node_id);
+        assert_eq!(
+            res.as_ref().unwrap().summary,
+            format!(
+                "Marked as paid externally by {} with reference SEPA-2024-0001",
+                payer.node_id
+            )
+        );
+        let nodes = block.get_nodes_from_block(&get_bill_keys());
+        assert!(nodes.is_ok());
+        assert_eq!(nodes.as_ref().unwrap().len(), 2);
+        assert!(nodes.as_ref().unwrap().contains(&payer.node_id));
+        assert!(nodes.as_ref().unwrap().contains(&holder.node_id));
+    }
+
     #[test]
     fn get_nodes_from_block_reject_to_accept() {
         let rejecter = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
@@ -1774,6 +2938,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: rejecter.postal_address,
+                reason: None,
             },
             &get_baseline_identity().key_pair,
             None,
@@ -1798,6 +2963,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: rejecter.postal_address,
+                reason: None,
             },
             &get_baseline_identity().key_pair,
             None,
@@ -1822,6 +2988,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: rejecter.postal_address,
+                reason: None,
             },
             &get_baseline_identity().key_pair,
             None,
@@ -1846,6 +3013,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: rejecter.postal_address,
+                reason: None,
             },
             &get_baseline_identity().key_pair,
             None,
@@ -2100,6 +3268,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                sell_deadline_seconds: None,
             },
             &identity_keys,
             None,
@@ -2156,6 +3325,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             None,
@@ -2171,7 +3341,7 @@ pub mod tests {
         );
         assert!(matches!(
             reject_to_accept_result.as_ref().unwrap().1,
-            Some(BillAction::RejectAcceptance)
+            Some(BillAction::RejectAcceptance(None))
         ));
 
         let reject_to_buy_block = BillBlock::create_block_for_reject_to_buy(
@@ -2182,6 +3352,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             None,
@@ -2197,7 +3368,7 @@ pub mod tests {
         );
         assert!(matches!(
             reject_to_buy_result.as_ref().unwrap().1,
-            Some(BillAction::RejectBuying)
+            Some(BillAction::RejectBuying(None))
         ));
 
         let reject_to_pay_block = BillBlock::create_block_for_reject_to_pay(
@@ -2208,6 +3379,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             None,
@@ -2223,7 +3395,7 @@ pub mod tests {
         );
         assert!(matches!(
             reject_to_pay_result.as_ref().unwrap().1,
-            Some(BillAction::RejectPayment)
+            Some(BillAction::RejectPayment(None))
         ));
 
         let reject_to_pay_recourse_block = BillBlock::create_block_for_reject_to_pay_recourse(
@@ -2234,6 +3406,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             None,
@@ -2522,6 +3695,7 @@ pub mod tests {
                 }),
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                sell_deadline_seconds: None,
             },
             &identity_keys,
             Some(&company_keys),
@@ -2584,6 +3758,7 @@ pub mod tests {
                 }),
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             Some(&company_keys),
@@ -2599,7 +3774,7 @@ pub mod tests {
         );
         assert!(matches!(
             reject_to_accept_result.as_ref().unwrap().1,
-            Some(BillAction::RejectAcceptance)
+            Some(BillAction::RejectAcceptance(None))
         ));
 
         let reject_to_buy_block = BillBlock::create_block_for_reject_to_buy(
@@ -2613,6 +3788,7 @@ pub mod tests {
                 }),
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             Some(&company_keys),
@@ -2628,7 +3804,7 @@ pub mod tests {
         );
         assert!(matches!(
             reject_to_buy_result.as_ref().unwrap().1,
-            Some(BillAction::RejectBuying)
+            Some(BillAction::RejectBuying(None))
         ));
 
         let reject_to_pay_block = BillBlock::create_block_for_reject_to_pay(
@@ -2642,6 +3818,7 @@ pub mod tests {
                 }),
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             Some(&company_keys),
@@ -2657,7 +3834,7 @@ pub mod tests {
         );
         assert!(matches!(
             reject_to_pay_result.as_ref().unwrap().1,
-            Some(BillAction::RejectPayment)
+            Some(BillAction::RejectPayment(None))
         ));
 
         let reject_to_pay_recourse_block = BillBlock::create_block_for_reject_to_pay_recourse(
@@ -2671,6 +3848,7 @@ pub mod tests {
                 }),
                 signing_timestamp: 1731593928,
                 signing_address: signer.postal_address.clone(),
+                reason: None,
             },
             &identity_keys,
             Some(&company_keys),
@@ -2959,6 +4137,17 @@ pub mod tests {
             signatory: Some(valid_bill_signatory_block_data()),
             signing_timestamp: 1731593928,
             signing_address: valid_address(),
+            payment_deadline_seconds: Some(PAYMENT_DEADLINE_SECONDS),
+            fiat_amount: None,
+        }
+    }
+
+    fn valid_bill_fiat_amount() -> BillFiatAmount {
+        BillFiatAmount {
+            currency: "eur".into(),
+            sum: "7.50".into(),
+            btc_rate: 50000.0,
+            rate_timestamp: 1731593928,
         }
     }
 
@@ -2968,6 +4157,58 @@ pub mod tests {
         assert_eq!(bill.validate(), Ok(()));
     }
 
+    #[test]
+    fn bill_issue_block_data_roundtrip_with_payment_deadline() {
+        let bill = BillIssueBlockData {
+            payment_deadline_seconds: Some(86400 * 14),
+            ..valid_bill_issue_block_data()
+        };
+        let bytes = to_vec(&bill).unwrap();
+        let deserialized: BillIssueBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, bill);
+    }
+
+    #[test]
+    fn bill_issue_block_data_without_payment_deadline_can_be_deserialized() {
+        let bill = BillIssueBlockData {
+            payment_deadline_seconds: None,
+            ..valid_bill_issue_block_data()
+        };
+        let mut bytes = to_vec(&bill).unwrap();
+        // remove the option tag, to get the layout of bills issued before the field existed
+        bytes.pop();
+        let deserialized: BillIssueBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized.payment_deadline_seconds, None);
+        assert_eq!(deserialized.id, bill.id);
+        assert_eq!(deserialized.signing_address, bill.signing_address);
+    }
+
+    #[test]
+    fn bill_issue_block_data_roundtrip_with_fiat_amount() {
+        let bill = BillIssueBlockData {
+            sum: 15000,
+            fiat_amount: Some(valid_bill_fiat_amount()),
+            ..valid_bill_issue_block_data()
+        };
+        assert_eq!(bill.validate(), Ok(()));
+        let bytes = to_vec(&bill).unwrap();
+        let deserialized: BillIssueBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, bill);
+    }
+
+    #[test]
+    fn bill_issue_block_data_without_fiat_amount_can_be_deserialized() {
+        let bill = BillIssueBlockData {
+            payment_deadline_seconds: Some(86400 * 14),
+            ..valid_bill_issue_block_data()
+        };
+        let mut bytes = to_vec(&bill).unwrap();
+        // remove the option tag, to get the layout of bills issued before the field existed
+        bytes.pop();
+        let deserialized: BillIssueBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, bill);
+    }
+
     #[rstest]
     #[case::empty_id(BillIssueBlockData { id: "".into(), ..valid_bill_issue_block_data() }, ValidationError::FieldEmpty(Field::Id))]
     #[case::blank_id(BillIssueBlockData { id: "  ".into(), ..valid_bill_issue_block_data() }, ValidationError::FieldEmpty(Field::Id))]
@@ -2990,6 +4231,11 @@ pub mod tests {
     #[case::invalid_drawer(BillIssueBlockData { payee: invalid_bill_identity_block_data(), ..valid_bill_issue_block_data() }, ValidationError::FieldEmpty(Field::Name))]
     #[case::invalid_payee(BillIssueBlockData { signatory: Some(invalid_bill_signatory_block_data()), ..valid_bill_issue_block_data() }, ValidationError::FieldEmpty(Field::Name))]
     #[case::invalid_signing_address(BillIssueBlockData { signing_address: invalid_address(), ..valid_bill_issue_block_data() }, ValidationError::FieldEmpty(Field::Country))]
+    #[case::invalid_payment_deadline(BillIssueBlockData { payment_deadline_seconds: Some(0), ..valid_bill_issue_block_data() }, ValidationError::InvalidPaymentDeadline)]
+    #[case::payment_deadline_too_long(BillIssueBlockData { payment_deadline_seconds: Some(u64::MAX), ..valid_bill_issue_block_data() }, ValidationError::InvalidPaymentDeadline)]
+    #[case::invalid_fiat_currency(BillIssueBlockData { fiat_amount: Some(BillFiatAmount { currency: "sat".into(), ..valid_bill_fiat_amount() }), ..valid_bill_issue_block_data() }, ValidationError::InvalidCurrency)]
+    #[case::invalid_fiat_sum(BillIssueBlockData { fiat_amount: Some(BillFiatAmount { sum: "0".into(), ..valid_bill_fiat_amount() }), ..valid_bill_issue_block_data() }, ValidationError::InvalidSum)]
+    #[case::invalid_fiat_rate(BillIssueBlockData { fiat_amount: Some(BillFiatAmount { btc_rate: 0.0, ..valid_bill_fiat_amount() }), ..valid_bill_issue_block_data() }, ValidationError::FiatRateUnavailable)]
     fn test_invalid_bill_issue_block_data(
         #[case] bill: BillIssueBlockData,
         #[case] expected: ValidationError,
@@ -3119,6 +4365,7 @@ pub mod tests {
             signatory: Some(valid_bill_signatory_block_data()),
             signing_timestamp: 1731593928,
             signing_address: valid_address(),
+            sell_deadline_seconds: None,
         }
     }
 
@@ -3128,6 +4375,29 @@ pub mod tests {
         assert_eq!(accept.validate(), Ok(()));
     }
 
+    #[test]
+    fn offer_to_sell_block_data_roundtrip_with_sell_deadline() {
+        let offer = BillOfferToSellBlockData {
+            sell_deadline_seconds: Some(86400 * 30),
+            ..valid_offer_to_sell_block_data()
+        };
+        let bytes = to_vec(&offer).unwrap();
+        let deserialized: BillOfferToSellBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, offer);
+        assert_eq!(deserialized.get_sell_deadline_seconds(86400), 86400 * 30);
+    }
+
+    #[test]
+    fn offer_to_sell_block_data_without_sell_deadline_can_be_deserialized() {
+        let offer = valid_offer_to_sell_block_data();
+        let mut bytes = to_vec(&offer).unwrap();
+        // remove the option tag, to get the layout of offers created before the field existed
+        bytes.pop();
+        let deserialized: BillOfferToSellBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, offer);
+        assert_eq!(deserialized.get_sell_deadline_seconds(86400), 86400);
+    }
+
     #[rstest]
     #[case::invalid_seller(BillOfferToSellBlockData { seller: invalid_bill_identity_block_data(), ..valid_offer_to_sell_block_data() }, ValidationError::FieldEmpty(Field::Name))]
     #[case::invalid_buyer(BillOfferToSellBlockData { buyer: invalid_bill_identity_block_data(), ..valid_offer_to_sell_block_data() }, ValidationError::FieldEmpty(Field::Name))]
@@ -3136,6 +4406,8 @@ pub mod tests {
     #[case::invalid_payment_address(BillOfferToSellBlockData { payment_address: "invalidaddress".into(), ..valid_offer_to_sell_block_data() }, ValidationError::InvalidPaymentAddress)]
     #[case::invalid_signing_address(BillOfferToSellBlockData { signing_address: invalid_address(), ..valid_offer_to_sell_block_data() }, ValidationError::FieldEmpty(Field::Country))]
     #[case::invalid_signatory(BillOfferToSellBlockData { signatory: Some(invalid_bill_signatory_block_data()), ..valid_offer_to_sell_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::invalid_sell_deadline(BillOfferToSellBlockData { sell_deadline_seconds: Some(0), ..valid_offer_to_sell_block_data() }, ValidationError::InvalidPaymentDeadline)]
+    #[case::sell_deadline_too_long(BillOfferToSellBlockData { sell_deadline_seconds: Some(u64::MAX), ..valid_offer_to_sell_block_data() }, ValidationError::InvalidPaymentDeadline)]
     fn test_invalid_offer_to_sell_block_data(
         #[case] block: BillOfferToSellBlockData,
         #[case] expected_error: ValidationError,
@@ -3277,6 +4549,7 @@ pub mod tests {
             signatory: Some(valid_bill_signatory_block_data()),
             signing_timestamp: 1731593928,
             signing_address: valid_address(),
+            reason: None,
         }
     }
 
@@ -3286,14 +4559,154 @@ pub mod tests {
         assert_eq!(accept.validate(), Ok(()));
     }
 
+    #[test]
+    fn reject_block_data_roundtrip_with_reason() {
+        let block = BillRejectBlockData {
+            reason: Some("goods not received".into()),
+            ..valid_reject_block_data()
+        };
+        assert_eq!(block.validate(), Ok(()));
+        let bytes = to_vec(&block).unwrap();
+        let deserialized: BillRejectBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, block);
+    }
+
+    #[test]
+    fn reject_block_data_without_reason_can_be_deserialized() {
+        let block = valid_reject_block_data();
+        let mut bytes = to_vec(&block).unwrap();
+        // remove the option tag, to get the layout of blocks recorded before the field existed
+        bytes.pop();
+        let deserialized: BillRejectBlockData = from_slice(&bytes).unwrap();
+        assert_eq!(deserialized, block);
+    }
+
     #[rstest]
     #[case::invalid_rejecter(BillRejectBlockData { rejecter: invalid_bill_identity_block_data(), ..valid_reject_block_data() }, ValidationError::FieldEmpty(Field::Name))]
     #[case::invalid_signing_address(BillRejectBlockData { signing_address: invalid_address(), ..valid_reject_block_data() }, ValidationError::FieldEmpty(Field::Country))]
     #[case::invalid_signatory(BillRejectBlockData { signatory: Some(invalid_bill_signatory_block_data()), ..valid_reject_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::reason_too_long(BillRejectBlockData { reason: Some("a".repeat(MAX_REJECT_REASON_CHARACTERS + 1)), ..valid_reject_block_data() }, ValidationError::RejectReasonTooLong(MAX_REJECT_REASON_CHARACTERS))]
     fn test_invalid_reject_block_data(
         #[case] block: BillRejectBlockData,
         #[case] expected_error: ValidationError,
     ) {
         assert_eq!(block.validate(), Err(expected_error));
     }
+
+    fn valid_attach_file_block_data() -> BillAttachFileBlockData {
+        BillAttachFileBlockData {
+            attacher: valid_bill_identity_block_data(),
+            files: vec![File {
+                name: "delivery_note.pdf".into(),
+                hash: "HgTkJ2iK5KC4yCPMa7GWAoxW6yxBaH8kVAsRBYg6oVc".into(),
+            }],
+            signatory: Some(valid_bill_signatory_block_data()),
+            signing_timestamp: 1731593928,
+            signing_address: valid_address(),
+        }
+    }
+
+    #[test]
+    fn test_valid_attach_file_block_data() {
+        let attach_file = valid_attach_file_block_data();
+        assert_eq!(attach_file.validate(), Ok(()));
+    }
+
+    #[rstest]
+    #[case::invalid_attacher(BillAttachFileBlockData { attacher: invalid_bill_identity_block_data(), ..valid_attach_file_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::no_files(BillAttachFileBlockData { files: vec![], ..valid_attach_file_block_data() }, ValidationError::FieldEmpty(Field::Files))]
+    #[case::empty_file_hash(BillAttachFileBlockData { files: vec![File { name: "delivery_note.pdf".into(), hash: "".into() }], ..valid_attach_file_block_data() }, ValidationError::FieldEmpty(Field::Files))]
+    #[case::invalid_signing_address(BillAttachFileBlockData { signing_address: invalid_address(), ..valid_attach_file_block_data() }, ValidationError::FieldEmpty(Field::Country))]
+    #[case::invalid_signatory(BillAttachFileBlockData { signatory: Some(invalid_bill_signatory_block_data()), ..valid_attach_file_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    fn test_invalid_attach_file_block_data(
+        #[case] block: BillAttachFileBlockData,
+        #[case] expected_error: ValidationError,
+    ) {
+        assert_eq!(block.validate(), Err(expected_error));
+    }
+
+    fn valid_external_payment_block_data() -> BillExternalPaymentBlockData {
+        BillExternalPaymentBlockData {
+            payer: valid_bill_identity_block_data(),
+            holder: other_valid_bill_identity_block_data(),
+            reference: "SEPA-2024-0001".into(),
+            signatory: Some(valid_bill_signatory_block_data()),
+            signing_timestamp: 1731593928,
+            signing_address: valid_address(),
+        }
+    }
+
+    #[test]
+    fn test_valid_external_payment_block_data() {
+        let external_payment = valid_external_payment_block_data();
+        assert_eq!(external_payment.validate(), Ok(()));
+    }
+
+    #[rstest]
+    #[case::invalid_payer(BillExternalPaymentBlockData { payer: invalid_bill_identity_block_data(), ..valid_external_payment_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::invalid_holder(BillExternalPaymentBlockData { holder: invalid_bill_identity_block_data(), ..valid_external_payment_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::empty_reference(BillExternalPaymentBlockData { reference: "".into(), ..valid_external_payment_block_data() }, ValidationError::FieldEmpty(Field::ExternalPaymentReference))]
+    #[case::blank_reference(BillExternalPaymentBlockData { reference: "  ".into(), ..valid_external_payment_block_data() }, ValidationError::FieldEmpty(Field::ExternalPaymentReference))]
+    #[case::invalid_signing_address(BillExternalPaymentBlockData { signing_address: invalid_address(), ..valid_external_payment_block_data() }, ValidationError::FieldEmpty(Field::Country))]
+    #[case::invalid_signatory(BillExternalPaymentBlockData { signatory: Some(invalid_bill_signatory_block_data()), ..valid_external_payment_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    fn test_invalid_external_payment_block_data(
+        #[case] block: BillExternalPaymentBlockData,
+        #[case] expected_error: ValidationError,
+    ) {
+        assert_eq!(block.validate(), Err(expected_error));
+    }
+
+    fn valid_acknowledge_block_data() -> BillAcknowledgeBlockData {
+        BillAcknowledgeBlockData {
+            acknowledger: valid_bill_identity_block_data(),
+            signatory: Some(valid_bill_signatory_block_data()),
+            signing_timestamp: 1731593928,
+            signing_address: valid_address(),
+        }
+    }
+
+    #[test]
+    fn test_valid_acknowledge_block_data() {
+        let acknowledge = valid_acknowledge_block_data();
+        assert_eq!(acknowledge.validate(), Ok(()));
+    }
+
+    #[rstest]
+    #[case::invalid_acknowledger(BillAcknowledgeBlockData { acknowledger: invalid_bill_identity_block_data(), ..valid_acknowledge_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::invalid_signing_address(BillAcknowledgeBlockData { signing_address: invalid_address(), ..valid_acknowledge_block_data() }, ValidationError::FieldEmpty(Field::Country))]
+    #[case::invalid_signatory(BillAcknowledgeBlockData { signatory: Some(invalid_bill_signatory_block_data()), ..valid_acknowledge_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    fn test_invalid_acknowledge_block_data(
+        #[case] block: BillAcknowledgeBlockData,
+        #[case] expected_error: ValidationError,
+    ) {
+        assert_eq!(block.validate(), Err(expected_error));
+    }
+
+    fn valid_correct_drawee_block_data() -> BillCorrectDraweeBlockData {
+        BillCorrectDraweeBlockData {
+            drawer: valid_bill_identity_block_data(),
+            drawee: other_valid_bill_identity_block_data(),
+            signatory: Some(valid_bill_signatory_block_data()),
+            signing_timestamp: 1731593928,
+            signing_address: valid_address(),
+        }
+    }
+
+    #[test]
+    fn test_valid_correct_drawee_block_data() {
+        let correct_drawee = valid_correct_drawee_block_data();
+        assert_eq!(correct_drawee.validate(), Ok(()));
+    }
+
+    #[rstest]
+    #[case::invalid_drawer(BillCorrectDraweeBlockData { drawer: invalid_bill_identity_block_data(), ..valid_correct_drawee_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::invalid_drawee(BillCorrectDraweeBlockData { drawee: invalid_bill_identity_block_data(), ..valid_correct_drawee_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    #[case::invalid_signing_address(BillCorrectDraweeBlockData { signing_address: invalid_address(), ..valid_correct_drawee_block_data() }, ValidationError::FieldEmpty(Field::Country))]
+    #[case::invalid_signatory(BillCorrectDraweeBlockData { signatory: Some(invalid_bill_signatory_block_data()), ..valid_correct_drawee_block_data() }, ValidationError::FieldEmpty(Field::Name))]
+    fn test_invalid_correct_drawee_block_data(
+        #[case] block: BillCorrectDraweeBlockData,
+        #[case] expected_error: ValidationError,
+    ) {
+        assert_eq!(block.validate(), Err(expected_error));
+    }
 }
@@ -1,15 +1,22 @@
 use super::super::Result;
 use super::PaymentInfo;
 use super::block::{
-    BillBlock, BillEndorseBlockData, BillIdentityBlockData, BillIssueBlockData, BillMintBlockData,
-    BillOfferToSellBlockData, BillRecourseBlockData, BillRequestRecourseBlockData,
-    BillSellBlockData,
+    BillAcknowledgeBlockData, BillAttachFileBlockData, BillBlock, BillCorrectDraweeBlockData,
+    BillEndorseBlockData, BillExternalPaymentBlockData, BillIdentityBlockData, BillIssueBlockData,
+    BillMintBlockData, BillMintQuoteBlockData, BillOfferToSellBlockData, BillRecourseBlockData,
+    BillRejectBlockData, BillRequestRecourseBlockData, BillSellBlockData,
 };
-use super::{BillOpCode, RecourseWaitingForPayment};
+use super::{BillOpCode, MintRequest, RecourseWaitingForPayment};
 use super::{OfferToSellWaitingForPayment, RecoursePaymentInfo};
-use crate::bill::{BillKeys, LightSignedBy, PastEndorsee, PastPaymentStatus};
-use crate::blockchain::{Block, Blockchain, Error};
-use crate::constants::{PAYMENT_DEADLINE_SECONDS, RECOURSE_DEADLINE_SECONDS};
+use crate::File;
+use crate::bill::{
+    BillAcknowledgement, BillKeys, LightSignedBy, PastEndorsee, PastPaymentStatus,
+    ResolvedParticipant,
+};
+use crate::blockchain::{Block, BlockValidationError, Blockchain, ChainVerificationReport, Error};
+use crate::constants::{
+    DEFAULT_MAX_BILL_CHAIN_BLOCKS, PAYMENT_DEADLINE_SECONDS, RECOURSE_DEADLINE_SECONDS,
+};
 use crate::contact::{ContactType, LightIdentityPublicData};
 use crate::util::{self, BcrKeys};
 use borsh_derive::{BorshDeserialize, BorshSerialize};
@@ -28,6 +35,10 @@ pub struct BillParties {
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
 pub struct BillBlockchain {
     blocks: Vec<BillBlock>,
+    /// The maximum number of blocks, if it's not the default - it's not serialized
+    #[borsh(skip)]
+    #[serde(skip)]
+    max_blocks: Option<usize>,
 }
 
 impl Blockchain for BillBlockchain {
@@ -40,6 +51,10 @@ impl Blockchain for BillBlockchain {
     fn blocks_mut(&mut self) -> &mut Vec<Self::Block> {
         &mut self.blocks
     }
+
+    fn max_blocks(&self) -> Option<usize> {
+        Some(self.max_blocks.unwrap_or(DEFAULT_MAX_BILL_CHAIN_BLOCKS))
+    }
 }
 
 impl BillBlockchain {
@@ -66,11 +81,29 @@ impl BillBlockchain {
 
         Ok(Self {
             blocks: vec![first_block],
+            max_blocks: None,
         })
     }
 
-    /// Creates a bill chain from a vec of blocks
+    /// Limits the chain to the given maximum number of blocks, instead of the default
+    pub fn with_max_blocks(mut self, max_blocks: usize) -> Self {
+        self.max_blocks = Some(max_blocks);
+        self
+    }
+
+    /// Creates a bill chain from a vec of blocks, with at most the default maximum number of blocks
     pub fn new_from_blocks(blocks_to_add: Vec<BillBlock>) -> Result<Self> {
+        Self::new_from_blocks_with_max(blocks_to_add, DEFAULT_MAX_BILL_CHAIN_BLOCKS)
+    }
+
+    /// Creates a bill chain from a vec of blocks, with at most the given number of blocks
+    pub fn new_from_blocks_with_max(
+        blocks_to_add: Vec<BillBlock>,
+        max_blocks: usize,
+    ) -> Result<Self> {
+        if blocks_to_add.len() > max_blocks {
+            return Err(Error::BlockchainTooLong(max_blocks));
+        }
         match blocks_to_add.first() {
             None => Err(Error::BlockchainInvalid),
             Some(first) => {
@@ -80,6 +113,7 @@ impl BillBlockchain {
 
                 let chain = Self {
                     blocks: blocks_to_add,
+                    max_blocks: Some(max_blocks),
                 };
 
                 if !chain.is_chain_valid() {
@@ -91,8 +125,87 @@ impl BillBlockchain {
         }
     }
 
+    /// Creates the chain an observer reads a bill view with - the blocks are verified as they were
+    /// signed, before their data is replaced with the given view data. The view data is
+    /// re-encrypted by the exporting participant, so it can't be checked against the signed block
+    /// hashes, which cover the original data - an observer has to trust the exporter to have
+    /// re-encrypted the signed data. The resulting chain can't be verified anymore for that reason
+    pub fn new_for_view(blocks: Vec<BillBlock>, view_data: Vec<String>) -> Result<Self> {
+        if blocks.len() != view_data.len() {
+            return Err(Error::BlockchainInvalid);
+        }
+        let chain = Self::new_from_blocks(blocks)?;
+        Ok(Self {
+            blocks: chain
+                .blocks
+                .into_iter()
+                .zip(view_data)
+                .map(|(block, data)| BillBlock { data, ..block })
+                .collect(),
+            max_blocks: chain.max_blocks,
+        })
+    }
+
+    /// Verifies the given blocks one by one - the hash, the linkage to the previous block, the
+    /// signature and the block data including its signer. The verification stops at the first
+    /// invalid block
+    pub fn verify_blocks(blocks: &[BillBlock], bill_keys: &BillKeys) -> ChainVerificationReport {
+        let mut valid_blocks = 0;
+        for (idx, block) in blocks.iter().enumerate() {
+            let result = match idx.checked_sub(1).map(|prev_idx| &blocks[prev_idx]) {
+                None => block.check(),
+                Some(previous_block) => block.check_with_previous(previous_block),
+            }
+            .and_then(|_| match block.verify_and_get_signer(bill_keys) {
+                Ok(_) => Ok(()),
+                Err(Error::BlockSignatureDoesNotMatchSigner) => {
+                    Err(BlockValidationError::SignerMismatch)
+                }
+                Err(e) => {
+                    error!("block with id: {} has invalid data: {e}", block.id);
+                    Err(BlockValidationError::InvalidBlockData)
+                }
+            });
+
+            if let Err(reason) = result {
+                return ChainVerificationReport {
+                    valid_blocks,
+                    first_invalid_block_height: Some(idx as u64 + 1),
+                    reason: Some(reason),
+                };
+            }
+            valid_blocks += 1;
+        }
+        ChainVerificationReport {
+            valid_blocks,
+            first_invalid_block_height: None,
+            reason: None,
+        }
+    }
+
+    /// Creates a chain from the blocks before the first invalid one, e.g. to still show a bill up
+    /// to a corrupted block. Returns the chain along with the verification report of all given
+    /// blocks and fails, if already the first block is invalid
+    pub fn new_from_valid_prefix(
+        mut blocks: Vec<BillBlock>,
+        bill_keys: &BillKeys,
+    ) -> Result<(Self, ChainVerificationReport)> {
+        let report = Self::verify_blocks(&blocks, bill_keys);
+        if report.valid_blocks == 0 {
+            return Err(Error::CorruptedBlock(1));
+        }
+        blocks.truncate(report.valid_blocks as usize);
+        Ok((
+            Self {
+                blocks,
+                max_blocks: None,
+            },
+            report,
+        ))
+    }
+
     /// Gets the past payment information for the given node id regarding sell operations (offer to sell, reject to buy,
-    /// / sell), where the node id is the beneficiary (seller)
+    /// / cancel offer to sell / sell), where the node id is the beneficiary (seller)
     pub fn get_past_sell_payments_for_node_id(
         &self,
         bill_keys: &BillKeys,
@@ -117,13 +230,16 @@ impl BillBlockchain {
                         current_offer_to_sell = Some(block.clone());
                     }
                 }
-                BillOpCode::RejectToBuy => {
+                BillOpCode::RejectToBuy | BillOpCode::CancelOfferToSell => {
                     if let Some(offer_to_sell_block) = current_offer_to_sell {
-                        // reject after offer to sell - push both, reset offer to sell
+                        // reject or cancel after offer to sell - push both, reset offer to sell
                         sell_pairs.push((offer_to_sell_block.clone(), Some(block.clone())));
                         current_offer_to_sell = None;
                     } else {
-                        error!("RejectToBuy block without Offer to Sell block detected");
+                        error!(
+                            "{:?} block without Offer to Sell block detected",
+                            block.op_code()
+                        );
                         return Err(Error::BlockchainInvalid);
                     }
                 }
@@ -145,10 +261,13 @@ impl BillBlockchain {
             sell_pairs.push((leftover_offer_to_sell_block.clone(), None));
         }
 
+        let payment_deadline_seconds = self.get_payment_deadline_seconds(bill_keys)?;
         for sell_pair in sell_pairs {
             let offer_to_sell_block = sell_pair.0;
             let block_data_decrypted: BillOfferToSellBlockData =
                 offer_to_sell_block.get_decrypted_block_bytes(bill_keys)?;
+            let sell_deadline_seconds =
+                block_data_decrypted.get_sell_deadline_seconds(payment_deadline_seconds);
 
             if node_id != block_data_decrypted.seller.node_id {
                 // node id is not beneficiary - skip
@@ -165,7 +284,7 @@ impl BillBlockchain {
 
             match sell_pair.1 {
                 Some(reject_or_sell_block) => match reject_or_sell_block.op_code() {
-                    BillOpCode::RejectToBuy => {
+                    BillOpCode::RejectToBuy | BillOpCode::CancelOfferToSell => {
                         result.push((
                             payment_info,
                             PastPaymentStatus::Rejected(reject_or_sell_block.timestamp),
@@ -186,12 +305,14 @@ impl BillBlockchain {
                     if util::date::check_if_deadline_has_passed(
                         offer_to_sell_block.timestamp,
                         timestamp,
-                        PAYMENT_DEADLINE_SECONDS,
+                        sell_deadline_seconds,
                     ) {
                         result.push((
                             payment_info,
                             PastPaymentStatus::Expired(
-                                offer_to_sell_block.timestamp + PAYMENT_DEADLINE_SECONDS,
+                                offer_to_sell_block
+                                    .timestamp
+                                    .saturating_add(sell_deadline_seconds),
                             ),
                             offer_to_sell_block.timestamp,
                         ));
@@ -370,6 +491,84 @@ impl BillBlockchain {
             .any(|block| matches!(block.op_code, BillOpCode::Sell | BillOpCode::Endorse))
     }
 
+    /// Returns the last request to accept block, unless the requester cancelled it afterwards
+    pub fn get_last_active_request_to_accept_block(&self) -> Option<&BillBlock> {
+        let req_to_accept =
+            self.get_last_version_block_with_op_code(BillOpCode::RequestToAccept)?;
+        match self.get_last_version_block_with_op_code(BillOpCode::CancelRequestToAccept) {
+            Some(cancel_req_to_accept) if cancel_req_to_accept.id > req_to_accept.id => None,
+            _ => Some(req_to_accept),
+        }
+    }
+
+    /// Returns the last request to mint, i.e. the last mint block
+    pub fn get_last_mint_request(&self, bill_keys: &BillKeys) -> Result<Option<MintRequest>> {
+        match self.get_last_version_block_with_op_code(BillOpCode::Mint) {
+            Some(mint_block) => {
+                let data: BillMintBlockData = mint_block.get_decrypted_block_bytes(bill_keys)?;
+                Ok(Some(MintRequest {
+                    block_id: mint_block.id,
+                    requester: data.endorser,
+                    mint: data.endorsee,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the request to mint, if it's waiting for a quote of the mint - i.e. if the latest
+    /// block is the mint block, or the rejection of a previous quote
+    pub fn get_mint_request_waiting_for_quote(
+        &self,
+        bill_keys: &BillKeys,
+    ) -> Result<Option<MintRequest>> {
+        match self.get_latest_block().op_code {
+            BillOpCode::Mint | BillOpCode::RejectMintQuote => self.get_last_mint_request(bill_keys),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the block id and data of the quote of the mint, if it wasn't approved, or
+    /// rejected yet
+    pub fn get_open_mint_quote(
+        &self,
+        bill_keys: &BillKeys,
+    ) -> Result<Option<(u64, BillMintQuoteBlockData)>> {
+        let latest_block = self.get_latest_block();
+        if latest_block.op_code != BillOpCode::MintQuote {
+            return Ok(None);
+        }
+        let data: BillMintQuoteBlockData = latest_block.get_decrypted_block_bytes(bill_keys)?;
+        Ok(Some((latest_block.id, data)))
+    }
+
+    /// Checks if the block at the given index is a request to pay, which directly follows a
+    /// request to accept, as created by a combined request to accept and pay
+    fn is_combined_request_to_pay(&self, index: usize) -> bool {
+        index > 0
+            && self.blocks[index].op_code == BillOpCode::RequestToPay
+            && self.blocks[index - 1].op_code == BillOpCode::RequestToAccept
+    }
+
+    /// Checks if the latest block is the request to pay of a combined request to accept and pay
+    pub fn is_latest_block_combined_request_to_pay(&self) -> bool {
+        self.is_combined_request_to_pay(self.blocks.len() - 1)
+    }
+
+    /// Returns the block the current waiting state of the bill depends on - usually the latest
+    /// block, but if the drawee accepted a combined request to accept and pay, the request to pay
+    /// before the accept block is still outstanding
+    pub fn get_latest_block_for_waiting_state(&self) -> &BillBlock {
+        let latest_index = self.blocks.len() - 1;
+        if latest_index > 0
+            && self.blocks[latest_index].op_code == BillOpCode::Accept
+            && self.is_combined_request_to_pay(latest_index - 1)
+        {
+            return &self.blocks[latest_index - 1];
+        }
+        self.get_latest_block()
+    }
+
     /// Checks if the last block is a request to recourse block, if it's deadline is still active and if so,
     /// returns the recoursee, recourser and sum
     pub fn is_last_request_to_recourse_block_waiting_for_payment(
@@ -421,17 +620,18 @@ impl BillBlockchain {
         {
             // we only wait for payment, if the last block is an Offer to Sell block
             if last_block.id == last_version_block_offer_to_sell.id {
+                let block_data_decrypted: BillOfferToSellBlockData =
+                    last_version_block_offer_to_sell.get_decrypted_block_bytes(bill_keys)?;
                 // if the deadline is up, we're not waiting for payment anymore
                 if util::date::check_if_deadline_has_passed(
                     last_version_block_offer_to_sell.timestamp,
                     current_timestamp,
-                    PAYMENT_DEADLINE_SECONDS,
+                    block_data_decrypted
+                        .get_sell_deadline_seconds(self.get_payment_deadline_seconds(bill_keys)?),
                 ) {
                     return Ok(OfferToSellWaitingForPayment::No);
                 }
 
-                let block_data_decrypted: BillOfferToSellBlockData =
-                    last_version_block_offer_to_sell.get_decrypted_block_bytes(bill_keys)?;
                 return Ok(OfferToSellWaitingForPayment::Yes(Box::new(PaymentInfo {
                     buyer: block_data_decrypted.buyer,
                     seller: block_data_decrypted.seller,
@@ -462,6 +662,94 @@ impl BillBlockchain {
         Ok(bill_first_version)
     }
 
+    /// Returns the payment deadline the bill was issued with, falling back to the global default
+    /// for bills issued before the payment deadline was configurable
+    pub fn get_payment_deadline_seconds(&self, bill_keys: &BillKeys) -> Result<u64> {
+        let bill_first_version = self.get_first_version_bill(bill_keys)?;
+        Ok(bill_first_version
+            .payment_deadline_seconds
+            .unwrap_or(PAYMENT_DEADLINE_SECONDS))
+    }
+
+    /// Returns the deadline for paying the last offer to sell - the sell deadline recorded with
+    /// it, or the payment deadline of the bill, if none was recorded, or there is no offer to sell
+    pub fn get_sell_deadline_seconds(&self, bill_keys: &BillKeys) -> Result<u64> {
+        let payment_deadline_seconds = self.get_payment_deadline_seconds(bill_keys)?;
+        match self.get_last_version_block_with_op_code(BillOpCode::OfferToSell) {
+            Some(offer_to_sell_block) => {
+                let block_data_decrypted: BillOfferToSellBlockData =
+                    offer_to_sell_block.get_decrypted_block_bytes(bill_keys)?;
+                Ok(block_data_decrypted.get_sell_deadline_seconds(payment_deadline_seconds))
+            }
+            None => Ok(payment_deadline_seconds),
+        }
+    }
+
+    /// Returns the files that were attached to the bill after issuance, in the order of the
+    /// AttachFile blocks they were recorded in
+    pub fn get_attached_files(&self, bill_keys: &BillKeys) -> Result<Vec<File>> {
+        let mut files = vec![];
+        for block in self
+            .blocks
+            .iter()
+            .filter(|block| block.op_code == BillOpCode::AttachFile)
+        {
+            let block_data_decrypted: BillAttachFileBlockData =
+                block.get_decrypted_block_bytes(bill_keys)?;
+            files.extend(block_data_decrypted.files);
+        }
+        Ok(files)
+    }
+
+    /// Returns who acknowledged the bill, in the order of the Acknowledge blocks
+    pub fn get_acknowledgements(&self, bill_keys: &BillKeys) -> Result<Vec<BillAcknowledgement>> {
+        let mut acknowledgements = vec![];
+        for block in self
+            .blocks
+            .iter()
+            .filter(|block| block.op_code == BillOpCode::Acknowledge)
+        {
+            let block_data_decrypted: BillAcknowledgeBlockData =
+                block.get_decrypted_block_bytes(bill_keys)?;
+            acknowledgements.push(BillAcknowledgement {
+                node_id: block_data_decrypted.acknowledger.node_id,
+                name: block_data_decrypted.acknowledger.name,
+                timestamp: block_data_decrypted.signing_timestamp,
+            });
+        }
+        Ok(acknowledgements)
+    }
+
+    /// Returns the reference of the external payment the holder attested, if the bill was marked
+    /// as paid externally
+    pub fn get_external_payment_reference(&self, bill_keys: &BillKeys) -> Result<Option<String>> {
+        match self.get_last_version_block_with_op_code(BillOpCode::ExternalPayment) {
+            Some(block) => {
+                let block_data_decrypted: BillExternalPaymentBlockData =
+                    block.get_decrypted_block_bytes(bill_keys)?;
+                Ok(Some(block_data_decrypted.reference))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the reason given in the last rejection block with the given op code, if there is
+    /// such a block and the rejecter gave a reason
+    pub fn get_reject_reason(
+        &self,
+        bill_keys: &BillKeys,
+        op_code: BillOpCode,
+    ) -> Result<Option<String>> {
+        match self.get_last_version_block_with_op_code(op_code) {
+            Some(block) => {
+                let block_data_decrypted: BillRejectBlockData =
+                    block.get_decrypted_block_bytes(bill_keys)?;
+                Ok(block_data_decrypted.reason)
+            }
+            None => Ok(None),
+        }
+    }
+
     /// This function iterates over all the blocks in the blockchain, extracts the nodes
     /// from each block, and compiles a unique list of nodes.
     ///
@@ -495,6 +783,37 @@ impl BillBlockchain {
         Ok(nodes)
     }
 
+    /// Returns the display data of all participants of this chain, in the order they were
+    /// added, as embedded in the latest block each of them appears in
+    pub fn get_resolved_participants(
+        &self,
+        bill_keys: &BillKeys,
+    ) -> Result<Vec<ResolvedParticipant>> {
+        let mut participants: Vec<ResolvedParticipant> = vec![];
+        for (height, block) in self.blocks.iter().enumerate() {
+            for data in block.get_participants_from_block(bill_keys)? {
+                if data.node_id.is_empty() {
+                    continue;
+                }
+                let resolved = ResolvedParticipant {
+                    t: data.t,
+                    node_id: data.node_id,
+                    name: data.name,
+                    postal_address: data.postal_address,
+                    block_height: height as u64 + 1,
+                };
+                match participants
+                    .iter_mut()
+                    .find(|p| p.node_id == resolved.node_id)
+                {
+                    Some(existing) => *existing = resolved,
+                    None => participants.push(resolved),
+                }
+            }
+        }
+        Ok(participants)
+    }
+
     pub fn get_past_endorsees_for_bill(
         &self,
         bill_keys: &BillKeys,
@@ -575,7 +894,7 @@ impl BillBlockchain {
 
         // sort by signing timestamp descending
         let mut list: Vec<PastEndorsee> = result.into_values().collect();
-        list.sort_by(|a, b| b.signing_timestamp.cmp(&a.signing_timestamp));
+        list.sort_by_key(|e| std::cmp::Reverse(e.signing_timestamp));
 
         Ok(list)
     }
@@ -647,8 +966,18 @@ impl BillBlockchain {
         .max_by_key(|(id, _)| *id)
         .map(|b| b.1);
 
+        // the drawer can correct the drawee, before the bill progressed
+        let drawee = match self.get_last_version_block_with_op_code(BillOpCode::CorrectDrawee) {
+            Some(correct_drawee_block_encrypted) => {
+                correct_drawee_block_encrypted
+                    .get_decrypted_block_bytes::<BillCorrectDraweeBlockData>(bill_keys)?
+                    .drawee
+            }
+            None => bill_first_version.drawee.to_owned(),
+        };
+
         Ok(BillParties {
-            drawee: bill_first_version.drawee.to_owned(),
+            drawee,
             drawer: bill_first_version.drawer.to_owned(),
             payee: bill_first_version.payee.to_owned(),
             endorsee: last_endorsee,
@@ -672,6 +1001,20 @@ mod tests {
         buyer_node_id: String,
         seller_node_id: String,
         previous_block: &BillBlock,
+    ) -> BillBlock {
+        get_offer_to_sell_block_with_sell_deadline(
+            buyer_node_id,
+            seller_node_id,
+            previous_block,
+            None,
+        )
+    }
+
+    fn get_offer_to_sell_block_with_sell_deadline(
+        buyer_node_id: String,
+        seller_node_id: String,
+        previous_block: &BillBlock,
+        sell_deadline_seconds: Option<u64>,
     ) -> BillBlock {
         let buyer = identity_public_data_only_node_id(buyer_node_id);
         let seller = identity_public_data_only_node_id(seller_node_id);
@@ -688,6 +1031,7 @@ mod tests {
                 signatory: None,
                 signing_timestamp: 1731593928,
                 signing_address: seller.postal_address,
+                sell_deadline_seconds,
             },
             &get_baseline_identity().key_pair,
             None,
@@ -762,6 +1106,110 @@ mod tests {
         assert_eq!(result.as_ref().unwrap(), &OfferToSellWaitingForPayment::No);
     }
 
+    #[test]
+    fn is_last_sell_block_waiting_for_payment_uses_bill_payment_deadline() {
+        let mut bill = empty_bitcredit_bill();
+        bill.payment_deadline_seconds = 86400 * 14;
+        let identity = get_baseline_identity();
+
+        let mut chain = BillBlockchain::new(
+            &BillIssueBlockData::from(bill, None, 1731593928),
+            identity.key_pair,
+            None,
+            BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593928,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(get_offer_to_sell_block(
+            BcrKeys::new().get_public_key(),
+            identity.identity.node_id,
+            chain.get_first_block()
+        ),));
+
+        let keys = get_bill_keys();
+        // the global deadline passed, but the bill's deadline didn't
+        let result = chain.is_last_offer_to_sell_block_waiting_for_payment(
+            &keys,
+            1731593928 + PAYMENT_DEADLINE_SECONDS + 1,
+        );
+        assert!(result.is_ok());
+        assert!(matches!(
+            result.as_ref().unwrap(),
+            OfferToSellWaitingForPayment::Yes(_)
+        ));
+    }
+
+    #[test]
+    fn is_last_sell_block_waiting_for_payment_uses_sell_deadline() {
+        let identity = get_baseline_identity();
+        let keys = get_bill_keys();
+        let after_payment_deadline = 1731593928 + PAYMENT_DEADLINE_SECONDS + 1;
+        let chain_with_offer = |sell_deadline_seconds: Option<u64>| {
+            let mut chain = BillBlockchain::new(
+                &BillIssueBlockData::from(empty_bitcredit_bill(), None, 1731593928),
+                identity.key_pair.clone(),
+                None,
+                BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+                1731593928,
+            )
+            .unwrap();
+            assert!(
+                chain.try_add_block(get_offer_to_sell_block_with_sell_deadline(
+                    BcrKeys::new().get_public_key(),
+                    identity.identity.node_id.clone(),
+                    chain.get_first_block(),
+                    sell_deadline_seconds,
+                ))
+            );
+            chain
+        };
+
+        // without a sell deadline, the offer expires with the payment deadline
+        let chain = chain_with_offer(None);
+        assert_eq!(
+            chain.get_sell_deadline_seconds(&keys).unwrap(),
+            PAYMENT_DEADLINE_SECONDS
+        );
+        assert_eq!(
+            chain
+                .is_last_offer_to_sell_block_waiting_for_payment(&keys, after_payment_deadline)
+                .unwrap(),
+            OfferToSellWaitingForPayment::No
+        );
+
+        // with a longer sell deadline recorded with the offer, it's still waiting for payment
+        let chain = chain_with_offer(Some(86400 * 30));
+        assert_eq!(chain.get_sell_deadline_seconds(&keys).unwrap(), 86400 * 30);
+        assert!(matches!(
+            chain
+                .is_last_offer_to_sell_block_waiting_for_payment(&keys, after_payment_deadline)
+                .unwrap(),
+            OfferToSellWaitingForPayment::Yes(_)
+        ));
+    }
+
+    #[test]
+    fn get_payment_deadline_seconds_falls_back_to_default() {
+        let bill = empty_bitcredit_bill();
+        let identity = get_baseline_identity();
+
+        let chain = BillBlockchain::new(
+            &BillIssueBlockData {
+                payment_deadline_seconds: None,
+                ..BillIssueBlockData::from(bill, None, 1731593928)
+            },
+            identity.key_pair,
+            None,
+            BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593928,
+        )
+        .unwrap();
+
+        let result = chain.get_payment_deadline_seconds(&get_bill_keys());
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), PAYMENT_DEADLINE_SECONDS);
+    }
+
     #[test]
     fn is_last_sell_block_waiting_for_payment_baseline() {
         let bill = empty_bitcredit_bill();
@@ -794,6 +1242,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn get_reject_reason_returns_reason_of_last_reject_block() {
+        let bill = empty_bitcredit_bill();
+        let identity = get_baseline_identity();
+
+        let mut chain = BillBlockchain::new(
+            &BillIssueBlockData::from(bill, None, 1731593928),
+            identity.key_pair.clone(),
+            None,
+            BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593928,
+        )
+        .unwrap();
+        let buyer_node_id = BcrKeys::new().get_public_key();
+        assert!(chain.try_add_block(get_offer_to_sell_block(
+            buyer_node_id.clone(),
+            identity.identity.node_id.clone(),
+            chain.get_first_block()
+        ),));
+        let rejecter = identity_public_data_only_node_id(buyer_node_id);
+        let reject_block = BillBlock::create_block_for_reject_to_buy(
+            TEST_BILL_ID.to_string(),
+            chain.get_latest_block(),
+            &BillRejectBlockData {
+                rejecter: rejecter.clone().into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: rejecter.postal_address,
+                reason: Some("price too high".to_string()),
+            },
+            &identity.key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(reject_block));
+
+        let keys = get_bill_keys();
+        assert_eq!(
+            chain
+                .get_reject_reason(&keys, BillOpCode::RejectToBuy)
+                .unwrap(),
+            Some("price too high".to_string())
+        );
+        assert_eq!(
+            chain
+                .get_reject_reason(&keys, BillOpCode::RejectToAccept)
+                .unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn get_all_nodes_from_bill_baseline() {
         let mut bill = empty_bitcredit_bill();
@@ -834,6 +1335,48 @@ mod tests {
         assert_eq!(result.as_ref().unwrap().len(), 3); // drawer, buyer, seller
     }
 
+    #[test]
+    fn get_resolved_participants_baseline() {
+        let mut bill = empty_bitcredit_bill();
+        let identity = get_baseline_identity();
+        bill.drawer = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        bill.drawee = IdentityPublicData::new(identity.identity.clone()).unwrap();
+        bill.payee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        bill.payee.name = "payee name".to_string();
+
+        let mut chain = BillBlockchain::new(
+            &BillIssueBlockData::from(bill.clone(), None, 1731593928),
+            identity.key_pair,
+            None,
+            BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593928,
+        )
+        .unwrap();
+        let node_id_buyer = BcrKeys::new().get_public_key();
+        assert!(chain.try_add_block(get_offer_to_sell_block(
+            node_id_buyer.clone(),
+            identity.identity.node_id.to_owned(),
+            chain.get_first_block()
+        ),));
+
+        let result = chain.get_resolved_participants(&get_bill_keys()).unwrap();
+        assert_eq!(result.len(), 3);
+        let payee = result
+            .iter()
+            .find(|p| p.node_id == bill.payee.node_id)
+            .unwrap();
+        assert_eq!(payee.name, "payee name");
+        assert_eq!(payee.block_height, 1);
+        // the drawer appears in both blocks, the data is taken from the latest one
+        let drawer = result
+            .iter()
+            .find(|p| p.node_id == identity.identity.node_id)
+            .unwrap();
+        assert_eq!(drawer.name, "some name");
+        assert_eq!(drawer.block_height, 2);
+        assert!(result.iter().any(|p| p.node_id == node_id_buyer));
+    }
+
     #[test]
     fn get_blocks_to_add_from_other_chain_no_changes() {
         let bill = empty_bitcredit_bill();
@@ -887,4 +1430,320 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].id, 2);
     }
+
+    fn get_chain_for_sync() -> BillBlockchain {
+        BillBlockchain::new(
+            &BillIssueBlockData::from(empty_bitcredit_bill(), None, 1731593928),
+            get_baseline_identity().key_pair,
+            None,
+            BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593928,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn get_missing_blocks_fast_forward() {
+        let seller = get_baseline_identity().identity.node_id;
+        let local_chain = get_chain_for_sync();
+        let mut remote_chain = local_chain.clone();
+        for _ in 0..2 {
+            let block = get_offer_to_sell_block(
+                BcrKeys::new().get_public_key(),
+                seller.clone(),
+                remote_chain.get_latest_block(),
+            );
+            assert!(remote_chain.try_add_block(block));
+        }
+        let remote_latest = remote_chain.get_latest_block();
+
+        // works with the full remote chain, as well as only the missing blocks
+        let result = local_chain
+            .get_missing_blocks(&remote_latest.hash, remote_latest.id, remote_chain.blocks())
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, 2);
+        assert_eq!(result[1].id, 3);
+
+        let result = local_chain
+            .get_missing_blocks(
+                &remote_latest.hash,
+                remote_latest.id,
+                &remote_chain.blocks()[1..],
+            )
+            .unwrap();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn get_missing_blocks_already_in_sync() {
+        let local_chain = get_chain_for_sync();
+        let latest = local_chain.get_latest_block();
+
+        let result = local_chain
+            .get_missing_blocks(&latest.hash, latest.id, &[])
+            .unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn get_missing_blocks_rejects_divergent_fork() {
+        let seller = get_baseline_identity().identity.node_id;
+        let mut local_chain = get_chain_for_sync();
+        let mut remote_chain = local_chain.clone();
+        assert!(local_chain.try_add_block(get_offer_to_sell_block(
+            BcrKeys::new().get_public_key(),
+            seller.clone(),
+            local_chain.get_latest_block(),
+        )));
+        for _ in 0..2 {
+            let block = get_offer_to_sell_block(
+                BcrKeys::new().get_public_key(),
+                seller.clone(),
+                remote_chain.get_latest_block(),
+            );
+            assert!(remote_chain.try_add_block(block));
+        }
+        let remote_latest = remote_chain.get_latest_block();
+
+        // the remote chain diverges at block 2
+        let result = local_chain.get_missing_blocks(
+            &remote_latest.hash,
+            remote_latest.id,
+            remote_chain.blocks(),
+        );
+        assert!(matches!(result, Err(Error::BlockchainFork)));
+
+        // only sending the blocks after the local tip doesn't link to the local tip
+        let result = local_chain.get_missing_blocks(
+            &remote_latest.hash,
+            remote_latest.id,
+            &remote_chain.blocks()[2..],
+        );
+        assert!(matches!(result, Err(Error::BlockchainFork)));
+
+        // a remote chain at the same height with a different tip
+        let remote_block = &remote_chain.blocks()[1];
+        let result = local_chain.get_missing_blocks(&remote_block.hash, remote_block.id, &[]);
+        assert!(matches!(result, Err(Error::BlockchainFork)));
+    }
+
+    #[test]
+    fn get_missing_blocks_rejects_gap() {
+        let seller = get_baseline_identity().identity.node_id;
+        let local_chain = get_chain_for_sync();
+        let mut remote_chain = local_chain.clone();
+        for _ in 0..2 {
+            let block = get_offer_to_sell_block(
+                BcrKeys::new().get_public_key(),
+                seller.clone(),
+                remote_chain.get_latest_block(),
+            );
+            assert!(remote_chain.try_add_block(block));
+        }
+        let remote_latest = remote_chain.get_latest_block();
+
+        let result = local_chain.get_missing_blocks(
+            &remote_latest.hash,
+            remote_latest.id,
+            &remote_chain.blocks()[2..],
+        );
+        assert!(matches!(result, Err(Error::BlockchainInvalid)));
+    }
+
+    #[test]
+    fn get_bill_parties_uses_corrected_drawee() {
+        let bill = empty_bitcredit_bill();
+        let identity = get_baseline_identity();
+        let bill_keys = get_bill_keys();
+        let mut chain = BillBlockchain::new(
+            &BillIssueBlockData::from(bill.clone(), None, 1731593928),
+            identity.key_pair.clone(),
+            None,
+            BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593928,
+        )
+        .unwrap();
+        let first_version = chain.get_first_version_bill(&bill_keys).unwrap();
+        assert_eq!(
+            chain
+                .get_bill_parties(&bill_keys, &first_version)
+                .unwrap()
+                .drawee
+                .node_id,
+            bill.drawee.node_id
+        );
+
+        let new_drawee = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let block = BillBlock::create_block_for_correct_drawee(
+            TEST_BILL_ID.to_string(),
+            chain.get_latest_block(),
+            &BillCorrectDraweeBlockData {
+                drawer: bill.drawer.clone().into(),
+                drawee: new_drawee.clone().into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: bill.drawer.postal_address.clone(),
+            },
+            &identity.key_pair,
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            1731593929,
+        )
+        .unwrap();
+        assert!(chain.try_add_block(block));
+
+        let parties = chain.get_bill_parties(&bill_keys, &first_version).unwrap();
+        assert_eq!(parties.drawee.node_id, new_drawee.node_id);
+        // the first version still has the drawee the bill was issued with
+        assert_eq!(first_version.drawee.node_id, bill.drawee.node_id);
+        assert!(
+            chain
+                .get_all_nodes_from_bill(&bill_keys)
+                .unwrap()
+                .contains(&new_drawee.node_id)
+        );
+    }
+
+    #[test]
+    fn new_from_blocks_rejects_too_long_chain() {
+        let chain = get_chain_for_sync();
+        // rejected before the blocks are validated
+        let blocks = vec![chain.get_first_block().clone(); 3];
+        let result = BillBlockchain::new_from_blocks_with_max(blocks, 2);
+        assert!(matches!(result, Err(Error::BlockchainTooLong(2))));
+    }
+
+    #[test]
+    fn try_add_block_rejects_block_above_max_blocks() {
+        let first_block = get_chain_for_sync().get_first_block().clone();
+        let mut chain = BillBlockchain {
+            blocks: vec![first_block.clone(); 2],
+            max_blocks: None,
+        }
+        .with_max_blocks(2);
+        let block = get_offer_to_sell_block(
+            BcrKeys::new().get_public_key(),
+            get_baseline_identity().identity.node_id,
+            &first_block,
+        );
+        assert!(!chain.try_add_block(block));
+        assert_eq!(chain.block_height(), 2);
+    }
+
+    fn get_blocks_to_verify() -> Vec<BillBlock> {
+        let identity = get_baseline_identity();
+        let signer = identity_public_data_only_node_id(identity.identity.node_id.clone());
+        let other_party = identity_public_data_only_node_id(BcrKeys::new().get_public_key());
+        let mut bill = empty_bitcredit_bill();
+        bill.drawer = signer.clone();
+        bill.drawee = signer.clone();
+        bill.payee = other_party.clone();
+        let bill_keys = BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap();
+
+        let chain = BillBlockchain::new(
+            &BillIssueBlockData::from(bill, None, 1731593928),
+            identity.key_pair.clone(),
+            None,
+            bill_keys.clone(),
+            1731593928,
+        )
+        .unwrap();
+        let endorse_block = BillBlock::create_block_for_endorse(
+            TEST_BILL_ID.to_string(),
+            chain.get_first_block(),
+            &BillEndorseBlockData {
+                endorser: signer.clone().into(),
+                endorsee: other_party.into(),
+                signatory: None,
+                signing_timestamp: 1731593929,
+                signing_address: signer.postal_address,
+            },
+            &identity.key_pair,
+            None,
+            &bill_keys,
+            1731593929,
+        )
+        .unwrap();
+        vec![chain.get_first_block().clone(), endorse_block]
+    }
+
+    #[test]
+    fn verify_blocks_valid_chain() {
+        let report = BillBlockchain::verify_blocks(&get_blocks_to_verify(), &get_bill_keys());
+        assert!(report.is_valid());
+        assert_eq!(report.valid_blocks, 2);
+        assert_eq!(report.first_invalid_block_height, None);
+        assert_eq!(report.reason, None);
+    }
+
+    #[test]
+    fn verify_blocks_reports_tampered_hash() {
+        let mut blocks = get_blocks_to_verify();
+        blocks[1].hash = util::sha256_hash("tampered".as_bytes());
+        let report = BillBlockchain::verify_blocks(&blocks, &get_bill_keys());
+        assert!(!report.is_valid());
+        assert_eq!(report.valid_blocks, 1);
+        assert_eq!(report.first_invalid_block_height, Some(2));
+        assert_eq!(report.reason, Some(BlockValidationError::InvalidHash));
+    }
+
+    #[test]
+    fn verify_blocks_reports_broken_previous_hash_link() {
+        let mut blocks = get_blocks_to_verify();
+        blocks[1].previous_hash = util::sha256_hash("other".as_bytes());
+        let report = BillBlockchain::verify_blocks(&blocks, &get_bill_keys());
+        assert_eq!(report.valid_blocks, 1);
+        assert_eq!(report.first_invalid_block_height, Some(2));
+        assert_eq!(
+            report.reason,
+            Some(BlockValidationError::InvalidPreviousHash)
+        );
+    }
+
+    #[test]
+    fn verify_blocks_reports_undecryptable_block_data() {
+        let other_keys = BcrKeys::new();
+        let report = BillBlockchain::verify_blocks(
+            &get_blocks_to_verify(),
+            &BillKeys {
+                private_key: other_keys.get_private_key_string(),
+                public_key: other_keys.get_public_key(),
+            },
+        );
+        assert_eq!(report.valid_blocks, 0);
+        assert_eq!(report.first_invalid_block_height, Some(1));
+        assert_eq!(report.reason, Some(BlockValidationError::InvalidBlockData));
+    }
+
+    #[test]
+    fn new_from_valid_prefix_stops_at_corrupted_block() {
+        let mut blocks = get_blocks_to_verify();
+        let last_block = blocks[1].clone();
+        // corrupt the data of the block in the middle of the chain
+        blocks[1].data = util::base58_encode("corrupted".as_bytes());
+        blocks.push(last_block);
+        let (chain, report) =
+            BillBlockchain::new_from_valid_prefix(blocks, &get_bill_keys()).unwrap();
+        assert_eq!(chain.blocks().len(), 1);
+        assert_eq!(report.valid_blocks, 1);
+        assert_eq!(report.first_invalid_block_height, Some(2));
+    }
+
+    #[test]
+    fn new_from_valid_prefix_fails_for_corrupted_first_block() {
+        let mut blocks = get_blocks_to_verify();
+        blocks[0].data = util::base58_encode("corrupted".as_bytes());
+        let res = BillBlockchain::new_from_valid_prefix(blocks, &get_bill_keys());
+        assert!(matches!(res, Err(Error::CorruptedBlock(1))));
+    }
+
+    #[test]
+    fn new_from_valid_prefix_keeps_valid_chain() {
+        let (chain, report) =
+            BillBlockchain::new_from_valid_prefix(get_blocks_to_verify(), &get_bill_keys())
+                .unwrap();
+        assert_eq!(chain.blocks().len(), 2);
+        assert!(report.is_valid());
+    }
 }
@@ -26,6 +26,15 @@ pub enum BillOpCode {
     RejectToPayRecourse,
     RequestRecourse,
     Recourse,
+    AttachFile,
+    CancelOfferToSell,
+    CancelRequestToAccept,
+    ExternalPayment,
+    Acknowledge,
+    CorrectDrawee,
+    MintQuote,
+    ApproveMintQuote,
+    RejectMintQuote,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -40,6 +49,14 @@ pub enum RecourseWaitingForPayment {
     No,
 }
 
+/// A request to mint - the mint block, which transferred the bill from the requester to the mint
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintRequest {
+    pub block_id: u64,
+    pub requester: BillIdentityBlockData,
+    pub mint: BillIdentityBlockData,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PaymentInfo {
     pub buyer: BillIdentityBlockData,
@@ -6,7 +6,7 @@ use crate::{
     File, OptionalPostalAddress, PostalAddress,
     company::{Company, CompanyKeys},
 };
-use borsh::to_vec;
+use borsh::{from_slice, to_vec};
 use borsh_derive::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
@@ -126,6 +126,24 @@ pub struct CompanyRemoveSignatoryBlockData {
     pub signatory: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum SignatoryChangeType {
+    Add,
+    Remove,
+}
+
+/// A change of the signatories of a company, as recorded on the company chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatoryChange {
+    pub change_type: SignatoryChangeType,
+    /// The node id of the signatory that was added or removed
+    pub signatory: String,
+    /// The node id of the signatory who made the change
+    pub changed_by: String,
+    pub block_id: u64,
+    pub timestamp: u64,
+}
+
 impl Block for CompanyBlock {
     type OpCode = CompanyOpCode;
     type BlockDataToHash = CompanyBlockDataToHash;
@@ -351,6 +369,20 @@ impl CompanyBlock {
         Ok(block)
     }
 
+    /// Decrypts the block data using the company private key and deserializes it
+    pub fn get_decrypted_block_bytes<T: borsh::BorshDeserialize>(
+        &self,
+        company_keys: &CompanyKeys,
+    ) -> Result<T> {
+        let bytes = util::base58_decode(&self.data)?;
+        let block_data: CompanyBlockData = from_slice(&bytes)?;
+        let decoded_data_bytes = util::base58_decode(&block_data.data)?;
+        let decrypted_bytes =
+            util::crypto::decrypt_ecies(&decoded_data_bytes, &company_keys.private_key)?;
+        let deserialized = from_slice::<T>(&decrypted_bytes)?;
+        Ok(deserialized)
+    }
+
     fn encrypt_data_create_block_and_validate<T: borsh::BorshSerialize>(
         company_id: String,
         previous_block: &Self,
@@ -371,15 +403,15 @@ impl CompanyBlock {
         let mut key = None;
 
         // in case there are keys to encrypt, encrypt them using the receiver's identity pub key
-        if op_code == CompanyOpCode::AddSignatory {
-            if let Some(signatory_public_key) = public_key_for_keys {
-                let key_bytes = to_vec(&company_keys.private_key)?;
-                let encrypted_key = util::base58_encode(&util::crypto::encrypt_ecies(
-                    &key_bytes,
-                    signatory_public_key,
-                )?);
-                key = Some(encrypted_key);
-            }
+        if op_code == CompanyOpCode::AddSignatory
+            && let Some(signatory_public_key) = public_key_for_keys
+        {
+            let key_bytes = to_vec(&company_keys.private_key)?;
+            let encrypted_key = util::base58_encode(&util::crypto::encrypt_ecies(
+                &key_bytes,
+                signatory_public_key,
+            )?);
+            key = Some(encrypted_key);
         }
 
         let data = CompanyBlockData {
@@ -447,6 +479,43 @@ impl CompanyBlockchain {
         })
     }
 
+    /// Returns all changes of the signatories of the company in chain order - the signatories
+    /// set on creation are returned as added by the creator
+    pub fn get_signatory_changes(
+        &self,
+        company_keys: &CompanyKeys,
+    ) -> Result<Vec<SignatoryChange>> {
+        let mut changes = vec![];
+        for block in self.blocks.iter() {
+            let (change_type, signatories) = match block.op_code {
+                CompanyOpCode::Create => {
+                    let data: CompanyCreateBlockData =
+                        block.get_decrypted_block_bytes(company_keys)?;
+                    (SignatoryChangeType::Add, data.signatories)
+                }
+                CompanyOpCode::AddSignatory => {
+                    let data: CompanyAddSignatoryBlockData =
+                        block.get_decrypted_block_bytes(company_keys)?;
+                    (SignatoryChangeType::Add, vec![data.signatory])
+                }
+                CompanyOpCode::RemoveSignatory => {
+                    let data: CompanyRemoveSignatoryBlockData =
+                        block.get_decrypted_block_bytes(company_keys)?;
+                    (SignatoryChangeType::Remove, vec![data.signatory])
+                }
+                CompanyOpCode::Update | CompanyOpCode::SignCompanyBill => continue,
+            };
+            changes.extend(signatories.into_iter().map(|signatory| SignatoryChange {
+                change_type: change_type.clone(),
+                signatory,
+                changed_by: block.signatory_node_id.clone(),
+                block_id: block.id,
+                timestamp: block.timestamp,
+            }));
+        }
+        Ok(changes)
+    }
+
     /// Creates a company chain from a vec of blocks
     pub fn new_from_blocks(blocks_to_add: Vec<CompanyBlock>) -> Result<Self> {
         match blocks_to_add.first() {
@@ -598,6 +667,27 @@ mod tests {
         assert_eq!(chain.blocks().len(), 5);
         assert!(chain.is_chain_valid());
 
+        let signatory_changes = chain.get_signatory_changes(&company_keys).unwrap();
+        assert_eq!(signatory_changes.len(), 3);
+        assert_eq!(signatory_changes[0].change_type, SignatoryChangeType::Add);
+        assert_eq!(
+            signatory_changes[0].signatory,
+            TEST_PUB_KEY_SECP.to_string()
+        );
+        assert_eq!(signatory_changes[1].change_type, SignatoryChangeType::Add);
+        assert_eq!(signatory_changes[1].signatory, "some_signatory".to_string());
+        assert_eq!(
+            signatory_changes[1].changed_by,
+            identity_keys.get_public_key()
+        );
+        assert_eq!(signatory_changes[1].timestamp, 1731593931);
+        assert_eq!(
+            signatory_changes[2].change_type,
+            SignatoryChangeType::Remove
+        );
+        assert_eq!(signatory_changes[2].signatory, "some_signatory".to_string());
+        assert_eq!(signatory_changes[2].block_id, 5);
+
         let new_chain_from_empty_blocks = CompanyBlockchain::new_from_blocks(vec![]);
         assert!(new_chain_from_empty_blocks.is_err());
 
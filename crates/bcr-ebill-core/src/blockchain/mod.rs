@@ -4,6 +4,7 @@ use crate::util::crypto;
 use crate::{ValidationError, util};
 use borsh::{BorshSerialize, to_vec};
 use log::{error, warn};
+use serde::{Deserialize, Serialize};
 use std::string::FromUtf8Error;
 
 pub mod bill;
@@ -26,6 +27,18 @@ pub enum Error {
     #[error("Blockchain is invalid")]
     BlockchainInvalid,
 
+    /// If a remote chain diverges from the local chain before the local chain's latest block
+    #[error("Remote blockchain diverges from the local blockchain")]
+    BlockchainFork,
+
+    /// If a block of a stored chain can't be read anymore, e.g. due to disk corruption
+    #[error("Block at height {0} is corrupted")]
+    CorruptedBlock(u64),
+
+    /// If a chain has more blocks than the given maximum
+    #[error("Blockchain exceeds the maximum of {0} blocks")]
+    BlockchainTooLong(usize),
+
     /// If there is an error deserializing and decrypting blocks
     #[error("Could not parse and decrypt blockchain")]
     BlockchainParse,
@@ -64,6 +77,42 @@ pub enum Error {
     InvalidBlockdata(String),
 }
 
+/// The reason a block failed validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockValidationError {
+    /// The block's hash doesn't match the hash of the previous block
+    InvalidPreviousHash,
+    /// The block's timestamp is lower than the one of the previous block
+    InvalidTimestamp,
+    /// The block's id doesn't follow the id of the previous block
+    InvalidBlockId,
+    /// The block's hash doesn't match its contents
+    InvalidHash,
+    /// The block's signature doesn't match its hash and public key
+    InvalidSignature,
+    /// The block's data can't be decrypted, or is invalid
+    InvalidBlockData,
+    /// The block's signer doesn't match the signer in the block data
+    SignerMismatch,
+}
+
+/// The result of verifying a chain block by block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainVerificationReport {
+    /// The number of valid blocks before the first invalid one
+    pub valid_blocks: u64,
+    /// The height of the first invalid block, if there is one
+    pub first_invalid_block_height: Option<u64>,
+    /// The reason the first invalid block is invalid, if there is one
+    pub reason: Option<BlockValidationError>,
+}
+
+impl ChainVerificationReport {
+    pub fn is_valid(&self) -> bool {
+        self.first_invalid_block_height.is_none()
+    }
+}
+
 /// Generic trait for a Block within a Blockchain
 pub trait Block {
     type OpCode: PartialEq + Clone + BorshSerialize;
@@ -108,33 +157,47 @@ pub trait Block {
         }
     }
 
-    /// Validates the block with a given previous block
-    fn validate_with_previous(&self, previous_block: &Self) -> bool {
+    /// Checks the block's hash and signature, returning the reason if the block is invalid
+    fn check(&self) -> std::result::Result<(), BlockValidationError> {
+        if !self.validate_hash() {
+            warn!("block with id: {} has invalid hash", self.id());
+            return Err(BlockValidationError::InvalidHash);
+        } else if !self.verify() {
+            warn!("block with id: {} has invalid signature", self.id());
+            return Err(BlockValidationError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// Checks the block with a given previous block, returning the reason if the block is invalid
+    fn check_with_previous(
+        &self,
+        previous_block: &Self,
+    ) -> std::result::Result<(), BlockValidationError> {
         if self.previous_hash() != previous_block.hash() {
             warn!("block with id: {} has wrong previous hash", self.id());
-            return false;
+            return Err(BlockValidationError::InvalidPreviousHash);
         } else if self.timestamp() < previous_block.timestamp() {
             warn!(
                 "block with id: {} has a timestamp lower than the previous block: {}",
                 self.id(),
                 previous_block.timestamp()
             );
-            return false;
+            return Err(BlockValidationError::InvalidTimestamp);
         } else if self.id() != previous_block.id() + 1 {
             warn!(
                 "block with id: {} is not the next block after the previous block: {}",
                 self.id(),
                 previous_block.id()
             );
-            return false;
-        } else if !self.validate_hash() {
-            warn!("block with id: {} has invalid hash", self.id());
-            return false;
-        } else if !self.verify() {
-            warn!("block with id: {} has invalid signature", self.id());
-            return false;
+            return Err(BlockValidationError::InvalidBlockId);
         }
-        true
+        self.check()
+    }
+
+    /// Validates the block with a given previous block
+    fn validate_with_previous(&self, previous_block: &Self) -> bool {
+        self.check_with_previous(previous_block).is_ok()
     }
 }
 
@@ -151,6 +214,11 @@ pub trait Blockchain {
         self.blocks().len()
     }
 
+    /// returns the maximum number of blocks of this blockchain, if it's limited
+    fn max_blocks(&self) -> Option<usize> {
+        None
+    }
+
     /// Validates the integrity of the blockchain by checking the validity of each block in the chain.
     fn is_chain_valid(&self) -> bool {
         let blocks = self.blocks();
@@ -177,6 +245,12 @@ pub trait Blockchain {
     /// * `false` if the block was invalid and could not be added.
     ///
     fn try_add_block(&mut self, block: Self::Block) -> bool {
+        if let Some(max_blocks) = self.max_blocks()
+            && self.block_height() >= max_blocks
+        {
+            error!("could not add block - chain has the maximum of {max_blocks} blocks");
+            return false;
+        }
         let latest_block = self.get_latest_block();
         if block.validate_with_previous(latest_block) {
             self.blocks_mut().push(block);
@@ -223,6 +297,92 @@ pub trait Blockchain {
         blocks_to_add
     }
 
+    /// Returns the blocks of a remote chain, which are missing in the local chain, given the
+    /// remote chain's latest block hash and height and the remote blocks received.
+    /// The remote blocks don't have to start at the genesis block - blocks the local chain already
+    /// has are compared by hash, to find the common ancestor. If the remote chain diverges before
+    /// the local chain's latest block, it's a fork and the blocks are rejected.
+    fn get_missing_blocks(
+        &self,
+        remote_latest_hash: &str,
+        remote_height: u64,
+        remote_blocks: &[Self::Block],
+    ) -> Result<Vec<Self::Block>> {
+        let local_latest_block = self.get_latest_block();
+
+        // the remote chain is not ahead - it has to contain the same block at its height
+        if remote_height <= local_latest_block.id() {
+            return match self.blocks().iter().find(|b| b.id() == remote_height) {
+                Some(block) if block.hash() == remote_latest_hash => Ok(vec![]),
+                _ => {
+                    warn!("remote chain with height {remote_height} diverges from local chain");
+                    Err(Error::BlockchainFork)
+                }
+            };
+        }
+
+        // the received blocks have to end with the announced latest block
+        match remote_blocks.last() {
+            Some(last) if last.id() == remote_height && last.hash() == remote_latest_hash => (),
+            _ => {
+                warn!("received blocks don't match remote chain height {remote_height}");
+                return Err(Error::BlockchainInvalid);
+            }
+        }
+
+        // blocks we already have have to be identical to ours
+        for remote_block in remote_blocks
+            .iter()
+            .filter(|b| b.id() <= local_latest_block.id())
+        {
+            match self.blocks().iter().find(|b| b.id() == remote_block.id()) {
+                Some(local_block) if local_block.hash() == remote_block.hash() => (),
+                _ => {
+                    warn!(
+                        "remote block with id: {} diverges from local chain",
+                        remote_block.id()
+                    );
+                    return Err(Error::BlockchainFork);
+                }
+            }
+        }
+
+        let missing_blocks: Vec<Self::Block> = remote_blocks
+            .iter()
+            .filter(|b| b.id() > local_latest_block.id())
+            .cloned()
+            .collect();
+
+        // there must not be a gap between our latest block and the first missing block
+        if missing_blocks
+            .first()
+            .is_some_and(|b| b.id() != local_latest_block.id() + 1)
+        {
+            warn!("received blocks don't connect to local chain height");
+            return Err(Error::BlockchainInvalid);
+        }
+
+        // the first missing block has to link to our latest block, the rest to their predecessor
+        let mut previous_block = local_latest_block;
+        for block in missing_blocks.iter() {
+            match block.check_with_previous(previous_block) {
+                Ok(()) => (),
+                Err(BlockValidationError::InvalidPreviousHash)
+                    if previous_block.id() == local_latest_block.id() =>
+                {
+                    warn!(
+                        "remote block with id: {} doesn't link to local chain",
+                        block.id()
+                    );
+                    return Err(Error::BlockchainFork);
+                }
+                Err(_) => return Err(Error::BlockchainInvalid),
+            }
+            previous_block = block;
+        }
+        Ok(missing_blocks)
+    }
+
     /// Retrieves the last block with the specified op code, or None if the block is not in the
     /// chain
     fn get_last_version_block_with_op_code(
@@ -231,8 +391,7 @@ pub trait Blockchain {
     ) -> Option<&Self::Block> {
         self.blocks()
             .iter()
-            .filter(|block| block.op_code() == &op_code)
-            .next_back()
+            .rfind(|block| block.op_code() == &op_code)
     }
 
     /// Checks if there is any block with a given operation code in the current blocks list.
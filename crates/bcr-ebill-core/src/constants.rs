@@ -1,5 +1,13 @@
 pub const PAYMENT_DEADLINE_SECONDS: u64 = 86400 * 2; // 2 days
 pub const ACCEPT_DEADLINE_SECONDS: u64 = 86400 * 2; // 2 days
 pub const RECOURSE_DEADLINE_SECONDS: u64 = 86400 * 2; // 2 days
+// Bills can't be issued with a longer payment deadline, so it can't overflow deadline timestamps
+pub const MAX_PAYMENT_DEADLINE_SECONDS: u64 = 86400 * 365; // 1 year
+// Bill chains with more blocks are rejected, to prevent resource exhaustion by malicious peers
+pub const DEFAULT_MAX_BILL_CHAIN_BLOCKS: usize = 10_000;
+// The reason of a rejection is recorded in the bill chain, so its length is limited
+pub const MAX_REJECT_REASON_CHARACTERS: usize = 500;
 //
 pub const VALID_CURRENCIES: [&str; 1] = ["sat"];
+// Bills can be issued in these fiat currencies - the sat equivalent is recorded at issuance
+pub const VALID_FIAT_CURRENCIES: [&str; 3] = ["eur", "usd", "chf"];
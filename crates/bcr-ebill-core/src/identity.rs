@@ -47,6 +47,15 @@ impl Identity {
     }
 }
 
+/// An identity the local user controls - the personal identity, or a company they're a
+/// signatory of
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ControlledIdentity {
+    pub node_id: String,
+    pub t: IdentityType,
+    pub name: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct ActiveIdentityState {
     pub personal: String,
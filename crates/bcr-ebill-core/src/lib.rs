@@ -163,6 +163,9 @@ pub enum Field {
     CountryOfPayment,
     CityOfPayment,
     Language,
+    HoldReason,
+    Files,
+    ExternalPaymentReference,
 }
 
 /// Generic validation error type
@@ -176,6 +179,10 @@ pub enum ValidationError {
     #[error("invalid sum")]
     InvalidSum,
 
+    /// error returned if the payment deadline of a bill was invalid
+    #[error("invalid payment deadline")]
+    InvalidPaymentDeadline,
+
     /// error returned if the date was invalid
     #[error("invalid date")]
     InvalidDate,
@@ -192,6 +199,14 @@ pub enum ValidationError {
     #[error("invalid currency")]
     InvalidCurrency,
 
+    /// error returned if a country is not an ISO 3166-1 alpha-2 country code
+    #[error("invalid country code: {0}")]
+    InvalidCountryCode(String),
+
+    /// error returned if no BTC rate could be fetched for the fiat currency of a bill to issue
+    #[error("no BTC rate could be fetched for the fiat currency")]
+    FiatRateUnavailable,
+
     /// error returned if the bitcoin address
     #[error("invalid payment address")]
     InvalidPaymentAddress,
@@ -200,6 +215,10 @@ pub enum ValidationError {
     #[error("invalid file upload id")]
     InvalidFileUploadId,
 
+    /// error returned if the cursor for paging notifications was invalid
+    #[error("invalid notification cursor")]
+    InvalidNotificationCursor,
+
     /// errors stemming from providing an invalid bill type
     #[error("invalid bill type")]
     InvalidBillType,
@@ -282,6 +301,51 @@ pub enum ValidationError {
     #[error("Caller is not the buyer and can't reject to buy")]
     CallerIsNotBuyer,
 
+    /// error returned if the caller of a cancel offer to sell operation is not the seller
+    #[error("Caller is not the seller and can't cancel the offer to sell")]
+    CallerIsNotSeller,
+
+    /// error returned if the caller of a cancel request to accept operation is not the requester
+    #[error("Caller is not the requester and can't cancel the request to accept")]
+    CallerIsNotRequester,
+
+    /// error returned if the caller of an acknowledge operation is neither the holder, nor the
+    /// drawee of the bill
+    #[error("Caller is neither holder nor drawee and can't acknowledge the bill")]
+    CallerIsNotHolderOrDrawee,
+
+    /// error returned if the caller of an operation is not the drawer, but would have to be for it
+    /// to be valid, e.g. correcting the drawee
+    #[error("Caller is not drawer")]
+    CallerIsNotDrawer,
+
+    /// error returned if the drawee is corrected after the bill progressed, e.g. after it was
+    /// requested to accept, or endorsed
+    #[error("The drawee can only be corrected before the bill progressed")]
+    DraweeCorrectionNotAllowed,
+
+    /// error returned if a quote is submitted for a bill, which is not waiting for a quote of a
+    /// mint
+    #[error("Bill was not requested to mint")]
+    BillWasNotRequestedToMint,
+
+    /// error returned if the caller submitting a quote is not the mint the bill was requested to
+    /// mint from
+    #[error("Caller is not the mint and can't submit a quote")]
+    CallerIsNotMint,
+
+    /// error returned if a quote is approved, or rejected, but there is no open quote of a mint
+    #[error("Bill has no open mint quote")]
+    BillHasNoOpenMintQuote,
+
+    /// error returned if the caller approving, or rejecting a quote did not request to mint
+    #[error("Caller did not request to mint and can't answer the quote")]
+    CallerIsNotMintRequester,
+
+    /// error returned if a quote is approved after it expired, or submitted already expired
+    #[error("The mint quote is expired")]
+    MintQuoteExpired,
+
     /// error returned if the caller of a reject operation trys to reject a request that is already
     /// expired
     #[error("The request already expired")]
@@ -384,6 +448,11 @@ pub enum ValidationError {
     #[error("Node id {0} is not a signatory.")]
     NotASignatory(String),
 
+    /// error returned if a signatory should be recorded in a block, whose keys aren't available
+    /// to sign the block with
+    #[error("Node id {0} can't sign - only the local identity can be recorded as signatory.")]
+    SignatoryCantSign(String),
+
     /// error returned if the given secp256k1 key is not valid
     #[error("Not a valid secp256k1 key: {0}")]
     InvalidSecp256k1Key(String),
@@ -392,10 +461,26 @@ pub enum ValidationError {
     #[error("Maximum file size is {0} bytes")]
     FileIsTooBig(usize),
 
+    /// error returned if a bill would have more files than allowed
+    #[error("A bill can have at most {0} files")]
+    TooManyFiles(usize),
+
+    /// error returned if the files of a bill would be bigger than allowed in total
+    #[error("The files of a bill can have at most {0} bytes in total")]
+    AttachmentsTooBig(usize),
+
     /// error returned if the file name is wrong
     #[error("File name needs to have between 1 and {0} characters")]
     InvalidFileName(usize),
 
+    /// error returned if the reason of a rejection is too long
+    #[error("Reject reason can have at most {0} characters")]
+    RejectReasonTooLong(usize),
+
+    /// error returned if a file with the same name is already attached to the bill
+    #[error("File {0} is already attached to the bill")]
+    FileAlreadyAttached(String),
+
     /// error returned if the file has an invalid, or unknown content type
     #[error("Invalid content type")]
     InvalidContentType,
@@ -404,6 +489,14 @@ pub enum ValidationError {
     #[error("Invalid contact type")]
     InvalidContactType,
 
+    /// error returned if two contacts with different node ids are supposed to be merged
+    #[error("Contacts with different node ids can't be merged")]
+    ContactsHaveDifferentNodeIds,
+
+    /// error returned if a required column is missing in the header of an imported CSV file
+    #[error("The CSV file has no column {0}")]
+    MissingCsvColumn(String),
+
     /// error returned if the given node is not a local one (company or identity)
     #[error("The provided node_id: {0} is not a valid company id, or personal node_id")]
     UnknownNodeId(String),
@@ -412,6 +505,44 @@ pub enum ValidationError {
     #[error("Backup not supported for given SurrealDB connection")]
     BackupNotSupported,
 
+    /// error returned if a bill bundle has a version we don't know
+    #[error("unknown bill bundle version {0}")]
+    UnknownBillBundleVersion(u8),
+
+    /// error returned if a bill bundle can't be read, its signature is invalid, or it contains
+    /// an invalid chain
+    #[error("invalid bill bundle")]
+    InvalidBillBundle,
+
+    /// error returned if a bill view can't be read, or it contains an invalid chain
+    #[error("invalid bill view")]
+    InvalidBillView,
+
+    /// error returned if an action is executed on a bill, of which only a read-only view was
+    /// imported
+    #[error("Bill is only available as a read-only view")]
+    BillIsReadOnlyView,
+
+    /// error returned if an identity backup has a version we don't know
+    #[error("unknown identity backup version {0}")]
+    UnknownIdentityBackupVersion(u8),
+
+    /// error returned if an identity backup can't be read or decrypted with the given passphrase
+    #[error("invalid identity backup or wrong passphrase")]
+    InvalidIdentityBackup,
+
+    /// error returned if restoring a backup would overwrite a different, existing identity
+    #[error("An identity already exists")]
+    IdentityAlreadyExists,
+
+    /// error returned if a bill QR payload can't be read, or its signature is invalid
+    #[error("invalid bill QR payload")]
+    InvalidBillQrPayload,
+
+    /// error returned if a bill violates a deployment specific issuing rule
+    #[error("The bill violates the issuing policy: {0}")]
+    IssuePolicyViolation(String),
+
     /// errors that stem from interacting with a blockchain
     #[error("Blockchain error: {0}")]
     Blockchain(String),
@@ -64,6 +64,21 @@ impl Display for NotificationType {
     }
 }
 
+/// The rendered texts of a notification, as counterparties would receive them for an action
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NotificationPreview {
+    /// The language the texts were rendered in
+    pub language: String,
+    /// The text shown in the in-app notification
+    pub in_app_text: String,
+    /// The subject of the notification email
+    pub email_subject: String,
+    /// The body of the notification email
+    pub email_body: String,
+    /// The text of the push notification
+    pub push_text: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[allow(clippy::enum_variant_names, dead_code)]
 pub enum ActionType {
@@ -73,13 +88,14 @@ pub enum ActionType {
     CheckBill,
     PayBill,
     CheckQuote,
+    RequestPayment,
 }
 
 /// The different types of events that can be sent via this service.
 /// For now we only have Bill events and this needs some clippy
 /// exceptions here. As soon as we have other event topics, we can
 /// add new types here and remove the clippy exceptions.
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[allow(clippy::enum_variant_names, dead_code)]
 pub enum BillEventType {
     BillSigned,
@@ -89,6 +105,7 @@ pub enum BillEventType {
     BillAcceptanceTimeout,
     BillAcceptanceRecourse,
     BillPaymentRequested,
+    BillAcceptanceAndPaymentRequested,
     BillPaymentRejected,
     BillPaymentRecourse,
     BillRecourseRejected,
@@ -103,6 +120,9 @@ pub enum BillEventType {
     BillMintingRequested,
     BillNewQuote,
     BillQuoteApproved,
+    BillQuoteRejected,
+    BillMaturityApproaching,
+    BillActionTimeoutApproaching,
     #[default]
     BillBlock,
 }
@@ -117,6 +137,7 @@ impl BillEventType {
             Self::BillAcceptanceTimeout,
             Self::BillAcceptanceRecourse,
             Self::BillPaymentRequested,
+            Self::BillAcceptanceAndPaymentRequested,
             Self::BillPaymentRejected,
             Self::BillPaymentTimeout,
             Self::BillPaymentRecourse,
@@ -131,6 +152,9 @@ impl BillEventType {
             Self::BillMintingRequested,
             Self::BillNewQuote,
             Self::BillQuoteApproved,
+            Self::BillQuoteRejected,
+            Self::BillMaturityApproaching,
+            Self::BillActionTimeoutApproaching,
             Self::BillBlock,
         ]
     }
@@ -5,6 +5,7 @@ pub mod tests {
     use crate::{
         Field, OptionalPostalAddress, PostalAddress, ValidationError,
         bill::{BillKeys, BitcreditBill},
+        constants::PAYMENT_DEADLINE_SECONDS,
         contact::{ContactType, IdentityPublicData},
         identity::Identity,
     };
@@ -172,6 +173,8 @@ pub mod tests {
             country_of_payment: "AT".to_string(),
             language: "DE".to_string(),
             files: vec![],
+            payment_deadline_seconds: PAYMENT_DEADLINE_SECONDS,
+            fiat_amount: None,
         }
     }
 
@@ -0,0 +1,133 @@
+use crate::ValidationError;
+
+/// All officially assigned ISO 3166-1 alpha-2 country codes
+pub const COUNTRY_CODES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// Common names of countries, in lowercase, mapped to their country code - only names, which
+/// unambiguously refer to one country are listed
+const COUNTRY_NAMES: [(&str, &str); 40] = [
+    ("austria", "AT"),
+    ("österreich", "AT"),
+    ("oesterreich", "AT"),
+    ("germany", "DE"),
+    ("deutschland", "DE"),
+    ("switzerland", "CH"),
+    ("schweiz", "CH"),
+    ("suisse", "CH"),
+    ("liechtenstein", "LI"),
+    ("france", "FR"),
+    ("frankreich", "FR"),
+    ("italy", "IT"),
+    ("italia", "IT"),
+    ("italien", "IT"),
+    ("spain", "ES"),
+    ("españa", "ES"),
+    ("espana", "ES"),
+    ("spanien", "ES"),
+    ("portugal", "PT"),
+    ("netherlands", "NL"),
+    ("the netherlands", "NL"),
+    ("nederland", "NL"),
+    ("niederlande", "NL"),
+    ("belgium", "BE"),
+    ("belgien", "BE"),
+    ("luxembourg", "LU"),
+    ("luxemburg", "LU"),
+    ("poland", "PL"),
+    ("polska", "PL"),
+    ("polen", "PL"),
+    ("czech republic", "CZ"),
+    ("czechia", "CZ"),
+    ("tschechien", "CZ"),
+    ("united kingdom", "GB"),
+    ("great britain", "GB"),
+    ("uk", "GB"),
+    ("united states", "US"),
+    ("united states of america", "US"),
+    ("usa", "US"),
+    ("el salvador", "SV"),
+];
+
+/// Validates, that the given country is an ISO 3166-1 alpha-2 country code in upper case
+pub fn validate_country_code(country: &str) -> Result<(), ValidationError> {
+    if !COUNTRY_CODES.contains(&country) {
+        return Err(ValidationError::InvalidCountryCode(country.to_owned()));
+    }
+    Ok(())
+}
+
+/// Maps the given country to its ISO 3166-1 alpha-2 country code - it can be a country code in
+/// any case, or a common name of a country in English or its local language. Returns None, if
+/// the country can't be mapped unambiguously.
+pub fn normalize_country(country: &str) -> Option<String> {
+    let trimmed = country.trim();
+    let upper = trimmed.to_uppercase();
+    if COUNTRY_CODES.contains(&upper.as_str()) {
+        return Some(upper);
+    }
+    let lower = trimmed.to_lowercase();
+    COUNTRY_NAMES
+        .iter()
+        .find(|(name, _)| *name == lower)
+        .map(|(_, code)| code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("AT")]
+    #[case("DE")]
+    #[case("GB")]
+    #[case("US")]
+    fn validate_country_code_valid(#[case] country: &str) {
+        assert_eq!(validate_country_code(country), Ok(()));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("at")]
+    #[case(" AT ")]
+    #[case("UK")]
+    #[case("AUT")]
+    #[case("Germany")]
+    fn validate_country_code_invalid(#[case] country: &str) {
+        assert_eq!(
+            validate_country_code(country),
+            Err(ValidationError::InvalidCountryCode(country.to_owned()))
+        );
+    }
+
+    #[rstest]
+    #[case("AT", Some("AT"))]
+    #[case(" de ", Some("DE"))]
+    #[case("Germany", Some("DE"))]
+    #[case("deutschland", Some("DE"))]
+    #[case("Österreich", Some("AT"))]
+    #[case("UK", Some("GB"))]
+    #[case("United States", Some("US"))]
+    #[case("Atlantis", None)]
+    #[case("", None)]
+    fn normalize_country_maps_to_codes(#[case] country: &str, #[case] expected: Option<&str>) {
+        assert_eq!(normalize_country(country), expected.map(|e| e.to_owned()));
+    }
+}
@@ -87,6 +87,12 @@ impl BcrKeys {
         Ok(Self { inner: keypair })
     }
 
+    /// Loads a keypair from the given raw 32 byte secret key
+    pub fn from_secret_bytes(secret: &[u8]) -> Result<Self> {
+        let keypair = Keypair::from_secret_key(SECP256K1, &SecretKey::from_slice(secret)?);
+        Ok(Self { inner: keypair })
+    }
+
     /// Returns the private key as a hex encoded string
     pub fn get_private_key_string(&self) -> String {
         self.inner.secret_key().display_secret().to_string()
@@ -189,6 +195,13 @@ fn load_keypair(private_key: &str) -> Result<Keypair> {
     Ok(pair)
 }
 
+/// Returns N cryptographically secure random bytes, e.g. to be used as a salt
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes);
+    bytes
+}
+
 // -------------------- Aggregated Signatures --------------------------
 
 /// Returns the combined public key for the given public keys
@@ -1,4 +1,13 @@
-use crate::{ValidationError, constants::VALID_CURRENCIES};
+use crate::{
+    ValidationError,
+    constants::{VALID_CURRENCIES, VALID_FIAT_CURRENCIES},
+};
+
+/// The currency, in which sums are stored on the bill chain
+const SAT_CURRENCY: &str = "sat";
+/// Sums can be given in btc, with up to 8 decimals, but they are always stored in sat
+const BTC_CURRENCY: &str = "btc";
+const BTC_DECIMALS: usize = 8;
 
 pub fn parse_sum(sum: &str) -> Result<u64, ValidationError> {
     match sum.parse::<u64>() {
@@ -23,6 +32,84 @@ pub fn sat_to_btc(val: u64) -> String {
     amount.to_string_in(bitcoin::Denomination::Bitcoin)
 }
 
+/// Converts the given sat value to fiat, using the given price of one BTC, rounded to 2 decimals
+pub fn sat_to_fiat(val: u64, btc_rate: f64) -> String {
+    let amount = bitcoin::Amount::from_sat(val);
+    format!("{:.2}", amount.to_btc() * btc_rate)
+}
+
+/// Parses a fiat sum with at most 2 decimals (e.g. `100`, `100.5`, `100.50`) into cents
+pub fn parse_fiat_sum(sum: &str) -> Result<u64, ValidationError> {
+    parse_decimal_sum(sum, 2)
+}
+
+/// Parses a sum with at most the given number of decimals into the smallest unit, e.g. with 2
+/// decimals `100.5` is parsed into `10050`
+fn parse_decimal_sum(sum: &str, decimals: usize) -> Result<u64, ValidationError> {
+    let (whole, fraction) = match sum.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (sum, ""),
+    };
+    if whole.is_empty()
+        || fraction.len() > decimals
+        || (decimals == 0 && sum.contains('.'))
+        || !whole.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(ValidationError::InvalidSum);
+    }
+    let whole = whole
+        .parse::<u64>()
+        .map_err(|_| ValidationError::InvalidSum)?;
+    let fraction = if decimals == 0 {
+        0
+    } else {
+        format!("{fraction:0<decimals$}")
+            .parse::<u64>()
+            .map_err(|_| ValidationError::InvalidSum)?
+    };
+    whole
+        .checked_mul(10u64.pow(decimals as u32))
+        .and_then(|w| w.checked_add(fraction))
+        .ok_or(ValidationError::InvalidSum)
+}
+
+/// Parses a sum given in the given currency into the smallest unit it's stored in and returns it
+/// together with the currency it's stored in - sat sums have to be whole numbers, btc sums can
+/// have up to 8 decimals and are stored in sat, and fiat sums can have up to 2 decimals and are
+/// stored in cents
+pub fn parse_sum_in_currency(sum: &str, currency: &str) -> Result<(u64, String), ValidationError> {
+    if is_fiat_currency(currency) {
+        return Ok((parse_fiat_sum(sum)?, currency.to_owned()));
+    }
+    if currency.to_lowercase() == BTC_CURRENCY {
+        let sat = parse_decimal_sum(sum, BTC_DECIMALS)?;
+        if sat > bitcoin::Amount::MAX_MONEY.to_sat() {
+            return Err(ValidationError::InvalidSum);
+        }
+        return Ok((sat, SAT_CURRENCY.to_owned()));
+    }
+    validate_currency(currency)?;
+    Ok((parse_decimal_sum(sum, 0)?, currency.to_owned()))
+}
+
+pub fn fiat_sum_to_string(cents: u64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Converts the given fiat cents to sat, using the given price of one BTC, rounded to the
+/// nearest sat - returns None if the rate is not a positive number
+pub fn fiat_to_sat(cents: u64, btc_rate: f64) -> Option<u64> {
+    if !btc_rate.is_finite() || btc_rate <= 0.0 {
+        return None;
+    }
+    let sat = (cents as f64 / 100.0 / btc_rate * bitcoin::Amount::ONE_BTC.to_sat() as f64).round();
+    if sat > bitcoin::Amount::MAX_MONEY.to_sat() as f64 {
+        return None;
+    }
+    Some(sat as u64)
+}
+
 pub fn validate_currency(currency: &str) -> Result<(), ValidationError> {
     if !VALID_CURRENCIES.contains(&currency.to_lowercase().as_str()) {
         return Err(ValidationError::InvalidCurrency);
@@ -30,6 +117,17 @@ pub fn validate_currency(currency: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+pub fn is_fiat_currency(currency: &str) -> bool {
+    VALID_FIAT_CURRENCIES.contains(&currency.to_lowercase().as_str())
+}
+
+pub fn validate_fiat_currency(currency: &str) -> Result<(), ValidationError> {
+    if !is_fiat_currency(currency) {
+        return Err(ValidationError::InvalidCurrency);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,4 +138,88 @@ mod tests {
         assert_eq!(sat_to_btc(10000), String::from("0.0001"));
         assert_eq!(sat_to_btc(1), String::from("0.00000001"));
     }
+
+    #[test]
+    fn sat_to_fiat_test() {
+        assert_eq!(sat_to_fiat(100_000_000, 50000.0), String::from("50000.00"));
+        assert_eq!(sat_to_fiat(15000, 50000.0), String::from("7.50"));
+        assert_eq!(sat_to_fiat(0, 50000.0), String::from("0.00"));
+    }
+
+    #[test]
+    fn parse_fiat_sum_test() {
+        assert_eq!(parse_fiat_sum("100"), Ok(10000));
+        assert_eq!(parse_fiat_sum("100.5"), Ok(10050));
+        assert_eq!(parse_fiat_sum("100.05"), Ok(10005));
+        assert_eq!(parse_fiat_sum("0.99"), Ok(99));
+        assert!(parse_fiat_sum("").is_err());
+        assert!(parse_fiat_sum(".5").is_err());
+        assert!(parse_fiat_sum("-1").is_err());
+        assert!(parse_fiat_sum("1.005").is_err());
+        assert!(parse_fiat_sum("1,50").is_err());
+        assert!(parse_fiat_sum("1.+5").is_err());
+    }
+
+    #[test]
+    fn parse_sum_in_currency_test() {
+        assert_eq!(parse_sum_in_currency("100", "sat"), Ok((100, "sat".into())));
+        assert_eq!(
+            parse_sum_in_currency("100.5", "sat"),
+            Err(ValidationError::InvalidSum)
+        );
+        assert_eq!(
+            parse_sum_in_currency("100.", "sat"),
+            Err(ValidationError::InvalidSum)
+        );
+        assert_eq!(
+            parse_sum_in_currency("+100", "sat"),
+            Err(ValidationError::InvalidSum)
+        );
+        assert_eq!(
+            parse_sum_in_currency("0.00000001", "btc"),
+            Ok((1, "sat".into()))
+        );
+        assert_eq!(
+            parse_sum_in_currency("1.5", "BTC"),
+            Ok((150_000_000, "sat".into()))
+        );
+        assert_eq!(
+            parse_sum_in_currency("0.000000001", "btc"),
+            Err(ValidationError::InvalidSum)
+        );
+        assert_eq!(
+            parse_sum_in_currency("21000001", "btc"),
+            Err(ValidationError::InvalidSum)
+        );
+        assert_eq!(
+            parse_sum_in_currency("100.5", "eur"),
+            Ok((10050, "eur".into()))
+        );
+        assert_eq!(
+            parse_sum_in_currency("100", "doge"),
+            Err(ValidationError::InvalidCurrency)
+        );
+    }
+
+    #[test]
+    fn fiat_sum_to_string_test() {
+        assert_eq!(fiat_sum_to_string(10050), String::from("100.50"));
+        assert_eq!(fiat_sum_to_string(5), String::from("0.05"));
+    }
+
+    #[test]
+    fn fiat_to_sat_test() {
+        assert_eq!(fiat_to_sat(5_000_000, 50000.0), Some(100_000_000));
+        assert_eq!(fiat_to_sat(750, 50000.0), Some(15000));
+        assert_eq!(fiat_to_sat(750, 0.0), None);
+        assert_eq!(fiat_to_sat(750, f64::NAN), None);
+        assert_eq!(fiat_to_sat(1, 60000.0), Some(17));
+    }
+
+    #[test]
+    fn is_fiat_currency_test() {
+        assert!(is_fiat_currency("EUR"));
+        assert!(is_fiat_currency("eur"));
+        assert!(!is_fiat_currency("sat"));
+    }
 }
@@ -1,6 +1,8 @@
+pub mod country;
 pub mod crypto;
 pub mod currency;
 pub mod date;
+pub mod search;
 
 pub use crypto::BcrKeys;
 
@@ -12,10 +14,10 @@ use uuid::Uuid;
 use crate::ValidationError;
 
 pub fn validate_file_upload_id(file_upload_id: Option<&str>) -> Result<(), ValidationError> {
-    if let Some(id) = file_upload_id {
-        if id.is_empty() {
-            return Err(ValidationError::InvalidFileUploadId);
-        }
+    if let Some(id) = file_upload_id
+        && id.is_empty()
+    {
+        return Err(ValidationError::InvalidFileUploadId);
     }
     Ok(())
 }
@@ -0,0 +1,118 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+/// Normalizes the given value for comparison in searches - lowercases it, trims it and strips
+/// diacritics, e.g. "Hayék" becomes "hayek"
+pub fn normalize(value: &str) -> String {
+    value
+        .trim()
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Checks if the normalized value contains the normalized search term
+pub fn matches_exact(value: &str, search_term: &str) -> bool {
+    normalize(value).contains(&normalize(search_term))
+}
+
+/// Checks if one of the words in the value is within the allowed edit distance for the search
+/// term. Short search terms never match fuzzily, since they would match almost everything.
+pub fn matches_fuzzy(value: &str, search_term: &str) -> bool {
+    let search_term = normalize(search_term);
+    let max_distance = max_edit_distance(search_term.chars().count());
+    if max_distance == 0 {
+        return false;
+    }
+    let value = normalize(value);
+    value
+        .split_whitespace()
+        .any(|word| edit_distance(word, &search_term) <= max_distance)
+}
+
+/// The allowed edit distance scales with the length of the search term
+fn max_edit_distance(search_term_len: usize) -> usize {
+    match search_term_len {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    }
+}
+
+/// Levenshtein distance, which also counts the transposition of two adjacent characters as a
+/// single edit (optimal string alignment), so "hayke" is within a distance of 1 of "hayek"
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (len_a, len_b) = (a.len(), b.len());
+    if len_a == 0 {
+        return len_b;
+    }
+    if len_b == 0 {
+        return len_a;
+    }
+
+    let mut dist = vec![vec![0usize; len_b + 1]; len_a + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, value) in dist[0].iter_mut().enumerate() {
+        *value = j;
+    }
+
+    for i in 1..=len_a {
+        for j in 1..=len_b {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut value = (dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1)
+                .min(dist[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(dist[i - 2][j - 2] + 1);
+            }
+            dist[i][j] = value;
+        }
+    }
+    dist[len_a][len_b]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_strips_diacritics_and_case() {
+        assert_eq!(normalize(" Hayék "), "hayek");
+        assert_eq!(normalize("Müller"), "muller");
+        assert_eq!(normalize("hayek"), "hayek");
+    }
+
+    #[test]
+    fn matches_exact_baseline() {
+        assert!(matches_exact("Friedrich Hayék", "hayek"));
+        assert!(matches_exact("Friedrich Hayek", "HAY"));
+        assert!(!matches_exact("Friedrich Hayek", "hayke"));
+    }
+
+    #[test]
+    fn matches_fuzzy_baseline() {
+        assert!(matches_fuzzy("Friedrich Hayek", "hayke"));
+        assert!(matches_fuzzy("Friedrich Hayek", "hayak"));
+        assert!(matches_fuzzy("Ludwig von Mises", "missees"));
+        assert!(!matches_fuzzy("Friedrich Hayek", "keynes"));
+    }
+
+    #[test]
+    fn matches_fuzzy_ignores_short_terms() {
+        assert!(!matches_fuzzy("Ludwig von Mises", "vom"));
+        assert!(!matches_fuzzy("Ludwig von Mises", ""));
+    }
+
+    #[test]
+    fn edit_distance_baseline() {
+        assert_eq!(edit_distance("hayek", "hayek"), 0);
+        assert_eq!(edit_distance("hayek", "hayke"), 1);
+        assert_eq!(edit_distance("hayek", "hayk"), 1);
+        assert_eq!(edit_distance("hayek", "mises"), 4);
+        assert_eq!(edit_distance("", "mises"), 5);
+    }
+}
@@ -15,4 +15,7 @@ pub trait BackupStoreApi: Send + Sync {
 
     /// drops the database with the given name
     async fn drop_db(&self, name: &str) -> Result<()>;
+
+    /// runs a trivial query to check, that the database is reachable
+    async fn ping(&self) -> Result<()>;
 }
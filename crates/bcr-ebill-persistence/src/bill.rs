@@ -3,7 +3,10 @@ use std::collections::HashSet;
 use super::Result;
 use async_trait::async_trait;
 use bcr_ebill_core::{
-    bill::{BillKeys, BitcreditBillResult},
+    bill::{
+        BillAutoAcceptRule, BillHold, BillKeys, BillTemplate, BillView, BitcreditBillResult,
+        QueuedAutoAccept,
+    },
     blockchain::bill::{BillBlock, BillBlockchain, BillOpCode},
 };
 
@@ -11,11 +14,12 @@ use borsh::{from_slice, to_vec};
 
 #[async_trait]
 pub trait BillStoreApi: Send + Sync {
-    /// Gets the bills from cache
+    /// Gets the bills from cache - bills cached at a lower height than their chain are skipped
     async fn get_bills_from_cache(&self, ids: &[String]) -> Result<Vec<BitcreditBillResult>>;
-    /// Gets the bill from cache
+    /// Gets the bill from cache - if it was cached at a lower height than its chain, it's treated
+    /// as not cached, so it gets recalculated
     async fn get_bill_from_cache(&self, id: &str) -> Result<Option<BitcreditBillResult>>;
-    /// Saves the bill to cache
+    /// Saves the bill to cache, evicting the least recently used bills, if the cache is full
     async fn save_bill_to_cache(&self, id: &str, bill: &BitcreditBillResult) -> Result<()>;
     /// Invalidates the cached bill
     async fn invalidate_bill_in_cache(&self, id: &str) -> Result<()>;
@@ -25,14 +29,85 @@ pub trait BillStoreApi: Send + Sync {
     async fn exists(&self, id: &str) -> bool;
     /// Gets all bill ids
     async fn get_ids(&self) -> Result<Vec<String>>;
+    /// Gets the ids of all bills the given node id is a participant of, using the participant
+    /// index, which is updated whenever a bill is saved to the cache
+    async fn get_bill_ids_by_participant(&self, node_id: &str) -> Result<Vec<String>>;
+    /// Gets the ids of all bills, which aren't in the participant index yet, since they were
+    /// never saved to the cache
+    async fn get_unindexed_bill_ids(&self) -> Result<Vec<String>>;
     /// Saves the keys
     async fn save_keys(&self, id: &str, keys: &BillKeys) -> Result<()>;
+    /// Removes the keys, e.g. to undo an incomplete import
+    async fn remove_keys(&self, id: &str) -> Result<()>;
     /// Get bill keys
     async fn get_keys(&self, id: &str) -> Result<BillKeys>;
+    /// Gets the ids of all bills, which have keys
+    async fn get_key_ids(&self) -> Result<Vec<String>>;
     /// Check if the given bill was paid
     async fn is_paid(&self, id: &str) -> Result<bool>;
     /// Set the given bill to paid on the given payment address
     async fn set_to_paid(&self, id: &str, payment_address: &str) -> Result<()>;
+    /// Gets the confirmed sum received on the payment address of the given bill so far - 0 if
+    /// nothing was received yet
+    async fn get_paid_sum(&self, id: &str) -> Result<u64>;
+    /// Sets the confirmed sum received on the given payment address of the given bill so far
+    async fn set_paid_sum(&self, id: &str, payment_address: &str, paid_sum: u64) -> Result<()>;
+    /// Gets the hold of the given bill, if it's on hold
+    async fn get_hold(&self, id: &str) -> Result<Option<BillHold>>;
+    /// Puts the given bill on hold with the given reason, replacing an existing hold
+    async fn set_hold(&self, id: &str, hold: &BillHold) -> Result<()>;
+    /// Removes the hold from the given bill
+    async fn clear_hold(&self, id: &str) -> Result<()>;
+    /// Gets the id of the block, which was created for the given idempotency key of an action
+    /// with the given op code on the given bill, if there is one - a reserved key, whose action
+    /// wasn't finished yet, has no block id
+    async fn get_idempotent_block_id(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+    ) -> Result<Option<u64>>;
+    /// Atomically reserves the given idempotency key of an action with the given op code on the
+    /// given bill, before the action is executed - returns false, if the key was reserved already
+    async fn reserve_idempotency_key(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+    ) -> Result<bool>;
+    /// Releases the reservation of the given idempotency key, e.g. if the action failed - a key,
+    /// for which a block was recorded already, isn't released
+    async fn release_idempotency_key(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+    ) -> Result<()>;
+    /// Records the id of the block, which was created for the given idempotency key of an action
+    /// with the given op code on the given bill
+    async fn save_idempotency_key(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+        block_id: u64,
+    ) -> Result<()>;
+    /// Gets the ids of all bills archived by the given node id
+    async fn get_archived_bill_ids(&self, node_id: &str) -> Result<Vec<String>>;
+    /// Archives the given bill for the given node id
+    async fn archive(&self, id: &str, node_id: &str) -> Result<()>;
+    /// Unarchives the given bill for the given node id
+    async fn unarchive(&self, id: &str, node_id: &str) -> Result<()>;
+    /// Gets the local note of the given node id on the given bill, if there is one
+    async fn get_note(&self, id: &str, node_id: &str) -> Result<Option<String>>;
+    /// Sets the local note of the given node id on the given bill, replacing an existing note
+    async fn set_note(&self, id: &str, node_id: &str, note: &str) -> Result<()>;
+    /// Removes the local note of the given node id from the given bill
+    async fn remove_note(&self, id: &str, node_id: &str) -> Result<()>;
+    /// Gets the imported read-only view of the given bill, if there is one
+    async fn get_view(&self, id: &str) -> Result<Option<BillView>>;
+    /// Saves the given read-only view of a bill, replacing an existing view of the same bill
+    async fn save_view(&self, view: &BillView) -> Result<()>;
     /// Gets all bills with a RequestToPay block, which are not paid already
     async fn get_bill_ids_waiting_for_payment(&self) -> Result<Vec<String>>;
     /// Gets all bills where the latest block is OfferToSell, which are still waiting for payment
@@ -56,6 +131,43 @@ pub trait BillChainStoreApi: Send + Sync {
     async fn add_block(&self, id: &str, block: &BillBlock) -> Result<()>;
     /// Get the whole blockchain
     async fn get_chain(&self, id: &str) -> Result<BillBlockchain>;
+    /// Get all blocks of the chain, without validating them
+    async fn get_blocks(&self, id: &str) -> Result<Vec<BillBlock>>;
+    /// Removes all blocks of the chain from the given block id on, e.g. to replace corrupted
+    /// blocks
+    async fn remove_blocks_from(&self, id: &str, block_id: u64) -> Result<()>;
+    /// Atomically replaces all blocks of the chain from the id of the first given block on with
+    /// the given blocks - the blocks have to be validated by the caller
+    async fn replace_blocks_from(&self, id: &str, blocks: &[BillBlock]) -> Result<()>;
+}
+
+#[async_trait]
+pub trait BillTemplateStoreApi: Send + Sync {
+    /// Gets all bill templates
+    async fn get_all(&self) -> Result<Vec<BillTemplate>>;
+    /// Gets the bill template with the given id
+    async fn get(&self, id: &str) -> Result<Option<BillTemplate>>;
+    /// Saves the given bill template, replacing an existing template with the same id
+    async fn save(&self, template: &BillTemplate) -> Result<()>;
+    /// Deletes the bill template with the given id
+    async fn delete(&self, id: &str) -> Result<()>;
+}
+
+#[async_trait]
+pub trait BillAutoAcceptStoreApi: Send + Sync {
+    /// Gets the auto-accept rules of the given drawee node id
+    async fn get_rules(&self, node_id: &str) -> Result<Vec<BillAutoAcceptRule>>;
+    /// Saves the given auto-accept rule, replacing an existing rule with the same id
+    async fn save_rule(&self, rule: &BillAutoAcceptRule) -> Result<()>;
+    /// Deletes the auto-accept rule with the given id
+    async fn delete_rule(&self, id: &str) -> Result<()>;
+    /// Queues the given bill to be accepted automatically, replacing an existing entry for the
+    /// same bill and node id
+    async fn queue_accept(&self, queued: &QueuedAutoAccept) -> Result<()>;
+    /// Gets all bills, which are queued to be accepted automatically
+    async fn get_queued_accepts(&self) -> Result<Vec<QueuedAutoAccept>>;
+    /// Removes the given bill of the given node id from the queue
+    async fn remove_queued_accept(&self, bill_id: &str, node_id: &str) -> Result<()>;
 }
 
 pub fn bill_chain_from_bytes(bytes: &[u8]) -> Result<BillBlockchain> {
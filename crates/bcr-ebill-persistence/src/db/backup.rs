@@ -55,6 +55,11 @@ impl BackupStoreApi for SurrealBackupStore {
             .await?;
         Ok(())
     }
+
+    async fn ping(&self) -> Result<()> {
+        self.db().await?.query("RETURN true").await?.check()?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -94,6 +99,12 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_ping() {
+        let store = get_store("ping").await;
+        assert!(store.ping().await.is_ok());
+    }
+
     async fn get_store(db_name: &str) -> SurrealBackupStore {
         let db = get_memory_db("test", db_name)
             .await
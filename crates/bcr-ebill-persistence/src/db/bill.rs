@@ -1,17 +1,23 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+use super::bill_chain::BillBlockDb;
 #[cfg(target_arch = "wasm32")]
 use super::get_new_surreal_db;
 use super::{FileDb, PostalAddressDb, Result};
-use crate::constants::{DB_BILL_ID, DB_IDS, DB_OP_CODE, DB_TABLE, DB_TIMESTAMP};
+use crate::constants::{
+    DB_BILL_ID, DB_IDS, DB_LIMIT, DB_NODE_ID, DB_OP_CODE, DB_TABLE, DB_TIMESTAMP,
+};
 use crate::{Error, bill::BillStoreApi};
 use async_trait::async_trait;
 use bcr_ebill_core::bill::{
-    BillAcceptanceStatus, BillCurrentWaitingState, BillData, BillParticipants, BillPaymentStatus,
-    BillRecourseStatus, BillSellStatus, BillStatus, BillWaitingForPaymentState,
-    BillWaitingForRecourseState, BillWaitingForSellState, BitcreditBillResult,
+    BillAcceptanceStatus, BillAcknowledgement, BillCurrentWaitingState, BillData, BillFiatAmount,
+    BillHold, BillParticipants, BillPaymentStatus, BillRecourseStatus, BillSellStatus, BillStatus,
+    BillView, BillViewKey, BillWaitingForPaymentState, BillWaitingForRecourseState,
+    BillWaitingForSellState, BitcreditBillResult, FeeEstimate, SettlementStatus,
+};
+use bcr_ebill_core::constants::{
+    MAX_PAYMENT_DEADLINE_SECONDS, PAYMENT_DEADLINE_SECONDS, RECOURSE_DEADLINE_SECONDS,
 };
-use bcr_ebill_core::constants::{PAYMENT_DEADLINE_SECONDS, RECOURSE_DEADLINE_SECONDS};
 use bcr_ebill_core::contact::{ContactType, IdentityPublicData};
 use bcr_ebill_core::{bill::BillKeys, blockchain::bill::BillOpCode, util};
 use serde::{Deserialize, Serialize};
@@ -21,16 +27,106 @@ use surrealdb::{Surreal, engine::any::Any, sql::Thing};
 pub struct SurrealBillStore {
     #[allow(dead_code)]
     db: Surreal<Any>,
+    /// The maximum number of cached bills, 0 meaning unlimited
+    max_cache_size: usize,
 }
 
 impl SurrealBillStore {
     const CHAIN_TABLE: &'static str = "bill_chain";
     const KEYS_TABLE: &'static str = "bill_keys";
     const PAID_TABLE: &'static str = "bill_paid";
+    const HOLD_TABLE: &'static str = "bill_hold";
+    const PAID_SUM_TABLE: &'static str = "bill_paid_sum";
+    const ARCHIVE_TABLE: &'static str = "bill_archive";
+    const NOTE_TABLE: &'static str = "bill_note";
+    const VIEW_TABLE: &'static str = "bill_view";
     const CACHE_TABLE: &'static str = "bill_cache";
+    const PARTICIPANT_TABLE: &'static str = "bill_participant";
+    const IDEMPOTENCY_TABLE: &'static str = "bill_idempotency";
 
     pub fn new(db: Surreal<Any>) -> Self {
-        Self { db }
+        Self {
+            db,
+            max_cache_size: 0,
+        }
+    }
+
+    /// Limits the number of cached bills - if the cache is full, the least recently used bills
+    /// are evicted
+    pub fn with_max_cache_size(mut self, max_cache_size: usize) -> Self {
+        self.max_cache_size = max_cache_size;
+        self
+    }
+
+    /// Archive state, notes and the participant index are scoped per node id, so the record id
+    /// combines bill and node id
+    fn node_scoped_id(id: &str, node_id: &str) -> String {
+        format!("{id}_{node_id}")
+    }
+
+    /// Idempotency keys are scoped per bill and action, so the record id combines bill id, op
+    /// code and key
+    fn idempotency_scoped_id(id: &str, op_code: &BillOpCode, idempotency_key: &str) -> String {
+        format!("{id}_{op_code:?}_{idempotency_key}")
+    }
+
+    /// Returns the height of the chain of each of the given bills, which has blocks
+    async fn get_chain_heights(&self, ids: &[String]) -> Result<HashMap<String, u64>> {
+        let results: Vec<BillChainHeightDb> = self
+            .db()
+            .await?
+            .query("SELECT bill_id, math::max(block_id) AS block_height FROM type::table($table) WHERE bill_id IN $ids GROUP BY bill_id")
+            .bind((DB_TABLE, Self::CHAIN_TABLE))
+            .bind((DB_IDS, ids.to_owned()))
+            .await?
+            .take(0)?;
+        Ok(results
+            .into_iter()
+            .map(|h| (h.bill_id, h.block_height))
+            .collect())
+    }
+
+    /// Marks the given cache entries as used now, so they're evicted last
+    async fn touch_cache_entries(&self, ids: Vec<Thing>) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.db()
+            .await?
+            .query("UPDATE type::table($table) SET last_accessed = $timestamp WHERE id IN $ids")
+            .bind((DB_TABLE, Self::CACHE_TABLE))
+            .bind((DB_IDS, ids))
+            .bind((DB_TIMESTAMP, util::date::now().timestamp_millis()))
+            .await?;
+        Ok(())
+    }
+
+    /// Removes the least recently used cache entries, which exceed the maximum cache size
+    async fn evict_cache_entries(&self) -> Result<()> {
+        if self.max_cache_size == 0 {
+            return Ok(());
+        }
+        let evicted: Vec<BillCacheAccessDb> = self
+            .db()
+            .await?
+            .query("SELECT id, last_accessed FROM type::table($table) ORDER BY last_accessed DESC START $limit")
+            .bind((DB_TABLE, Self::CACHE_TABLE))
+            .bind((DB_LIMIT, self.max_cache_size))
+            .await?
+            .take(0)?;
+        if evicted.is_empty() {
+            return Ok(());
+        }
+        self.db()
+            .await?
+            .query("DELETE FROM type::table($table) WHERE id IN $ids")
+            .bind((DB_TABLE, Self::CACHE_TABLE))
+            .bind((
+                DB_IDS,
+                evicted.into_iter().map(|e| e.id).collect::<Vec<Thing>>(),
+            ))
+            .await?;
+        Ok(())
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -60,7 +156,14 @@ impl BillStoreApi for SurrealBillStore {
             .bind((DB_IDS, db_ids))
             .await?
             .take(0)?;
-        Ok(results.into_iter().map(|bill| bill.into()).collect())
+        let chain_heights = self.get_chain_heights(ids).await?;
+        let bills: Vec<BitcreditBillResultDb> = results
+            .into_iter()
+            .filter(|bill| !bill.is_outdated() && !bill.is_stale(&chain_heights))
+            .collect();
+        self.touch_cache_entries(bills.iter().map(|bill| bill.id.clone()).collect())
+            .await?;
+        Ok(bills.into_iter().map(|bill| bill.into()).collect())
     }
 
     async fn get_bill_from_cache(&self, id: &str) -> Result<Option<BitcreditBillResult>> {
@@ -68,19 +171,45 @@ impl BillStoreApi for SurrealBillStore {
             self.db().await?.select((Self::CACHE_TABLE, id)).await?;
         match result {
             None => Ok(None),
-            Some(c) => Ok(Some(c.into())),
+            Some(c) if c.is_outdated() => Ok(None),
+            Some(c) => {
+                let chain_heights = self.get_chain_heights(&[id.to_owned()]).await?;
+                if c.is_stale(&chain_heights) {
+                    return Ok(None);
+                }
+                self.touch_cache_entries(vec![c.id.clone()]).await?;
+                Ok(Some(c.into()))
+            }
         }
     }
 
     async fn save_bill_to_cache(&self, id: &str, bill: &BitcreditBillResult) -> Result<()> {
         let id = id.to_owned();
-        let entity: BitcreditBillResultDb = bill.into();
+        let mut entity: BitcreditBillResultDb = bill.into();
+        entity.last_accessed = util::date::now().timestamp_millis();
         let _: Option<BitcreditBillResultDb> = self
             .db()
             .await?
-            .upsert((Self::CACHE_TABLE, id))
+            .upsert((Self::CACHE_TABLE, id.clone()))
             .content(entity)
             .await?;
+        // participants of a bill never change once they're on the chain, so the index doesn't have
+        // to be cleaned up when the cache is invalidated
+        for node_id in bill.participants.all_participant_node_ids.iter() {
+            let participant_id = Self::node_scoped_id(&id, node_id);
+            let entity = BillParticipantDb {
+                id: (Self::PARTICIPANT_TABLE, participant_id.as_str()).into(),
+                bill_id: id.clone(),
+                node_id: node_id.to_owned(),
+            };
+            let _: Option<BillParticipantDb> = self
+                .db()
+                .await?
+                .upsert((Self::PARTICIPANT_TABLE, participant_id))
+                .content(entity)
+                .await?;
+        }
+        self.evict_cache_entries().await?;
         Ok(())
     }
 
@@ -129,6 +258,35 @@ impl BillStoreApi for SurrealBillStore {
         Ok(ids.into_iter().map(|b| b.bill_id).collect())
     }
 
+    async fn get_bill_ids_by_participant(&self, node_id: &str) -> Result<Vec<String>> {
+        let result: Vec<BillIdDb> = self
+            .db()
+            .await?
+            .query("SELECT bill_id FROM type::table($table) WHERE node_id = $node_id")
+            .bind((DB_TABLE, Self::PARTICIPANT_TABLE))
+            .bind((DB_NODE_ID, node_id.to_owned()))
+            .await?
+            .take(0)?;
+        Ok(result.into_iter().map(|bid| bid.bill_id).collect())
+    }
+
+    async fn get_unindexed_bill_ids(&self) -> Result<Vec<String>> {
+        let indexed: Vec<BillIdDb> = self
+            .db()
+            .await?
+            .query("SELECT bill_id FROM type::table($table) GROUP BY bill_id")
+            .bind((DB_TABLE, Self::PARTICIPANT_TABLE))
+            .await?
+            .take(0)?;
+        let indexed: HashSet<String> = indexed.into_iter().map(|bid| bid.bill_id).collect();
+        Ok(self
+            .get_ids()
+            .await?
+            .into_iter()
+            .filter(|id| !indexed.contains(id))
+            .collect())
+    }
+
     async fn save_keys(&self, id: &str, key_pair: &BillKeys) -> Result<()> {
         let entity: BillKeysDb = key_pair.into();
         let _: Option<BillKeysDb> = self
@@ -140,6 +298,11 @@ impl BillStoreApi for SurrealBillStore {
         Ok(())
     }
 
+    async fn remove_keys(&self, id: &str) -> Result<()> {
+        let _: Option<BillKeysDb> = self.db().await?.delete((Self::KEYS_TABLE, id)).await?;
+        Ok(())
+    }
+
     async fn get_keys(&self, id: &str) -> Result<BillKeys> {
         let result: Option<BillKeysDb> = self.db().await?.select((Self::KEYS_TABLE, id)).await?;
         match result {
@@ -148,6 +311,14 @@ impl BillStoreApi for SurrealBillStore {
         }
     }
 
+    async fn get_key_ids(&self) -> Result<Vec<String>> {
+        let keys: Vec<BillKeysDb> = self.db().await?.select(Self::KEYS_TABLE).await?;
+        Ok(keys
+            .into_iter()
+            .filter_map(|k| k.id.map(|id| id.id.to_raw()))
+            .collect())
+    }
+
     async fn is_paid(&self, id: &str) -> Result<bool> {
         let result: Option<BillPaidDb> = self.db().await?.select((Self::PAID_TABLE, id)).await?;
         Ok(result.is_some())
@@ -167,6 +338,241 @@ impl BillStoreApi for SurrealBillStore {
         Ok(())
     }
 
+    async fn get_paid_sum(&self, id: &str) -> Result<u64> {
+        let result: Option<BillPaidSumDb> =
+            self.db().await?.select((Self::PAID_SUM_TABLE, id)).await?;
+        Ok(result.map(|p| p.paid_sum).unwrap_or(0))
+    }
+
+    async fn set_paid_sum(&self, id: &str, payment_address: &str, paid_sum: u64) -> Result<()> {
+        let entity = BillPaidSumDb {
+            id: (Self::PAID_SUM_TABLE, id).into(),
+            payment_address: payment_address.to_string(),
+            paid_sum,
+        };
+        let _: Option<BillPaidSumDb> = self
+            .db()
+            .await?
+            .upsert((Self::PAID_SUM_TABLE, id))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_hold(&self, id: &str) -> Result<Option<BillHold>> {
+        let result: Option<BillHoldDb> = self.db().await?.select((Self::HOLD_TABLE, id)).await?;
+        Ok(result.map(|h| h.into()))
+    }
+
+    async fn set_hold(&self, id: &str, hold: &BillHold) -> Result<()> {
+        let entity = BillHoldDb {
+            id: (Self::HOLD_TABLE, id).into(),
+            reason: hold.reason.clone(),
+            timestamp: hold.timestamp,
+        };
+        let _: Option<BillHoldDb> = self
+            .db()
+            .await?
+            .upsert((Self::HOLD_TABLE, id))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn clear_hold(&self, id: &str) -> Result<()> {
+        let _: Option<BillHoldDb> = self.db().await?.delete((Self::HOLD_TABLE, id)).await?;
+        Ok(())
+    }
+
+    async fn get_idempotent_block_id(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+    ) -> Result<Option<u64>> {
+        let result: Option<BillIdempotencyDb> = self
+            .db()
+            .await?
+            .select((
+                Self::IDEMPOTENCY_TABLE,
+                Self::idempotency_scoped_id(id, op_code, idempotency_key),
+            ))
+            .await?;
+        Ok(result.and_then(|i| i.block_id))
+    }
+
+    async fn reserve_idempotency_key(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+    ) -> Result<bool> {
+        let idempotency_id = Self::idempotency_scoped_id(id, op_code, idempotency_key);
+        let entity = BillIdempotencyDb {
+            id: (Self::IDEMPOTENCY_TABLE, idempotency_id.as_str()).into(),
+            bill_id: id.to_owned(),
+            op_code: op_code.to_owned(),
+            idempotency_key: idempotency_key.to_owned(),
+            block_id: None,
+        };
+        // creating fails, if the record exists already, so only one caller gets the reservation
+        let created: std::result::Result<Option<BillIdempotencyDb>, _> = self
+            .db()
+            .await?
+            .create((Self::IDEMPOTENCY_TABLE, idempotency_id.as_str()))
+            .content(entity)
+            .await;
+        match created {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                let existing: Option<BillIdempotencyDb> = self
+                    .db()
+                    .await?
+                    .select((Self::IDEMPOTENCY_TABLE, idempotency_id.as_str()))
+                    .await?;
+                match existing {
+                    Some(_) => Ok(false),
+                    None => Err(e.into()),
+                }
+            }
+        }
+    }
+
+    async fn release_idempotency_key(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+    ) -> Result<()> {
+        let idempotency_id = Self::idempotency_scoped_id(id, op_code, idempotency_key);
+        // a key, for which a block was recorded already, stays, so the action isn't repeated
+        self.db()
+            .await?
+            .query("DELETE FROM type::table($table) WHERE id IN $ids AND block_id = NONE")
+            .bind((DB_TABLE, Self::IDEMPOTENCY_TABLE))
+            .bind((
+                DB_IDS,
+                vec![Thing::from((
+                    Self::IDEMPOTENCY_TABLE,
+                    idempotency_id.as_str(),
+                ))],
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn save_idempotency_key(
+        &self,
+        id: &str,
+        op_code: &BillOpCode,
+        idempotency_key: &str,
+        block_id: u64,
+    ) -> Result<()> {
+        let idempotency_id = Self::idempotency_scoped_id(id, op_code, idempotency_key);
+        let entity = BillIdempotencyDb {
+            id: (Self::IDEMPOTENCY_TABLE, idempotency_id.as_str()).into(),
+            bill_id: id.to_owned(),
+            op_code: op_code.to_owned(),
+            idempotency_key: idempotency_key.to_owned(),
+            block_id: Some(block_id),
+        };
+        let _: Option<BillIdempotencyDb> = self
+            .db()
+            .await?
+            .upsert((Self::IDEMPOTENCY_TABLE, idempotency_id))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_archived_bill_ids(&self, node_id: &str) -> Result<Vec<String>> {
+        let result: Vec<BillIdDb> = self
+            .db()
+            .await?
+            .query("SELECT bill_id FROM type::table($table) WHERE node_id = $node_id")
+            .bind((DB_TABLE, Self::ARCHIVE_TABLE))
+            .bind((DB_NODE_ID, node_id.to_owned()))
+            .await?
+            .take(0)?;
+        Ok(result.into_iter().map(|bid| bid.bill_id).collect())
+    }
+
+    async fn archive(&self, id: &str, node_id: &str) -> Result<()> {
+        let archive_id = Self::node_scoped_id(id, node_id);
+        let entity = BillArchiveDb {
+            id: (Self::ARCHIVE_TABLE, archive_id.as_str()).into(),
+            bill_id: id.to_owned(),
+            node_id: node_id.to_owned(),
+        };
+        let _: Option<BillArchiveDb> = self
+            .db()
+            .await?
+            .upsert((Self::ARCHIVE_TABLE, archive_id))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn unarchive(&self, id: &str, node_id: &str) -> Result<()> {
+        let _: Option<BillArchiveDb> = self
+            .db()
+            .await?
+            .delete((Self::ARCHIVE_TABLE, Self::node_scoped_id(id, node_id)))
+            .await?;
+        Ok(())
+    }
+
+    async fn get_note(&self, id: &str, node_id: &str) -> Result<Option<String>> {
+        let result: Option<BillNoteDb> = self
+            .db()
+            .await?
+            .select((Self::NOTE_TABLE, Self::node_scoped_id(id, node_id)))
+            .await?;
+        Ok(result.map(|n| n.note))
+    }
+
+    async fn set_note(&self, id: &str, node_id: &str, note: &str) -> Result<()> {
+        let note_id = Self::node_scoped_id(id, node_id);
+        let entity = BillNoteDb {
+            id: (Self::NOTE_TABLE, note_id.as_str()).into(),
+            bill_id: id.to_owned(),
+            node_id: node_id.to_owned(),
+            note: note.to_owned(),
+        };
+        let _: Option<BillNoteDb> = self
+            .db()
+            .await?
+            .upsert((Self::NOTE_TABLE, note_id))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_note(&self, id: &str, node_id: &str) -> Result<()> {
+        let _: Option<BillNoteDb> = self
+            .db()
+            .await?
+            .delete((Self::NOTE_TABLE, Self::node_scoped_id(id, node_id)))
+            .await?;
+        Ok(())
+    }
+
+    async fn get_view(&self, id: &str) -> Result<Option<BillView>> {
+        let result: Option<BillViewDb> = self.db().await?.select((Self::VIEW_TABLE, id)).await?;
+        Ok(result.map(|v| v.into()))
+    }
+
+    async fn save_view(&self, view: &BillView) -> Result<()> {
+        let entity: BillViewDb = view.into();
+        let _: Option<BillViewDb> = self
+            .db()
+            .await?
+            .upsert((Self::VIEW_TABLE, view.bill_id.to_owned()))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
     async fn get_bill_ids_waiting_for_payment(&self) -> Result<Vec<String>> {
         let bill_ids_paid: Vec<BillPaidDb> = self.db().await?.select(Self::PAID_TABLE).await?;
         let with_req_to_pay_bill_ids: Vec<BillIdDb> = self
@@ -196,8 +602,10 @@ impl BillStoreApi for SurrealBillStore {
     }
 
     async fn get_bill_ids_waiting_for_sell_payment(&self) -> Result<Vec<String>> {
-        let timestamp_now_minus_payment_deadline =
-            util::date::now().timestamp() - PAYMENT_DEADLINE_SECONDS as i64;
+        // the sell deadline of an offer is only known from the encrypted chain, so the longest
+        // possible deadline is used here and the caller checks the deadline of the offer itself
+        let timestamp_now_minus_sell_deadline =
+            util::date::now().timestamp() - MAX_PAYMENT_DEADLINE_SECONDS as i64;
         let query = r#"SELECT bill_id FROM 
             (SELECT bill_id, math::max(block_id) as block_id, op_code, timestamp FROM type::table($table) GROUP BY bill_id)
             .map(|$v| {
@@ -209,7 +617,7 @@ impl BillStoreApi for SurrealBillStore {
             .await?
             .query(query)
             .bind((DB_TABLE, Self::CHAIN_TABLE))
-            .bind((DB_TIMESTAMP, timestamp_now_minus_payment_deadline))
+            .bind((DB_TIMESTAMP, timestamp_now_minus_sell_deadline))
             .bind((DB_OP_CODE, BillOpCode::OfferToSell))
             .await?
             .take(0)?;
@@ -261,6 +669,25 @@ pub struct BitcreditBillResultDb {
     pub data: BillDataDb,
     pub status: BillStatusDb,
     pub current_waiting_state: Option<BillCurrentWaitingStateDb>,
+    /// When the entry was last read, or written, in milliseconds - used for LRU eviction
+    #[serde(default)]
+    pub last_accessed: i64,
+}
+
+impl BitcreditBillResultDb {
+    /// Cache entries from before the chain summary, or the settlement status were cached have to
+    /// be recalculated
+    fn is_outdated(&self) -> bool {
+        self.status.last_op_code.is_none() || self.status.settlement_status.is_none()
+    }
+
+    /// Cache entries calculated from fewer blocks than the chain has by now have to be
+    /// recalculated - this way, a missed invalidation doesn't lead to stale bills
+    fn is_stale(&self, chain_heights: &HashMap<String, u64>) -> bool {
+        chain_heights
+            .get(&self.id.id.to_raw())
+            .is_some_and(|height| *height > self.status.block_height)
+    }
 }
 
 impl From<BitcreditBillResultDb> for BitcreditBillResult {
@@ -283,6 +710,7 @@ impl From<&BitcreditBillResult> for BitcreditBillResultDb {
             data: (&value.data).into(),
             status: (&value.status).into(),
             current_waiting_state: value.current_waiting_state.as_ref().map(|cws| cws.into()),
+            last_accessed: 0,
         }
     }
 }
@@ -332,6 +760,8 @@ pub struct BillWaitingForSellStateDb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    #[serde(default)]
+    pub fee_estimate: Option<FeeEstimateDb>,
 }
 
 impl From<BillWaitingForSellStateDb> for BillWaitingForSellState {
@@ -345,6 +775,7 @@ impl From<BillWaitingForSellStateDb> for BillWaitingForSellState {
             link_to_pay: value.link_to_pay,
             address_to_pay: value.address_to_pay,
             mempool_link_for_address_to_pay: value.mempool_link_for_address_to_pay,
+            fee_estimate: value.fee_estimate.map(|f| f.into()),
         }
     }
 }
@@ -360,6 +791,7 @@ impl From<&BillWaitingForSellState> for BillWaitingForSellStateDb {
             link_to_pay: value.link_to_pay.clone(),
             address_to_pay: value.address_to_pay.clone(),
             mempool_link_for_address_to_pay: value.mempool_link_for_address_to_pay.clone(),
+            fee_estimate: value.fee_estimate.as_ref().map(|f| f.into()),
         }
     }
 }
@@ -374,6 +806,8 @@ pub struct BillWaitingForPaymentStateDb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    #[serde(default)]
+    pub fee_estimate: Option<FeeEstimateDb>,
 }
 
 impl From<BillWaitingForPaymentStateDb> for BillWaitingForPaymentState {
@@ -387,6 +821,7 @@ impl From<BillWaitingForPaymentStateDb> for BillWaitingForPaymentState {
             link_to_pay: value.link_to_pay,
             address_to_pay: value.address_to_pay,
             mempool_link_for_address_to_pay: value.mempool_link_for_address_to_pay,
+            fee_estimate: value.fee_estimate.map(|f| f.into()),
         }
     }
 }
@@ -402,6 +837,7 @@ impl From<&BillWaitingForPaymentState> for BillWaitingForPaymentStateDb {
             link_to_pay: value.link_to_pay.clone(),
             address_to_pay: value.address_to_pay.clone(),
             mempool_link_for_address_to_pay: value.mempool_link_for_address_to_pay.clone(),
+            fee_estimate: value.fee_estimate.as_ref().map(|f| f.into()),
         }
     }
 }
@@ -416,6 +852,8 @@ pub struct BillWaitingForRecourseStateDb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    #[serde(default)]
+    pub fee_estimate: Option<FeeEstimateDb>,
 }
 
 impl From<BillWaitingForRecourseStateDb> for BillWaitingForRecourseState {
@@ -429,6 +867,7 @@ impl From<BillWaitingForRecourseStateDb> for BillWaitingForRecourseState {
             link_to_pay: value.link_to_pay,
             address_to_pay: value.address_to_pay,
             mempool_link_for_address_to_pay: value.mempool_link_for_address_to_pay,
+            fee_estimate: value.fee_estimate.map(|f| f.into()),
         }
     }
 }
@@ -444,6 +883,31 @@ impl From<&BillWaitingForRecourseState> for BillWaitingForRecourseStateDb {
             link_to_pay: value.link_to_pay.clone(),
             address_to_pay: value.address_to_pay.clone(),
             mempool_link_for_address_to_pay: value.mempool_link_for_address_to_pay.clone(),
+            fee_estimate: value.fee_estimate.as_ref().map(|f| f.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeEstimateDb {
+    pub fee_rate_sat_vb: u64,
+    pub estimated_confirmation_seconds: u64,
+}
+
+impl From<FeeEstimateDb> for FeeEstimate {
+    fn from(value: FeeEstimateDb) -> Self {
+        Self {
+            fee_rate_sat_vb: value.fee_rate_sat_vb,
+            estimated_confirmation_seconds: value.estimated_confirmation_seconds,
+        }
+    }
+}
+
+impl From<&FeeEstimate> for FeeEstimateDb {
+    fn from(value: &FeeEstimate) -> Self {
+        Self {
+            fee_rate_sat_vb: value.fee_rate_sat_vb,
+            estimated_confirmation_seconds: value.estimated_confirmation_seconds,
         }
     }
 }
@@ -456,6 +920,15 @@ pub struct BillStatusDb {
     pub recourse: BillRecourseStatusDb,
     pub redeemed_funds_available: bool,
     pub has_requested_funds: bool,
+    #[serde(default)]
+    pub hold: Option<BillHoldStatusDb>,
+    /// Not set for bills cached before it was introduced - these are treated as not cached
+    #[serde(default)]
+    pub last_op_code: Option<BillOpCode>,
+    #[serde(default)]
+    pub block_height: u64,
+    #[serde(default)]
+    pub settlement_status: Option<SettlementStatus>,
 }
 
 impl From<BillStatusDb> for BillStatus {
@@ -467,6 +940,10 @@ impl From<BillStatusDb> for BillStatus {
             recourse: value.recourse.into(),
             redeemed_funds_available: value.redeemed_funds_available,
             has_requested_funds: value.has_requested_funds,
+            hold: value.hold.map(|h| h.into()),
+            last_op_code: value.last_op_code.unwrap_or(BillOpCode::Issue),
+            block_height: value.block_height,
+            settlement_status: value.settlement_status.unwrap_or(SettlementStatus::Active),
         }
     }
 }
@@ -480,6 +957,34 @@ impl From<&BillStatus> for BillStatusDb {
             recourse: (&value.recourse).into(),
             redeemed_funds_available: value.redeemed_funds_available,
             has_requested_funds: value.has_requested_funds,
+            hold: value.hold.as_ref().map(|h| h.into()),
+            last_op_code: Some(value.last_op_code.clone()),
+            block_height: value.block_height,
+            settlement_status: Some(value.settlement_status),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillHoldStatusDb {
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+impl From<BillHoldStatusDb> for BillHold {
+    fn from(value: BillHoldStatusDb) -> Self {
+        Self {
+            reason: value.reason,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+impl From<&BillHold> for BillHoldStatusDb {
+    fn from(value: &BillHold) -> Self {
+        Self {
+            reason: value.reason.clone(),
+            timestamp: value.timestamp,
         }
     }
 }
@@ -491,6 +996,8 @@ pub struct BillAcceptanceStatusDb {
     pub accepted: bool,
     pub request_to_accept_timed_out: bool,
     pub rejected_to_accept: bool,
+    #[serde(default)]
+    pub rejected_to_accept_reason: Option<String>,
 }
 
 impl From<BillAcceptanceStatusDb> for BillAcceptanceStatus {
@@ -501,6 +1008,7 @@ impl From<BillAcceptanceStatusDb> for BillAcceptanceStatus {
             accepted: value.accepted,
             request_to_accept_timed_out: value.request_to_accept_timed_out,
             rejected_to_accept: value.rejected_to_accept,
+            rejected_to_accept_reason: value.rejected_to_accept_reason,
         }
     }
 }
@@ -513,6 +1021,7 @@ impl From<&BillAcceptanceStatus> for BillAcceptanceStatusDb {
             accepted: value.accepted,
             request_to_accept_timed_out: value.request_to_accept_timed_out,
             rejected_to_accept: value.rejected_to_accept,
+            rejected_to_accept_reason: value.rejected_to_accept_reason.clone(),
         }
     }
 }
@@ -522,8 +1031,14 @@ pub struct BillPaymentStatusDb {
     pub time_of_request_to_pay: Option<u64>,
     pub requested_to_pay: bool,
     pub paid: bool,
+    #[serde(default)]
+    pub paid_sum: u64,
     pub request_to_pay_timed_out: bool,
     pub rejected_to_pay: bool,
+    #[serde(default)]
+    pub rejected_to_pay_reason: Option<String>,
+    #[serde(default)]
+    pub external_payment_reference: Option<String>,
 }
 
 impl From<BillPaymentStatusDb> for BillPaymentStatus {
@@ -532,8 +1047,11 @@ impl From<BillPaymentStatusDb> for BillPaymentStatus {
             time_of_request_to_pay: value.time_of_request_to_pay,
             requested_to_pay: value.requested_to_pay,
             paid: value.paid,
+            paid_sum: value.paid_sum,
             request_to_pay_timed_out: value.request_to_pay_timed_out,
             rejected_to_pay: value.rejected_to_pay,
+            rejected_to_pay_reason: value.rejected_to_pay_reason,
+            external_payment_reference: value.external_payment_reference,
         }
     }
 }
@@ -544,8 +1062,11 @@ impl From<&BillPaymentStatus> for BillPaymentStatusDb {
             time_of_request_to_pay: value.time_of_request_to_pay,
             requested_to_pay: value.requested_to_pay,
             paid: value.paid,
+            paid_sum: value.paid_sum,
             request_to_pay_timed_out: value.request_to_pay_timed_out,
             rejected_to_pay: value.rejected_to_pay,
+            rejected_to_pay_reason: value.rejected_to_pay_reason.clone(),
+            external_payment_reference: value.external_payment_reference.clone(),
         }
     }
 }
@@ -557,6 +1078,10 @@ pub struct BillSellStatusDb {
     pub offered_to_sell: bool,
     pub offer_to_sell_timed_out: bool,
     pub rejected_offer_to_sell: bool,
+    #[serde(default)]
+    pub rejected_offer_to_sell_reason: Option<String>,
+    #[serde(default)]
+    pub sell_deadline_seconds: Option<u64>,
 }
 
 impl From<BillSellStatusDb> for BillSellStatus {
@@ -567,6 +1092,8 @@ impl From<BillSellStatusDb> for BillSellStatus {
             offered_to_sell: value.offered_to_sell,
             offer_to_sell_timed_out: value.offer_to_sell_timed_out,
             rejected_offer_to_sell: value.rejected_offer_to_sell,
+            rejected_offer_to_sell_reason: value.rejected_offer_to_sell_reason,
+            sell_deadline_seconds: value.sell_deadline_seconds,
         }
     }
 }
@@ -579,6 +1106,8 @@ impl From<&BillSellStatus> for BillSellStatusDb {
             offered_to_sell: value.offered_to_sell,
             offer_to_sell_timed_out: value.offer_to_sell_timed_out,
             rejected_offer_to_sell: value.rejected_offer_to_sell,
+            rejected_offer_to_sell_reason: value.rejected_offer_to_sell_reason.clone(),
+            sell_deadline_seconds: value.sell_deadline_seconds,
         }
     }
 }
@@ -630,6 +1159,75 @@ pub struct BillDataDb {
     pub currency: String,
     pub sum: String,
     pub files: Vec<FileDb>,
+    #[serde(default)]
+    pub tampered_files: Vec<String>,
+    #[serde(default = "default_payment_deadline_seconds")]
+    pub payment_deadline_seconds: u64,
+    #[serde(default)]
+    pub fiat_amount: Option<BillFiatAmountDb>,
+    #[serde(default)]
+    pub acknowledgements: Vec<BillAcknowledgementDb>,
+}
+
+fn default_payment_deadline_seconds() -> u64 {
+    PAYMENT_DEADLINE_SECONDS
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillFiatAmountDb {
+    pub currency: String,
+    pub sum: String,
+    pub btc_rate: f64,
+    pub rate_timestamp: u64,
+}
+
+impl From<BillFiatAmountDb> for BillFiatAmount {
+    fn from(value: BillFiatAmountDb) -> Self {
+        Self {
+            currency: value.currency,
+            sum: value.sum,
+            btc_rate: value.btc_rate,
+            rate_timestamp: value.rate_timestamp,
+        }
+    }
+}
+
+impl From<&BillFiatAmount> for BillFiatAmountDb {
+    fn from(value: &BillFiatAmount) -> Self {
+        Self {
+            currency: value.currency.clone(),
+            sum: value.sum.clone(),
+            btc_rate: value.btc_rate,
+            rate_timestamp: value.rate_timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillAcknowledgementDb {
+    pub node_id: String,
+    pub name: String,
+    pub timestamp: u64,
+}
+
+impl From<BillAcknowledgementDb> for BillAcknowledgement {
+    fn from(value: BillAcknowledgementDb) -> Self {
+        Self {
+            node_id: value.node_id,
+            name: value.name,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+impl From<&BillAcknowledgement> for BillAcknowledgementDb {
+    fn from(value: &BillAcknowledgement) -> Self {
+        Self {
+            node_id: value.node_id.clone(),
+            name: value.name.clone(),
+            timestamp: value.timestamp,
+        }
+    }
 }
 
 impl From<BillDataDb> for BillData {
@@ -647,7 +1245,16 @@ impl From<BillDataDb> for BillData {
             currency: value.currency,
             sum: value.sum,
             files: value.files.iter().map(|f| f.to_owned().into()).collect(),
+            tampered_files: value.tampered_files,
+            payment_deadline_seconds: value.payment_deadline_seconds,
+            fiat_amount: value.fiat_amount.map(|f| f.into()),
             active_notification: None,
+            note: None,
+            acknowledgements: value
+                .acknowledgements
+                .into_iter()
+                .map(|a| a.into())
+                .collect(),
         }
     }
 }
@@ -667,6 +1274,10 @@ impl From<&BillData> for BillDataDb {
             currency: value.currency.clone(),
             sum: value.sum.clone(),
             files: value.files.iter().map(|f| f.clone().into()).collect(),
+            tampered_files: value.tampered_files.clone(),
+            payment_deadline_seconds: value.payment_deadline_seconds,
+            fiat_amount: value.fiat_amount.as_ref().map(|f| f.into()),
+            acknowledgements: value.acknowledgements.iter().map(|a| a.into()).collect(),
         }
     }
 }
@@ -746,16 +1357,118 @@ pub struct BillPaidDb {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BillIdDb {
-    pub bill_id: String,
+pub struct BillPaidSumDb {
+    pub id: Thing,
+    pub payment_address: String,
+    pub paid_sum: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BillKeysDb {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<Thing>,
-    pub public_key: String,
-    pub private_key: String,
+pub struct BillHoldDb {
+    pub id: Thing,
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+impl From<BillHoldDb> for BillHold {
+    fn from(value: BillHoldDb) -> Self {
+        Self {
+            reason: value.reason,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillArchiveDb {
+    pub id: Thing,
+    pub bill_id: String,
+    pub node_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillNoteDb {
+    pub id: Thing,
+    pub bill_id: String,
+    pub node_id: String,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillViewDb {
+    pub bill_id: String,
+    pub public_key: String,
+    pub private_key: String,
+    pub blocks: Vec<BillBlockDb>,
+    pub view_data: Vec<String>,
+}
+
+impl From<BillViewDb> for BillView {
+    fn from(value: BillViewDb) -> Self {
+        Self {
+            bill_id: value.bill_id,
+            view_key: BillViewKey {
+                public_key: value.public_key,
+                private_key: value.private_key,
+            },
+            blocks: value.blocks.into_iter().map(|b| b.into()).collect(),
+            view_data: value.view_data,
+        }
+    }
+}
+
+impl From<&BillView> for BillViewDb {
+    fn from(value: &BillView) -> Self {
+        Self {
+            bill_id: value.bill_id.clone(),
+            public_key: value.view_key.public_key.clone(),
+            private_key: value.view_key.private_key.clone(),
+            blocks: value.blocks.iter().map(|b| b.into()).collect(),
+            view_data: value.view_data.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillIdempotencyDb {
+    pub id: Thing,
+    pub bill_id: String,
+    pub op_code: BillOpCode,
+    pub idempotency_key: String,
+    /// None, while the action of the reserved key is executed
+    pub block_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillParticipantDb {
+    pub id: Thing,
+    pub bill_id: String,
+    pub node_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillIdDb {
+    pub bill_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillChainHeightDb {
+    pub bill_id: String,
+    pub block_height: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillCacheAccessDb {
+    pub id: Thing,
+    pub last_accessed: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillKeysDb {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Thing>,
+    pub public_key: String,
+    pub private_key: String,
 }
 
 impl From<BillKeysDb> for BillKeys {
@@ -781,7 +1494,7 @@ impl From<&BillKeys> for BillKeysDb {
 pub mod tests {
     use std::collections::HashSet;
 
-    use super::SurrealBillStore;
+    use super::{BitcreditBillResultDb, SurrealBillStore};
     use crate::{
         bill::{BillChainStoreApi, BillStoreApi},
         db::{bill_chain::SurrealBillChainStore, get_memory_db},
@@ -792,7 +1505,7 @@ pub mod tests {
         util::{self, BcrKeys},
     };
     use bcr_ebill_core::{
-        bill::BillKeys,
+        bill::{BillHold, BillKeys, BillView, BillViewKey},
         blockchain::bill::{
             BillBlock, BillOpCode,
             block::{
@@ -900,6 +1613,42 @@ pub mod tests {
         assert_eq!(get_res.as_ref().unwrap().private_key, TEST_PRIVATE_KEY_SECP);
     }
 
+    #[tokio::test]
+    async fn test_remove_keys() {
+        let store = get_store(get_db().await).await;
+        let keys = BillKeys {
+            private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+            public_key: TEST_PUB_KEY_SECP.to_owned(),
+        };
+        store.save_keys("1234", &keys).await.unwrap();
+        store.remove_keys("1234").await.unwrap();
+        assert!(store.get_keys("1234").await.is_err());
+        // the keys can be saved again afterwards
+        assert!(store.save_keys("1234", &keys).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_key_ids() {
+        let store = get_store(get_db().await).await;
+        let get_res_empty = store.get_key_ids().await;
+        assert!(get_res_empty.is_ok());
+        assert!(get_res_empty.as_ref().unwrap().is_empty());
+
+        store
+            .save_keys(
+                "1234",
+                &BillKeys {
+                    private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+                    public_key: TEST_PUB_KEY_SECP.to_owned(),
+                },
+            )
+            .await
+            .unwrap();
+        let get_res = store.get_key_ids().await;
+        assert!(get_res.is_ok());
+        assert_eq!(get_res.as_ref().unwrap(), &vec!["1234".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_paid() {
         let store = get_store(get_db().await).await;
@@ -926,6 +1675,228 @@ pub mod tests {
         assert!(!get_res_not_paid.as_ref().unwrap());
     }
 
+    #[tokio::test]
+    async fn test_archive() {
+        let store = get_store(get_db().await).await;
+        let get_res_none = store.get_archived_bill_ids("node_1").await;
+        assert!(get_res_none.is_ok());
+        assert!(get_res_none.as_ref().unwrap().is_empty());
+
+        let res = store.archive("1234", "node_1").await;
+        assert!(res.is_ok());
+        // archiving again is a no-op
+        let res_again = store.archive("1234", "node_1").await;
+        assert!(res_again.is_ok());
+        let get_res = store.get_archived_bill_ids("node_1").await;
+        assert!(get_res.is_ok());
+        assert_eq!(get_res.as_ref().unwrap(), &vec!["1234".to_string()]);
+
+        // archive state is scoped per node id
+        let get_res_other = store.get_archived_bill_ids("node_2").await;
+        assert!(get_res_other.is_ok());
+        assert!(get_res_other.as_ref().unwrap().is_empty());
+
+        let res_unarchive = store.unarchive("1234", "node_1").await;
+        assert!(res_unarchive.is_ok());
+        let get_res_unarchived = store.get_archived_bill_ids("node_1").await;
+        assert!(get_res_unarchived.is_ok());
+        assert!(get_res_unarchived.as_ref().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_note() {
+        let store = get_store(get_db().await).await;
+        let get_res_none = store.get_note("1234", "node_1").await;
+        assert!(get_res_none.is_ok());
+        assert!(get_res_none.as_ref().unwrap().is_none());
+
+        let res = store.set_note("1234", "node_1", "called customer").await;
+        assert!(res.is_ok());
+        let res_again = store.set_note("1234", "node_1", "promised payment").await;
+        assert!(res_again.is_ok());
+        let get_res = store.get_note("1234", "node_1").await;
+        assert!(get_res.is_ok());
+        assert_eq!(get_res.unwrap(), Some("promised payment".to_string()));
+
+        // notes are scoped per node id
+        let get_res_other = store.get_note("1234", "node_2").await;
+        assert!(get_res_other.is_ok());
+        assert!(get_res_other.as_ref().unwrap().is_none());
+
+        let res_remove = store.remove_note("1234", "node_1").await;
+        assert!(res_remove.is_ok());
+        let get_res_removed = store.get_note("1234", "node_1").await;
+        assert!(get_res_removed.is_ok());
+        assert!(get_res_removed.as_ref().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_view() {
+        let store = get_store(get_db().await).await;
+        let get_res_none = store.get_view("1234").await;
+        assert!(get_res_none.is_ok());
+        assert!(get_res_none.as_ref().unwrap().is_none());
+
+        let view = BillView {
+            bill_id: "1234".to_string(),
+            view_key: BillViewKey::new(&get_bill_keys().public_key, &BcrKeys::new()),
+            blocks: vec![get_first_block("1234")],
+            view_data: vec!["view_data".to_string()],
+        };
+        let res = store.save_view(&view).await;
+        assert!(res.is_ok());
+        let get_res = store.get_view("1234").await;
+        assert!(get_res.is_ok());
+        assert_eq!(get_res.unwrap(), Some(view));
+        // a view doesn't make the bill exist locally
+        assert!(!store.exists("1234").await);
+    }
+
+    #[tokio::test]
+    async fn test_paid_sum() {
+        let store = get_store(get_db().await).await;
+        let get_res_none = store.get_paid_sum("1234").await;
+        assert!(get_res_none.is_ok());
+        assert_eq!(get_res_none.as_ref().unwrap(), &0);
+
+        let res = store
+            .set_paid_sum("1234", "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk", 500)
+            .await;
+        assert!(res.is_ok());
+        let get_res = store.get_paid_sum("1234").await;
+        assert!(get_res.is_ok());
+        assert_eq!(get_res.as_ref().unwrap(), &500);
+
+        // update with more accumulated funds
+        let res_again = store
+            .set_paid_sum("1234", "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk", 1500)
+            .await;
+        assert!(res_again.is_ok());
+        let get_res_again = store.get_paid_sum("1234").await;
+        assert!(get_res_again.is_ok());
+        assert_eq!(get_res_again.as_ref().unwrap(), &1500);
+    }
+
+    #[tokio::test]
+    async fn test_hold() {
+        let store = get_store(get_db().await).await;
+        let get_res_no_hold = store.get_hold("1234").await;
+        assert!(get_res_no_hold.is_ok());
+        assert!(get_res_no_hold.as_ref().unwrap().is_none());
+
+        let res = store
+            .set_hold(
+                "1234",
+                &BillHold {
+                    reason: "disputed".to_string(),
+                    timestamp: 1731593928,
+                },
+            )
+            .await;
+        assert!(res.is_ok());
+        let get_res = store.get_hold("1234").await;
+        assert!(get_res.is_ok());
+        assert_eq!(
+            get_res.as_ref().unwrap().as_ref().unwrap().reason,
+            "disputed".to_string()
+        );
+
+        // different bill isn't on hold
+        let get_res_other = store.get_hold("4321").await;
+        assert!(get_res_other.is_ok());
+        assert!(get_res_other.as_ref().unwrap().is_none());
+
+        let res_clear = store.clear_hold("1234").await;
+        assert!(res_clear.is_ok());
+        let get_res_cleared = store.get_hold("1234").await;
+        assert!(get_res_cleared.is_ok());
+        assert!(get_res_cleared.as_ref().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key() {
+        let store = get_store(get_db().await).await;
+        let get_res_none = store
+            .get_idempotent_block_id("1234", &BillOpCode::Endorse, "key")
+            .await;
+        assert!(get_res_none.is_ok());
+        assert!(get_res_none.as_ref().unwrap().is_none());
+
+        let res = store
+            .save_idempotency_key("1234", &BillOpCode::Endorse, "key", 2)
+            .await;
+        assert!(res.is_ok());
+        let get_res = store
+            .get_idempotent_block_id("1234", &BillOpCode::Endorse, "key")
+            .await;
+        assert!(get_res.is_ok());
+        assert_eq!(get_res.as_ref().unwrap(), &Some(2));
+
+        // the key is scoped per bill and action
+        let get_res_other_bill = store
+            .get_idempotent_block_id("4321", &BillOpCode::Endorse, "key")
+            .await;
+        assert!(get_res_other_bill.is_ok());
+        assert!(get_res_other_bill.as_ref().unwrap().is_none());
+        let get_res_other_action = store
+            .get_idempotent_block_id("1234", &BillOpCode::OfferToSell, "key")
+            .await;
+        assert!(get_res_other_action.is_ok());
+        assert!(get_res_other_action.as_ref().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reserve_idempotency_key() {
+        let store = get_store(get_db().await).await;
+        let reserved = store
+            .reserve_idempotency_key("1234", &BillOpCode::Endorse, "key")
+            .await;
+        assert!(reserved.unwrap());
+        // only the first caller gets the reservation
+        let reserved_again = store
+            .reserve_idempotency_key("1234", &BillOpCode::Endorse, "key")
+            .await;
+        assert!(!reserved_again.unwrap());
+        // a reserved key has no block yet
+        let get_res = store
+            .get_idempotent_block_id("1234", &BillOpCode::Endorse, "key")
+            .await;
+        assert!(get_res.unwrap().is_none());
+
+        store
+            .save_idempotency_key("1234", &BillOpCode::Endorse, "key", 2)
+            .await
+            .unwrap();
+        let get_res_saved = store
+            .get_idempotent_block_id("1234", &BillOpCode::Endorse, "key")
+            .await;
+        assert_eq!(get_res_saved.unwrap(), Some(2));
+
+        // a key with a recorded block isn't released
+        store
+            .release_idempotency_key("1234", &BillOpCode::Endorse, "key")
+            .await
+            .unwrap();
+        let get_res_after_release = store
+            .get_idempotent_block_id("1234", &BillOpCode::Endorse, "key")
+            .await;
+        assert_eq!(get_res_after_release.unwrap(), Some(2));
+
+        // a released key can be reserved again
+        store
+            .reserve_idempotency_key("1234", &BillOpCode::OfferToSell, "key")
+            .await
+            .unwrap();
+        store
+            .release_idempotency_key("1234", &BillOpCode::OfferToSell, "key")
+            .await
+            .unwrap();
+        let reserved_after_release = store
+            .reserve_idempotency_key("1234", &BillOpCode::OfferToSell, "key")
+            .await;
+        assert!(reserved_after_release.unwrap());
+    }
+
     #[tokio::test]
     async fn test_bills_waiting_for_payment() {
         let db = get_db().await;
@@ -1011,6 +1982,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: now,
                 signing_address: empty_address(),
+                sell_deadline_seconds: None,
             },
             &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
             None,
@@ -1064,6 +2036,55 @@ pub mod tests {
 
     #[tokio::test]
     async fn test_bills_waiting_for_payment_offer_to_sell_expired() {
+        let db = get_db().await;
+        let chain_store = get_chain_store(db.clone()).await;
+        let store = get_store(db.clone()).await;
+        let now_minus_more_than_a_year = util::date::now()
+            .checked_sub_months(Months::new(13))
+            .unwrap()
+            .timestamp() as u64;
+
+        let first_block = get_first_block("1234");
+        chain_store
+            .add_block("4321", &get_first_block("4321"))
+            .await
+            .unwrap(); // not returned, no offer to sell block
+        chain_store.add_block("1234", &first_block).await.unwrap();
+        let second_block = BillBlock::create_block_for_offer_to_sell(
+            "1234".to_string(),
+            &first_block,
+            &BillOfferToSellBlockData {
+                seller: identity_public_data_only_node_id(
+                    BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP)
+                        .unwrap()
+                        .get_public_key(),
+                )
+                .into(),
+                buyer: identity_public_data_only_node_id(BcrKeys::new().get_public_key()).into(),
+                currency: "sat".to_string(),
+                sum: 15000,
+                payment_address: "tb1qteyk7pfvvql2r2zrsu4h4xpvju0nz7ykvguyk".to_string(),
+                signatory: None,
+                signing_timestamp: now_minus_more_than_a_year,
+                signing_address: empty_address(),
+                sell_deadline_seconds: None,
+            },
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            None,
+            &BcrKeys::from_private_key(&get_bill_keys().private_key).unwrap(),
+            now_minus_more_than_a_year,
+        )
+        .unwrap();
+        chain_store.add_block("1234", &second_block).await.unwrap();
+
+        // nothing gets returned, because the offer to sell is expired for any payment deadline
+        let res = store.get_bill_ids_waiting_for_sell_payment().await;
+        assert!(res.is_ok());
+        assert_eq!(res.as_ref().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_bills_waiting_for_payment_offer_to_sell_past_default_deadline() {
         let db = get_db().await;
         let chain_store = get_chain_store(db.clone()).await;
         let store = get_store(db.clone()).await;
@@ -1095,6 +2116,7 @@ pub mod tests {
                 signatory: None,
                 signing_timestamp: now_minus_one_month,
                 signing_address: empty_address(),
+                sell_deadline_seconds: None,
             },
             &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
             None,
@@ -1104,10 +2126,10 @@ pub mod tests {
         .unwrap();
         chain_store.add_block("1234", &second_block).await.unwrap();
 
-        // nothing gets returned, because the offer to sell is expired
+        // still returned, since the bill could have been issued with a longer payment deadline
         let res = store.get_bill_ids_waiting_for_sell_payment().await;
         assert!(res.is_ok());
-        assert_eq!(res.as_ref().unwrap().len(), 0);
+        assert_eq!(res.as_ref().unwrap().len(), 1);
     }
 
     #[tokio::test]
@@ -1418,4 +2440,184 @@ pub mod tests {
             .expect("could not fetch from cache");
         assert_eq!(cached_bills_after_invalidate.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_participant_index() {
+        let db = get_db().await;
+        let chain_store = get_chain_store(db.clone()).await;
+        let store = get_store(db.clone()).await;
+        chain_store
+            .add_block("1234", &get_first_block("1234"))
+            .await
+            .unwrap();
+        chain_store
+            .add_block("4321", &get_first_block("4321"))
+            .await
+            .unwrap();
+        // not cached yet, so not indexed
+        let unindexed = store.get_unindexed_bill_ids().await.unwrap();
+        assert_eq!(unindexed.len(), 2);
+        assert!(
+            store
+                .get_bill_ids_by_participant("drawee")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let mut bill = cached_bill("1234".to_string());
+        bill.participants.all_participant_node_ids =
+            vec!["drawee".to_string(), "drawer".to_string()];
+        store.save_bill_to_cache("1234", &bill).await.unwrap();
+        // saving again doesn't duplicate index entries
+        store.save_bill_to_cache("1234", &bill).await.unwrap();
+
+        assert_eq!(
+            store.get_bill_ids_by_participant("drawee").await.unwrap(),
+            vec!["1234".to_string()]
+        );
+        assert!(
+            store
+                .get_bill_ids_by_participant("payee")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            store.get_unindexed_bill_ids().await.unwrap(),
+            vec!["4321".to_string()]
+        );
+
+        // the index survives cache invalidation
+        store.invalidate_bill_in_cache("1234").await.unwrap();
+        assert_eq!(
+            store.get_bill_ids_by_participant("drawer").await.unwrap(),
+            vec!["1234".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn bill_caching_skips_outdated_entries() {
+        let db = get_db().await;
+        let store = get_store(db.clone()).await;
+        let bill = cached_bill("1234".to_string());
+        let mut entity: BitcreditBillResultDb = (&bill).into();
+        entity.status.last_op_code = None;
+        let _: Option<BitcreditBillResultDb> = db
+            .upsert((SurrealBillStore::CACHE_TABLE, "1234"))
+            .content(entity)
+            .await
+            .expect("could not save outdated bill to cache");
+
+        let cached_bill = store
+            .get_bill_from_cache("1234")
+            .await
+            .expect("could not fetch from cache");
+        assert!(cached_bill.is_none());
+        let cached_bills = store
+            .get_bills_from_cache(&["1234".to_string()])
+            .await
+            .expect("could not fetch from cache");
+        assert!(cached_bills.is_empty());
+
+        // once re-cached, the bill carries the chain summary
+        store
+            .save_bill_to_cache("1234", &bill)
+            .await
+            .expect("could not save bill to cache");
+        let cached_bill = store
+            .get_bill_from_cache("1234")
+            .await
+            .expect("could not fetch from cache")
+            .expect("bill is cached");
+        assert_eq!(cached_bill.status.last_op_code, BillOpCode::Issue);
+        assert_eq!(cached_bill.status.block_height, 1);
+    }
+
+    #[tokio::test]
+    async fn bill_caching_bypasses_stale_entries() {
+        let db = get_db().await;
+        let chain_store = get_chain_store(db.clone()).await;
+        let store = get_store(db.clone()).await;
+        let first_block = get_first_block("1234");
+        chain_store.add_block("1234", &first_block).await.unwrap();
+
+        // cached at the current height of the chain
+        let mut bill = cached_bill("1234".to_string());
+        store.save_bill_to_cache("1234", &bill).await.unwrap();
+        assert!(store.get_bill_from_cache("1234").await.unwrap().is_some());
+
+        // the chain grows without the cache being invalidated
+        chain_store
+            .add_block(
+                "1234",
+                &request_to_accept_block("1234", first_block.timestamp + 1, &first_block),
+            )
+            .await
+            .unwrap();
+        assert!(store.get_bill_from_cache("1234").await.unwrap().is_none());
+        assert!(
+            store
+                .get_bills_from_cache(&["1234".to_string()])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // once recalculated at the new height, the bill is served from the cache again
+        bill.status.block_height = 2;
+        bill.status.last_op_code = BillOpCode::RequestToAccept;
+        store.save_bill_to_cache("1234", &bill).await.unwrap();
+        let cached_bill = store
+            .get_bill_from_cache("1234")
+            .await
+            .unwrap()
+            .expect("bill is cached");
+        assert_eq!(cached_bill.status.block_height, 2);
+        assert_eq!(
+            store
+                .get_bills_from_cache(&["1234".to_string()])
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn bill_caching_evicts_least_recently_used_entries() {
+        let db = get_db().await;
+        let store = get_store(db.clone()).await.with_max_cache_size(2);
+        for (idx, id) in ["1234", "4321"].into_iter().enumerate() {
+            let mut entity: BitcreditBillResultDb = (&cached_bill(id.to_string())).into();
+            entity.last_accessed = idx as i64 + 1;
+            let _: Option<BitcreditBillResultDb> = db
+                .upsert((SurrealBillStore::CACHE_TABLE, id))
+                .content(entity)
+                .await
+                .expect("could not save bill to cache");
+        }
+        // reading the older entry makes it the most recently used one
+        assert!(store.get_bill_from_cache("1234").await.unwrap().is_some());
+
+        store
+            .save_bill_to_cache("5678", &cached_bill("5678".to_string()))
+            .await
+            .unwrap();
+
+        assert!(store.get_bill_from_cache("4321").await.unwrap().is_none());
+        assert_eq!(
+            store
+                .get_bills_from_cache(&["1234".to_string(), "4321".to_string(), "5678".to_string()])
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // a full wipe still removes everything
+        store.clear_bill_cache().await.unwrap();
+        assert!(store.get_bill_from_cache("1234").await.unwrap().is_none());
+        assert!(store.get_bill_from_cache("5678").await.unwrap().is_none());
+    }
 }
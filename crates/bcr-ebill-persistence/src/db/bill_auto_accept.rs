@@ -0,0 +1,254 @@
+use super::Result;
+#[cfg(target_arch = "wasm32")]
+use super::get_new_surreal_db;
+use crate::bill::BillAutoAcceptStoreApi;
+use crate::constants::{DB_NODE_ID, DB_TABLE};
+use async_trait::async_trait;
+use bcr_ebill_core::bill::{BillAutoAcceptRule, QueuedAutoAccept};
+use serde::{Deserialize, Serialize};
+use surrealdb::{Surreal, engine::any::Any, sql::Thing};
+
+#[derive(Clone)]
+pub struct SurrealBillAutoAcceptStore {
+    #[allow(dead_code)]
+    db: Surreal<Any>,
+}
+
+impl SurrealBillAutoAcceptStore {
+    const RULE_TABLE: &'static str = "bill_auto_accept_rule";
+    const QUEUE_TABLE: &'static str = "bill_auto_accept_queue";
+
+    pub fn new(db: Surreal<Any>) -> Self {
+        Self { db }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn db(&self) -> Result<Surreal<Any>> {
+        get_new_surreal_db().await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn db(&self) -> Result<Surreal<Any>> {
+        Ok(self.db.clone())
+    }
+
+    /// A bill is queued at most once per node id, so the record id combines bill and node id
+    fn queue_id(bill_id: &str, node_id: &str) -> String {
+        format!("{bill_id}_{node_id}")
+    }
+}
+
+#[async_trait]
+impl BillAutoAcceptStoreApi for SurrealBillAutoAcceptStore {
+    async fn get_rules(&self, node_id: &str) -> Result<Vec<BillAutoAcceptRule>> {
+        let result: Vec<BillAutoAcceptRuleDb> = self
+            .db()
+            .await?
+            .query("SELECT * FROM type::table($table) WHERE node_id = $node_id")
+            .bind((DB_TABLE, Self::RULE_TABLE))
+            .bind((DB_NODE_ID, node_id.to_owned()))
+            .await?
+            .take(0)?;
+        Ok(result.into_iter().map(|r| r.into()).collect())
+    }
+
+    async fn save_rule(&self, rule: &BillAutoAcceptRule) -> Result<()> {
+        let entity: BillAutoAcceptRuleDb = rule.into();
+        let _: Option<BillAutoAcceptRuleDb> = self
+            .db()
+            .await?
+            .upsert((Self::RULE_TABLE, rule.id.to_owned()))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_rule(&self, id: &str) -> Result<()> {
+        let _: Option<BillAutoAcceptRuleDb> = self
+            .db()
+            .await?
+            .delete((Self::RULE_TABLE, id.to_owned()))
+            .await?;
+        Ok(())
+    }
+
+    async fn queue_accept(&self, queued: &QueuedAutoAccept) -> Result<()> {
+        let queue_id = Self::queue_id(&queued.bill_id, &queued.node_id);
+        let entity = QueuedAutoAcceptDb {
+            id: (Self::QUEUE_TABLE, queue_id.as_str()).into(),
+            bill_id: queued.bill_id.clone(),
+            node_id: queued.node_id.clone(),
+            rule_id: queued.rule_id.clone(),
+            timestamp: queued.timestamp,
+        };
+        let _: Option<QueuedAutoAcceptDb> = self
+            .db()
+            .await?
+            .upsert((Self::QUEUE_TABLE, queue_id))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_queued_accepts(&self) -> Result<Vec<QueuedAutoAccept>> {
+        let result: Vec<QueuedAutoAcceptDb> = self
+            .db()
+            .await?
+            .query("SELECT * FROM type::table($table) ORDER BY timestamp ASC")
+            .bind((DB_TABLE, Self::QUEUE_TABLE))
+            .await?
+            .take(0)?;
+        Ok(result.into_iter().map(|q| q.into()).collect())
+    }
+
+    async fn remove_queued_accept(&self, bill_id: &str, node_id: &str) -> Result<()> {
+        let _: Option<QueuedAutoAcceptDb> = self
+            .db()
+            .await?
+            .delete((Self::QUEUE_TABLE, Self::queue_id(bill_id, node_id)))
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillAutoAcceptRuleDb {
+    pub rule_id: String,
+    pub node_id: String,
+    pub drawer_node_id: String,
+    pub max_sum: u64,
+}
+
+impl From<BillAutoAcceptRuleDb> for BillAutoAcceptRule {
+    fn from(value: BillAutoAcceptRuleDb) -> Self {
+        Self {
+            id: value.rule_id,
+            node_id: value.node_id,
+            drawer_node_id: value.drawer_node_id,
+            max_sum: value.max_sum,
+        }
+    }
+}
+
+impl From<&BillAutoAcceptRule> for BillAutoAcceptRuleDb {
+    fn from(value: &BillAutoAcceptRule) -> Self {
+        Self {
+            rule_id: value.id.clone(),
+            node_id: value.node_id.clone(),
+            drawer_node_id: value.drawer_node_id.clone(),
+            max_sum: value.max_sum,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedAutoAcceptDb {
+    pub id: Thing,
+    pub bill_id: String,
+    pub node_id: String,
+    pub rule_id: String,
+    pub timestamp: u64,
+}
+
+impl From<QueuedAutoAcceptDb> for QueuedAutoAccept {
+    fn from(value: QueuedAutoAcceptDb) -> Self {
+        Self {
+            bill_id: value.bill_id,
+            node_id: value.node_id,
+            rule_id: value.rule_id,
+            timestamp: value.timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::get_memory_db,
+        tests::tests::{TEST_NODE_ID_SECP, TEST_PUB_KEY_SECP},
+    };
+
+    async fn get_store() -> SurrealBillAutoAcceptStore {
+        let mem_db = get_memory_db("test", "bill_auto_accept")
+            .await
+            .expect("could not create memory db");
+        SurrealBillAutoAcceptStore::new(mem_db)
+    }
+
+    fn get_rule(id: &str, node_id: &str) -> BillAutoAcceptRule {
+        BillAutoAcceptRule {
+            id: id.to_owned(),
+            node_id: node_id.to_owned(),
+            drawer_node_id: TEST_PUB_KEY_SECP.to_owned(),
+            max_sum: 1000,
+        }
+    }
+
+    fn get_queued(bill_id: &str, timestamp: u64) -> QueuedAutoAccept {
+        QueuedAutoAccept {
+            bill_id: bill_id.to_owned(),
+            node_id: TEST_NODE_ID_SECP.to_owned(),
+            rule_id: "rule_1".to_owned(),
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get_rules_of_node_id() {
+        let store = get_store().await;
+        store
+            .save_rule(&get_rule("rule_1", TEST_NODE_ID_SECP))
+            .await
+            .unwrap();
+        store
+            .save_rule(&get_rule("rule_2", TEST_PUB_KEY_SECP))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_rules(TEST_NODE_ID_SECP).await.unwrap(),
+            vec![get_rule("rule_1", TEST_NODE_ID_SECP)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_rule() {
+        let store = get_store().await;
+        store
+            .save_rule(&get_rule("rule_1", TEST_NODE_ID_SECP))
+            .await
+            .unwrap();
+        store.delete_rule("rule_1").await.unwrap();
+        assert!(store.get_rules(TEST_NODE_ID_SECP).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queue_and_remove_accepts() {
+        let store = get_store().await;
+        store
+            .queue_accept(&get_queued("bill_2", 2000))
+            .await
+            .unwrap();
+        store
+            .queue_accept(&get_queued("bill_1", 1000))
+            .await
+            .unwrap();
+        // queueing the same bill again replaces the entry
+        store
+            .queue_accept(&get_queued("bill_1", 1000))
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_queued_accepts().await.unwrap(),
+            vec![get_queued("bill_1", 1000), get_queued("bill_2", 2000)]
+        );
+        store
+            .remove_queued_accept("bill_1", TEST_NODE_ID_SECP)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get_queued_accepts().await.unwrap(),
+            vec![get_queued("bill_2", 2000)]
+        );
+    }
+}
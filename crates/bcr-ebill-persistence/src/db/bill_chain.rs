@@ -158,6 +158,13 @@ impl BillChainStoreApi for SurrealBillChainStore {
     }
 
     async fn get_chain(&self, id: &str) -> Result<BillBlockchain> {
+        let blocks = self.get_blocks(id).await?;
+        let chain = BillBlockchain::new_from_blocks(blocks)?;
+
+        Ok(chain)
+    }
+
+    async fn get_blocks(&self, id: &str) -> Result<Vec<BillBlock>> {
         let result: Vec<BillBlockDb> = self
             .db()
             .await?
@@ -168,15 +175,55 @@ impl BillChainStoreApi for SurrealBillChainStore {
             .bind((DB_BILL_ID, id.to_owned()))
             .await
             .map_err(|e| {
-                log::error!("Get Bill Chain: {e}");
+                log::error!("Get Bill Blocks: {e}");
                 e
             })?
             .take(0)?;
 
-        let blocks: Vec<BillBlock> = result.into_iter().map(|b| b.into()).collect();
-        let chain = BillBlockchain::new_from_blocks(blocks)?;
+        Ok(result.into_iter().map(|b| b.into()).collect())
+    }
 
-        Ok(chain)
+    async fn remove_blocks_from(&self, id: &str, block_id: u64) -> Result<()> {
+        self.db()
+            .await?
+            .query("DELETE FROM type::table($table) WHERE bill_id = $bill_id AND block_id >= $block_id")
+            .bind((DB_TABLE, Self::TABLE))
+            .bind((DB_BILL_ID, id.to_owned()))
+            .bind((DB_BLOCK_ID, block_id))
+            .await?
+            .check()?;
+        Ok(())
+    }
+
+    async fn replace_blocks_from(&self, id: &str, blocks: &[BillBlock]) -> Result<()> {
+        let first_block_id = match blocks.first() {
+            Some(block) => block.id,
+            None => return Ok(()),
+        };
+        let entities: Vec<BillBlockDb> = blocks.iter().map(|b| b.into()).collect();
+        self.db()
+            .await?
+            .query(
+                r#"
+                BEGIN TRANSACTION;
+                DELETE FROM type::table($table) WHERE bill_id = $bill_id AND block_id >= $block_id;
+                FOR $block IN $blocks {
+                    CREATE type::table($table) CONTENT $block;
+                };
+                COMMIT TRANSACTION;
+            "#,
+            )
+            .bind((DB_TABLE, Self::TABLE))
+            .bind((DB_BILL_ID, id.to_owned()))
+            .bind((DB_BLOCK_ID, first_block_id))
+            .bind(("blocks", entities))
+            .await
+            .map_err(|e| {
+                log::error!("Replace Bill Blocks: {e}");
+                e
+            })?
+            .check()?;
+        Ok(())
     }
 }
 
@@ -283,5 +330,97 @@ mod tests {
         assert_eq!(last_block.as_ref().unwrap().id, 2);
         let chain = store.get_chain("1234").await.unwrap();
         assert_eq!(chain.blocks().len(), 2);
+        let blocks = store.get_blocks("1234").await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1].id, 2);
+    }
+
+    #[tokio::test]
+    async fn test_remove_blocks_from() {
+        let store = get_store().await;
+        let block = get_first_block("1234");
+        store.add_block("1234", &block).await.unwrap();
+        store
+            .add_block("5678", &get_first_block("5678"))
+            .await
+            .unwrap();
+        let block2 = BillBlock::create_block_for_accept(
+            "1234".to_string(),
+            &block,
+            &BillAcceptBlockData {
+                accepter: BillIdentityBlockData {
+                    t: ContactType::Person,
+                    node_id: "555555".to_owned(),
+                    name: "some dude".to_owned(),
+                    postal_address: empty_address(),
+                },
+                signatory: None,
+                signing_timestamp: 1731593928,
+                signing_address: empty_address(),
+            },
+            &BcrKeys::new(),
+            None,
+            &BcrKeys::from_private_key(&get_bill_keys().private_key).unwrap(),
+            1731593928,
+        )
+        .unwrap();
+        store.add_block("1234", &block2).await.unwrap();
+
+        store.remove_blocks_from("1234", 2).await.unwrap();
+        let blocks = store.get_blocks("1234").await.unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].id, 1);
+        // the removed block can be added again
+        store.add_block("1234", &block2).await.unwrap();
+        assert_eq!(store.get_blocks("1234").await.unwrap().len(), 2);
+        // other bills are not affected
+        assert_eq!(store.get_blocks("5678").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_replace_blocks_from() {
+        let store = get_store().await;
+        let block = get_first_block("1234");
+        store.add_block("1234", &block).await.unwrap();
+        store
+            .add_block("5678", &get_first_block("5678"))
+            .await
+            .unwrap();
+        let accept_block = |name: &str| {
+            BillBlock::create_block_for_accept(
+                "1234".to_string(),
+                &block,
+                &BillAcceptBlockData {
+                    accepter: BillIdentityBlockData {
+                        t: ContactType::Person,
+                        node_id: "555555".to_owned(),
+                        name: name.to_owned(),
+                        postal_address: empty_address(),
+                    },
+                    signatory: None,
+                    signing_timestamp: 1731593928,
+                    signing_address: empty_address(),
+                },
+                &BcrKeys::new(),
+                None,
+                &BcrKeys::from_private_key(&get_bill_keys().private_key).unwrap(),
+                1731593928,
+            )
+            .unwrap()
+        };
+        let corrupted_block = accept_block("some dude");
+        let replacement_block = accept_block("some other dude");
+        store.add_block("1234", &corrupted_block).await.unwrap();
+
+        store
+            .replace_blocks_from("1234", std::slice::from_ref(&replacement_block))
+            .await
+            .unwrap();
+        let blocks = store.get_blocks("1234").await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].hash, block.hash);
+        assert_eq!(blocks[1].hash, replacement_block.hash);
+        // other bills are not affected
+        assert_eq!(store.get_blocks("5678").await.unwrap().len(), 1);
     }
 }
@@ -0,0 +1,220 @@
+use super::Result;
+use super::bill::IdentityDataDb;
+#[cfg(target_arch = "wasm32")]
+use super::get_new_surreal_db;
+use crate::bill::BillTemplateStoreApi;
+use async_trait::async_trait;
+use bcr_ebill_core::bill::BillTemplate;
+use serde::{Deserialize, Serialize};
+use surrealdb::{Surreal, engine::any::Any};
+
+#[derive(Clone)]
+pub struct SurrealBillTemplateStore {
+    #[allow(dead_code)]
+    db: Surreal<Any>,
+}
+
+impl SurrealBillTemplateStore {
+    const TABLE: &'static str = "bill_template";
+
+    pub fn new(db: Surreal<Any>) -> Self {
+        Self { db }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn db(&self) -> Result<Surreal<Any>> {
+        get_new_surreal_db().await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn db(&self) -> Result<Surreal<Any>> {
+        Ok(self.db.clone())
+    }
+}
+
+#[async_trait]
+impl BillTemplateStoreApi for SurrealBillTemplateStore {
+    async fn get_all(&self) -> Result<Vec<BillTemplate>> {
+        let all: Vec<BillTemplateDb> = self.db().await?.select(Self::TABLE).await?;
+        Ok(all.into_iter().map(|t| t.into()).collect())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<BillTemplate>> {
+        let result: Option<BillTemplateDb> = self
+            .db()
+            .await?
+            .select((Self::TABLE, id.to_owned()))
+            .await?;
+        Ok(result.map(|t| t.into()))
+    }
+
+    async fn save(&self, template: &BillTemplate) -> Result<()> {
+        let entity: BillTemplateDb = template.into();
+        let _: Option<BillTemplateDb> = self
+            .db()
+            .await?
+            .upsert((Self::TABLE, template.id.to_owned()))
+            .content(entity)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let _: Option<BillTemplateDb> = self
+            .db()
+            .await?
+            .delete((Self::TABLE, id.to_owned()))
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillTemplateDb {
+    pub template_id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub t: u64,
+    pub country_of_issuing: String,
+    pub city_of_issuing: String,
+    pub issue_date: String,
+    pub maturity_date: String,
+    pub drawee: String,
+    pub payee: String,
+    pub sum: String,
+    pub currency: String,
+    pub country_of_payment: String,
+    pub city_of_payment: String,
+    pub language: String,
+    pub file_upload_ids: Vec<String>,
+    pub drawer_public_data: IdentityDataDb,
+    pub payment_deadline_seconds: Option<u64>,
+}
+
+impl From<BillTemplateDb> for BillTemplate {
+    fn from(value: BillTemplateDb) -> Self {
+        Self {
+            id: value.template_id,
+            name: value.name,
+            t: value.t,
+            country_of_issuing: value.country_of_issuing,
+            city_of_issuing: value.city_of_issuing,
+            issue_date: value.issue_date,
+            maturity_date: value.maturity_date,
+            drawee: value.drawee,
+            payee: value.payee,
+            sum: value.sum,
+            currency: value.currency,
+            country_of_payment: value.country_of_payment,
+            city_of_payment: value.city_of_payment,
+            language: value.language,
+            file_upload_ids: value.file_upload_ids,
+            drawer_public_data: value.drawer_public_data.into(),
+            payment_deadline_seconds: value.payment_deadline_seconds,
+        }
+    }
+}
+
+impl From<&BillTemplate> for BillTemplateDb {
+    fn from(value: &BillTemplate) -> Self {
+        Self {
+            template_id: value.id.clone(),
+            name: value.name.clone(),
+            t: value.t,
+            country_of_issuing: value.country_of_issuing.clone(),
+            city_of_issuing: value.city_of_issuing.clone(),
+            issue_date: value.issue_date.clone(),
+            maturity_date: value.maturity_date.clone(),
+            drawee: value.drawee.clone(),
+            payee: value.payee.clone(),
+            sum: value.sum.clone(),
+            currency: value.currency.clone(),
+            country_of_payment: value.country_of_payment.clone(),
+            city_of_payment: value.city_of_payment.clone(),
+            language: value.language.clone(),
+            file_upload_ids: value.file_upload_ids.clone(),
+            drawer_public_data: (&value.drawer_public_data).into(),
+            payment_deadline_seconds: value.payment_deadline_seconds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::get_memory_db,
+        tests::tests::{TEST_NODE_ID_SECP, TEST_PUB_KEY_SECP, empty_address},
+    };
+    use bcr_ebill_core::contact::{ContactType, IdentityPublicData};
+
+    async fn get_store() -> SurrealBillTemplateStore {
+        let mem_db = get_memory_db("test", "bill_template")
+            .await
+            .expect("could not create memory db");
+        SurrealBillTemplateStore::new(mem_db)
+    }
+
+    fn get_template(id: &str) -> BillTemplate {
+        BillTemplate {
+            id: id.to_owned(),
+            name: "monthly rent".to_owned(),
+            t: 2,
+            country_of_issuing: "AT".to_owned(),
+            city_of_issuing: "Vienna".to_owned(),
+            issue_date: "2030-01-01".to_owned(),
+            maturity_date: "2030-04-01".to_owned(),
+            drawee: TEST_NODE_ID_SECP.to_owned(),
+            payee: TEST_PUB_KEY_SECP.to_owned(),
+            sum: "500".to_owned(),
+            currency: "sat".to_owned(),
+            country_of_payment: "AT".to_owned(),
+            city_of_payment: "Vienna".to_owned(),
+            language: "de".to_owned(),
+            file_upload_ids: vec![],
+            drawer_public_data: IdentityPublicData {
+                t: ContactType::Person,
+                node_id: TEST_PUB_KEY_SECP.to_owned(),
+                name: "drawer".to_owned(),
+                postal_address: empty_address(),
+                email: None,
+                nostr_relay: None,
+            },
+            payment_deadline_seconds: Some(86400),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get_template() {
+        let store = get_store().await;
+        store.save(&get_template("template_1")).await.unwrap();
+        assert_eq!(
+            store.get("template_1").await.unwrap(),
+            Some(get_template("template_1"))
+        );
+        assert_eq!(store.get("template_2").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_save_replaces_template() {
+        let store = get_store().await;
+        store.save(&get_template("template_1")).await.unwrap();
+        let mut template = get_template("template_1");
+        template.sum = "1000".to_owned();
+        store.save(&template).await.unwrap();
+        let all = store.get_all().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].sum, "1000");
+    }
+
+    #[tokio::test]
+    async fn test_delete_template() {
+        let store = get_store().await;
+        store.save(&get_template("template_1")).await.unwrap();
+        store.save(&get_template("template_2")).await.unwrap();
+        store.delete("template_1").await.unwrap();
+        let all = store.get_all().await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, "template_2");
+    }
+}
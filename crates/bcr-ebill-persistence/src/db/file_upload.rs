@@ -1,16 +1,25 @@
 #![cfg(any(target_arch = "wasm32", test))]
-use super::super::{Error, Result, file_upload::FileUploadStoreApi};
+use super::super::{
+    Error, Result,
+    file_upload::{
+        FileUploadStoreApi, TempUploadEncryption, TempUploadUsage, decrypt_temp_file,
+        encrypt_temp_file,
+    },
+};
 #[cfg(target_arch = "wasm32")]
 use super::get_new_surreal_files_db;
 use crate::constants::{DB_ENTITY_ID, DB_FILE_NAME, DB_FILE_UPLOAD_ID, DB_TABLE};
 use async_trait::async_trait;
 use base64::{Engine as _, engine::general_purpose::STANDARD};
+use bcr_ebill_core::util::date;
 use serde::{Deserialize, Serialize};
 use surrealdb::{Surreal, engine::any::Any};
 
 pub struct FileUploadStore {
     #[allow(dead_code)]
     db: Surreal<Any>,
+    usage: TempUploadUsage,
+    encryption: Option<TempUploadEncryption>,
 }
 
 impl FileUploadStore {
@@ -18,7 +27,17 @@ impl FileUploadStore {
     const ATTACHED_FILES_TABLE: &'static str = "attached_files";
 
     pub fn new(db: Surreal<Any>) -> Self {
-        Self { db }
+        Self {
+            db,
+            usage: TempUploadUsage::default(),
+            encryption: None,
+        }
+    }
+
+    /// Encrypts temp upload files at rest with the given encryption
+    pub fn with_encryption(mut self, encryption: TempUploadEncryption) -> Self {
+        self.encryption = Some(encryption);
+        self
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -36,6 +55,34 @@ impl FileUploadStore {
         let _: Vec<FileDb> = self.db().await?.delete(Self::TEMP_FILES_TABLE).await?;
         Ok(())
     }
+
+    /// Encrypts all plaintext temp upload files - a no-op, if encryption is disabled
+    pub async fn encrypt_plaintext_temp_uploads(&self) -> Result<()> {
+        let Some(ref encryption) = self.encryption else {
+            return Ok(());
+        };
+        let files: Vec<FileDb> = self.db().await?.select(Self::TEMP_FILES_TABLE).await?;
+        for mut file in files {
+            let file_bytes = STANDARD
+                .decode(&file.file_bytes)
+                .map_err(|_| Error::EncodingError)?;
+            if TempUploadEncryption::is_encrypted(&file_bytes) {
+                continue;
+            }
+            log::info!(
+                "encrypting plaintext temp upload file for {}",
+                file.file_upload_id
+            );
+            file.file_bytes = STANDARD.encode(encryption.encrypt(&file_bytes)?);
+            let _: Option<FileDb> = self
+                .db()
+                .await?
+                .upsert((Self::TEMP_FILES_TABLE, file.file_upload_id.to_owned()))
+                .content(file)
+                .await?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +90,13 @@ pub struct FileDb {
     pub file_upload_id: String,
     pub file_name: String,
     pub file_bytes: String,
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TempUploadIdDb {
+    file_upload_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,9 +122,27 @@ impl FileUploadStoreApi for FileUploadStore {
             .bind((DB_FILE_UPLOAD_ID, file_upload_id.to_owned()))
             .await?
             .take(0)?;
+        self.usage.remove(file_upload_id);
         Ok(())
     }
 
+    async fn list_temp_upload_folders_older_than(&self, ttl_seconds: u64) -> Result<Vec<String>> {
+        let threshold = (date::now().timestamp() as u64).saturating_sub(ttl_seconds);
+        let result: Vec<TempUploadIdDb> = self
+            .db()
+            .await?
+            .query("SELECT file_upload_id FROM type::table($table) WHERE created_at < $threshold")
+            .bind((DB_TABLE, Self::TEMP_FILES_TABLE))
+            .bind(("threshold", threshold))
+            .await?
+            .take(0)?;
+        Ok(result
+            .into_iter()
+            .map(|f| f.file_upload_id)
+            .filter(|id| self.usage.last_used(id, 0) < threshold)
+            .collect())
+    }
+
     async fn write_temp_upload_file(
         &self,
         file_upload_id: &str,
@@ -80,7 +152,8 @@ impl FileUploadStoreApi for FileUploadStore {
         let entity = FileDb {
             file_upload_id: file_upload_id.to_owned(),
             file_name: file_name.to_owned(),
-            file_bytes: STANDARD.encode(file_bytes),
+            file_bytes: STANDARD.encode(encrypt_temp_file(&self.encryption, file_bytes)?),
+            created_at: date::now().timestamp() as u64,
         };
         let _: Option<FileDb> = self
             .db()
@@ -92,6 +165,7 @@ impl FileUploadStoreApi for FileUploadStore {
     }
 
     async fn read_temp_upload_file(&self, file_upload_id: &str) -> Result<(String, Vec<u8>)> {
+        self.usage.mark_used(file_upload_id);
         let result: Option<FileDb> = self
             .db()
             .await?
@@ -104,9 +178,12 @@ impl FileUploadStoreApi for FileUploadStore {
             )),
             Some(f) => Ok((
                 f.file_name,
-                STANDARD
-                    .decode(&f.file_bytes)
-                    .map_err(|_| Error::EncodingError)?,
+                decrypt_temp_file(
+                    &self.encryption,
+                    STANDARD
+                        .decode(&f.file_bytes)
+                        .map_err(|_| Error::EncodingError)?,
+                )?,
             )),
         }
     }
@@ -169,6 +246,7 @@ impl FileUploadStoreApi for FileUploadStore {
 pub mod tests {
     use super::*;
     use crate::db::get_memory_db;
+    use bcr_ebill_core::util::BcrKeys;
 
     #[tokio::test]
     async fn test_temp_file() {
@@ -225,6 +303,86 @@ pub mod tests {
         FileUploadStore::new(mem_db)
     }
 
+    #[tokio::test]
+    async fn test_list_temp_upload_folders_older_than() {
+        let temp_store = get_temp_store().await;
+        temp_store
+            .write_temp_upload_file("fresh_id", "file_name.jpg", &[])
+            .await
+            .unwrap();
+        let old = FileDb {
+            file_upload_id: "old_id".to_string(),
+            file_name: "file_name.jpg".to_string(),
+            file_bytes: "".to_string(),
+            created_at: date::now().timestamp() as u64 - 7200,
+        };
+        let _: Option<FileDb> = temp_store
+            .db
+            .create((FileUploadStore::TEMP_FILES_TABLE, "old_id"))
+            .content(old)
+            .await
+            .unwrap();
+
+        let stale = temp_store
+            .list_temp_upload_folders_older_than(3600)
+            .await
+            .unwrap();
+        assert_eq!(stale, vec!["old_id".to_string()]);
+
+        // an upload, which is in use by an operation isn't stale
+        temp_store.read_temp_upload_file("old_id").await.unwrap();
+        let stale = temp_store
+            .list_temp_upload_folders_older_than(3600)
+            .await
+            .unwrap();
+        assert!(stale.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_temp_file_encrypted_at_rest() {
+        let temp_store = get_temp_store()
+            .await
+            .with_encryption(TempUploadEncryption::new(BcrKeys::new()));
+        temp_store
+            .write_temp_upload_file("some_id", "file_name.jpg", "hello_world".as_bytes())
+            .await
+            .unwrap();
+        let stored: Option<FileDb> = temp_store
+            .db
+            .select((FileUploadStore::TEMP_FILES_TABLE, "some_id"))
+            .await
+            .unwrap();
+        let stored_bytes = STANDARD.decode(stored.unwrap().file_bytes).unwrap();
+        assert!(TempUploadEncryption::is_encrypted(&stored_bytes));
+
+        let temp_file = temp_store.read_temp_upload_file("some_id").await.unwrap();
+        assert_eq!(temp_file.1, "hello_world".as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_plaintext_temp_uploads() {
+        let temp_store = get_temp_store().await;
+        temp_store
+            .write_temp_upload_file("some_id", "file_name.jpg", "hello_world".as_bytes())
+            .await
+            .unwrap();
+        let temp_store = temp_store.with_encryption(TempUploadEncryption::new(BcrKeys::new()));
+        // plaintext files can still be read
+        let temp_file = temp_store.read_temp_upload_file("some_id").await.unwrap();
+        assert_eq!(temp_file.1, "hello_world".as_bytes());
+
+        temp_store.encrypt_plaintext_temp_uploads().await.unwrap();
+        let stored: Option<FileDb> = temp_store
+            .db
+            .select((FileUploadStore::TEMP_FILES_TABLE, "some_id"))
+            .await
+            .unwrap();
+        let stored_bytes = STANDARD.decode(stored.unwrap().file_bytes).unwrap();
+        assert!(TempUploadEncryption::is_encrypted(&stored_bytes));
+        let temp_file = temp_store.read_temp_upload_file("some_id").await.unwrap();
+        assert_eq!(temp_file.1, "hello_world".as_bytes());
+    }
+
     async fn get_attached_store() -> FileUploadStore {
         let mem_db = get_memory_db("test", "attached_files")
             .await
@@ -17,6 +17,7 @@ impl SurrealIdentityStore {
     const IDENTITY_TABLE: &'static str = "identity";
     const ACTIVE_IDENTITY_TABLE: &'static str = "active_identity";
     const KEY_TABLE: &'static str = "identity_key";
+    const DATA_KEY_TABLE: &'static str = "identity_data_key";
     const UNIQUE_ID: &'static str = "unique_record";
 
     pub fn new(db: Surreal<Any>) -> Self {
@@ -111,6 +112,29 @@ impl IdentityStoreApi for SurrealIdentityStore {
         Ok(keys)
     }
 
+    async fn get_or_create_data_key(&self) -> Result<BcrKeys> {
+        let result: Option<DataKeyDb> = self
+            .db()
+            .await?
+            .select((Self::DATA_KEY_TABLE, Self::UNIQUE_ID))
+            .await?;
+        match result {
+            Some(value) => value.try_into(),
+            None => {
+                let new_keys = BcrKeys::new();
+                let _: Option<DataKeyDb> = self
+                    .db()
+                    .await?
+                    .upsert((Self::DATA_KEY_TABLE, Self::UNIQUE_ID))
+                    .content(DataKeyDb {
+                        key: new_keys.get_private_key_string(),
+                    })
+                    .await?;
+                Ok(new_keys)
+            }
+        }
+    }
+
     async fn get_seedphrase(&self) -> Result<String> {
         let result = self.get_db_keys().await?;
         match result {
@@ -246,6 +270,18 @@ impl TryFrom<KeyDb> for BcrKeys {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataKeyDb {
+    pub key: String,
+}
+
+impl TryFrom<DataKeyDb> for BcrKeys {
+    type Error = crate::Error;
+    fn try_from(value: DataKeyDb) -> Result<Self> {
+        Ok(BcrKeys::from_private_key(&value.key)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,4 +336,13 @@ mod tests {
         let fetched_key_pair = store.get_key_pair().await.unwrap();
         assert_eq!(keys.get_public_key(), fetched_key_pair.get_public_key());
     }
+
+    #[tokio::test]
+    async fn test_data_key() {
+        let store = get_store().await;
+        let data_key = store.get_or_create_data_key().await.unwrap();
+        // the data key is only created once
+        let fetched_data_key = store.get_or_create_data_key().await.unwrap();
+        assert_eq!(data_key.get_public_key(), fetched_data_key.get_public_key());
+    }
 }
@@ -14,7 +14,9 @@ use surrealdb::{
 
 pub mod backup;
 pub mod bill;
+pub mod bill_auto_accept;
 pub mod bill_chain;
+pub mod bill_template;
 pub mod company;
 pub mod company_chain;
 pub mod contact;
@@ -39,14 +39,22 @@ impl SurrealNostrEventOffsetStore {
 #[async_trait]
 impl NostrEventOffsetStoreApi for SurrealNostrEventOffsetStore {
     async fn current_offset(&self, node_id: &str) -> Result<u64> {
-        let result: Vec<NostrEventOffsetDb> = self
-            .db()
-            .await?
-            .query(format!("SELECT * FROM type::table($table) where {DB_NODE_ID} = $node_id ORDER BY time DESC LIMIT 1"))
+        let db = self.db().await?;
+        // don't move past events, which failed to be processed
+        let mut result: Vec<NostrEventOffsetDb> = db
+            .query(format!("SELECT * FROM type::table($table) where {DB_NODE_ID} = $node_id AND success = false ORDER BY time ASC LIMIT 1"))
             .bind((DB_TABLE, Self::TABLE))
             .bind((DB_NODE_ID, node_id.to_owned()))
             .await?
             .take(0)?;
+        if result.is_empty() {
+            result = db
+                .query(format!("SELECT * FROM type::table($table) where {DB_NODE_ID} = $node_id ORDER BY time DESC LIMIT 1"))
+                .bind((DB_TABLE, Self::TABLE))
+                .bind((DB_NODE_ID, node_id.to_owned()))
+                .await?
+                .take(0)?;
+        }
         let value = result
             .first()
             .map(|c| c.time.timestamp())
@@ -58,7 +66,7 @@ impl NostrEventOffsetStoreApi for SurrealNostrEventOffsetStore {
     async fn is_processed(&self, event_id: &str) -> Result<bool> {
         let result: Option<NostrEventOffsetDb> =
             self.db().await?.select((Self::TABLE, event_id)).await?;
-        Ok(result.is_some())
+        Ok(result.is_some_and(|e| e.success))
     }
 
     async fn add_event(&self, data: NostrEventOffset) -> Result<()> {
@@ -66,7 +74,7 @@ impl NostrEventOffsetStoreApi for SurrealNostrEventOffsetStore {
         let _: Option<NostrEventOffsetDb> = self
             .db()
             .await?
-            .create((Self::TABLE, db.event_id.to_owned()))
+            .upsert((Self::TABLE, db.event_id.to_owned()))
             .content(db)
             .await?;
         Ok(())
@@ -155,6 +163,21 @@ mod tests {
             .expect("could not check if processed");
         assert!(!is_known, "new event should not be known");
 
+        store
+            .add_event(data.clone())
+            .await
+            .expect("could not add event offset");
+        let is_processed = store
+            .is_processed(&data.event_id)
+            .await
+            .expect("could not check if processed");
+        assert!(!is_processed, "failed event should be processed again");
+
+        let data = NostrEventOffset {
+            success: true,
+            ..data
+        };
+
         store
             .add_event(data.clone())
             .await
@@ -166,6 +189,47 @@ mod tests {
         assert!(is_processed, "existing event should be known");
     }
 
+    #[tokio::test]
+    async fn test_current_offset_does_not_move_past_failed_events() {
+        let store = get_store().await;
+        for (event_id, time, success) in [
+            ("first", 1000, true),
+            ("failed", 2000, false),
+            ("last", 3000, true),
+        ] {
+            store
+                .add_event(NostrEventOffset {
+                    event_id: event_id.to_string(),
+                    time,
+                    success,
+                    node_id: "node_id".to_string(),
+                })
+                .await
+                .expect("could not add event offset");
+        }
+        let offset = store
+            .current_offset("node_id")
+            .await
+            .expect("could not get offset");
+        assert_eq!(offset, 2000);
+
+        // once the failed event was processed successfully, the offset moves on
+        store
+            .add_event(NostrEventOffset {
+                event_id: "failed".to_string(),
+                time: 2000,
+                success: true,
+                node_id: "node_id".to_string(),
+            })
+            .await
+            .expect("could not add event offset");
+        let offset = store
+            .current_offset("node_id")
+            .await
+            .expect("could not get offset");
+        assert_eq!(offset, 3000);
+    }
+
     async fn get_store() -> SurrealNostrEventOffsetStore {
         let mem_db = get_memory_db("test", "nostr_event_offset")
             .await
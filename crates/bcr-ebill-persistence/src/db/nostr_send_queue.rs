@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use surrealdb::{Surreal, engine::any::Any, sql::Thing};
 
-use crate::nostr::{NostrQueuedMessage, NostrQueuedMessageStoreApi};
+use crate::nostr::{NostrQueuedMessage, NostrQueuedMessageInfo, NostrQueuedMessageStoreApi};
 
 #[derive(Clone)]
 pub struct SurrealNostrEventQueueStore {
@@ -61,13 +61,15 @@ impl NostrQueuedMessageStoreApi for SurrealNostrEventQueueStore {
             .await?;
         Ok(())
     }
-    /// Selects all messages that are ready to be retried
-    async fn get_retry_messages(&self, limit: u64) -> Result<Vec<NostrQueuedMessage>> {
+    /// Selects all messages that are ready to be retried at the given timestamp, skipping
+    /// messages with a `next_retry_at` in the future
+    async fn get_retry_messages(&self, limit: u64, now: u64) -> Result<Vec<NostrQueuedMessage>> {
         let items: Vec<QueuedMessageDb> = self
             .db().await?
-            .query("SELECT * FROM type::table($table) WHERE completed = false AND processing = false ORDER BY last_try ASC LIMIT $limit")
+            .query("SELECT * FROM type::table($table) WHERE completed = false AND dead_letter = false AND processing = false AND next_retry_at <= $now ORDER BY next_retry_at ASC LIMIT $limit")
             .bind((DB_TABLE, Self::TABLE))
             .bind((DB_LIMIT, limit))
+            .bind(("now", now))
             .await?
             .take(0)?;
         let ids = items.iter().map(|i| i.id.to_owned()).collect();
@@ -76,9 +78,9 @@ impl NostrQueuedMessageStoreApi for SurrealNostrEventQueueStore {
         Ok(results)
     }
 
-    /// Fail a retry attempt, schedules a new retry or fails the message if
-    /// all retries have been exhausted.
-    async fn fail_retry(&self, id: &str) -> Result<()> {
+    /// Fail a retry attempt, schedules a new retry at `next_retry_at`, or moves the message
+    /// to the dead-letter state if all retries have been exhausted.
+    async fn fail_retry(&self, id: &str, next_retry_at: u64) -> Result<()> {
         let current: Option<QueuedMessageDb> = self
             .db()
             .await?
@@ -87,7 +89,8 @@ impl NostrQueuedMessageStoreApi for SurrealNostrEventQueueStore {
         if let Some(mut msg) = current {
             msg.num_retries += 1;
             msg.last_try = date::now();
-            msg.completed = msg.num_retries >= msg.max_retries;
+            msg.next_retry_at = next_retry_at;
+            msg.dead_letter = msg.num_retries >= msg.max_retries;
             msg.processing = false;
             let _: Option<QueuedMessageDb> = self
                 .db()
@@ -118,6 +121,28 @@ impl NostrQueuedMessageStoreApi for SurrealNostrEventQueueStore {
         }
         Ok(())
     }
+
+    async fn get_pending_messages(&self, limit: u64) -> Result<Vec<NostrQueuedMessageInfo>> {
+        let items: Vec<QueuedMessageDb> = self
+            .db().await?
+            .query("SELECT * FROM type::table($table) WHERE completed = false AND dead_letter = false ORDER BY next_retry_at ASC LIMIT $limit")
+            .bind((DB_TABLE, Self::TABLE))
+            .bind((DB_LIMIT, limit))
+            .await?
+            .take(0)?;
+        Ok(items.into_iter().map(|i| i.into()).collect())
+    }
+
+    async fn count_dead_letter_messages(&self) -> Result<u64> {
+        let count: Option<u64> = self
+            .db()
+            .await?
+            .query("SELECT count() AS count FROM type::table($table) WHERE dead_letter = true GROUP ALL")
+            .bind((DB_TABLE, Self::TABLE))
+            .await?
+            .take((0, "count"))?;
+        Ok(count.unwrap_or(0))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,7 +155,12 @@ struct QueuedMessageDb {
     pub last_try: DateTimeUtc,
     pub num_retries: i32,
     pub max_retries: i32,
+    #[serde(default)]
+    pub next_retry_at: u64,
     pub completed: bool,
+    /// Messages, which exhausted all their retries are kept, but never retried again
+    #[serde(default)]
+    pub dead_letter: bool,
     pub processing: bool,
 }
 
@@ -146,9 +176,11 @@ impl QueuedMessageDb {
             payload: value.payload,
             created: date::now(),
             last_try: date::seconds(0),
-            num_retries: 0,
+            num_retries: value.retry_count,
             max_retries,
+            next_retry_at: value.next_retry_at,
             completed: false,
+            dead_letter: false,
             processing: false,
         }
     }
@@ -161,6 +193,21 @@ impl From<QueuedMessageDb> for NostrQueuedMessage {
             sender_id: value.sender_id,
             node_id: value.node_id,
             payload: value.payload,
+            retry_count: value.num_retries,
+            next_retry_at: value.next_retry_at,
+        }
+    }
+}
+
+impl From<QueuedMessageDb> for NostrQueuedMessageInfo {
+    fn from(value: QueuedMessageDb) -> Self {
+        NostrQueuedMessageInfo {
+            id: value.id.id.to_raw(),
+            sender_id: value.sender_id,
+            node_id: value.node_id,
+            retry_count: value.num_retries,
+            next_retry_at: value.next_retry_at,
+            created: value.created.timestamp() as u64,
         }
     }
 }
@@ -179,13 +226,13 @@ mod tests {
             .expect("could not add message");
 
         let messages = store
-            .get_retry_messages(1)
+            .get_retry_messages(1, 100)
             .await
             .expect("could not get messages");
         assert!(!messages.is_empty(), "should have gotten a queued message");
 
         let messages_empty = store
-            .get_retry_messages(1)
+            .get_retry_messages(1, 100)
             .await
             .expect("could not get messages");
 
@@ -200,7 +247,7 @@ mod tests {
             .expect("could not mark message as succeeded");
 
         let messages_done = store
-            .get_retry_messages(1)
+            .get_retry_messages(1, 100)
             .await
             .expect("could not get messages");
         assert!(
@@ -218,13 +265,13 @@ mod tests {
             .expect("could not add message");
 
         let messages = store
-            .get_retry_messages(1)
+            .get_retry_messages(1, 100)
             .await
             .expect("could not get messages");
         assert!(!messages.is_empty(), "should have gotten a queued message");
 
         let messages_empty = store
-            .get_retry_messages(1)
+            .get_retry_messages(1, 100)
             .await
             .expect("could not get messages");
 
@@ -234,12 +281,12 @@ mod tests {
         );
 
         store
-            .fail_retry(&messages[0].id)
+            .fail_retry(&messages[0].id, 100)
             .await
             .expect("could not mark message as failed");
 
         let messages_failed = store
-            .get_retry_messages(1)
+            .get_retry_messages(1, 100)
             .await
             .expect("could not get failed messages");
 
@@ -249,12 +296,12 @@ mod tests {
         );
 
         store
-            .fail_retry(&messages_failed[0].id)
+            .fail_retry(&messages_failed[0].id, 100)
             .await
             .expect("could not mark message as failed");
 
         let messages_failed_again = store
-            .get_retry_messages(1)
+            .get_retry_messages(1, 100)
             .await
             .expect("could not get failed messages");
 
@@ -264,6 +311,75 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_skips_messages_scheduled_in_the_future() {
+        let store = get_store().await;
+        store
+            .add_message(get_test_message("test_message"), 3)
+            .await
+            .expect("could not add message");
+        let messages = store
+            .get_retry_messages(1, 100)
+            .await
+            .expect("could not get messages");
+        assert_eq!(messages.len(), 1);
+
+        store
+            .fail_retry(&messages[0].id, 200)
+            .await
+            .expect("could not mark message as failed");
+
+        let messages_too_early = store
+            .get_retry_messages(1, 150)
+            .await
+            .expect("could not get messages");
+        assert!(
+            messages_too_early.is_empty(),
+            "should not have gotten a message scheduled in the future"
+        );
+
+        let messages_due = store
+            .get_retry_messages(1, 200)
+            .await
+            .expect("could not get messages");
+        assert_eq!(messages_due.len(), 1);
+        assert_eq!(messages_due[0].retry_count, 1);
+        assert_eq!(messages_due[0].next_retry_at, 200);
+    }
+
+    #[tokio::test]
+    async fn test_get_pending_messages_and_count_dead_letters() {
+        let store = get_store().await;
+        store
+            .add_message(get_test_message("pending"), 3)
+            .await
+            .expect("could not add message");
+        store
+            .add_message(get_test_message("done"), 3)
+            .await
+            .expect("could not add message");
+        store
+            .add_message(get_test_message("dead"), 1)
+            .await
+            .expect("could not add message");
+        store.succeed_retry("done").await.unwrap();
+        store.fail_retry("dead", 100).await.unwrap();
+
+        let pending = store
+            .get_pending_messages(10)
+            .await
+            .expect("could not get pending messages");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, "pending");
+        assert_eq!(pending[0].node_id, "test_node");
+        assert_eq!(pending[0].retry_count, 0);
+        assert!(pending[0].created > 0);
+        assert_eq!(store.count_dead_letter_messages().await.unwrap(), 1);
+
+        // listing doesn't take the messages from the queue
+        assert_eq!(store.get_retry_messages(10, 100).await.unwrap().len(), 1);
+    }
+
     async fn get_store() -> SurrealNostrEventQueueStore {
         let mem_db = get_memory_db("test", "nostr_event_queue")
             .await
@@ -277,6 +393,8 @@ mod tests {
             sender_id: "test_sender".to_string(),
             node_id: "test_node".to_string(),
             payload: serde_json::json!({"foo": "bar"}),
+            retry_count: 0,
+            next_retry_at: 0,
         }
     }
 }
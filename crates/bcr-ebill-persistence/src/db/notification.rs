@@ -10,7 +10,9 @@ use surrealdb::{Surreal, engine::any::Any, sql::Thing};
 
 use crate::{
     constants::{DB_ACTIVE, DB_IDS, DB_NOTIFICATION_TYPE, DB_TABLE},
-    notification::{NotificationFilter, NotificationStoreApi},
+    notification::{
+        NotificationCursor, NotificationFilter, NotificationPage, NotificationStoreApi,
+    },
     util::date::{DateTimeUtc, now},
 };
 use bcr_ebill_core::notification::ActionType;
@@ -25,6 +27,7 @@ pub struct SurrealNotificationStore {
 impl SurrealNotificationStore {
     const TABLE: &'static str = "notifications";
     const SENT_TABLE: &'static str = "sent_notifications";
+    const NOTIFIED_PARTICIPANT_TABLE: &'static str = "notified_bill_participants";
 
     pub fn new(db: Surreal<Any>) -> Self {
         Self { db }
@@ -63,16 +66,19 @@ impl NotificationStoreApi for SurrealNotificationStore {
             ))),
         }
     }
-    /// Returns all currently active notifications from the database
-    async fn list(&self, filter: NotificationFilter) -> Result<Vec<Notification>> {
+    /// Returns a page of notifications from the database, ordered by creation time and id, so
+    /// the cursor of the last one is a stable position for the next page
+    async fn list(&self, filter: NotificationFilter) -> Result<NotificationPage> {
         let filters = filter.filters();
+        let limit = filter.get_limit();
         let db = self.db().await?;
+        // we fetch one more than requested, to know whether there is a next page
         let mut query = db.query(format!(
-                "SELECT * FROM type::table($table) {} ORDER BY datetime DESC LIMIT $limit START $offset",
+                "SELECT * FROM type::table($table) {} ORDER BY datetime DESC, id DESC LIMIT $limit START $offset",
                 filters
             ))
             .bind(("table", Self::TABLE))
-            .bind(("limit", filter.get_limit()))
+            .bind(("limit", limit + 1))
             .bind(("offset", filter.get_offset()));
 
         if let Some(active) = filter.get_active() {
@@ -84,8 +90,21 @@ impl NotificationStoreApi for SurrealNotificationStore {
         if let Some(notification_type) = filter.get_notification_type() {
             query = query.bind(notification_type.to_owned());
         }
+        if let Some((after_datetime, after_id)) = filter.get_after() {
+            query = query.bind(after_datetime).bind(after_id);
+        }
         let result: Vec<NotificationDb> = query.await?.take(0)?;
-        Ok(result.into_iter().map(|n| n.into()).collect())
+        let mut notifications: Vec<Notification> = result.into_iter().map(|n| n.into()).collect();
+        let next_cursor = if notifications.len() as i64 > limit {
+            notifications.truncate(limit.max(0) as usize);
+            notifications.last().map(NotificationCursor::new)
+        } else {
+            None
+        };
+        Ok(NotificationPage {
+            notifications,
+            next_cursor,
+        })
     }
     /// Returns the latest active notifications for the given reference and notification type
     async fn get_latest_by_references(
@@ -141,7 +160,7 @@ impl NotificationStoreApi for SurrealNotificationStore {
                 ..Default::default()
             })
             .await?;
-        Ok(result.first().cloned())
+        Ok(result.notifications.first().cloned())
     }
     /// Returns all notifications for the given reference and notification type that are active
     async fn list_by_type(&self, notification_type: NotificationType) -> Result<Vec<Notification>> {
@@ -152,7 +171,7 @@ impl NotificationStoreApi for SurrealNotificationStore {
                 ..Default::default()
             })
             .await?;
-        Ok(result)
+        Ok(result.notifications)
     }
     /// Marks an active notification as done
     async fn mark_as_done(&self, notification_id: &str) -> Result<()> {
@@ -164,6 +183,31 @@ impl NotificationStoreApi for SurrealNotificationStore {
             .await?;
         Ok(())
     }
+    /// Marks all active notifications for the given node id, optionally only the ones of the
+    /// given type, as done and returns the number of notifications marked
+    async fn mark_all_as_done(
+        &self,
+        node_id: &str,
+        notification_type: Option<NotificationType>,
+    ) -> Result<u64> {
+        let type_filter = if notification_type.is_some() {
+            " AND notification_type = $notification_type"
+        } else {
+            ""
+        };
+        let db = self.db().await?;
+        let mut query = db
+            .query(format!(
+                "UPDATE type::table($table) SET active = false WHERE active = true AND node_id = $node_id{type_filter}"
+            ))
+            .bind((DB_TABLE, Self::TABLE))
+            .bind(("node_id", node_id.to_owned()));
+        if let Some(notification_type) = notification_type {
+            query = query.bind((DB_NOTIFICATION_TYPE, notification_type));
+        }
+        let updated: Vec<NotificationDb> = query.await?.take(0)?;
+        Ok(updated.len() as u64)
+    }
     /// deletes a notification from the database
     async fn delete(&self, notification_id: &str) -> Result<()> {
         let _: Option<NotificationDb> = self
@@ -213,6 +257,44 @@ impl NotificationStoreApi for SurrealNotificationStore {
             .take(0)?;
         Ok(res.is_some())
     }
+
+    async fn set_bill_participant_notified(
+        &self,
+        bill_id: &str,
+        block_height: i32,
+        node_id: &str,
+    ) -> Result<()> {
+        // a participant is marked at most once per block, so the record id combines all of them
+        let id = format!("{bill_id}_{block_height}_{node_id}");
+        let db = NotifiedBillParticipantDb {
+            reference_id: bill_id.to_owned(),
+            block_height,
+            node_id: node_id.to_owned(),
+            datetime: now(),
+        };
+        let _: Option<NotifiedBillParticipantDb> = self
+            .db()
+            .await?
+            .upsert((Self::NOTIFIED_PARTICIPANT_TABLE, id))
+            .content(db)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_notified_bill_participants(
+        &self,
+        bill_id: &str,
+        block_height: i32,
+    ) -> Result<Vec<String>> {
+        let res: Vec<NotifiedBillParticipantDb> = self.db().await?
+            .query("SELECT * FROM type::table($table) WHERE reference_id = $reference_id AND block_height = $block_height")
+            .bind((DB_TABLE, Self::NOTIFIED_PARTICIPANT_TABLE))
+            .bind(("reference_id", bill_id.to_owned()))
+            .bind(("block_height", block_height))
+            .await?
+            .take(0)?;
+        Ok(res.into_iter().map(|p| p.node_id).collect())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -274,6 +356,15 @@ struct SentBlockNotificationDb {
     pub datetime: DateTimeUtc,
 }
 
+/// Tracks, which participants the event for a block of a bill was sent to
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct NotifiedBillParticipantDb {
+    pub reference_id: String,
+    pub block_height: i32,
+    pub node_id: String,
+    pub datetime: DateTimeUtc,
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -328,6 +419,30 @@ mod tests {
         assert!(!sent);
     }
 
+    #[tokio::test]
+    async fn test_get_notified_bill_participants() {
+        let store = get_store().await;
+        for (bill_id, block_height, node_id) in [
+            ("bill_id", 2, "node_1"),
+            ("bill_id", 2, "node_2"),
+            // marking the same participant again doesn't add it twice
+            ("bill_id", 2, "node_2"),
+            ("bill_id", 1, "node_3"),
+            ("other_bill_id", 2, "node_4"),
+        ] {
+            store
+                .set_bill_participant_notified(bill_id, block_height, node_id)
+                .await
+                .expect("could not mark participant as notified");
+        }
+        let mut notified = store
+            .get_notified_bill_participants("bill_id", 2)
+            .await
+            .expect("could not get notified participants");
+        notified.sort();
+        assert_eq!(notified, vec!["node_1".to_string(), "node_2".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_inserts_and_queries_notification() {
         let store = get_store().await;
@@ -340,7 +455,8 @@ mod tests {
         let all = store
             .list(NotificationFilter::default())
             .await
-            .expect("could not list notifications");
+            .expect("could not list notifications")
+            .notifications;
         assert!(!all.is_empty());
         assert_eq!(notification.id, r.id);
     }
@@ -357,7 +473,8 @@ mod tests {
         let all = store
             .list(NotificationFilter::default())
             .await
-            .expect("could not list notifications");
+            .expect("could not list notifications")
+            .notifications;
         assert!(!all.is_empty());
 
         store
@@ -367,7 +484,8 @@ mod tests {
         let all = store
             .list(NotificationFilter::default())
             .await
-            .expect("could not list notifications");
+            .expect("could not list notifications")
+            .notifications;
         assert!(all.is_empty());
     }
 
@@ -387,7 +505,8 @@ mod tests {
         let all = store
             .list(filter.clone())
             .await
-            .expect("could not list notifications");
+            .expect("could not list notifications")
+            .notifications;
         assert!(!all.is_empty());
 
         store
@@ -398,7 +517,8 @@ mod tests {
         let all = store
             .list(filter)
             .await
-            .expect("could not list notifications");
+            .expect("could not list notifications")
+            .notifications;
         assert!(all.is_empty());
     }
 
@@ -538,6 +658,110 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn test_marks_all_done_for_node_id_and_type() {
+        let store = get_store().await;
+        let notification1 = test_notification("bill_id1", Some(test_payload()));
+        let notification2 = test_notification("bill_id2", Some(test_payload()));
+        let general = test_general_notification();
+        let mut other_node = test_notification("bill_id3", Some(test_payload()));
+        other_node.node_id = Some("other_node_id".to_string());
+        for notification in [&notification1, &notification2, &general, &other_node] {
+            store
+                .add(notification.clone())
+                .await
+                .expect("notification created");
+        }
+
+        let marked = store
+            .mark_all_as_done("node_id", Some(NotificationType::Bill))
+            .await
+            .expect("notifications marked done");
+        assert_eq!(marked, 2);
+
+        let unread = store
+            .list(NotificationFilter {
+                only_unread: true,
+                ..Default::default()
+            })
+            .await
+            .expect("could not list notifications")
+            .notifications;
+        assert_eq!(unread.len(), 2);
+        assert!(unread.iter().any(|n| n.id == general.id));
+        assert!(unread.iter().any(|n| n.id == other_node.id));
+
+        let marked = store
+            .mark_all_as_done("node_id", None)
+            .await
+            .expect("notifications marked done");
+        assert_eq!(marked, 1);
+        let unread = store
+            .list(NotificationFilter {
+                only_unread: true,
+                ..Default::default()
+            })
+            .await
+            .expect("could not list notifications")
+            .notifications;
+        assert_eq!(unread.len(), 1);
+        assert_eq!(unread[0].id, other_node.id);
+    }
+
+    #[tokio::test]
+    async fn test_list_pages_with_stable_cursor() {
+        let store = get_store().await;
+        let datetime = now();
+        // some notifications share the same creation time, so the id has to break the tie
+        let mut ids = vec![];
+        for i in 0..5 {
+            let mut notification = test_general_notification();
+            notification.datetime = datetime - chrono::Duration::seconds(i / 2);
+            ids.push(notification.id.clone());
+            store
+                .add(notification)
+                .await
+                .expect("could not create notification");
+        }
+
+        let first = store
+            .list(NotificationFilter {
+                limit: Some(2),
+                ..Default::default()
+            })
+            .await
+            .expect("could not list notifications");
+        assert_eq!(first.notifications.len(), 2);
+        assert!(first.next_cursor.is_some());
+
+        // a new notification while paging must not shift the next pages
+        store
+            .add(test_general_notification())
+            .await
+            .expect("could not create notification");
+
+        let mut seen: Vec<String> = first.notifications.iter().map(|n| n.id.clone()).collect();
+        let mut cursor = first.next_cursor;
+        while let Some(after) = cursor {
+            let page = store
+                .list(NotificationFilter {
+                    limit: Some(2),
+                    after: Some(after),
+                    ..Default::default()
+                })
+                .await
+                .expect("could not list notifications");
+            seen.extend(page.notifications.iter().map(|n| n.id.clone()));
+            cursor = page.next_cursor;
+        }
+
+        assert_eq!(seen.len(), 5);
+        ids.sort();
+        let mut sorted_seen = seen.clone();
+        sorted_seen.sort();
+        assert_eq!(sorted_seen, ids);
+    }
+
     fn test_notification(bill_id: &str, payload: Option<Value>) -> Notification {
         Notification::new_bill_notification(bill_id, "node_id", "test_notification", payload)
     }
@@ -3,8 +3,13 @@ use crate::Error;
 
 use super::Result;
 use async_trait::async_trait;
+use bcr_ebill_core::util::{BcrKeys, crypto, date};
+use std::collections::HashMap;
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::UNIX_EPOCH;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::io::AsyncReadExt;
 
@@ -16,6 +21,10 @@ pub trait FileUploadStoreApi: Send + Sync {
     /// Deletes temporary upload folder with the given name
     async fn remove_temp_upload_folder(&self, file_upload_id: &str) -> Result<()>;
 
+    /// Returns the ids of the temporary upload folders, which weren't written or read within the
+    /// last `ttl_seconds`. Uploads, which are used by an in-progress operation are never returned
+    async fn list_temp_upload_folders_older_than(&self, ttl_seconds: u64) -> Result<Vec<String>>;
+
     /// Writes the temporary upload file with the given file name and bytes for the given file_upload_id
     async fn write_temp_upload_file(
         &self,
@@ -29,7 +38,7 @@ pub trait FileUploadStoreApi: Send + Sync {
     async fn read_temp_upload_file(&self, file_upload_id: &str) -> Result<(String, Vec<u8>)>;
 
     /// Writes the given encrypted bytes of an attached file to disk, in a folder named id within
-    /// the files folder
+    /// the files folder, or within its shard folder, if sharding is enabled
     async fn save_attached_file(
         &self,
         encrypted_bytes: &[u8],
@@ -44,11 +53,107 @@ pub trait FileUploadStoreApi: Send + Sync {
     async fn delete_attached_files(&self, id: &str) -> Result<()>;
 }
 
+/// Keeps track of when temporary uploads were last used by an operation, e.g. read during the
+/// issuing of a bill, so uploads, which are still in use aren't considered stale
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TempUploadUsage {
+    last_used: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl TempUploadUsage {
+    pub(crate) fn mark_used(&self, file_upload_id: &str) {
+        if let Ok(mut last_used) = self.last_used.lock() {
+            last_used.insert(file_upload_id.to_owned(), date::now().timestamp() as u64);
+        }
+    }
+
+    pub(crate) fn remove(&self, file_upload_id: &str) {
+        if let Ok(mut last_used) = self.last_used.lock() {
+            last_used.remove(file_upload_id);
+        }
+    }
+
+    /// Returns the timestamp of the last use, falling back to the given timestamp, if it's later
+    pub(crate) fn last_used(&self, file_upload_id: &str, timestamp: u64) -> u64 {
+        match self.last_used.lock() {
+            Ok(last_used) => last_used
+                .get(file_upload_id)
+                .map(|used| (*used).max(timestamp))
+                .unwrap_or(timestamp),
+            Err(_) => timestamp,
+        }
+    }
+}
+
+/// Transparently encrypts temp upload files at rest with the data key of the local identity.
+/// Encrypted files are prefixed with a marker, so plaintext files, which were written before
+/// encryption was enabled, can still be read and migrated
+#[derive(Clone)]
+pub struct TempUploadEncryption {
+    keys: BcrKeys,
+}
+
+impl TempUploadEncryption {
+    const MARKER: &'static [u8] = b"BCR-ENC-V1:";
+
+    pub fn new(keys: BcrKeys) -> Self {
+        Self { keys }
+    }
+
+    pub(crate) fn is_encrypted(bytes: &[u8]) -> bool {
+        bytes.starts_with(Self::MARKER)
+    }
+
+    pub(crate) fn encrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut result = Self::MARKER.to_vec();
+        result.extend(crypto::encrypt_ecies(bytes, &self.keys.get_public_key())?);
+        Ok(result)
+    }
+
+    /// Decrypts the given bytes, or returns them as they are, if they aren't encrypted
+    pub(crate) fn decrypt(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        match bytes.strip_prefix(Self::MARKER) {
+            Some(encrypted) => Ok(crypto::decrypt_ecies(
+                encrypted,
+                &self.keys.get_private_key_string(),
+            )?),
+            None => Ok(bytes.to_vec()),
+        }
+    }
+}
+
+/// Encrypts the given temp file bytes, if encryption is enabled
+pub(crate) fn encrypt_temp_file(
+    encryption: &Option<TempUploadEncryption>,
+    bytes: &[u8],
+) -> Result<Vec<u8>> {
+    match encryption {
+        Some(encryption) => encryption.encrypt(bytes),
+        None => Ok(bytes.to_vec()),
+    }
+}
+
+/// Decrypts the given temp file bytes, if encryption is enabled
+pub(crate) fn decrypt_temp_file(
+    encryption: &Option<TempUploadEncryption>,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>> {
+    match encryption {
+        Some(encryption) => encryption.decrypt(&bytes),
+        None => Ok(bytes),
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Clone)]
 pub struct FileUploadStore {
     temp_upload_folder: String,
     files_folder: String,
+    usage: TempUploadUsage,
+    encryption: Option<TempUploadEncryption>,
+    /// The length of the prefix of the id hash, attached files are sharded by - 0 meaning all
+    /// attached files are stored in a flat layout
+    shard_prefix_length: usize,
 }
 
 /// Given a base path and a directory path, ensures that the directory
@@ -71,13 +176,129 @@ impl FileUploadStore {
         Ok(Self {
             temp_upload_folder,
             files_folder,
+            usage: TempUploadUsage::default(),
+            encryption: None,
+            shard_prefix_length: 0,
         })
     }
 
+    /// Encrypts temp upload files at rest with the given encryption
+    pub fn with_encryption(mut self, encryption: TempUploadEncryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Shards attached files into subfolders by the prefix with the given length of the id hash
+    pub fn with_sharding(mut self, shard_prefix_length: usize) -> Self {
+        self.shard_prefix_length = shard_prefix_length;
+        self
+    }
+
     pub fn get_path_for_files(&self, id: &str) -> PathBuf {
+        match self.shard_prefix(id) {
+            Some(prefix) => PathBuf::from(self.files_folder.as_str())
+                .join(prefix)
+                .join(id),
+            None => self.get_flat_path_for_files(id),
+        }
+    }
+
+    /// The path of the attached files of the given id in the flat layout, which was used before
+    /// sharding was enabled
+    fn get_flat_path_for_files(&self, id: &str) -> PathBuf {
         PathBuf::from(self.files_folder.as_str()).join(id)
     }
 
+    fn shard_prefix(&self, id: &str) -> Option<String> {
+        if self.shard_prefix_length == 0 {
+            return None;
+        }
+        Some(Self::hash_prefix(id, self.shard_prefix_length))
+    }
+
+    fn hash_prefix(id: &str, length: usize) -> String {
+        bcr_ebill_core::util::sha256_hash(id.as_bytes())
+            .chars()
+            .take(length)
+            .collect()
+    }
+
+    /// Moves all attached files, which aren't stored in the configured layout, e.g. since they
+    /// were stored before sharding was enabled, or with a different prefix length, to where they
+    /// belong - flat folders contain the files of a bill, while shard folders only contain bill
+    /// folders, whose id hash starts with the name of the shard folder
+    pub async fn migrate_attached_files_layout(&self) -> Result<()> {
+        let temp_upload_folder = PathBuf::from(self.temp_upload_folder.as_str());
+        let mut bill_folders: Vec<(String, PathBuf)> = vec![];
+        let mut shard_folders: Vec<PathBuf> = vec![];
+        let mut dir = tokio::fs::read_dir(Path::new(&self.files_folder)).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let path = entry.path();
+            if !path.is_dir() || path == temp_upload_folder {
+                continue;
+            }
+            let Some(name) = entry.file_name().to_str().map(|n| n.to_owned()) else {
+                continue;
+            };
+            match Self::get_sharded_bill_folders(&path, &name).await? {
+                Some(sharded) => {
+                    bill_folders.extend(sharded);
+                    shard_folders.push(path);
+                }
+                None => bill_folders.push((name, path)),
+            }
+        }
+
+        for (id, path) in bill_folders {
+            let dest_dir = self.get_path_for_files(&id);
+            if path == dest_dir {
+                continue;
+            }
+            if dest_dir.exists() {
+                log::warn!("not moving attached files at {path:?}, since {dest_dir:?} exists");
+                continue;
+            }
+            if let Some(shard_dir) = dest_dir.parent() {
+                tokio::fs::create_dir_all(shard_dir).await?;
+            }
+            log::info!("moving attached files at {path:?} to {dest_dir:?}");
+            tokio::fs::rename(&path, &dest_dir).await?;
+        }
+
+        // shard folders of a previous layout are left empty
+        for path in shard_folders {
+            let mut entries = tokio::fs::read_dir(&path).await?;
+            if entries.next_entry().await?.is_none() {
+                tokio::fs::remove_dir(&path).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the ids and paths of the bill folders in the given folder, if it's a shard folder,
+    /// or None, if it's the folder of a bill
+    async fn get_sharded_bill_folders(
+        path: &Path,
+        name: &str,
+    ) -> Result<Option<Vec<(String, PathBuf)>>> {
+        let mut bill_folders = vec![];
+        let mut dir = tokio::fs::read_dir(path).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let child_path = entry.path();
+            let Some(id) = entry.file_name().to_str().map(|n| n.to_owned()) else {
+                return Ok(None);
+            };
+            if !child_path.is_dir() || Self::hash_prefix(&id, name.chars().count()) != name {
+                return Ok(None);
+            }
+            bill_folders.push((id, child_path));
+        }
+        if bill_folders.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(bill_folders))
+    }
+
     pub async fn cleanup_temp_uploads(&self) -> Result<()> {
         log::info!("cleaning up temp upload folder");
         let path = Path::new(&self.temp_upload_folder);
@@ -91,6 +312,29 @@ impl FileUploadStore {
         }
         Ok(())
     }
+
+    /// Encrypts all plaintext temp upload files - a no-op, if encryption is disabled
+    pub async fn encrypt_plaintext_temp_uploads(&self) -> Result<()> {
+        let Some(ref encryption) = self.encryption else {
+            return Ok(());
+        };
+        let mut dir = tokio::fs::read_dir(Path::new(&self.temp_upload_folder)).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            if !entry.path().is_dir() {
+                continue;
+            }
+            let mut files = tokio::fs::read_dir(entry.path()).await?;
+            while let Some(file) = files.next_entry().await? {
+                let file_path = file.path();
+                let file_bytes = tokio::fs::read(&file_path).await?;
+                if !TempUploadEncryption::is_encrypted(&file_bytes) {
+                    log::info!("encrypting plaintext temp upload file at {file_path:?}");
+                    tokio::fs::write(&file_path, encryption.encrypt(&file_bytes)?).await?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -110,9 +354,35 @@ impl FileUploadStoreApi for FileUploadStore {
             log::info!("deleting temp upload folder for bill at {dest_dir:?}");
             tokio::fs::remove_dir_all(dest_dir).await?;
         }
+        self.usage.remove(file_upload_id);
         Ok(())
     }
 
+    async fn list_temp_upload_folders_older_than(&self, ttl_seconds: u64) -> Result<Vec<String>> {
+        let threshold = (date::now().timestamp() as u64).saturating_sub(ttl_seconds);
+        let mut result = vec![];
+        let mut dir = tokio::fs::read_dir(Path::new(&self.temp_upload_folder)).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if !metadata.is_dir() {
+                continue;
+            }
+            let Some(file_upload_id) = entry.file_name().to_str().map(|n| n.to_owned()) else {
+                continue;
+            };
+            // the folder is modified when files are written into it
+            let modified = metadata
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            if self.usage.last_used(&file_upload_id, modified) < threshold {
+                result.push(file_upload_id);
+            }
+        }
+        Ok(result)
+    }
+
     async fn write_temp_upload_file(
         &self,
         file_upload_id: &str,
@@ -122,21 +392,25 @@ impl FileUploadStoreApi for FileUploadStore {
         let dest = Path::new(&self.temp_upload_folder)
             .join(file_upload_id)
             .join(file_name);
-        tokio::fs::write(dest, file_bytes).await?;
+        tokio::fs::write(dest, encrypt_temp_file(&self.encryption, file_bytes)?).await?;
         Ok(())
     }
 
     async fn read_temp_upload_file(&self, file_upload_id: &str) -> Result<(String, Vec<u8>)> {
+        self.usage.mark_used(file_upload_id);
         let mut files = Vec::new();
         let folder = Path::new(&self.temp_upload_folder).join(file_upload_id);
         let mut dir = tokio::fs::read_dir(&folder).await?;
         while let Some(entry) = dir.next_entry().await? {
             let file_path = entry.path();
-            if let Some(file_name) = file_path.file_name() {
-                if let Some(file_name_str) = file_name.to_str() {
-                    let file_bytes = tokio::fs::read(&file_path).await?;
-                    files.push((file_name_str.to_owned(), file_bytes));
-                }
+            if let Some(file_name) = file_path.file_name()
+                && let Some(file_name_str) = file_name.to_str()
+            {
+                let file_bytes = tokio::fs::read(&file_path).await?;
+                files.push((
+                    file_name_str.to_owned(),
+                    decrypt_temp_file(&self.encryption, file_bytes)?,
+                ));
             }
         }
         if files.is_empty() {
@@ -164,7 +438,11 @@ impl FileUploadStoreApi for FileUploadStore {
     }
 
     async fn open_attached_file(&self, id: &str, file_name: &str) -> Result<Vec<u8>> {
-        let path = self.get_path_for_files(id).join(file_name);
+        let mut path = self.get_path_for_files(id).join(file_name);
+        // files, which weren't migrated yet, are still in the flat layout
+        if !path.exists() {
+            path = self.get_flat_path_for_files(id).join(file_name);
+        }
 
         let mut file = tokio::fs::File::open(&path).await?;
         let mut buf = Vec::new();
@@ -174,12 +452,127 @@ impl FileUploadStoreApi for FileUploadStore {
     }
 
     async fn delete_attached_files(&self, id: &str) -> Result<()> {
-        let path = self.get_path_for_files(id);
-
-        if path.is_dir() {
-            log::info!("deleting attached files at {path:?}");
-            tokio::fs::remove_dir_all(path).await?;
+        for path in [
+            self.get_path_for_files(id),
+            self.get_flat_path_for_files(id),
+        ] {
+            if path.is_dir() {
+                log::info!("deleting attached files at {path:?}");
+                tokio::fs::remove_dir_all(path).await?;
+            }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+
+    async fn get_store(name: &str) -> FileUploadStore {
+        let data_dir = std::env::temp_dir().join(format!(
+            "bcr_ebill_file_upload_{name}_{}",
+            bcr_ebill_core::util::get_uuid_v4()
+        ));
+        FileUploadStore::new(data_dir.to_str().unwrap(), "files", "temp_upload")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_save_and_open_sharded_attached_file() {
+        let store = get_store("sharded").await.with_sharding(2);
+        store
+            .save_attached_file(&[1, 2, 3], "bill_id", "file.pdf")
+            .await
+            .unwrap();
+        assert!(
+            store
+                .get_path_for_files("bill_id")
+                .join("file.pdf")
+                .exists()
+        );
+        assert!(!store.get_flat_path_for_files("bill_id").exists());
+
+        let bytes = store
+            .open_attached_file("bill_id", "file.pdf")
+            .await
+            .unwrap();
+        assert_eq!(bytes, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_attached_files_layout() {
+        let flat_store = get_store("migrate").await;
+        flat_store
+            .save_attached_file(&[1, 2, 3], "bill_id", "file.pdf")
+            .await
+            .unwrap();
+        let store = flat_store.with_sharding(2);
+
+        // not migrated yet - read from the flat layout
+        let bytes = store
+            .open_attached_file("bill_id", "file.pdf")
+            .await
+            .unwrap();
+        assert_eq!(bytes, vec![1, 2, 3]);
+
+        store.migrate_attached_files_layout().await.unwrap();
+        assert!(
+            store
+                .get_path_for_files("bill_id")
+                .join("file.pdf")
+                .exists()
+        );
+        assert!(!store.get_flat_path_for_files("bill_id").exists());
+        // the temp upload folder isn't moved
+        assert!(Path::new(&store.temp_upload_folder).exists());
+        let bytes = store
+            .open_attached_file("bill_id", "file.pdf")
+            .await
+            .unwrap();
+        assert_eq!(bytes, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_attached_files_between_prefix_lengths() {
+        let store = get_store("migrate_prefix").await.with_sharding(2);
+        // short ids are migrated as well
+        for id in ["bill_id", "ab", "a"] {
+            store
+                .save_attached_file(&[1, 2, 3], id, "file.pdf")
+                .await
+                .unwrap();
+        }
+        let old_shard_dir = store
+            .get_path_for_files("bill_id")
+            .parent()
+            .unwrap()
+            .to_owned();
+
+        let store = store.with_sharding(3);
+        store.migrate_attached_files_layout().await.unwrap();
+        for id in ["bill_id", "ab", "a"] {
+            assert!(store.get_path_for_files(id).join("file.pdf").exists());
+        }
+        assert!(!old_shard_dir.exists());
+
+        // and back to the flat layout
+        let store = store.with_sharding(0);
+        store.migrate_attached_files_layout().await.unwrap();
+        for id in ["bill_id", "ab", "a"] {
+            assert!(store.get_flat_path_for_files(id).join("file.pdf").exists());
+            let bytes = store.open_attached_file(id, "file.pdf").await.unwrap();
+            assert_eq!(bytes, vec![1, 2, 3]);
+        }
+        // running it again doesn't change anything
+        store.migrate_attached_files_layout().await.unwrap();
+        assert!(
+            store
+                .get_flat_path_for_files("ab")
+                .join("file.pdf")
+                .exists()
+        );
+    }
+}
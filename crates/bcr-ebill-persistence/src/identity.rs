@@ -24,6 +24,9 @@ pub trait IdentityStoreApi: Send + Sync {
     /// Gets the local key pair or creates a new one if it doesn't exist.
     /// The new key pair is saved to the store together with the node id.
     async fn get_or_create_key_pair(&self) -> Result<BcrKeys>;
+    /// Gets the data key of the local identity, which is used to encrypt local data at rest, or
+    /// creates and saves a new one, if it doesn't exist
+    async fn get_or_create_data_key(&self) -> Result<BcrKeys>;
     /// Returns the seed phrase that generated the private keys.
     async fn get_seedphrase(&self) -> Result<String>;
     /// Returns actively set identity
@@ -12,7 +12,6 @@ pub mod notification;
 mod tests;
 
 use bcr_ebill_core::util;
-use log::error;
 use thiserror::Error;
 
 /// Generic persistence result type
@@ -98,7 +97,8 @@ pub use contact::ContactStoreApi;
 pub use db::file_upload::FileUploadStore;
 pub use db::{
     SurrealDbConfig, backup::SurrealBackupStore, bill::SurrealBillStore,
-    bill_chain::SurrealBillChainStore, company::SurrealCompanyStore,
+    bill_auto_accept::SurrealBillAutoAcceptStore, bill_chain::SurrealBillChainStore,
+    bill_template::SurrealBillTemplateStore, company::SurrealCompanyStore,
     company_chain::SurrealCompanyChainStore, contact::SurrealContactStore, get_surreal_db,
     identity::SurrealIdentityStore, identity_chain::SurrealIdentityChainStore,
     nostr_event_offset::SurrealNostrEventOffsetStore, notification::SurrealNotificationStore,
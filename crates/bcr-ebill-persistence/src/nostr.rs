@@ -8,20 +8,22 @@ use serde_json::Value;
 #[async_trait]
 pub trait NostrEventOffsetStoreApi: Send + Sync {
     /// Returns the current timestamp offset of our nostr subscription
-    /// Will return 0 if there are no events in the store yet, otherwise
-    /// the highest timestamp of all events processed.
+    /// Will return 0 if there are no events in the store yet, the lowest
+    /// timestamp of all events, which failed to be processed, if there are
+    /// any, so they are delivered again, otherwise the highest timestamp of
+    /// all events processed.
     /// there is still a possibility that we get events delivered that are
     /// older than the current offset just because they were not processed
     /// or the faked timestamp on the GiftWrap event was higher than the
     /// current offset.
     async fn current_offset(&self, node_id: &str) -> Result<u64>;
 
-    /// Returns whether the given event id has been processed already. This
-    /// will return true if we never tried to process the event independent
-    /// of whether it was successful or not.
+    /// Returns whether the given event id has been processed successfully
+    /// already. Events, which failed to be processed, are processed again.
     async fn is_processed(&self, event_id: &str) -> Result<bool>;
 
-    /// Stores the given event data in the store.
+    /// Stores the given event data in the store, replacing the data of a
+    /// previous attempt to process the event.
     async fn add_event(&self, data: NostrEventOffset) -> Result<()>;
 }
 
@@ -44,13 +46,19 @@ pub struct NostrEventOffset {
 pub trait NostrQueuedMessageStoreApi: Send + Sync {
     /// Adds a new retry message
     async fn add_message(&self, message: NostrQueuedMessage, max_retries: i32) -> Result<()>;
-    /// Selects all messages that are ready to be retried
-    async fn get_retry_messages(&self, limit: u64) -> Result<Vec<NostrQueuedMessage>>;
-    /// Fail a retry attempt, schedules a new retry or fails the message if
-    /// all retries have been exhausted.
-    async fn fail_retry(&self, id: &str) -> Result<()>;
+    /// Selects all messages that are ready to be retried at the given timestamp, skipping
+    /// messages with a `next_retry_at` in the future
+    async fn get_retry_messages(&self, limit: u64, now: u64) -> Result<Vec<NostrQueuedMessage>>;
+    /// Fail a retry attempt, schedules a new retry at `next_retry_at`, or moves the message
+    /// to the dead-letter state if all retries have been exhausted.
+    async fn fail_retry(&self, id: &str, next_retry_at: u64) -> Result<()>;
     /// Flags a retry as successful
     async fn succeed_retry(&self, id: &str) -> Result<()>;
+    /// Returns up to `limit` messages, which are neither completed, nor dead-lettered, ordered by
+    /// their next retry. Doesn't mark them as processing.
+    async fn get_pending_messages(&self, limit: u64) -> Result<Vec<NostrQueuedMessageInfo>>;
+    /// Returns the number of messages, which exhausted all their retries
+    async fn count_dead_letter_messages(&self) -> Result<u64>;
 }
 
 #[derive(Clone, Debug)]
@@ -59,4 +67,22 @@ pub struct NostrQueuedMessage {
     pub sender_id: String,
     pub node_id: String,
     pub payload: Value,
+    /// The number of failed retries so far
+    pub retry_count: i32,
+    /// The timestamp, after which the message should be retried next
+    pub next_retry_at: u64,
+}
+
+/// The metadata of a queued message, without its payload
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NostrQueuedMessageInfo {
+    pub id: String,
+    pub sender_id: String,
+    pub node_id: String,
+    /// The number of failed retries so far
+    pub retry_count: i32,
+    /// The timestamp, after which the message should be retried next
+    pub next_retry_at: u64,
+    /// The timestamp, at which the message was queued
+    pub created: u64,
 }
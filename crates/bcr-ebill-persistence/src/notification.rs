@@ -4,13 +4,16 @@ use async_trait::async_trait;
 
 use super::Result;
 use bcr_ebill_core::notification::{ActionType, Notification, NotificationType};
+use bcr_ebill_core::util::{self, date::DateTimeUtc};
+use chrono::{DateTime, SecondsFormat, Utc};
 
 #[async_trait]
 pub trait NotificationStoreApi: Send + Sync {
     /// Stores a new notification into the database
     async fn add(&self, notification: Notification) -> Result<Notification>;
-    /// Returns all currently active notifications from the database
-    async fn list(&self, filter: NotificationFilter) -> Result<Vec<Notification>>;
+    /// Returns a page of notifications matching the given filter, newest first, along with the
+    /// cursor to fetch the next page with
+    async fn list(&self, filter: NotificationFilter) -> Result<NotificationPage>;
     /// Returns the latest active notifications for the given reference and notification type
     async fn get_latest_by_references(
         &self,
@@ -28,6 +31,13 @@ pub trait NotificationStoreApi: Send + Sync {
     async fn list_by_type(&self, notification_type: NotificationType) -> Result<Vec<Notification>>;
     /// Marks an active notification as done
     async fn mark_as_done(&self, notification_id: &str) -> Result<()>;
+    /// Marks all active notifications for the given node id, optionally only the ones of the
+    /// given type, as done and returns the number of notifications marked
+    async fn mark_all_as_done(
+        &self,
+        node_id: &str,
+        notification_type: Option<NotificationType>,
+    ) -> Result<u64>;
     /// deletes a notification from the database
     #[allow(unused)]
     async fn delete(&self, notification_id: &str) -> Result<()>;
@@ -45,6 +55,21 @@ pub trait NotificationStoreApi: Send + Sync {
         block_height: i32,
         action_type: ActionType,
     ) -> Result<bool>;
+    /// marks that the event for the given block of the given bill was sent to the given
+    /// participant
+    async fn set_bill_participant_notified(
+        &self,
+        bill_id: &str,
+        block_height: i32,
+        node_id: &str,
+    ) -> Result<()>;
+    /// returns the node ids of the participants the event for the given block of the given bill
+    /// was sent to
+    async fn get_notified_bill_participants(
+        &self,
+        bill_id: &str,
+        block_height: i32,
+    ) -> Result<Vec<String>>;
 }
 
 #[derive(Default, Clone, PartialEq, Debug)]
@@ -54,6 +79,63 @@ pub struct NotificationFilter {
     pub notification_type: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Returns only notifications, which were not marked as done yet
+    pub only_unread: bool,
+    /// Returns only notifications after the given cursor, i.e. the next page
+    pub after: Option<NotificationCursor>,
+}
+
+/// A page of notifications and the cursor to fetch the next page with, if there is one
+#[derive(Default, Clone, Debug)]
+pub struct NotificationPage {
+    pub notifications: Vec<Notification>,
+    pub next_cursor: Option<NotificationCursor>,
+}
+
+/// The position of a notification in the list, made up of its creation time and id. Since the
+/// list is ordered by both, new notifications don't shift the position, unlike an offset does
+#[derive(Clone, PartialEq, Debug)]
+pub struct NotificationCursor {
+    pub datetime: DateTimeUtc,
+    pub id: String,
+}
+
+impl NotificationCursor {
+    pub fn new(notification: &Notification) -> Self {
+        Self {
+            datetime: notification.datetime,
+            id: notification.id.clone(),
+        }
+    }
+
+    /// Encodes the cursor as an opaque string for clients
+    pub fn encode(&self) -> String {
+        util::base58_encode(
+            format!(
+                "{}|{}",
+                self.datetime.to_rfc3339_opts(SecondsFormat::Nanos, true),
+                self.id
+            )
+            .as_bytes(),
+        )
+    }
+
+    /// Decodes a cursor created with `encode` - returns None, if it's invalid
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = util::base58_decode(cursor).ok()?;
+        let decoded = String::from_utf8(bytes).ok()?;
+        let (datetime, id) = decoded.split_once('|')?;
+        if id.is_empty() {
+            return None;
+        }
+        let datetime = DateTime::parse_from_rfc3339(datetime)
+            .ok()?
+            .with_timezone(&Utc);
+        Some(Self {
+            datetime,
+            id: id.to_owned(),
+        })
+    }
 }
 
 impl NotificationFilter {
@@ -62,12 +144,20 @@ impl NotificationFilter {
         if self.active.is_some() {
             parts.push("active = $active");
         }
+        if self.only_unread {
+            parts.push("active = true");
+        }
         if self.reference_id.is_some() {
             parts.push("reference_id = $reference_id");
         }
         if self.notification_type.is_some() {
             parts.push("notification_type = $notification_type");
         }
+        if self.after.is_some() {
+            parts.push(
+                "(datetime < $after_datetime OR (datetime = $after_datetime AND id < type::thing($table, $after_id)))",
+            );
+        }
 
         let filters = parts.join(" AND ");
         if filters.is_empty() {
@@ -95,6 +185,15 @@ impl NotificationFilter {
             .map(|reference_id| ("reference_id".to_string(), reference_id.to_string()))
     }
 
+    pub fn get_after(&self) -> Option<((String, DateTimeUtc), (String, String))> {
+        self.after.as_ref().map(|after| {
+            (
+                ("after_datetime".to_string(), after.datetime),
+                ("after_id".to_string(), after.id.to_owned()),
+            )
+        })
+    }
+
     pub fn get_notification_type(&self) -> Option<(String, String)> {
         self.notification_type.as_ref().map(|notification_type| {
             (
@@ -118,6 +217,12 @@ mod tests {
         };
         assert_eq!(active.filters(), "WHERE active = $active");
 
+        let only_unread = super::NotificationFilter {
+            only_unread: true,
+            ..Default::default()
+        };
+        assert_eq!(only_unread.filters(), "WHERE active = true");
+
         let all = super::NotificationFilter {
             active: Some(true),
             reference_id: Some("123".to_string()),
@@ -129,5 +234,43 @@ mod tests {
             all.filters(),
             "WHERE active = $active AND reference_id = $reference_id AND notification_type = $notification_type"
         );
+
+        let after = super::NotificationFilter {
+            after: Some(super::NotificationCursor {
+                datetime: super::util::date::now(),
+                id: "123".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            after.filters(),
+            "WHERE (datetime < $after_datetime OR (datetime = $after_datetime AND id < type::thing($table, $after_id)))"
+        );
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = super::NotificationCursor {
+            datetime: super::util::date::now(),
+            id: "a4b5c6d7-1234-5678-9abc-def012345678".to_string(),
+        };
+        let encoded = cursor.encode();
+        assert_eq!(super::NotificationCursor::decode(&encoded), Some(cursor));
+    }
+
+    #[test]
+    fn test_cursor_decode_invalid() {
+        assert_eq!(super::NotificationCursor::decode(""), None);
+        assert_eq!(super::NotificationCursor::decode("not base58 0OIl"), None);
+        assert_eq!(
+            super::NotificationCursor::decode(&super::util::base58_encode(b"no separator")),
+            None
+        );
+        assert_eq!(
+            super::NotificationCursor::decode(&super::util::base58_encode(
+                b"2025-01-01T00:00:00Z|"
+            )),
+            None
+        );
     }
 }
@@ -6,7 +6,10 @@ pub mod tests {
         bill::{
             BillAcceptanceStatus, BillData, BillKeys, BillParticipants, BillPaymentStatus,
             BillRecourseStatus, BillSellStatus, BillStatus, BitcreditBill, BitcreditBillResult,
+            SettlementStatus,
         },
+        blockchain::bill::BillOpCode,
+        constants::PAYMENT_DEADLINE_SECONDS,
         contact::{ContactType, IdentityPublicData},
         identity::Identity,
     };
@@ -84,6 +87,8 @@ pub mod tests {
             country_of_payment: "".to_string(),
             language: "".to_string(),
             files: vec![],
+            payment_deadline_seconds: PAYMENT_DEADLINE_SECONDS,
+            fiat_amount: None,
         }
     }
 
@@ -111,7 +116,12 @@ pub mod tests {
                 currency: "sat".to_string(),
                 sum: "15000".to_string(),
                 files: vec![],
+                tampered_files: vec![],
+                acknowledgements: vec![],
+                payment_deadline_seconds: PAYMENT_DEADLINE_SECONDS,
+                fiat_amount: None,
                 active_notification: None,
+                note: None,
             },
             status: BillStatus {
                 acceptance: BillAcceptanceStatus {
@@ -120,13 +130,17 @@ pub mod tests {
                     accepted: false,
                     request_to_accept_timed_out: false,
                     rejected_to_accept: false,
+                    rejected_to_accept_reason: None,
                 },
                 payment: BillPaymentStatus {
                     time_of_request_to_pay: None,
                     requested_to_pay: false,
                     paid: false,
+                    paid_sum: 0,
                     request_to_pay_timed_out: false,
                     rejected_to_pay: false,
+                    rejected_to_pay_reason: None,
+                    external_payment_reference: None,
                 },
                 sell: BillSellStatus {
                     time_of_last_offer_to_sell: None,
@@ -134,6 +148,8 @@ pub mod tests {
                     offered_to_sell: false,
                     offer_to_sell_timed_out: false,
                     rejected_offer_to_sell: false,
+                    rejected_offer_to_sell_reason: None,
+                    sell_deadline_seconds: None,
                 },
                 recourse: BillRecourseStatus {
                     time_of_last_request_to_recourse: None,
@@ -144,6 +160,10 @@ pub mod tests {
                 },
                 redeemed_funds_available: false,
                 has_requested_funds: false,
+                hold: None,
+                last_op_code: BillOpCode::Issue,
+                block_height: 1,
+                settlement_status: SettlementStatus::Active,
             },
             current_waiting_state: None,
         }
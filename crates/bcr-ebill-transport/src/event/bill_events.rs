@@ -16,6 +16,8 @@ pub struct BillChainEventPayload {
     pub bill_id: String,
     pub action_type: Option<ActionType>,
     pub sum: Option<u64>,
+    /// The reason given by the participant, if the event is a rejection
+    pub reason: Option<String>,
     pub keys: Option<BillKeys>,
     pub blocks: Vec<BillBlock>,
 }
@@ -20,6 +20,7 @@ pub struct BillChainEvent {
     participants: HashMap<String, usize>,
     bill_keys: BillKeys,
     new_blocks: bool,
+    new_block_count: usize,
     sender_node_id: String,
 }
 
@@ -49,28 +50,42 @@ impl BillChainEvent {
             participants,
             bill_keys: bill_keys.clone(),
             new_blocks,
+            new_block_count: 1,
             sender_node_id: sender_node_id.to_owned(),
         })
     }
 
+    /// Sets the number of blocks that were added to the chain by the action this event is
+    /// created for. Defaults to 1.
+    pub fn with_new_block_count(mut self, new_block_count: usize) -> Self {
+        self.new_block_count = new_block_count.max(1);
+        self
+    }
+
     pub fn sender(&self) -> String {
         self.sender_node_id.clone()
     }
 
-    // Returns the latest block in the chain.
-    fn latest_block(&self) -> BillBlock {
-        self.chain.get_latest_block().clone()
+    // Returns the newly added blocks in the chain.
+    fn latest_blocks(&self) -> Vec<BillBlock> {
+        let blocks = self.chain.blocks();
+        blocks[blocks.len().saturating_sub(self.new_block_count)..].to_vec()
+    }
+
+    // Checks whether the participant was added with one of the newly added blocks.
+    fn is_new_participant(&self, height: usize) -> bool {
+        height + self.new_block_count > self.chain.block_height()
     }
 
-    // Returns all blocks for newly added participants, otherwise just the latest block or no
+    // Returns all blocks for newly added participants, otherwise just the latest blocks or no
     // blocks if the node is not a participant.
     fn get_blocks_for_node(&self, node_id: &str) -> Vec<BillBlock> {
         if !self.new_blocks {
             return Vec::new();
         }
         match self.participants.get(node_id) {
-            Some(height) if *height == self.chain.block_height() => self.chain.blocks().clone(),
-            Some(_) => vec![self.latest_block()],
+            Some(height) if self.is_new_participant(*height) => self.chain.blocks().clone(),
+            Some(_) => self.latest_blocks(),
             _ => Vec::new(),
         }
     }
@@ -80,7 +95,7 @@ impl BillChainEvent {
             return None;
         }
         match self.participants.get(node_id) {
-            Some(height) if *height == self.chain.block_height() => Some(self.bill_keys.clone()),
+            Some(height) if self.is_new_participant(*height) => Some(self.bill_keys.clone()),
             _ => None,
         }
     }
@@ -110,6 +125,7 @@ impl BillChainEvent {
                         bill_id: self.bill.id.to_owned(),
                         action_type: override_action.or(action.clone()),
                         sum: Some(self.bill.sum),
+                        reason: None,
                         blocks: self.get_blocks_for_node(node_id),
                         keys: self.get_keys_for_node(node_id),
                     },
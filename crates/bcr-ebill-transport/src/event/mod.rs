@@ -2,19 +2,25 @@ pub mod bill_events;
 pub mod chain_event;
 
 use crate::{Error, Result};
-use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde::de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, de::DeserializeOwned};
 use serde_json::Value;
+use std::fmt;
 
 /// The global event type that is used for all events.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum EventType {
     /// Bill related events
     Bill,
+    /// Company related events
+    Company,
+    /// Identity related events
+    Identity,
 }
 
 impl EventType {
     pub fn all() -> Vec<EventType> {
-        vec![EventType::Bill]
+        vec![EventType::Bill, EventType::Company, EventType::Identity]
     }
 }
 
@@ -69,6 +75,131 @@ pub struct EventEnvelope {
     pub data: Value,
 }
 
+impl EventEnvelope {
+    /// Parses an envelope from the given json. The blocks of a bill event are only counted first,
+    /// so an event with more than the given number of blocks is rejected before they are
+    /// deserialized
+    pub fn from_json(json: &str, max_bill_blocks: usize) -> Result<Self> {
+        let counted: EnvelopeBlockCount = serde_json::from_str(json)?;
+        if counted.event_type == EventType::Bill && counted.data.0 > max_bill_blocks {
+            return Err(Error::Blockchain(format!(
+                "Received {} bill blocks, which exceeds the maximum of {max_bill_blocks} blocks",
+                counted.data.0
+            )));
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+// the event type and the number of blocks in the data of an envelope
+#[derive(Deserialize)]
+struct EnvelopeBlockCount {
+    event_type: EventType,
+    #[serde(default)]
+    data: DataBlockCount,
+}
+
+// the length of the blocks array of the event data - everything else is skipped
+#[derive(Default)]
+struct DataBlockCount(usize);
+
+impl<'de> Deserialize<'de> for DataBlockCount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(DataBlockCountVisitor)
+    }
+}
+
+struct DataBlockCountVisitor;
+
+impl<'de> Visitor<'de> for DataBlockCountVisitor {
+    type Value = DataBlockCount;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("event data")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut count = 0;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "blocks" {
+                count = map.next_value::<BlockCount>()?.0;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(DataBlockCount(count))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(DataBlockCount(0))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(DataBlockCount(0))
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> std::result::Result<Self::Value, E> {
+        Ok(DataBlockCount(0))
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> std::result::Result<Self::Value, E> {
+        Ok(DataBlockCount(0))
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> std::result::Result<Self::Value, E> {
+        Ok(DataBlockCount(0))
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> std::result::Result<Self::Value, E> {
+        Ok(DataBlockCount(0))
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> std::result::Result<Self::Value, E> {
+        Ok(DataBlockCount(0))
+    }
+}
+
+// the number of elements of an array, without deserializing them
+struct BlockCount(usize);
+
+impl<'de> Deserialize<'de> for BlockCount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(BlockCountVisitor)
+    }
+}
+
+struct BlockCountVisitor;
+
+impl<'de> Visitor<'de> for BlockCountVisitor {
+    type Value = BlockCount;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of blocks")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut count = 0;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            count += 1;
+        }
+        Ok(BlockCount(count))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Self::Value, E> {
+        Ok(BlockCount(0))
+    }
+}
+
 impl<T: Serialize> TryFrom<Event<T>> for EventEnvelope {
     type Error = Error;
 
@@ -159,6 +290,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_json_rejects_too_many_bill_blocks() {
+        // the blocks are not even valid blocks - they are only counted
+        let json = serde_json::json!({
+            "event_type": "Bill",
+            "version": "1.0",
+            "node_id": "node_id",
+            "data": { "bill_id": "bill_id", "blocks": [{}, {}, {}] },
+        })
+        .to_string();
+        assert!(matches!(
+            EventEnvelope::from_json(&json, 2),
+            Err(Error::Blockchain(_))
+        ));
+        assert!(EventEnvelope::from_json(&json, 3).is_ok());
+    }
+
+    #[test]
+    fn test_from_json_parses_events_without_blocks() {
+        let event = Event::new(EventType::Company, "node_id", create_test_event_payload());
+        let json = serde_json::to_string(&event).unwrap();
+        let envelope = EventEnvelope::from_json(&json, 0).unwrap();
+        let deserialized: Event<TestEventPayload> = envelope.try_into().unwrap();
+        assert_eq!(deserialized.data, create_test_event_payload());
+    }
+
     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
     pub struct TestEventPayload {
         pub foo: String,
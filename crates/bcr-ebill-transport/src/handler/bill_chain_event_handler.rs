@@ -7,13 +7,17 @@ use bcr_ebill_core::ServiceTraitBounds;
 use bcr_ebill_core::Validate;
 use bcr_ebill_core::bill::BillKeys;
 use bcr_ebill_core::bill::BillValidateActionData;
+use bcr_ebill_core::bill::QueuedAutoAccept;
 use bcr_ebill_core::blockchain::Blockchain;
 use bcr_ebill_core::blockchain::bill::BillOpCode;
 use bcr_ebill_core::blockchain::bill::block::BillIssueBlockData;
 use bcr_ebill_core::blockchain::bill::{BillBlock, BillBlockchain};
+use bcr_ebill_core::constants::DEFAULT_MAX_BILL_CHAIN_BLOCKS;
 use bcr_ebill_core::notification::BillEventType;
 use bcr_ebill_core::notification::{Notification, NotificationType};
+use bcr_ebill_core::util;
 use bcr_ebill_persistence::NotificationStoreApi;
+use bcr_ebill_persistence::bill::BillAutoAcceptStoreApi;
 use bcr_ebill_persistence::bill::BillChainStoreApi;
 use bcr_ebill_persistence::bill::BillStoreApi;
 use log::debug;
@@ -26,8 +30,10 @@ use std::sync::Arc;
 pub struct BillChainEventHandler {
     notification_store: Arc<dyn NotificationStoreApi>,
     push_service: Arc<dyn PushApi>,
-    bill_blockchain_store: Arc<dyn BillChainStoreApi>,
-    bill_store: Arc<dyn BillStoreApi>,
+    chain_sync: BillChainSync,
+    /// If set, bills requested to be accepted are queued to be accepted automatically, if they
+    /// match an auto-accept rule of the drawee
+    auto_accept_store: Option<Arc<dyn BillAutoAcceptStoreApi>>,
 }
 
 impl BillChainEventHandler {
@@ -40,11 +46,88 @@ impl BillChainEventHandler {
         Self {
             notification_store,
             push_service,
-            bill_blockchain_store,
-            bill_store,
+            chain_sync: BillChainSync::new(bill_blockchain_store, bill_store),
+            auto_accept_store: None,
         }
     }
 
+    /// Evaluates the auto-accept rules in the given store for received requests to accept
+    pub fn with_auto_accept(mut self, auto_accept_store: Arc<dyn BillAutoAcceptStoreApi>) -> Self {
+        self.auto_accept_store = Some(auto_accept_store);
+        self
+    }
+
+    /// Rejects received bill chains with more than the given number of blocks, instead of the
+    /// default maximum
+    pub fn with_max_chain_blocks(mut self, max_blocks: usize) -> Self {
+        self.chain_sync = self.chain_sync.with_max_chain_blocks(max_blocks);
+        self
+    }
+
+    /// If the given node id is the drawee of the bill and the bill matches one of its
+    /// auto-accept rules, the bill is queued to be accepted. The accept action itself is executed
+    /// and fully validated by the bill service, when the queue is processed
+    async fn queue_auto_accept(
+        &self,
+        auto_accept_store: &Arc<dyn BillAutoAcceptStoreApi>,
+        bill_id: &str,
+        node_id: &str,
+    ) -> Result<()> {
+        let rules = auto_accept_store
+            .get_rules(node_id)
+            .await
+            .map_err(|e| Error::Persistence(e.to_string()))?;
+        if rules.is_empty() {
+            return Ok(());
+        }
+        let keys = self
+            .chain_sync
+            .bill_store
+            .get_keys(bill_id)
+            .await
+            .map_err(|e| Error::Persistence(e.to_string()))?;
+        let chain = self
+            .chain_sync
+            .bill_blockchain_store
+            .get_chain(bill_id)
+            .await
+            .map_err(|e| Error::Persistence(e.to_string()))?;
+        let bill = chain
+            .get_first_version_bill(&keys)
+            .map_err(|e| Error::Blockchain(e.to_string()))?;
+        // the drawee might have been corrected since the bill was issued
+        let bill_parties = chain
+            .get_bill_parties(&keys, &bill)
+            .map_err(|e| Error::Blockchain(e.to_string()))?;
+        if bill_parties.drawee.node_id != node_id {
+            return Ok(());
+        }
+        match rules
+            .iter()
+            .find(|rule| rule.matches(&bill_parties.drawer.node_id, bill.sum))
+        {
+            Some(rule) => {
+                info!(
+                    "Bill {bill_id} from drawer {} with sum {} matches auto-accept rule {} of {node_id} - queueing it to be accepted",
+                    bill_parties.drawer.node_id, bill.sum, rule.id
+                );
+                auto_accept_store
+                    .queue_accept(&QueuedAutoAccept {
+                        bill_id: bill_id.to_owned(),
+                        node_id: node_id.to_owned(),
+                        rule_id: rule.id.clone(),
+                        timestamp: util::date::now().timestamp() as u64,
+                    })
+                    .await
+                    .map_err(|e| Error::Persistence(e.to_string()))?;
+            }
+            None => {
+                debug!("Bill {bill_id} matches no auto-accept rule of {node_id}");
+            }
+        }
+        Ok(())
+    }
+
     async fn create_notification(
         &self,
         event: &BillChainEventPayload,
@@ -104,6 +187,54 @@ impl BillChainEventHandler {
         }
         Ok(())
     }
+}
+
+/// Validates, decrypts and persists received bill chains - used for bill events from the
+/// subscription, as well as for bill events, which were received via another channel
+#[derive(Clone)]
+pub struct BillChainSync {
+    bill_blockchain_store: Arc<dyn BillChainStoreApi>,
+    bill_store: Arc<dyn BillStoreApi>,
+    max_chain_blocks: usize,
+}
+
+impl BillChainSync {
+    pub fn new(
+        bill_blockchain_store: Arc<dyn BillChainStoreApi>,
+        bill_store: Arc<dyn BillStoreApi>,
+    ) -> Self {
+        Self {
+            bill_blockchain_store,
+            bill_store,
+            max_chain_blocks: DEFAULT_MAX_BILL_CHAIN_BLOCKS,
+        }
+    }
+
+    /// Rejects bill chains with more than the given number of blocks, instead of the default
+    /// maximum
+    pub fn with_max_chain_blocks(mut self, max_blocks: usize) -> Self {
+        self.max_chain_blocks = max_blocks;
+        self
+    }
+
+    /// Ingests the bill chain of the given bill event and returns the bill id. If the chain is
+    /// already known, only the missing blocks are added
+    pub async fn ingest_bill_event(&self, event: EventEnvelope) -> Result<String> {
+        check_chain_length(&event, self.max_chain_blocks)?;
+        let decoded = Event::<BillChainEventPayload>::try_from(event)?;
+        if decoded.data.blocks.is_empty() {
+            return Err(Error::Blockchain(
+                "Received bill event without bill blocks".to_string(),
+            ));
+        }
+        self.process_chain_data(
+            &decoded.data.bill_id,
+            decoded.data.blocks,
+            decoded.data.keys,
+        )
+        .await?;
+        Ok(decoded.data.bill_id)
+    }
 
     async fn process_chain_data(
         &self,
@@ -112,7 +243,18 @@ impl BillChainEventHandler {
         keys: Option<BillKeys>,
     ) -> Result<()> {
         if let Ok(existing_chain) = self.bill_blockchain_store.get_chain(bill_id).await {
-            self.add_bill_blocks(bill_id, existing_chain, blocks).await
+            let (remote_latest_hash, remote_height) = match blocks.last() {
+                Some(latest) => (latest.hash.clone(), latest.id),
+                None => return Ok(()),
+            };
+            self.sync_bill_blocks(
+                bill_id,
+                existing_chain,
+                &remote_latest_hash,
+                remote_height,
+                blocks,
+            )
+            .await
         } else {
             match keys {
                 Some(keys) => self.add_new_chain(blocks, &keys).await,
@@ -126,6 +268,30 @@ impl BillChainEventHandler {
         }
     }
 
+    /// Syncs the local chain with a remote chain with the given latest block hash and height,
+    /// only adding the blocks after the common ancestor. Fails, if the remote chain forked
+    async fn sync_bill_blocks(
+        &self,
+        bill_id: &str,
+        existing: BillBlockchain,
+        remote_latest_hash: &str,
+        remote_height: u64,
+        blocks: Vec<BillBlock>,
+    ) -> Result<()> {
+        let missing_blocks = existing
+            .get_missing_blocks(remote_latest_hash, remote_height, &blocks)
+            .map_err(|e| {
+                error!("Could not sync received blocks for bill {bill_id}: {e}");
+                Error::Blockchain(e.to_string())
+            })?;
+        if missing_blocks.is_empty() {
+            debug!("bill {bill_id} is already in sync with height {remote_height}");
+            return Ok(());
+        }
+        self.add_bill_blocks(bill_id, existing, missing_blocks)
+            .await
+    }
+
     async fn add_bill_blocks(
         &self,
         bill_id: &str,
@@ -133,7 +299,7 @@ impl BillChainEventHandler {
         blocks: Vec<BillBlock>,
     ) -> Result<()> {
         let mut block_added = false;
-        let mut chain = existing;
+        let mut chain = existing.with_max_blocks(self.max_chain_blocks);
         let bill_keys = self.bill_store.get_keys(bill_id).await.map_err(|e| {
             error!("Could not process received blocks for {bill_id} because the bill keys could not be fetched");
             Error::Persistence(e.to_string())
@@ -259,16 +425,18 @@ impl BillChainEventHandler {
         // issue block was validate in get_valid_chain
         let issue_block = chain.get_first_block().to_owned();
         // create a chain that starts from issue, to simulate adding blocks and validating them
-        let mut chain_starting_at_issue =
-            match BillBlockchain::new_from_blocks(vec![issue_block.clone()]) {
-                Ok(chain) => chain,
-                Err(e) => {
-                    error!("Newly received chain is not valid: {e}");
-                    return Err(Error::Blockchain(
-                        "Newly received chain is not valid".to_string(),
-                    ));
-                }
-            };
+        let mut chain_starting_at_issue = match BillBlockchain::new_from_blocks_with_max(
+            vec![issue_block.clone()],
+            self.max_chain_blocks,
+        ) {
+            Ok(chain) => chain,
+            Err(e) => {
+                error!("Newly received chain is not valid: {e}");
+                return Err(Error::Blockchain(
+                    "Newly received chain is not valid".to_string(),
+                ));
+            }
+        };
         self.save_block(&bill_id, &issue_block).await?;
 
         // Only add other blocks, if there are any
@@ -296,7 +464,7 @@ impl BillChainEventHandler {
         keys: &BillKeys,
     ) -> Result<(String, BillIssueBlockData, BillBlockchain)> {
         // cheap integrity checks first
-        match BillBlockchain::new_from_blocks(blocks) {
+        match BillBlockchain::new_from_blocks_with_max(blocks, self.max_chain_blocks) {
             Ok(chain) if chain.is_chain_valid() => {
                 // make sure first block is of type Issue
                 if chain.get_first_block().op_code != BillOpCode::Issue {
@@ -385,19 +553,38 @@ impl NotificationHandlerApi for BillChainEventHandler {
 
     async fn handle_event(&self, event: EventEnvelope, node_id: &str) -> Result<()> {
         debug!("incoming bill chain event {event:?} for {node_id}");
+        if let Err(e) = check_chain_length(&event, self.chain_sync.max_chain_blocks) {
+            error!("Rejecting bill chain event: {e}");
+            return Ok(());
+        }
         if let Ok(decoded) = Event::<BillChainEventPayload>::try_from(event.clone()) {
-            if !decoded.data.blocks.is_empty() {
-                if let Err(e) = self
+            if !decoded.data.blocks.is_empty()
+                && let Err(e) = self
+                    .chain_sync
                     .process_chain_data(
                         &decoded.data.bill_id,
                         decoded.data.blocks.clone(),
                         decoded.data.keys.clone(),
                     )
                     .await
-                {
-                    error!("Failed to process chain data: {}", e);
-                    return Ok(());
-                }
+            {
+                error!("Failed to process chain data: {}", e);
+                return Ok(());
+            }
+            if let Some(ref auto_accept_store) = self.auto_accept_store
+                && matches!(
+                    decoded.data.event_type,
+                    BillEventType::BillAcceptanceRequested
+                        | BillEventType::BillAcceptanceAndPaymentRequested
+                )
+                && let Err(e) = self
+                    .queue_auto_accept(auto_accept_store, &decoded.data.bill_id, node_id)
+                    .await
+            {
+                error!(
+                    "Failed to evaluate auto-accept rules for bill {}: {e}",
+                    decoded.data.bill_id
+                );
             }
             if let Err(e) = self.create_notification(&decoded.data, node_id).await {
                 error!("Failed to create notification for bill event: {}", e);
@@ -409,6 +596,21 @@ impl NotificationHandlerApi for BillChainEventHandler {
     }
 }
 
+// checks the number of blocks in the event, before deserializing and validating the blocks
+fn check_chain_length(event: &EventEnvelope, max_blocks: usize) -> Result<()> {
+    match event
+        .data
+        .get("blocks")
+        .and_then(|blocks| blocks.as_array())
+    {
+        Some(blocks) if blocks.len() > max_blocks => Err(Error::Blockchain(format!(
+            "Received {} bill blocks, which exceeds the maximum of {max_blocks} blocks",
+            blocks.len()
+        ))),
+        _ => Ok(()),
+    }
+}
+
 // generates a human readable description for an event
 fn event_description(event_type: &BillEventType) -> String {
     match event_type {
@@ -419,6 +621,9 @@ fn event_description(event_type: &BillEventType) -> String {
         BillEventType::BillAcceptanceTimeout => "bill_acceptance_timed_out".to_string(),
         BillEventType::BillAcceptanceRecourse => "bill_recourse_acceptance_required".to_string(),
         BillEventType::BillPaymentRequested => "bill_payment_required".to_string(),
+        BillEventType::BillAcceptanceAndPaymentRequested => {
+            "bill_should_be_accepted_and_paid".to_string()
+        }
         BillEventType::BillPaymentRejected => "bill_payment_rejected".to_string(),
         BillEventType::BillPaymentTimeout => "bill_payment_timed_out".to_string(),
         BillEventType::BillPaymentRecourse => "bill_recourse_payment_required".to_string(),
@@ -433,6 +638,11 @@ fn event_description(event_type: &BillEventType) -> String {
         BillEventType::BillMintingRequested => "bill_minted".to_string(),
         BillEventType::BillNewQuote => "new_quote".to_string(),
         BillEventType::BillQuoteApproved => "quote_approved".to_string(),
+        BillEventType::BillQuoteRejected => "quote_rejected".to_string(),
+        BillEventType::BillMaturityApproaching => "bill_maturity_approaching".to_string(),
+        BillEventType::BillActionTimeoutApproaching => {
+            "bill_action_timeout_approaching".to_string()
+        }
         BillEventType::BillBlock => "".to_string(),
     }
 }
@@ -441,8 +651,9 @@ fn event_description(event_type: &BillEventType) -> String {
 mod tests {
     use bcr_ebill_core::{
         OptionalPostalAddress, PostalAddress,
-        bill::BitcreditBill,
+        bill::{BillAutoAcceptRule, BitcreditBill},
         blockchain::bill::block::{BillEndorseBlockData, BillIssueBlockData, BillRejectBlockData},
+        constants::PAYMENT_DEADLINE_SECONDS,
         contact::{ContactType, IdentityPublicData},
         identity::{Identity, IdentityWithAll},
         notification::ActionType,
@@ -451,7 +662,8 @@ mod tests {
     use mockall::predicate::{always, eq};
 
     use crate::handler::test_utils::{
-        MockBillChainStore, MockBillStore, MockNotificationStore, MockPushService,
+        MockBillAutoAcceptStore, MockBillChainStore, MockBillStore, MockNotificationStore,
+        MockPushService,
     };
 
     use super::*;
@@ -513,6 +725,7 @@ mod tests {
                 keys: Some(keys.clone()),
                 sum: Some(0),
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -522,6 +735,104 @@ mod tests {
             .expect("Event should be handled");
     }
 
+    #[tokio::test]
+    async fn test_ingests_exported_bill_event() {
+        let payer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let mut payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        payee.node_id = OTHER_TEST_PUB_KEY_SECP.to_owned();
+        let drawer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, Some(&drawer), None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+        let keys = get_bill_keys();
+
+        let (_, _, mut bill_chain_store, mut bill_store) = create_mocks();
+
+        bill_chain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID))
+            .times(1)
+            .returning(move |_| Err(bcr_ebill_persistence::Error::NoBillBlock));
+        bill_chain_store
+            .expect_add_block()
+            .with(eq(TEST_BILL_ID), eq(chain.blocks()[0].clone()))
+            .times(1)
+            .returning(move |_, _| Ok(()));
+        bill_store
+            .expect_save_keys()
+            .with(eq(TEST_BILL_ID), always())
+            .times(1)
+            .returning(move |_, _| Ok(()));
+
+        let sync = BillChainSync::new(Arc::new(bill_chain_store), Arc::new(bill_store));
+        let event = Event::new(
+            EventType::Bill,
+            "node_id",
+            BillChainEventPayload {
+                bill_id: TEST_BILL_ID.to_string(),
+                event_type: BillEventType::BillBlock,
+                blocks: chain.blocks().clone(),
+                keys: Some(keys.clone()),
+                sum: Some(0),
+                action_type: None,
+                reason: None,
+            },
+        );
+        // the event was exported, e.g. to a file, and is imported again
+        let envelope: EventEnvelope = event.try_into().expect("Envelope from event");
+        let exported = serde_json::to_string(&envelope).expect("Exported envelope");
+        let imported: EventEnvelope = serde_json::from_str(&exported).expect("Imported envelope");
+
+        let bill_id = sync
+            .ingest_bill_event(imported)
+            .await
+            .expect("Event should be ingested");
+        assert_eq!(bill_id, TEST_BILL_ID);
+    }
+
+    #[tokio::test]
+    async fn test_ingest_bill_event_does_not_add_known_blocks() {
+        let payer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let mut payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        payee.node_id = OTHER_TEST_PUB_KEY_SECP.to_owned();
+        let drawer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, Some(&drawer), None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+        let chain_clone = chain.clone();
+        let keys = get_bill_keys();
+
+        let (_, _, mut bill_chain_store, mut bill_store) = create_mocks();
+
+        // the chain is already known
+        bill_chain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID))
+            .times(1)
+            .returning(move |_| Ok(chain_clone.clone()));
+        bill_chain_store.expect_add_block().never();
+        bill_store.expect_save_keys().never();
+
+        let sync = BillChainSync::new(Arc::new(bill_chain_store), Arc::new(bill_store));
+        let event = Event::new(
+            EventType::Bill,
+            "node_id",
+            BillChainEventPayload {
+                bill_id: TEST_BILL_ID.to_string(),
+                event_type: BillEventType::BillBlock,
+                blocks: chain.blocks().clone(),
+                keys: Some(keys.clone()),
+                sum: Some(0),
+                action_type: None,
+                reason: None,
+            },
+        );
+
+        let bill_id = sync
+            .ingest_bill_event(event.try_into().expect("Envelope from event"))
+            .await
+            .expect("Event should be ingested");
+        assert_eq!(bill_id, TEST_BILL_ID);
+    }
+
     #[tokio::test]
     async fn test_fails_to_create_new_chain_for_new_chain_event_if_block_validation_fails() {
         let payer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
@@ -541,6 +852,7 @@ mod tests {
                 signatory: None,
                 signing_timestamp: chain.get_latest_block().timestamp + 1000,
                 signing_address: empty_address(),
+                reason: None,
             },
             &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
             None,
@@ -586,6 +898,7 @@ mod tests {
                 keys: Some(keys.clone()),
                 sum: Some(0),
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -640,6 +953,7 @@ mod tests {
                 keys: Some(keys.clone()),
                 sum: Some(0),
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -719,6 +1033,7 @@ mod tests {
                 keys: None,
                 sum: Some(0),
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -728,6 +1043,188 @@ mod tests {
             .expect("Event should be handled");
     }
 
+    fn get_endorse_block(chain: &BillBlockchain, endorsee_node_id: &str) -> BillBlock {
+        let mut endorsee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        endorsee.node_id = endorsee_node_id.to_owned();
+        BillBlock::create_block_for_endorse(
+            TEST_BILL_ID.to_string(),
+            chain.get_latest_block(),
+            &BillEndorseBlockData {
+                endorsee: endorsee.into(),
+                // endorsed by payee
+                endorser: IdentityPublicData::new(get_baseline_identity().identity)
+                    .unwrap()
+                    .into(),
+                signatory: None,
+                signing_timestamp: chain.get_latest_block().timestamp + 1000,
+                signing_address: empty_address(),
+            },
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            None,
+            &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
+            chain.get_latest_block().timestamp + 1000,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_syncs_only_missing_blocks_for_existing_chain_event() {
+        let payer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let chain = get_genesis_chain(Some(bill.clone()));
+        let mut remote_chain = chain.clone();
+        let block = get_endorse_block(&remote_chain, OTHER_TEST_PUB_KEY_SECP);
+        assert!(remote_chain.try_add_block(block.clone()));
+
+        let (notification_store, push_service, mut bill_chain_store, mut bill_store) =
+            create_mocks();
+
+        let chain_clone = chain.clone();
+        bill_store
+            .expect_invalidate_bill_in_cache()
+            .returning(|_| Ok(()));
+        bill_store.expect_is_paid().returning(|_| Ok(false));
+        bill_store.expect_get_keys().returning(|_| {
+            Ok(BillKeys {
+                private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+                public_key: TEST_PUB_KEY_SECP.to_owned(),
+            })
+        });
+        bill_chain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID))
+            .times(1)
+            .returning(move |_| Ok(chain_clone.clone()));
+
+        // only the block after the common ancestor is added
+        bill_chain_store
+            .expect_add_block()
+            .with(eq(TEST_BILL_ID), eq(block.clone()))
+            .times(1)
+            .returning(move |_, _| Ok(()));
+
+        let handler = BillChainEventHandler::new(
+            Arc::new(notification_store),
+            Arc::new(push_service),
+            Arc::new(bill_chain_store),
+            Arc::new(bill_store),
+        );
+        let event = Event::new(
+            EventType::Bill,
+            "node_id",
+            BillChainEventPayload {
+                bill_id: TEST_BILL_ID.to_string(),
+                event_type: BillEventType::BillBlock,
+                blocks: remote_chain.blocks().clone(),
+                keys: None,
+                sum: Some(0),
+                action_type: None,
+                reason: None,
+            },
+        );
+
+        handler
+            .handle_event(event.try_into().expect("Envelope from event"), "node_id")
+            .await
+            .expect("Event should be handled");
+    }
+
+    #[tokio::test]
+    async fn test_fails_to_sync_blocks_for_forked_chain() {
+        let payer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &payer, &payee, None, None);
+        let genesis_chain = get_genesis_chain(Some(bill.clone()));
+        let mut chain = genesis_chain.clone();
+        assert!(chain.try_add_block(get_endorse_block(&chain, OTHER_TEST_PUB_KEY_SECP)));
+        // the remote chain endorsed to someone else at the same height
+        let mut remote_chain = genesis_chain.clone();
+        assert!(remote_chain.try_add_block(get_endorse_block(
+            &remote_chain,
+            &BcrKeys::new().get_public_key()
+        )));
+
+        let (notification_store, push_service, mut bill_chain_store, mut bill_store) =
+            create_mocks();
+
+        let chain_clone = chain.clone();
+        bill_store.expect_is_paid().returning(|_| Ok(false));
+        bill_store.expect_get_keys().returning(|_| {
+            Ok(BillKeys {
+                private_key: TEST_PRIVATE_KEY_SECP.to_owned(),
+                public_key: TEST_PUB_KEY_SECP.to_owned(),
+            })
+        });
+        bill_chain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID))
+            .times(1)
+            .returning(move |_| Ok(chain_clone.clone()));
+
+        // no block is added for a forked chain
+        bill_chain_store.expect_add_block().never();
+
+        let handler = BillChainEventHandler::new(
+            Arc::new(notification_store),
+            Arc::new(push_service),
+            Arc::new(bill_chain_store),
+            Arc::new(bill_store),
+        );
+        let event = Event::new(
+            EventType::Bill,
+            "node_id",
+            BillChainEventPayload {
+                bill_id: TEST_BILL_ID.to_string(),
+                event_type: BillEventType::BillBlock,
+                blocks: remote_chain.blocks().clone(),
+                keys: None,
+                sum: Some(0),
+                action_type: None,
+                reason: None,
+            },
+        );
+
+        handler
+            .handle_event(event.try_into().expect("Envelope from event"), "node_id")
+            .await
+            .expect("Event should be handled");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_chain_event_exceeding_max_blocks() {
+        let (notification_store, push_service, mut bill_chain_store, mut bill_store) =
+            create_mocks();
+        bill_chain_store.expect_get_chain().never();
+        bill_chain_store.expect_add_block().never();
+        bill_store.expect_save_keys().never();
+
+        let handler = BillChainEventHandler::new(
+            Arc::new(notification_store),
+            Arc::new(push_service),
+            Arc::new(bill_chain_store),
+            Arc::new(bill_store),
+        )
+        .with_max_chain_blocks(2);
+        // the blocks are not even valid blocks - the event is rejected before deserializing them
+        let event = EventEnvelope {
+            event_type: EventType::Bill,
+            version: "1.0".to_string(),
+            node_id: "node_id".to_string(),
+            data: serde_json::json!({
+                "bill_id": TEST_BILL_ID,
+                "event_type": "BillBlock",
+                "blocks": vec![serde_json::json!({}); 3],
+            }),
+        };
+        assert!(check_chain_length(&event, 2).is_err());
+
+        handler
+            .handle_event(event, "node_id")
+            .await
+            .expect("Event should be handled");
+    }
+
     #[tokio::test]
     async fn test_fails_to_add_block_for_invalid_bill_action() {
         let payer = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
@@ -744,6 +1241,7 @@ mod tests {
                 signatory: None,
                 signing_timestamp: chain.get_latest_block().timestamp + 1000,
                 signing_address: empty_address(),
+                reason: None,
             },
             &BcrKeys::from_private_key(TEST_PRIVATE_KEY_SECP).unwrap(),
             None,
@@ -789,6 +1287,7 @@ mod tests {
                 keys: None,
                 sum: Some(0),
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -864,6 +1363,7 @@ mod tests {
                 keys: None,
                 sum: Some(0),
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -927,6 +1427,7 @@ mod tests {
                 keys: None,
                 sum: Some(0),
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -966,6 +1467,7 @@ mod tests {
                 keys: None,
                 sum: None,
                 action_type: None,
+                reason: None,
             },
         );
 
@@ -1016,6 +1518,7 @@ mod tests {
                 keys: None,
                 sum: Some(0),
                 action_type: Some(ActionType::CheckBill),
+                reason: None,
             },
         );
 
@@ -1024,6 +1527,114 @@ mod tests {
             .await
             .expect("Event should be handled");
     }
+    fn get_auto_accept_handler(
+        chain: BillBlockchain,
+        auto_accept_store: MockBillAutoAcceptStore,
+    ) -> BillChainEventHandler {
+        let (notification_store, push_service, mut bill_chain_store, mut bill_store) =
+            create_mocks();
+        // the chain is already known, so only the auto-accept rules are evaluated
+        bill_chain_store
+            .expect_get_chain()
+            .with(eq(TEST_BILL_ID))
+            .returning(move |_| Ok(chain.clone()));
+        bill_chain_store.expect_add_block().never();
+        bill_store
+            .expect_get_keys()
+            .returning(|_| Ok(get_bill_keys()));
+        BillChainEventHandler::new(
+            Arc::new(notification_store),
+            Arc::new(push_service),
+            Arc::new(bill_chain_store),
+            Arc::new(bill_store),
+        )
+        .with_auto_accept(Arc::new(auto_accept_store))
+    }
+
+    fn get_acceptance_requested_event(chain: &BillBlockchain) -> EventEnvelope {
+        Event::new(
+            EventType::Bill,
+            "node_id",
+            BillChainEventPayload {
+                bill_id: TEST_BILL_ID.to_string(),
+                event_type: BillEventType::BillAcceptanceRequested,
+                blocks: chain.blocks().clone(),
+                keys: Some(get_bill_keys()),
+                sum: Some(500),
+                action_type: None,
+                reason: None,
+            },
+        )
+        .try_into()
+        .expect("Envelope from event")
+    }
+
+    #[tokio::test]
+    async fn test_queues_auto_accept_for_matching_rule() {
+        let drawee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let mut payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        payee.node_id = OTHER_TEST_PUB_KEY_SECP.to_owned();
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &drawee, &payee, Some(&drawee), None);
+        let chain = get_genesis_chain(Some(bill));
+
+        let mut auto_accept_store = MockBillAutoAcceptStore::new();
+        auto_accept_store
+            .expect_get_rules()
+            .with(eq(drawee.node_id.clone()))
+            .returning(|node_id| {
+                Ok(vec![BillAutoAcceptRule {
+                    id: "rule".to_owned(),
+                    node_id: node_id.to_owned(),
+                    // the bill was drawn by the drawee itself
+                    drawer_node_id: node_id.to_owned(),
+                    max_sum: 500,
+                }])
+            });
+        let drawee_node_id = drawee.node_id.clone();
+        auto_accept_store
+            .expect_queue_accept()
+            .withf(move |queued| {
+                queued.bill_id == TEST_BILL_ID
+                    && queued.node_id == drawee_node_id
+                    && queued.rule_id == "rule"
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let handler = get_auto_accept_handler(chain.clone(), auto_accept_store);
+        handler
+            .handle_event(get_acceptance_requested_event(&chain), &drawee.node_id)
+            .await
+            .expect("Event should be handled");
+    }
+
+    #[tokio::test]
+    async fn test_does_not_queue_auto_accept_for_sum_over_limit() {
+        let drawee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        let mut payee = IdentityPublicData::new(get_baseline_identity().identity).unwrap();
+        payee.node_id = OTHER_TEST_PUB_KEY_SECP.to_owned();
+        let bill = get_test_bitcredit_bill(TEST_BILL_ID, &drawee, &payee, Some(&drawee), None);
+        let chain = get_genesis_chain(Some(bill));
+
+        let mut auto_accept_store = MockBillAutoAcceptStore::new();
+        auto_accept_store.expect_get_rules().returning(|node_id| {
+            Ok(vec![BillAutoAcceptRule {
+                id: "rule".to_owned(),
+                node_id: node_id.to_owned(),
+                drawer_node_id: node_id.to_owned(),
+                // the bill has a sum of 500
+                max_sum: 499,
+            }])
+        });
+        auto_accept_store.expect_queue_accept().never();
+
+        let handler = get_auto_accept_handler(chain.clone(), auto_accept_store);
+        handler
+            .handle_event(get_acceptance_requested_event(&chain), &drawee.node_id)
+            .await
+            .expect("Event should be handled");
+    }
+
     pub fn get_test_bitcredit_bill(
         id: &str,
         payer: &IdentityPublicData,
@@ -1081,6 +1692,8 @@ mod tests {
             country_of_payment: "AT".to_string(),
             language: "DE".to_string(),
             files: vec![],
+            payment_deadline_seconds: PAYMENT_DEADLINE_SECONDS,
+            fiat_amount: None,
         }
     }
 
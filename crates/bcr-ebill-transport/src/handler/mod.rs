@@ -9,7 +9,7 @@ use super::{EventEnvelope, EventType};
 
 mod bill_chain_event_handler;
 
-pub use bill_chain_event_handler::BillChainEventHandler;
+pub use bill_chain_event_handler::{BillChainEventHandler, BillChainSync};
 
 #[cfg(test)]
 impl ServiceTraitBounds for MockNotificationHandlerApi {}
@@ -156,14 +156,16 @@ mod tests {
 mod test_utils {
     use async_trait::async_trait;
     use bcr_ebill_core::{
-        bill::{BillKeys, BitcreditBillResult},
+        bill::{
+            BillAutoAcceptRule, BillHold, BillKeys, BillView, BitcreditBillResult, QueuedAutoAccept,
+        },
         blockchain::bill::{BillBlock, BillBlockchain, BillOpCode},
         notification::{ActionType, Notification, NotificationType},
     };
     use bcr_ebill_persistence::{
         NotificationStoreApi, Result,
-        bill::{BillChainStoreApi, BillStoreApi},
-        notification::NotificationFilter,
+        bill::{BillAutoAcceptStoreApi, BillChainStoreApi, BillStoreApi},
+        notification::{NotificationFilter, NotificationPage},
     };
     use mockall::mock;
     use std::collections::HashMap;
@@ -176,7 +178,7 @@ mod test_utils {
         #[async_trait]
         impl NotificationStoreApi for NotificationStore {
             async fn add(&self, notification: Notification) -> Result<Notification>;
-            async fn list(&self, filter: NotificationFilter) -> Result<Vec<Notification>>;
+            async fn list(&self, filter: NotificationFilter) -> Result<NotificationPage>;
             async fn get_latest_by_references(
                 &self,
                 reference: &[String],
@@ -190,6 +192,11 @@ mod test_utils {
             #[allow(unused)]
             async fn list_by_type(&self, notification_type: bcr_ebill_core::notification::NotificationType) -> Result<Vec<Notification>>;
             async fn mark_as_done(&self, notification_id: &str) -> Result<()>;
+            async fn mark_all_as_done(
+                &self,
+                node_id: &str,
+                notification_type: Option<NotificationType>,
+            ) -> Result<u64>;
             #[allow(unused)]
             async fn delete(&self, notification_id: &str) -> Result<()>;
             async fn set_bill_notification_sent(
@@ -204,6 +211,17 @@ mod test_utils {
                 block_height: i32,
                 action_type: ActionType,
             ) -> Result<bool>;
+            async fn set_bill_participant_notified(
+                &self,
+                bill_id: &str,
+                block_height: i32,
+                node_id: &str,
+            ) -> Result<()>;
+            async fn get_notified_bill_participants(
+                &self,
+                bill_id: &str,
+                block_height: i32,
+            ) -> Result<Vec<String>>;
         }
     }
 
@@ -224,6 +242,9 @@ mod test_utils {
             async fn get_latest_block(&self, id: &str) -> Result<BillBlock>;
             async fn add_block(&self, id: &str, block: &BillBlock) -> Result<()>;
             async fn get_chain(&self, id: &str) -> Result<BillBlockchain>;
+            async fn get_blocks(&self, id: &str) -> Result<Vec<BillBlock>>;
+            async fn remove_blocks_from(&self, id: &str, block_id: u64) -> Result<()>;
+            async fn replace_blocks_from(&self, id: &str, blocks: &[BillBlock]) -> Result<()>;
         }
     }
 
@@ -239,10 +260,52 @@ mod test_utils {
             async fn clear_bill_cache(&self) -> Result<()>;
             async fn exists(&self, id: &str) -> bool;
             async fn get_ids(&self) -> Result<Vec<String>>;
+            async fn get_bill_ids_by_participant(&self, node_id: &str) -> Result<Vec<String>>;
+            async fn get_unindexed_bill_ids(&self) -> Result<Vec<String>>;
             async fn save_keys(&self, id: &str, keys: &BillKeys) -> Result<()>;
+            async fn remove_keys(&self, id: &str) -> Result<()>;
             async fn get_keys(&self, id: &str) -> Result<BillKeys>;
+            async fn get_key_ids(&self) -> Result<Vec<String>>;
             async fn is_paid(&self, id: &str) -> Result<bool>;
             async fn set_to_paid(&self, id: &str, payment_address: &str) -> Result<()>;
+            async fn get_paid_sum(&self, id: &str) -> Result<u64>;
+            async fn set_paid_sum(&self, id: &str, payment_address: &str, paid_sum: u64) -> Result<()>;
+            async fn get_hold(&self, id: &str) -> Result<Option<BillHold>>;
+            async fn set_hold(&self, id: &str, hold: &BillHold) -> Result<()>;
+            async fn clear_hold(&self, id: &str) -> Result<()>;
+            async fn get_idempotent_block_id(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+            ) -> Result<Option<u64>>;
+            async fn reserve_idempotency_key(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+            ) -> Result<bool>;
+            async fn release_idempotency_key(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+            ) -> Result<()>;
+            async fn save_idempotency_key(
+                &self,
+                id: &str,
+                op_code: &BillOpCode,
+                idempotency_key: &str,
+                block_id: u64,
+            ) -> Result<()>;
+            async fn get_archived_bill_ids(&self, node_id: &str) -> Result<Vec<String>>;
+            async fn archive(&self, id: &str, node_id: &str) -> Result<()>;
+            async fn unarchive(&self, id: &str, node_id: &str) -> Result<()>;
+            async fn get_note(&self, id: &str, node_id: &str) -> Result<Option<String>>;
+            async fn set_note(&self, id: &str, node_id: &str, note: &str) -> Result<()>;
+            async fn remove_note(&self, id: &str, node_id: &str) -> Result<()>;
+            async fn get_view(&self, id: &str) -> Result<Option<BillView>>;
+            async fn save_view(&self, view: &BillView) -> Result<()>;
             async fn get_bill_ids_waiting_for_payment(&self) -> Result<Vec<String>>;
             async fn get_bill_ids_waiting_for_sell_payment(&self) -> Result<Vec<String>>;
             async fn get_bill_ids_waiting_for_recourse_payment(&self) -> Result<Vec<String>>;
@@ -253,4 +316,18 @@ mod test_utils {
             ) -> Result<Vec<String>>;
         }
     }
+
+    mock! {
+        pub BillAutoAcceptStore {}
+
+        #[async_trait]
+        impl BillAutoAcceptStoreApi for BillAutoAcceptStore {
+            async fn get_rules(&self, node_id: &str) -> Result<Vec<BillAutoAcceptRule>>;
+            async fn save_rule(&self, rule: &BillAutoAcceptRule) -> Result<()>;
+            async fn delete_rule(&self, id: &str) -> Result<()>;
+            async fn queue_accept(&self, queued: &QueuedAutoAccept) -> Result<()>;
+            async fn get_queued_accepts(&self) -> Result<Vec<QueuedAutoAccept>>;
+            async fn remove_queued_accept(&self, bill_id: &str, node_id: &str) -> Result<()>;
+        }
+    }
 }
@@ -5,6 +5,7 @@ pub mod event;
 pub mod handler;
 pub mod notification_service;
 pub mod push_notification;
+pub mod template;
 pub mod transport;
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -44,6 +45,6 @@ impl From<serde_json::Error> for Error {
 pub use event::bill_events::BillChainEventPayload;
 pub use event::chain_event::BillChainEvent;
 pub use event::{Event, EventEnvelope, EventType};
-pub use notification_service::NotificationServiceApi;
+pub use notification_service::{NotificationServiceApi, QueuedMessageInfo};
 pub use push_notification::{PushApi, PushService};
-pub use transport::NotificationJsonTransportApi;
+pub use transport::{NostrProfile, NotificationJsonTransportApi, RelayConnection, RelayStatus};
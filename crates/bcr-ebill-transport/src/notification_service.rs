@@ -1,12 +1,13 @@
-use crate::{Result, event::chain_event::BillChainEvent};
+use crate::{RelayConnection, RelayStatus, Result, event::chain_event::BillChainEvent};
 use async_trait::async_trait;
 use bcr_ebill_core::ServiceTraitBounds;
 use bcr_ebill_core::{
     bill::BitcreditBill,
+    blockchain::bill::BillBlock,
     contact::IdentityPublicData,
-    notification::{ActionType, Notification},
+    notification::{ActionType, Notification, NotificationType},
 };
-use bcr_ebill_persistence::notification::NotificationFilter;
+use bcr_ebill_persistence::notification::{NotificationFilter, NotificationPage};
 #[cfg(test)]
 use mockall::automock;
 use std::collections::HashMap;
@@ -37,6 +38,10 @@ pub trait NotificationServiceApi: ServiceTraitBounds {
     /// Receiver: Payer, Action: PayBill
     async fn send_request_to_pay_event(&self, event: &BillChainEvent) -> Result<()>;
 
+    /// Sent when: A bill is requested to be accepted and paid in one step, Sent by: Holder
+    /// Receiver: Payer, Action: PayBill
+    async fn send_request_to_accept_and_pay_event(&self, event: &BillChainEvent) -> Result<()>;
+
     /// Sent when: A bill is paid by: Payer (Bitcoin API)
     /// Receiver: Payee, Action: CheckBill
     async fn send_bill_is_paid_event(&self, event: &BillChainEvent) -> Result<()>;
@@ -45,6 +50,27 @@ pub trait NotificationServiceApi: ServiceTraitBounds {
     /// Receiver: NewHolder, Action: CheckBill
     async fn send_bill_is_endorsed_event(&self, event: &BillChainEvent) -> Result<()>;
 
+    /// Sent when: Files are attached to a bill by: Holder
+    /// Receiver: All participants, Action: None (the new block only)
+    async fn send_bill_files_attached_event(&self, event: &BillChainEvent) -> Result<()>;
+
+    /// Sent when: An offer to sell is cancelled by: Seller
+    /// Receiver: All participants, Action: None (the new block only)
+    async fn send_offer_to_sell_cancelled_event(&self, event: &BillChainEvent) -> Result<()>;
+
+    /// Sent when: A request to accept is cancelled by: Requester
+    /// Receiver: All participants, Action: None (the new block only)
+    async fn send_request_to_accept_cancelled_event(&self, event: &BillChainEvent) -> Result<()>;
+
+    /// Sent when: A bill is acknowledged by: Holder or Drawee
+    /// Receiver: All participants, Action: None (the new block only)
+    async fn send_bill_acknowledged_event(&self, event: &BillChainEvent) -> Result<()>;
+
+    /// Sent when: The drawee of a bill is corrected by: Drawer
+    /// Receiver: New Payer, Action: AcceptBill
+    /// Receiver: All other participants, Action: None (the new block only)
+    async fn send_drawee_corrected_event(&self, event: &BillChainEvent) -> Result<()>;
+
     /// Sent when: A bill is offered to be sold, Sent by: Holder
     /// Receiver: Buyer, Action: CheckBill (with buy page)
     async fn send_offer_to_sell_event(
@@ -75,11 +101,13 @@ pub trait NotificationServiceApi: ServiceTraitBounds {
     /// Arguments:
     /// * bill_id: The id of the bill affected
     /// * rejected_action: The action that was rejected
+    /// * reason: The optional reason the participant gave for the rejection
     /// * recipients: The list of recipients that should receive the notification
     async fn send_request_to_action_rejected_event(
         &self,
         event: &BillChainEvent,
         rejected_action: ActionType,
+        reason: Option<String>,
     ) -> Result<()>;
 
     /// In case a participant did not perform an action (e.g. request to accept, request
@@ -113,6 +141,27 @@ pub trait NotificationServiceApi: ServiceTraitBounds {
         recoursee: &IdentityPublicData,
     ) -> Result<()>;
 
+    /// Sent when: The maturity date of a bill is within the reminder lead time, Sent by: Holder
+    /// Receiver: Holder, Action: RequestPayment
+    async fn send_maturity_approaching_event(
+        &self,
+        sender_node_id: &str,
+        bill_id: &str,
+        sum: Option<u64>,
+        recipient: &IdentityPublicData,
+    ) -> Result<()>;
+
+    /// Sent when: An open request is about to time out, Sent by: Expected actor
+    /// Receiver: Expected actor, Action: The action, which is about to time out
+    async fn send_request_to_action_timeout_approaching_event(
+        &self,
+        sender_node_id: &str,
+        bill_id: &str,
+        sum: Option<u64>,
+        action: ActionType,
+        recipient: &IdentityPublicData,
+    ) -> Result<()>;
+
     /// Sent when: A bill is requested to be minted, Sent by: Holder
     /// Receiver: Mint, Action: CheckBill (with generate quote page)
     async fn send_request_to_mint_event(
@@ -122,22 +171,50 @@ pub trait NotificationServiceApi: ServiceTraitBounds {
     ) -> Result<()>;
 
     /// Sent when: A new quote is created, Sent by: Mint
-    /// Receiver: Holder, Action: Check quote page
-    async fn send_new_quote_event(&self, quote: &BitcreditBill) -> Result<()>;
+    /// Receiver: Requester of the mint, Action: CheckQuote
+    async fn send_new_quote_event(
+        &self,
+        event: &BillChainEvent,
+        requester: &IdentityPublicData,
+    ) -> Result<()>;
 
-    /// Sent when: A quote is approved by: Previous Holder
+    /// Sent when: A quote is approved by: Requester of the mint
     /// Receiver: Mint (new holder), Action: CheckBill
-    async fn send_quote_is_approved_event(&self, quote: &BitcreditBill) -> Result<()>;
+    async fn send_quote_is_approved_event(
+        &self,
+        event: &BillChainEvent,
+        mint: &IdentityPublicData,
+    ) -> Result<()>;
+
+    /// Sent when: A quote is rejected by: Requester of the mint
+    /// Receiver: Mint (new holder), Action: CheckBill
+    async fn send_quote_is_rejected_event(
+        &self,
+        event: &BillChainEvent,
+        mint: &IdentityPublicData,
+    ) -> Result<()>;
 
-    /// Returns filtered client notifications
+    /// Returns a page of filtered client notifications and the cursor for the next page
     async fn get_client_notifications(
         &self,
         filter: NotificationFilter,
-    ) -> Result<Vec<Notification>>;
+    ) -> Result<NotificationPage>;
 
     /// Marks the notification with given id as done
     async fn mark_notification_as_done(&self, notification_id: &str) -> Result<()>;
 
+    /// Marks all active notifications of the given identity as done, optionally only the ones
+    /// of the given type. Returns the number of notifications marked as done.
+    async fn mark_all_as_done(
+        &self,
+        node_id: &str,
+        notification_type: Option<NotificationType>,
+    ) -> Result<u64>;
+
+    /// Marks all active notifications of the given identity, which reference the given bill, as
+    /// done. Returns the number of notifications marked as done.
+    async fn mark_bill_notifications_done(&self, bill_id: &str, node_id: &str) -> Result<u64>;
+
     /// Returns the active bill notification for the given bill id
     async fn get_active_bill_notification(&self, bill_id: &str) -> Option<Notification>;
 
@@ -162,6 +239,55 @@ pub trait NotificationServiceApi: ServiceTraitBounds {
         action: ActionType,
     ) -> Result<()>;
 
+    /// Returns the node ids of the participants the event for the given block of the given bill
+    /// was sent to
+    async fn get_notified_bill_participants(
+        &self,
+        bill_id: &str,
+        block_height: i32,
+    ) -> Result<Vec<String>>;
+
     /// Retry sending a queued message to the given node id
     async fn send_retry_messages(&self) -> Result<()>;
+
+    /// Resends the bill event for the latest block of the given chain event to the given node id
+    /// only, e.g. if the recipient was offline for longer than the relays retain events. Other
+    /// participants and the webhooks are not notified again
+    async fn resend_bill_event(&self, event: &BillChainEvent, node_id: &str) -> Result<()>;
+
+    /// Returns the delivery statistics of all relays, combined over all local identities
+    async fn get_relay_status(&self) -> Result<Vec<RelayStatus>>;
+
+    /// Returns the connection state of all relays - a relay counts as connected, if at least one
+    /// local identity is connected to it
+    async fn get_relay_connections(&self) -> Result<Vec<RelayConnection>>;
+
+    /// Fetches the blocks of the given bill from the bill events sent to the local identities,
+    /// which the relays still retain, e.g. to replace corrupted local blocks
+    async fn fetch_bill_blocks(&self, bill_id: &str) -> Result<Vec<BillBlock>>;
+
+    /// Returns up to `limit` messages, which are waiting in the retry queue, ordered by their
+    /// next retry
+    async fn list_pending_retry_messages(&self, limit: u64) -> Result<Vec<QueuedMessageInfo>>;
+
+    /// Returns the number of messages, which exhausted all their retries and won't be sent
+    async fn count_dead_letter_messages(&self) -> Result<u64>;
+}
+
+/// The state of a message in the retry queue - the payload is left out on purpose, since it can
+/// contain private bill data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedMessageInfo {
+    /// The id of the queued message
+    pub id: String,
+    /// The node id of the local identity, which sends the message
+    pub sender_id: String,
+    /// The node id of the recipient
+    pub node_id: String,
+    /// The number of failed retries so far
+    pub retry_count: i32,
+    /// The timestamp, after which the message is retried next
+    pub next_retry_at: u64,
+    /// The number of seconds since the message was queued
+    pub age_seconds: u64,
 }
@@ -0,0 +1,409 @@
+use bcr_ebill_core::notification::{ActionType, BillEventType, NotificationPreview};
+
+/// The language we fall back to, if there are no templates for a requested language
+pub const DEFAULT_TEMPLATE_LANGUAGE: &str = "en";
+
+/// A localized notification template for a bill event. The placeholders `{bill_id}`, `{sum}` and
+/// `{counterparty}` are replaced with the event data when rendering. The same template is used
+/// for the in-app notification, the email and the push notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationTemplate {
+    pub language: &'static str,
+    pub subject: &'static str,
+    pub body: &'static str,
+}
+
+/// The event data used to fill a notification template
+#[derive(Debug, Clone)]
+pub struct NotificationTemplateData {
+    pub bill_id: String,
+    /// The sum including its currency, e.g. `500 sat`
+    pub sum: String,
+    /// The name of the participant, who caused the event
+    pub counterparty: String,
+}
+
+/// A notification template filled with event data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedNotification {
+    pub language: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Returns the template for the given event type and language. A language like `de-AT` is
+/// matched by its primary language `de`. Falls back to English, if there are no templates for
+/// the language.
+pub fn get_template(event_type: &BillEventType, language: &str) -> NotificationTemplate {
+    match primary_language(language).as_str() {
+        "de" => german_template(event_type),
+        _ => english_template(event_type),
+    }
+}
+
+/// Renders the subject and body of the notification for the given event type, language and data
+pub fn render(
+    event_type: &BillEventType,
+    language: &str,
+    data: &NotificationTemplateData,
+) -> RenderedNotification {
+    let template = get_template(event_type, language);
+    RenderedNotification {
+        language: template.language.to_owned(),
+        subject: fill_template(template.subject, data),
+        body: fill_template(template.body, data),
+    }
+}
+
+/// Renders the texts of the notification counterparties receive for the given action, as it
+/// would be delivered in-app, via email and via push notification
+pub fn render_notification_preview(
+    action_type: &ActionType,
+    language: &str,
+    data: &NotificationTemplateData,
+) -> NotificationPreview {
+    let rendered = render(&event_type_for_action(action_type), language, data);
+    NotificationPreview {
+        language: rendered.language,
+        in_app_text: rendered.body.clone(),
+        email_subject: rendered.subject.clone(),
+        email_body: rendered.body,
+        push_text: rendered.subject,
+    }
+}
+
+// the event counterparties receive, when the given action is requested from them
+fn event_type_for_action(action_type: &ActionType) -> BillEventType {
+    match action_type {
+        ActionType::AcceptBill => BillEventType::BillAcceptanceRequested,
+        ActionType::PayBill => BillEventType::BillPaymentRequested,
+        ActionType::BuyBill => BillEventType::BillSellOffered,
+        ActionType::RecourseBill => BillEventType::BillPaymentRecourse,
+        ActionType::CheckBill => BillEventType::BillBlock,
+        ActionType::CheckQuote => BillEventType::BillNewQuote,
+        ActionType::RequestPayment => BillEventType::BillMaturityApproaching,
+    }
+}
+
+fn primary_language(language: &str) -> String {
+    language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn fill_template(text: &str, data: &NotificationTemplateData) -> String {
+    text.replace("{bill_id}", &data.bill_id)
+        .replace("{sum}", &data.sum)
+        .replace("{counterparty}", &data.counterparty)
+}
+
+fn english_template(event_type: &BillEventType) -> NotificationTemplate {
+    let (subject, body) = match event_type {
+        BillEventType::BillSigned => (
+            "New bill {bill_id}",
+            "{counterparty} issued the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillAccepted => (
+            "Bill {bill_id} accepted",
+            "{counterparty} accepted the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillAcceptanceRequested => (
+            "Acceptance of bill {bill_id} requested",
+            "{counterparty} requested you to accept the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillAcceptanceRejected => (
+            "Acceptance of bill {bill_id} rejected",
+            "{counterparty} rejected to accept the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillAcceptanceTimeout => (
+            "Acceptance of bill {bill_id} timed out",
+            "The request to accept the bill {bill_id} over {sum} timed out.",
+        ),
+        BillEventType::BillAcceptanceRecourse => (
+            "Recourse for non-acceptance of bill {bill_id}",
+            "{counterparty} requested a recourse for the non-acceptance of the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillPaymentRequested => (
+            "Payment of bill {bill_id} requested",
+            "{counterparty} requested you to pay the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillAcceptanceAndPaymentRequested => (
+            "Acceptance and payment of bill {bill_id} requested",
+            "{counterparty} requested you to accept and pay the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillPaymentRejected => (
+            "Payment of bill {bill_id} rejected",
+            "{counterparty} rejected to pay the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillPaymentRecourse => (
+            "Recourse for non-payment of bill {bill_id}",
+            "{counterparty} requested a recourse for the non-payment of the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillRecourseRejected => (
+            "Recourse for bill {bill_id} rejected",
+            "{counterparty} rejected to pay the recourse for the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillRecourseTimeout => (
+            "Recourse for bill {bill_id} timed out",
+            "The request to pay the recourse for the bill {bill_id} over {sum} timed out.",
+        ),
+        BillEventType::BillPaymentTimeout => (
+            "Payment of bill {bill_id} timed out",
+            "The request to pay the bill {bill_id} over {sum} timed out.",
+        ),
+        BillEventType::BillSellOffered => (
+            "Bill {bill_id} offered to buy",
+            "{counterparty} offered you to buy the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillBuyingRejected => (
+            "Offer to buy bill {bill_id} rejected",
+            "{counterparty} rejected to buy the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillPaid => (
+            "Bill {bill_id} paid",
+            "The bill {bill_id} over {sum} was paid by {counterparty}.",
+        ),
+        BillEventType::BillRecoursePaid => (
+            "Recourse for bill {bill_id} paid",
+            "The recourse for the bill {bill_id} over {sum} was paid by {counterparty}.",
+        ),
+        BillEventType::BillEndorsed => (
+            "Bill {bill_id} endorsed",
+            "{counterparty} endorsed the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillSold => (
+            "Bill {bill_id} sold",
+            "{counterparty} sold the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillMintingRequested => (
+            "Minting of bill {bill_id} requested",
+            "{counterparty} requested to mint the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillNewQuote => (
+            "New quote for bill {bill_id}",
+            "{counterparty} sent a new quote for the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillQuoteApproved => (
+            "Quote for bill {bill_id} approved",
+            "{counterparty} approved the quote for the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillQuoteRejected => (
+            "Quote for bill {bill_id} rejected",
+            "{counterparty} rejected the quote for the bill {bill_id} over {sum}.",
+        ),
+        BillEventType::BillMaturityApproaching => (
+            "Bill {bill_id} matures soon",
+            "The bill {bill_id} over {sum} matures soon, please prepare to request payment.",
+        ),
+        BillEventType::BillActionTimeoutApproaching => (
+            "Deadline for bill {bill_id} approaching",
+            "The deadline of the pending request on the bill {bill_id} over {sum} expires soon.",
+        ),
+        BillEventType::BillBlock => (
+            "Bill {bill_id} updated",
+            "{counterparty} updated the bill {bill_id} over {sum}, please check it.",
+        ),
+    };
+    NotificationTemplate {
+        language: DEFAULT_TEMPLATE_LANGUAGE,
+        subject,
+        body,
+    }
+}
+
+fn german_template(event_type: &BillEventType) -> NotificationTemplate {
+    let (subject, body) = match event_type {
+        BillEventType::BillSigned => (
+            "Neuer Wechsel {bill_id}",
+            "{counterparty} hat den Wechsel {bill_id} über {sum} ausgestellt.",
+        ),
+        BillEventType::BillAccepted => (
+            "Wechsel {bill_id} akzeptiert",
+            "{counterparty} hat den Wechsel {bill_id} über {sum} akzeptiert.",
+        ),
+        BillEventType::BillAcceptanceRequested => (
+            "Akzeptanz des Wechsels {bill_id} angefordert",
+            "{counterparty} hat Sie aufgefordert, den Wechsel {bill_id} über {sum} zu akzeptieren.",
+        ),
+        BillEventType::BillAcceptanceRejected => (
+            "Akzeptanz des Wechsels {bill_id} abgelehnt",
+            "{counterparty} hat die Akzeptanz des Wechsels {bill_id} über {sum} abgelehnt.",
+        ),
+        BillEventType::BillAcceptanceTimeout => (
+            "Frist zur Akzeptanz des Wechsels {bill_id} abgelaufen",
+            "Die Frist zur Akzeptanz des Wechsels {bill_id} über {sum} ist abgelaufen.",
+        ),
+        BillEventType::BillAcceptanceRecourse => (
+            "Regress mangels Akzeptanz des Wechsels {bill_id}",
+            "{counterparty} hat Regress mangels Akzeptanz des Wechsels {bill_id} über {sum} angefordert.",
+        ),
+        BillEventType::BillPaymentRequested => (
+            "Zahlung des Wechsels {bill_id} angefordert",
+            "{counterparty} hat Sie aufgefordert, den Wechsel {bill_id} über {sum} zu bezahlen.",
+        ),
+        BillEventType::BillAcceptanceAndPaymentRequested => (
+            "Akzeptanz und Zahlung des Wechsels {bill_id} angefordert",
+            "{counterparty} hat Sie aufgefordert, den Wechsel {bill_id} über {sum} zu akzeptieren und zu bezahlen.",
+        ),
+        BillEventType::BillPaymentRejected => (
+            "Zahlung des Wechsels {bill_id} abgelehnt",
+            "{counterparty} hat die Zahlung des Wechsels {bill_id} über {sum} abgelehnt.",
+        ),
+        BillEventType::BillPaymentRecourse => (
+            "Regress mangels Zahlung des Wechsels {bill_id}",
+            "{counterparty} hat Regress mangels Zahlung des Wechsels {bill_id} über {sum} angefordert.",
+        ),
+        BillEventType::BillRecourseRejected => (
+            "Regress für den Wechsel {bill_id} abgelehnt",
+            "{counterparty} hat die Zahlung des Regresses für den Wechsel {bill_id} über {sum} abgelehnt.",
+        ),
+        BillEventType::BillRecourseTimeout => (
+            "Frist für den Regress des Wechsels {bill_id} abgelaufen",
+            "Die Frist zur Zahlung des Regresses für den Wechsel {bill_id} über {sum} ist abgelaufen.",
+        ),
+        BillEventType::BillPaymentTimeout => (
+            "Frist zur Zahlung des Wechsels {bill_id} abgelaufen",
+            "Die Frist zur Zahlung des Wechsels {bill_id} über {sum} ist abgelaufen.",
+        ),
+        BillEventType::BillSellOffered => (
+            "Wechsel {bill_id} zum Kauf angeboten",
+            "{counterparty} hat Ihnen den Wechsel {bill_id} über {sum} zum Kauf angeboten.",
+        ),
+        BillEventType::BillBuyingRejected => (
+            "Kauf des Wechsels {bill_id} abgelehnt",
+            "{counterparty} hat den Kauf des Wechsels {bill_id} über {sum} abgelehnt.",
+        ),
+        BillEventType::BillPaid => (
+            "Wechsel {bill_id} bezahlt",
+            "Der Wechsel {bill_id} über {sum} wurde von {counterparty} bezahlt.",
+        ),
+        BillEventType::BillRecoursePaid => (
+            "Regress für den Wechsel {bill_id} bezahlt",
+            "Der Regress für den Wechsel {bill_id} über {sum} wurde von {counterparty} bezahlt.",
+        ),
+        BillEventType::BillEndorsed => (
+            "Wechsel {bill_id} indossiert",
+            "{counterparty} hat den Wechsel {bill_id} über {sum} indossiert.",
+        ),
+        BillEventType::BillSold => (
+            "Wechsel {bill_id} verkauft",
+            "{counterparty} hat den Wechsel {bill_id} über {sum} verkauft.",
+        ),
+        BillEventType::BillMintingRequested => (
+            "Minting des Wechsels {bill_id} angefordert",
+            "{counterparty} hat das Minting des Wechsels {bill_id} über {sum} angefordert.",
+        ),
+        BillEventType::BillNewQuote => (
+            "Neues Angebot für den Wechsel {bill_id}",
+            "{counterparty} hat ein neues Angebot für den Wechsel {bill_id} über {sum} gesendet.",
+        ),
+        BillEventType::BillQuoteApproved => (
+            "Angebot für den Wechsel {bill_id} angenommen",
+            "{counterparty} hat das Angebot für den Wechsel {bill_id} über {sum} angenommen.",
+        ),
+        BillEventType::BillQuoteRejected => (
+            "Angebot für den Wechsel {bill_id} abgelehnt",
+            "{counterparty} hat das Angebot für den Wechsel {bill_id} über {sum} abgelehnt.",
+        ),
+        BillEventType::BillMaturityApproaching => (
+            "Wechsel {bill_id} wird bald fällig",
+            "Der Wechsel {bill_id} über {sum} wird bald fällig, bitte bereiten Sie die Zahlungsaufforderung vor.",
+        ),
+        BillEventType::BillActionTimeoutApproaching => (
+            "Frist für den Wechsel {bill_id} läuft bald ab",
+            "Die Frist der offenen Anfrage zum Wechsel {bill_id} über {sum} läuft bald ab.",
+        ),
+        BillEventType::BillBlock => (
+            "Wechsel {bill_id} aktualisiert",
+            "{counterparty} hat den Wechsel {bill_id} über {sum} aktualisiert, bitte prüfen Sie ihn.",
+        ),
+    };
+    NotificationTemplate {
+        language: "de",
+        subject,
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data() -> NotificationTemplateData {
+        NotificationTemplateData {
+            bill_id: "some_bill".to_string(),
+            sum: "500 sat".to_string(),
+            counterparty: "Jane Doe".to_string(),
+        }
+    }
+
+    #[test]
+    fn render_fills_placeholders_for_all_event_types_and_languages() {
+        for language in ["en", "de"] {
+            for event_type in BillEventType::all() {
+                let rendered = render(&event_type, language, &test_data());
+                assert!(rendered.subject.contains("some_bill"));
+                assert!(rendered.body.contains("some_bill"));
+                assert!(rendered.body.contains("500 sat"));
+                assert!(!rendered.subject.contains('{'));
+                assert!(!rendered.body.contains('{'));
+            }
+        }
+        // every event type is translated
+        for event_type in BillEventType::all() {
+            assert_ne!(
+                render(&event_type, "de", &test_data()).body,
+                render(&event_type, "en", &test_data()).body
+            );
+        }
+    }
+
+    #[test]
+    fn render_uses_language() {
+        assert_eq!(
+            render(&BillEventType::BillEndorsed, "en-UK", &test_data()),
+            RenderedNotification {
+                language: "en".to_string(),
+                subject: "Bill some_bill endorsed".to_string(),
+                body: "Jane Doe endorsed the bill some_bill over 500 sat.".to_string(),
+            }
+        );
+        assert_eq!(
+            render(&BillEventType::BillEndorsed, "de-AT", &test_data()),
+            RenderedNotification {
+                language: "de".to_string(),
+                subject: "Wechsel some_bill indossiert".to_string(),
+                body: "Jane Doe hat den Wechsel some_bill über 500 sat indossiert.".to_string(),
+            }
+        );
+        assert_eq!(get_template(&BillEventType::BillPaid, "DE").language, "de");
+    }
+
+    #[test]
+    fn render_falls_back_to_english() {
+        assert_eq!(
+            render(&BillEventType::BillPaid, "fr", &test_data()),
+            render(&BillEventType::BillPaid, "en", &test_data()),
+        );
+        assert_eq!(
+            render(&BillEventType::BillPaid, "", &test_data()),
+            render(&BillEventType::BillPaid, "en", &test_data()),
+        );
+    }
+
+    #[test]
+    fn render_notification_preview_uses_the_event_template_of_the_action() {
+        let preview = render_notification_preview(&ActionType::PayBill, "en", &test_data());
+        let rendered = render(&BillEventType::BillPaymentRequested, "en", &test_data());
+        assert_eq!(preview.language, "en");
+        assert_eq!(
+            preview.in_app_text,
+            "Jane Doe requested you to pay the bill some_bill over 500 sat."
+        );
+        assert_eq!(preview.email_body, rendered.body);
+        assert_eq!(preview.email_subject, rendered.subject);
+        assert_eq!(preview.push_text, "Payment of bill some_bill requested");
+    }
+}
@@ -17,6 +17,59 @@ impl ServiceTraitBounds for MockNotificationJsonTransportApi {}
 pub trait NotificationJsonTransportApi: ServiceTraitBounds {
     fn get_sender_key(&self) -> String;
     async fn send(&self, recipient: &IdentityPublicData, event: EventEnvelope) -> Result<()>;
+    /// Returns the delivery statistics of all relays the transport publishes to
+    fn get_relay_status(&self) -> Vec<RelayStatus>;
+    /// Returns all relays the transport publishes to and whether it's currently connected to them
+    async fn get_relay_connections(&self) -> Vec<RelayConnection>;
+    /// Fetches the latest profile the given node published, or None, if it can't be found
+    async fn fetch_profile(&self, node_id: &str) -> Result<Option<NostrProfile>>;
+    /// Fetches the events sent to the identity of the transport, which the relays still retain
+    async fn fetch_received_events(&self) -> Result<Vec<EventEnvelope>>;
+}
+
+/// The latest profile a node published via its metadata and relay list
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NostrProfile {
+    /// The name from the metadata, if there is one
+    pub name: Option<String>,
+    /// The relays the node reads events from
+    pub relays: Vec<String>,
+}
+
+/// The connection state of a single relay
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayConnection {
+    /// The url of the relay
+    pub relay: String,
+    /// Whether the relay is currently connected
+    pub connected: bool,
+}
+
+/// Delivery statistics of a single relay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelayStatus {
+    /// The url of the relay
+    pub relay: String,
+    /// The number of events the relay accepted
+    pub success_count: u64,
+    /// The number of events the relay rejected, or that could not be sent to it
+    pub failure_count: u64,
+    /// The error of the last failed send, if there was one
+    pub last_error: Option<String>,
+}
+
+impl RelayStatus {
+    pub fn new(relay: &str) -> Self {
+        Self {
+            relay: relay.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// A relay is considered healthy, if it accepted at least one event, or didn't fail yet
+    pub fn is_healthy(&self) -> bool {
+        self.success_count > 0 || self.failure_count == 0
+    }
 }
 
 /// A dummy transport that logs all events that are sent as json.
@@ -37,4 +90,18 @@ impl NotificationJsonTransportApi for LoggingNotificationJsonTransport {
         );
         Ok(())
     }
+    fn get_relay_status(&self) -> Vec<RelayStatus> {
+        vec![]
+    }
+    async fn get_relay_connections(&self) -> Vec<RelayConnection> {
+        vec![]
+    }
+    async fn fetch_profile(&self, node_id: &str) -> Result<Option<NostrProfile>> {
+        info!("Fetching profile of peer: {node_id}");
+        Ok(None)
+    }
+    async fn fetch_received_events(&self) -> Result<Vec<EventEnvelope>> {
+        info!("Fetching received events");
+        Ok(vec![])
+    }
 }
@@ -2,9 +2,11 @@ use super::Result;
 use bcr_ebill_api::{
     data::{
         bill::{
-            BillAction, BillIssueData, BillsFilterRole, LightBitcreditBillResult, RecourseReason,
+            BillAction, BillIssueData, BillsFilterRole, BillsFilterStatus,
+            LightBitcreditBillResult, RecourseReason,
         },
         contact::IdentityPublicData,
+        notification::ActionType,
     },
     external,
     service::{Error, bill_service::error::Error as BillServiceError},
@@ -22,13 +24,19 @@ use crate::{
     data::{
         BinaryFileResponse, FromWeb, IntoWeb, UploadFile,
         bill::{
-            AcceptBitcreditBillPayload, BillId, BillNumbersToWordsForSum, BillsResponse,
-            BillsSearchFilterPayload, BitcreditBillPayload, EndorseBitcreditBillPayload,
-            EndorsementsResponse, LightBillsResponse, MintBitcreditBillPayload,
-            OfferToSellBitcreditBillPayload, PastEndorseesResponse, PastPaymentsResponse,
+            AcceptBitcreditBillPayload, AcknowledgeBillPayload, AddAutoAcceptRulePayload,
+            AttachFilesToBillPayload, BillActionTypeWeb, BillAutoAcceptRulesResponse,
+            BillHistoryResponse, BillId, BillNumbersToWordsForSum, BillWaitingStateResponse,
+            BillsGroupWeb, BillsGroupedResponse, BillsResponse, BillsSearchFilterPayload,
+            BitcreditBillPayload, CorrectDraweePayload, EndorseBitcreditBillPayload,
+            EndorsementsResponse, LightBillsResponse, MarkPaidExternallyPayload,
+            MintBitcreditBillPayload, OfferToSellBitcreditBillPayload, PastEndorseesResponse,
+            PastHolderResponse, PastPaymentsResponse, PreviewNotificationPayload,
             RejectActionBillPayload, RequestRecourseForAcceptancePayload,
             RequestRecourseForPaymentPayload, RequestToAcceptBitcreditBillPayload,
             RequestToMintBitcreditBillPayload, RequestToPayBitcreditBillPayload,
+            ResendBillEventPayload, SetBillHoldPayload, SetBillNotePayload,
+            ValidateBillActionPayload,
         },
     },
 };
@@ -57,6 +65,32 @@ impl Bill {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "BillHistoryResponse")]
+    pub async fn history(&self, id: &str) -> Result<JsValue> {
+        let result = get_ctx()
+            .bill_service
+            .get_bill_history(id, &get_current_identity_node_id().await?)
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&BillHistoryResponse {
+            history: result.into_iter().map(|e| e.into_web()).collect(),
+        })?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "ChainVerificationReportWeb")]
+    pub async fn verify_chain(&self, id: &str) -> Result<JsValue> {
+        let result = get_ctx().bill_service.verify_bill_chain(id).await?;
+        let res = serde_wasm_bindgen::to_value(&result.into_web())?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "ChainVerificationReportWeb")]
+    pub async fn repair_chain(&self, id: &str) -> Result<JsValue> {
+        let result = get_ctx().bill_service.repair_bill_chain(id).await?;
+        let res = serde_wasm_bindgen::to_value(&result.into_web())?;
+        Ok(res)
+    }
+
     #[wasm_bindgen(unchecked_return_type = "PastPaymentsResponse")]
     pub async fn past_payments(&self, id: &str) -> Result<JsValue> {
         let (caller_public_data, caller_keys) = get_signer_public_data_and_keys().await?;
@@ -87,6 +121,208 @@ impl Bill {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "PastHolderResponse")]
+    pub async fn is_past_holder(&self, id: &str, node_id: &str) -> Result<JsValue> {
+        let is_past_holder = get_ctx()
+            .bill_service
+            .is_past_holder(id, node_id, &get_current_identity_node_id().await?)
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&PastHolderResponse { is_past_holder })?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "NotificationPreviewWeb")]
+    pub async fn preview_notification(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "PreviewNotificationPayload")] payload: JsValue,
+    ) -> Result<JsValue> {
+        let preview_payload: PreviewNotificationPayload = serde_wasm_bindgen::from_value(payload)?;
+        let preview = get_ctx()
+            .bill_service
+            .preview_notification(
+                &preview_payload.bill_id,
+                &ActionType::from_web(preview_payload.action_type),
+                &preview_payload.language,
+            )
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&preview.into_web())?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen]
+    pub async fn validate_action(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "ValidateBillActionPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let validate_payload: ValidateBillActionPayload = serde_wasm_bindgen::from_value(payload)?;
+        let bill_action = bill_action_to_validate(&validate_payload).await?;
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, _) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .validate_bill_action(
+                &validate_payload.bill_id,
+                bill_action,
+                &signer_public_data,
+                timestamp,
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn resend_event(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "ResendBillEventPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let resend_payload: ResendBillEventPayload = serde_wasm_bindgen::from_value(payload)?;
+        get_ctx()
+            .bill_service
+            .resend_bill_event(
+                &resend_payload.bill_id,
+                resend_payload.block_height,
+                &resend_payload.node_id,
+                &get_current_identity_node_id().await?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn set_hold(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "SetBillHoldPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let set_bill_hold_payload: SetBillHoldPayload = serde_wasm_bindgen::from_value(payload)?;
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        get_ctx()
+            .bill_service
+            .set_bill_hold(
+                &set_bill_hold_payload.bill_id,
+                &set_bill_hold_payload.reason,
+                &get_current_identity_node_id().await?,
+                timestamp,
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn clear_hold(&self, id: &str) -> Result<()> {
+        get_ctx()
+            .bill_service
+            .clear_bill_hold(id, &get_current_identity_node_id().await?)
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn archive(&self, id: &str) -> Result<()> {
+        get_ctx()
+            .bill_service
+            .archive_bill(id, &get_current_identity_node_id().await?)
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn unarchive(&self, id: &str) -> Result<()> {
+        get_ctx()
+            .bill_service
+            .unarchive_bill(id, &get_current_identity_node_id().await?)
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn set_note(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "SetBillNotePayload")] payload: JsValue,
+    ) -> Result<()> {
+        let set_bill_note_payload: SetBillNotePayload = serde_wasm_bindgen::from_value(payload)?;
+        get_ctx()
+            .bill_service
+            .set_bill_note(
+                &set_bill_note_payload.bill_id,
+                &get_current_identity_node_id().await?,
+                set_bill_note_payload.note,
+            )
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "BillAutoAcceptRulesResponse")]
+    pub async fn auto_accept_rules(&self) -> Result<JsValue> {
+        let rules = get_ctx()
+            .bill_service
+            .get_auto_accept_rules(&get_current_identity_node_id().await?)
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&BillAutoAcceptRulesResponse {
+            rules: rules.into_iter().map(|r| r.into_web()).collect(),
+        })?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "BillAutoAcceptRuleWeb")]
+    pub async fn add_auto_accept_rule(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "AddAutoAcceptRulePayload")] payload: JsValue,
+    ) -> Result<JsValue> {
+        let add_auto_accept_rule_payload: AddAutoAcceptRulePayload =
+            serde_wasm_bindgen::from_value(payload)?;
+        let max_sum = currency::parse_sum(&add_auto_accept_rule_payload.max_sum)?;
+        let rule = get_ctx()
+            .bill_service
+            .add_auto_accept_rule(
+                &get_current_identity_node_id().await?,
+                &add_auto_accept_rule_payload.drawer_node_id,
+                max_sum,
+            )
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&rule.into_web())?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen]
+    pub async fn remove_auto_accept_rule(&self, id: &str) -> Result<()> {
+        get_ctx()
+            .bill_service
+            .remove_auto_accept_rule(&get_current_identity_node_id().await?, id)
+            .await?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "BinaryFileResponse")]
+    pub async fn export_bundle(&self, id: &str) -> Result<JsValue> {
+        let (_, caller_keys) = get_signer_public_data_and_keys().await?;
+        let bundle = get_ctx()
+            .bill_service
+            .export_bill_bundle(id, &caller_keys)
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&BinaryFileResponse {
+            data: bundle,
+            name: format!("bill_{id}.bundle"),
+            content_type: "application/octet-stream".to_owned(),
+        })?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "BillId")]
+    pub async fn import_bundle(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "UploadFile")] payload: JsValue,
+    ) -> Result<JsValue> {
+        let upload_file: UploadFile = serde_wasm_bindgen::from_value(payload)?;
+        let (_, caller_keys) = get_signer_public_data_and_keys().await?;
+        let bill_id = get_ctx()
+            .bill_service
+            .import_bill_bundle(&upload_file.data, &caller_keys)
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&BillId { id: bill_id })?;
+        Ok(res)
+    }
+
     #[wasm_bindgen(unchecked_return_type = "BillCombinedBitcoinKeyWeb")]
     pub async fn bitcoin_key(&self, id: &str) -> Result<JsValue> {
         let (caller_public_data, caller_keys) = get_signer_public_data_and_keys().await?;
@@ -165,6 +401,10 @@ impl Bill {
                 from,
                 to,
                 &BillsFilterRole::from_web(filter.role),
+                &filter
+                    .status
+                    .map(BillsFilterStatus::from_web)
+                    .unwrap_or(BillsFilterStatus::All),
                 &get_current_identity_node_id().await?,
             )
             .await?;
@@ -176,10 +416,13 @@ impl Bill {
     }
 
     #[wasm_bindgen(unchecked_return_type = "LightBillsResponse")]
-    pub async fn list_light(&self) -> Result<JsValue> {
+    pub async fn list_light(&self, include_archived: Option<bool>) -> Result<JsValue> {
         let bills: Vec<LightBitcreditBillResult> = get_ctx()
             .bill_service
-            .get_bills(&get_current_identity_node_id().await?)
+            .get_bills(
+                &get_current_identity_node_id().await?,
+                include_archived.unwrap_or(false),
+            )
             .await?
             .into_iter()
             .map(|b| b.into())
@@ -190,11 +433,80 @@ impl Bill {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "BillsGroupedResponse")]
+    pub async fn list_grouped(&self) -> Result<JsValue> {
+        let node_ids: Vec<String> = get_ctx()
+            .company_service
+            .get_controlled_node_ids()
+            .await?
+            .into_iter()
+            .map(|i| i.node_id)
+            .collect();
+        let mut grouped = get_ctx().bill_service.get_bills_grouped(&node_ids).await?;
+        let res = serde_wasm_bindgen::to_value(&BillsGroupedResponse {
+            groups: node_ids
+                .into_iter()
+                .map(|node_id| BillsGroupWeb {
+                    bills: grouped
+                        .remove(&node_id)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|b| b.into_web())
+                        .collect(),
+                    node_id,
+                })
+                .collect(),
+        })?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "LightBillsResponse")]
+    pub async fn list_by_participant(&self, node_id: &str) -> Result<JsValue> {
+        let bills = get_ctx()
+            .bill_service
+            .get_bills_by_participant(node_id, &get_current_identity_node_id().await?)
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&LightBillsResponse {
+            bills: bills.into_iter().map(|b| b.into_web()).collect(),
+        })?;
+        Ok(res)
+    }
+
+    /// Lists the bills, which mature between the given dates, e.g. 2025-01-01 - bills with a
+    /// malformed maturity date are excluded
+    #[wasm_bindgen(unchecked_return_type = "LightBillsResponse")]
+    pub async fn list_by_maturity(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+    ) -> Result<JsValue> {
+        let from = from
+            .map(|from| util::date::date_string_to_timestamp(&from, None))
+            .transpose()?;
+        let to = to
+            .map(|to| {
+                util::date::date_string_to_timestamp(&to, None)
+                    .map(util::date::end_of_day_as_timestamp)
+            })
+            .transpose()?;
+        let bills = get_ctx()
+            .bill_service
+            .get_bills_by_maturity(&get_current_identity_node_id().await?, from, to)
+            .await?;
+        let res = serde_wasm_bindgen::to_value(&LightBillsResponse {
+            bills: bills.into_iter().map(|b| b.into_web()).collect(),
+        })?;
+        Ok(res)
+    }
+
     #[wasm_bindgen(unchecked_return_type = "BillsResponse")]
-    pub async fn list(&self) -> Result<JsValue> {
+    pub async fn list(&self, include_archived: Option<bool>) -> Result<JsValue> {
         let bills = get_ctx()
             .bill_service
-            .get_bills(&get_current_identity_node_id().await?)
+            .get_bills(
+                &get_current_identity_node_id().await?,
+                include_archived.unwrap_or(false),
+            )
             .await?;
         let res = serde_wasm_bindgen::to_value(&BillsResponse {
             bills: bills.into_iter().map(|b| b.into_web()).collect(),
@@ -215,9 +527,12 @@ impl Bill {
                 current_timestamp,
             )
             .await?;
-        let sum = bill.data.sum;
-        let parsed_sum = currency::parse_sum(&sum)?;
-        let sum_as_words = util::numbers_to_words::encode(&parsed_sum);
+        let parsed_sum = currency::parse_sum(&bill.data.sum)?;
+        let sum_as_words = util::numbers_to_words::sum_to_words(
+            parsed_sum,
+            &bill.data.currency,
+            &bill.data.language,
+        );
         let res = serde_wasm_bindgen::to_value(&BillNumbersToWordsForSum {
             sum: parsed_sum,
             sum_as_words,
@@ -243,6 +558,24 @@ impl Bill {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "BillWaitingStateResponse")]
+    pub async fn waiting_state(&self, id: &str) -> Result<JsValue> {
+        let current_timestamp = util::date::now().timestamp() as u64;
+        let current_waiting_state = get_ctx()
+            .bill_service
+            .get_bill_waiting_state(
+                id,
+                &get_current_identity_node_id().await?,
+                current_timestamp,
+            )
+            .await?;
+
+        let res = serde_wasm_bindgen::to_value(&BillWaitingStateResponse {
+            current_waiting_state: current_waiting_state.map(|cws| cws.into_web()),
+        })?;
+        Ok(res)
+    }
+
     #[wasm_bindgen]
     pub async fn check_payment(&self) -> Result<()> {
         if let Err(e) = get_ctx().bill_service.check_bills_payment().await {
@@ -287,6 +620,7 @@ impl Bill {
                 drawer_public_data: drawer_public_data.clone(),
                 drawer_keys: drawer_keys.clone(),
                 timestamp,
+                payment_deadline_seconds: bill_payload.payment_deadline_seconds,
             })
             .await?;
 
@@ -314,7 +648,10 @@ impl Bill {
             }
         };
 
-        let sum = currency::parse_sum(&offer_to_sell_payload.sum)?;
+        let (sum, sum_currency) = currency::parse_sum_in_currency(
+            &offer_to_sell_payload.sum,
+            &offer_to_sell_payload.currency,
+        )?;
         let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
         let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
 
@@ -322,14 +659,11 @@ impl Bill {
             .bill_service
             .execute_bill_action(
                 &offer_to_sell_payload.bill_id,
-                BillAction::OfferToSell(
-                    public_data_buyer.clone(),
-                    sum,
-                    offer_to_sell_payload.currency.clone(),
-                ),
+                BillAction::OfferToSell(public_data_buyer.clone(), sum, sum_currency),
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
 
@@ -364,6 +698,7 @@ impl Bill {
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
         Ok(())
@@ -388,6 +723,60 @@ impl Bill {
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn request_to_accept_and_pay(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "RequestToPayBitcreditBillPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let request_to_pay_bill_payload: RequestToPayBitcreditBillPayload =
+            serde_wasm_bindgen::from_value(payload)?;
+
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .execute_bill_action(
+                &request_to_pay_bill_payload.bill_id,
+                BillAction::RequestAcceptanceAndPayment(
+                    request_to_pay_bill_payload.currency.clone(),
+                ),
+                &signer_public_data,
+                &signer_keys,
+                timestamp,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn attach_files(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "AttachFilesToBillPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let attach_files_payload: AttachFilesToBillPayload =
+            serde_wasm_bindgen::from_value(payload)?;
+
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .attach_files(
+                &attach_files_payload.bill_id,
+                &attach_files_payload.file_upload_ids,
+                &signer_public_data,
+                &signer_keys,
+                timestamp,
             )
             .await?;
 
@@ -414,6 +803,7 @@ impl Bill {
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
 
@@ -439,6 +829,7 @@ impl Bill {
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
 
@@ -470,7 +861,8 @@ impl Bill {
         info!("mint bill called with payload {mint_bill_payload:?} - not implemented");
 
         let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
-        let sum = currency::parse_sum(&mint_bill_payload.sum)?;
+        let (sum, sum_currency) =
+            currency::parse_sum_in_currency(&mint_bill_payload.sum, &mint_bill_payload.currency)?;
 
         let public_mint_node = match get_ctx()
             .contact_service
@@ -488,10 +880,11 @@ impl Bill {
             .bill_service
             .execute_bill_action(
                 &mint_bill_payload.bill_id,
-                BillAction::Mint(public_mint_node, sum, mint_bill_payload.currency.clone()),
+                BillAction::Mint(public_mint_node, sum, sum_currency),
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
 
@@ -512,10 +905,11 @@ impl Bill {
             .bill_service
             .execute_bill_action(
                 &reject_payload.bill_id,
-                BillAction::RejectAcceptance,
+                BillAction::RejectAcceptance(reject_payload.reason.clone()),
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
 
@@ -536,10 +930,11 @@ impl Bill {
             .bill_service
             .execute_bill_action(
                 &reject_payload.bill_id,
-                BillAction::RejectPayment,
+                BillAction::RejectPayment(reject_payload.reason.clone()),
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
 
@@ -560,10 +955,151 @@ impl Bill {
             .bill_service
             .execute_bill_action(
                 &reject_payload.bill_id,
-                BillAction::RejectBuying,
+                BillAction::RejectBuying(reject_payload.reason.clone()),
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn cancel_offer_to_sell(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "RejectActionBillPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let cancel_payload: RejectActionBillPayload = serde_wasm_bindgen::from_value(payload)?;
+
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .execute_bill_action(
+                &cancel_payload.bill_id,
+                BillAction::CancelOfferToSell,
+                &signer_public_data,
+                &signer_keys,
+                timestamp,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub async fn cancel_request_to_accept(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "RejectActionBillPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let cancel_payload: RejectActionBillPayload = serde_wasm_bindgen::from_value(payload)?;
+
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .execute_bill_action(
+                &cancel_payload.bill_id,
+                BillAction::CancelRequestToAccept,
+                &signer_public_data,
+                &signer_keys,
+                timestamp,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Marks the bill as paid outside of the chain - this is an attestation by the holder, not a
+    /// cryptographic proof of payment
+    #[wasm_bindgen]
+    pub async fn mark_paid_externally(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "MarkPaidExternallyPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let mark_paid_payload: MarkPaidExternallyPayload = serde_wasm_bindgen::from_value(payload)?;
+
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .execute_bill_action(
+                &mark_paid_payload.bill_id,
+                BillAction::MarkPaidExternally(mark_paid_payload.reference),
+                &signer_public_data,
+                &signer_keys,
+                timestamp,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Attests, that the holder, or the drawee has seen the bill - this is not an acceptance and
+    /// carries no payment obligation
+    #[wasm_bindgen]
+    pub async fn acknowledge(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "AcknowledgeBillPayload")] payload: JsValue,
+    ) -> Result<()> {
+        let acknowledge_payload: AcknowledgeBillPayload = serde_wasm_bindgen::from_value(payload)?;
+
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .execute_bill_action(
+                &acknowledge_payload.bill_id,
+                BillAction::Acknowledge,
+                &signer_public_data,
+                &signer_keys,
+                timestamp,
+                None,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replaces the drawee of a bill, which didn't progress yet - e.g. if the drawer made a mistake
+    #[wasm_bindgen]
+    pub async fn correct_drawee(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "CorrectDraweePayload")] payload: JsValue,
+    ) -> Result<()> {
+        let correct_drawee_payload: CorrectDraweePayload = serde_wasm_bindgen::from_value(payload)?;
+        let public_data_drawee = match get_ctx()
+            .contact_service
+            .get_identity_by_node_id(&correct_drawee_payload.drawee)
+            .await
+        {
+            Ok(Some(drawee)) => drawee,
+            Ok(None) | Err(_) => {
+                return Err(BillServiceError::DraweeNotInContacts.into());
+            }
+        };
+
+        let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+        let (signer_public_data, signer_keys) = get_signer_public_data_and_keys().await?;
+
+        get_ctx()
+            .bill_service
+            .execute_bill_action(
+                &correct_drawee_payload.bill_id,
+                BillAction::CorrectDrawee(public_data_drawee),
+                &signer_public_data,
+                &signer_keys,
+                timestamp,
+                None,
             )
             .await?;
 
@@ -588,6 +1124,7 @@ impl Bill {
                 &signer_public_data,
                 &signer_keys,
                 timestamp,
+                None,
             )
             .await?;
 
@@ -601,9 +1138,12 @@ impl Bill {
     ) -> Result<()> {
         let request_recourse_payload: RequestRecourseForPaymentPayload =
             serde_wasm_bindgen::from_value(payload)?;
-        let sum = currency::parse_sum(&request_recourse_payload.sum)?;
+        let (sum, sum_currency) = currency::parse_sum_in_currency(
+            &request_recourse_payload.sum,
+            &request_recourse_payload.currency,
+        )?;
         request_recourse(
-            RecourseReason::Pay(sum, request_recourse_payload.currency.clone()),
+            RecourseReason::Pay(sum, sum_currency),
             &request_recourse_payload.bill_id,
             &request_recourse_payload.recoursee,
         )
@@ -660,6 +1200,7 @@ async fn request_recourse(
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
@@ -672,6 +1213,73 @@ impl Default for Bill {
     }
 }
 
+/// Creates the bill action to validate from the payload, resolving the counterparty from the
+/// contacts the same way the actions do
+async fn bill_action_to_validate(payload: &ValidateBillActionPayload) -> Result<BillAction> {
+    let counterparty = payload.counterparty.as_deref().unwrap_or_default();
+    let sum = payload.sum.as_deref().unwrap_or_default();
+    let currency = payload.currency.as_deref().unwrap_or_default();
+    let bill_action = match payload.action_type {
+        BillActionTypeWeb::RequestAcceptance => BillAction::RequestAcceptance,
+        BillActionTypeWeb::Accept => BillAction::Accept,
+        BillActionTypeWeb::RequestToPay => BillAction::RequestToPay(currency.to_owned()),
+        BillActionTypeWeb::RequestAcceptanceAndPayment => {
+            BillAction::RequestAcceptanceAndPayment(currency.to_owned())
+        }
+        BillActionTypeWeb::OfferToSell => {
+            let buyer = get_contact(counterparty, BillServiceError::BuyerNotInContacts).await?;
+            let (sum, sum_currency) = currency::parse_sum_in_currency(sum, currency)?;
+            BillAction::OfferToSell(buyer, sum, sum_currency)
+        }
+        BillActionTypeWeb::Endorse => BillAction::Endorse(
+            get_contact(counterparty, BillServiceError::EndorseeNotInContacts).await?,
+        ),
+        BillActionTypeWeb::RequestRecourseForPayment => {
+            let recoursee =
+                get_contact(counterparty, BillServiceError::RecourseeNotInContacts).await?;
+            let (sum, sum_currency) = currency::parse_sum_in_currency(sum, currency)?;
+            BillAction::RequestRecourse(recoursee, RecourseReason::Pay(sum, sum_currency))
+        }
+        BillActionTypeWeb::RequestRecourseForAcceptance => BillAction::RequestRecourse(
+            get_contact(counterparty, BillServiceError::RecourseeNotInContacts).await?,
+            RecourseReason::Accept,
+        ),
+        BillActionTypeWeb::Mint => {
+            let mint = get_contact(counterparty, BillServiceError::MintNotInContacts).await?;
+            let (sum, sum_currency) = currency::parse_sum_in_currency(sum, currency)?;
+            BillAction::Mint(mint, sum, sum_currency)
+        }
+        BillActionTypeWeb::RejectAcceptance => BillAction::RejectAcceptance(payload.reason.clone()),
+        BillActionTypeWeb::RejectPayment => BillAction::RejectPayment(payload.reason.clone()),
+        BillActionTypeWeb::RejectBuying => BillAction::RejectBuying(payload.reason.clone()),
+        BillActionTypeWeb::RejectPaymentForRecourse => BillAction::RejectPaymentForRecourse,
+        BillActionTypeWeb::CancelOfferToSell => BillAction::CancelOfferToSell,
+        BillActionTypeWeb::CancelRequestToAccept => BillAction::CancelRequestToAccept,
+        BillActionTypeWeb::MarkPaidExternally => {
+            BillAction::MarkPaidExternally(payload.reason.clone().unwrap_or_default())
+        }
+        BillActionTypeWeb::Acknowledge => BillAction::Acknowledge,
+        BillActionTypeWeb::CorrectDrawee => BillAction::CorrectDrawee(
+            get_contact(counterparty, BillServiceError::DraweeNotInContacts).await?,
+        ),
+    };
+    Ok(bill_action)
+}
+
+async fn get_contact(
+    node_id: &str,
+    not_in_contacts: BillServiceError,
+) -> Result<IdentityPublicData> {
+    match get_ctx()
+        .contact_service
+        .get_identity_by_node_id(node_id)
+        .await
+    {
+        Ok(Some(contact)) => Ok(contact),
+        Ok(None) | Err(_) => Err(not_in_contacts.into()),
+    }
+}
+
 async fn get_signer_public_data_and_keys() -> Result<(IdentityPublicData, BcrKeys)> {
     let current_identity = get_current_identity().await?;
     let local_node_id = current_identity.personal;
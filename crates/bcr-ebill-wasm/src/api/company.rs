@@ -17,7 +17,7 @@ use crate::{
         BinaryFileResponse, FromWeb, IntoWeb, UploadFile,
         company::{
             AddSignatoryPayload, CompaniesResponse, CreateCompanyPayload, EditCompanyPayload,
-            ListSignatoriesResponse, RemoveSignatoryPayload,
+            ListSignatoriesResponse, RemoveSignatoryPayload, SignatoryHistoryResponse,
         },
     },
 };
@@ -102,6 +102,15 @@ impl Company {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "SignatoryHistoryResponse")]
+    pub async fn signatory_history(&self, id: &str) -> Result<JsValue> {
+        let changes = get_ctx().company_service.get_signatory_history(id).await?;
+        let res = serde_wasm_bindgen::to_value(&SignatoryHistoryResponse {
+            changes: changes.into_iter().map(|c| c.into_web()).collect(),
+        })?;
+        Ok(res)
+    }
+
     #[wasm_bindgen(unchecked_return_type = "CompanyWeb")]
     pub async fn detail(&self, id: &str) -> Result<JsValue> {
         let company = get_ctx().company_service.get_company_by_id(id).await?;
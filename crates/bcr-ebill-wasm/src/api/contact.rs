@@ -1,5 +1,6 @@
 use crate::data::contact::{
     ContactTypeWeb, ContactWeb, ContactsResponse, EditContactPayload, NewContactPayload,
+    RefreshedContactWeb,
 };
 use crate::data::{BinaryFileResponse, FromWeb, IntoWeb, UploadFile};
 use crate::{Result, context::get_ctx};
@@ -90,6 +91,17 @@ impl Contact {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "RefreshedContactWeb")]
+    pub async fn refresh_metadata(&self, node_id: &str) -> Result<JsValue> {
+        let refreshed: RefreshedContactWeb = get_ctx()
+            .contact_service
+            .refresh_contact_metadata(node_id)
+            .await?
+            .into_web();
+        let res = serde_wasm_bindgen::to_value(&refreshed)?;
+        Ok(res)
+    }
+
     #[wasm_bindgen]
     pub async fn remove(&self, node_id: &str) -> Result<()> {
         get_ctx().contact_service.delete(node_id).await?;
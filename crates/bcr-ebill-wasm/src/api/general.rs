@@ -10,9 +10,10 @@ use crate::{
     api::identity::get_current_identity_node_id,
     context::get_ctx,
     data::{
-        BalanceResponse, BinaryFileResponse, CurrenciesResponse, CurrencyResponse, FromWeb,
-        GeneralSearchFilterPayload, IntoWeb, OverviewBalanceResponse, OverviewResponse,
-        StatusResponse,
+        BalanceResponse, BalanceWithFiatResponse, BinaryFileResponse, CurrenciesResponse,
+        CurrencyResponse, ExposureResponse, FiatRateResponse, FromWeb, GeneralSearchFilterPayload,
+        IntoWeb, OverviewBalanceResponse, OverviewBalanceWithFiatResponse, OverviewResponse,
+        OverviewWithFiatResponse, StatusResponse,
     },
 };
 
@@ -103,6 +104,73 @@ impl General {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "ExposureResponse")]
+    pub async fn exposure(&self) -> Result<JsValue> {
+        let result = get_ctx()
+            .bill_service
+            .get_total_exposure(&get_current_identity_node_id().await?)
+            .await?;
+
+        let res = serde_wasm_bindgen::to_value(&ExposureResponse {
+            balances: OverviewBalanceResponse {
+                payee: BalanceResponse {
+                    sum: result.balances.payee.sum,
+                },
+                payer: BalanceResponse {
+                    sum: result.balances.payer.sum,
+                },
+                contingent: BalanceResponse {
+                    sum: result.balances.contingent.sum,
+                },
+            },
+            net_position: result.net_position,
+            accepted_count: result.accepted_count,
+            awaiting_acceptance_count: result.awaiting_acceptance_count,
+            overdue_count: result.overdue_count,
+        })?;
+        Ok(res)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "OverviewWithFiatResponse")]
+    pub async fn overview_with_fiat(&self, currency: &str, fiat_code: &str) -> Result<JsValue> {
+        if !VALID_CURRENCIES.contains(&currency) {
+            return Err(Error::Validation(ValidationError::InvalidCurrency).into());
+        }
+        let result = get_ctx()
+            .bill_service
+            .get_bill_balances_with_fiat(
+                currency,
+                fiat_code,
+                &get_current_identity_node_id().await?,
+            )
+            .await?;
+
+        let res = serde_wasm_bindgen::to_value(&OverviewWithFiatResponse {
+            currency: currency.to_owned(),
+            fiat_code: fiat_code.to_owned(),
+            balances: OverviewBalanceWithFiatResponse {
+                payee: BalanceWithFiatResponse {
+                    sum: result.payee.sum,
+                    fiat_sum: result.payee.fiat_sum,
+                },
+                payer: BalanceWithFiatResponse {
+                    sum: result.payer.sum,
+                    fiat_sum: result.payer.fiat_sum,
+                },
+                contingent: BalanceWithFiatResponse {
+                    sum: result.contingent.sum,
+                    fiat_sum: result.contingent.fiat_sum,
+                },
+            },
+            fiat_rate: result.fiat_rate.map(|rate| FiatRateResponse {
+                fiat_code: rate.fiat_code,
+                rate: rate.rate,
+                timestamp: rate.timestamp,
+            }),
+        })?;
+        Ok(res)
+    }
+
     #[wasm_bindgen(unchecked_return_type = "GeneralSearchResponse")]
     pub async fn search(
         &self,
@@ -4,7 +4,8 @@ use crate::{
     data::{
         BinaryFileResponse, FromWeb, IntoWeb, UploadFile,
         identity::{
-            ChangeIdentityPayload, IdentityWeb, NewIdentityPayload, SeedPhrase, SwitchIdentity,
+            ChangeIdentityPayload, ControlledIdentitiesResponse, IdentityWeb, NewIdentityPayload,
+            SeedPhrase, SwitchIdentity,
         },
     },
 };
@@ -184,6 +185,15 @@ impl Identity {
         Ok(res)
     }
 
+    #[wasm_bindgen(unchecked_return_type = "ControlledIdentitiesResponse")]
+    pub async fn controlled(&self) -> Result<JsValue> {
+        let identities = get_ctx().company_service.get_controlled_node_ids().await?;
+        let res = serde_wasm_bindgen::to_value(&ControlledIdentitiesResponse {
+            identities: identities.into_iter().map(|i| i.into_web()).collect(),
+        })?;
+        Ok(res)
+    }
+
     #[wasm_bindgen]
     pub async fn switch(
         &self,
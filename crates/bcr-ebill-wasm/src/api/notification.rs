@@ -1,9 +1,16 @@
 use super::Result;
 use crate::{
+    api::identity::get_current_identity_node_id,
     context::get_ctx,
-    data::{IntoWeb, notification::NotificationWeb},
+    data::{
+        FromWeb, IntoWeb,
+        notification::{NotificationTypeWeb, NotificationsResponse, RelayStatusResponse},
+    },
+};
+use bcr_ebill_api::{
+    NotificationCursor, NotificationFilter, data::notification::NotificationType, service::Error,
+    util::ValidationError,
 };
-use bcr_ebill_api::NotificationFilter;
 use log::{error, info};
 use wasm_bindgen::prelude::*;
 
@@ -37,7 +44,7 @@ impl Notification {
         });
     }
 
-    #[wasm_bindgen(unchecked_return_type = "NotificationWeb[]")]
+    #[wasm_bindgen(unchecked_return_type = "NotificationsResponse")]
     pub async fn list(
         &self,
         active: Option<bool>,
@@ -45,8 +52,16 @@ impl Notification {
         notification_type: Option<String>,
         limit: Option<i64>,
         offset: Option<i64>,
+        only_unread: Option<bool>,
+        after: Option<String>,
     ) -> Result<JsValue> {
-        let notifications = get_ctx()
+        let after = match after {
+            Some(cursor) => Some(NotificationCursor::decode(&cursor).ok_or(Error::Validation(
+                ValidationError::InvalidNotificationCursor,
+            ))?),
+            None => None,
+        };
+        let page = get_ctx()
             .notification_service
             .get_client_notifications(NotificationFilter {
                 active,
@@ -54,10 +69,12 @@ impl Notification {
                 notification_type,
                 limit,
                 offset,
+                only_unread: only_unread.unwrap_or(false),
+                after,
             })
             .await?;
 
-        let web: Vec<NotificationWeb> = notifications.into_iter().map(|n| n.into_web()).collect();
+        let web: NotificationsResponse = page.into_web();
         let res = serde_wasm_bindgen::to_value(&web)?;
         Ok(res)
     }
@@ -71,6 +88,38 @@ impl Notification {
         Ok(())
     }
 
+    #[wasm_bindgen]
+    pub async fn mark_all_as_done(
+        &self,
+        notification_type: Option<NotificationTypeWeb>,
+    ) -> Result<u64> {
+        let node_id = get_current_identity_node_id().await?;
+        let marked = get_ctx()
+            .notification_service
+            .mark_all_as_done(&node_id, notification_type.map(NotificationType::from_web))
+            .await?;
+        Ok(marked)
+    }
+
+    #[wasm_bindgen]
+    pub async fn mark_bill_notifications_as_done(&self, bill_id: &str) -> Result<u64> {
+        let node_id = get_current_identity_node_id().await?;
+        let marked = get_ctx()
+            .notification_service
+            .mark_bill_notifications_done(bill_id, &node_id)
+            .await?;
+        Ok(marked)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "RelayStatusResponse")]
+    pub async fn relay_status(&self) -> Result<JsValue> {
+        let relays = get_ctx().notification_service.get_relay_status().await?;
+        let res = serde_wasm_bindgen::to_value(&RelayStatusResponse {
+            relays: relays.into_iter().map(|r| r.into_web()).collect(),
+        })?;
+        Ok(res)
+    }
+
     #[wasm_bindgen]
     pub async fn trigger_test_msg(&self, payload: JsValue) -> Result<()> {
         let msg: serde_json::Value = serde_wasm_bindgen::from_value(payload)?;
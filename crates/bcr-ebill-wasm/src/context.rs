@@ -2,7 +2,7 @@
 use super::{CONTEXT, Result};
 use bcr_ebill_api::{
     Config, DbContext,
-    external::bitcoin::BitcoinClient,
+    external::{bitcoin::BitcoinClient, fiat::FiatRateClient, rate::RateProviderApi},
     service::{
         bill_service::{BillServiceApi, service::BillService},
         company_service::{CompanyService, CompanyServiceApi},
@@ -10,7 +10,8 @@ use bcr_ebill_api::{
         file_upload_service::{FileUploadService, FileUploadServiceApi},
         identity_service::{IdentityService, IdentityServiceApi},
         notification_service::{
-            NostrConsumer, create_nostr_clients, create_nostr_consumer, create_notification_service,
+            NostrConsumer, NotificationJsonTransportApi, create_nostr_clients,
+            create_nostr_consumer, create_notification_service, create_webhook_dispatcher,
         },
         search_service::{SearchService, SearchServiceApi},
     },
@@ -36,22 +37,36 @@ pub struct Context {
 }
 
 impl Context {
-    pub async fn new(cfg: Config, db: DbContext) -> Result<Self> {
+    /// Creates the services with exchange rates from the given `rate_provider`
+    pub async fn new(
+        cfg: Config,
+        db: DbContext,
+        rate_provider: Arc<dyn RateProviderApi>,
+    ) -> Result<Self> {
+        let nostr_clients =
+            create_nostr_clients(&cfg, db.identity_store.clone(), db.company_store.clone()).await?;
         let contact_service = Arc::new(ContactService::new(
             db.contact_store.clone(),
             db.file_upload_store.clone(),
             db.identity_store.clone(),
+            nostr_clients
+                .iter()
+                .map(|c| c.clone() as Arc<dyn NotificationJsonTransportApi>)
+                .collect(),
         ));
-        let bitcoin_client = Arc::new(BitcoinClient::new());
+        let bitcoin_client = Arc::new(BitcoinClient::from_config(&cfg));
+        let fiat_rate_client = Arc::new(FiatRateClient::new(rate_provider));
 
-        let nostr_clients =
-            create_nostr_clients(&cfg, db.identity_store.clone(), db.company_store.clone()).await?;
+        let webhook_dispatcher = create_webhook_dispatcher(&cfg, db.identity_store.clone()).await?;
         let notification_service = create_notification_service(
             nostr_clients.clone(),
             db.notification_store.clone(),
             contact_service.clone(),
             db.queued_message_store.clone(),
-            &cfg.nostr_relay,
+            &cfg.nostr_relay(),
+            cfg.nostr_max_retry_delay_seconds,
+            cfg.nostr_max_retries,
+            webhook_dispatcher.clone(),
         )
         .await?;
 
@@ -61,11 +76,14 @@ impl Context {
             db.identity_store.clone(),
             db.file_upload_store.clone(),
             bitcoin_client,
+            fiat_rate_client,
             notification_service.clone(),
             db.identity_chain_store.clone(),
             db.company_chain_store.clone(),
             db.contact_store.clone(),
             db.company_store.clone(),
+            db.bill_template_store.clone(),
+            db.bill_auto_accept_store.clone(),
         ));
         let identity_service = IdentityService::new(
             db.identity_store.clone(),
@@ -93,6 +111,8 @@ impl Context {
             push_service.clone(),
             db.bill_blockchain_store.clone(),
             db.bill_store.clone(),
+            db.bill_auto_accept_store.clone(),
+            webhook_dispatcher,
         )
         .await?;
 
@@ -1,21 +1,25 @@
 use bcr_ebill_api::data::{
     bill::{
-        BillAcceptanceStatus, BillCombinedBitcoinKey, BillCurrentWaitingState, BillData,
+        BillAcceptanceStatus, BillAcknowledgement, BillAutoAcceptRule, BillCombinedBitcoinKey,
+        BillCurrentWaitingState, BillData, BillFiatAmount, BillHistoryEntry, BillHold,
         BillParticipants, BillPaymentStatus, BillRecourseStatus, BillSellStatus, BillStatus,
         BillWaitingForPaymentState, BillWaitingForRecourseState, BillWaitingForSellState,
-        BillsFilterRole, BitcreditBillResult, Endorsement, LightBitcreditBillResult, LightSignedBy,
-        PastEndorsee, PastPaymentDataPayment, PastPaymentDataRecourse, PastPaymentDataSell,
-        PastPaymentResult, PastPaymentStatus,
+        BillsFilterRole, BillsFilterStatus, BitcreditBillResult, Endorsement, FeeEstimate,
+        LightBitcreditBillResult, LightSignedBy, PastEndorsee, PastPaymentDataPayment,
+        PastPaymentDataRecourse, PastPaymentDataSell, PastPaymentResult, PastPaymentStatus,
+        SettlementStatus,
     },
     contact::{IdentityPublicData, LightIdentityPublicData, LightIdentityPublicDataWithAddress},
 };
+use bcr_ebill_api::{BillOpCode, BlockValidationError, ChainVerificationReport};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
 use super::{
-    FileWeb, FromWeb, IntoWeb, PostalAddressWeb, contact::ContactTypeWeb,
-    notification::NotificationWeb,
+    FileWeb, FromWeb, IntoWeb, PostalAddressWeb,
+    contact::ContactTypeWeb,
+    notification::{ActionTypeWeb, NotificationWeb},
 };
 
 #[derive(Tsify, Debug, Serialize)]
@@ -34,12 +38,16 @@ pub struct BitcreditBillPayload {
     pub maturity_date: String,
     pub payee: String,
     pub drawee: String,
+    /// The sum in the given currency - either in sat, or a fiat sum with up to 2 decimals
     pub sum: String,
+    /// Either `sat`, or a fiat currency (e.g. `EUR`), for which the sat equivalent of the sum is
+    /// computed with the current BTC rate and recorded in the bill
     pub currency: String,
     pub country_of_payment: String,
     pub city_of_payment: String,
     pub language: String,
     pub file_upload_ids: Vec<String>,
+    pub payment_deadline_seconds: Option<u64>,
 }
 
 #[derive(Tsify, Debug, Clone, Serialize)]
@@ -88,6 +96,33 @@ pub struct RequestToPayBitcreditBillPayload {
     pub currency: String,
 }
 
+#[derive(Tsify, Debug, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct AttachFilesToBillPayload {
+    pub bill_id: String,
+    pub file_upload_ids: Vec<String>,
+}
+
+#[derive(Tsify, Debug, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct AcknowledgeBillPayload {
+    pub bill_id: String,
+}
+
+#[derive(Tsify, Debug, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct CorrectDraweePayload {
+    pub bill_id: String,
+    pub drawee: String,
+}
+
+#[derive(Tsify, Debug, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct MarkPaidExternallyPayload {
+    pub bill_id: String,
+    pub reference: String,
+}
+
 #[derive(Tsify, Debug, Deserialize)]
 #[tsify(from_wasm_abi)]
 pub struct RequestRecourseForPaymentPayload {
@@ -120,6 +155,8 @@ pub struct RequestToAcceptBitcreditBillPayload {
 #[tsify(from_wasm_abi)]
 pub struct RejectActionBillPayload {
     pub bill_id: String,
+    /// An optional reason for the rejection, shown to the other participants
+    pub reason: Option<String>,
 }
 
 #[derive(Tsify, Debug, Clone, Serialize)]
@@ -145,6 +182,115 @@ pub enum BillsFilterRoleWeb {
     Contingent,
 }
 
+#[derive(Tsify, Debug, Clone, Copy, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub enum BillsFilterStatusWeb {
+    All,
+    Paid,
+    Unpaid,
+    Overdue,
+    AwaitingAction,
+}
+
+#[derive(Tsify, Debug, Clone, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct ResendBillEventPayload {
+    pub bill_id: String,
+    pub block_height: usize,
+    pub node_id: String,
+}
+
+#[derive(Tsify, Debug, Clone, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct SetBillHoldPayload {
+    pub bill_id: String,
+    pub reason: String,
+}
+
+#[derive(Tsify, Debug, Clone, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct SetBillNotePayload {
+    pub bill_id: String,
+    pub note: Option<String>,
+}
+
+#[derive(Tsify, Debug, Clone, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct AddAutoAcceptRulePayload {
+    pub drawer_node_id: String,
+    pub max_sum: String,
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct BillAutoAcceptRuleWeb {
+    pub id: String,
+    pub drawer_node_id: String,
+    pub max_sum: String,
+}
+
+impl IntoWeb<BillAutoAcceptRuleWeb> for BillAutoAcceptRule {
+    fn into_web(self) -> BillAutoAcceptRuleWeb {
+        BillAutoAcceptRuleWeb {
+            id: self.id,
+            drawer_node_id: self.drawer_node_id,
+            max_sum: self.max_sum.to_string(),
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct BillAutoAcceptRulesResponse {
+    pub rules: Vec<BillAutoAcceptRuleWeb>,
+}
+
+#[derive(Tsify, Debug, Clone, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct PreviewNotificationPayload {
+    pub bill_id: String,
+    pub action_type: ActionTypeWeb,
+    pub language: String,
+}
+
+/// The bill actions, which can be validated without executing them
+#[derive(Tsify, Debug, Copy, Clone, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum BillActionTypeWeb {
+    RequestAcceptance,
+    Accept,
+    RequestToPay,
+    RequestAcceptanceAndPayment,
+    OfferToSell,
+    Endorse,
+    RequestRecourseForPayment,
+    RequestRecourseForAcceptance,
+    Mint,
+    RejectAcceptance,
+    RejectPayment,
+    RejectBuying,
+    RejectPaymentForRecourse,
+    CancelOfferToSell,
+    CancelRequestToAccept,
+    MarkPaidExternally,
+    Acknowledge,
+    CorrectDrawee,
+}
+
+/// The fields, which aren't needed for the given action, are ignored
+#[derive(Tsify, Debug, Clone, Deserialize)]
+#[tsify(from_wasm_abi)]
+pub struct ValidateBillActionPayload {
+    pub bill_id: String,
+    pub action_type: BillActionTypeWeb,
+    /// The buyer, endorsee, recoursee, mint, or new drawee
+    pub counterparty: Option<String>,
+    pub sum: Option<String>,
+    pub currency: Option<String>,
+    /// The reason of a rejection, or the reference of an external payment
+    pub reason: Option<String>,
+}
+
 impl FromWeb<BillsFilterRoleWeb> for BillsFilterRole {
     fn from_web(value: BillsFilterRoleWeb) -> Self {
         match value {
@@ -156,6 +302,18 @@ impl FromWeb<BillsFilterRoleWeb> for BillsFilterRole {
     }
 }
 
+impl FromWeb<BillsFilterStatusWeb> for BillsFilterStatus {
+    fn from_web(value: BillsFilterStatusWeb) -> Self {
+        match value {
+            BillsFilterStatusWeb::All => BillsFilterStatus::All,
+            BillsFilterStatusWeb::Paid => BillsFilterStatus::Paid,
+            BillsFilterStatusWeb::Unpaid => BillsFilterStatus::Unpaid,
+            BillsFilterStatusWeb::Overdue => BillsFilterStatus::Overdue,
+            BillsFilterStatusWeb::AwaitingAction => BillsFilterStatus::AwaitingAction,
+        }
+    }
+}
+
 #[derive(Tsify, Debug, Clone, Serialize)]
 #[tsify(into_wasm_abi)]
 pub struct PastEndorseeWeb {
@@ -176,6 +334,134 @@ impl IntoWeb<PastEndorseeWeb> for PastEndorsee {
     }
 }
 
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub enum BillOpCodeWeb {
+    Issue,
+    Accept,
+    Endorse,
+    RequestToAccept,
+    RequestToPay,
+    OfferToSell,
+    Sell,
+    Mint,
+    RejectToAccept,
+    RejectToPay,
+    RejectToBuy,
+    RejectToPayRecourse,
+    RequestRecourse,
+    Recourse,
+    AttachFile,
+    ExternalPayment,
+    Acknowledge,
+    CorrectDrawee,
+    CancelOfferToSell,
+    CancelRequestToAccept,
+    MintQuote,
+    ApproveMintQuote,
+    RejectMintQuote,
+}
+
+impl IntoWeb<BillOpCodeWeb> for BillOpCode {
+    fn into_web(self) -> BillOpCodeWeb {
+        match self {
+            BillOpCode::Issue => BillOpCodeWeb::Issue,
+            BillOpCode::Accept => BillOpCodeWeb::Accept,
+            BillOpCode::Endorse => BillOpCodeWeb::Endorse,
+            BillOpCode::RequestToAccept => BillOpCodeWeb::RequestToAccept,
+            BillOpCode::RequestToPay => BillOpCodeWeb::RequestToPay,
+            BillOpCode::OfferToSell => BillOpCodeWeb::OfferToSell,
+            BillOpCode::Sell => BillOpCodeWeb::Sell,
+            BillOpCode::Mint => BillOpCodeWeb::Mint,
+            BillOpCode::RejectToAccept => BillOpCodeWeb::RejectToAccept,
+            BillOpCode::RejectToPay => BillOpCodeWeb::RejectToPay,
+            BillOpCode::RejectToBuy => BillOpCodeWeb::RejectToBuy,
+            BillOpCode::RejectToPayRecourse => BillOpCodeWeb::RejectToPayRecourse,
+            BillOpCode::RequestRecourse => BillOpCodeWeb::RequestRecourse,
+            BillOpCode::Recourse => BillOpCodeWeb::Recourse,
+            BillOpCode::AttachFile => BillOpCodeWeb::AttachFile,
+            BillOpCode::ExternalPayment => BillOpCodeWeb::ExternalPayment,
+            BillOpCode::Acknowledge => BillOpCodeWeb::Acknowledge,
+            BillOpCode::CorrectDrawee => BillOpCodeWeb::CorrectDrawee,
+            BillOpCode::CancelOfferToSell => BillOpCodeWeb::CancelOfferToSell,
+            BillOpCode::CancelRequestToAccept => BillOpCodeWeb::CancelRequestToAccept,
+            BillOpCode::MintQuote => BillOpCodeWeb::MintQuote,
+            BillOpCode::ApproveMintQuote => BillOpCodeWeb::ApproveMintQuote,
+            BillOpCode::RejectMintQuote => BillOpCodeWeb::RejectMintQuote,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct BillHistoryEntryWeb {
+    pub block_height: u64,
+    pub op_code: BillOpCodeWeb,
+    pub acting_node_id: String,
+    pub timestamp: u64,
+    pub summary: String,
+}
+
+impl IntoWeb<BillHistoryEntryWeb> for BillHistoryEntry {
+    fn into_web(self) -> BillHistoryEntryWeb {
+        BillHistoryEntryWeb {
+            block_height: self.block_height,
+            op_code: self.op_code.into_web(),
+            acting_node_id: self.acting_node_id,
+            timestamp: self.timestamp,
+            summary: self.summary,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub enum BlockValidationErrorWeb {
+    InvalidPreviousHash,
+    InvalidTimestamp,
+    InvalidBlockId,
+    InvalidHash,
+    InvalidSignature,
+    InvalidBlockData,
+    SignerMismatch,
+}
+
+impl IntoWeb<BlockValidationErrorWeb> for BlockValidationError {
+    fn into_web(self) -> BlockValidationErrorWeb {
+        match self {
+            BlockValidationError::InvalidPreviousHash => {
+                BlockValidationErrorWeb::InvalidPreviousHash
+            }
+            BlockValidationError::InvalidTimestamp => BlockValidationErrorWeb::InvalidTimestamp,
+            BlockValidationError::InvalidBlockId => BlockValidationErrorWeb::InvalidBlockId,
+            BlockValidationError::InvalidHash => BlockValidationErrorWeb::InvalidHash,
+            BlockValidationError::InvalidSignature => BlockValidationErrorWeb::InvalidSignature,
+            BlockValidationError::InvalidBlockData => BlockValidationErrorWeb::InvalidBlockData,
+            BlockValidationError::SignerMismatch => BlockValidationErrorWeb::SignerMismatch,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct ChainVerificationReportWeb {
+    pub valid: bool,
+    pub valid_blocks: u64,
+    pub first_invalid_block_height: Option<u64>,
+    pub reason: Option<BlockValidationErrorWeb>,
+}
+
+impl IntoWeb<ChainVerificationReportWeb> for ChainVerificationReport {
+    fn into_web(self) -> ChainVerificationReportWeb {
+        ChainVerificationReportWeb {
+            valid: self.is_valid(),
+            valid_blocks: self.valid_blocks,
+            first_invalid_block_height: self.first_invalid_block_height,
+            reason: self.reason.map(|r| r.into_web()),
+        }
+    }
+}
+
 #[derive(Tsify, Debug, Clone, Serialize)]
 #[tsify(into_wasm_abi)]
 pub struct LightSignedByWeb {
@@ -231,6 +517,8 @@ pub struct BillsSearchFilter {
     pub search_term: Option<String>,
     pub date_range: Option<DateRange>,
     pub role: BillsFilterRoleWeb,
+    /// Only bills with the given status are returned - all bills, if not set
+    pub status: Option<BillsFilterStatusWeb>,
     pub currency: String,
 }
 
@@ -246,18 +534,43 @@ pub struct LightBillsResponse {
     pub bills: Vec<LightBitcreditBillWeb>,
 }
 
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct BillsGroupWeb {
+    pub node_id: String,
+    pub bills: Vec<LightBitcreditBillWeb>,
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct BillsGroupedResponse {
+    pub groups: Vec<BillsGroupWeb>,
+}
+
 #[derive(Tsify, Debug, Clone, Serialize)]
 #[tsify(into_wasm_abi)]
 pub struct EndorsementsResponse {
     pub endorsements: Vec<EndorsementWeb>,
 }
 
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct BillHistoryResponse {
+    pub history: Vec<BillHistoryEntryWeb>,
+}
+
 #[derive(Tsify, Debug, Clone, Serialize)]
 #[tsify(into_wasm_abi)]
 pub struct PastEndorseesResponse {
     pub past_endorsees: Vec<PastEndorseeWeb>,
 }
 
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct PastHolderResponse {
+    pub is_past_holder: bool,
+}
+
 #[derive(Tsify, Debug, Clone, Serialize)]
 #[tsify(into_wasm_abi)]
 pub struct PastPaymentsResponse {
@@ -395,6 +708,8 @@ impl IntoWeb<PastPaymentDataRecourseWeb> for PastPaymentDataRecourse {
     }
 }
 
+/// Only exported as the TypeScript return type of the not yet implemented quote API
+#[allow(dead_code)]
 #[derive(Tsify, Debug, Serialize, Clone)]
 #[tsify(into_wasm_abi)]
 pub struct BitcreditEbillQuote {
@@ -429,6 +744,12 @@ impl IntoWeb<BitcreditBillWeb> for BitcreditBillResult {
     }
 }
 
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct BillWaitingStateResponse {
+    pub current_waiting_state: Option<BillCurrentWaitingStateWeb>,
+}
+
 #[derive(Tsify, Debug, Serialize, Clone)]
 #[tsify(into_wasm_abi)]
 pub enum BillCurrentWaitingStateWeb {
@@ -453,6 +774,22 @@ impl IntoWeb<BillCurrentWaitingStateWeb> for BillCurrentWaitingState {
     }
 }
 
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct FeeEstimateWeb {
+    pub fee_rate_sat_vb: u64,
+    pub estimated_confirmation_seconds: u64,
+}
+
+impl IntoWeb<FeeEstimateWeb> for FeeEstimate {
+    fn into_web(self) -> FeeEstimateWeb {
+        FeeEstimateWeb {
+            fee_rate_sat_vb: self.fee_rate_sat_vb,
+            estimated_confirmation_seconds: self.estimated_confirmation_seconds,
+        }
+    }
+}
+
 #[derive(Tsify, Debug, Serialize, Clone)]
 #[tsify(into_wasm_abi)]
 pub struct BillWaitingForSellStateWeb {
@@ -464,6 +801,7 @@ pub struct BillWaitingForSellStateWeb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimateWeb>,
 }
 
 impl IntoWeb<BillWaitingForSellStateWeb> for BillWaitingForSellState {
@@ -477,6 +815,7 @@ impl IntoWeb<BillWaitingForSellStateWeb> for BillWaitingForSellState {
             link_to_pay: self.link_to_pay,
             address_to_pay: self.address_to_pay,
             mempool_link_for_address_to_pay: self.mempool_link_for_address_to_pay,
+            fee_estimate: self.fee_estimate.map(|f| f.into_web()),
         }
     }
 }
@@ -492,6 +831,7 @@ pub struct BillWaitingForPaymentStateWeb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimateWeb>,
 }
 
 impl IntoWeb<BillWaitingForPaymentStateWeb> for BillWaitingForPaymentState {
@@ -505,6 +845,7 @@ impl IntoWeb<BillWaitingForPaymentStateWeb> for BillWaitingForPaymentState {
             link_to_pay: self.link_to_pay,
             address_to_pay: self.address_to_pay,
             mempool_link_for_address_to_pay: self.mempool_link_for_address_to_pay,
+            fee_estimate: self.fee_estimate.map(|f| f.into_web()),
         }
     }
 }
@@ -520,6 +861,7 @@ pub struct BillWaitingForRecourseStateWeb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimateWeb>,
 }
 impl IntoWeb<BillWaitingForRecourseStateWeb> for BillWaitingForRecourseState {
     fn into_web(self) -> BillWaitingForRecourseStateWeb {
@@ -532,6 +874,7 @@ impl IntoWeb<BillWaitingForRecourseStateWeb> for BillWaitingForRecourseState {
             link_to_pay: self.link_to_pay,
             address_to_pay: self.address_to_pay,
             mempool_link_for_address_to_pay: self.mempool_link_for_address_to_pay,
+            fee_estimate: self.fee_estimate.map(|f| f.into_web()),
         }
     }
 }
@@ -545,6 +888,8 @@ pub struct BillStatusWeb {
     pub recourse: BillRecourseStatusWeb,
     pub redeemed_funds_available: bool,
     pub has_requested_funds: bool,
+    pub hold: Option<BillHoldWeb>,
+    pub settlement_status: SettlementStatusWeb,
 }
 
 impl IntoWeb<BillStatusWeb> for BillStatus {
@@ -556,6 +901,42 @@ impl IntoWeb<BillStatusWeb> for BillStatus {
             recourse: self.recourse.into_web(),
             redeemed_funds_available: self.redeemed_funds_available,
             has_requested_funds: self.has_requested_funds,
+            hold: self.hold.map(|h| h.into_web()),
+            settlement_status: self.settlement_status.into_web(),
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub enum SettlementStatusWeb {
+    Active,
+    Settled,
+    Defaulted,
+}
+
+impl IntoWeb<SettlementStatusWeb> for SettlementStatus {
+    fn into_web(self) -> SettlementStatusWeb {
+        match self {
+            SettlementStatus::Active => SettlementStatusWeb::Active,
+            SettlementStatus::Settled => SettlementStatusWeb::Settled,
+            SettlementStatus::Defaulted => SettlementStatusWeb::Defaulted,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct BillHoldWeb {
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+impl IntoWeb<BillHoldWeb> for BillHold {
+    fn into_web(self) -> BillHoldWeb {
+        BillHoldWeb {
+            reason: self.reason,
+            timestamp: self.timestamp,
         }
     }
 }
@@ -568,6 +949,7 @@ pub struct BillAcceptanceStatusWeb {
     pub accepted: bool,
     pub request_to_accept_timed_out: bool,
     pub rejected_to_accept: bool,
+    pub rejected_to_accept_reason: Option<String>,
 }
 
 impl IntoWeb<BillAcceptanceStatusWeb> for BillAcceptanceStatus {
@@ -578,6 +960,7 @@ impl IntoWeb<BillAcceptanceStatusWeb> for BillAcceptanceStatus {
             accepted: self.accepted,
             request_to_accept_timed_out: self.request_to_accept_timed_out,
             rejected_to_accept: self.rejected_to_accept,
+            rejected_to_accept_reason: self.rejected_to_accept_reason,
         }
     }
 }
@@ -588,8 +971,11 @@ pub struct BillPaymentStatusWeb {
     pub time_of_request_to_pay: Option<u64>,
     pub requested_to_pay: bool,
     pub paid: bool,
+    pub paid_sum: u64,
     pub request_to_pay_timed_out: bool,
     pub rejected_to_pay: bool,
+    pub rejected_to_pay_reason: Option<String>,
+    pub external_payment_reference: Option<String>,
 }
 impl IntoWeb<BillPaymentStatusWeb> for BillPaymentStatus {
     fn into_web(self) -> BillPaymentStatusWeb {
@@ -597,8 +983,11 @@ impl IntoWeb<BillPaymentStatusWeb> for BillPaymentStatus {
             time_of_request_to_pay: self.time_of_request_to_pay,
             requested_to_pay: self.requested_to_pay,
             paid: self.paid,
+            paid_sum: self.paid_sum,
             request_to_pay_timed_out: self.request_to_pay_timed_out,
             rejected_to_pay: self.rejected_to_pay,
+            rejected_to_pay_reason: self.rejected_to_pay_reason,
+            external_payment_reference: self.external_payment_reference,
         }
     }
 }
@@ -611,6 +1000,7 @@ pub struct BillSellStatusWeb {
     pub offered_to_sell: bool,
     pub offer_to_sell_timed_out: bool,
     pub rejected_offer_to_sell: bool,
+    pub rejected_offer_to_sell_reason: Option<String>,
 }
 impl IntoWeb<BillSellStatusWeb> for BillSellStatus {
     fn into_web(self) -> BillSellStatusWeb {
@@ -620,6 +1010,7 @@ impl IntoWeb<BillSellStatusWeb> for BillSellStatus {
             offered_to_sell: self.offered_to_sell,
             offer_to_sell_timed_out: self.offer_to_sell_timed_out,
             rejected_offer_to_sell: self.rejected_offer_to_sell,
+            rejected_offer_to_sell_reason: self.rejected_offer_to_sell_reason,
         }
     }
 }
@@ -661,7 +1052,12 @@ pub struct BillDataWeb {
     pub currency: String,
     pub sum: String,
     pub files: Vec<FileWeb>,
+    pub tampered_files: Vec<String>,
+    pub payment_deadline_seconds: u64,
+    pub fiat_amount: Option<BillFiatAmountWeb>,
     pub active_notification: Option<NotificationWeb>,
+    pub note: Option<String>,
+    pub acknowledgements: Vec<BillAcknowledgementWeb>,
 }
 
 impl IntoWeb<BillDataWeb> for BillData {
@@ -679,7 +1075,57 @@ impl IntoWeb<BillDataWeb> for BillData {
             currency: self.currency,
             sum: self.sum,
             files: self.files.into_iter().map(|f| f.into_web()).collect(),
+            tampered_files: self.tampered_files,
+            payment_deadline_seconds: self.payment_deadline_seconds,
+            fiat_amount: self.fiat_amount.map(|f| f.into_web()),
             active_notification: self.active_notification.map(|an| an.into_web()),
+            note: self.note,
+            acknowledgements: self
+                .acknowledgements
+                .into_iter()
+                .map(|a| a.into_web())
+                .collect(),
+        }
+    }
+}
+
+/// An attestation of the holder, or the drawee, that they have seen the bill
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct BillAcknowledgementWeb {
+    pub node_id: String,
+    pub name: String,
+    pub timestamp: u64,
+}
+
+impl IntoWeb<BillAcknowledgementWeb> for BillAcknowledgement {
+    fn into_web(self) -> BillAcknowledgementWeb {
+        BillAcknowledgementWeb {
+            node_id: self.node_id,
+            name: self.name,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// The fiat sum a bill was issued with - the sum of the bill is the sat equivalent, computed
+/// with the given BTC rate at issuance
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct BillFiatAmountWeb {
+    pub currency: String,
+    pub sum: String,
+    pub btc_rate: f64,
+    pub rate_timestamp: u64,
+}
+
+impl IntoWeb<BillFiatAmountWeb> for BillFiatAmount {
+    fn into_web(self) -> BillFiatAmountWeb {
+        BillFiatAmountWeb {
+            currency: self.currency,
+            sum: self.sum,
+            btc_rate: self.btc_rate,
+            rate_timestamp: self.rate_timestamp,
         }
     }
 }
@@ -722,6 +1168,8 @@ pub struct LightBitcreditBillWeb {
     pub issue_date: String,
     pub time_of_drawing: u64,
     pub time_of_maturity: u64,
+    pub last_op_code: BillOpCodeWeb,
+    pub block_height: u64,
 }
 
 impl IntoWeb<LightBitcreditBillWeb> for LightBitcreditBillResult {
@@ -738,6 +1186,8 @@ impl IntoWeb<LightBitcreditBillWeb> for LightBitcreditBillResult {
             issue_date: self.issue_date,
             time_of_drawing: self.time_of_drawing,
             time_of_maturity: self.time_of_maturity,
+            last_op_code: self.last_op_code.into_web(),
+            block_height: self.block_height,
         }
     }
 }
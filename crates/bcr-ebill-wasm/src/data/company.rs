@@ -1,4 +1,7 @@
-use bcr_ebill_api::data::{company::Company, contact::Contact};
+use bcr_ebill_api::{
+    SignatoryChange, SignatoryChangeType,
+    data::{company::Company, contact::Contact},
+};
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
@@ -106,6 +109,52 @@ pub struct SignatoryResponse {
     pub avatar_file: Option<FileWeb>,
 }
 
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct SignatoryHistoryResponse {
+    pub changes: Vec<SignatoryChangeWeb>,
+}
+
+#[derive(Tsify, Debug, Copy, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub enum SignatoryChangeTypeWeb {
+    Add,
+    Remove,
+}
+
+impl IntoWeb<SignatoryChangeTypeWeb> for SignatoryChangeType {
+    fn into_web(self) -> SignatoryChangeTypeWeb {
+        match self {
+            SignatoryChangeType::Add => SignatoryChangeTypeWeb::Add,
+            SignatoryChangeType::Remove => SignatoryChangeTypeWeb::Remove,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct SignatoryChangeWeb {
+    pub change_type: SignatoryChangeTypeWeb,
+    /// The node id of the signatory that was added or removed
+    pub signatory: String,
+    /// The node id of the signatory who made the change
+    pub changed_by: String,
+    pub block_id: u64,
+    pub timestamp: u64,
+}
+
+impl IntoWeb<SignatoryChangeWeb> for SignatoryChange {
+    fn into_web(self) -> SignatoryChangeWeb {
+        SignatoryChangeWeb {
+            change_type: self.change_type.into_web(),
+            signatory: self.signatory,
+            changed_by: self.changed_by,
+            block_id: self.block_id,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
 impl From<Contact> for SignatoryResponse {
     fn from(value: Contact) -> Self {
         Self {
@@ -1,6 +1,6 @@
 use bcr_ebill_api::{
     data::contact::{Contact, ContactType},
-    service::Error,
+    service::{Error, contact_service::RefreshedContact},
     util::ValidationError,
 };
 use serde::{Deserialize, Serialize};
@@ -121,3 +121,20 @@ impl IntoWeb<ContactWeb> for Contact {
         }
     }
 }
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct RefreshedContactWeb {
+    pub contact: ContactWeb,
+    /// Set, if no Nostr profile of the contact could be found, so the contact might be outdated
+    pub stale: bool,
+}
+
+impl IntoWeb<RefreshedContactWeb> for RefreshedContact {
+    fn into_web(self) -> RefreshedContactWeb {
+        RefreshedContactWeb {
+            contact: self.contact.into_web(),
+            stale: self.stale,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use bcr_ebill_api::{
-    data::identity::{Identity, IdentityType},
+    data::identity::{ControlledIdentity, Identity, IdentityType},
     service::Result,
     util::BcrKeys,
 };
@@ -35,6 +35,30 @@ impl IntoWeb<IdentityTypeWeb> for IdentityType {
     }
 }
 
+#[derive(Tsify, Debug, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct ControlledIdentityWeb {
+    pub node_id: String,
+    pub t: IdentityTypeWeb,
+    pub name: String,
+}
+
+impl IntoWeb<ControlledIdentityWeb> for ControlledIdentity {
+    fn into_web(self) -> ControlledIdentityWeb {
+        ControlledIdentityWeb {
+            node_id: self.node_id,
+            t: self.t.into_web(),
+            name: self.name,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct ControlledIdentitiesResponse {
+    pub identities: Vec<ControlledIdentityWeb>,
+}
+
 #[derive(Tsify, Debug, Deserialize)]
 #[tsify(from_wasm_abi)]
 pub struct NewIdentityPayload {
@@ -105,6 +105,48 @@ pub struct BalanceResponse {
     pub sum: String,
 }
 
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct ExposureResponse {
+    pub balances: OverviewBalanceResponse,
+    pub net_position: String,
+    pub accepted_count: u64,
+    pub awaiting_acceptance_count: u64,
+    pub overdue_count: u64,
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct OverviewWithFiatResponse {
+    pub currency: String,
+    pub fiat_code: String,
+    pub balances: OverviewBalanceWithFiatResponse,
+    pub fiat_rate: Option<FiatRateResponse>,
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct OverviewBalanceWithFiatResponse {
+    pub payee: BalanceWithFiatResponse,
+    pub payer: BalanceWithFiatResponse,
+    pub contingent: BalanceWithFiatResponse,
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct BalanceWithFiatResponse {
+    pub sum: String,
+    pub fiat_sum: Option<String>,
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct FiatRateResponse {
+    pub fiat_code: String,
+    pub rate: f64,
+    pub timestamp: u64,
+}
+
 #[derive(Tsify, Debug, Clone, Serialize)]
 #[tsify(into_wasm_abi)]
 pub struct CurrenciesResponse {
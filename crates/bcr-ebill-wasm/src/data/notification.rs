@@ -1,10 +1,35 @@
-use bcr_ebill_api::data::notification::{Notification, NotificationType};
+use bcr_ebill_api::NotificationPage;
+use bcr_ebill_api::data::notification::{
+    ActionType, Notification, NotificationPreview, NotificationType,
+};
+use bcr_ebill_transport::RelayStatus;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tsify::Tsify;
 use wasm_bindgen::prelude::*;
 
-use super::IntoWeb;
+use super::{FromWeb, IntoWeb};
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct NotificationsResponse {
+    pub notifications: Vec<NotificationWeb>,
+    /// The cursor to pass as `after` to fetch the next page - empty, if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+impl IntoWeb<NotificationsResponse> for NotificationPage {
+    fn into_web(self) -> NotificationsResponse {
+        NotificationsResponse {
+            notifications: self
+                .notifications
+                .into_iter()
+                .map(|n| n.into_web())
+                .collect(),
+            next_cursor: self.next_cursor.map(|c| c.encode()),
+        }
+    }
+}
 
 #[derive(Tsify, Debug, Clone, Serialize, Deserialize)]
 #[tsify(into_wasm_abi, from_wasm_abi)]
@@ -52,3 +77,88 @@ impl IntoWeb<NotificationTypeWeb> for NotificationType {
         }
     }
 }
+
+impl FromWeb<NotificationTypeWeb> for NotificationType {
+    fn from_web(value: NotificationTypeWeb) -> Self {
+        match value {
+            NotificationTypeWeb::Bill => NotificationType::Bill,
+            NotificationTypeWeb::General => NotificationType::General,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Copy, Clone, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum ActionTypeWeb {
+    BuyBill,
+    RecourseBill,
+    AcceptBill,
+    CheckBill,
+    PayBill,
+    CheckQuote,
+    RequestPayment,
+}
+
+impl FromWeb<ActionTypeWeb> for ActionType {
+    fn from_web(value: ActionTypeWeb) -> Self {
+        match value {
+            ActionTypeWeb::BuyBill => ActionType::BuyBill,
+            ActionTypeWeb::RecourseBill => ActionType::RecourseBill,
+            ActionTypeWeb::AcceptBill => ActionType::AcceptBill,
+            ActionTypeWeb::CheckBill => ActionType::CheckBill,
+            ActionTypeWeb::PayBill => ActionType::PayBill,
+            ActionTypeWeb::CheckQuote => ActionType::CheckQuote,
+            ActionTypeWeb::RequestPayment => ActionType::RequestPayment,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Clone, Serialize)]
+#[tsify(into_wasm_abi)]
+pub struct NotificationPreviewWeb {
+    pub language: String,
+    pub in_app_text: String,
+    pub email_subject: String,
+    pub email_body: String,
+    pub push_text: String,
+}
+
+impl IntoWeb<NotificationPreviewWeb> for NotificationPreview {
+    fn into_web(self) -> NotificationPreviewWeb {
+        NotificationPreviewWeb {
+            language: self.language,
+            in_app_text: self.in_app_text,
+            email_subject: self.email_subject,
+            email_body: self.email_body,
+            push_text: self.push_text,
+        }
+    }
+}
+
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct RelayStatusResponse {
+    pub relays: Vec<RelayStatusWeb>,
+}
+
+#[derive(Tsify, Debug, Serialize, Clone)]
+#[tsify(into_wasm_abi)]
+pub struct RelayStatusWeb {
+    pub relay: String,
+    pub healthy: bool,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub last_error: Option<String>,
+}
+
+impl IntoWeb<RelayStatusWeb> for RelayStatus {
+    fn into_web(self) -> RelayStatusWeb {
+        RelayStatusWeb {
+            healthy: self.is_healthy(),
+            relay: self.relay,
+            success_count: self.success_count,
+            failure_count: self.failure_count,
+            last_error: self.last_error,
+        }
+    }
+}
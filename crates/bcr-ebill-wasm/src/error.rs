@@ -40,14 +40,20 @@ pub enum WasmError {
 enum JsErrorType {
     FieldEmpty,
     InvalidSum,
+    InvalidPaymentDeadline,
     InvalidCurrency,
+    InvalidCountryCode,
+    FiatRateUnavailable,
     InvalidPaymentAddress,
     InvalidContentType,
     InvalidContactType,
+    ContactsHaveDifferentNodeIds,
+    MissingCsvColumn,
     InvalidDate,
     IssueDateAfterMaturityDate,
     MaturityDateInThePast,
     InvalidFileUploadId,
+    InvalidNotificationCursor,
     InvalidBillType,
     DraweeCantBePayee,
     EndorserCantBeEndorsee,
@@ -87,6 +93,18 @@ enum JsErrorType {
     CallerIsNotHolder,
     CallerIsNotRecoursee,
     CallerIsNotBuyer,
+    CallerIsNotSeller,
+    CallerIsNotRequester,
+    CallerIsNotHolderOrDrawee,
+    IssuePolicyViolation,
+    InvalidBillQrPayload,
+    CallerIsNotDrawer,
+    DraweeCorrectionNotAllowed,
+    BillWasNotRequestedToMint,
+    CallerIsNotMint,
+    BillHasNoOpenMintQuote,
+    CallerIsNotMintRequester,
+    MintQuoteExpired,
     RequestAlreadyExpired,
     RequestAlreadyRejected,
     BillAlreadyPaid,
@@ -112,20 +130,388 @@ enum JsErrorType {
     SignatoryAlreadySignatory,
     CantRemoveLastSignatory,
     NotASignatory,
+    SignatoryCantSign,
     InvalidSecp256k1Key,
     FileIsTooBig,
+    TooManyFiles,
+    AttachmentsTooBig,
     InvalidFileName,
+    RejectReasonTooLong,
+    FileAlreadyAttached,
     UnknownNodeId,
     BackupNotSupported,
+    UnknownBillBundleVersion,
+    InvalidBillBundle,
+    InvalidBillView,
+    BillIsReadOnlyView,
+    UnknownIdentityBackupVersion,
+    InvalidIdentityBackup,
+    IdentityAlreadyExists,
     CallerMustBeSignatory,
 }
 
+/// A stable, machine-readable error code, namespaced by the kind of error, so the frontend can
+/// branch on it, e.g. `validation.invalid_currency`, or `network.error`
+#[derive(Tsify, Debug, Clone, Copy, Serialize)]
+#[tsify(into_wasm_abi)]
+enum JsErrorCode {
+    #[serde(rename = "validation.field_empty")]
+    FieldEmpty,
+    #[serde(rename = "validation.invalid_sum")]
+    InvalidSum,
+    #[serde(rename = "validation.invalid_payment_deadline")]
+    InvalidPaymentDeadline,
+    #[serde(rename = "validation.invalid_currency")]
+    InvalidCurrency,
+    #[serde(rename = "validation.invalid_country_code")]
+    InvalidCountryCode,
+    #[serde(rename = "validation.fiat_rate_unavailable")]
+    FiatRateUnavailable,
+    #[serde(rename = "validation.invalid_payment_address")]
+    InvalidPaymentAddress,
+    #[serde(rename = "validation.invalid_content_type")]
+    InvalidContentType,
+    #[serde(rename = "validation.invalid_contact_type")]
+    InvalidContactType,
+    #[serde(rename = "validation.contacts_have_different_node_ids")]
+    ContactsHaveDifferentNodeIds,
+    #[serde(rename = "validation.missing_csv_column")]
+    MissingCsvColumn,
+    #[serde(rename = "validation.invalid_date")]
+    InvalidDate,
+    #[serde(rename = "validation.issue_date_after_maturity_date")]
+    IssueDateAfterMaturityDate,
+    #[serde(rename = "validation.maturity_date_in_the_past")]
+    MaturityDateInThePast,
+    #[serde(rename = "validation.invalid_file_upload_id")]
+    InvalidFileUploadId,
+    #[serde(rename = "validation.invalid_notification_cursor")]
+    InvalidNotificationCursor,
+    #[serde(rename = "validation.invalid_bill_type")]
+    InvalidBillType,
+    #[serde(rename = "validation.drawee_cant_be_payee")]
+    DraweeCantBePayee,
+    #[serde(rename = "validation.endorser_cant_be_endorsee")]
+    EndorserCantBeEndorsee,
+    #[serde(rename = "validation.buyer_cant_be_seller")]
+    BuyerCantBeSeller,
+    #[serde(rename = "validation.recourser_cant_be_recoursee")]
+    RecourserCantBeRecoursee,
+    #[serde(rename = "validation.drawee_not_in_contacts")]
+    DraweeNotInContacts,
+    #[serde(rename = "validation.payee_not_in_contacts")]
+    PayeeNotInContacts,
+    #[serde(rename = "validation.mint_not_in_contacts")]
+    MintNotInContacts,
+    #[serde(rename = "validation.buyer_not_in_contacts")]
+    BuyerNotInContacts,
+    #[serde(rename = "validation.endorsee_not_in_contacts")]
+    EndorseeNotInContacts,
+    #[serde(rename = "validation.recoursee_not_in_contacts")]
+    RecourseeNotInContacts,
+    #[serde(rename = "validation.no_file_for_file_upload_id")]
+    NoFileForFileUploadId,
+    #[serde(rename = "general.not_found")]
+    NotFound,
+    #[serde(rename = "network.external_api")]
+    ExternalApi,
+    #[serde(rename = "internal.io")]
+    Io,
+    #[serde(rename = "internal.crypto")]
+    Crypto,
+    #[serde(rename = "internal.persistence")]
+    Persistence,
+    #[serde(rename = "internal.blockchain")]
+    Blockchain,
+    #[serde(rename = "internal.serialization")]
+    Serialization,
+    #[serde(rename = "internal.init")]
+    Init,
+    #[serde(rename = "network.error")]
+    NotificationNetwork,
+    #[serde(rename = "network.message")]
+    NotificationMessage,
+    #[serde(rename = "validation.invalid_operation")]
+    InvalidOperation,
+    #[serde(rename = "validation.bill_already_accepted")]
+    BillAlreadyAccepted,
+    #[serde(rename = "validation.bill_was_rejected_to_accept")]
+    BillWasRejectedToAccept,
+    #[serde(rename = "validation.bill_acceptance_expired")]
+    BillAcceptanceExpired,
+    #[serde(rename = "validation.bill_was_rejected_to_pay")]
+    BillWasRejectedToPay,
+    #[serde(rename = "validation.bill_payment_expired")]
+    BillPaymentExpired,
+    #[serde(rename = "validation.bill_was_rejected_to_recourse")]
+    BillWasRejectedToRecourse,
+    #[serde(rename = "validation.bill_request_to_recourse_expired")]
+    BillRequestToRecourseExpired,
+    #[serde(rename = "validation.bill_was_recoursed_to_the_end")]
+    BillWasRecoursedToTheEnd,
+    #[serde(rename = "validation.bill_already_requested_to_accept")]
+    BillAlreadyRequestedToAccept,
+    #[serde(rename = "validation.bill_not_accepted")]
+    BillNotAccepted,
+    #[serde(rename = "validation.caller_is_not_drawee")]
+    CallerIsNotDrawee,
+    #[serde(rename = "validation.caller_is_not_holder")]
+    CallerIsNotHolder,
+    #[serde(rename = "validation.caller_is_not_recoursee")]
+    CallerIsNotRecoursee,
+    #[serde(rename = "validation.caller_is_not_buyer")]
+    CallerIsNotBuyer,
+    #[serde(rename = "validation.caller_is_not_seller")]
+    CallerIsNotSeller,
+    #[serde(rename = "validation.caller_is_not_requester")]
+    CallerIsNotRequester,
+    #[serde(rename = "validation.caller_is_not_holder_or_drawee")]
+    CallerIsNotHolderOrDrawee,
+    #[serde(rename = "validation.issue_policy_violation")]
+    IssuePolicyViolation,
+    #[serde(rename = "validation.invalid_bill_qr_payload")]
+    InvalidBillQrPayload,
+    #[serde(rename = "validation.caller_is_not_drawer")]
+    CallerIsNotDrawer,
+    #[serde(rename = "validation.drawee_correction_not_allowed")]
+    DraweeCorrectionNotAllowed,
+    #[serde(rename = "validation.bill_was_not_requested_to_mint")]
+    BillWasNotRequestedToMint,
+    #[serde(rename = "validation.caller_is_not_mint")]
+    CallerIsNotMint,
+    #[serde(rename = "validation.bill_has_no_open_mint_quote")]
+    BillHasNoOpenMintQuote,
+    #[serde(rename = "validation.caller_is_not_mint_requester")]
+    CallerIsNotMintRequester,
+    #[serde(rename = "validation.mint_quote_expired")]
+    MintQuoteExpired,
+    #[serde(rename = "validation.request_already_expired")]
+    RequestAlreadyExpired,
+    #[serde(rename = "validation.request_already_rejected")]
+    RequestAlreadyRejected,
+    #[serde(rename = "validation.bill_already_paid")]
+    BillAlreadyPaid,
+    #[serde(rename = "validation.bill_was_not_requested_to_accept")]
+    BillWasNotRequestedToAccept,
+    #[serde(rename = "validation.bill_was_not_requested_to_pay")]
+    BillWasNotRequestedToPay,
+    #[serde(rename = "validation.bill_was_not_offered_to_sell")]
+    BillWasNotOfferedToSell,
+    #[serde(rename = "validation.bill_request_to_accept_did_not_expire_and_was_not_rejected")]
+    BillRequestToAcceptDidNotExpireAndWasNotRejected,
+    #[serde(rename = "validation.bill_request_to_pay_did_not_expire_and_was_not_rejected")]
+    BillRequestToPayDidNotExpireAndWasNotRejected,
+    #[serde(rename = "validation.recoursee_not_past_holder")]
+    RecourseeNotPastHolder,
+    #[serde(rename = "validation.bill_was_not_requested_to_recourse")]
+    BillWasNotRequestedToRecourse,
+    #[serde(rename = "validation.bill_is_not_requested_to_recourse_and_waiting_for_payment")]
+    BillIsNotRequestedToRecourseAndWaitingForPayment,
+    #[serde(rename = "validation.bill_is_not_offer_to_sell_waiting_for_payment")]
+    BillIsNotOfferToSellWaitingForPayment,
+    #[serde(rename = "validation.bill_sell_data_invalid")]
+    BillSellDataInvalid,
+    #[serde(rename = "validation.bill_recourse_data_invalid")]
+    BillRecourseDataInvalid,
+    #[serde(rename = "validation.bill_is_requested_to_pay_and_waiting_for_payment")]
+    BillIsRequestedToPayAndWaitingForPayment,
+    #[serde(rename = "validation.bill_is_offered_to_sell_and_waiting_for_payment")]
+    BillIsOfferedToSellAndWaitingForPayment,
+    #[serde(rename = "validation.bill_is_in_recourse_and_waiting_for_payment")]
+    BillIsInRecourseAndWaitingForPayment,
+    #[serde(rename = "validation.bill_was_requested_to_pay")]
+    BillWasRequestedToPay,
+    #[serde(rename = "validation.bill_requested_to_pay_before_maturity_date")]
+    BillRequestedToPayBeforeMaturityDate,
+    #[serde(rename = "validation.drawer_is_not_bill_issuer")]
+    DrawerIsNotBillIssuer,
+    #[serde(rename = "validation.signatory_not_in_contacts")]
+    SignatoryNotInContacts,
+    #[serde(rename = "validation.signatory_already_signatory")]
+    SignatoryAlreadySignatory,
+    #[serde(rename = "validation.cant_remove_last_signatory")]
+    CantRemoveLastSignatory,
+    #[serde(rename = "validation.not_a_signatory")]
+    NotASignatory,
+    #[serde(rename = "validation.signatory_cant_sign")]
+    SignatoryCantSign,
+    #[serde(rename = "validation.invalid_secp256k1_key")]
+    InvalidSecp256k1Key,
+    #[serde(rename = "validation.file_is_too_big")]
+    FileIsTooBig,
+    #[serde(rename = "validation.too_many_files")]
+    TooManyFiles,
+    #[serde(rename = "validation.attachments_too_big")]
+    AttachmentsTooBig,
+    #[serde(rename = "validation.invalid_file_name")]
+    InvalidFileName,
+    #[serde(rename = "validation.reject_reason_too_long")]
+    RejectReasonTooLong,
+    #[serde(rename = "validation.file_already_attached")]
+    FileAlreadyAttached,
+    #[serde(rename = "validation.unknown_node_id")]
+    UnknownNodeId,
+    #[serde(rename = "validation.backup_not_supported")]
+    BackupNotSupported,
+    #[serde(rename = "validation.unknown_bill_bundle_version")]
+    UnknownBillBundleVersion,
+    #[serde(rename = "validation.invalid_bill_bundle")]
+    InvalidBillBundle,
+    #[serde(rename = "validation.invalid_bill_view")]
+    InvalidBillView,
+    #[serde(rename = "validation.bill_is_read_only_view")]
+    BillIsReadOnlyView,
+    #[serde(rename = "validation.unknown_identity_backup_version")]
+    UnknownIdentityBackupVersion,
+    #[serde(rename = "validation.invalid_identity_backup")]
+    InvalidIdentityBackup,
+    #[serde(rename = "validation.identity_already_exists")]
+    IdentityAlreadyExists,
+    #[serde(rename = "validation.caller_must_be_signatory")]
+    CallerMustBeSignatory,
+}
+
+impl From<&JsErrorType> for JsErrorCode {
+    fn from(value: &JsErrorType) -> Self {
+        match value {
+            JsErrorType::FieldEmpty => JsErrorCode::FieldEmpty,
+            JsErrorType::InvalidSum => JsErrorCode::InvalidSum,
+            JsErrorType::InvalidPaymentDeadline => JsErrorCode::InvalidPaymentDeadline,
+            JsErrorType::InvalidCurrency => JsErrorCode::InvalidCurrency,
+            JsErrorType::InvalidCountryCode => JsErrorCode::InvalidCountryCode,
+            JsErrorType::FiatRateUnavailable => JsErrorCode::FiatRateUnavailable,
+            JsErrorType::InvalidPaymentAddress => JsErrorCode::InvalidPaymentAddress,
+            JsErrorType::InvalidContentType => JsErrorCode::InvalidContentType,
+            JsErrorType::InvalidContactType => JsErrorCode::InvalidContactType,
+            JsErrorType::ContactsHaveDifferentNodeIds => JsErrorCode::ContactsHaveDifferentNodeIds,
+            JsErrorType::MissingCsvColumn => JsErrorCode::MissingCsvColumn,
+            JsErrorType::InvalidDate => JsErrorCode::InvalidDate,
+            JsErrorType::IssueDateAfterMaturityDate => JsErrorCode::IssueDateAfterMaturityDate,
+            JsErrorType::MaturityDateInThePast => JsErrorCode::MaturityDateInThePast,
+            JsErrorType::InvalidFileUploadId => JsErrorCode::InvalidFileUploadId,
+            JsErrorType::InvalidNotificationCursor => JsErrorCode::InvalidNotificationCursor,
+            JsErrorType::InvalidBillType => JsErrorCode::InvalidBillType,
+            JsErrorType::DraweeCantBePayee => JsErrorCode::DraweeCantBePayee,
+            JsErrorType::EndorserCantBeEndorsee => JsErrorCode::EndorserCantBeEndorsee,
+            JsErrorType::BuyerCantBeSeller => JsErrorCode::BuyerCantBeSeller,
+            JsErrorType::RecourserCantBeRecoursee => JsErrorCode::RecourserCantBeRecoursee,
+            JsErrorType::DraweeNotInContacts => JsErrorCode::DraweeNotInContacts,
+            JsErrorType::PayeeNotInContacts => JsErrorCode::PayeeNotInContacts,
+            JsErrorType::MintNotInContacts => JsErrorCode::MintNotInContacts,
+            JsErrorType::BuyerNotInContacts => JsErrorCode::BuyerNotInContacts,
+            JsErrorType::EndorseeNotInContacts => JsErrorCode::EndorseeNotInContacts,
+            JsErrorType::RecourseeNotInContacts => JsErrorCode::RecourseeNotInContacts,
+            JsErrorType::NoFileForFileUploadId => JsErrorCode::NoFileForFileUploadId,
+            JsErrorType::NotFound => JsErrorCode::NotFound,
+            JsErrorType::ExternalApi => JsErrorCode::ExternalApi,
+            JsErrorType::Io => JsErrorCode::Io,
+            JsErrorType::Crypto => JsErrorCode::Crypto,
+            JsErrorType::Persistence => JsErrorCode::Persistence,
+            JsErrorType::Blockchain => JsErrorCode::Blockchain,
+            JsErrorType::Serialization => JsErrorCode::Serialization,
+            JsErrorType::Init => JsErrorCode::Init,
+            JsErrorType::NotificationNetwork => JsErrorCode::NotificationNetwork,
+            JsErrorType::NotificationMessage => JsErrorCode::NotificationMessage,
+            JsErrorType::InvalidOperation => JsErrorCode::InvalidOperation,
+            JsErrorType::BillAlreadyAccepted => JsErrorCode::BillAlreadyAccepted,
+            JsErrorType::BillWasRejectedToAccept => JsErrorCode::BillWasRejectedToAccept,
+            JsErrorType::BillAcceptanceExpired => JsErrorCode::BillAcceptanceExpired,
+            JsErrorType::BillWasRejectedToPay => JsErrorCode::BillWasRejectedToPay,
+            JsErrorType::BillPaymentExpired => JsErrorCode::BillPaymentExpired,
+            JsErrorType::BillWasRejectedToRecourse => JsErrorCode::BillWasRejectedToRecourse,
+            JsErrorType::BillRequestToRecourseExpired => JsErrorCode::BillRequestToRecourseExpired,
+            JsErrorType::BillWasRecoursedToTheEnd => JsErrorCode::BillWasRecoursedToTheEnd,
+            JsErrorType::BillAlreadyRequestedToAccept => JsErrorCode::BillAlreadyRequestedToAccept,
+            JsErrorType::BillNotAccepted => JsErrorCode::BillNotAccepted,
+            JsErrorType::CallerIsNotDrawee => JsErrorCode::CallerIsNotDrawee,
+            JsErrorType::CallerIsNotHolder => JsErrorCode::CallerIsNotHolder,
+            JsErrorType::CallerIsNotRecoursee => JsErrorCode::CallerIsNotRecoursee,
+            JsErrorType::CallerIsNotBuyer => JsErrorCode::CallerIsNotBuyer,
+            JsErrorType::CallerIsNotSeller => JsErrorCode::CallerIsNotSeller,
+            JsErrorType::CallerIsNotRequester => JsErrorCode::CallerIsNotRequester,
+            JsErrorType::CallerIsNotHolderOrDrawee => JsErrorCode::CallerIsNotHolderOrDrawee,
+            JsErrorType::IssuePolicyViolation => JsErrorCode::IssuePolicyViolation,
+            JsErrorType::InvalidBillQrPayload => JsErrorCode::InvalidBillQrPayload,
+            JsErrorType::CallerIsNotDrawer => JsErrorCode::CallerIsNotDrawer,
+            JsErrorType::DraweeCorrectionNotAllowed => JsErrorCode::DraweeCorrectionNotAllowed,
+            JsErrorType::BillWasNotRequestedToMint => JsErrorCode::BillWasNotRequestedToMint,
+            JsErrorType::CallerIsNotMint => JsErrorCode::CallerIsNotMint,
+            JsErrorType::BillHasNoOpenMintQuote => JsErrorCode::BillHasNoOpenMintQuote,
+            JsErrorType::CallerIsNotMintRequester => JsErrorCode::CallerIsNotMintRequester,
+            JsErrorType::MintQuoteExpired => JsErrorCode::MintQuoteExpired,
+            JsErrorType::RequestAlreadyExpired => JsErrorCode::RequestAlreadyExpired,
+            JsErrorType::RequestAlreadyRejected => JsErrorCode::RequestAlreadyRejected,
+            JsErrorType::BillAlreadyPaid => JsErrorCode::BillAlreadyPaid,
+            JsErrorType::BillWasNotRequestedToAccept => JsErrorCode::BillWasNotRequestedToAccept,
+            JsErrorType::BillWasNotRequestedToPay => JsErrorCode::BillWasNotRequestedToPay,
+            JsErrorType::BillWasNotOfferedToSell => JsErrorCode::BillWasNotOfferedToSell,
+            JsErrorType::BillRequestToAcceptDidNotExpireAndWasNotRejected => {
+                JsErrorCode::BillRequestToAcceptDidNotExpireAndWasNotRejected
+            }
+            JsErrorType::BillRequestToPayDidNotExpireAndWasNotRejected => {
+                JsErrorCode::BillRequestToPayDidNotExpireAndWasNotRejected
+            }
+            JsErrorType::RecourseeNotPastHolder => JsErrorCode::RecourseeNotPastHolder,
+            JsErrorType::BillWasNotRequestedToRecourse => {
+                JsErrorCode::BillWasNotRequestedToRecourse
+            }
+            JsErrorType::BillIsNotRequestedToRecourseAndWaitingForPayment => {
+                JsErrorCode::BillIsNotRequestedToRecourseAndWaitingForPayment
+            }
+            JsErrorType::BillIsNotOfferToSellWaitingForPayment => {
+                JsErrorCode::BillIsNotOfferToSellWaitingForPayment
+            }
+            JsErrorType::BillSellDataInvalid => JsErrorCode::BillSellDataInvalid,
+            JsErrorType::BillRecourseDataInvalid => JsErrorCode::BillRecourseDataInvalid,
+            JsErrorType::BillIsRequestedToPayAndWaitingForPayment => {
+                JsErrorCode::BillIsRequestedToPayAndWaitingForPayment
+            }
+            JsErrorType::BillIsOfferedToSellAndWaitingForPayment => {
+                JsErrorCode::BillIsOfferedToSellAndWaitingForPayment
+            }
+            JsErrorType::BillIsInRecourseAndWaitingForPayment => {
+                JsErrorCode::BillIsInRecourseAndWaitingForPayment
+            }
+            JsErrorType::BillWasRequestedToPay => JsErrorCode::BillWasRequestedToPay,
+            JsErrorType::BillRequestedToPayBeforeMaturityDate => {
+                JsErrorCode::BillRequestedToPayBeforeMaturityDate
+            }
+            JsErrorType::DrawerIsNotBillIssuer => JsErrorCode::DrawerIsNotBillIssuer,
+            JsErrorType::SignatoryNotInContacts => JsErrorCode::SignatoryNotInContacts,
+            JsErrorType::SignatoryAlreadySignatory => JsErrorCode::SignatoryAlreadySignatory,
+            JsErrorType::CantRemoveLastSignatory => JsErrorCode::CantRemoveLastSignatory,
+            JsErrorType::NotASignatory => JsErrorCode::NotASignatory,
+            JsErrorType::SignatoryCantSign => JsErrorCode::SignatoryCantSign,
+            JsErrorType::InvalidSecp256k1Key => JsErrorCode::InvalidSecp256k1Key,
+            JsErrorType::FileIsTooBig => JsErrorCode::FileIsTooBig,
+            JsErrorType::TooManyFiles => JsErrorCode::TooManyFiles,
+            JsErrorType::AttachmentsTooBig => JsErrorCode::AttachmentsTooBig,
+            JsErrorType::InvalidFileName => JsErrorCode::InvalidFileName,
+            JsErrorType::RejectReasonTooLong => JsErrorCode::RejectReasonTooLong,
+            JsErrorType::FileAlreadyAttached => JsErrorCode::FileAlreadyAttached,
+            JsErrorType::UnknownNodeId => JsErrorCode::UnknownNodeId,
+            JsErrorType::BackupNotSupported => JsErrorCode::BackupNotSupported,
+            JsErrorType::UnknownBillBundleVersion => JsErrorCode::UnknownBillBundleVersion,
+            JsErrorType::InvalidBillBundle => JsErrorCode::InvalidBillBundle,
+            JsErrorType::InvalidBillView => JsErrorCode::InvalidBillView,
+            JsErrorType::BillIsReadOnlyView => JsErrorCode::BillIsReadOnlyView,
+            JsErrorType::UnknownIdentityBackupVersion => JsErrorCode::UnknownIdentityBackupVersion,
+            JsErrorType::InvalidIdentityBackup => JsErrorCode::InvalidIdentityBackup,
+            JsErrorType::IdentityAlreadyExists => JsErrorCode::IdentityAlreadyExists,
+            JsErrorType::CallerMustBeSignatory => JsErrorCode::CallerMustBeSignatory,
+        }
+    }
+}
+
 #[derive(Tsify, Debug, Clone, Serialize)]
 #[tsify(into_wasm_abi)]
 struct JsErrorData {
     error: JsErrorType,
+    code: JsErrorCode,
+    /// The human-readable message for display
     message: String,
-    code: u16,
+    /// The HTTP-like status of the error, e.g. 400 for validation errors
+    status: u16,
 }
 impl From<WasmError> for JsValue {
     fn from(error: WasmError) -> JsValue {
@@ -191,9 +577,16 @@ fn validation_error_data(e: ValidationError) -> JsErrorData {
     match e {
         ValidationError::FieldEmpty(_) => err_400(e, JsErrorType::FieldEmpty),
         ValidationError::InvalidSum => err_400(e, JsErrorType::InvalidSum),
+        ValidationError::InvalidPaymentDeadline => err_400(e, JsErrorType::InvalidPaymentDeadline),
         ValidationError::InvalidCurrency => err_400(e, JsErrorType::InvalidCurrency),
+        ValidationError::InvalidCountryCode(_) => err_400(e, JsErrorType::InvalidCountryCode),
+        ValidationError::FiatRateUnavailable => err_400(e, JsErrorType::FiatRateUnavailable),
         ValidationError::InvalidPaymentAddress => err_400(e, JsErrorType::InvalidPaymentAddress),
         ValidationError::InvalidContactType => err_400(e, JsErrorType::InvalidContactType),
+        ValidationError::ContactsHaveDifferentNodeIds => {
+            err_400(e, JsErrorType::ContactsHaveDifferentNodeIds)
+        }
+        ValidationError::MissingCsvColumn(_) => err_400(e, JsErrorType::MissingCsvColumn),
         ValidationError::InvalidContentType => err_400(e, JsErrorType::InvalidContentType),
         ValidationError::InvalidDate => err_400(e, JsErrorType::InvalidDate),
         ValidationError::MaturityDateInThePast => err_400(e, JsErrorType::MaturityDateInThePast),
@@ -201,6 +594,9 @@ fn validation_error_data(e: ValidationError) -> JsErrorData {
             err_400(e, JsErrorType::IssueDateAfterMaturityDate)
         }
         ValidationError::InvalidFileUploadId => err_400(e, JsErrorType::InvalidFileUploadId),
+        ValidationError::InvalidNotificationCursor => {
+            err_400(e, JsErrorType::InvalidNotificationCursor)
+        }
         ValidationError::InvalidBillType => err_400(e, JsErrorType::InvalidBillType),
         ValidationError::DraweeCantBePayee => err_400(e, JsErrorType::DraweeCantBePayee),
         ValidationError::EndorserCantBeEndorsee => err_400(e, JsErrorType::EndorserCantBeEndorsee),
@@ -277,6 +673,26 @@ fn validation_error_data(e: ValidationError) -> JsErrorData {
         ValidationError::RecourseeNotPastHolder => err_400(e, JsErrorType::RecourseeNotPastHolder),
         ValidationError::CallerIsNotDrawee => err_400(e, JsErrorType::CallerIsNotDrawee),
         ValidationError::CallerIsNotBuyer => err_400(e, JsErrorType::CallerIsNotBuyer),
+        ValidationError::CallerIsNotSeller => err_400(e, JsErrorType::CallerIsNotSeller),
+        ValidationError::CallerIsNotRequester => err_400(e, JsErrorType::CallerIsNotRequester),
+        ValidationError::CallerIsNotHolderOrDrawee => {
+            err_400(e, JsErrorType::CallerIsNotHolderOrDrawee)
+        }
+        ValidationError::IssuePolicyViolation(_) => err_400(e, JsErrorType::IssuePolicyViolation),
+        ValidationError::InvalidBillQrPayload => err_400(e, JsErrorType::InvalidBillQrPayload),
+        ValidationError::CallerIsNotDrawer => err_400(e, JsErrorType::CallerIsNotDrawer),
+        ValidationError::DraweeCorrectionNotAllowed => {
+            err_400(e, JsErrorType::DraweeCorrectionNotAllowed)
+        }
+        ValidationError::BillWasNotRequestedToMint => {
+            err_400(e, JsErrorType::BillWasNotRequestedToMint)
+        }
+        ValidationError::CallerIsNotMint => err_400(e, JsErrorType::CallerIsNotMint),
+        ValidationError::BillHasNoOpenMintQuote => err_400(e, JsErrorType::BillHasNoOpenMintQuote),
+        ValidationError::CallerIsNotMintRequester => {
+            err_400(e, JsErrorType::CallerIsNotMintRequester)
+        }
+        ValidationError::MintQuoteExpired => err_400(e, JsErrorType::MintQuoteExpired),
         ValidationError::CallerIsNotRecoursee => err_400(e, JsErrorType::CallerIsNotRecoursee),
         ValidationError::RequestAlreadyRejected => err_400(e, JsErrorType::RequestAlreadyRejected),
         ValidationError::CallerIsNotHolder => err_400(e, JsErrorType::CallerIsNotHolder),
@@ -292,35 +708,54 @@ fn validation_error_data(e: ValidationError) -> JsErrorData {
             err_400(e, JsErrorType::CantRemoveLastSignatory)
         }
         ValidationError::NotASignatory(_) => err_400(e, JsErrorType::NotASignatory),
+        ValidationError::SignatoryCantSign(_) => err_400(e, JsErrorType::SignatoryCantSign),
         ValidationError::InvalidSecp256k1Key(_) => err_400(e, JsErrorType::InvalidSecp256k1Key),
         ValidationError::FileIsTooBig(_) => err_400(e, JsErrorType::FileIsTooBig),
+        ValidationError::TooManyFiles(_) => err_400(e, JsErrorType::TooManyFiles),
+        ValidationError::AttachmentsTooBig(_) => err_400(e, JsErrorType::AttachmentsTooBig),
         ValidationError::InvalidFileName(_) => err_400(e, JsErrorType::InvalidFileName),
+        ValidationError::RejectReasonTooLong(_) => err_400(e, JsErrorType::RejectReasonTooLong),
+        ValidationError::FileAlreadyAttached(_) => err_400(e, JsErrorType::FileAlreadyAttached),
         ValidationError::UnknownNodeId(_) => err_400(e, JsErrorType::UnknownNodeId),
         ValidationError::BackupNotSupported => err_400(e, JsErrorType::BackupNotSupported),
+        ValidationError::UnknownBillBundleVersion(_) => {
+            err_400(e, JsErrorType::UnknownBillBundleVersion)
+        }
+        ValidationError::InvalidBillBundle => err_400(e, JsErrorType::InvalidBillBundle),
+        ValidationError::InvalidBillView => err_400(e, JsErrorType::InvalidBillView),
+        ValidationError::BillIsReadOnlyView => err_400(e, JsErrorType::BillIsReadOnlyView),
+        ValidationError::UnknownIdentityBackupVersion(_) => {
+            err_400(e, JsErrorType::UnknownIdentityBackupVersion)
+        }
+        ValidationError::InvalidIdentityBackup => err_400(e, JsErrorType::InvalidIdentityBackup),
+        ValidationError::IdentityAlreadyExists => err_400(e, JsErrorType::IdentityAlreadyExists),
         ValidationError::Blockchain(e) => err_500(e, JsErrorType::Blockchain),
     }
 }
 
 fn err_400<E: ToString>(e: E, t: JsErrorType) -> JsErrorData {
     JsErrorData {
+        code: JsErrorCode::from(&t),
         error: t,
         message: e.to_string(),
-        code: 400,
+        status: 400,
     }
 }
 
 fn err_404<E: ToString>(e: E, t: JsErrorType) -> JsErrorData {
     JsErrorData {
+        code: JsErrorCode::from(&t),
         error: t,
         message: e.to_string(),
-        code: 404,
+        status: 404,
     }
 }
 
 fn err_500<E: ToString>(e: E, t: JsErrorType) -> JsErrorData {
     JsErrorData {
+        code: JsErrorCode::from(&t),
         error: t,
         message: e.to_string(),
-        code: 500,
+        status: 500,
     }
 }
@@ -1,16 +1,28 @@
-use bcr_ebill_api::util::date::now;
+use bcr_ebill_api::util::{date::now, job::JobSchedule};
+use futures::FutureExt;
 use log::{error, info};
+use std::time::Duration;
 
 use crate::context::get_ctx;
 
 pub fn run_jobs() {
     wasm_bindgen_futures::spawn_local(async {
-        futures::join!(
-            run_check_bill_payment_job(),
-            run_check_bill_offer_to_sell_payment_job(),
-            run_check_bill_recourse_payment_job(),
-            run_process_nostr_message_queue_job(),
+        let cfg = &get_ctx().cfg;
+        let schedule = JobSchedule::new(
+            Duration::from_secs(cfg.job_runner_stagger_seconds),
+            cfg.job_runner_max_concurrent_jobs,
         );
+        schedule
+            .run(vec![
+                run_check_bill_payment_job().boxed_local(),
+                run_check_bill_offer_to_sell_payment_job().boxed_local(),
+                run_check_bill_recourse_payment_job().boxed_local(),
+                run_process_nostr_message_queue_job().boxed_local(),
+                run_cleanup_stale_temp_uploads_job().boxed_local(),
+                run_check_bill_maturity_reminders_job().boxed_local(),
+                run_process_auto_accepts_job().boxed_local(),
+            ])
+            .await;
         run_check_bill_timeouts().await;
     });
 }
@@ -47,6 +59,35 @@ async fn run_check_bill_recourse_payment_job() {
     info!("Finished running Check Bill Recourse Payment Job");
 }
 
+async fn run_check_bill_maturity_reminders_job() {
+    info!("Running Check Bill Maturity Reminders Job");
+    let current_time = now().timestamp();
+    if let Err(e) = get_ctx()
+        .bill_service
+        .check_bills_maturity_reminders(
+            current_time as u64,
+            get_ctx().cfg.maturity_reminder_lead_time_seconds,
+        )
+        .await
+    {
+        error!("Error while running Check Bill Maturity Reminders Job: {e}");
+    }
+    info!("Finished running Check Bill Maturity Reminders Job");
+}
+
+async fn run_process_auto_accepts_job() {
+    info!("Running Process Auto Accepts Job");
+    let current_time = now().timestamp();
+    if let Err(e) = get_ctx()
+        .bill_service
+        .process_auto_accepts(current_time as u64)
+        .await
+    {
+        error!("Error while running Process Auto Accepts Job: {e}");
+    }
+    info!("Finished running Process Auto Accepts Job");
+}
+
 async fn run_check_bill_timeouts() {
     info!("Running Check Bill Timeouts Job");
     let current_time = now().timestamp();
@@ -68,3 +109,15 @@ async fn run_process_nostr_message_queue_job() {
     }
     info!("Finished running process Nostr message queue Job");
 }
+
+async fn run_cleanup_stale_temp_uploads_job() {
+    info!("Running Cleanup Stale Temp Uploads Job");
+    if let Err(e) = get_ctx()
+        .file_upload_service
+        .cleanup_stale_temp_uploads(get_ctx().cfg.temp_upload_ttl_seconds)
+        .await
+    {
+        error!("Error while running Cleanup Stale Temp Uploads Job: {e}");
+    }
+    info!("Finished running Cleanup Stale Temp Uploads Job");
+}
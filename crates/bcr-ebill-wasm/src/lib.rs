@@ -1,6 +1,24 @@
 #![allow(clippy::arc_with_non_send_sync)]
 use api::general::VERSION;
-use bcr_ebill_api::{Config as ApiConfig, get_db_context, init};
+use bcr_ebill_api::{
+    Config as ApiConfig,
+    constants::{
+        DEFAULT_BILL_CACHE_MAX_SIZE, DEFAULT_BITCOIN_EXPLORER_CONNECT_TIMEOUT_SECONDS,
+        DEFAULT_BITCOIN_EXPLORER_MAX_RETRIES, DEFAULT_BITCOIN_EXPLORER_READ_TIMEOUT_SECONDS,
+        DEFAULT_FEE_ESTIMATOR_URL, DEFAULT_FIAT_RATE_PROVIDER_URL,
+        DEFAULT_JOB_RUNNER_BILLS_PER_TICK, DEFAULT_JOB_RUNNER_MAX_CONCURRENT_JOBS,
+        DEFAULT_JOB_RUNNER_STAGGER_SECONDS, DEFAULT_MATURITY_REMINDER_LEAD_TIME_SECONDS,
+        DEFAULT_MAX_BILL_CHAIN_BLOCKS, DEFAULT_MAX_FILES_PER_BILL,
+        DEFAULT_MAX_TOTAL_ATTACHMENT_BYTES, DEFAULT_NOSTR_BILL_EVENT_KIND,
+        DEFAULT_NOSTR_COMPANY_EVENT_KIND, DEFAULT_NOSTR_EVENT_TIMEOUT_SECONDS,
+        DEFAULT_NOSTR_IDENTITY_EVENT_KIND, DEFAULT_NOSTR_MAX_CONCURRENT_EVENTS,
+        DEFAULT_NOSTR_MAX_RETRIES, DEFAULT_NOSTR_MAX_RETRY_DELAY_SECONDS,
+        DEFAULT_PAYMENT_CONFIRMATIONS_REQUIRED, DEFAULT_REQUEST_TIMEOUT_WARNING_SECONDS,
+        DEFAULT_TEMP_UPLOAD_TTL_SECONDS,
+    },
+    external::rate::HttpRateProvider,
+    get_db_context, init,
+};
 use constants::SURREAL_DB_CON_INDXDB_DATA;
 use context::{Context, get_ctx};
 use futures::{StreamExt, future::ready};
@@ -9,6 +27,7 @@ use job::run_jobs;
 use log::info;
 use serde::Deserialize;
 use std::cell::RefCell;
+use std::sync::Arc;
 use std::thread_local;
 use tokio::spawn;
 use tokio_with_wasm::alias as tokio;
@@ -27,9 +46,35 @@ mod job;
 pub struct Config {
     pub log_level: Option<String>,
     pub bitcoin_network: String,
-    pub nostr_relay: String,
+    pub nostr_relays: Vec<String>,
     pub job_runner_initial_delay_seconds: u32,
     pub job_runner_check_interval_seconds: u32,
+    pub nostr_max_concurrent_events: Option<u32>,
+    pub nostr_event_timeout_seconds: Option<u32>,
+    pub nostr_max_retry_delay_seconds: Option<u32>,
+    pub nostr_max_retries: Option<u32>,
+    pub nostr_bill_event_kind: Option<u16>,
+    pub nostr_company_event_kind: Option<u16>,
+    pub nostr_identity_event_kind: Option<u16>,
+    pub fiat_rate_provider_url: Option<String>,
+    pub fee_estimator_url: Option<String>,
+    pub bitcoin_explorer_max_retries: Option<u32>,
+    pub webhook_urls: Option<Vec<String>>,
+    pub temp_upload_ttl_seconds: Option<u32>,
+    pub encrypt_temp_uploads: Option<bool>,
+    pub max_bill_chain_blocks: Option<u32>,
+    pub max_files_per_bill: Option<u32>,
+    pub max_total_attachment_bytes: Option<u32>,
+    pub sell_deadline_seconds: Option<u32>,
+    pub bill_cache_max_size: Option<u32>,
+    pub maturity_reminder_lead_time_seconds: Option<u32>,
+    pub payment_confirmations_required: Option<u32>,
+    pub mempool_base_url: Option<String>,
+    pub request_timeout_warning_seconds: Option<u32>,
+    pub job_runner_stagger_seconds: Option<u32>,
+    pub job_runner_max_concurrent_jobs: Option<u32>,
+    pub job_runner_bills_per_tick: Option<u32>,
+    pub auto_accept_enabled: Option<bool>,
 }
 
 pub type Result<T> = std::result::Result<T, error::WasmError>;
@@ -60,9 +105,96 @@ pub async fn initialize_api(
     console_log::init_with_level(log_level).expect("can initialize logging");
     let api_config = ApiConfig {
         bitcoin_network: config.bitcoin_network,
-        nostr_relay: config.nostr_relay,
+        nostr_relays: config.nostr_relays,
         surreal_db_connection: SURREAL_DB_CON_INDXDB_DATA.to_owned(),
         data_dir: "./".to_owned(), // unused in wasm
+        nostr_max_concurrent_events: config
+            .nostr_max_concurrent_events
+            .map(|n| n as usize)
+            .unwrap_or(DEFAULT_NOSTR_MAX_CONCURRENT_EVENTS),
+        nostr_event_timeout_seconds: config
+            .nostr_event_timeout_seconds
+            .map(|s| s as u64)
+            .unwrap_or(DEFAULT_NOSTR_EVENT_TIMEOUT_SECONDS),
+        nostr_max_retry_delay_seconds: config
+            .nostr_max_retry_delay_seconds
+            .map(|s| s as u64)
+            .unwrap_or(DEFAULT_NOSTR_MAX_RETRY_DELAY_SECONDS),
+        nostr_max_retries: config
+            .nostr_max_retries
+            .map(|n| n as i32)
+            .unwrap_or(DEFAULT_NOSTR_MAX_RETRIES),
+        nostr_bill_event_kind: config
+            .nostr_bill_event_kind
+            .unwrap_or(DEFAULT_NOSTR_BILL_EVENT_KIND),
+        nostr_company_event_kind: config
+            .nostr_company_event_kind
+            .unwrap_or(DEFAULT_NOSTR_COMPANY_EVENT_KIND),
+        nostr_identity_event_kind: config
+            .nostr_identity_event_kind
+            .unwrap_or(DEFAULT_NOSTR_IDENTITY_EVENT_KIND),
+        fiat_rate_provider_url: config
+            .fiat_rate_provider_url
+            .unwrap_or(DEFAULT_FIAT_RATE_PROVIDER_URL.to_owned()),
+        fee_estimator_url: config
+            .fee_estimator_url
+            .unwrap_or(DEFAULT_FEE_ESTIMATOR_URL.to_owned()),
+        // the browser decides when a request times out
+        bitcoin_explorer_connect_timeout_seconds: DEFAULT_BITCOIN_EXPLORER_CONNECT_TIMEOUT_SECONDS,
+        bitcoin_explorer_read_timeout_seconds: DEFAULT_BITCOIN_EXPLORER_READ_TIMEOUT_SECONDS,
+        bitcoin_explorer_max_retries: config
+            .bitcoin_explorer_max_retries
+            .unwrap_or(DEFAULT_BITCOIN_EXPLORER_MAX_RETRIES),
+        webhook_urls: config.webhook_urls.unwrap_or_default(),
+        temp_upload_ttl_seconds: config
+            .temp_upload_ttl_seconds
+            .map(|s| s as u64)
+            .unwrap_or(DEFAULT_TEMP_UPLOAD_TTL_SECONDS),
+        encrypt_temp_uploads: config.encrypt_temp_uploads.unwrap_or(true),
+        file_storage_shard_prefix_length: 0, // unused in wasm
+        max_bill_chain_blocks: config
+            .max_bill_chain_blocks
+            .map(|m| m as usize)
+            .unwrap_or(DEFAULT_MAX_BILL_CHAIN_BLOCKS),
+        max_files_per_bill: config
+            .max_files_per_bill
+            .map(|m| m as usize)
+            .unwrap_or(DEFAULT_MAX_FILES_PER_BILL),
+        max_total_attachment_bytes: config
+            .max_total_attachment_bytes
+            .map(|m| m as usize)
+            .unwrap_or(DEFAULT_MAX_TOTAL_ATTACHMENT_BYTES),
+        sell_deadline_seconds: config.sell_deadline_seconds.map(|s| s as u64),
+        bill_cache_max_size: config
+            .bill_cache_max_size
+            .map(|m| m as usize)
+            .unwrap_or(DEFAULT_BILL_CACHE_MAX_SIZE),
+        maturity_reminder_lead_time_seconds: config
+            .maturity_reminder_lead_time_seconds
+            .map(|s| s as u64)
+            .unwrap_or(DEFAULT_MATURITY_REMINDER_LEAD_TIME_SECONDS),
+        payment_confirmations_required: config
+            .payment_confirmations_required
+            .map(|c| c as u64)
+            .unwrap_or(DEFAULT_PAYMENT_CONFIRMATIONS_REQUIRED),
+        mempool_base_url: config.mempool_base_url,
+        request_timeout_warning_seconds: config
+            .request_timeout_warning_seconds
+            .map(|s| s as u64)
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT_WARNING_SECONDS),
+        job_runner_stagger_seconds: config
+            .job_runner_stagger_seconds
+            .map(|s| s as u64)
+            .unwrap_or(DEFAULT_JOB_RUNNER_STAGGER_SECONDS),
+        job_runner_max_concurrent_jobs: config
+            .job_runner_max_concurrent_jobs
+            .map(|m| m as usize)
+            .unwrap_or(DEFAULT_JOB_RUNNER_MAX_CONCURRENT_JOBS),
+        job_runner_bills_per_tick: config
+            .job_runner_bills_per_tick
+            .map(|b| b as usize)
+            .unwrap_or(DEFAULT_JOB_RUNNER_BILLS_PER_TICK),
+        auto_accept_enabled: config.auto_accept_enabled.unwrap_or(true),
     };
     init(api_config.clone())?;
 
@@ -77,7 +209,7 @@ pub async fn initialize_api(
     info!("Config: {api_config:?}");
 
     // init context as static reference
-    let ctx = Context::new(api_config.clone(), db).await?;
+    let ctx = Context::new(api_config.clone(), db, Arc::new(HttpRateProvider::new())).await?;
     CONTEXT.with(|context| {
         let mut context_ref = context.borrow_mut();
         if context_ref.is_none() {
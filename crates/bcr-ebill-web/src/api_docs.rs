@@ -11,24 +11,75 @@ use utoipa::OpenApi;
     paths(
         handlers::notifications::list_notifications,
         handlers::notifications::mark_notification_done,
+        handlers::notifications::mark_all_notifications_done,
+        handlers::notifications::mark_bill_notifications_done,
+        handlers::notifications::relay_status,
         handlers::notifications::websocket,
         handlers::notifications::sse,
         handlers::bill::list,
         handlers::bill::list_light,
+        handlers::bill::list_grouped,
+        handlers::bill::list_by_participant,
+        handlers::bill::list_by_maturity,
         handlers::bill::search,
         handlers::bill::bill_detail,
+        handlers::bill::bill_waiting_state,
         handlers::bill::get_past_endorsees_for_bill,
+        handlers::bill::is_past_holder_of_bill,
         handlers::bill::get_endorsements_for_bill,
+        handlers::bill::get_bill_history,
+        handlers::bill::verify_bill_chain,
+        handlers::bill::repair_bill_chain,
+        handlers::bill::preview_notification,
+        handlers::bill::validate_bill_action,
+        handlers::bill::resend_bill_event,
+        handlers::bill::set_bill_hold,
+        handlers::bill::clear_bill_hold,
+        handlers::bill::archive_bill,
+        handlers::bill::unarchive_bill,
+        handlers::bill::set_bill_note,
+        handlers::bill::list_auto_accept_rules,
+        handlers::bill::add_auto_accept_rule,
+        handlers::bill::remove_auto_accept_rule,
+        handlers::bill::export_bundle,
+        handlers::bill::import_bundle,
+        handlers::bill::issue_bill,
+        handlers::bill::offer_to_sell_bill,
+        handlers::bill::endorse_bill,
+        handlers::bill::request_to_pay_bill,
+        handlers::bill::request_to_accept_and_pay_bill,
+        handlers::bill::attach_files,
+        handlers::bill::request_to_accept_bill,
+        handlers::bill::accept_bill,
+        handlers::bill::request_to_mint_bill,
+        handlers::bill::mint_bill,
+        handlers::bill::reject_to_accept_bill,
+        handlers::bill::reject_to_pay_bill,
+        handlers::bill::reject_to_buy_bill,
+        handlers::bill::cancel_offer_to_sell_bill,
+        handlers::bill::cancel_request_to_accept_bill,
+        handlers::bill::mark_paid_externally_bill,
+        handlers::bill::acknowledge_bill,
+        handlers::bill::correct_drawee_bill,
+        handlers::bill::reject_to_pay_recourse_bill,
+        handlers::bill::request_to_recourse_bill_payment,
+        handlers::bill::request_to_recourse_bill_acceptance,
         handlers::identity::return_identity,
         handlers::identity::create_identity,
         handlers::identity::change_identity,
         handlers::identity::active,
+        handlers::identity::controlled,
         handlers::identity::switch,
         handlers::identity::get_seed_phrase,
         handlers::identity::recover_from_seed_phrase,
+        handlers::identity::export_identity_backup,
+        handlers::identity::restore_identity_backup,
         handlers::identity::backup_identity,
         handlers::identity::restore_identity,
         handlers::search,
+        handlers::health,
+        handlers::pause_jobs,
+        handlers::resume_jobs,
     )
 )]
 pub struct ApiDocs;
@@ -16,14 +16,89 @@ pub struct Config {
     pub surreal_db_connection: String,
     #[arg(default_value_t = String::from("testnet"),  long, env = "BITCOIN_NETWORK")]
     pub bitcoin_network: String,
-    #[arg(default_value_t = String::from("ws://localhost:8080"), long, env = "NOSTR_RELAY")]
-    pub nostr_relay: String,
+    #[arg(
+        default_value = "ws://localhost:8080",
+        long,
+        env = "NOSTR_RELAYS",
+        value_delimiter = ','
+    )]
+    pub nostr_relays: Vec<String>,
+    #[arg(default_value_t = 10, long, env = "NOSTR_MAX_CONCURRENT_EVENTS")]
+    pub nostr_max_concurrent_events: usize,
+    #[arg(default_value_t = 30, long, env = "NOSTR_EVENT_TIMEOUT_SECONDS")]
+    pub nostr_event_timeout_seconds: u64,
+    #[arg(default_value_t = 3600, long, env = "NOSTR_MAX_RETRY_DELAY_SECONDS")]
+    pub nostr_max_retry_delay_seconds: u64,
+    #[arg(default_value_t = 10, long, env = "NOSTR_MAX_RETRIES")]
+    pub nostr_max_retries: i32,
+    #[arg(default_value_t = 4, long, env = "NOSTR_BILL_EVENT_KIND")]
+    pub nostr_bill_event_kind: u16,
+    #[arg(default_value_t = 4, long, env = "NOSTR_COMPANY_EVENT_KIND")]
+    pub nostr_company_event_kind: u16,
+    #[arg(default_value_t = 4, long, env = "NOSTR_IDENTITY_EVENT_KIND")]
+    pub nostr_identity_event_kind: u16,
+    #[arg(default_value_t = String::from("https://api.coingecko.com/api/v3/simple/price"), long, env = "FIAT_RATE_PROVIDER_URL")]
+    pub fiat_rate_provider_url: String,
+    #[arg(default_value_t = String::from("https://mempool.space/api/v1/fees/recommended"), long, env = "FEE_ESTIMATOR_URL")]
+    pub fee_estimator_url: String,
+    #[arg(
+        default_value_t = 10,
+        long,
+        env = "BITCOIN_EXPLORER_CONNECT_TIMEOUT_SECONDS"
+    )]
+    pub bitcoin_explorer_connect_timeout_seconds: u64,
+    #[arg(
+        default_value_t = 30,
+        long,
+        env = "BITCOIN_EXPLORER_READ_TIMEOUT_SECONDS"
+    )]
+    pub bitcoin_explorer_read_timeout_seconds: u64,
+    #[arg(default_value_t = 2, long, env = "BITCOIN_EXPLORER_MAX_RETRIES")]
+    pub bitcoin_explorer_max_retries: u32,
+    #[arg(long, env = "MEMPOOL_BASE_URL")]
+    pub mempool_base_url: Option<String>,
+    #[arg(long, env = "WEBHOOK_URLS", value_delimiter = ',')]
+    pub webhook_urls: Vec<String>,
+    #[arg(default_value_t = 86400, long, env = "TEMP_UPLOAD_TTL_SECONDS")]
+    pub temp_upload_ttl_seconds: u64,
+    #[arg(default_value_t = true, long, env = "ENCRYPT_TEMP_UPLOADS", action = clap::ArgAction::Set)]
+    pub encrypt_temp_uploads: bool,
+    #[arg(default_value_t = 2, long, env = "FILE_STORAGE_SHARD_PREFIX_LENGTH")]
+    pub file_storage_shard_prefix_length: usize,
+    #[arg(default_value_t = 10000, long, env = "MAX_BILL_CHAIN_BLOCKS")]
+    pub max_bill_chain_blocks: usize,
+    #[arg(default_value_t = 100, long, env = "MAX_FILES_PER_BILL")]
+    pub max_files_per_bill: usize,
+    #[arg(default_value_t = 100000000, long, env = "MAX_TOTAL_ATTACHMENT_BYTES")]
+    pub max_total_attachment_bytes: usize,
+    #[arg(long, env = "SELL_DEADLINE_SECONDS")]
+    pub sell_deadline_seconds: Option<u64>,
+    #[arg(default_value_t = 1000, long, env = "BILL_CACHE_MAX_SIZE")]
+    pub bill_cache_max_size: usize,
+    #[arg(
+        default_value_t = 259200,
+        long,
+        env = "MATURITY_REMINDER_LEAD_TIME_SECONDS"
+    )]
+    pub maturity_reminder_lead_time_seconds: u64,
+    #[arg(default_value_t = 1, long, env = "PAYMENT_CONFIRMATIONS_REQUIRED")]
+    pub payment_confirmations_required: u64,
+    #[arg(default_value_t = 43200, long, env = "REQUEST_TIMEOUT_WARNING_SECONDS")]
+    pub request_timeout_warning_seconds: u64,
     #[arg(default_value_t = String::from("https://moksha.minibill.tech"), long, env = "MINT_URL")]
     pub mint_url: String,
     #[arg(default_value_t = 1, long, env = "JOB_RUNNER_INITIAL_DELAY_SECONDS")]
     pub job_runner_initial_delay_seconds: u64,
     #[arg(default_value_t = 600, long, env = "JOB_RUNNER_CHECK_INTERVAL_SECONDS")]
     pub job_runner_check_interval_seconds: u64,
+    #[arg(default_value_t = 0, long, env = "JOB_RUNNER_STAGGER_SECONDS")]
+    pub job_runner_stagger_seconds: u64,
+    #[arg(default_value_t = 0, long, env = "JOB_RUNNER_MAX_CONCURRENT_JOBS")]
+    pub job_runner_max_concurrent_jobs: usize,
+    #[arg(default_value_t = 0, long, env = "JOB_RUNNER_BILLS_PER_TICK")]
+    pub job_runner_bills_per_tick: usize,
+    #[arg(default_value_t = true, long, env = "AUTO_ACCEPT_ENABLED", action = clap::ArgAction::Set)]
+    pub auto_accept_enabled: bool,
     #[arg(default_value_t = String::from("frontend"), long, env = "FRONTEND_SERVE_FOLDER")]
     pub frontend_serve_folder: String,
     #[arg(default_value_t = String::from("/"), long, env = "FRONTEND_URL_PATH")]
@@ -1,28 +1,34 @@
 use async_trait::async_trait;
 use bcr_ebill_api::service::Error;
+use bcr_ebill_api::service::contact_service::RefreshedContact;
+use bcr_ebill_api::service::health_service::{DependencyStatus, HealthReport};
 use bcr_ebill_api::util::file::{UploadFileHandler, detect_content_type_for_bytes};
 use bcr_ebill_api::util::{BcrKeys, date::DateTimeUtc};
 use bcr_ebill_api::{
+    BillOpCode, BlockValidationError, ChainVerificationReport, NotificationPage, SignatoryChange,
+    SignatoryChangeType,
     data::{
         File, GeneralSearchFilterItemType, GeneralSearchResult, OptionalPostalAddress,
         PostalAddress, UploadFileResult,
         bill::{
-            BillAcceptanceStatus, BillCombinedBitcoinKey, BillCurrentWaitingState, BillData,
+            BillAcceptanceStatus, BillAcknowledgement, BillAutoAcceptRule, BillCombinedBitcoinKey,
+            BillCurrentWaitingState, BillData, BillFiatAmount, BillHistoryEntry, BillHold,
             BillParticipants, BillPaymentStatus, BillRecourseStatus, BillSellStatus, BillStatus,
             BillWaitingForPaymentState, BillWaitingForRecourseState, BillWaitingForSellState,
-            BillsFilterRole, BitcreditBillResult, Endorsement, LightBitcreditBillResult,
-            LightSignedBy, PastEndorsee,
+            BillsFilterRole, BillsFilterStatus, BitcreditBillResult, Endorsement, FeeEstimate,
+            LightBitcreditBillResult, LightSignedBy, PastEndorsee, SettlementStatus,
         },
         company::Company,
         contact::{
             Contact, ContactType, IdentityPublicData, LightIdentityPublicData,
             LightIdentityPublicDataWithAddress,
         },
-        identity::{Identity, IdentityType},
-        notification::{Notification, NotificationType},
+        identity::{ControlledIdentity, Identity, IdentityType},
+        notification::{ActionType, Notification, NotificationPreview, NotificationType},
     },
     util::ValidationError,
 };
+use bcr_ebill_transport::RelayStatus;
 use rocket::FromForm;
 use rocket::fs::TempFile;
 use serde::{Deserialize, Serialize};
@@ -44,6 +50,40 @@ pub struct StatusResponse {
     pub app_version: String,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthResponse {
+    pub ready: bool,
+    pub database: DependencyStatusWeb,
+    pub relays: Vec<DependencyStatusWeb>,
+}
+
+impl IntoWeb<HealthResponse> for HealthReport {
+    fn into_web(self) -> HealthResponse {
+        HealthResponse {
+            ready: self.ready,
+            database: self.database.into_web(),
+            relays: self.relays.into_iter().map(|r| r.into_web()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DependencyStatusWeb {
+    pub name: String,
+    pub ready: bool,
+    pub error: Option<String>,
+}
+
+impl IntoWeb<DependencyStatusWeb> for DependencyStatus {
+    fn into_web(self) -> DependencyStatusWeb {
+        DependencyStatusWeb {
+            name: self.name,
+            ready: self.ready,
+            error: self.error,
+        }
+    }
+}
+
 /// A dummy response type signaling success of a request
 #[derive(Debug, Serialize, ToSchema)]
 pub struct SuccessResponse {
@@ -61,11 +101,21 @@ pub struct EndorsementsResponse {
     pub endorsements: Vec<EndorsementWeb>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BillHistoryResponse {
+    pub history: Vec<BillHistoryEntryWeb>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PastEndorseesResponse {
     pub past_endorsees: Vec<PastEndorseeWeb>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PastHolderResponse {
+    pub is_past_holder: bool,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct GeneralSearchResponse {
     pub bills: Vec<LightBitcreditBillWeb>,
@@ -88,6 +138,17 @@ pub struct BillsResponse<T: Serialize> {
     pub bills: Vec<T>,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BillsGroupWeb {
+    pub node_id: String,
+    pub bills: Vec<LightBitcreditBillWeb>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BillsGroupedResponse {
+    pub groups: Vec<BillsGroupWeb>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ContactsResponse<T: Serialize> {
     pub contacts: Vec<T>,
@@ -137,6 +198,8 @@ pub struct BillsSearchFilter {
     pub search_term: Option<String>,
     pub date_range: Option<DateRange>,
     pub role: BillsFilterRoleWeb,
+    /// Only bills with the given status are returned - all bills, if not set
+    pub status: Option<BillsFilterStatusWeb>,
     pub currency: String,
 }
 
@@ -164,6 +227,43 @@ pub struct BalanceResponse {
     pub sum: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ExposureResponse {
+    pub balances: OverviewBalanceResponse,
+    pub net_position: String,
+    pub accepted_count: u64,
+    pub awaiting_acceptance_count: u64,
+    pub overdue_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OverviewWithFiatResponse {
+    pub currency: String,
+    pub fiat_code: String,
+    pub balances: OverviewBalanceWithFiatResponse,
+    pub fiat_rate: Option<FiatRateResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OverviewBalanceWithFiatResponse {
+    pub payee: BalanceWithFiatResponse,
+    pub payer: BalanceWithFiatResponse,
+    pub contingent: BalanceWithFiatResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BalanceWithFiatResponse {
+    pub sum: String,
+    pub fiat_sum: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FiatRateResponse {
+    pub fiat_code: String,
+    pub rate: f64,
+    pub timestamp: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CurrenciesResponse {
     pub currencies: Vec<CurrencyResponse>,
@@ -184,12 +284,16 @@ pub struct BitcreditBillPayload {
     pub maturity_date: String,
     pub payee: String,
     pub drawee: String,
+    /// The sum in the given currency - either in sat, or a fiat sum with up to 2 decimals
     pub sum: String,
+    /// Either `sat`, or a fiat currency (e.g. `EUR`), for which the sat equivalent of the sum is
+    /// computed with the current BTC rate and recorded in the bill
     pub currency: String,
     pub country_of_payment: String,
     pub city_of_payment: String,
     pub language: String,
     pub file_upload_ids: Vec<String>,
+    pub payment_deadline_seconds: Option<u64>,
 }
 
 #[derive(Debug, FromForm, ToSchema)]
@@ -245,6 +349,8 @@ pub struct RequestToAcceptBitcreditBillPayload {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RejectActionBillPayload {
     pub bill_id: String,
+    /// An optional reason for the rejection, shown to the other participants
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -279,6 +385,163 @@ impl FromWeb<BillsFilterRoleWeb> for BillsFilterRole {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub enum BillsFilterStatusWeb {
+    All,
+    Paid,
+    Unpaid,
+    Overdue,
+    AwaitingAction,
+}
+
+impl FromWeb<BillsFilterStatusWeb> for BillsFilterStatus {
+    fn from_web(value: BillsFilterStatusWeb) -> Self {
+        match value {
+            BillsFilterStatusWeb::All => BillsFilterStatus::All,
+            BillsFilterStatusWeb::Paid => BillsFilterStatus::Paid,
+            BillsFilterStatusWeb::Unpaid => BillsFilterStatus::Unpaid,
+            BillsFilterStatusWeb::Overdue => BillsFilterStatus::Overdue,
+            BillsFilterStatusWeb::AwaitingAction => BillsFilterStatus::AwaitingAction,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub enum ActionTypeWeb {
+    BuyBill,
+    RecourseBill,
+    AcceptBill,
+    CheckBill,
+    PayBill,
+    CheckQuote,
+    RequestPayment,
+}
+
+impl FromWeb<ActionTypeWeb> for ActionType {
+    fn from_web(value: ActionTypeWeb) -> Self {
+        match value {
+            ActionTypeWeb::BuyBill => ActionType::BuyBill,
+            ActionTypeWeb::RecourseBill => ActionType::RecourseBill,
+            ActionTypeWeb::AcceptBill => ActionType::AcceptBill,
+            ActionTypeWeb::CheckBill => ActionType::CheckBill,
+            ActionTypeWeb::PayBill => ActionType::PayBill,
+            ActionTypeWeb::CheckQuote => ActionType::CheckQuote,
+            ActionTypeWeb::RequestPayment => ActionType::RequestPayment,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetBillHoldPayload {
+    pub bill_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetBillNotePayload {
+    pub bill_id: String,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddAutoAcceptRulePayload {
+    pub drawer_node_id: String,
+    pub max_sum: String,
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct BillAutoAcceptRuleWeb {
+    pub id: String,
+    pub drawer_node_id: String,
+    pub max_sum: String,
+}
+
+impl IntoWeb<BillAutoAcceptRuleWeb> for BillAutoAcceptRule {
+    fn into_web(self) -> BillAutoAcceptRuleWeb {
+        BillAutoAcceptRuleWeb {
+            id: self.id,
+            drawer_node_id: self.drawer_node_id,
+            max_sum: self.max_sum.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BillAutoAcceptRulesResponse {
+    pub rules: Vec<BillAutoAcceptRuleWeb>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResendBillEventPayload {
+    pub bill_id: String,
+    pub block_height: usize,
+    pub node_id: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PreviewNotificationPayload {
+    pub bill_id: String,
+    pub action_type: ActionTypeWeb,
+    pub language: String,
+}
+
+/// The bill actions, which can be validated without executing them
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub enum BillActionTypeWeb {
+    RequestAcceptance,
+    Accept,
+    RequestToPay,
+    RequestAcceptanceAndPayment,
+    OfferToSell,
+    Endorse,
+    RequestRecourseForPayment,
+    RequestRecourseForAcceptance,
+    Mint,
+    RejectAcceptance,
+    RejectPayment,
+    RejectBuying,
+    RejectPaymentForRecourse,
+    CancelOfferToSell,
+    CancelRequestToAccept,
+    MarkPaidExternally,
+    Acknowledge,
+    CorrectDrawee,
+}
+
+/// The fields, which aren't needed for the given action, are ignored
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ValidateBillActionPayload {
+    pub bill_id: String,
+    pub action_type: BillActionTypeWeb,
+    /// The buyer, endorsee, recoursee, mint, or new drawee
+    pub counterparty: Option<String>,
+    pub sum: Option<String>,
+    pub currency: Option<String>,
+    /// The reason of a rejection, or the reference of an external payment
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPreviewWeb {
+    pub language: String,
+    pub in_app_text: String,
+    pub email_subject: String,
+    pub email_body: String,
+    pub push_text: String,
+}
+
+impl IntoWeb<NotificationPreviewWeb> for NotificationPreview {
+    fn into_web(self) -> NotificationPreviewWeb {
+        NotificationPreviewWeb {
+            language: self.language,
+            in_app_text: self.in_app_text,
+            email_subject: self.email_subject,
+            email_body: self.email_body,
+            push_text: self.push_text,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PastEndorseeWeb {
     pub pay_to_the_order_of: LightIdentityPublicDataWeb,
@@ -298,6 +561,130 @@ impl IntoWeb<PastEndorseeWeb> for PastEndorsee {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub enum BillOpCodeWeb {
+    Issue,
+    Accept,
+    Endorse,
+    RequestToAccept,
+    RequestToPay,
+    OfferToSell,
+    Sell,
+    Mint,
+    RejectToAccept,
+    RejectToPay,
+    RejectToBuy,
+    RejectToPayRecourse,
+    RequestRecourse,
+    Recourse,
+    AttachFile,
+    ExternalPayment,
+    Acknowledge,
+    CorrectDrawee,
+    CancelOfferToSell,
+    CancelRequestToAccept,
+    MintQuote,
+    ApproveMintQuote,
+    RejectMintQuote,
+}
+
+impl IntoWeb<BillOpCodeWeb> for BillOpCode {
+    fn into_web(self) -> BillOpCodeWeb {
+        match self {
+            BillOpCode::Issue => BillOpCodeWeb::Issue,
+            BillOpCode::Accept => BillOpCodeWeb::Accept,
+            BillOpCode::Endorse => BillOpCodeWeb::Endorse,
+            BillOpCode::RequestToAccept => BillOpCodeWeb::RequestToAccept,
+            BillOpCode::RequestToPay => BillOpCodeWeb::RequestToPay,
+            BillOpCode::OfferToSell => BillOpCodeWeb::OfferToSell,
+            BillOpCode::Sell => BillOpCodeWeb::Sell,
+            BillOpCode::Mint => BillOpCodeWeb::Mint,
+            BillOpCode::RejectToAccept => BillOpCodeWeb::RejectToAccept,
+            BillOpCode::RejectToPay => BillOpCodeWeb::RejectToPay,
+            BillOpCode::RejectToBuy => BillOpCodeWeb::RejectToBuy,
+            BillOpCode::RejectToPayRecourse => BillOpCodeWeb::RejectToPayRecourse,
+            BillOpCode::RequestRecourse => BillOpCodeWeb::RequestRecourse,
+            BillOpCode::Recourse => BillOpCodeWeb::Recourse,
+            BillOpCode::AttachFile => BillOpCodeWeb::AttachFile,
+            BillOpCode::ExternalPayment => BillOpCodeWeb::ExternalPayment,
+            BillOpCode::Acknowledge => BillOpCodeWeb::Acknowledge,
+            BillOpCode::CorrectDrawee => BillOpCodeWeb::CorrectDrawee,
+            BillOpCode::CancelOfferToSell => BillOpCodeWeb::CancelOfferToSell,
+            BillOpCode::CancelRequestToAccept => BillOpCodeWeb::CancelRequestToAccept,
+            BillOpCode::MintQuote => BillOpCodeWeb::MintQuote,
+            BillOpCode::ApproveMintQuote => BillOpCodeWeb::ApproveMintQuote,
+            BillOpCode::RejectMintQuote => BillOpCodeWeb::RejectMintQuote,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct BillHistoryEntryWeb {
+    pub block_height: u64,
+    pub op_code: BillOpCodeWeb,
+    pub acting_node_id: String,
+    pub timestamp: u64,
+    pub summary: String,
+}
+
+impl IntoWeb<BillHistoryEntryWeb> for BillHistoryEntry {
+    fn into_web(self) -> BillHistoryEntryWeb {
+        BillHistoryEntryWeb {
+            block_height: self.block_height,
+            op_code: self.op_code.into_web(),
+            acting_node_id: self.acting_node_id,
+            timestamp: self.timestamp,
+            summary: self.summary,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub enum BlockValidationErrorWeb {
+    InvalidPreviousHash,
+    InvalidTimestamp,
+    InvalidBlockId,
+    InvalidHash,
+    InvalidSignature,
+    InvalidBlockData,
+    SignerMismatch,
+}
+
+impl IntoWeb<BlockValidationErrorWeb> for BlockValidationError {
+    fn into_web(self) -> BlockValidationErrorWeb {
+        match self {
+            BlockValidationError::InvalidPreviousHash => {
+                BlockValidationErrorWeb::InvalidPreviousHash
+            }
+            BlockValidationError::InvalidTimestamp => BlockValidationErrorWeb::InvalidTimestamp,
+            BlockValidationError::InvalidBlockId => BlockValidationErrorWeb::InvalidBlockId,
+            BlockValidationError::InvalidHash => BlockValidationErrorWeb::InvalidHash,
+            BlockValidationError::InvalidSignature => BlockValidationErrorWeb::InvalidSignature,
+            BlockValidationError::InvalidBlockData => BlockValidationErrorWeb::InvalidBlockData,
+            BlockValidationError::SignerMismatch => BlockValidationErrorWeb::SignerMismatch,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct ChainVerificationReportWeb {
+    pub valid: bool,
+    pub valid_blocks: u64,
+    pub first_invalid_block_height: Option<u64>,
+    pub reason: Option<BlockValidationErrorWeb>,
+}
+
+impl IntoWeb<ChainVerificationReportWeb> for ChainVerificationReport {
+    fn into_web(self) -> ChainVerificationReportWeb {
+        ChainVerificationReportWeb {
+            valid: self.is_valid(),
+            valid_blocks: self.valid_blocks,
+            first_invalid_block_height: self.first_invalid_block_height,
+            reason: self.reason.map(|r| r.into_web()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct LightSignedByWeb {
     #[serde(flatten)]
@@ -358,12 +745,58 @@ impl IntoWeb<IdentityTypeWeb> for IdentityType {
     }
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ControlledIdentityWeb {
+    pub node_id: String,
+    #[serde(rename = "type")]
+    pub t: IdentityTypeWeb,
+    pub name: String,
+}
+
+impl IntoWeb<ControlledIdentityWeb> for ControlledIdentity {
+    fn into_web(self) -> ControlledIdentityWeb {
+        ControlledIdentityWeb {
+            node_id: self.node_id,
+            t: self.t.into_web(),
+            name: self.name,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ControlledIdentitiesResponse {
+    pub identities: Vec<ControlledIdentityWeb>,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RequestToPayBitcreditBillPayload {
     pub bill_id: String,
     pub currency: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AttachFilesToBillPayload {
+    pub bill_id: String,
+    pub file_upload_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AcknowledgeBillPayload {
+    pub bill_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CorrectDraweePayload {
+    pub bill_id: String,
+    pub drawee: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MarkPaidExternallyPayload {
+    pub bill_id: String,
+    pub reference: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct RequestRecourseForPaymentPayload {
     pub bill_id: String,
@@ -463,6 +896,22 @@ pub struct SeedPhrase {
     pub seed_phrase: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentityBackupPayload {
+    /// The passphrase the backup is encrypted with
+    pub passphrase: String,
+}
+
+#[derive(Debug, FromForm, ToSchema)]
+pub struct RestoreIdentityBackupForm<'r> {
+    #[schema(value_type = String, format = Binary)]
+    pub file: TempFile<'r>,
+    /// The passphrase the backup was encrypted with
+    pub passphrase: String,
+    /// Whether an existing, different identity should be overwritten
+    pub force: bool,
+}
+
 // Company
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct CreateCompanyPayload {
@@ -533,6 +982,49 @@ impl From<Contact> for SignatoryResponse {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct SignatoryHistoryResponse {
+    pub changes: Vec<SignatoryChangeWeb>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub enum SignatoryChangeTypeWeb {
+    Add,
+    Remove,
+}
+
+impl IntoWeb<SignatoryChangeTypeWeb> for SignatoryChangeType {
+    fn into_web(self) -> SignatoryChangeTypeWeb {
+        match self {
+            SignatoryChangeType::Add => SignatoryChangeTypeWeb::Add,
+            SignatoryChangeType::Remove => SignatoryChangeTypeWeb::Remove,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct SignatoryChangeWeb {
+    pub change_type: SignatoryChangeTypeWeb,
+    /// The node id of the signatory that was added or removed
+    pub signatory: String,
+    /// The node id of the signatory who made the change
+    pub changed_by: String,
+    pub block_id: u64,
+    pub timestamp: u64,
+}
+
+impl IntoWeb<SignatoryChangeWeb> for SignatoryChange {
+    fn into_web(self) -> SignatoryChangeWeb {
+        SignatoryChangeWeb {
+            change_type: self.change_type.into_web(),
+            signatory: self.signatory,
+            changed_by: self.changed_by,
+            block_id: self.block_id,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct IdentityWeb {
     pub node_id: String,
@@ -740,6 +1232,22 @@ impl IntoWeb<ContactWeb> for Contact {
     }
 }
 
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RefreshedContactWeb {
+    pub contact: ContactWeb,
+    /// Set, if no Nostr profile of the contact could be found, so the contact might be outdated
+    pub stale: bool,
+}
+
+impl IntoWeb<RefreshedContactWeb> for RefreshedContact {
+    fn into_web(self) -> RefreshedContactWeb {
+        RefreshedContactWeb {
+            contact: self.contact.into_web(),
+            stale: self.stale,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct CompanyWeb {
     pub id: String,
@@ -806,6 +1314,11 @@ impl IntoWeb<BitcreditBillWeb> for BitcreditBillResult {
     }
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BillWaitingStateResponse {
+    pub current_waiting_state: Option<BillCurrentWaitingStateWeb>,
+}
+
 #[derive(Debug, Serialize, Clone, ToSchema)]
 pub enum BillCurrentWaitingStateWeb {
     Sell(BillWaitingForSellStateWeb),
@@ -829,6 +1342,21 @@ impl IntoWeb<BillCurrentWaitingStateWeb> for BillCurrentWaitingState {
     }
 }
 
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct FeeEstimateWeb {
+    pub fee_rate_sat_vb: u64,
+    pub estimated_confirmation_seconds: u64,
+}
+
+impl IntoWeb<FeeEstimateWeb> for FeeEstimate {
+    fn into_web(self) -> FeeEstimateWeb {
+        FeeEstimateWeb {
+            fee_rate_sat_vb: self.fee_rate_sat_vb,
+            estimated_confirmation_seconds: self.estimated_confirmation_seconds,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone, ToSchema)]
 pub struct BillWaitingForSellStateWeb {
     pub time_of_request: u64,
@@ -839,6 +1367,7 @@ pub struct BillWaitingForSellStateWeb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimateWeb>,
 }
 
 impl IntoWeb<BillWaitingForSellStateWeb> for BillWaitingForSellState {
@@ -852,6 +1381,7 @@ impl IntoWeb<BillWaitingForSellStateWeb> for BillWaitingForSellState {
             link_to_pay: self.link_to_pay,
             address_to_pay: self.address_to_pay,
             mempool_link_for_address_to_pay: self.mempool_link_for_address_to_pay,
+            fee_estimate: self.fee_estimate.map(|f| f.into_web()),
         }
     }
 }
@@ -866,6 +1396,7 @@ pub struct BillWaitingForPaymentStateWeb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimateWeb>,
 }
 
 impl IntoWeb<BillWaitingForPaymentStateWeb> for BillWaitingForPaymentState {
@@ -879,6 +1410,7 @@ impl IntoWeb<BillWaitingForPaymentStateWeb> for BillWaitingForPaymentState {
             link_to_pay: self.link_to_pay,
             address_to_pay: self.address_to_pay,
             mempool_link_for_address_to_pay: self.mempool_link_for_address_to_pay,
+            fee_estimate: self.fee_estimate.map(|f| f.into_web()),
         }
     }
 }
@@ -893,6 +1425,7 @@ pub struct BillWaitingForRecourseStateWeb {
     pub link_to_pay: String,
     pub address_to_pay: String,
     pub mempool_link_for_address_to_pay: String,
+    pub fee_estimate: Option<FeeEstimateWeb>,
 }
 impl IntoWeb<BillWaitingForRecourseStateWeb> for BillWaitingForRecourseState {
     fn into_web(self) -> BillWaitingForRecourseStateWeb {
@@ -905,6 +1438,7 @@ impl IntoWeb<BillWaitingForRecourseStateWeb> for BillWaitingForRecourseState {
             link_to_pay: self.link_to_pay,
             address_to_pay: self.address_to_pay,
             mempool_link_for_address_to_pay: self.mempool_link_for_address_to_pay,
+            fee_estimate: self.fee_estimate.map(|f| f.into_web()),
         }
     }
 }
@@ -917,6 +1451,8 @@ pub struct BillStatusWeb {
     pub recourse: BillRecourseStatusWeb,
     pub redeemed_funds_available: bool,
     pub has_requested_funds: bool,
+    pub hold: Option<BillHoldWeb>,
+    pub settlement_status: SettlementStatusWeb,
 }
 
 impl IntoWeb<BillStatusWeb> for BillStatus {
@@ -928,6 +1464,40 @@ impl IntoWeb<BillStatusWeb> for BillStatus {
             recourse: self.recourse.into_web(),
             redeemed_funds_available: self.redeemed_funds_available,
             has_requested_funds: self.has_requested_funds,
+            hold: self.hold.map(|h| h.into_web()),
+            settlement_status: self.settlement_status.into_web(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub enum SettlementStatusWeb {
+    Active,
+    Settled,
+    Defaulted,
+}
+
+impl IntoWeb<SettlementStatusWeb> for SettlementStatus {
+    fn into_web(self) -> SettlementStatusWeb {
+        match self {
+            SettlementStatus::Active => SettlementStatusWeb::Active,
+            SettlementStatus::Settled => SettlementStatusWeb::Settled,
+            SettlementStatus::Defaulted => SettlementStatusWeb::Defaulted,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct BillHoldWeb {
+    pub reason: String,
+    pub timestamp: u64,
+}
+
+impl IntoWeb<BillHoldWeb> for BillHold {
+    fn into_web(self) -> BillHoldWeb {
+        BillHoldWeb {
+            reason: self.reason,
+            timestamp: self.timestamp,
         }
     }
 }
@@ -939,6 +1509,7 @@ pub struct BillAcceptanceStatusWeb {
     pub accepted: bool,
     pub request_to_accept_timed_out: bool,
     pub rejected_to_accept: bool,
+    pub rejected_to_accept_reason: Option<String>,
 }
 
 impl IntoWeb<BillAcceptanceStatusWeb> for BillAcceptanceStatus {
@@ -949,6 +1520,7 @@ impl IntoWeb<BillAcceptanceStatusWeb> for BillAcceptanceStatus {
             accepted: self.accepted,
             request_to_accept_timed_out: self.request_to_accept_timed_out,
             rejected_to_accept: self.rejected_to_accept,
+            rejected_to_accept_reason: self.rejected_to_accept_reason,
         }
     }
 }
@@ -958,8 +1530,11 @@ pub struct BillPaymentStatusWeb {
     pub time_of_request_to_pay: Option<u64>,
     pub requested_to_pay: bool,
     pub paid: bool,
+    pub paid_sum: u64,
     pub request_to_pay_timed_out: bool,
     pub rejected_to_pay: bool,
+    pub rejected_to_pay_reason: Option<String>,
+    pub external_payment_reference: Option<String>,
 }
 impl IntoWeb<BillPaymentStatusWeb> for BillPaymentStatus {
     fn into_web(self) -> BillPaymentStatusWeb {
@@ -967,8 +1542,11 @@ impl IntoWeb<BillPaymentStatusWeb> for BillPaymentStatus {
             time_of_request_to_pay: self.time_of_request_to_pay,
             requested_to_pay: self.requested_to_pay,
             paid: self.paid,
+            paid_sum: self.paid_sum,
             request_to_pay_timed_out: self.request_to_pay_timed_out,
             rejected_to_pay: self.rejected_to_pay,
+            rejected_to_pay_reason: self.rejected_to_pay_reason,
+            external_payment_reference: self.external_payment_reference,
         }
     }
 }
@@ -980,6 +1558,7 @@ pub struct BillSellStatusWeb {
     pub offered_to_sell: bool,
     pub offer_to_sell_timed_out: bool,
     pub rejected_offer_to_sell: bool,
+    pub rejected_offer_to_sell_reason: Option<String>,
 }
 impl IntoWeb<BillSellStatusWeb> for BillSellStatus {
     fn into_web(self) -> BillSellStatusWeb {
@@ -989,6 +1568,7 @@ impl IntoWeb<BillSellStatusWeb> for BillSellStatus {
             offered_to_sell: self.offered_to_sell,
             offer_to_sell_timed_out: self.offer_to_sell_timed_out,
             rejected_offer_to_sell: self.rejected_offer_to_sell,
+            rejected_offer_to_sell_reason: self.rejected_offer_to_sell_reason,
         }
     }
 }
@@ -1028,7 +1608,12 @@ pub struct BillDataWeb {
     pub currency: String,
     pub sum: String,
     pub files: Vec<FileWeb>,
+    pub tampered_files: Vec<String>,
+    pub payment_deadline_seconds: u64,
+    pub fiat_amount: Option<BillFiatAmountWeb>,
     pub active_notification: Option<NotificationWeb>,
+    pub note: Option<String>,
+    pub acknowledgements: Vec<BillAcknowledgementWeb>,
 }
 
 impl IntoWeb<BillDataWeb> for BillData {
@@ -1046,7 +1631,55 @@ impl IntoWeb<BillDataWeb> for BillData {
             currency: self.currency,
             sum: self.sum,
             files: self.files.into_iter().map(|f| f.into_web()).collect(),
+            tampered_files: self.tampered_files,
+            payment_deadline_seconds: self.payment_deadline_seconds,
+            fiat_amount: self.fiat_amount.map(|f| f.into_web()),
             active_notification: self.active_notification.map(|an| an.into_web()),
+            note: self.note,
+            acknowledgements: self
+                .acknowledgements
+                .into_iter()
+                .map(|a| a.into_web())
+                .collect(),
+        }
+    }
+}
+
+/// An attestation of the holder, or the drawee, that they have seen the bill
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct BillAcknowledgementWeb {
+    pub node_id: String,
+    pub name: String,
+    pub timestamp: u64,
+}
+
+impl IntoWeb<BillAcknowledgementWeb> for BillAcknowledgement {
+    fn into_web(self) -> BillAcknowledgementWeb {
+        BillAcknowledgementWeb {
+            node_id: self.node_id,
+            name: self.name,
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// The fiat sum a bill was issued with - the sum of the bill is the sat equivalent, computed
+/// with the given BTC rate at issuance
+#[derive(Debug, Serialize, Clone, ToSchema)]
+pub struct BillFiatAmountWeb {
+    pub currency: String,
+    pub sum: String,
+    pub btc_rate: f64,
+    pub rate_timestamp: u64,
+}
+
+impl IntoWeb<BillFiatAmountWeb> for BillFiatAmount {
+    fn into_web(self) -> BillFiatAmountWeb {
+        BillFiatAmountWeb {
+            currency: self.currency,
+            sum: self.sum,
+            btc_rate: self.btc_rate,
+            rate_timestamp: self.rate_timestamp,
         }
     }
 }
@@ -1087,6 +1720,8 @@ pub struct LightBitcreditBillWeb {
     pub issue_date: String,
     pub time_of_drawing: u64,
     pub time_of_maturity: u64,
+    pub last_op_code: BillOpCodeWeb,
+    pub block_height: u64,
 }
 impl IntoWeb<LightBitcreditBillWeb> for LightBitcreditBillResult {
     fn into_web(self) -> LightBitcreditBillWeb {
@@ -1102,6 +1737,8 @@ impl IntoWeb<LightBitcreditBillWeb> for LightBitcreditBillResult {
             issue_date: self.issue_date,
             time_of_drawing: self.time_of_drawing,
             time_of_maturity: self.time_of_maturity,
+            last_op_code: self.last_op_code.into_web(),
+            block_height: self.block_height,
         }
     }
 }
@@ -1170,6 +1807,26 @@ impl IntoWeb<LightIdentityPublicDataWeb> for LightIdentityPublicData {
     }
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationsResponse {
+    pub notifications: Vec<NotificationWeb>,
+    /// The cursor to pass as `after` to fetch the next page - empty, if this is the last page
+    pub next_cursor: Option<String>,
+}
+
+impl IntoWeb<NotificationsResponse> for NotificationPage {
+    fn into_web(self) -> NotificationsResponse {
+        NotificationsResponse {
+            notifications: self
+                .notifications
+                .into_iter()
+                .map(|n| n.into_web())
+                .collect(),
+            next_cursor: self.next_cursor.map(|c| c.encode()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct NotificationWeb {
     pub id: String,
@@ -1212,6 +1869,51 @@ impl IntoWeb<NotificationTypeWeb> for NotificationType {
     }
 }
 
+impl FromWeb<NotificationTypeWeb> for NotificationType {
+    fn from_web(value: NotificationTypeWeb) -> Self {
+        match value {
+            NotificationTypeWeb::Bill => NotificationType::Bill,
+            NotificationTypeWeb::General => NotificationType::General,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarkAllNotificationsDonePayload {
+    pub notification_type: Option<NotificationTypeWeb>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MarkAllNotificationsDoneResponse {
+    pub marked: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RelayStatusResponse {
+    pub relays: Vec<RelayStatusWeb>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RelayStatusWeb {
+    pub relay: String,
+    pub healthy: bool,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub last_error: Option<String>,
+}
+
+impl IntoWeb<RelayStatusWeb> for RelayStatus {
+    fn into_web(self) -> RelayStatusWeb {
+        RelayStatusWeb {
+            healthy: self.is_healthy(),
+            relay: self.relay,
+            success_count: self.success_count,
+            failure_count: self.failure_count,
+            last_error: self.last_error,
+        }
+    }
+}
+
 pub struct TempFileWrapper<'a>(pub &'a TempFile<'a>);
 
 #[async_trait]
@@ -1,20 +1,30 @@
 use super::Result;
 use super::middleware::IdentityCheck;
 use crate::data::{
-    AcceptBitcreditBillPayload, BillCombinedBitcoinKeyWeb, BillId, BillNumbersToWordsForSum,
+    AcceptBitcreditBillPayload, AcknowledgeBillPayload, AddAutoAcceptRulePayload,
+    AttachFilesToBillPayload, BillActionTypeWeb, BillAutoAcceptRuleWeb,
+    BillAutoAcceptRulesResponse, BillCombinedBitcoinKeyWeb, BillHistoryResponse, BillId,
+    BillNumbersToWordsForSum, BillWaitingStateResponse, BillsGroupWeb, BillsGroupedResponse,
     BillsResponse, BillsSearchFilterPayload, BitcreditBillPayload, BitcreditBillWeb,
-    EndorseBitcreditBillPayload, EndorsementsResponse, FromWeb, IntoWeb, LightBitcreditBillWeb,
-    MintBitcreditBillPayload, OfferToSellBitcreditBillPayload, PastEndorseesResponse,
-    RejectActionBillPayload, RequestRecourseForAcceptancePayload, RequestRecourseForPaymentPayload,
+    ChainVerificationReportWeb, CorrectDraweePayload, EndorseBitcreditBillPayload,
+    EndorsementsResponse, FromWeb, IntoWeb, LightBitcreditBillWeb, MarkPaidExternallyPayload,
+    MintBitcreditBillPayload, NotificationPreviewWeb, OfferToSellBitcreditBillPayload,
+    PastEndorseesResponse, PastHolderResponse, PreviewNotificationPayload, RejectActionBillPayload,
+    RequestRecourseForAcceptancePayload, RequestRecourseForPaymentPayload,
     RequestToAcceptBitcreditBillPayload, RequestToMintBitcreditBillPayload,
-    RequestToPayBitcreditBillPayload, SuccessResponse, TempFileWrapper, UploadFileForm,
-    UploadFileResponse,
+    RequestToPayBitcreditBillPayload, ResendBillEventPayload, SetBillHoldPayload,
+    SetBillNotePayload, SuccessResponse, TempFileWrapper, UploadFileForm, UploadFileResponse,
+    ValidateBillActionPayload,
 };
+use crate::router::ErrorResponse;
 use crate::service_context::ServiceContext;
 use bcr_ebill_api::data::bill::BillIssueData;
 use bcr_ebill_api::data::{
-    bill::{BillAction, BillsFilterRole, LightBitcreditBillResult, RecourseReason},
+    bill::{
+        BillAction, BillsFilterRole, BillsFilterStatus, LightBitcreditBillResult, RecourseReason,
+    },
     contact::IdentityPublicData,
+    notification::ActionType,
 };
 use bcr_ebill_api::service::bill_service::error::Error as BillServiceError;
 use bcr_ebill_api::util::file::{UploadFileHandler, detect_content_type_for_bytes};
@@ -24,7 +34,7 @@ use log::{error, info};
 use rocket::form::Form;
 use rocket::http::ContentType;
 use rocket::serde::json::Json;
-use rocket::{State, get, post, put};
+use rocket::{State, delete, get, post, put};
 
 pub async fn get_current_identity_node_id(state: &State<ServiceContext>) -> String {
     let current_identity = state.get_current_identity().await;
@@ -94,6 +104,65 @@ pub async fn get_endorsements_for_bill(
     }))
 }
 
+#[utoipa::path(
+    tag = "Bills",
+    path = "/bill/history/{id}",
+    description = "Get the history of the given bill, with one entry per block",
+    responses(
+        (status = 200, description = "Bill History", body = BillHistoryResponse)
+    )
+)]
+#[get("/history/<id>")]
+pub async fn get_bill_history(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<BillHistoryResponse>> {
+    let result = state
+        .bill_service
+        .get_bill_history(id, &get_current_identity_node_id(state).await)
+        .await?;
+    Ok(Json(BillHistoryResponse {
+        history: result.into_iter().map(|e| e.into_web()).collect(),
+    }))
+}
+
+#[utoipa::path(
+    tag = "Bills",
+    path = "/bill/verify/{id}",
+    description = "Verifies the integrity of the given bill's chain block by block",
+    responses(
+        (status = 200, description = "Chain Verification Report", body = ChainVerificationReportWeb)
+    )
+)]
+#[get("/verify/<id>")]
+pub async fn verify_bill_chain(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<ChainVerificationReportWeb>> {
+    let result = state.bill_service.verify_bill_chain(id).await?;
+    Ok(Json(result.into_web()))
+}
+
+#[utoipa::path(
+    tag = "Bills",
+    path = "/bill/repair/{id}",
+    description = "Attempts to repair the given bill's chain by re-fetching corrupted blocks from the relays",
+    responses(
+        (status = 200, description = "Chain Verification Report after the repair", body = ChainVerificationReportWeb)
+    )
+)]
+#[put("/repair/<id>")]
+pub async fn repair_bill_chain(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<ChainVerificationReportWeb>> {
+    let result = state.bill_service.repair_bill_chain(id).await?;
+    Ok(Json(result.into_web()))
+}
+
 #[utoipa::path(
     tag = "Past Endorsees",
     path = "/bill/past_endorsees/{id}",
@@ -117,6 +186,446 @@ pub async fn get_past_endorsees_for_bill(
     }))
 }
 
+#[utoipa::path(
+    tag = "Past Endorsees",
+    path = "/bill/past_holder/{id}/{node_id}",
+    description = "Checks if the given node id is a past endorsee of the given bill, which can be selected for recourse",
+    params(
+        ("id" = String, Path, description = "The id of the bill"),
+        ("node_id" = String, Path, description = "The node id of the candidate")
+    ),
+    responses(
+        (status = 200, description = "Whether the node id is a past holder", body = PastHolderResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse)
+    )
+)]
+#[get("/past_holder/<id>/<node_id>")]
+pub async fn is_past_holder_of_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+    node_id: &str,
+) -> Result<Json<PastHolderResponse>> {
+    let is_past_holder = state
+        .bill_service
+        .is_past_holder(id, node_id, &get_current_identity_node_id(state).await)
+        .await?;
+    Ok(Json(PastHolderResponse { is_past_holder }))
+}
+
+#[utoipa::path(
+    tag = "Notification Preview",
+    path = "/bill/preview_notification",
+    description = "Get the notification texts counterparties would receive for the given action on the given bill, without sending anything",
+    responses(
+        (status = 200, description = "Notification Preview", body = NotificationPreviewWeb)
+    )
+)]
+#[post(
+    "/preview_notification",
+    format = "json",
+    data = "<preview_notification_payload>"
+)]
+pub async fn preview_notification(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    preview_notification_payload: Json<PreviewNotificationPayload>,
+) -> Result<Json<NotificationPreviewWeb>> {
+    let payload = preview_notification_payload.0;
+    let preview = state
+        .bill_service
+        .preview_notification(
+            &payload.bill_id,
+            &ActionType::from_web(payload.action_type),
+            &payload.language,
+        )
+        .await?;
+    Ok(Json(preview.into_web()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/validate_action",
+    description = "Validates the given action on the given bill for the current identity, without executing it",
+    request_body(description = "The bill, the action and its parameters", content((ValidateBillActionPayload))),
+    responses(
+        (status = 200, description = "Bill action is valid", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[post(
+    "/validate_action",
+    format = "json",
+    data = "<validate_bill_action_payload>"
+)]
+pub async fn validate_bill_action(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    validate_bill_action_payload: Json<ValidateBillActionPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let payload = validate_bill_action_payload.0;
+    let bill_action = bill_action_to_validate(state, &payload).await?;
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, _) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .validate_bill_action(
+            &payload.bill_id,
+            bill_action,
+            &signer_public_data,
+            timestamp,
+        )
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+/// Creates the bill action to validate from the payload, resolving the counterparty from the
+/// contacts the same way the endpoints of the actions do
+async fn bill_action_to_validate(
+    state: &State<ServiceContext>,
+    payload: &ValidateBillActionPayload,
+) -> Result<BillAction> {
+    let counterparty = payload.counterparty.as_deref().unwrap_or_default();
+    let sum = payload.sum.as_deref().unwrap_or_default();
+    let currency = payload.currency.as_deref().unwrap_or_default();
+    let bill_action = match payload.action_type {
+        BillActionTypeWeb::RequestAcceptance => BillAction::RequestAcceptance,
+        BillActionTypeWeb::Accept => BillAction::Accept,
+        BillActionTypeWeb::RequestToPay => BillAction::RequestToPay(currency.to_owned()),
+        BillActionTypeWeb::RequestAcceptanceAndPayment => {
+            BillAction::RequestAcceptanceAndPayment(currency.to_owned())
+        }
+        BillActionTypeWeb::OfferToSell => {
+            let buyer =
+                get_contact(state, counterparty, BillServiceError::BuyerNotInContacts).await?;
+            let (sum, sum_currency) = currency::parse_sum_in_currency(sum, currency)?;
+            BillAction::OfferToSell(buyer, sum, sum_currency)
+        }
+        BillActionTypeWeb::Endorse => BillAction::Endorse(
+            get_contact(state, counterparty, BillServiceError::EndorseeNotInContacts).await?,
+        ),
+        BillActionTypeWeb::RequestRecourseForPayment => {
+            let recoursee = get_contact(
+                state,
+                counterparty,
+                BillServiceError::RecourseeNotInContacts,
+            )
+            .await?;
+            let (sum, sum_currency) = currency::parse_sum_in_currency(sum, currency)?;
+            BillAction::RequestRecourse(recoursee, RecourseReason::Pay(sum, sum_currency))
+        }
+        BillActionTypeWeb::RequestRecourseForAcceptance => BillAction::RequestRecourse(
+            get_contact(
+                state,
+                counterparty,
+                BillServiceError::RecourseeNotInContacts,
+            )
+            .await?,
+            RecourseReason::Accept,
+        ),
+        BillActionTypeWeb::Mint => {
+            let mint =
+                get_contact(state, counterparty, BillServiceError::MintNotInContacts).await?;
+            let (sum, sum_currency) = currency::parse_sum_in_currency(sum, currency)?;
+            BillAction::Mint(mint, sum, sum_currency)
+        }
+        BillActionTypeWeb::RejectAcceptance => BillAction::RejectAcceptance(payload.reason.clone()),
+        BillActionTypeWeb::RejectPayment => BillAction::RejectPayment(payload.reason.clone()),
+        BillActionTypeWeb::RejectBuying => BillAction::RejectBuying(payload.reason.clone()),
+        BillActionTypeWeb::RejectPaymentForRecourse => BillAction::RejectPaymentForRecourse,
+        BillActionTypeWeb::CancelOfferToSell => BillAction::CancelOfferToSell,
+        BillActionTypeWeb::CancelRequestToAccept => BillAction::CancelRequestToAccept,
+        BillActionTypeWeb::MarkPaidExternally => {
+            BillAction::MarkPaidExternally(payload.reason.clone().unwrap_or_default())
+        }
+        BillActionTypeWeb::Acknowledge => BillAction::Acknowledge,
+        BillActionTypeWeb::CorrectDrawee => BillAction::CorrectDrawee(
+            get_contact(state, counterparty, BillServiceError::DraweeNotInContacts).await?,
+        ),
+    };
+    Ok(bill_action)
+}
+
+async fn get_contact(
+    state: &State<ServiceContext>,
+    node_id: &str,
+    not_in_contacts: BillServiceError,
+) -> Result<IdentityPublicData> {
+    match state.contact_service.get_identity_by_node_id(node_id).await {
+        Ok(Some(contact)) => Ok(contact),
+        Ok(None) | Err(_) => Err(not_in_contacts.into()),
+    }
+}
+
+#[utoipa::path(
+    tag = "Notification Resend",
+    path = "/bill/resend_event",
+    description = "Resends the event for the block at the given height of the given bill to the given participant, e.g. if they were offline past the relay's retention",
+    request_body(description = "The bill, block height and recipient to resend the event for", content((ResendBillEventPayload))),
+    responses(
+        (status = 200, description = "Event was resent", body = SuccessResponse),
+        (status = 404, description = "Bill, block or recipient not found", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[post("/resend_event", format = "json", data = "<resend_bill_event_payload>")]
+pub async fn resend_bill_event(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    resend_bill_event_payload: Json<ResendBillEventPayload>,
+) -> Result<Json<SuccessResponse>> {
+    state
+        .bill_service
+        .resend_bill_event(
+            &resend_bill_event_payload.bill_id,
+            resend_bill_event_payload.block_height,
+            &resend_bill_event_payload.node_id,
+            &get_current_identity_node_id(state).await,
+        )
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Hold",
+    path = "/bill/hold",
+    description = "Manually puts the given bill on hold with the given reason, so background jobs skip it",
+    responses(
+        (status = 200, description = "Bill was put on hold", body = SuccessResponse)
+    )
+)]
+#[put("/hold", format = "json", data = "<set_bill_hold_payload>")]
+pub async fn set_bill_hold(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    set_bill_hold_payload: Json<SetBillHoldPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    state
+        .bill_service
+        .set_bill_hold(
+            &set_bill_hold_payload.bill_id,
+            &set_bill_hold_payload.reason,
+            &get_current_identity_node_id(state).await,
+            timestamp,
+        )
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Hold",
+    path = "/bill/hold/{id}",
+    description = "Clears the hold of the given bill, so background jobs pick it up again",
+    responses(
+        (status = 200, description = "Hold was cleared", body = SuccessResponse)
+    )
+)]
+#[delete("/hold/<id>")]
+pub async fn clear_bill_hold(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<SuccessResponse>> {
+    state
+        .bill_service
+        .clear_bill_hold(id, &get_current_identity_node_id(state).await)
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Archive",
+    path = "/bill/archive/{id}",
+    description = "Archives the given bill for the current identity, hiding it from the default bill list and the balances",
+    responses(
+        (status = 200, description = "Bill was archived", body = SuccessResponse)
+    )
+)]
+#[put("/archive/<id>")]
+pub async fn archive_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<SuccessResponse>> {
+    state
+        .bill_service
+        .archive_bill(id, &get_current_identity_node_id(state).await)
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Archive",
+    path = "/bill/archive/{id}",
+    description = "Unarchives the given bill for the current identity",
+    responses(
+        (status = 200, description = "Bill was unarchived", body = SuccessResponse)
+    )
+)]
+#[delete("/archive/<id>")]
+pub async fn unarchive_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<SuccessResponse>> {
+    state
+        .bill_service
+        .unarchive_bill(id, &get_current_identity_node_id(state).await)
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Note",
+    path = "/bill/note",
+    description = "Sets the private note of the current identity on the given bill, or removes it, if it's empty. The note is only stored locally and never shared",
+    responses(
+        (status = 200, description = "Note was set", body = SuccessResponse)
+    )
+)]
+#[put("/note", format = "json", data = "<set_bill_note_payload>")]
+pub async fn set_bill_note(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    set_bill_note_payload: Json<SetBillNotePayload>,
+) -> Result<Json<SuccessResponse>> {
+    state
+        .bill_service
+        .set_bill_note(
+            &set_bill_note_payload.bill_id,
+            &get_current_identity_node_id(state).await,
+            set_bill_note_payload.note.clone(),
+        )
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Auto Accept",
+    path = "/bill/auto_accept",
+    description = "Returns the auto-accept rules of the current identity",
+    responses(
+        (status = 200, description = "Auto-accept rules", body = BillAutoAcceptRulesResponse)
+    )
+)]
+#[get("/auto_accept")]
+pub async fn list_auto_accept_rules(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+) -> Result<Json<BillAutoAcceptRulesResponse>> {
+    let rules = state
+        .bill_service
+        .get_auto_accept_rules(&get_current_identity_node_id(state).await)
+        .await?;
+    Ok(Json(BillAutoAcceptRulesResponse {
+        rules: rules.into_iter().map(|r| r.into_web()).collect(),
+    }))
+}
+
+#[utoipa::path(
+    tag = "Bill Auto Accept",
+    path = "/bill/auto_accept",
+    description = "Adds a rule to accept bills from the given drawer up to the given sum automatically, when the current identity is requested to accept them",
+    responses(
+        (status = 200, description = "Auto-accept rule was added", body = BillAutoAcceptRuleWeb)
+    )
+)]
+#[post(
+    "/auto_accept",
+    format = "json",
+    data = "<add_auto_accept_rule_payload>"
+)]
+pub async fn add_auto_accept_rule(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    add_auto_accept_rule_payload: Json<AddAutoAcceptRulePayload>,
+) -> Result<Json<BillAutoAcceptRuleWeb>> {
+    let max_sum = currency::parse_sum(&add_auto_accept_rule_payload.max_sum)?;
+    let rule = state
+        .bill_service
+        .add_auto_accept_rule(
+            &get_current_identity_node_id(state).await,
+            &add_auto_accept_rule_payload.drawer_node_id,
+            max_sum,
+        )
+        .await?;
+    Ok(Json(rule.into_web()))
+}
+
+#[utoipa::path(
+    tag = "Bill Auto Accept",
+    path = "/bill/auto_accept/{id}",
+    description = "Removes the given auto-accept rule of the current identity",
+    responses(
+        (status = 200, description = "Auto-accept rule was removed", body = SuccessResponse)
+    )
+)]
+#[delete("/auto_accept/<id>")]
+pub async fn remove_auto_accept_rule(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<SuccessResponse>> {
+    state
+        .bill_service
+        .remove_auto_accept_rule(&get_current_identity_node_id(state).await, id)
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Bundle",
+    path = "/bill/bundle/{id}",
+    description = "Exports the whole chain, keys and attached files of the given bill as a signed bundle, encrypted to the caller",
+    responses(
+        (status = 200, description = "The bill bundle")
+    )
+)]
+#[get("/bundle/<id>")]
+pub async fn export_bundle(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<(ContentType, Vec<u8>)> {
+    let (_, caller_keys) = get_signer_public_data_and_keys(state).await?;
+    let bundle = state
+        .bill_service
+        .export_bill_bundle(id, &caller_keys)
+        .await?;
+    Ok((ContentType::Binary, bundle))
+}
+
+#[utoipa::path(
+    post,
+    tag = "Bill Bundle",
+    path = "/bill/import_bundle",
+    request_body(content_type = "multipart/form-data", content = UploadFileForm, description = "Bill bundle to import"),
+    responses(
+        (status = 200, description = "The id of the imported bill", body = BillId)
+    )
+)]
+#[post("/import_bundle", data = "<file_upload_form>")]
+pub async fn import_bundle(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    file_upload_form: Form<UploadFileForm<'_>>,
+) -> Result<Json<BillId>> {
+    let (_, caller_keys) = get_signer_public_data_and_keys(state).await?;
+    let bundle = TempFileWrapper(&file_upload_form.file)
+        .get_contents()
+        .await
+        .map_err(service::Error::Io)?;
+    let bill_id = state
+        .bill_service
+        .import_bill_bundle(&bundle, &caller_keys)
+        .await?;
+    Ok(Json(BillId { id: bill_id }))
+}
+
 #[get("/bitcoin_key/<id>")]
 pub async fn bitcoin_key(
     _identity: IdentityCheck,
@@ -189,6 +698,10 @@ pub async fn search(
             from,
             to,
             &BillsFilterRole::from_web(filter.role),
+            &filter
+                .status
+                .map(BillsFilterStatus::from_web)
+                .unwrap_or(BillsFilterStatus::All),
             &get_current_identity_node_id(state).await,
         )
         .await?;
@@ -201,22 +714,128 @@ pub async fn search(
     tag = "Bills Light",
     path = "/bill/list/light",
     description = "Get all bill details in a light version",
+    params(
+        ("include_archived" = Option<bool>, Query, description = "Includes bills archived by the current identity when true")
+    ),
     responses(
         (status = 200, description = "List of bills light", body = BillsResponse<LightBitcreditBillWeb>)
     )
 )]
-#[get("/list/light")]
+#[get("/list/light?<include_archived>")]
 pub async fn list_light(
     _identity: IdentityCheck,
     state: &State<ServiceContext>,
+    include_archived: Option<bool>,
 ) -> Result<Json<BillsResponse<LightBitcreditBillWeb>>> {
     let bills: Vec<LightBitcreditBillResult> = state
         .bill_service
-        .get_bills(&get_current_identity_node_id(state).await)
-        .await?
-        .into_iter()
-        .map(|b| b.into())
-        .collect();
+        .get_bills(
+            &get_current_identity_node_id(state).await,
+            include_archived.unwrap_or(false),
+        )
+        .await?
+        .into_iter()
+        .map(|b| b.into())
+        .collect();
+    Ok(Json(BillsResponse {
+        bills: bills.into_iter().map(|b| b.into_web()).collect(),
+    }))
+}
+
+#[utoipa::path(
+    tag = "Bills Light",
+    path = "/bill/list/grouped",
+    description = "Get all bills in a light version, grouped by the personal identity and each company of the caller. A bill shared between them is listed for each of them",
+    responses(
+        (status = 200, description = "List of bills light, grouped by node id", body = BillsGroupedResponse)
+    )
+)]
+#[get("/list/grouped")]
+pub async fn list_grouped(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+) -> Result<Json<BillsGroupedResponse>> {
+    let node_ids: Vec<String> = state
+        .company_service
+        .get_controlled_node_ids()
+        .await?
+        .into_iter()
+        .map(|i| i.node_id)
+        .collect();
+    let mut grouped = state.bill_service.get_bills_grouped(&node_ids).await?;
+    Ok(Json(BillsGroupedResponse {
+        groups: node_ids
+            .into_iter()
+            .map(|node_id| BillsGroupWeb {
+                bills: grouped
+                    .remove(&node_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|b| b.into_web())
+                    .collect(),
+                node_id,
+            })
+            .collect(),
+    }))
+}
+
+#[utoipa::path(
+    tag = "Bills Light",
+    path = "/bill/list/participant/{node_id}",
+    description = "Get all bills in a light version, in which the given node id is a participant",
+    params(
+        ("node_id" = String, Path, description = "The node id of the participant")
+    ),
+    responses(
+        (status = 200, description = "List of bills light", body = BillsResponse<LightBitcreditBillWeb>)
+    )
+)]
+#[get("/list/participant/<node_id>")]
+pub async fn list_by_participant(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    node_id: &str,
+) -> Result<Json<BillsResponse<LightBitcreditBillWeb>>> {
+    let bills = state
+        .bill_service
+        .get_bills_by_participant(node_id, &get_current_identity_node_id(state).await)
+        .await?;
+    Ok(Json(BillsResponse {
+        bills: bills.into_iter().map(|b| b.into_web()).collect(),
+    }))
+}
+
+#[utoipa::path(
+    tag = "Bills Light",
+    path = "/bill/list/maturity",
+    description = "Get all bills in a light version, which mature between the given dates - bills with a malformed maturity date are excluded",
+    params(
+        ("from" = Option<String>, Query, description = "The earliest maturity date, e.g. 2025-01-01"),
+        ("to" = Option<String>, Query, description = "The latest maturity date, e.g. 2025-01-31")
+    ),
+    responses(
+        (status = 200, description = "List of bills light", body = BillsResponse<LightBitcreditBillWeb>)
+    )
+)]
+#[get("/list/maturity?<from>&<to>")]
+pub async fn list_by_maturity(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<Json<BillsResponse<LightBitcreditBillWeb>>> {
+    let from = from
+        .map(|from| util::date::date_string_to_timestamp(from, None))
+        .transpose()?;
+    let to = to
+        .map(|to| {
+            util::date::date_string_to_timestamp(to, None).map(util::date::end_of_day_as_timestamp)
+        })
+        .transpose()?;
+    let bills = state
+        .bill_service
+        .get_bills_by_maturity(&get_current_identity_node_id(state).await, from, to)
+        .await?;
     Ok(Json(BillsResponse {
         bills: bills.into_iter().map(|b| b.into_web()).collect(),
     }))
@@ -226,18 +845,25 @@ pub async fn list_light(
     tag = "Bills",
     path = "/bill/list",
     description = "Get all bill details",
+    params(
+        ("include_archived" = Option<bool>, Query, description = "Includes bills archived by the current identity when true")
+    ),
     responses(
         (status = 200, description = "List of bills", body = BillsResponse<BitcreditBillWeb>)
     )
 )]
-#[get("/list")]
+#[get("/list?<include_archived>")]
 pub async fn list(
     _identity: IdentityCheck,
     state: &State<ServiceContext>,
+    include_archived: Option<bool>,
 ) -> Result<Json<BillsResponse<BitcreditBillWeb>>> {
     let bills = state
         .bill_service
-        .get_bills(&get_current_identity_node_id(state).await)
+        .get_bills(
+            &get_current_identity_node_id(state).await,
+            include_archived.unwrap_or(false),
+        )
         .await?;
     Ok(Json(BillsResponse {
         bills: bills.into_iter().map(|b| b.into_web()).collect(),
@@ -261,9 +887,9 @@ pub async fn numbers_to_words_for_sum(
             current_timestamp,
         )
         .await?;
-    let sum = bill.data.sum;
-    let parsed_sum = currency::parse_sum(&sum)?;
-    let sum_as_words = util::numbers_to_words::encode(&parsed_sum);
+    let parsed_sum = currency::parse_sum(&bill.data.sum)?;
+    let sum_as_words =
+        util::numbers_to_words::sum_to_words(parsed_sum, &bill.data.currency, &bill.data.language);
     Ok(Json(BillNumbersToWordsForSum {
         sum: parsed_sum,
         sum_as_words,
@@ -302,6 +928,38 @@ pub async fn bill_detail(
     Ok(Json(bill_detail.into_web()))
 }
 
+#[utoipa::path(
+    tag = "Bills",
+    path = "/bill/waiting_state/{id}",
+    description = "Get only the payment the bill is currently waiting for - cheaper than the bill details for polling",
+    params(
+        ("id" = String, Path, description = "Id of the bill")
+    ),
+    responses(
+        (status = 200, description = "The current waiting state of the bill", body = BillWaitingStateResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse)
+    )
+)]
+#[get("/waiting_state/<id>")]
+pub async fn bill_waiting_state(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<BillWaitingStateResponse>> {
+    let current_timestamp = util::date::now().timestamp() as u64;
+    let current_waiting_state = state
+        .bill_service
+        .get_bill_waiting_state(
+            id,
+            &get_current_identity_node_id(state).await,
+            current_timestamp,
+        )
+        .await?;
+    Ok(Json(BillWaitingStateResponse {
+        current_waiting_state: current_waiting_state.map(|cws| cws.into_web()),
+    }))
+}
+
 #[get("/check_payment")]
 pub async fn check_payment(
     _identity: IdentityCheck,
@@ -341,6 +999,17 @@ pub async fn upload_file(
     Ok(Json(file_upload_response.into_web()))
 }
 
+#[utoipa::path(
+    tag = "Bills",
+    path = "/bill/issue",
+    description = "Issues a new bill with the given data",
+    request_body(description = "The data of the bill to issue", content((BitcreditBillPayload))),
+    responses(
+        (status = 200, description = "The id of the issued bill", body = BillId),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[post("/issue", format = "json", data = "<bill_payload>")]
 pub async fn issue_bill(
     _identity: IdentityCheck,
@@ -369,6 +1038,7 @@ pub async fn issue_bill(
             drawer_public_data: drawer_public_data.clone(),
             drawer_keys: drawer_keys.clone(),
             timestamp,
+            payment_deadline_seconds: bill_payload.payment_deadline_seconds,
         })
         .await?;
 
@@ -377,6 +1047,18 @@ pub async fn issue_bill(
     }))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/offer_to_sell",
+    description = "Offers the given bill to sell to the given buyer",
+    request_body(description = "The bill, buyer, sum and currency of the offer", content((OfferToSellBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Bill was offered to sell", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/offer_to_sell", format = "json", data = "<offer_to_sell_payload>")]
 pub async fn offer_to_sell_bill(
     _identity: IdentityCheck,
@@ -394,7 +1076,10 @@ pub async fn offer_to_sell_bill(
         }
     };
 
-    let sum = currency::parse_sum(&offer_to_sell_payload.sum)?;
+    let (sum, sum_currency) = currency::parse_sum_in_currency(
+        &offer_to_sell_payload.sum,
+        &offer_to_sell_payload.currency,
+    )?;
     let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
     let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
 
@@ -402,20 +1087,29 @@ pub async fn offer_to_sell_bill(
         .bill_service
         .execute_bill_action(
             &offer_to_sell_payload.bill_id,
-            BillAction::OfferToSell(
-                public_data_buyer.clone(),
-                sum,
-                offer_to_sell_payload.currency.clone(),
-            ),
+            BillAction::OfferToSell(public_data_buyer.clone(), sum, sum_currency),
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/endorse",
+    description = "Endorses the given bill to the given endorsee",
+    request_body(description = "The bill and the endorsee", content((EndorseBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Bill was endorsed", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/endorse", format = "json", data = "<endorse_bill_payload>")]
 pub async fn endorse_bill(
     _identity: IdentityCheck,
@@ -443,12 +1137,25 @@ pub async fn endorse_bill(
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/request_to_pay",
+    description = "Requests payment of the given bill from the drawee",
+    request_body(description = "The bill and the currency to pay in", content((RequestToPayBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Bill was requested to pay", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put(
     "/request_to_pay",
     format = "json",
@@ -470,12 +1177,100 @@ pub async fn request_to_pay_bill(
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
+        )
+        .await?;
+
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/request_to_accept_and_pay",
+    description = "Requests acceptance and payment of the given bill from the drawee in a single action",
+    request_body(description = "The bill and the currency to pay in", content((RequestToPayBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Bill was requested to accept and pay", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[put(
+    "/request_to_accept_and_pay",
+    format = "json",
+    data = "<request_to_pay_bill_payload>"
+)]
+pub async fn request_to_accept_and_pay_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    request_to_pay_bill_payload: Json<RequestToPayBitcreditBillPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .execute_bill_action(
+            &request_to_pay_bill_payload.bill_id,
+            BillAction::RequestAcceptanceAndPayment(request_to_pay_bill_payload.currency.clone()),
+            &signer_public_data,
+            &signer_keys,
+            timestamp,
+            None,
+        )
+        .await?;
+
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/attach_files",
+    description = "Attaches the given uploaded files to the given bill",
+    request_body(description = "The bill and the ids of the uploaded files", content((AttachFilesToBillPayload))),
+    responses(
+        (status = 200, description = "Files were attached to the bill", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[put("/attach_files", format = "json", data = "<attach_files_payload>")]
+pub async fn attach_files(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    attach_files_payload: Json<AttachFilesToBillPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .attach_files(
+            &attach_files_payload.bill_id,
+            &attach_files_payload.file_upload_ids,
+            &signer_public_data,
+            &signer_keys,
+            timestamp,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/request_to_accept",
+    description = "Requests acceptance of the given bill from the drawee",
+    request_body(description = "The bill to request acceptance for", content((RequestToAcceptBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Bill was requested to accept", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put(
     "/request_to_accept",
     format = "json",
@@ -493,16 +1288,29 @@ pub async fn request_to_accept_bill(
         .bill_service
         .execute_bill_action(
             &request_to_accept_bill_payload.bill_id,
-            BillAction::RejectAcceptance,
+            BillAction::RejectAcceptance(None),
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/accept",
+    description = "Accepts the given bill as the drawee",
+    request_body(description = "The bill to accept", content((AcceptBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Bill was accepted", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/accept", format = "json", data = "<accept_bill_payload>")]
 pub async fn accept_bill(
     _identity: IdentityCheck,
@@ -520,12 +1328,24 @@ pub async fn accept_bill(
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/request_to_mint",
+    description = "Requests minting of the given bill - not implemented yet",
+    request_body(description = "The bill and the mint to request minting from", content((RequestToMintBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Request was received", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put(
     "/request_to_mint",
     format = "json",
@@ -542,6 +1362,18 @@ pub async fn request_to_mint_bill(
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/mint",
+    description = "Mints the given bill with the given mint",
+    request_body(description = "The bill, mint, sum and currency", content((MintBitcreditBillPayload))),
+    responses(
+        (status = 200, description = "Bill was minted", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/mint", format = "json", data = "<mint_bill_payload>")]
 pub async fn mint_bill(
     _identity: IdentityCheck,
@@ -550,7 +1382,8 @@ pub async fn mint_bill(
 ) -> Result<Json<SuccessResponse>> {
     info!("mint bill called with payload {mint_bill_payload:?} - not implemented");
     let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
-    let sum = currency::parse_sum(&mint_bill_payload.sum)?;
+    let (sum, sum_currency) =
+        currency::parse_sum_in_currency(&mint_bill_payload.sum, &mint_bill_payload.currency)?;
 
     let public_mint_node = match state
         .contact_service
@@ -568,16 +1401,29 @@ pub async fn mint_bill(
         .bill_service
         .execute_bill_action(
             &mint_bill_payload.bill_id,
-            BillAction::Mint(public_mint_node, sum, mint_bill_payload.currency.clone()),
+            BillAction::Mint(public_mint_node, sum, sum_currency),
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
     Ok(Json(SuccessResponse::new()))
 }
 
 // Rejection
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/reject_to_accept",
+    description = "Rejects to accept the given bill as the drawee",
+    request_body(description = "The bill to reject", content((RejectActionBillPayload))),
+    responses(
+        (status = 200, description = "Acceptance was rejected", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/reject_to_accept", format = "json", data = "<reject_payload>")]
 pub async fn reject_to_accept_bill(
     _identity: IdentityCheck,
@@ -591,16 +1437,29 @@ pub async fn reject_to_accept_bill(
         .bill_service
         .execute_bill_action(
             &reject_payload.bill_id,
-            BillAction::RejectAcceptance,
+            BillAction::RejectAcceptance(reject_payload.reason.clone()),
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/reject_to_pay",
+    description = "Rejects to pay the given bill as the drawee",
+    request_body(description = "The bill to reject", content((RejectActionBillPayload))),
+    responses(
+        (status = 200, description = "Payment was rejected", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/reject_to_pay", format = "json", data = "<reject_payload>")]
 pub async fn reject_to_pay_bill(
     _identity: IdentityCheck,
@@ -614,16 +1473,29 @@ pub async fn reject_to_pay_bill(
         .bill_service
         .execute_bill_action(
             &reject_payload.bill_id,
-            BillAction::RejectPayment,
+            BillAction::RejectPayment(reject_payload.reason.clone()),
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/reject_to_buy",
+    description = "Rejects to buy the given bill as the buyer",
+    request_body(description = "The bill to reject", content((RejectActionBillPayload))),
+    responses(
+        (status = 200, description = "Buying was rejected", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/reject_to_buy", format = "json", data = "<reject_payload>")]
 pub async fn reject_to_buy_bill(
     _identity: IdentityCheck,
@@ -637,16 +1509,224 @@ pub async fn reject_to_buy_bill(
         .bill_service
         .execute_bill_action(
             &reject_payload.bill_id,
-            BillAction::RejectBuying,
+            BillAction::RejectBuying(reject_payload.reason.clone()),
+            &signer_public_data,
+            &signer_keys,
+            timestamp,
+            None,
+        )
+        .await?;
+
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/cancel_offer_to_sell",
+    description = "Withdraws the offer to sell of the given bill as the seller, as long as it wasn't paid or expired",
+    request_body(description = "The bill to cancel the offer to sell for", content((RejectActionBillPayload))),
+    responses(
+        (status = 200, description = "Offer to sell was cancelled", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[put("/cancel_offer_to_sell", format = "json", data = "<cancel_payload>")]
+pub async fn cancel_offer_to_sell_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    cancel_payload: Json<RejectActionBillPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .execute_bill_action(
+            &cancel_payload.bill_id,
+            BillAction::CancelOfferToSell,
+            &signer_public_data,
+            &signer_keys,
+            timestamp,
+            None,
+        )
+        .await?;
+
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/cancel_request_to_accept",
+    description = "Withdraws the request to accept of the given bill as the requester, as long as it wasn't accepted, rejected or expired",
+    request_body(description = "The bill to cancel the request to accept for", content((RejectActionBillPayload))),
+    responses(
+        (status = 200, description = "Request to accept was cancelled", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[put(
+    "/cancel_request_to_accept",
+    format = "json",
+    data = "<cancel_payload>"
+)]
+pub async fn cancel_request_to_accept_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    cancel_payload: Json<RejectActionBillPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .execute_bill_action(
+            &cancel_payload.bill_id,
+            BillAction::CancelRequestToAccept,
+            &signer_public_data,
+            &signer_keys,
+            timestamp,
+            None,
+        )
+        .await?;
+
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/mark_paid_externally",
+    description = "Marks the given bill as paid outside of the chain as the holder, e.g. via bank transfer. This is an attestation by the holder with the given reference, not a cryptographic proof of payment",
+    request_body(description = "The bill and the reference of the external payment", content((MarkPaidExternallyPayload))),
+    responses(
+        (status = 200, description = "Bill was marked as paid", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[put("/mark_paid_externally", format = "json", data = "<mark_paid_payload>")]
+pub async fn mark_paid_externally_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    mark_paid_payload: Json<MarkPaidExternallyPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .execute_bill_action(
+            &mark_paid_payload.bill_id,
+            BillAction::MarkPaidExternally(mark_paid_payload.reference.to_owned()),
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/acknowledge",
+    description = "Attests, that the caller has seen the given bill, as its holder, or drawee. This is not an acceptance and carries no payment obligation - acknowledging a bill again has no effect",
+    request_body(description = "The bill to acknowledge", content((AcknowledgeBillPayload))),
+    responses(
+        (status = 200, description = "Bill was acknowledged", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[put("/acknowledge", format = "json", data = "<acknowledge_payload>")]
+pub async fn acknowledge_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    acknowledge_payload: Json<AcknowledgeBillPayload>,
+) -> Result<Json<SuccessResponse>> {
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .execute_bill_action(
+            &acknowledge_payload.bill_id,
+            BillAction::Acknowledge,
+            &signer_public_data,
+            &signer_keys,
+            timestamp,
+            None,
+        )
+        .await?;
+
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/correct_drawee",
+    description = "Replaces the drawee of the given bill with the given contact. Only the drawer can do this and only before the bill progressed, e.g. before it was requested to accept, or endorsed",
+    request_body(description = "The bill and the node id of the new drawee", content((CorrectDraweePayload))),
+    responses(
+        (status = 200, description = "Drawee was corrected", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the new drawee is not in the contacts, or the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
+#[put("/correct_drawee", format = "json", data = "<correct_drawee_payload>")]
+pub async fn correct_drawee_bill(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    correct_drawee_payload: Json<CorrectDraweePayload>,
+) -> Result<Json<SuccessResponse>> {
+    let public_data_drawee = match state
+        .contact_service
+        .get_identity_by_node_id(&correct_drawee_payload.drawee)
+        .await
+    {
+        Ok(Some(drawee)) => drawee,
+        Ok(None) | Err(_) => {
+            return Err(BillServiceError::DraweeNotInContacts.into());
+        }
+    };
+
+    let timestamp = external::time::TimeApi::get_atomic_time().await.timestamp;
+    let (signer_public_data, signer_keys) = get_signer_public_data_and_keys(state).await?;
+
+    state
+        .bill_service
+        .execute_bill_action(
+            &correct_drawee_payload.bill_id,
+            BillAction::CorrectDrawee(public_data_drawee),
+            &signer_public_data,
+            &signer_keys,
+            timestamp,
+            None,
+        )
+        .await?;
+
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/reject_to_pay_recourse",
+    description = "Rejects to pay the recourse of the given bill as the recoursee",
+    request_body(description = "The bill to reject", content((RejectActionBillPayload))),
+    responses(
+        (status = 200, description = "Recourse payment was rejected", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put("/reject_to_pay_recourse", format = "json", data = "<reject_payload>")]
 pub async fn reject_to_pay_recourse_bill(
     _identity: IdentityCheck,
@@ -664,6 +1744,7 @@ pub async fn reject_to_pay_recourse_bill(
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
@@ -671,6 +1752,18 @@ pub async fn reject_to_pay_recourse_bill(
 }
 
 // Recourse
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/request_recourse_for_payment",
+    description = "Requests recourse for payment of the given bill from the given recoursee",
+    request_body(description = "The bill, recoursee, sum and currency", content((RequestRecourseForPaymentPayload))),
+    responses(
+        (status = 200, description = "Recourse was requested", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put(
     "/request_recourse_for_payment",
     format = "json",
@@ -681,16 +1774,31 @@ pub async fn request_to_recourse_bill_payment(
     state: &State<ServiceContext>,
     request_recourse_payload: Json<RequestRecourseForPaymentPayload>,
 ) -> Result<Json<SuccessResponse>> {
-    let sum = currency::parse_sum(&request_recourse_payload.sum)?;
+    let (sum, sum_currency) = currency::parse_sum_in_currency(
+        &request_recourse_payload.sum,
+        &request_recourse_payload.currency,
+    )?;
     request_recourse(
         state,
-        RecourseReason::Pay(sum, request_recourse_payload.currency.clone()),
+        RecourseReason::Pay(sum, sum_currency),
         &request_recourse_payload.bill_id,
         &request_recourse_payload.recoursee,
     )
     .await
 }
 
+#[utoipa::path(
+    tag = "Bill Actions",
+    path = "/bill/request_recourse_for_acceptance",
+    description = "Requests recourse for acceptance of the given bill from the given recoursee",
+    request_body(description = "The bill and the recoursee", content((RequestRecourseForAcceptancePayload))),
+    responses(
+        (status = 200, description = "Recourse was requested", body = SuccessResponse),
+        (status = 400, description = "Invalid request, e.g. the validation of the bill action failed", body = ErrorResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 500, description = "Internal error")
+    )
+)]
 #[put(
     "/request_recourse_for_acceptance",
     format = "json",
@@ -738,6 +1846,7 @@ async fn request_recourse(
             &signer_public_data,
             &signer_keys,
             timestamp,
+            None,
         )
         .await?;
 
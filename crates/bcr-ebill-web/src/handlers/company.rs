@@ -2,8 +2,8 @@ use super::Result;
 use super::middleware::IdentityCheck;
 use crate::data::{
     AddSignatoryPayload, CompaniesResponse, CompanyWeb, CreateCompanyPayload, EditCompanyPayload,
-    FromWeb, IntoWeb, ListSignatoriesResponse, RemoveSignatoryPayload, SuccessResponse,
-    TempFileWrapper, UploadFileForm, UploadFileResponse,
+    FromWeb, IntoWeb, ListSignatoriesResponse, RemoveSignatoryPayload, SignatoryHistoryResponse,
+    SuccessResponse, TempFileWrapper, UploadFileForm, UploadFileResponse,
 };
 use crate::service_context::ServiceContext;
 use bcr_ebill_api::data::{OptionalPostalAddress, PostalAddress};
@@ -38,6 +38,17 @@ pub async fn list_signatories(
     }))
 }
 
+#[get("/signatories/history/<id>")]
+pub async fn signatory_history(
+    state: &State<ServiceContext>,
+    id: &str,
+) -> Result<Json<SignatoryHistoryResponse>> {
+    let changes = state.company_service.get_signatory_history(id).await?;
+    Ok(Json(SignatoryHistoryResponse {
+        changes: changes.into_iter().map(|c| c.into_web()).collect(),
+    }))
+}
+
 #[get("/file/<id>/<file_name>")]
 pub async fn get_file(
     _identity: IdentityCheck,
@@ -2,8 +2,8 @@ use super::super::data::{EditContactPayload, NewContactPayload};
 use super::Result;
 use super::middleware::IdentityCheck;
 use crate::data::{
-    ContactTypeWeb, ContactWeb, ContactsResponse, FromWeb, IntoWeb, SuccessResponse,
-    TempFileWrapper, UploadFileForm, UploadFileResponse,
+    ContactTypeWeb, ContactWeb, ContactsResponse, FromWeb, IntoWeb, RefreshedContactWeb,
+    SuccessResponse, TempFileWrapper, UploadFileForm, UploadFileResponse,
 };
 use crate::service_context::ServiceContext;
 use bcr_ebill_api::data::{
@@ -95,6 +95,19 @@ pub async fn return_contact(
     Ok(Json(contact))
 }
 
+#[put("/refresh/<node_id>")]
+pub async fn refresh_contact_metadata(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+    node_id: &str,
+) -> Result<Json<RefreshedContactWeb>> {
+    let refreshed = state
+        .contact_service
+        .refresh_contact_metadata(node_id)
+        .await?;
+    Ok(Json(refreshed.into_web()))
+}
+
 #[delete("/remove/<node_id>")]
 pub async fn remove_contact(
     _identity: IdentityCheck,
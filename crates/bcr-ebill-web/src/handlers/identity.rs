@@ -3,7 +3,8 @@ use std::env;
 use super::Result;
 use super::middleware::IdentityCheck;
 use crate::data::{
-    ChangeIdentityPayload, FromWeb, IdentityWeb, IntoWeb, NewIdentityPayload, SeedPhrase,
+    ChangeIdentityPayload, ControlledIdentitiesResponse, FromWeb, IdentityBackupPayload,
+    IdentityWeb, IntoWeb, NewIdentityPayload, RestoreIdentityBackupForm, SeedPhrase,
     SuccessResponse, SwitchIdentity, TempFileWrapper, UploadFileForm, UploadFileResponse,
 };
 use crate::service_context::ServiceContext;
@@ -196,6 +197,25 @@ pub async fn active(state: &State<ServiceContext>) -> Result<Json<SwitchIdentity
     }))
 }
 
+#[utoipa::path(
+    tag = "Identity",
+    path = "/api/identity/controlled",
+    description = "Returns all identities the caller controls - the personal identity, followed by all companies the caller is a signatory of",
+    responses(
+        (status = 200, description = "The identities the caller controls", body = ControlledIdentitiesResponse)
+    )
+)]
+#[get("/controlled")]
+pub async fn controlled(
+    _identity: IdentityCheck,
+    state: &State<ServiceContext>,
+) -> Result<Json<ControlledIdentitiesResponse>> {
+    let identities = state.company_service.get_controlled_node_ids().await?;
+    Ok(Json(ControlledIdentitiesResponse {
+        identities: identities.into_iter().map(|i| i.into_web()).collect(),
+    }))
+}
+
 #[utoipa::path(
     tag = "Identity",
     path = "/api/identity/switch",
@@ -270,6 +290,60 @@ pub async fn recover_from_seed_phrase(
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    post,
+    tag = "Identity",
+    path = "/api/identity/keys/backup",
+    description = "Returns a backup of the seed phrase and identity data, encrypted with the given passphrase",
+    responses(
+        (status = 200, description = "The encrypted identity backup")
+    ),
+    request_body(description = "The passphrase to encrypt the backup with", content((IdentityBackupPayload)))
+)]
+#[post("/keys/backup", format = "json", data = "<payload>")]
+pub async fn export_identity_backup(
+    state: &State<ServiceContext>,
+    payload: Json<IdentityBackupPayload>,
+) -> Result<BinaryFileResponse> {
+    let file_name = format!(
+        "bitcredit_identity_backup_{}.bin",
+        format_date_string(now())
+    );
+    let bytes = state
+        .identity_service
+        .export_identity_backup(&payload.into_inner().passphrase)
+        .await?;
+    Ok(BinaryFileResponse {
+        data: bytes,
+        name: file_name,
+    })
+}
+
+#[utoipa::path(
+    post,
+    tag = "Identity",
+    path = "/api/identity/keys/restore",
+    request_body(content_type = "multipart/form-data", content = RestoreIdentityBackupForm, description = "Identity backup to restore"),
+    responses(
+        (status = 200, description = "Seed phrase and identity data have been restored")
+    )
+)]
+#[post("/keys/restore", data = "<data>")]
+pub async fn restore_identity_backup(
+    state: &State<ServiceContext>,
+    data: Form<RestoreIdentityBackupForm<'_>>,
+) -> Result<Json<SuccessResponse>> {
+    let bytes = TempFileWrapper(&data.file)
+        .get_contents()
+        .await
+        .map_err(Error::Io)?;
+    state
+        .identity_service
+        .restore_identity_backup(&bytes, &data.passphrase, data.force)
+        .await?;
+    Ok(Json(SuccessResponse::new()))
+}
+
 #[utoipa::path(
     tag = "Identity",
     path = "/api/identity/backup",
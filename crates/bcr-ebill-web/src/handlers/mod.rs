@@ -1,8 +1,9 @@
 use crate::CONFIG;
 use crate::data::{
-    BalanceResponse, CurrenciesResponse, CurrencyResponse, FromWeb, GeneralSearchFilterPayload,
-    GeneralSearchResponse, IntoWeb, OverviewBalanceResponse, OverviewResponse, StatusResponse,
-    SuccessResponse,
+    BalanceResponse, BalanceWithFiatResponse, CurrenciesResponse, CurrencyResponse,
+    ExposureResponse, FiatRateResponse, FromWeb, GeneralSearchFilterPayload, GeneralSearchResponse,
+    HealthResponse, IntoWeb, OverviewBalanceResponse, OverviewBalanceWithFiatResponse,
+    OverviewResponse, OverviewWithFiatResponse, StatusResponse, SuccessResponse,
 };
 use crate::router::ErrorResponse;
 use crate::service_context::ServiceContext;
@@ -15,7 +16,9 @@ use bcr_ebill_api::{
 use bill::get_current_identity_node_id;
 use log::error;
 use rocket::Response;
-use rocket::{Shutdown, State, fs::NamedFile, get, http::ContentType, post, serde::json::Json};
+use rocket::{
+    Shutdown, State, fs::NamedFile, get, http::ContentType, post, put, serde::json::Json,
+};
 use rocket::{http::Status, response::Responder};
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
@@ -56,6 +59,26 @@ pub async fn status() -> Result<Json<StatusResponse>> {
     }))
 }
 
+#[utoipa::path(
+    tag = "Health",
+    path = "/health",
+    description = "Checks, whether the database is reachable and at least one Nostr relay is connected",
+    responses(
+        (status = 200, description = "The node is ready", body = HealthResponse),
+        (status = 503, description = "The node is not ready", body = HealthResponse)
+    )
+)]
+#[get("/")]
+pub async fn health(state: &State<ServiceContext>) -> (Status, Json<HealthResponse>) {
+    let report = state.health_service.check().await;
+    let status = if report.ready {
+        Status::Ok
+    } else {
+        Status::ServiceUnavailable
+    };
+    (status, Json(report.into_web()))
+}
+
 #[get("/")]
 pub async fn exit(
     shutdown: Shutdown,
@@ -67,6 +90,36 @@ pub async fn exit(
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Jobs",
+    path = "/jobs/pause",
+    description = "Pauses the background jobs, e.g. for maintenance - ticks of the job runner are skipped until the jobs are resumed",
+    responses(
+        (status = 200, description = "The jobs are paused", body = SuccessResponse)
+    )
+)]
+#[put("/pause")]
+pub async fn pause_jobs(state: &State<ServiceContext>) -> Result<Json<SuccessResponse>> {
+    log::info!("Pausing background jobs");
+    state.job_control.pause_jobs();
+    Ok(Json(SuccessResponse::new()))
+}
+
+#[utoipa::path(
+    tag = "Jobs",
+    path = "/jobs/resume",
+    description = "Resumes the paused background jobs, starting with the next tick of the job runner",
+    responses(
+        (status = 200, description = "The jobs are resumed", body = SuccessResponse)
+    )
+)]
+#[put("/resume")]
+pub async fn resume_jobs(state: &State<ServiceContext>) -> Result<Json<SuccessResponse>> {
+    log::info!("Resuming background jobs");
+    state.job_control.resume_jobs();
+    Ok(Json(SuccessResponse::new()))
+}
+
 #[get("/")]
 pub async fn currencies(_state: &State<ServiceContext>) -> Result<Json<CurrenciesResponse>> {
     Ok(Json(CurrenciesResponse {
@@ -139,6 +192,77 @@ pub async fn overview(
     }))
 }
 
+#[get("/exposure")]
+pub async fn exposure(state: &State<ServiceContext>) -> Result<Json<ExposureResponse>> {
+    let result = state
+        .bill_service
+        .get_total_exposure(&get_current_identity_node_id(state).await)
+        .await?;
+
+    Ok(Json(ExposureResponse {
+        balances: OverviewBalanceResponse {
+            payee: BalanceResponse {
+                sum: result.balances.payee.sum,
+            },
+            payer: BalanceResponse {
+                sum: result.balances.payer.sum,
+            },
+            contingent: BalanceResponse {
+                sum: result.balances.contingent.sum,
+            },
+        },
+        net_position: result.net_position,
+        accepted_count: result.accepted_count,
+        awaiting_acceptance_count: result.awaiting_acceptance_count,
+        overdue_count: result.overdue_count,
+    }))
+}
+
+#[get("/fiat?<currency>&<fiat_code>")]
+pub async fn overview_with_fiat(
+    currency: &str,
+    fiat_code: &str,
+    state: &State<ServiceContext>,
+) -> Result<Json<OverviewWithFiatResponse>> {
+    if !VALID_CURRENCIES.contains(&currency) {
+        return Err(
+            Error::Validation(bcr_ebill_api::util::ValidationError::InvalidCurrency).into(),
+        );
+    }
+    let result = state
+        .bill_service
+        .get_bill_balances_with_fiat(
+            currency,
+            fiat_code,
+            &get_current_identity_node_id(state).await,
+        )
+        .await?;
+
+    Ok(Json(OverviewWithFiatResponse {
+        currency: currency.to_owned(),
+        fiat_code: fiat_code.to_owned(),
+        balances: OverviewBalanceWithFiatResponse {
+            payee: BalanceWithFiatResponse {
+                sum: result.payee.sum,
+                fiat_sum: result.payee.fiat_sum,
+            },
+            payer: BalanceWithFiatResponse {
+                sum: result.payer.sum,
+                fiat_sum: result.payer.fiat_sum,
+            },
+            contingent: BalanceWithFiatResponse {
+                sum: result.contingent.sum,
+                fiat_sum: result.contingent.fiat_sum,
+            },
+        },
+        fiat_rate: result.fiat_rate.map(|rate| FiatRateResponse {
+            fiat_code: rate.fiat_code,
+            rate: rate.rate,
+            timestamp: rate.timestamp,
+        }),
+    }))
+}
+
 #[utoipa::path(
     tag = "General Search",
     path = "/search",
@@ -306,15 +430,21 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for ValidationError {
             bcr_ebill_api::util::ValidationError::RequestAlreadyExpired
                 | bcr_ebill_api::util::ValidationError::FieldEmpty(_)
                 | bcr_ebill_api::util::ValidationError::InvalidSum
+                | bcr_ebill_api::util::ValidationError::InvalidPaymentDeadline
                 | bcr_ebill_api::util::ValidationError::InvalidCurrency
+                | bcr_ebill_api::util::ValidationError::InvalidCountryCode(_)
+                | bcr_ebill_api::util::ValidationError::FiatRateUnavailable
                 | bcr_ebill_api::util::ValidationError::InvalidPaymentAddress
                 | bcr_ebill_api::util::ValidationError::InvalidDate
                 | bcr_ebill_api::util::ValidationError::IssueDateAfterMaturityDate
                 | bcr_ebill_api::util::ValidationError::MaturityDateInThePast
                 | bcr_ebill_api::util::ValidationError::InvalidFileUploadId
+                | bcr_ebill_api::util::ValidationError::InvalidNotificationCursor
                 | bcr_ebill_api::util::ValidationError::InvalidBillType
                 | bcr_ebill_api::util::ValidationError::InvalidContentType
                 | bcr_ebill_api::util::ValidationError::InvalidContactType
+                | bcr_ebill_api::util::ValidationError::ContactsHaveDifferentNodeIds
+                | bcr_ebill_api::util::ValidationError::MissingCsvColumn(_)
                 | bcr_ebill_api::util::ValidationError::DraweeCantBePayee
                 | bcr_ebill_api::util::ValidationError::EndorserCantBeEndorsee
                 | bcr_ebill_api::util::ValidationError::BuyerCantBeSeller
@@ -348,14 +478,38 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for ValidationError {
                 | bcr_ebill_api::util::ValidationError::RecourseeNotPastHolder
                 | bcr_ebill_api::util::ValidationError::CallerIsNotDrawee
                 | bcr_ebill_api::util::ValidationError::CallerIsNotBuyer
+                | bcr_ebill_api::util::ValidationError::CallerIsNotSeller
+                | bcr_ebill_api::util::ValidationError::CallerIsNotRequester
+                | bcr_ebill_api::util::ValidationError::CallerIsNotHolderOrDrawee
+                | bcr_ebill_api::util::ValidationError::IssuePolicyViolation(_)
+                | bcr_ebill_api::util::ValidationError::InvalidBillQrPayload
+                | bcr_ebill_api::util::ValidationError::CallerIsNotDrawer
+                | bcr_ebill_api::util::ValidationError::DraweeCorrectionNotAllowed
+                | bcr_ebill_api::util::ValidationError::BillWasNotRequestedToMint
+                | bcr_ebill_api::util::ValidationError::CallerIsNotMint
+                | bcr_ebill_api::util::ValidationError::BillHasNoOpenMintQuote
+                | bcr_ebill_api::util::ValidationError::CallerIsNotMintRequester
+                | bcr_ebill_api::util::ValidationError::MintQuoteExpired
                 | bcr_ebill_api::util::ValidationError::CallerIsNotRecoursee
                 | bcr_ebill_api::util::ValidationError::RequestAlreadyRejected
                 | bcr_ebill_api::util::ValidationError::BackupNotSupported
+                | bcr_ebill_api::util::ValidationError::UnknownBillBundleVersion(_)
+                | bcr_ebill_api::util::ValidationError::InvalidBillBundle
+                | bcr_ebill_api::util::ValidationError::InvalidBillView
+                | bcr_ebill_api::util::ValidationError::BillIsReadOnlyView
+                | bcr_ebill_api::util::ValidationError::UnknownIdentityBackupVersion(_)
+                | bcr_ebill_api::util::ValidationError::InvalidIdentityBackup
+                | bcr_ebill_api::util::ValidationError::IdentityAlreadyExists
                 | bcr_ebill_api::util::ValidationError::UnknownNodeId(_)
                 | bcr_ebill_api::util::ValidationError::InvalidFileName(_)
+                | bcr_ebill_api::util::ValidationError::RejectReasonTooLong(_)
+                | bcr_ebill_api::util::ValidationError::FileAlreadyAttached(_)
                 | bcr_ebill_api::util::ValidationError::FileIsTooBig(_)
+                | bcr_ebill_api::util::ValidationError::TooManyFiles(_)
+                | bcr_ebill_api::util::ValidationError::AttachmentsTooBig(_)
                 | bcr_ebill_api::util::ValidationError::InvalidSecp256k1Key(_)
                 | bcr_ebill_api::util::ValidationError::NotASignatory(_)
+                | bcr_ebill_api::util::ValidationError::SignatoryCantSign(_)
                 | bcr_ebill_api::util::ValidationError::SignatoryAlreadySignatory(_)
                 | bcr_ebill_api::util::ValidationError::SignatoryNotInContacts(_)
                 | bcr_ebill_api::util::ValidationError::CantRemoveLastSignatory
@@ -1,8 +1,15 @@
 use super::Result;
-use crate::data::{IntoWeb, NotificationWeb, SuccessResponse};
+use crate::data::{
+    FromWeb, IntoWeb, MarkAllNotificationsDonePayload, MarkAllNotificationsDoneResponse,
+    NotificationsResponse, RelayStatusResponse, SuccessResponse,
+};
+use crate::handlers::bill::get_current_identity_node_id;
+use crate::router::ErrorResponse;
 use crate::service_context::ServiceContext;
-use bcr_ebill_api::NotificationFilter;
-use bcr_ebill_api::data::notification::Notification;
+use bcr_ebill_api::data::notification::NotificationType;
+use bcr_ebill_api::service::Error;
+use bcr_ebill_api::util::ValidationError;
+use bcr_ebill_api::{NotificationCursor, NotificationFilter};
 use rocket::response::stream::{Event, EventStream};
 use rocket::serde::json::Json;
 use rocket::{State, get, post};
@@ -13,17 +20,22 @@ use serde_json::Value;
     tag = "Notifications",
     description = "Get all active notifications",
     responses(
-        (status = 200, description = "List of notifications", body = Vec<NotificationWeb>)
+        (status = 200, description = "A page of notifications, newest first, and the cursor for the next page", body = NotificationsResponse),
+        (status = 400, description = "Invalid cursor", body = ErrorResponse)
     ),
     params(
         ("active" = Option<bool>, Query, description = "Returns only active notifications when true, inactive when false and all when left out"),
         ("reference_id" = Option<String>, Query, description = "The id of the entity to filter by (eg. a bill id)"),
         ("notification_type" = Option<String>, Query, description = "The type of notifications to return (eg. Bill)"),
         ("limit" = Option<i64>, Query, description = "The max number of notifications to return"),
-        ("offset" = Option<i64>, Query, description = "The number of notifications to skip at the start of the result")
+        ("offset" = Option<i64>, Query, description = "The number of notifications to skip at the start of the result"),
+        ("only_unread" = Option<bool>, Query, description = "Returns only notifications, which were not marked as done yet, when true"),
+        ("after" = Option<String>, Query, description = "The next_cursor of the previous page, to fetch the next page. Unlike the offset, it's not affected by new notifications")
     )
 )]
-#[get("/notifications?<active>&<reference_id>&<notification_type>&<limit>&<offset>")]
+#[get(
+    "/notifications?<active>&<reference_id>&<notification_type>&<limit>&<offset>&<only_unread>&<after>"
+)]
 pub async fn list_notifications(
     state: &State<ServiceContext>,
     active: Option<bool>,
@@ -31,8 +43,16 @@ pub async fn list_notifications(
     notification_type: Option<String>,
     limit: Option<i64>,
     offset: Option<i64>,
-) -> Result<Json<Vec<NotificationWeb>>> {
-    let notifications: Vec<Notification> = state
+    only_unread: Option<bool>,
+    after: Option<String>,
+) -> Result<Json<NotificationsResponse>> {
+    let after = match after {
+        Some(cursor) => Some(NotificationCursor::decode(&cursor).ok_or(Error::Validation(
+            ValidationError::InvalidNotificationCursor,
+        ))?),
+        None => None,
+    };
+    let page = state
         .notification_service
         .get_client_notifications(NotificationFilter {
             active,
@@ -40,11 +60,11 @@ pub async fn list_notifications(
             notification_type,
             limit,
             offset,
+            only_unread: only_unread.unwrap_or(false),
+            after,
         })
         .await?;
-    Ok(Json(
-        notifications.into_iter().map(|n| n.into_web()).collect(),
-    ))
+    Ok(Json(page.into_web()))
 }
 
 #[utoipa::path(
@@ -69,6 +89,71 @@ pub async fn mark_notification_done(
     Ok(Json(SuccessResponse::new()))
 }
 
+#[utoipa::path(
+    tag = "Notifications",
+    description = "Marks all active notifications of the current identity as done, optionally only the ones of the given type",
+    request_body(description = "The type of notifications to mark as done", content = MarkAllNotificationsDonePayload),
+    responses(
+        (status = 200, description = "Notifications set to done", body = MarkAllNotificationsDoneResponse)
+    )
+)]
+#[post("/notifications/done", format = "json", data = "<payload>")]
+pub async fn mark_all_notifications_done(
+    state: &State<ServiceContext>,
+    payload: Json<MarkAllNotificationsDonePayload>,
+) -> Result<Json<MarkAllNotificationsDoneResponse>> {
+    let node_id = get_current_identity_node_id(state).await;
+    let marked = state
+        .notification_service
+        .mark_all_as_done(
+            &node_id,
+            payload
+                .into_inner()
+                .notification_type
+                .map(NotificationType::from_web),
+        )
+        .await?;
+    Ok(Json(MarkAllNotificationsDoneResponse { marked }))
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    description = "Marks all active notifications of the current identity for the given bill as done",
+    params(
+        ("bill_id" = String, description = "Id of the bill to mark the notifications as done for")
+    ),
+    responses(
+        (status = 200, description = "Notifications set to done", body = MarkAllNotificationsDoneResponse)
+    )
+)]
+#[post("/notifications/bill/<bill_id>/done")]
+pub async fn mark_bill_notifications_done(
+    state: &State<ServiceContext>,
+    bill_id: &str,
+) -> Result<Json<MarkAllNotificationsDoneResponse>> {
+    let node_id = get_current_identity_node_id(state).await;
+    let marked = state
+        .notification_service
+        .mark_bill_notifications_done(bill_id, &node_id)
+        .await?;
+    Ok(Json(MarkAllNotificationsDoneResponse { marked }))
+}
+
+#[utoipa::path(
+    tag = "Notifications",
+    description = "Returns the delivery statistics and health of all configured Nostr relays",
+    responses(
+        (status = 200, description = "The status of the relays", body = RelayStatusResponse)
+    )
+)]
+#[get("/notifications/relays")]
+pub async fn relay_status(state: &State<ServiceContext>) -> Result<Json<RelayStatusResponse>> {
+    let relays = state.notification_service.get_relay_status().await?;
+    Ok(Json(RelayStatusResponse {
+        relays: relays.into_iter().map(|r| r.into_web()).collect(),
+    }))
+}
+
 #[utoipa::path(
     tag = "Push notifications",
     description = "Subscribe to push notifications via websocket",
@@ -1,8 +1,8 @@
 use crate::CONFIG;
 use crate::service_context::ServiceContext;
-use bcr_ebill_api::util::date::now;
+use bcr_ebill_api::util::{date::now, job::JobSchedule};
 use log::{error, info};
-use std::time::Duration;
+use std::{future::Future, pin::Pin, time::Duration};
 use tokio::{
     sync::broadcast,
     time::{interval, sleep},
@@ -25,7 +25,9 @@ pub async fn run(
     loop {
         tokio::select! {
                 _ = check_interval_tick.tick() => {
-                    run_jobs(&service_context.clone()).await;
+                    if !service_context.job_control.run_tick(run_jobs(&service_context)).await {
+                        info!("Job runner is paused - skipping tick");
+                    }
                 },
                 _ = shutdown_jobs_client_receiver.recv() => {
                     info!("Shutting down job runner...");
@@ -36,12 +38,24 @@ pub async fn run(
 }
 
 async fn run_jobs(service_context: &ServiceContext) {
-    tokio::join!(
-        run_check_bill_payment_job(service_context.clone()),
-        run_check_bill_offer_to_sell_payment_job(service_context.clone()),
-        run_check_bill_recourse_payment_job(service_context.clone())
+    let schedule = JobSchedule::new(
+        Duration::from_secs(CONFIG.job_runner_stagger_seconds),
+        CONFIG.job_runner_max_concurrent_jobs,
     );
-    // explicitly not added to join! because we want to run this job after
+    let jobs: Vec<Pin<Box<dyn Future<Output = ()> + Send>>> = vec![
+        Box::pin(run_check_bill_payment_job(service_context.clone())),
+        Box::pin(run_check_bill_offer_to_sell_payment_job(
+            service_context.clone(),
+        )),
+        Box::pin(run_check_bill_recourse_payment_job(service_context.clone())),
+        Box::pin(run_cleanup_stale_temp_uploads_job(service_context.clone())),
+        Box::pin(run_check_bill_maturity_reminders_job(
+            service_context.clone(),
+        )),
+        Box::pin(run_process_auto_accepts_job(service_context.clone())),
+    ];
+    schedule.run(jobs).await;
+    // explicitly not added to the scheduled jobs because we want to run this job after
     // all payment jobs are done and avoid any concurrency issues.
     run_check_bill_timeouts(service_context.clone()).await;
 }
@@ -78,6 +92,47 @@ async fn run_check_bill_recourse_payment_job(service_context: ServiceContext) {
     info!("Finished running Check Bill Recourse Payment Job");
 }
 
+async fn run_cleanup_stale_temp_uploads_job(service_context: ServiceContext) {
+    info!("Running Cleanup Stale Temp Uploads Job");
+    if let Err(e) = service_context
+        .file_upload_service
+        .cleanup_stale_temp_uploads(CONFIG.temp_upload_ttl_seconds)
+        .await
+    {
+        error!("Error while running Cleanup Stale Temp Uploads Job: {e}");
+    }
+    info!("Finished running Cleanup Stale Temp Uploads Job");
+}
+
+async fn run_check_bill_maturity_reminders_job(service_context: ServiceContext) {
+    info!("Running Check Bill Maturity Reminders Job");
+    let current_time = now().timestamp();
+    if let Err(e) = service_context
+        .bill_service
+        .check_bills_maturity_reminders(
+            current_time as u64,
+            CONFIG.maturity_reminder_lead_time_seconds,
+        )
+        .await
+    {
+        error!("Error while running Check Bill Maturity Reminders Job: {e}");
+    }
+    info!("Finished running Check Bill Maturity Reminders Job");
+}
+
+async fn run_process_auto_accepts_job(service_context: ServiceContext) {
+    info!("Running Process Auto Accepts Job");
+    let current_time = now().timestamp();
+    if let Err(e) = service_context
+        .bill_service
+        .process_auto_accepts(current_time as u64)
+        .await
+    {
+        error!("Error while running Process Auto Accepts Job: {e}");
+    }
+    info!("Finished running Process Auto Accepts Job");
+}
+
 async fn run_check_bill_timeouts(service_context: ServiceContext) {
     info!("Running Check Bill Timeouts Job");
     let current_time = now().timestamp();
@@ -1,10 +1,13 @@
 use crate::service_context::create_service_context;
 use anyhow::Result;
+use bcr_ebill_api::external::rate::HttpRateProvider;
 use bcr_ebill_api::get_db_context;
+use bcr_ebill_api::service::bill_service::NoOpBillIssueValidator;
 use clap::Parser;
 use config::Config;
 use constants::SHUTDOWN_GRACE_PERIOD_MS;
 use log::{error, info};
+use std::sync::Arc;
 use tokio::{spawn, sync::broadcast};
 
 mod api_docs;
@@ -31,9 +34,38 @@ async fn main() -> Result<()> {
     // Initialize the API
     let api_config = bcr_ebill_api::Config {
         bitcoin_network: conf.bitcoin_network.clone(),
-        nostr_relay: conf.nostr_relay.clone(),
+        nostr_relays: conf.nostr_relays.clone(),
         surreal_db_connection: conf.surreal_db_connection.clone(),
         data_dir: conf.data_dir.clone(),
+        nostr_max_concurrent_events: conf.nostr_max_concurrent_events,
+        nostr_event_timeout_seconds: conf.nostr_event_timeout_seconds,
+        nostr_max_retry_delay_seconds: conf.nostr_max_retry_delay_seconds,
+        nostr_max_retries: conf.nostr_max_retries,
+        nostr_bill_event_kind: conf.nostr_bill_event_kind,
+        nostr_company_event_kind: conf.nostr_company_event_kind,
+        nostr_identity_event_kind: conf.nostr_identity_event_kind,
+        fiat_rate_provider_url: conf.fiat_rate_provider_url.clone(),
+        fee_estimator_url: conf.fee_estimator_url.clone(),
+        bitcoin_explorer_connect_timeout_seconds: conf.bitcoin_explorer_connect_timeout_seconds,
+        bitcoin_explorer_read_timeout_seconds: conf.bitcoin_explorer_read_timeout_seconds,
+        bitcoin_explorer_max_retries: conf.bitcoin_explorer_max_retries,
+        webhook_urls: conf.webhook_urls.clone(),
+        temp_upload_ttl_seconds: conf.temp_upload_ttl_seconds,
+        encrypt_temp_uploads: conf.encrypt_temp_uploads,
+        file_storage_shard_prefix_length: conf.file_storage_shard_prefix_length,
+        max_bill_chain_blocks: conf.max_bill_chain_blocks,
+        max_files_per_bill: conf.max_files_per_bill,
+        max_total_attachment_bytes: conf.max_total_attachment_bytes,
+        sell_deadline_seconds: conf.sell_deadline_seconds,
+        bill_cache_max_size: conf.bill_cache_max_size,
+        maturity_reminder_lead_time_seconds: conf.maturity_reminder_lead_time_seconds,
+        payment_confirmations_required: conf.payment_confirmations_required,
+        mempool_base_url: conf.mempool_base_url.clone(),
+        request_timeout_warning_seconds: conf.request_timeout_warning_seconds,
+        job_runner_stagger_seconds: conf.job_runner_stagger_seconds,
+        job_runner_max_concurrent_jobs: conf.job_runner_max_concurrent_jobs,
+        job_runner_bills_per_tick: conf.job_runner_bills_per_tick,
+        auto_accept_enabled: conf.auto_accept_enabled,
     };
     info!("Chosen Network: {:?}", api_config.bitcoin_network());
     bcr_ebill_api::init(api_config.clone())?;
@@ -109,6 +141,8 @@ async fn start(
         service_context_shutdown_sender,
         db,
         reboot_sender,
+        Arc::new(NoOpBillIssueValidator),
+        Arc::new(HttpRateProvider::new()),
     )
     .await?;
 
@@ -6,7 +6,7 @@ use rocket::http::Method;
 use rocket::{Build, Config, Request, Rocket, catch, catchers, routes};
 use rocket_cors::{AllowedHeaders, AllowedOrigins, CorsOptions};
 use serde::Serialize;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::CONFIG;
@@ -17,7 +17,7 @@ use rocket::fs::FileServer;
 use rocket::serde::json::Json;
 use serde_json::json;
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Debug, Clone, ToSchema)]
 pub struct ErrorResponse {
     error: &'static str,
     message: String,
@@ -34,7 +34,7 @@ impl ErrorResponse {
     }
 
     pub fn to_json_string(&self) -> String {
-        json!({ "error": self.error, "message": self.message }).to_string()
+        json!({ "error": self.error, "message": self.message, "code": self.code }).to_string()
     }
 }
 
@@ -79,8 +79,20 @@ pub fn rocket_main(conf: crate::Config, context: ServiceContext) -> Rocket<Build
         .manage(cors)
         .mount("/api/exit", routes![handlers::exit])
         .mount("/api/status", routes![handlers::status])
+        .mount("/api/health", routes![handlers::health])
+        .mount(
+            "/api/jobs",
+            routes![handlers::pause_jobs, handlers::resume_jobs],
+        )
         .mount("/api/currencies", routes![handlers::currencies])
-        .mount("/api/overview", routes![handlers::overview])
+        .mount(
+            "/api/overview",
+            routes![
+                handlers::overview,
+                handlers::overview_with_fiat,
+                handlers::exposure
+            ],
+        )
         .mount("/api/search", routes![handlers::search])
         .mount("/api/temp_file", routes![handlers::get_temp_file])
         .mount(
@@ -90,9 +102,12 @@ pub fn rocket_main(conf: crate::Config, context: ServiceContext) -> Rocket<Build
                 handlers::identity::change_identity,
                 handlers::identity::return_identity,
                 handlers::identity::active,
+                handlers::identity::controlled,
                 handlers::identity::switch,
                 handlers::identity::get_seed_phrase,
                 handlers::identity::recover_from_seed_phrase,
+                handlers::identity::export_identity_backup,
+                handlers::identity::restore_identity_backup,
                 handlers::identity::get_file,
                 handlers::identity::upload_file,
                 handlers::identity::backup_identity,
@@ -107,6 +122,7 @@ pub fn rocket_main(conf: crate::Config, context: ServiceContext) -> Rocket<Build
                 handlers::contacts::remove_contact,
                 handlers::contacts::return_contacts,
                 handlers::contacts::return_contact,
+                handlers::contacts::refresh_contact_metadata,
                 handlers::contacts::get_file,
                 handlers::contacts::upload_file,
             ],
@@ -123,6 +139,7 @@ pub fn rocket_main(conf: crate::Config, context: ServiceContext) -> Rocket<Build
                 handlers::company::add_signatory,
                 handlers::company::remove_signatory,
                 handlers::company::list_signatories,
+                handlers::company::signatory_history,
             ],
         )
         .mount(
@@ -130,14 +147,20 @@ pub fn rocket_main(conf: crate::Config, context: ServiceContext) -> Rocket<Build
             routes![
                 handlers::bill::issue_bill,
                 handlers::bill::bill_detail,
+                handlers::bill::bill_waiting_state,
                 handlers::bill::list,
                 handlers::bill::list_light,
+                handlers::bill::list_grouped,
+                handlers::bill::list_by_participant,
+                handlers::bill::list_by_maturity,
                 handlers::bill::attachment,
                 handlers::bill::upload_file,
                 handlers::bill::endorse_bill,
                 handlers::bill::request_to_accept_bill,
                 handlers::bill::accept_bill,
                 handlers::bill::request_to_pay_bill,
+                handlers::bill::request_to_accept_and_pay_bill,
+                handlers::bill::attach_files,
                 handlers::bill::offer_to_sell_bill,
                 handlers::bill::mint_bill,
                 handlers::bill::request_to_mint_bill,
@@ -146,10 +169,32 @@ pub fn rocket_main(conf: crate::Config, context: ServiceContext) -> Rocket<Build
                 handlers::bill::numbers_to_words_for_sum,
                 handlers::bill::search,
                 handlers::bill::get_past_endorsees_for_bill,
+                handlers::bill::is_past_holder_of_bill,
                 handlers::bill::get_endorsements_for_bill,
+                handlers::bill::get_bill_history,
+                handlers::bill::verify_bill_chain,
+                handlers::bill::repair_bill_chain,
+                handlers::bill::preview_notification,
+                handlers::bill::validate_bill_action,
+                handlers::bill::resend_bill_event,
+                handlers::bill::set_bill_hold,
+                handlers::bill::clear_bill_hold,
+                handlers::bill::archive_bill,
+                handlers::bill::unarchive_bill,
+                handlers::bill::set_bill_note,
+                handlers::bill::list_auto_accept_rules,
+                handlers::bill::add_auto_accept_rule,
+                handlers::bill::remove_auto_accept_rule,
+                handlers::bill::export_bundle,
+                handlers::bill::import_bundle,
                 handlers::bill::reject_to_accept_bill,
                 handlers::bill::reject_to_pay_bill,
                 handlers::bill::reject_to_buy_bill,
+                handlers::bill::cancel_offer_to_sell_bill,
+                handlers::bill::cancel_request_to_accept_bill,
+                handlers::bill::mark_paid_externally_bill,
+                handlers::bill::acknowledge_bill,
+                handlers::bill::correct_drawee_bill,
                 handlers::bill::reject_to_pay_recourse_bill,
                 handlers::bill::request_to_recourse_bill_payment,
                 handlers::bill::request_to_recourse_bill_acceptance,
@@ -167,6 +212,9 @@ pub fn rocket_main(conf: crate::Config, context: ServiceContext) -> Rocket<Build
             routes![
                 handlers::notifications::list_notifications,
                 handlers::notifications::mark_notification_done,
+                handlers::notifications::mark_all_notifications_done,
+                handlers::notifications::mark_bill_notifications_done,
+                handlers::notifications::relay_status,
                 handlers::notifications::websocket,
                 handlers::notifications::sse,
                 handlers::notifications::trigger_msg,
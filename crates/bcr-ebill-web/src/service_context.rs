@@ -1,14 +1,21 @@
 use bcr_ebill_api::external::bitcoin::BitcoinClient;
+use bcr_ebill_api::external::fiat::FiatRateClient;
+use bcr_ebill_api::external::rate::RateProviderApi;
 use bcr_ebill_api::service::backup_service::{BackupService, BackupServiceApi};
-use bcr_ebill_api::service::bill_service::{BillServiceApi, service::BillService};
+use bcr_ebill_api::service::bill_service::{
+    BillIssueValidator, BillServiceApi, service::BillService,
+};
 use bcr_ebill_api::service::company_service::{CompanyService, CompanyServiceApi};
 use bcr_ebill_api::service::contact_service::{ContactService, ContactServiceApi};
 use bcr_ebill_api::service::file_upload_service::{FileUploadService, FileUploadServiceApi};
+use bcr_ebill_api::service::health_service::{HealthService, HealthServiceApi};
 use bcr_ebill_api::service::identity_service::{IdentityService, IdentityServiceApi};
 use bcr_ebill_api::service::notification_service::{
-    NostrConsumer, create_nostr_clients, create_nostr_consumer, create_notification_service,
+    NostrConsumer, NotificationJsonTransportApi, create_nostr_clients, create_nostr_consumer,
+    create_notification_service, create_webhook_dispatcher,
 };
 use bcr_ebill_api::service::search_service::{SearchService, SearchServiceApi};
+use bcr_ebill_api::util::job::JobControl;
 use bcr_ebill_api::{Config, DbContext, SurrealDbConfig, service::Result};
 use bcr_ebill_transport::{
     NotificationServiceApi,
@@ -34,6 +41,8 @@ pub struct ServiceContext {
     pub push_service: Arc<dyn PushApi>,
     pub current_identity: Arc<RwLock<SwitchIdentityState>>,
     pub backup_service: Arc<dyn BackupServiceApi>,
+    pub health_service: Arc<dyn HealthServiceApi>,
+    pub job_control: JobControl,
 }
 
 /// A structure describing the currently selected identity between the personal and multiple
@@ -76,44 +85,63 @@ impl ServiceContext {
 }
 
 /// building up the service context dependencies here for now. Later we can modularize this
-/// and make it more flexible.
+/// and make it more flexible. Deployments can plug in their own rules for issuing bills with the
+/// given `bill_issue_validator` and their own source of exchange rates with the given
+/// `rate_provider`.
 pub async fn create_service_context(
     local_node_id: &str,
     config: Config,
     shutdown_sender: broadcast::Sender<bool>,
     db: DbContext,
     reboot_sender: watch::Sender<bool>,
+    bill_issue_validator: Arc<dyn BillIssueValidator>,
+    rate_provider: Arc<dyn RateProviderApi>,
 ) -> Result<ServiceContext> {
+    let nostr_clients =
+        create_nostr_clients(&config, db.identity_store.clone(), db.company_store.clone()).await?;
     let contact_service = Arc::new(ContactService::new(
         db.contact_store.clone(),
         db.file_upload_store.clone(),
         db.identity_store.clone(),
+        nostr_clients
+            .iter()
+            .map(|c| c.clone() as Arc<dyn NotificationJsonTransportApi>)
+            .collect(),
     ));
-    let bitcoin_client = Arc::new(BitcoinClient::new());
+    let bitcoin_client = Arc::new(BitcoinClient::from_config(&config));
+    let fiat_rate_client = Arc::new(FiatRateClient::new(rate_provider));
 
-    let nostr_clients =
-        create_nostr_clients(&config, db.identity_store.clone(), db.company_store.clone()).await?;
+    let webhook_dispatcher = create_webhook_dispatcher(&config, db.identity_store.clone()).await?;
     let notification_service = create_notification_service(
         nostr_clients.clone(),
         db.notification_store.clone(),
         contact_service.clone(),
         db.queued_message_store.clone(),
-        &config.nostr_relay,
+        &config.nostr_relay(),
+        config.nostr_max_retry_delay_seconds,
+        config.nostr_max_retries,
+        webhook_dispatcher.clone(),
     )
     .await?;
 
-    let bill_service = Arc::new(BillService::new(
-        db.bill_store.clone(),
-        db.bill_blockchain_store.clone(),
-        db.identity_store.clone(),
-        db.file_upload_store.clone(),
-        bitcoin_client,
-        notification_service.clone(),
-        db.identity_chain_store.clone(),
-        db.company_chain_store.clone(),
-        db.contact_store.clone(),
-        db.company_store.clone(),
-    ));
+    let bill_service = Arc::new(
+        BillService::new(
+            db.bill_store.clone(),
+            db.bill_blockchain_store.clone(),
+            db.identity_store.clone(),
+            db.file_upload_store.clone(),
+            bitcoin_client,
+            fiat_rate_client,
+            notification_service.clone(),
+            db.identity_chain_store.clone(),
+            db.company_chain_store.clone(),
+            db.contact_store.clone(),
+            db.company_store.clone(),
+            db.bill_template_store.clone(),
+            db.bill_auto_accept_store.clone(),
+        )
+        .with_issue_validator(bill_issue_validator),
+    );
     let identity_service = IdentityService::new(
         db.identity_store.clone(),
         db.file_upload_store.clone(),
@@ -140,6 +168,8 @@ pub async fn create_service_context(
         push_service.clone(),
         db.bill_blockchain_store.clone(),
         db.bill_store.clone(),
+        db.bill_auto_accept_store.clone(),
+        webhook_dispatcher,
     )
     .await?;
 
@@ -149,6 +179,8 @@ pub async fn create_service_context(
         Arc::new(company_service.clone()),
     );
 
+    let health_service = HealthService::new(db.backup_store.clone(), notification_service.clone());
+
     let backup_service = BackupService::new(
         db.backup_store.clone(),
         db.identity_store.clone(),
@@ -173,5 +205,7 @@ pub async fn create_service_context(
             company: None,
         })),
         backup_service: Arc::new(backup_service),
+        health_service: Arc::new(health_service),
+        job_control: JobControl::new(),
     })
 }